@@ -97,6 +97,7 @@ $OPENTALK_CONTROLLER_CMD --config example/controller.toml jobs default-parameter
 $OPENTALK_CONTROLLER_CMD --config example/controller.toml jobs default-parameters sync-storage-files | codify json > "$JOBS_DIR"/parameters-sync-storage-files.json.md
 $OPENTALK_CONTROLLER_CMD --config example/controller.toml jobs default-parameters room-cleanup | codify json > "$JOBS_DIR"/parameters-room-cleanup.json.md
 $OPENTALK_CONTROLLER_CMD --config example/controller.toml jobs default-parameters keycloak-account-sync | codify json > "$JOBS_DIR"/parameters-keycloak-account-sync.json.md
+$OPENTALK_CONTROLLER_CMD --config example/controller.toml jobs default-parameters room-trash-cleanup | codify json > "$JOBS_DIR"/parameters-room-trash-cleanup.json.md
 
 $OPENTALK_CONTROLLER_CMD --config example/controller.toml modules list | codify text > "$CLI_DIR"/"$CMDNAME"-modules-list.md
 
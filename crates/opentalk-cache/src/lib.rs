@@ -195,6 +195,30 @@ where
 
         Ok(())
     }
+
+    /// Remove an entry from the cache
+    pub async fn remove(&self, key: &K) -> Result<(), CacheError> {
+        if let Some(RedisConfig {
+            redis,
+            prefix,
+            hash_key,
+            ..
+        }) = &self.redis
+        {
+            redis
+                .clone()
+                .del::<_, ()>(RedisCacheKey {
+                    prefix,
+                    key,
+                    hash_key: *hash_key,
+                })
+                .await?;
+        }
+
+        self.local.invalidate(key).await;
+
+        Ok(())
+    }
 }
 
 /// [`ToRedisArgs`] implementation for the cache-key
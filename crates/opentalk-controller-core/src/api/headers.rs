@@ -45,3 +45,24 @@ pub(crate) struct SignalingProtocolHeaders {
     #[param(inline, required = true)]
     pub upgrade: WebsocketUpgrade,
 }
+
+/// This is a dummy type to define the structure of the optional headers
+/// for downloading an asset.
+#[derive(utoipa::IntoParams)]
+#[into_params(
+    parameter_in = Header,
+)]
+#[allow(dead_code)]
+pub(crate) struct AssetDownloadHeaders {
+    /// Entity tags of cached versions of the asset, the asset is only returned if none of them
+    /// matches the current version
+    #[param(
+        rename = "If-None-Match",
+        example = "\"d41d8cd98f00b204e9800998ecf8427e\""
+    )]
+    pub if_none_match: Option<String>,
+
+    /// A single byte range of the asset to return, multiple ranges are not supported
+    #[param(rename = "Range", example = "bytes=0-1048575")]
+    pub range: Option<String>,
+}
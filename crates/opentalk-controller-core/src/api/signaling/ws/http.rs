@@ -5,24 +5,34 @@
 use std::{
     collections::{BTreeMap, BTreeSet},
     marker::PhantomData,
+    net::{IpAddr, SocketAddr},
     sync::Arc,
     time::Instant,
 };
 
-use actix_web::{HttpMessage, HttpRequest, HttpResponse, get, http::header, web, web::Data};
+use actix_web::{
+    HttpMessage, HttpRequest, HttpResponse, get,
+    http::{Version, header},
+    web,
+    web::Data,
+};
 use actix_web_actors::ws;
 use kustos::Authz;
-use opentalk_controller_service::signaling::{
-    resumption::{ResumptionData, ResumptionTokenKeepAlive},
-    storage::{SignalingStorage, SignalingStorageProvider as _},
-    ticket::TicketData,
+use opentalk_controller_service::{
+    services::WebhookService,
+    signaling::{
+        resumption::{ResumptionData, ResumptionTokenKeepAlive},
+        storage::{SignalingStorage, SignalingStorageProvider as _},
+        ticket::TicketData,
+    },
 };
-use opentalk_controller_settings::SettingsProvider;
-use opentalk_controller_utils::CaptureApiError;
+use opentalk_controller_settings::{FeatureOverrides, SettingsProvider, Tariffs};
+use opentalk_controller_utils::{CaptureApiError, tariff};
 use opentalk_database::Db;
 use opentalk_db_storage::{rooms::Room, users::User};
 use opentalk_signaling_core::{
-    ExchangeHandle, ObjectStorage, Participant, SignalingMetrics, SignalingModule, VolatileStorage,
+    ExchangeHandle, ModuleStateSummarizer, ObjectStorage, Participant, SignalingMetrics,
+    SignalingModule, VolatileStorage, control::storage::ConnectionOwner,
 };
 use opentalk_types_api_v1::error::ApiError;
 use opentalk_types_common::{
@@ -67,6 +77,20 @@ impl SignalingModules {
             .map(|m| (m.module_id(), m.provided_features()))
             .collect()
     }
+
+    pub fn get_module_state_summarizers(&self) -> BTreeMap<ModuleId, ModuleStateSummarizer> {
+        self.0
+            .iter()
+            .map(|m| (m.module_id(), m.state_summarizer()))
+            .collect()
+    }
+
+    /// Apply the hot-reloadable settings to the parameters of all registered modules
+    pub fn reload_params(&self, settings: &Settings) {
+        for module in &self.0 {
+            module.reload_params(settings);
+        }
+    }
 }
 
 pub struct SignalingProtocols(&'static [&'static str]);
@@ -120,6 +144,7 @@ pub(crate) async fn ws_service(
     authz: Data<Authz>,
     volatile: Data<VolatileStorage>,
     exchange_handle: Data<ExchangeHandle>,
+    webhook: Data<WebhookService>,
     metrics: Data<SignalingMetrics>,
     protocols: Data<SignalingProtocols>,
     modules: Data<SignalingModules>,
@@ -134,6 +159,7 @@ pub(crate) async fn ws_service(
         authz.into_inner(),
         (**volatile).clone(),
         (**exchange_handle).clone(),
+        (**webhook).clone(),
         metrics.into_inner(),
         &protocols,
         &modules,
@@ -152,6 +178,7 @@ async fn ws_service_inner(
     authz: Arc<Authz>,
     mut volatile: VolatileStorage,
     exchange_handle: ExchangeHandle,
+    webhook: WebhookService,
     metrics: Arc<SignalingMetrics>,
     protocols: &SignalingProtocols,
     modules: &SignalingModules,
@@ -166,6 +193,11 @@ async fn ws_service_inner(
         return Ok(HttpResponse::InternalServerError().finish());
     };
 
+    // WebSockets require an HTTP/1.1 upgrade, reject other versions before the ticket is consumed
+    if request.version() != Version::HTTP_11 {
+        return Ok(HttpResponse::HttpVersionNotSupported().finish());
+    }
+
     // Read ticket and protocol from protocol header
     let (ticket, protocol) = read_request_header(&request, protocols.0)?;
 
@@ -188,10 +220,13 @@ async fn ws_service_inner(
         breakout_room: ticket_data.breakout_room,
     };
 
+    let settings = settings_provider.get();
     let room_tariff = get_tariff_for_room(
         &db,
         &room,
-        settings_provider.get().defaults.disabled_features.clone(),
+        &settings.tariffs,
+        &settings.feature_overrides,
+        settings.defaults.disabled_features.clone(),
         modules.get_module_features(),
     )
     .await?;
@@ -200,13 +235,6 @@ async fn ws_service_inner(
     let resumption_keep_alive =
         ResumptionTokenKeepAlive::new(ticket_data.resumption, resumption_data);
 
-    // Finish websocket handshake
-    let (sender, recv) = mpsc::unbounded_channel();
-    let (addr, response) =
-        ws::WsResponseBuilder::new(WebSocketActor::new(sender), &request, stream)
-            .protocols(protocols.0)
-            .start_with_addr()?;
-
     let mut builder = match Runner::builder(
         request_id.into(),
         ticket_data.participant_id,
@@ -222,6 +250,7 @@ async fn ws_service_inner(
         authz,
         volatile,
         exchange_handle,
+        webhook,
         resumption_keep_alive,
     )
     .await
@@ -234,6 +263,42 @@ async fn ws_service_inner(
         }
     };
 
+    // Enforce the connection limit before the websocket is established
+    if let Some(owner) = connection_owner(&request, &builder.participant) {
+        match builder.acquire_connection(owner, &settings).await {
+            Ok(true) => {}
+            Ok(false) => {
+                log::debug!("Rejecting websocket request, connection limit of {owner} reached");
+
+                builder.abort().await;
+                return Err(ApiError::forbidden()
+                    .with_code("connection_limit_reached")
+                    .with_message("The maximum number of concurrent connections has been reached")
+                    .into());
+            }
+            Err(e) => {
+                log::error!("Failed to acquire connection, {}", Report::from_error(e));
+
+                builder.abort().await;
+                return Ok(HttpResponse::InternalServerError().finish());
+            }
+        }
+    }
+
+    // Finish websocket handshake
+    let (sender, recv) = mpsc::unbounded_channel();
+    let (addr, response) =
+        match ws::WsResponseBuilder::new(WebSocketActor::new(sender), &request, stream)
+            .protocols(protocols.0)
+            .start_with_addr()
+        {
+            Ok(started) => started,
+            Err(e) => {
+                builder.abort().await;
+                return Err(e);
+            }
+        };
+
     let startup_start_time = Instant::now();
 
     // add all modules
@@ -271,6 +336,30 @@ async fn ws_service_inner(
     Ok(response)
 }
 
+/// The owner under which a connection is counted for the connection limit
+///
+/// Guests are identified by the address of the client, other participants without a user are
+/// not limited.
+fn connection_owner(
+    request: &HttpRequest,
+    participant: &Participant<User>,
+) -> Option<ConnectionOwner> {
+    match participant {
+        Participant::User(user) => Some(ConnectionOwner::User(user.id)),
+        Participant::Guest => {
+            let connection_info = request.connection_info();
+            let address = connection_info.realip_remote_addr()?;
+
+            address
+                .parse::<IpAddr>()
+                .or_else(|_| address.parse::<SocketAddr>().map(|address| address.ip()))
+                .ok()
+                .map(ConnectionOwner::Address)
+        }
+        Participant::Sip | Participant::Recorder => None,
+    }
+}
+
 fn read_request_header(
     request: &HttpRequest,
     allowed_protocols: &'static [&'static str],
@@ -383,12 +472,17 @@ async fn get_user_and_room_from_ticket_data(
 async fn get_tariff_for_room(
     db: &Db,
     room: &Room,
+    tariffs: &Tariffs,
+    feature_overrides: &FeatureOverrides,
     disabled_features: BTreeSet<ModuleFeatureId>,
     module_features: BTreeMap<ModuleId, BTreeSet<FeatureId>>,
 ) -> Result<TariffResource, CaptureApiError> {
     let mut conn = db.get_conn().await?;
 
-    let tariff = room.get_tariff(&mut conn).await?;
+    let tariff = tariff::get_room_tariff(tariffs, &mut conn, room).await?;
+    let tariff =
+        tariff::apply_feature_overrides(feature_overrides, &mut conn, room.tenant_id, tariff)
+            .await?;
 
     Ok(tariff.to_tariff_resource(disabled_features, module_features))
 }
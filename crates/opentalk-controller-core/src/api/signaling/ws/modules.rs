@@ -11,9 +11,15 @@ use std::{
 
 use actix_http::ws::{CloseCode, Message};
 use futures::stream::SelectAll;
-use opentalk_signaling_core::{AnyStream, Event, InitContext, SignalingMetrics, VolatileStorage};
+use opentalk_controller_settings::Settings;
+use opentalk_signaling_core::{
+    AnyStream, Event, InitContext, MalformedMessage, MalformedMessageEvent, ModuleStateSummarizer,
+    SignalingMetrics, VolatileStorage, parse_ws_message,
+};
 use opentalk_types_common::{features::FeatureId, modules::ModuleId, time::Timestamp};
-use opentalk_types_signaling::{LeaveReason, ModuleData, Participant, ParticipantId, Role};
+use opentalk_types_signaling::{
+    LeaveReason, ModuleData, NamespacedEvent, Participant, ParticipantId, Role,
+};
 use opentalk_types_signaling_control::state::ControlState;
 use serde_json::Value;
 use snafu::{Report, ResultExt, Snafu};
@@ -44,14 +50,19 @@ impl Modules {
         &mut self.module_features
     }
 
-    pub async fn add_module<M>(&mut self, module: M)
+    pub async fn add_module<M>(&mut self, module: M, metrics: Arc<SignalingMetrics>)
     where
         M: SignalingModule,
     {
         log::debug!("Registering module {}", M::NAMESPACE);
 
-        self.modules
-            .insert(M::NAMESPACE, Box::new(ModuleCallerImpl { module }));
+        self.modules.insert(
+            M::NAMESPACE,
+            Box::new(ModuleCallerImpl {
+                module,
+                _session: ModuleSession::new(M::NAMESPACE, metrics),
+            }),
+        );
         self.module_features
             .insert(M::NAMESPACE, M::get_provided_features());
     }
@@ -166,21 +177,52 @@ trait ModuleCaller {
 
 struct ModuleCallerImpl<M> {
     pub module: M,
+    _session: ModuleSession,
+}
+
+/// Tracks a module in the active module sessions metric for as long as it exists
+///
+/// The metric is decremented on drop, so modules which are dropped without being destroyed,
+/// e.g. when the runner exits on an error, are still accounted for.
+struct ModuleSession {
+    namespace: ModuleId,
+    metrics: Arc<SignalingMetrics>,
+}
+
+impl ModuleSession {
+    fn new(namespace: ModuleId, metrics: Arc<SignalingMetrics>) -> Self {
+        metrics.increment_active_module_sessions(&namespace);
+
+        Self { namespace, metrics }
+    }
+}
+
+impl Drop for ModuleSession {
+    fn drop(&mut self) {
+        self.metrics
+            .decrement_active_module_sessions(&self.namespace);
+    }
 }
 
 impl<M> ModuleCallerImpl<M>
 where
     M: SignalingModule,
 {
+    /// Handle a targeted event
+    ///
+    /// Returns the parse error if the event is a websocket message which does not match the
+    /// module's incoming message type, so it can be reported back to the client.
     async fn handle_dyn_targeted_event(
         &mut self,
         ctx: ModuleContext<'_, M>,
         dyn_event: DynTargetedEvent,
-    ) -> Result<()> {
+    ) -> Result<Option<MalformedMessage>> {
         match dyn_event {
             DynTargetedEvent::WsMessage(msg) => {
-                let msg =
-                    serde_json::from_value(msg).whatever_context("Failed to parse WS message")?;
+                let msg = match parse_ws_message(msg) {
+                    Ok(msg) => msg,
+                    Err(malformed) => return Ok(Some(malformed)),
+                };
                 self.module
                     .on_event(ctx, Event::WsMessage(msg))
                     .await
@@ -202,7 +244,7 @@ where
             }
         }
 
-        Ok(())
+        Ok(None)
     }
 
     async fn handle_dyn_broadcast_event(
@@ -347,7 +389,30 @@ where
 
         dyn_ctx.ws_messages.append(&mut ws_messages_serialized);
 
-        result
+        let malformed = result?;
+
+        if let Some(malformed) = malformed {
+            log::debug!(
+                "Received malformed websocket message for module {}, field: {:?}, reason: {:?}",
+                M::NAMESPACE,
+                malformed.field,
+                malformed.reason
+            );
+
+            let event = NamespacedEvent {
+                module: M::NAMESPACE,
+                timestamp: dyn_ctx.timestamp,
+                payload: MalformedMessageEvent::from(malformed),
+            };
+
+            dyn_ctx.ws_messages.push(Message::Text(
+                serde_json::to_string(&event)
+                    .expect("Failed to convert namespaced to json")
+                    .into(),
+            ));
+        }
+
+        Ok(())
     }
 
     #[tracing::instrument(skip(self, dyn_ctx, dyn_event), fields(module = %M::NAMESPACE))]
@@ -404,6 +469,10 @@ pub trait ModuleBuilder: Send + Sync {
     fn module_id(&self) -> ModuleId;
 
     fn provided_features(&self) -> BTreeSet<FeatureId>;
+
+    fn state_summarizer(&self) -> ModuleStateSummarizer;
+
+    fn reload_params(&self, settings: &Settings);
 }
 
 pub struct ModuleBuilderImpl<M>
@@ -440,7 +509,10 @@ where
             .await
             .whatever_context("Failed to init module")?
         {
-            builder.modules.add_module(module).await;
+            builder
+                .modules
+                .add_module(module, builder.metrics.clone())
+                .await;
         }
 
         Ok(())
@@ -460,6 +532,14 @@ where
     fn provided_features(&self) -> BTreeSet<FeatureId> {
         M::get_provided_features()
     }
+
+    fn state_summarizer(&self) -> ModuleStateSummarizer {
+        M::summarize_state
+    }
+
+    fn reload_params(&self, settings: &Settings) {
+        M::reload_params(&self.params, settings)
+    }
 }
 
 impl Clone for Box<dyn ModuleBuilder> {
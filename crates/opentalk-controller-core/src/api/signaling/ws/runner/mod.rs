@@ -20,6 +20,7 @@ use kustos::Authz;
 use log::log_enabled;
 use opentalk_controller_service::{
     email_to_libravatar_url,
+    services::WebhookService,
     signaling::{
         resumption::ResumptionTokenKeepAlive,
         storage::{SignalingStorageError, SignalingStorageProvider},
@@ -30,19 +31,22 @@ use opentalk_controller_service::{
         },
     },
 };
-use opentalk_controller_settings::SettingsProvider;
+use opentalk_controller_settings::{Settings, SettingsProvider};
+use opentalk_controller_utils::tariff;
 use opentalk_database::{Db, DbConnection};
 use opentalk_db_storage::{
-    events::EventInvite, rooms::Room, tariffs::Tariff, users::User, utils::build_event_info,
+    events::EventInvite, rooms::Room, tariffs::Tariff, tenants::Tenant, users::User,
+    utils::build_event_info,
 };
 use opentalk_signaling_core::{
-    AnyStream, ExchangeHandle, LockError, ObjectStorage, Participant, RoomLockingProvider as _,
-    RunnerId, SignalingMetrics, SignalingModule, SignalingModuleError, SignalingRoomId,
-    SubscriberHandle, VolatileStorage,
+    AnyStream, ExchangeDelivery, ExchangeHandle, LockError, ObjectStorage, Participant,
+    RoomLockingProvider as _, RunnerId, SignalingMetrics, SignalingModule, SignalingModuleError,
+    SignalingRoomId, SubscriberHandle, VolatileStorage,
     control::{
         self, ControlStateExt as _, ControlStorageProvider, MODULE_ID, exchange,
         storage::{
-            AVATAR_URL, AttributeActions, BREAKOUT_ROOM, ControlStorageParticipantAttributes,
+            AVATAR_URL, AttributeActions, BREAKOUT_ROOM, CONNECTION_REFRESH_INTERVAL,
+            ConnectionOwner, ControlStorageConnections as _, ControlStorageParticipantAttributes,
             DISPLAY_NAME, GlobalRoomAttributeId, HAND_IS_UP, HAND_UPDATED_AT, IS_PRESENT,
             IS_ROOM_OWNER, JOINED_AT, KIND, LEFT_AT, LocalRoomAttributeId, ROLE, USER_ID,
         },
@@ -124,6 +128,8 @@ pub enum RunnerError {
 
     InvalidDisplayName,
 
+    DisplayNameRejected,
+
     #[snafu(display("InvalidState: {message}"))]
     InvalidState {
         message: String,
@@ -166,13 +172,53 @@ pub struct Builder {
     pub(super) authz: Arc<Authz>,
     pub(super) volatile: VolatileStorage,
     pub(super) exchange_handle: ExchangeHandle,
+    webhook: WebhookService,
     resumption_keep_alive: ResumptionTokenKeepAlive,
+    connection_owner: Option<ConnectionOwner>,
 }
 
 impl Builder {
+    /// Register the connection in the connection limit of its owner
+    ///
+    /// Returns `false` if the owner already reached the limit configured for the tenant of the
+    /// room. Moderators are exempt from the limit.
+    pub async fn acquire_connection(
+        &mut self,
+        owner: ConnectionOwner,
+        settings: &Settings,
+    ) -> Result<bool> {
+        if self.role == Role::Moderator {
+            return Ok(true);
+        }
+
+        let tenant = Tenant::get(&mut self.db.get_conn().await?, self.room.tenant_id).await?;
+
+        let Some(limit) = settings
+            .connection_limit
+            .limit_for(&tenant.oidc_tenant_id.to_string())
+        else {
+            return Ok(true);
+        };
+
+        let acquired = self
+            .volatile
+            .control_storage()
+            .try_acquire_connection(owner, self.runner_id, limit)
+            .await
+            .whatever_context::<_, RunnerError>("Failed to acquire connection")?;
+
+        if acquired {
+            self.connection_owner = Some(owner);
+        }
+
+        Ok(acquired)
+    }
+
     /// Abort the building process and destroy all already built modules
     #[tracing::instrument(skip(self))]
     pub async fn abort(mut self) {
+        release_connection(&mut self.volatile, self.connection_owner, self.runner_id).await;
+
         let ctx = DestroyContext {
             volatile: &mut self.volatile,
             // We haven't joined yet
@@ -185,6 +231,28 @@ impl Builder {
     /// Build to runner from the data inside the builder and provided websocket
     #[tracing::instrument(err, skip_all)]
     pub async fn build(
+        self,
+        to_ws_actor: Addr<WebSocketActor>,
+        from_ws_actor: mpsc::UnboundedReceiver<RunnerMessage>,
+        shutdown_sig: broadcast::Receiver<()>,
+        settings_provider: SettingsProvider,
+    ) -> Result<Runner> {
+        let runner_id = self.runner_id;
+        let connection_owner = self.connection_owner;
+        let mut volatile = self.volatile.clone();
+
+        let result = self
+            .build_runner(to_ws_actor, from_ws_actor, shutdown_sig, settings_provider)
+            .await;
+
+        if result.is_err() {
+            release_connection(&mut volatile, connection_owner, runner_id).await;
+        }
+
+        result
+    }
+
+    async fn build_runner(
         mut self,
         to_ws_actor: Addr<WebSocketActor>,
         from_ws_actor: mpsc::UnboundedReceiver<RunnerMessage>,
@@ -267,12 +335,14 @@ impl Builder {
             volatile: self.volatile,
             exchange_handle: self.exchange_handle,
             subscriber_handle,
+            webhook: self.webhook,
             resumption_keep_alive: self.resumption_keep_alive,
             shutdown_sig,
             exit: false,
             leave_reason: LeaveReason::Quit,
             settings_provider,
             time_limit_future: Box::pin(future::pending()),
+            connection_owner: self.connection_owner,
         })
     }
 }
@@ -329,6 +399,9 @@ pub struct Runner {
     /// Exchange Subscriber - channel we receive all messages from
     subscriber_handle: SubscriberHandle,
 
+    /// Webhook handle - used to publish room lifecycle events
+    webhook: WebhookService,
+
     /// Util to keep the resumption token alive
     resumption_keep_alive: ResumptionTokenKeepAlive,
 
@@ -345,6 +418,9 @@ pub struct Runner {
     settings_provider: SettingsProvider,
 
     time_limit_future: Pin<Box<dyn Future<Output = ()>>>,
+
+    /// The owner under which the connection is counted, `None` if the connection is not limited
+    connection_owner: Option<ConnectionOwner>,
 }
 
 /// Current state of the runner
@@ -388,6 +464,28 @@ async fn get_participant_role(
     }
 }
 
+/// Remove the connection from the connection limit of its owner, if it was registered
+async fn release_connection(
+    volatile: &mut VolatileStorage,
+    owner: Option<ConnectionOwner>,
+    runner_id: RunnerId,
+) {
+    let Some(owner) = owner else {
+        return;
+    };
+
+    if let Err(e) = volatile
+        .control_storage()
+        .release_connection(owner, runner_id)
+        .await
+    {
+        log::error!(
+            "Failed to release connection of {owner}, {}",
+            Report::from_error(e)
+        );
+    }
+}
+
 async fn get_adhoc_role(
     volatile: &mut VolatileStorage,
     room_id: RoomId,
@@ -417,6 +515,7 @@ impl Runner {
         authz: Arc<Authz>,
         mut volatile: VolatileStorage,
         exchange_handle: ExchangeHandle,
+        webhook: WebhookService,
         resumption_keep_alive: ResumptionTokenKeepAlive,
     ) -> Result<Builder> {
         let role = match get_adhoc_role(&mut volatile, room.id, id).await? {
@@ -443,7 +542,9 @@ impl Runner {
             authz,
             volatile,
             exchange_handle,
+            webhook,
             resumption_keep_alive,
+            connection_owner: None,
         })
     }
 
@@ -454,6 +555,9 @@ impl Runner {
         let mut encountered_error = false;
         let mut cleanup_scope = CleanupScope::None;
 
+        // Release the connection first, so it is not counted anymore even if the cleanup fails
+        release_connection(&mut self.volatile, self.connection_owner, self.runner_id).await;
+
         if let RunnerState::Joined | RunnerState::Waiting { .. } = &self.state {
             // The retry/wait_time values are set extra high
             // since a lot of operations are being done while holding the lock
@@ -815,6 +919,8 @@ impl Runner {
 
                 self.metrics
                     .record_room_destroyed_metrics(self.room_id.room_id());
+
+                self.webhook.room_ended(self.room_id.room_id());
             }
         }
     }
@@ -1010,6 +1116,8 @@ impl Runner {
         let mut skip_waiting_room_refresh_interval = interval(Duration::from_secs(
             opentalk_signaling_core::control::storage::SKIP_WAITING_ROOM_KEY_REFRESH_INTERVAL,
         ));
+        let mut connection_refresh_interval =
+            interval(Duration::from_secs(CONNECTION_REFRESH_INTERVAL));
 
         while matches!(self.ws.state, State::Open) {
             if self.exit && matches!(self.ws.state, State::Open) {
@@ -1059,6 +1167,15 @@ impl Runner {
                     )
                     .await;
                 }
+                _ = connection_refresh_interval.tick(), if self.connection_owner.is_some() => {
+                    if let Some(owner) = self.connection_owner {
+                        _ = self.volatile.control_storage().refresh_connection(
+                            owner,
+                            self.runner_id,
+                        )
+                        .await;
+                    }
+                }
                 _ = &mut self.time_limit_future => {
                     self.ws_send_control(Timestamp::now(), ControlEvent::TimeLimitQuotaElapsed).await;
                     self.ws.close(CloseCode::Normal).await;
@@ -1192,6 +1309,15 @@ impl Runner {
 
                         return Ok(());
                     }
+                    Err(RunnerError::DisplayNameRejected) => {
+                        self.ws_send_control_error(
+                            timestamp,
+                            control_event::Error::DisplayNameRequired,
+                        )
+                        .await;
+
+                        return Ok(());
+                    }
                     other => other,
                 }?;
 
@@ -1321,6 +1447,8 @@ impl Runner {
         let display_name = self.username_or(join_display_name).await;
         let avatar_url = self.avatar_url().await;
 
+        self.check_display_name_policy(&display_name).await?;
+
         if display_name.is_empty() || display_name.len() > 100 {
             return InvalidDisplayNameSnafu.fail();
         }
@@ -1702,11 +1830,22 @@ impl Runner {
                 module_features.insert(k.clone(), v.clone());
             });
 
+        let mut conn = self.db.get_conn().await?;
+
+        let tenant = Tenant::get(&mut conn, self.room.tenant_id).await?;
+
+        let tariff = match settings
+            .feature_overrides
+            .for_tenant(&tenant.oidc_tenant_id.to_string())
+        {
+            Some(overrides) => tariff::with_feature_overrides(tariff, overrides),
+            None => tariff,
+        };
+
         let tariff_resource = tariff
             .to_tariff_resource(settings.defaults.disabled_features.clone(), module_features)
             .into();
 
-        let mut conn = self.db.get_conn().await?;
         let event_info = match event.as_ref() {
             Some(event) => {
                 let call_in_tel = settings.call_in.as_ref().map(|call_in| call_in.tel.clone());
@@ -1772,6 +1911,8 @@ impl Runner {
             } else {
                 self.metrics
                     .record_room_creation_metrics(self.room_id.room_id());
+
+                self.webhook.room_started(self.room_id.room_id());
             }
 
             self.volatile
@@ -2300,7 +2441,19 @@ impl Runner {
     }
 
     fn exchange_publish(&mut self, routing_key: String, message: String) {
-        if let Err(e) = self.exchange_handle.publish(routing_key, message) {
+        self.exchange_publish_with_delivery(routing_key, message, ExchangeDelivery::BestEffort);
+    }
+
+    fn exchange_publish_with_delivery(
+        &mut self,
+        routing_key: String,
+        message: String,
+        delivery: ExchangeDelivery,
+    ) {
+        if let Err(e) = self
+            .exchange_handle
+            .publish_with_delivery(routing_key, message, delivery)
+        {
             log::warn!(
                 "Failed to publish message to exchange, {}",
                 Report::from_error(e)
@@ -2397,7 +2550,11 @@ impl Runner {
         }
 
         for publish in exchange_publish {
-            self.exchange_publish(publish.routing_key, publish.message);
+            self.exchange_publish_with_delivery(
+                publish.routing_key,
+                publish.message,
+                publish.delivery,
+            );
         }
 
         if invalidate_data {
@@ -2449,6 +2606,42 @@ impl Runner {
             .await;
     }
 
+    /// Reject display names which violate the display name policy of the room's tenant.
+    ///
+    /// Moderators and the room owner joining with their account name are always admitted, as are
+    /// recorders and call-in participants whose names are not chosen by a person.
+    async fn check_display_name_policy(
+        &self,
+        display_name: &DisplayName,
+    ) -> Result<(), RunnerError> {
+        let exempt = match &self.participant {
+            Participant::User(user) => {
+                (self.role == Role::Moderator || user.id == self.room.created_by)
+                    && *display_name == user.display_name
+            }
+            Participant::Guest => false,
+            Participant::Recorder | Participant::Sip => true,
+        };
+
+        let settings = self.settings_provider.get();
+        let policy = &settings.display_name_policy;
+
+        if exempt || (!policy.rules.enforce && policy.tenants.is_empty()) {
+            return Ok(());
+        }
+
+        let tenant = Tenant::get(&mut self.db.get_conn().await?, self.room.tenant_id).await?;
+        let oidc_tenant_id = tenant.oidc_tenant_id.to_string();
+        let rules = policy.rules_for(Some(&oidc_tenant_id));
+
+        ensure!(
+            !rules.enforce || rules.is_valid(display_name.as_str()),
+            DisplayNameRejectedSnafu
+        );
+
+        Ok(())
+    }
+
     async fn username_or(&self, join_display_name: Option<DisplayName>) -> DisplayName {
         let join_display_name = join_display_name.unwrap_or_default();
 
@@ -4,12 +4,20 @@
 
 use actix_http::StatusCode;
 use actix_web::{
-    HttpResponse, delete, get, post,
-    web::{Data, Path, Payload, Query},
+    HttpResponse, HttpResponseBuilder, delete, get,
+    http::header::{
+        AcceptRanges, ByteRangeSpec, CacheControl, CacheDirective, ContentRange, ContentRangeSpec,
+        ETag, EntityTag, IfNoneMatch, Range, RangeUnit,
+    },
+    post,
+    web::{Data, Header, Path, Payload, Query},
 };
 use futures::TryStreamExt;
 use opentalk_controller_service_facade::OpenTalkControllerService;
-use opentalk_signaling_core::{ObjectStorageError, assets::NewAssetFileName};
+use opentalk_signaling_core::{
+    ObjectStorageError,
+    assets::{AssetDownload, AssetRetentionTag, IfNoneMatchCondition, NewAssetFileName, RangeSpec},
+};
 use opentalk_types_api_v1::{
     error::ApiError,
     pagination::PagePaginationQuery,
@@ -20,7 +28,10 @@ use opentalk_types_api_v1::{
 use opentalk_types_common::{assets::AssetId, rooms::RoomId, time::Timestamp};
 
 use super::{ApiResponse, DefaultApiResult, response::NoContent};
-use crate::api::responses::{BinaryData, Forbidden, InternalServerError, NotFound, Unauthorized};
+use crate::api::{
+    headers::AssetDownloadHeaders,
+    responses::{BadRequest, BinaryData, Forbidden, InternalServerError, NotFound, Unauthorized},
+};
 
 /// Get the assets associated with a room.
 ///
@@ -80,17 +91,31 @@ pub async fn room_assets(
 /// Get a specific asset inside a room.
 ///
 /// This will return the plain asset contents, e.g. the binary file contents or
-/// whatever else is stored inside the asset storage.
+/// whatever else is stored inside the asset storage. The response contains an
+/// `ETag` header which can be sent in the `If-None-Match` header of subsequent
+/// requests to avoid downloading an unchanged asset again. A single byte range
+/// can be requested with the `Range` header, e.g. for seeking in recordings.
 #[utoipa::path(
     params(
         ("room_id" = RoomId, description = "The id of the room"),
         ("asset_id" = AssetId, description = "The id of the asset"),
+        AssetDownloadHeaders,
     ),
     responses(
         (
             status = StatusCode::OK,
             response = BinaryData,
         ),
+        (
+            status = StatusCode::NOT_MODIFIED,
+            description = "The asset matches one of the entity tags in the `If-None-Match` header",
+        ),
+        (
+            status = StatusCode::PARTIAL_CONTENT,
+            description = "The requested range of the asset, the `Content-Range` header contains the position",
+            content_type = "application/octet-stream",
+            body = String,
+        ),
         (
             status = StatusCode::UNAUTHORIZED,
             response = Unauthorized,
@@ -103,6 +128,10 @@ pub async fn room_assets(
             status = StatusCode::NOT_FOUND,
             response = NotFound,
         ),
+        (
+            status = StatusCode::RANGE_NOT_SATISFIABLE,
+            description = "The requested range is outside of the asset or multiple ranges were requested",
+        ),
         (
             status = StatusCode::INTERNAL_SERVER_ERROR,
             response = InternalServerError,
@@ -116,12 +145,78 @@ pub async fn room_assets(
 pub async fn room_asset(
     service: Data<OpenTalkControllerService>,
     path: Path<(RoomId, AssetId)>,
+    if_none_match: Option<Header<IfNoneMatch>>,
+    range: Option<Header<Range>>,
 ) -> Result<HttpResponse, ApiError> {
     let (room_id, asset_id) = path.into_inner();
 
-    let stream = service.get_room_asset(room_id, asset_id).await?;
+    let if_none_match = if_none_match.map(|header| match header.into_inner() {
+        IfNoneMatch::Any => IfNoneMatchCondition::Any,
+        IfNoneMatch::Items(tags) => {
+            IfNoneMatchCondition::Tags(tags.into_iter().map(|tag| tag.tag().to_string()).collect())
+        }
+    });
+
+    // Ranges with a unit other than bytes are ignored and the full asset is returned
+    let range = range.and_then(|header| match header.into_inner() {
+        Range::Bytes(ranges) => Some(ranges.into_iter().map(range_spec).collect()),
+        Range::Unregistered(..) => None,
+    });
+
+    match service
+        .get_room_asset(room_id, asset_id, if_none_match, range)
+        .await?
+    {
+        AssetDownload::NotModified { e_tag } => {
+            Ok(asset_response(StatusCode::NOT_MODIFIED, e_tag).finish())
+        }
+        AssetDownload::Content { e_tag, data } => {
+            Ok(asset_response(StatusCode::OK, e_tag).streaming(data))
+        }
+        AssetDownload::PartialContent {
+            e_tag,
+            range,
+            size,
+            data,
+        } => Ok(asset_response(StatusCode::PARTIAL_CONTENT, e_tag)
+            .insert_header(ContentRange(ContentRangeSpec::Bytes {
+                range: Some((range.start, range.end)),
+                instance_length: Some(size),
+            }))
+            .streaming(data)),
+        AssetDownload::RangeNotSatisfiable { size } => {
+            Ok(HttpResponse::build(StatusCode::RANGE_NOT_SATISFIABLE)
+                .insert_header(ContentRange(ContentRangeSpec::Bytes {
+                    range: None,
+                    instance_length: Some(size),
+                }))
+                .finish())
+        }
+    }
+}
+
+fn range_spec(spec: ByteRangeSpec) -> RangeSpec {
+    match spec {
+        ByteRangeSpec::FromTo(start, end) => RangeSpec::FromTo(start, end),
+        ByteRangeSpec::From(start) => RangeSpec::From(start),
+        ByteRangeSpec::Last(length) => RangeSpec::Last(length),
+    }
+}
 
-    Ok(HttpResponse::build(StatusCode::OK).streaming(stream))
+/// Start an asset download response with the caching and range headers
+///
+/// Assets can only be accessed with authorization, so they must not be stored by shared
+/// caches, and clients must revalidate their cached copy using the entity tag.
+fn asset_response(status: StatusCode, e_tag: String) -> HttpResponseBuilder {
+    let mut response = HttpResponse::build(status);
+    response
+        .insert_header(AcceptRanges(vec![RangeUnit::Bytes]))
+        .insert_header(ETag(EntityTag::new_strong(e_tag)))
+        .insert_header(CacheControl(vec![
+            CacheDirective::Private,
+            CacheDirective::NoCache,
+        ]));
+    response
 }
 
 /// Create an asset for a room from an uploaded file
@@ -144,6 +239,10 @@ pub async fn room_asset(
             description = "The asset has been created successfully",
             body = PostAssetResponseBody,
         ),
+        (
+            status = StatusCode::BAD_REQUEST,
+            response = BadRequest,
+        ),
         (
             status = StatusCode::UNAUTHORIZED,
             response = Unauthorized,
@@ -175,13 +274,22 @@ pub async fn create(
     let room_id = path.into_inner();
     let query = query.into_inner();
 
-    let filename = NewAssetFileName::new_with_event_title(
+    let mut filename = NewAssetFileName::new_with_event_title(
         query.event_title,
         query.kind,
         Timestamp::now(),
         query.file_extension,
     );
 
+    if let Some(retention_tag) = query.retention_tag {
+        let retention_tag = retention_tag.parse::<AssetRetentionTag>().map_err(|e| {
+            ApiError::bad_request()
+                .with_code("invalid_retention_tag")
+                .with_message(e.to_string())
+        })?;
+        filename = filename.with_retention_tag(retention_tag);
+    }
+
     let data = data.map_err(|e| ObjectStorageError::Other {
         message: "Upload error".to_string(),
         source: Some(e.into()),
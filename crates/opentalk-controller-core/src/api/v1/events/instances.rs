@@ -138,7 +138,9 @@ pub async fn get_event_instance(
 /// Modifies an event instance
 ///
 /// Patch an instance of a recurring event. This creates or modifies an exception for the event
-/// at the point of time of the given instance_id.
+/// at the point of time of the given instance_id. The instance can be moved to another room of
+/// the event creator by setting the `room` field, setting it to `null` moves it back to the
+/// room of the event.
 /// Returns the patched event instance
 #[utoipa::path(
     params(
@@ -168,6 +170,10 @@ pub async fn get_event_instance(
             status = StatusCode::NOT_FOUND,
             response = NotFound,
         ),
+        (
+            status = StatusCode::UNPROCESSABLE_ENTITY,
+            description = "The room is not an active room of the event creator",
+        ),
         (
             status = StatusCode::INTERNAL_SERVER_ERROR,
             response = InternalServerError,
@@ -85,8 +85,9 @@ pub async fn add_invite(
 
 /// Get all invites for a room
 ///
-/// This returns all invites that are available for a room. If no
-/// pagination query is added, the default page size is used.
+/// This returns all invites that are available for a room, including how
+/// often and when they were last used to join the room. If no pagination
+/// query is added, the default page size is used.
 #[utoipa::path(
     params(
         ("room_id" = RoomId, description = "The id of the room"),
@@ -236,7 +237,8 @@ pub async fn update_invite(
 
 /// Delete an invite code
 ///
-/// The invite code will no longer be usable once it is deleted.
+/// The invite code will no longer be usable once it is deleted. Participants
+/// who already joined the room with the invite code stay in the meeting.
 #[utoipa::path(
     params(RoomIdAndInviteCode),
     responses(
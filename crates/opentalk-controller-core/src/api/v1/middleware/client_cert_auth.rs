@@ -0,0 +1,128 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use core::{
+    any::Any,
+    future::{Ready, ready},
+};
+use std::rc::Rc;
+
+use actix_tls::accept::rustls_0_23::TlsStream;
+use actix_web::{
+    ResponseError,
+    dev::{Extensions, Service, ServiceRequest, ServiceResponse, Transform, forward_ready},
+    error::Error,
+    rt::net::TcpStream,
+};
+use futures::future::{Either, LocalBoxFuture};
+use opentalk_controller_settings::settings_file::ClientAuthEnforcement;
+use opentalk_types_api_v1::error::ApiError;
+
+/// Connection data which marks that the client presented a certificate during the TLS handshake
+///
+/// The certificate has already been verified against the configured CA by rustls at that point.
+#[derive(Debug, Clone, Copy)]
+pub struct ClientCertificate;
+
+/// Connection callback for the HTTP server which inserts [`ClientCertificate`] into the
+/// connection data if the client authenticated itself with a certificate
+pub fn extract_client_certificate(connection: &dyn Any, data: &mut Extensions) {
+    let Some(stream) = connection.downcast_ref::<TlsStream<TcpStream>>() else {
+        return;
+    };
+
+    let (_, session) = stream.get_ref();
+
+    if session
+        .peer_certificates()
+        .is_some_and(|certificates| !certificates.is_empty())
+    {
+        data.insert(ClientCertificate);
+    }
+}
+
+/// Middleware factory for [`ClientCertAuthMiddleware`]
+///
+/// Passes all requests through when no enforcement is configured.
+pub struct ClientCertAuth {
+    enforcement: Option<ClientAuthEnforcement>,
+}
+
+impl ClientCertAuth {
+    pub fn new(enforcement: Option<ClientAuthEnforcement>) -> Self {
+        Self { enforcement }
+    }
+}
+
+impl<S> Transform<S, ServiceRequest> for ClientCertAuth
+where
+    S: Service<ServiceRequest, Response = ServiceResponse, Error = Error> + 'static,
+    S::Future: 'static,
+{
+    type Response = ServiceResponse;
+    type Error = Error;
+    type Transform = ClientCertAuthMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(ClientCertAuthMiddleware {
+            service: Rc::new(service),
+            enforcement: self.enforcement,
+        }))
+    }
+}
+
+/// Middleware which checks that the connection of the request was authenticated with a client
+/// certificate
+pub struct ClientCertAuthMiddleware<S> {
+    service: Rc<S>,
+
+    enforcement: Option<ClientAuthEnforcement>,
+}
+
+impl<S> Service<ServiceRequest> for ClientCertAuthMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse, Error = Error> + 'static,
+    S::Future: 'static,
+{
+    type Response = ServiceResponse;
+    type Error = Error;
+    type Future = Either<S::Future, LocalBoxFuture<'static, Result<Self::Response, Self::Error>>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let Some(enforcement) = self.enforcement else {
+            return Either::Left(self.service.call(req));
+        };
+
+        if req.conn_data::<ClientCertificate>().is_some() {
+            return Either::Left(self.service.call(req));
+        }
+
+        match enforcement {
+            ClientAuthEnforcement::Required => {
+                log::warn!(
+                    "Rejecting request to {} without a valid client certificate",
+                    req.path()
+                );
+
+                let error = ApiError::unauthorized()
+                    .with_message("A valid client certificate is required for this endpoint");
+
+                let response = req.into_response(error.error_response());
+                Either::Right(Box::pin(ready(Ok(response))))
+            }
+            ClientAuthEnforcement::Optional => {
+                log::warn!(
+                    "Request to {} without a valid client certificate",
+                    req.path()
+                );
+
+                Either::Left(self.service.call(req))
+            }
+        }
+    }
+}
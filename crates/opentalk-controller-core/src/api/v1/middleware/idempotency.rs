@@ -0,0 +1,319 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+//! Replays the results of `POST` requests that are sent with an `Idempotency-Key` header
+//!
+//! Clients retrying a request after a network failure send the same key again. Instead of
+//! creating a second resource, the response of the first request is returned.
+use core::future::ready;
+use std::{
+    future::{Future, Ready},
+    pin::Pin,
+    rc::Rc,
+    task::{Context, Poll},
+};
+
+use actix_web::{
+    HttpMessage, HttpResponse, ResponseError,
+    body::{BoxBody, to_bytes},
+    dev::{Payload, Service, ServiceRequest, ServiceResponse, Transform},
+    error::Error,
+    http::{
+        Method, StatusCode,
+        header::{CONTENT_TYPE, HeaderName, HeaderValue},
+    },
+    web::{Bytes, Data},
+};
+use bincode::{Decode, Encode};
+use opentalk_cache::Cache;
+use opentalk_controller_service_facade::RequestUser;
+use opentalk_types_api_v1::error::ApiError;
+use serde::{Deserialize, Serialize};
+use snafu::Report;
+
+use crate::caches::Caches;
+
+/// The header carrying the idempotency key of a request
+const IDEMPOTENCY_KEY: &str = "idempotency-key";
+
+/// The header which marks a response as the replayed result of a previous request
+const IDEMPOTENT_REPLAYED: HeaderName = HeaderName::from_static("idempotent-replayed");
+
+/// Maximum accepted length of an idempotency key
+const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 255;
+
+pub type IdempotencyKeyCache = Cache<String, IdempotencyRecord>;
+
+/// The state of a request that was sent with an idempotency key
+#[derive(Clone, Serialize, Deserialize, Encode, Decode)]
+pub struct IdempotencyRecord {
+    /// MD5 hash of the request body, used to detect the reuse of a key for a different request
+    request_hash: String,
+
+    /// The response of the request, `None` while the request is still being processed
+    response: Option<IdempotentResponse>,
+}
+
+#[derive(Clone, Serialize, Deserialize, Encode, Decode)]
+struct IdempotentResponse {
+    status: u16,
+    content_type: Option<String>,
+    body: Vec<u8>,
+}
+
+impl IdempotentResponse {
+    fn to_http_response(&self) -> HttpResponse {
+        let status = StatusCode::from_u16(self.status).unwrap_or(StatusCode::OK);
+
+        let mut response = HttpResponse::build(status);
+        response.insert_header((IDEMPOTENT_REPLAYED, HeaderValue::from_static("true")));
+        if let Some(content_type) = &self.content_type {
+            response.insert_header((CONTENT_TYPE, content_type.as_str()));
+        }
+
+        response.body(self.body.clone())
+    }
+}
+
+/// Middleware factory
+///
+/// Transforms into [`IdempotencyMiddleware`]
+pub struct Idempotency;
+
+impl<S> Transform<S, ServiceRequest> for Idempotency
+where
+    S: Service<ServiceRequest, Response = ServiceResponse, Error = Error> + 'static,
+    S::Future: 'static,
+{
+    type Response = ServiceResponse;
+    type Error = Error;
+    type Transform = IdempotencyMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(IdempotencyMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+/// Idempotency middleware
+///
+/// Applies to authenticated `POST` requests carrying an `Idempotency-Key` header. The first
+/// successful response for a key is stored in the [`IdempotencyKeyCache`] and returned for every
+/// following request of the same user with the same key and body. Reusing a key with a
+/// different body, or while the first request is still processed, is rejected with
+/// `409 Conflict`.
+pub struct IdempotencyMiddleware<S> {
+    service: Rc<S>,
+}
+
+type ResultFuture<O, E> = Pin<Box<dyn Future<Output = Result<O, E>>>>;
+
+impl<S> Service<ServiceRequest> for IdempotencyMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse, Error = Error> + 'static,
+    S::Future: 'static,
+{
+    type Response = ServiceResponse;
+    type Error = Error;
+    type Future = ResultFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&self, ctx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(ctx)
+    }
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+
+        if req.method() != Method::POST {
+            return Box::pin(service.call(req));
+        }
+
+        let Some(idempotency_key) = req.headers().get(IDEMPOTENCY_KEY).cloned() else {
+            return Box::pin(service.call(req));
+        };
+
+        // Requests authenticated by an invite code are not tied to a user and never create
+        // rooms or events, they are passed through unchanged.
+        let Some(user_id) = req.extensions().get::<RequestUser>().map(|user| user.id) else {
+            return Box::pin(service.call(req));
+        };
+
+        let caches = req
+            .app_data::<Data<Caches>>()
+            .expect("Caches must be provided as AppData")
+            .clone();
+
+        Box::pin(async move {
+            let idempotency_key = match idempotency_key.to_str() {
+                Ok(key) if !key.is_empty() && key.len() <= MAX_IDEMPOTENCY_KEY_LENGTH => {
+                    key.to_owned()
+                }
+                _ => {
+                    let error = ApiError::bad_request()
+                        .with_code("invalid_idempotency_key")
+                        .with_message(format!(
+                            "The Idempotency-Key header must contain between 1 and \
+                             {MAX_IDEMPOTENCY_KEY_LENGTH} visible ASCII characters"
+                        ));
+                    return Ok(req.into_response(error.error_response()));
+                }
+            };
+
+            let mut req = req;
+            let body = req.extract::<Bytes>().await?;
+            let request_hash = format!("{:x}", md5::compute(&body));
+            req.set_payload(Payload::from(body));
+
+            let cache_key = format!("{user_id}:{}:{idempotency_key}", req.path());
+
+            match caches.idempotency_keys.get(&cache_key).await {
+                Ok(Some(record)) if record.request_hash != request_hash => {
+                    let error = ApiError::conflict()
+                        .with_code("idempotency_key_reused")
+                        .with_message(
+                            "The Idempotency-Key has already been used for a different request",
+                        );
+                    return Ok(req.into_response(error.error_response()));
+                }
+                Ok(Some(IdempotencyRecord {
+                    response: Some(response),
+                    ..
+                })) => {
+                    return Ok(req.into_response(response.to_http_response()));
+                }
+                Ok(Some(IdempotencyRecord { response: None, .. })) => {
+                    let error = ApiError::conflict()
+                        .with_code("idempotency_key_in_use")
+                        .with_message(
+                            "A request with the same Idempotency-Key is still being processed",
+                        );
+                    return Ok(req.into_response(error.error_response()));
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    log::warn!(
+                        "Failed to look up idempotency key, processing request without it, {}",
+                        Report::from_error(e)
+                    );
+                    return service.call(req).await;
+                }
+            }
+
+            if let Err(e) = caches
+                .idempotency_keys
+                .insert(
+                    cache_key.clone(),
+                    IdempotencyRecord {
+                        request_hash: request_hash.clone(),
+                        response: None,
+                    },
+                )
+                .await
+            {
+                log::warn!(
+                    "Failed to store idempotency key, processing request without it, {}",
+                    Report::from_error(e)
+                );
+                return service.call(req).await;
+            }
+
+            let res = match service.call(req).await {
+                Ok(res) if res.status().is_success() => res,
+                res => {
+                    // Failed requests may be retried with the same key
+                    if let Err(e) = caches.idempotency_keys.remove(&cache_key).await {
+                        log::warn!(
+                            "Failed to remove idempotency key, {}",
+                            Report::from_error(e)
+                        );
+                    }
+                    return res;
+                }
+            };
+
+            let (req, res) = res.into_parts();
+            let (res, body) = res.into_parts();
+            let body = match to_bytes(body).await {
+                Ok(body) => body,
+                Err(e) => {
+                    log::error!("Failed to read response body of idempotent request, {e}");
+                    if let Err(e) = caches.idempotency_keys.remove(&cache_key).await {
+                        log::warn!(
+                            "Failed to remove idempotency key, {}",
+                            Report::from_error(e)
+                        );
+                    }
+                    return Ok(ServiceResponse::new(
+                        req,
+                        HttpResponse::InternalServerError().finish(),
+                    ));
+                }
+            };
+
+            let response = IdempotentResponse {
+                status: res.status().as_u16(),
+                content_type: res
+                    .headers()
+                    .get(CONTENT_TYPE)
+                    .and_then(|value| value.to_str().ok())
+                    .map(ToOwned::to_owned),
+                body: body.to_vec(),
+            };
+
+            if let Err(e) = caches
+                .idempotency_keys
+                .insert(
+                    cache_key,
+                    IdempotencyRecord {
+                        request_hash,
+                        response: Some(response),
+                    },
+                )
+                .await
+            {
+                log::warn!(
+                    "Failed to store response for idempotency key, {}",
+                    Report::from_error(e)
+                );
+            }
+
+            Ok(ServiceResponse::new(req, res.set_body(BoxBody::new(body))))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::{body::to_bytes, http::header::CONTENT_TYPE};
+    use pretty_assertions::assert_eq;
+
+    use super::IdempotentResponse;
+
+    #[actix_rt::test]
+    async fn replayed_response() {
+        let response = IdempotentResponse {
+            status: 201,
+            content_type: Some("application/json".to_owned()),
+            body: br#"{"id":"00000000-0000-0000-0000-000000000001"}"#.to_vec(),
+        }
+        .to_http_response();
+
+        assert_eq!(response.status().as_u16(), 201);
+        assert_eq!(
+            response.headers().get(CONTENT_TYPE).unwrap(),
+            "application/json"
+        );
+        assert_eq!(
+            response.headers().get("idempotent-replayed").unwrap(),
+            "true"
+        );
+        assert_eq!(
+            to_bytes(response.into_body()).await.unwrap(),
+            br#"{"id":"00000000-0000-0000-0000-000000000001"}"#.as_slice()
+        );
+    }
+}
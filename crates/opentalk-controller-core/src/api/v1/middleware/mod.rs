@@ -4,7 +4,9 @@
 
 //! Actix middleware implementations
 
+pub mod client_cert_auth;
 pub mod headers;
+pub mod idempotency;
 mod locale;
 pub mod metrics;
 pub mod service_auth;
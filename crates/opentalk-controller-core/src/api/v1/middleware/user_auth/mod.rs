@@ -31,7 +31,7 @@ use opentalk_controller_service_facade::RequestUser;
 use opentalk_controller_settings::{
     Settings, SettingsProvider, TariffAssignment, TariffStatusMapping, TenantAssignment,
 };
-use opentalk_controller_utils::CaptureApiError;
+use opentalk_controller_utils::{CaptureApiError, tariff::get_default_tariff};
 use opentalk_database::{Db, OptionalExt};
 use opentalk_db_storage::{
     groups::{Group, get_or_create_groups_by_name},
@@ -360,28 +360,54 @@ async fn check_access_token_inner(
 
     let mut conn = db.get_conn().await?;
 
-    // Get tariff depending on the configured assignment
+    // Get the tenant_id depending on the configured assignment
+    let oidc_tenant_id = match &settings.tenants.assignment {
+        TenantAssignment::Static { static_tenant_id } => static_tenant_id.clone(),
+        TenantAssignment::ByExternalTenantId { .. } => info.tenant_id.clone().ok_or_else(|| {
+            log::error!("Invalid access token, missing tenant_id");
+            ApiError::unauthorized().with_www_authenticate(AuthenticationError::InvalidAccessToken)
+        })?,
+    };
+    // Get tariff depending on the configured assignment, falling back to the default tariff of
+    // the tenant
     let (tariff, tariff_status) = match &settings.tariffs.assignment {
-        TariffAssignment::Static { static_tariff_name } => (
-            Tariff::get_by_name(&mut conn, static_tariff_name).await?,
-            TariffStatus::Default,
-        ),
+        TariffAssignment::Static { static_tariff_name } => {
+            match Tariff::get_by_name(&mut conn, static_tariff_name)
+                .await
+                .optional()?
+            {
+                Some(tariff) => (tariff, TariffStatus::Default),
+                None => (
+                    get_default_tariff(&settings.tariffs, &mut conn, &oidc_tenant_id).await?,
+                    TariffStatus::Default,
+                ),
+            }
+        }
         TariffAssignment::ByExternalTariffId { status_mapping } => {
-            let external_tariff_id = info.tariff_id.clone().ok_or_else(|| {
-                ApiError::bad_request()
-                    .with_code("invalid_claims")
-                    .with_message("tariff_id missing in id_token claims")
-            })?;
-
-            let tariff =
-                Tariff::get_by_external_id(&mut conn, &ExternalTariffId::from(external_tariff_id))
-                    .await
-                    .optional()?
-                    .ok_or_else(|| {
-                        ApiError::internal()
-                            .with_code("invalid_tariff_id")
-                            .with_message("JWT contained unknown tariff_id")
-                    })?;
+            let external_tariff = match info.tariff_id.clone() {
+                Some(external_tariff_id) => Tariff::get_by_external_id(
+                    &mut conn,
+                    &ExternalTariffId::from(external_tariff_id),
+                )
+                .await
+                .optional()?,
+                None if settings
+                    .tariffs
+                    .default_tariff_name_for(&oidc_tenant_id)
+                    .is_none() =>
+                {
+                    return Err(ApiError::bad_request()
+                        .with_code("invalid_claims")
+                        .with_message("tariff_id missing in id_token claims")
+                        .into());
+                }
+                None => None,
+            };
+
+            let tariff = match external_tariff {
+                Some(tariff) => tariff,
+                None => get_default_tariff(&settings.tariffs, &mut conn, &oidc_tenant_id).await?,
+            };
 
             if let Some(mapping) = status_mapping.as_ref() {
                 let status_name = info.tariff_status.clone().ok_or_else(|| {
@@ -410,15 +436,8 @@ async fn check_access_token_inner(
         }
     };
 
-    // Get the tenant_id depending on the configured assignment
-    let tenant_id = match &settings.tenants.assignment {
-        TenantAssignment::Static { static_tenant_id } => static_tenant_id.clone(),
-        TenantAssignment::ByExternalTenantId { .. } => info.tenant_id.clone().ok_or_else(|| {
-            log::error!("Invalid access token, missing tenant_id");
-            ApiError::unauthorized().with_www_authenticate(AuthenticationError::InvalidAccessToken)
-        })?,
-    };
-    let tenant = get_or_create_tenant_by_oidc_id(&mut conn, &OidcTenantId::from(tenant_id)).await?;
+    let tenant =
+        get_or_create_tenant_by_oidc_id(&mut conn, &OidcTenantId::from(oidc_tenant_id)).await?;
 
     let groups: Vec<(TenantId, GroupName)> = info
         .groups
@@ -27,6 +27,17 @@ impl Responder for Created {
     }
 }
 
+/// Represents a 202 Accepted HTTP Response
+pub struct Accepted;
+
+impl Responder for Accepted {
+    type Body = BoxBody;
+
+    fn respond_to(self, _: &actix_web::HttpRequest) -> HttpResponse {
+        HttpResponse::Accepted().finish()
+    }
+}
+
 /// Represents a 204 No Content HTTP Response
 pub struct NoContent;
 
@@ -21,11 +21,16 @@ use opentalk_types_api_v1::{
         GetRoomsResponseBody, PostRoomsRequestBody, RoomResource,
         by_room_id::{
             DeleteRoomQuery, GetRoomEventResponseBody, PatchRoomsRequestBody,
-            PostRoomsStartInvitedRequestBody, PostRoomsStartRequestBody, RoomsStartResponseBody,
+            PostRoomsStartInvitedRequestBody, PostRoomsStartRequestBody,
+            PostRoomsTransferOwnershipRequestBody, RoomsStartResponseBody,
+            capabilities::GetRoomCapabilitiesResponseBody,
         },
     },
 };
-use opentalk_types_common::{events::EventInfo, rooms::RoomId, tariffs::TariffResource};
+use opentalk_types_common::{
+    events::EventInfo, module_resources::ModuleResourceId, modules::ModuleId, rooms::RoomId,
+    tariffs::TariffResource,
+};
 
 use super::response::NoContent;
 use crate::api::{
@@ -202,6 +207,11 @@ pub async fn patch(
 /// Deletes the room by the id if found. See the query parameters for affecting
 /// the behavior of this endpoint, such as succeding even if external resources
 /// cannot be successfully deleted.
+///
+/// If soft deletion is enabled in the controller configuration, the room is only
+/// marked as deleted and can be restored until it is permanently deleted by the
+/// `room-trash-cleanup` job. This endpoint never deletes a room permanently
+/// in that case, administrators run the job with the ids of the rooms instead.
 #[utoipa::path(
     params(
         ("room_id" = RoomId, description = "The id of the room"),
@@ -253,6 +263,114 @@ pub async fn delete(
     Ok(NoContent)
 }
 
+/// Restore a deleted room
+///
+/// Restores a room that was marked as deleted and has not been permanently
+/// deleted yet. Rooms are only marked as deleted when soft deletion is enabled
+/// in the controller configuration.
+#[utoipa::path(
+    params(
+        ("room_id" = RoomId, description = "The id of the room"),
+    ),
+    responses(
+        (
+            status = StatusCode::OK,
+            description = "Room was successfully restored",
+            body = RoomResource
+        ),
+        (
+            status = StatusCode::UNAUTHORIZED,
+            response = Unauthorized,
+        ),
+        (
+            status = StatusCode::FORBIDDEN,
+            response = Forbidden,
+        ),
+        (
+            status = StatusCode::NOT_FOUND,
+            response = NotFound,
+        ),
+        (
+            status = StatusCode::INTERNAL_SERVER_ERROR,
+            response = InternalServerError,
+        ),
+    ),
+    security(
+        ("BearerAuth" = []),
+    ),
+)]
+#[post("/rooms/{room_id}/restore")]
+pub async fn restore(
+    service: Data<OpenTalkControllerService>,
+    room_id: Path<RoomId>,
+) -> Result<Json<RoomResource>, ApiError> {
+    let room_resource = service.restore_room(room_id.into_inner()).await?;
+
+    Ok(Json(room_resource))
+}
+
+/// Transfer the ownership of a room
+///
+/// Makes another user of the same tenant the owner of the room and grants
+/// them the owner permissions, including access to the module resources of
+/// the room such as legal votes. The previous owner keeps their access unless
+/// `revoke_previous_owner` is set. Only the current owner can transfer the
+/// ownership.
+#[utoipa::path(
+    params(
+        ("room_id" = RoomId, description = "The id of the room"),
+    ),
+    request_body = PostRoomsTransferOwnershipRequestBody,
+    responses(
+        (
+            status = StatusCode::OK,
+            description = "Ownership was successfully transferred",
+            body = RoomResource
+        ),
+        (
+            status = StatusCode::UNAUTHORIZED,
+            response = Unauthorized,
+        ),
+        (
+            status = StatusCode::FORBIDDEN,
+            response = Forbidden,
+        ),
+        (
+            status = StatusCode::NOT_FOUND,
+            response = NotFound,
+        ),
+        (
+            status = StatusCode::UNPROCESSABLE_ENTITY,
+            description = "The new owner does not exist in the tenant of the room or already owns it",
+            body = ErrorBody,
+        ),
+        (
+            status = StatusCode::INTERNAL_SERVER_ERROR,
+            response = InternalServerError,
+        ),
+    ),
+    security(
+        ("BearerAuth" = []),
+    ),
+)]
+#[post("/rooms/{room_id}/transfer_ownership")]
+pub async fn transfer_ownership(
+    service: Data<OpenTalkControllerService>,
+    current_user: ReqData<RequestUser>,
+    room_id: Path<RoomId>,
+    body: Json<PostRoomsTransferOwnershipRequestBody>,
+) -> Result<Json<RoomResource>, ApiError> {
+    let room_resource = service
+        .transfer_room_ownership(
+            current_user.into_inner(),
+            room_id.into_inner(),
+            body.into_inner(),
+        )
+        .await?;
+
+    Ok(Json(room_resource))
+}
+
 /// Get a room
 ///
 /// Returns the room resource including additional information such as the creator profile.
@@ -295,6 +413,113 @@ pub async fn get(
     Ok(Json(service.get_room(&room_id).await?))
 }
 
+/// Get the state of a single signaling module in a room
+///
+/// Returns a read-only snapshot of the state a signaling module currently
+/// keeps for the room, e.g. the configuration, history, remaining participants
+/// and current speaker of an active automod session. Participant lists the
+/// moderator chose to hide are not included. Besides the owner of the room,
+/// the moderators invited to the room's event can access the state.
+#[utoipa::path(
+    params(
+        ("room_id" = RoomId, description = "The id of the room"),
+        ("module_id" = ModuleId, description = "The id of the module"),
+    ),
+    responses(
+        (
+            status = StatusCode::OK,
+            description = "The module state has been returned successfully",
+            body = Object,
+        ),
+        (
+            status = StatusCode::UNAUTHORIZED,
+            response = Unauthorized,
+        ),
+        (
+            status = StatusCode::FORBIDDEN,
+            response = Forbidden,
+        ),
+        (
+            status = StatusCode::NOT_FOUND,
+            description = "The room or module does not exist, or the module has no active state in the room",
+        ),
+        (
+            status = StatusCode::INTERNAL_SERVER_ERROR,
+            response = InternalServerError,
+        ),
+    ),
+    security(
+        ("BearerAuth" = []),
+    ),
+)]
+#[get("/rooms/{room_id}/module_states/{module_id}")]
+pub async fn get_room_module_state(
+    service: Data<OpenTalkControllerService>,
+    current_user: ReqData<RequestUser>,
+    path: Path<(RoomId, ModuleId)>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let (room_id, module_id) = path.into_inner();
+
+    let state = service
+        .get_room_module_state(current_user.into_inner(), room_id, module_id)
+        .await?;
+
+    Ok(Json(state))
+}
+
+/// Get the protocol of a legal vote in a room
+///
+/// Returns the stored protocol of the vote as JSON, the same data the PDF
+/// report is rendered from. For pseudonymous votes, the information
+/// identifying the participants is removed. Access is granted to the users
+/// who have access to the vote, i.e. its initiator and the owner of the room.
+#[utoipa::path(
+    params(
+        ("room_id" = RoomId, description = "The id of the room"),
+        ("legal_vote_id" = ModuleResourceId, description = "The id of the legal vote"),
+    ),
+    responses(
+        (
+            status = StatusCode::OK,
+            description = "The protocol has been returned successfully",
+            body = Object,
+        ),
+        (
+            status = StatusCode::UNAUTHORIZED,
+            response = Unauthorized,
+        ),
+        (
+            status = StatusCode::FORBIDDEN,
+            response = Forbidden,
+        ),
+        (
+            status = StatusCode::NOT_FOUND,
+            response = NotFound,
+        ),
+        (
+            status = StatusCode::INTERNAL_SERVER_ERROR,
+            response = InternalServerError,
+        ),
+    ),
+    security(
+        ("BearerAuth" = []),
+    ),
+)]
+#[get("/rooms/{room_id}/legal_votes/{legal_vote_id}/protocol")]
+pub async fn get_room_legal_vote_protocol(
+    service: Data<OpenTalkControllerService>,
+    current_user: ReqData<RequestUser>,
+    path: Path<(RoomId, ModuleResourceId)>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let (room_id, legal_vote_id) = path.into_inner();
+
+    let protocol = service
+        .get_room_legal_vote_protocol(current_user.into_inner(), room_id, legal_vote_id)
+        .await?;
+
+    Ok(Json(protocol))
+}
+
 /// Get a room's tariff
 ///
 /// This returns the tariff that applies to the room, typically the tariff of
@@ -339,6 +564,51 @@ pub async fn get_room_tariff(
     Ok(Json(service.get_room_tariff(&room_id).await?))
 }
 
+/// Get a room's capabilities
+///
+/// Combines the modules and features enabled by the room's tariff, the
+/// feature overrides of the tenant and the current limits, e.g. the maximum
+/// number of participants and the remaining storage of the room owner. This
+/// allows clients to enable or disable controls before the room is started.
+#[utoipa::path(
+    params(
+        ("room_id" = RoomId, description = "The id of the room"),
+    ),
+    responses(
+        (
+            status = StatusCode::OK,
+            description = "The room's capabilities were successfully retrieved",
+            body = GetRoomCapabilitiesResponseBody
+        ),
+        (
+            status = StatusCode::UNAUTHORIZED,
+            response = Unauthorized,
+        ),
+        (
+            status = StatusCode::FORBIDDEN,
+            response = Forbidden,
+        ),
+        (
+            status = StatusCode::NOT_FOUND,
+            response = NotFound,
+        ),
+        (
+            status = StatusCode::INTERNAL_SERVER_ERROR,
+            response = InternalServerError,
+        ),
+    ),
+    security(
+        ("BearerAuth" = []),
+    ),
+)]
+#[get("/rooms/{room_id}/capabilities")]
+pub async fn get_room_capabilities(
+    service: Data<OpenTalkControllerService>,
+    room_id: Path<RoomId>,
+) -> Result<Json<GetRoomCapabilitiesResponseBody>, ApiError> {
+    Ok(Json(service.get_room_capabilities(&room_id).await?))
+}
+
 /// Get a room's event
 ///
 /// This returns the event with which the room is associated. Please note
@@ -7,7 +7,7 @@ use actix_web::{
     HttpRequest, HttpResponse,
     dev::HttpServiceFactory,
     get, post, web,
-    web::{Data, Json, Query},
+    web::{Data, Json, Path, Query},
 };
 use actix_web_actors::ws;
 use bytes::Bytes;
@@ -24,12 +24,14 @@ use opentalk_types_api_v1::{
         recording::{GetRecordingUploadQuery, PostRecordingStartRequestBody},
     },
 };
+use opentalk_types_common::rooms::RoomId;
 use tokio::{sync::mpsc, task};
 
 use crate::api::{
     headers::{ConnectionUpgrade, WebsocketUpgrade},
     responses::{InternalServerError, Unauthorized},
     upload::{MAXIMUM_WEBSOCKET_BUFFER_SIZE, UploadWebSocketActor},
+    v1::response::Accepted,
 };
 
 // Note to devs:
@@ -83,6 +85,62 @@ pub async fn post_recording_start(
     Ok(Json(response))
 }
 
+/// Stops the recording of a room
+///
+/// Requests the recorders of the room and its active breakout rooms to stop all running recordings
+/// and streams. The recorders finalize and upload the recordings asynchronously, the resulting
+/// assets are attached to the room once they are available.
+#[utoipa::path(
+    context_path = "/rooms/{room_id}/recording",
+    params(
+        ("room_id" = RoomId, description = "The id of the room"),
+    ),
+    operation_id = "stop_recording",
+    responses(
+        (
+            status = StatusCode::ACCEPTED,
+            description = "The recorders have been requested to stop the recording",
+        ),
+        (
+            status = StatusCode::UNAUTHORIZED,
+            response = Unauthorized,
+        ),
+        (
+            status = StatusCode::NOT_FOUND,
+            description = "Recording has not been configured or the room does not exist",
+            body = ErrorBody,
+            example = json!(ApiError::not_found().body),
+        ),
+        (
+            status = StatusCode::CONFLICT,
+            description = "No recording is active in the room",
+            body = ErrorBody,
+            example = json!(
+                ApiError::conflict()
+                    .with_code("recording_not_active")
+                    .with_message("No recording is active in this room")
+                    .body
+            ),
+        ),
+        (
+            status = StatusCode::INTERNAL_SERVER_ERROR,
+            response = InternalServerError,
+        ),
+    ),
+    security(
+        ("BearerAuth" = []),
+    ),
+)]
+#[post("/stop")]
+pub async fn post_recording_stop(
+    service: Data<OpenTalkControllerService>,
+    room_id: Path<RoomId>,
+) -> Result<Accepted, ApiError> {
+    service.stop_recording(room_id.into_inner()).await?;
+
+    Ok(Accepted)
+}
+
 /// This is a dummy type to define the structure of the headers required for
 /// upgrading a request to a recording upload websocket connection.
 #[derive(utoipa::IntoParams)]
@@ -181,3 +239,11 @@ pub fn services() -> impl HttpServiceFactory {
         .service(post_recording_start)
         .service(get_recording_upload)
 }
+
+/// Recording endpoints that are located below the room resources, but authenticated
+/// like the other recording service endpoints
+pub fn room_services() -> impl HttpServiceFactory {
+    actix_web::web::scope("")
+        .wrap(super::RequiredRealmRole::new(REQUIRED_RECORDING_ROLE))
+        .service(post_recording_stop)
+}
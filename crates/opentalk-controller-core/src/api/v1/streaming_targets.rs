@@ -4,7 +4,7 @@
 
 //! Contains invite related REST endpoints.
 use actix_web::{
-    delete, get, patch, post,
+    delete, get, patch, post, put,
     web::{Data, Json, Path, Query, ReqData},
 };
 use opentalk_controller_service_facade::{OpenTalkControllerService, RequestUser};
@@ -16,7 +16,7 @@ use opentalk_types_api_v1::{
         GetRoomStreamingTargetResponseBody, GetRoomStreamingTargetsResponseBody,
         PatchRoomStreamingTargetRequestBody, PatchRoomStreamingTargetResponseBody,
         PostRoomStreamingTargetRequestBody, PostRoomStreamingTargetResponseBody,
-        RoomAndStreamingTargetId,
+        PutRoomStreamingTargetsOrderRequestBody, RoomAndStreamingTargetId,
     },
 };
 use opentalk_types_common::rooms::RoomId;
@@ -236,6 +236,106 @@ pub async fn patch_streaming_target(
     Ok(ApiResponse::new(response))
 }
 
+/// Changes the order of the streaming targets
+///
+/// The request must contain the ids of all streaming targets of the room in the new order.
+#[utoipa::path(
+    params(
+        ("room_id" = RoomId, description = "The id of the room"),
+    ),
+    request_body = PutRoomStreamingTargetsOrderRequestBody,
+    responses(
+        (
+            status = StatusCode::OK,
+            description = "The streaming targets in their new order",
+            body = GetRoomStreamingTargetsResponseBody,
+        ),
+        (
+            status = StatusCode::BAD_REQUEST,
+            description = r"The order does not contain every streaming target of the room
+                exactly once",
+        ),
+        (
+            status = StatusCode::UNAUTHORIZED,
+            response = Unauthorized,
+        ),
+        (
+            status = StatusCode::FORBIDDEN,
+            response = Forbidden,
+        ),
+        (
+            status = StatusCode::NOT_FOUND,
+            response = NotFound,
+        ),
+        (
+            status = StatusCode::INTERNAL_SERVER_ERROR,
+            response = InternalServerError,
+        ),
+    ),
+    security(
+        ("BearerAuth" = []),
+    ),
+)]
+#[put("/rooms/{room_id}/streaming_targets/order")]
+pub async fn put_streaming_targets_order(
+    service: Data<OpenTalkControllerService>,
+    current_user: ReqData<RequestUser>,
+    room_id: Path<RoomId>,
+    order: Json<PutRoomStreamingTargetsOrderRequestBody>,
+) -> DefaultApiResult<GetRoomStreamingTargetsResponseBody> {
+    let response = service
+        .put_streaming_targets_order(current_user.id, room_id.into_inner(), order.into_inner())
+        .await?;
+
+    Ok(ApiResponse::new(response))
+}
+
+/// Sets the primary streaming target
+///
+/// Makes the streaming target the primary of the room, which is used when only a single
+/// stream is started.
+#[utoipa::path(
+    params(RoomAndStreamingTargetId),
+    responses(
+        (
+            status = StatusCode::OK,
+            description = "The streaming target is now the primary of the room",
+            body = GetRoomStreamingTargetResponseBody,
+        ),
+        (
+            status = StatusCode::UNAUTHORIZED,
+            response = Unauthorized,
+        ),
+        (
+            status = StatusCode::FORBIDDEN,
+            response = Forbidden,
+        ),
+        (
+            status = StatusCode::NOT_FOUND,
+            response = NotFound,
+        ),
+        (
+            status = StatusCode::INTERNAL_SERVER_ERROR,
+            response = InternalServerError,
+        ),
+    ),
+    security(
+        ("BearerAuth" = []),
+    ),
+)]
+#[put("/rooms/{room_id}/streaming_targets/{streaming_target_id}/primary")]
+pub async fn put_primary_streaming_target(
+    service: Data<OpenTalkControllerService>,
+    current_user: ReqData<RequestUser>,
+    path_params: Path<RoomAndStreamingTargetId>,
+) -> DefaultApiResult<GetRoomStreamingTargetResponseBody> {
+    let response = service
+        .put_primary_streaming_target(current_user.id, path_params.into_inner())
+        .await?;
+
+    Ok(ApiResponse::new(response))
+}
+
 /// Deletes a streaming target
 ///
 /// The streaming target is deleted from the room
@@ -6,29 +6,40 @@ use core::time::Duration;
 
 use opentalk_signaling_core::RedisConnection;
 
-use crate::api::v1::middleware::user_auth::UserAccessTokenCache;
+use crate::api::v1::middleware::{
+    idempotency::IdempotencyKeyCache, user_auth::UserAccessTokenCache,
+};
 
 /// Holds all application level caches
 pub struct Caches {
     /// Cache the results of user access-token checks
     pub user_access_tokens: UserAccessTokenCache,
+
+    /// Stores the results of requests sent with an `Idempotency-Key` header
+    pub idempotency_keys: IdempotencyKeyCache,
 }
 
 impl Caches {
-    pub fn create(redis: Option<RedisConnection>) -> Self {
+    pub fn create(redis: Option<RedisConnection>, idempotency_key_ttl: Duration) -> Self {
         let mut user_access_tokens = UserAccessTokenCache::new(Duration::from_secs(300));
+        let mut idempotency_keys = IdempotencyKeyCache::new(idempotency_key_ttl);
 
         if let Some(redis) = redis {
             let redis = redis.into_manager();
 
             user_access_tokens = user_access_tokens.with_redis(
-                redis,
+                redis.clone(),
                 "user-access-tokens",
                 Duration::from_secs(300),
                 true,
-            )
+            );
+            idempotency_keys =
+                idempotency_keys.with_redis(redis, "idempotency-keys", idempotency_key_ttl, true);
         };
 
-        Self { user_access_tokens }
+        Self {
+            user_access_tokens,
+            idempotency_keys,
+        }
     }
 }
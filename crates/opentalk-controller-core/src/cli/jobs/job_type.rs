@@ -49,4 +49,7 @@ pub enum JobType {
 
     /// A job to synchronize the user account states with Keycloak
     KeycloakAccountSync,
+
+    /// A job to permanently delete rooms that were marked as deleted a defined duration ago
+    RoomTrashCleanup,
 }
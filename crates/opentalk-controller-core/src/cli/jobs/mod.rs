@@ -102,10 +102,12 @@ async fn execute_job(
         )
     });
     let exchange_handle = match (settings.redis.is_some(), &rabbitmq_pool) {
-        (true, Some(rabbitmq_pool)) => ExchangeTask::spawn_with_rabbitmq(rabbitmq_pool.clone())
-            .await
-            .whatever_context("Failed to spawn exchange task")?,
-        _ => ExchangeTask::spawn()
+        (true, Some(rabbitmq_pool)) => {
+            ExchangeTask::spawn_with_rabbitmq(rabbitmq_pool.clone(), None)
+                .await
+                .whatever_context("Failed to spawn exchange task")?
+        }
+        _ => ExchangeTask::spawn(None)
             .await
             .whatever_context("Failed to spawn exchange task")?,
     };
@@ -138,6 +140,10 @@ async fn execute_job(
             data.execute::<opentalk_jobs::jobs::KeycloakAccountSync>()
                 .await
         }
+        JobType::RoomTrashCleanup => {
+            data.execute::<opentalk_jobs::jobs::RoomTrashCleanup>()
+                .await
+        }
     }
     .whatever_context("Failed to execute job")?;
 
@@ -168,6 +174,9 @@ fn show_default_parameters(job_type: JobType) -> Result<()> {
         JobType::KeycloakAccountSync => {
             show_job_type_default_parameters::<opentalk_jobs::jobs::KeycloakAccountSync>()
         }
+        JobType::RoomTrashCleanup => {
+            show_job_type_default_parameters::<opentalk_jobs::jobs::RoomTrashCleanup>()
+        }
     }
 }
 
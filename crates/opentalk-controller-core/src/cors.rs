@@ -0,0 +1,265 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+//! CORS configuration of the HTTP server
+
+use std::sync::Arc;
+
+use actix_cors::Cors;
+use actix_web::http::{Method, header::*};
+use opentalk_controller_settings::HttpCors;
+use snafu::Snafu;
+use url::Url;
+
+#[derive(Debug, Snafu)]
+#[snafu(display("Invalid CORS origin {origin:?}: {reason}"))]
+pub(crate) struct InvalidOriginError {
+    origin: String,
+    reason: &'static str,
+}
+
+/// The origins that are allowed to access the HTTP server
+#[derive(Debug, Clone, Default)]
+pub(crate) struct AllowedOrigins {
+    /// `None` allows any origin
+    patterns: Option<Arc<[OriginPattern]>>,
+}
+
+impl AllowedOrigins {
+    /// Parse and validate the configured origins
+    pub(crate) fn from_settings(settings: &HttpCors) -> Result<Self, InvalidOriginError> {
+        let Some(allowed_origins) = &settings.allowed_origins else {
+            return Ok(Self::default());
+        };
+
+        let patterns = allowed_origins
+            .iter()
+            .map(|origin| OriginPattern::parse(origin))
+            .collect::<Result<Arc<[_]>, _>>()?;
+
+        Ok(Self {
+            patterns: Some(patterns),
+        })
+    }
+
+    fn is_allowed(&self, origin: &str) -> bool {
+        match &self.patterns {
+            Some(patterns) => patterns.iter().any(|pattern| pattern.matches(origin)),
+            None => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum OriginPattern {
+    /// An exact origin in its ascii serialization, e.g. `https://opentalk.example.com`
+    Exact(String),
+    /// Any subdomain of `domain`, e.g. `*.example.com`
+    Wildcard {
+        scheme: Option<String>,
+        domain: String,
+        port: Option<u16>,
+    },
+}
+
+impl OriginPattern {
+    fn parse(origin: &str) -> Result<Self, InvalidOriginError> {
+        let invalid = |reason| InvalidOriginError {
+            origin: origin.to_owned(),
+            reason,
+        };
+
+        if origin.contains('*') {
+            let (scheme, rest) = match origin.split_once("://") {
+                Some((scheme, rest)) => (Some(scheme.to_ascii_lowercase()), rest),
+                None => (None, origin),
+            };
+
+            if scheme
+                .as_deref()
+                .is_some_and(|scheme| scheme != "http" && scheme != "https")
+            {
+                return Err(invalid("scheme must be either http or https"));
+            }
+
+            let rest = rest
+                .strip_prefix("*.")
+                .ok_or_else(|| invalid("wildcard is only allowed as the leftmost label"))?;
+
+            // Parse the remainder as an origin to validate the domain and the port
+            let url = Url::parse(&format!(
+                "{}://{rest}",
+                scheme.as_deref().unwrap_or("https")
+            ))
+            .map_err(|_| invalid("not a valid domain"))?;
+
+            if url.path() != "/" || url.query().is_some() || url.fragment().is_some() {
+                return Err(invalid("must not contain a path, query or fragment"));
+            }
+
+            let Some(domain) = url.domain() else {
+                return Err(invalid("wildcard patterns require a domain name"));
+            };
+
+            if domain.contains('*') {
+                return Err(invalid("wildcard is only allowed as the leftmost label"));
+            }
+
+            return Ok(Self::Wildcard {
+                scheme,
+                domain: domain.to_owned(),
+                port: url.port(),
+            });
+        }
+
+        let url = Url::parse(origin).map_err(|_| invalid("not a valid URL"))?;
+
+        if url.scheme() != "http" && url.scheme() != "https" {
+            return Err(invalid("scheme must be either http or https"));
+        }
+
+        if url.host().is_none() {
+            return Err(invalid("missing host"));
+        }
+
+        if url.path() != "/" || url.query().is_some() || url.fragment().is_some() {
+            return Err(invalid("must not contain a path, query or fragment"));
+        }
+
+        Ok(Self::Exact(url.origin().ascii_serialization()))
+    }
+
+    fn matches(&self, origin: &str) -> bool {
+        match self {
+            Self::Exact(exact) => exact.eq_ignore_ascii_case(origin),
+            Self::Wildcard {
+                scheme,
+                domain,
+                port,
+            } => {
+                let Ok(url) = Url::parse(origin) else {
+                    return false;
+                };
+
+                let scheme_matches = match scheme {
+                    Some(scheme) => url.scheme() == scheme,
+                    None => url.scheme() == "http" || url.scheme() == "https",
+                };
+
+                let domain_matches = url.domain().is_some_and(|host| {
+                    host.strip_suffix(domain.as_str())
+                        .is_some_and(|prefix| prefix.len() > 1 && prefix.ends_with('.'))
+                });
+
+                scheme_matches && domain_matches && url.port() == *port
+            }
+        }
+    }
+}
+
+/// Create the CORS middleware for the HTTP server
+pub(crate) fn setup(allowed_origins: &AllowedOrigins) -> Cors {
+    let cors = if allowed_origins.patterns.is_some() {
+        let allowed_origins = allowed_origins.clone();
+
+        Cors::default().allowed_origin_fn(move |origin, _request| {
+            origin
+                .to_str()
+                .is_ok_and(|origin| allowed_origins.is_allowed(origin))
+        })
+    } else {
+        // Use a permissive CORS configuration.
+        // The HTTP API is using Bearer tokens for authentication, which are handled by the application and not the browser.
+        Cors::default().allow_any_origin().send_wildcard()
+    };
+
+    cors.allowed_header(CONTENT_TYPE)
+        .allowed_header(AUTHORIZATION)
+        .allowed_header(HeaderName::from_static("idempotency-key"))
+        .allowed_header(IF_NONE_MATCH)
+        .allowed_header(RANGE)
+        .expose_headers([ETAG, CONTENT_RANGE, ACCEPT_RANGES])
+        .allowed_methods([
+            Method::GET,
+            Method::POST,
+            Method::PUT,
+            Method::PATCH,
+            Method::DELETE,
+        ])
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn allowed_origins(origins: &[&str]) -> AllowedOrigins {
+        AllowedOrigins::from_settings(&HttpCors {
+            allowed_origins: Some(origins.iter().map(ToString::to_string).collect()),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn any_origin_when_unset() {
+        let origins = AllowedOrigins::from_settings(&HttpCors::default()).unwrap();
+
+        assert!(origins.is_allowed("https://anything.example.org"));
+    }
+
+    #[test]
+    fn exact_origins() {
+        let origins = allowed_origins(&["https://opentalk.example.com", "http://localhost:3000"]);
+
+        assert!(origins.is_allowed("https://opentalk.example.com"));
+        assert!(origins.is_allowed("http://localhost:3000"));
+        assert!(!origins.is_allowed("http://opentalk.example.com"));
+        assert!(!origins.is_allowed("http://localhost:3001"));
+        assert!(!origins.is_allowed("https://evil.example.com"));
+    }
+
+    #[test]
+    fn wildcard_origins() {
+        let origins = allowed_origins(&["*.example.com", "https://*.example.org:8443"]);
+
+        assert!(origins.is_allowed("https://app.example.com"));
+        assert!(origins.is_allowed("http://a.b.example.com"));
+        assert!(origins.is_allowed("https://app.example.org:8443"));
+        assert!(!origins.is_allowed("https://example.com"));
+        assert!(!origins.is_allowed("https://evilexample.com"));
+        assert!(!origins.is_allowed("https://app.example.com.evil.org"));
+        assert!(!origins.is_allowed("http://app.example.org:8443"));
+        assert!(!origins.is_allowed("https://app.example.org"));
+    }
+
+    #[test]
+    fn invalid_origins() {
+        for origin in [
+            "https://example.com/path",
+            "ftp://example.com",
+            "example.com",
+            "app.*.example.com",
+            "https://*example.com",
+            "ftp://*.example.com",
+            "*.example.com/path",
+        ] {
+            assert!(
+                AllowedOrigins::from_settings(&HttpCors {
+                    allowed_origins: Some(vec![origin.to_owned()]),
+                })
+                .is_err(),
+                "{origin} must be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn exact_origin_is_normalized() {
+        assert_eq!(
+            OriginPattern::parse("HTTPS://OpenTalk.Example.com:443/").unwrap(),
+            OriginPattern::Exact("https://opentalk.example.com".to_owned())
+        );
+    }
+}
@@ -0,0 +1,84 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+//! Runtime diagnostics which are logged when the controller receives `SIGUSR1`
+
+use std::{sync::Arc, time::Duration};
+
+use opentalk_database::Db;
+use opentalk_signaling_core::{ExchangeHandle, SignalingMetrics, VolatileStorage};
+use snafu::Report;
+use tokio::{sync::broadcast, time::timeout};
+
+/// Maximum time to wait for the redis server to answer the diagnostics ping
+const REDIS_PING_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Handles to the internal state of the controller that is included in the diagnostics
+#[derive(Clone)]
+pub(crate) struct Diagnostics {
+    pub(crate) signaling_metrics: Arc<SignalingMetrics>,
+    pub(crate) db: Arc<Db>,
+    pub(crate) volatile: VolatileStorage,
+    pub(crate) exchange_handle: ExchangeHandle,
+    pub(crate) shutdown: broadcast::Sender<()>,
+}
+
+impl Diagnostics {
+    /// Log a snapshot of the runtime state on a separate task
+    ///
+    /// Returns immediately, so the caller is never blocked by slow backends.
+    pub(crate) fn spawn_dump(&self) {
+        let diagnostics = self.clone();
+
+        actix_rt::spawn(async move { diagnostics.dump().await });
+    }
+
+    async fn dump(self) {
+        log::info!(
+            "Diagnostics: active rooms: {}, connected participants: {}, shutdown listeners: {}",
+            self.signaling_metrics.active_rooms_count(),
+            self.signaling_metrics.active_participants_count(),
+            self.shutdown.receiver_count(),
+        );
+
+        log::info!(
+            "Diagnostics: exchange task is {}",
+            if self.exchange_handle.is_available() {
+                "running"
+            } else {
+                "stopped"
+            }
+        );
+
+        let pool = self.db.pool_status();
+        log::info!(
+            "Diagnostics: database pool size: {}/{}, idle: {}, waiting: {}",
+            pool.size,
+            pool.max_size,
+            pool.available,
+            pool.waiting,
+        );
+
+        let Some(mut redis) = self.volatile.right() else {
+            log::info!("Diagnostics: redis is not configured, using in-memory storage");
+            return;
+        };
+
+        match timeout(
+            REDIS_PING_TIMEOUT,
+            redis::cmd("PING").query_async::<()>(&mut redis),
+        )
+        .await
+        {
+            Ok(Ok(())) => log::info!("Diagnostics: redis is reachable"),
+            Ok(Err(e)) => log::info!(
+                "Diagnostics: redis is not reachable, {}",
+                Report::from_error(e)
+            ),
+            Err(_) => {
+                log::info!("Diagnostics: redis did not respond within {REDIS_PING_TIMEOUT:?}")
+            }
+        }
+    }
+}
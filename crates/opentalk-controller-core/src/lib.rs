@@ -38,11 +38,11 @@ use std::{
     io::BufReader,
     marker::PhantomData,
     net::{Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener, ToSocketAddrs as _},
+    path::PathBuf,
     sync::Arc,
     time::Duration,
 };
 
-use actix_cors::Cors;
 use actix_web::{App, HttpServer, Scope, web, web::Data};
 use api::signaling::SignalingModules;
 use async_trait::async_trait;
@@ -51,26 +51,28 @@ use lapin_pool::RabbitMqPool;
 use opentalk_controller_service::{
     ControllerBackend, Whatever,
     oidc::OidcContext,
-    services::MailService,
+    services::{MailService, WebhookService},
     signaling::ws_modules::{breakout::BreakoutRooms, echo::Echo, moderation::ModerationModule},
 };
 use opentalk_controller_service_facade::OpenTalkControllerService;
 use opentalk_controller_settings::{
-    HttpTls, Monitoring, Settings, SettingsProvider, UserSearchBackend, UserSearchBackendKeycloak,
+    HttpTls, HttpTlsClientAuth, Monitoring, Settings, SettingsProvider, UserSearchBackend,
+    UserSearchBackendKeycloak, settings_file::ClientAuthEnforcement,
 };
-use opentalk_database::Db;
+use opentalk_database::{Db, OptionalExt as _};
+use opentalk_db_storage::tariffs::Tariff;
 use opentalk_jobs::job_runner::JobRunner;
 use opentalk_keycloak_admin::{AuthorizedClient, KeycloakAdminClient};
 use opentalk_roomserver_client::Client as RoomServerClient;
 use opentalk_signaling_core::{
-    ExchangeHandle, ExchangeTask, ModulesRegistrar, ObjectStorage, RedisConnection,
-    RegisterModules, SignalingModule, SignalingModuleInitData, VolatileStaticMemoryStorage,
-    VolatileStorage,
+    ExchangeError, ExchangeHandle, ExchangeTask, ModulesRegistrar, ObjectStorage,
+    PdfGenerationLimiter, RedisConnection, RegisterModules, SignalingMetrics, SignalingModule,
+    SignalingModuleInitData, VolatileStaticMemoryStorage, VolatileStorage,
 };
 use opentalk_types_api_v1::{auth::OidcProvider, error::ApiError};
 use rustls_pki_types::{CertificateDer, PrivatePkcs8KeyDer};
 use service_probe::{ServiceState, set_service_state, start_probe};
-use snafu::{ErrorCompat, Report, ResultExt, Snafu};
+use snafu::{ErrorCompat, Report, ResultExt, Snafu, whatever};
 use swagger::WithSwagger as _;
 use tokio::{
     signal::{
@@ -95,6 +97,8 @@ use crate::{
 mod acl;
 mod caches;
 mod cli;
+mod cors;
+mod diagnostics;
 mod metrics;
 mod swagger;
 mod trace;
@@ -120,6 +124,9 @@ impl From<BlockingError> for ApiError {
 
 type Result<T, E = Whatever> = std::result::Result<T, E>;
 
+/// Upper bound for the delay between two rabbitmq connection attempts on startup
+const MAX_RABBITMQ_STARTUP_RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
 /// Custom version of `actix_web::web::block` which retains the current tracing span
 pub async fn block<F, R>(f: F) -> Result<R, BlockingError>
 where
@@ -210,6 +217,9 @@ pub struct Controller {
     /// Handle to the internal message exchange
     pub exchange_handle: ExchangeHandle,
 
+    /// Handle to the task delivering room lifecycle events to the webhook endpoint
+    webhook: WebhookService,
+
     /// Cloneable volatile storage
     pub volatile: VolatileStorage,
 
@@ -232,6 +242,15 @@ pub struct Controller {
     /// Can and should be used to extend the controllers signaling endpoint's capabilities.
     pub signaling_modules: SignalingModules,
 
+    /// Limiter shared by all signaling modules that render PDF reports
+    pdf_generation_limiter: PdfGenerationLimiter,
+
+    /// Directory into which the signaling modules dump report generation data
+    report_dump_path: Option<PathBuf>,
+
+    /// Directory containing additional fonts for the report generation of the signaling modules
+    report_font_path: Option<PathBuf>,
+
     /// All metrics of the Application
     pub metrics: metrics::CombinedMetrics,
 }
@@ -294,11 +313,25 @@ impl Controller {
         // This assumes that the existence of redis means multiple controllers are used
         // and share their signaling state via redis. Only in this case rabbitmq is required
         // in the exchange.
-        let exchange_handle = match (settings.redis.is_some(), rabbitmq_pool.as_ref()) {
-            (true, Some(rabbitmq_pool)) => ExchangeTask::spawn_with_rabbitmq(rabbitmq_pool.clone())
-                .await
-                .whatever_context("Failed to spawn exchange task")?,
-            _ => ExchangeTask::spawn()
+        let exchange_handle = match (
+            settings.rabbit_mq.as_ref(),
+            settings.redis.is_some(),
+            rabbitmq_pool.as_ref(),
+        ) {
+            (Some(config), true, Some(rabbitmq_pool)) => spawn_exchange_with_retry(
+                rabbitmq_pool.clone(),
+                metrics.signaling.clone(),
+                config.startup_retries,
+                config.startup_retry_interval,
+            )
+            .await
+            .with_whatever_context(|_| {
+                format!(
+                    "Failed to spawn exchange task, rabbitmq is unreachable after {} retries",
+                    config.startup_retries
+                )
+            })?,
+            _ => ExchangeTask::spawn(Some(metrics.signaling.clone()))
                 .await
                 .whatever_context("Failed to spawn exchange task")?,
         };
@@ -309,6 +342,8 @@ impl Controller {
         db.set_metrics(metrics.database.clone());
         let db = Arc::new(db);
 
+        verify_default_tariffs(&settings, &db).await?;
+
         // Connect to MinIO
         let storage = Arc::new(
             ObjectStorage::new(&settings.minio)
@@ -379,6 +414,28 @@ impl Controller {
         let (shutdown, _) = broadcast::channel::<()>(1);
         let (reload, _) = broadcast::channel::<()>(4);
 
+        let pdf_generation_limiter =
+            PdfGenerationLimiter::new(settings.reports.max_concurrent_pdf_generations);
+
+        let report_dump_path =
+            opentalk_report_generation::resolve_dump_path(settings.reports.dump_path.as_deref());
+        if let Some(report_dump_path) = &report_dump_path {
+            log::info!("Report generation data will be dumped to {report_dump_path:?}");
+
+            if let Err(e) = opentalk_report_generation::check_dump_path(report_dump_path) {
+                log::warn!("Report dump path {report_dump_path:?} is not writable: {e}");
+            }
+        }
+
+        let report_font_path = settings.reports.font_path.clone();
+        if let Some(report_font_path) = &report_font_path {
+            let font_count = opentalk_report_generation::check_font_path(report_font_path)
+                .with_whatever_context(|_| {
+                    format!("Failed to load the report fonts from {report_font_path:?}")
+                })?;
+            log::info!("Loaded {font_count} report fonts from {report_font_path:?}");
+        }
+
         let authz = match (
             settings.authz.synchronize_controllers,
             rabbitmq_pool.as_ref(),
@@ -407,6 +464,9 @@ impl Controller {
             None => None,
         });
 
+        let webhook = WebhookService::spawn(settings_provider.clone(), db.clone())
+            .whatever_context("Failed to create webhook client")?;
+
         let mut initializer = ModuleInitializer {
             init_data: SignalingModuleInitData {
                 startup_settings: settings.clone(),
@@ -415,6 +475,10 @@ impl Controller {
                 volatile: volatile.clone(),
                 shutdown: shutdown.clone(),
                 reload: reload.clone(),
+                metrics: metrics.signaling.clone(),
+                pdf_generation_limiter: pdf_generation_limiter.clone(),
+                report_dump_path: report_dump_path.clone(),
+                report_font_path: report_font_path.clone(),
             },
             signaling_modules: SignalingModules::default(),
         };
@@ -452,7 +516,9 @@ impl Controller {
                 mail_service.clone(),
                 user_search_client.clone(),
                 initializer.signaling_modules.get_module_features(),
+                initializer.signaling_modules.get_module_state_summarizers(),
                 roomserver_client,
+                webhook.clone(),
             )
         };
         let service = OpenTalkControllerService::new(backend);
@@ -469,10 +535,14 @@ impl Controller {
             authz,
             rabbitmq_pool,
             exchange_handle,
+            webhook,
             volatile,
             shutdown,
             reload,
             signaling_modules: initializer.signaling_modules,
+            pdf_generation_limiter,
+            report_dump_path,
+            report_font_path,
             metrics,
         };
 
@@ -499,11 +569,20 @@ impl Controller {
         .await
         .whatever_context("Failed to start Job Runner")?;
 
+        let diagnostics = diagnostics::Diagnostics {
+            signaling_metrics: self.metrics.signaling.clone(),
+            db: self.db.clone(),
+            volatile: self.volatile.clone(),
+            exchange_handle: self.exchange_handle.clone(),
+            shutdown: self.shutdown.clone(),
+        };
+
         // Start HTTP Server
         let http_server = {
             let settings_provider = self.settings_provider.clone();
             let volatile = self.volatile.clone();
             let exchange_handle = Data::new(self.exchange_handle);
+            let webhook = Data::new(self.webhook);
             let signaling_modules = Arc::downgrade(&signaling_modules);
             let signaling_metrics = Data::from(self.metrics.signaling.clone());
             let db = Arc::downgrade(&self.db);
@@ -523,11 +602,26 @@ impl Controller {
 
             let metrics = Data::new(self.metrics);
 
-            let caches = Data::new(caches::Caches::create(self.volatile.right().clone()));
+            let caches = Data::new(caches::Caches::create(
+                self.volatile.right().clone(),
+                self.startup_settings.http.idempotency_key_ttl,
+            ));
             let service = Data::new(self.service.clone());
 
+            let allowed_origins =
+                cors::AllowedOrigins::from_settings(&self.startup_settings.http.cors)
+                    .whatever_context("Invalid CORS configuration")?;
+
+            let client_auth = self
+                .startup_settings
+                .http
+                .tls
+                .as_ref()
+                .and_then(|tls| tls.client_auth.as_ref())
+                .map(|client_auth| client_auth.enforcement);
+
             HttpServer::new(move || {
-                let cors = setup_cors();
+                let cors = cors::setup(&allowed_origins);
 
                 // Unwraps cannot panic. Server gets stopped before dropping the Arc.
                 let db = Data::from(db.upgrade().unwrap());
@@ -559,6 +653,7 @@ impl Controller {
                     .app_data(volatile)
                     .app_data(Data::new(shutdown.clone()))
                     .app_data(exchange_handle.clone())
+                    .app_data(webhook.clone())
                     .app_data(signaling_modules)
                     .app_data(SignalingProtocols::data())
                     .app_data(signaling_metrics.clone())
@@ -573,8 +668,10 @@ impl Controller {
                         db.clone(),
                         oidc_ctx.clone(),
                         acl,
+                        client_auth,
                     ))
             })
+            .on_connect(api::v1::middleware::client_cert_auth::extract_client_certificate)
         };
 
         let socket_address = determine_socket_address(
@@ -583,10 +680,17 @@ impl Controller {
         )
         .whatever_context("Unable to determine bind address")?;
 
+        let http2 = self.startup_settings.http.http2;
+
         let http_server = if let Some(tls) = &self.startup_settings.http.tls {
+            // actix-web always offers h2 and http/1.1 via ALPN, the settings validation rejects
+            // disabling `http2` together with TLS
             let config = setup_rustls(tls).whatever_context("Failed to setup TLS context")?;
 
             http_server.bind_rustls_0_23(&socket_address[..], config)
+        } else if http2 {
+            // Accept HTTP/2 with prior knowledge (h2c), HTTP/1.1 requests are detected and served as before
+            http_server.bind_auto_h2c(&socket_address[..])
         } else {
             http_server.bind(&socket_address[..])
         };
@@ -603,10 +707,12 @@ impl Controller {
 
         let mut reload_signal = signal(SignalKind::hangup())
             .whatever_context("Failed to register SIGHUP signal handler")?;
+        let mut diagnostics_signal = signal(SignalKind::user_defined1())
+            .whatever_context("Failed to register SIGUSR1 signal handler")?;
 
         actix_rt::spawn(http_server);
 
-        // Wait for either SIGTERM or SIGHUP and handle them accordingly
+        // Wait for either SIGTERM, SIGHUP or SIGUSR1 and handle them accordingly
         loop {
             tokio::select! {
                 _ = ctrl_c() => {
@@ -616,14 +722,24 @@ impl Controller {
                 _ = reload_signal.recv() => {
                     log::info!("Got reload signal, reloading");
 
+                    let previous_settings = self.settings_provider.get();
                     if let Err(e) = self.settings_provider.reload_from_path_or_standard_paths(self.args.config.as_deref()) {
                         log::error!("Failed to reload settings, {}", Report::from_error(e));
                         continue
                     }
 
+                    let settings = self.settings_provider.get();
+                    warn_about_restart_required_changes(&previous_settings, &settings);
+                    signaling_modules.reload_params(&settings);
+
                     // discard result, might fail if no one is subscribed
                     let _ = self.reload.send(());
                 }
+                _ = diagnostics_signal.recv() => {
+                    log::info!("Got diagnostics signal, dumping runtime diagnostics");
+
+                    diagnostics.spawn_dump();
+                }
             }
         }
 
@@ -682,6 +798,10 @@ impl ModulesRegistrar for Controller {
             shutdown: self.shutdown.clone(),
             reload: self.reload.clone(),
             volatile: self.volatile.clone(),
+            metrics: self.metrics.signaling.clone(),
+            pdf_generation_limiter: self.pdf_generation_limiter.clone(),
+            report_dump_path: self.report_dump_path.clone(),
+            report_font_path: self.report_font_path.clone(),
         };
 
         let params = M::build_params(init)
@@ -813,14 +933,20 @@ impl ModulesRegistrar for Controller {
         api::v1::rooms::delete,
         api::v1::rooms::get,
         api::v1::rooms::get_room_event,
+        api::v1::rooms::get_room_module_state,
+        api::v1::rooms::get_room_legal_vote_protocol,
         api::v1::rooms::get_room_tariff,
+        api::v1::rooms::get_room_capabilities,
         api::v1::rooms::new,
         api::v1::rooms::patch,
+        api::v1::rooms::restore,
+        api::v1::rooms::transfer_ownership,
         api::v1::rooms::start,
         api::v1::rooms::start_invited,
         api::v1::services::call_in::post_call_in_start,
         api::v1::services::recording::get_recording_upload,
         api::v1::services::recording::post_recording_start,
+        api::v1::services::recording::post_recording_stop,
         api::v1::sip_configs::delete,
         api::v1::sip_configs::get,
         api::v1::sip_configs::put,
@@ -829,6 +955,8 @@ impl ModulesRegistrar for Controller {
         api::v1::streaming_targets::get_streaming_targets,
         api::v1::streaming_targets::patch_streaming_target,
         api::v1::streaming_targets::post_streaming_target,
+        api::v1::streaming_targets::put_primary_streaming_target,
+        api::v1::streaming_targets::put_streaming_targets_order,
         api::v1::turn::get,
         api::v1::users::find,
         api::v1::users::get_me,
@@ -882,8 +1010,10 @@ impl ModulesRegistrar for Controller {
             opentalk_types_api_v1::rooms::RoomResource,
             opentalk_types_api_v1::rooms::by_room_id::PatchRoomsRequestBody,
             opentalk_types_api_v1::rooms::by_room_id::PostRoomsStartInvitedRequestBody,
+            opentalk_types_api_v1::rooms::by_room_id::PostRoomsTransferOwnershipRequestBody,
             opentalk_types_api_v1::rooms::by_room_id::PostRoomsStartRequestBody,
             opentalk_types_api_v1::rooms::by_room_id::RoomsStartResponseBody,
+            opentalk_types_api_v1::rooms::by_room_id::capabilities::GetRoomCapabilitiesResponseBody,
             opentalk_types_api_v1::rooms::by_room_id::invites::GetRoomsInvitesResponseBody,
             opentalk_types_api_v1::rooms::by_room_id::invites::InviteResource,
             opentalk_types_api_v1::rooms::by_room_id::invites::PostInviteRequestBody,
@@ -898,6 +1028,7 @@ impl ModulesRegistrar for Controller {
             opentalk_types_api_v1::rooms::by_room_id::streaming_targets::PatchRoomStreamingTargetResponseBody,
             opentalk_types_api_v1::rooms::by_room_id::streaming_targets::PostRoomStreamingTargetRequestBody,
             opentalk_types_api_v1::rooms::by_room_id::streaming_targets::PostRoomStreamingTargetResponseBody,
+            opentalk_types_api_v1::rooms::by_room_id::streaming_targets::PutRoomStreamingTargetsOrderRequestBody,
             opentalk_types_api_v1::rooms::streaming_targets::UpdateStreamingTargetKind,
             opentalk_types_api_v1::services::PostServiceStartResponseBody,
             opentalk_types_api_v1::services::call_in::PostCallInStartRequestBody,
@@ -931,6 +1062,7 @@ impl ModulesRegistrar for Controller {
             opentalk_types_common::events::invites::EventInviteStatus,
             opentalk_types_common::events::invites::InviteRole,
             opentalk_types_common::features::FeatureId,
+            opentalk_types_common::module_resources::ModuleResourceId,
             opentalk_types_common::modules::ModuleId,
             opentalk_types_common::order::Ordering,
             opentalk_types_common::rooms::BreakoutRoomId,
@@ -1005,6 +1137,7 @@ fn v1_scope(
     db: Data<Db>,
     oidc_ctx: Data<OidcContext>,
     acl: kustos::actix_web::KustosService,
+    client_auth: Option<ClientAuthEnforcement>,
 ) -> Scope {
     // the latest version contains the root services
 
@@ -1022,12 +1155,26 @@ fn v1_scope(
                 .wrap(api::v1::middleware::service_auth::ServiceAuth::new(
                     oidc_ctx.clone(),
                 ))
+                .wrap(api::v1::middleware::client_cert_auth::ClientCertAuth::new(
+                    client_auth,
+                ))
                 .service(api::v1::services::call_in::services())
                 .service(api::v1::services::recording::services()),
         )
+        .service(
+            web::scope("/rooms/{room_id}/recording")
+                .wrap(api::v1::middleware::service_auth::ServiceAuth::new(
+                    oidc_ctx.clone(),
+                ))
+                .wrap(api::v1::middleware::client_cert_auth::ClientCertAuth::new(
+                    client_auth,
+                ))
+                .service(api::v1::services::recording::room_services()),
+        )
         .service(
             // empty scope to differentiate between auth endpoints
             web::scope("")
+                .wrap(api::v1::middleware::idempotency::Idempotency)
                 .wrap(acl)
                 .wrap(api::v1::middleware::user_auth::OidcAuth {
                     settings_provider,
@@ -1046,10 +1193,15 @@ fn v1_scope(
                 .service(api::v1::rooms::patch)
                 .service(api::v1::rooms::get)
                 .service(api::v1::rooms::get_room_event)
+                .service(api::v1::rooms::get_room_module_state)
+                .service(api::v1::rooms::get_room_legal_vote_protocol)
                 .service(api::v1::rooms::get_room_tariff)
+                .service(api::v1::rooms::get_room_capabilities)
                 .service(api::v1::rooms::start)
                 .service(api::v1::rooms::roomserver::start)
                 .service(api::v1::rooms::delete)
+                .service(api::v1::rooms::restore)
+                .service(api::v1::rooms::transfer_ownership)
                 .service(api::v1::events::new_event)
                 .service(api::v1::events::get_events)
                 .service(api::v1::events::get_event)
@@ -1085,36 +1237,97 @@ fn v1_scope(
                 .service(api::v1::assets::delete)
                 .service(api::v1::streaming_targets::get_streaming_targets)
                 .service(api::v1::streaming_targets::post_streaming_target)
+                .service(api::v1::streaming_targets::put_streaming_targets_order)
+                .service(api::v1::streaming_targets::put_primary_streaming_target)
                 .service(api::v1::streaming_targets::get_streaming_target)
                 .service(api::v1::streaming_targets::patch_streaming_target)
                 .service(api::v1::streaming_targets::delete_streaming_target),
         )
 }
 
-fn setup_cors() -> Cors {
-    use actix_web::http::{Method, header::*};
-
-    // Use a permissive CORS configuration.
-    // The HTTP API is using Bearer tokens for authentication, which are handled by the application and not the browser.
-    Cors::default()
-        .allow_any_origin()
-        .send_wildcard()
-        .allowed_header(CONTENT_TYPE)
-        .allowed_header(AUTHORIZATION)
-        .allowed_methods([
-            Method::GET,
-            Method::POST,
-            Method::PUT,
-            Method::PATCH,
-            Method::DELETE,
-        ])
+/// Spawn the exchange task with rabbitmq support, retrying with exponential backoff
+///
+/// Only the initial connection is retried here, once the exchange task is running it handles
+/// reconnects on its own.
+async fn spawn_exchange_with_retry(
+    rabbitmq_pool: Arc<RabbitMqPool>,
+    metrics: Arc<SignalingMetrics>,
+    retries: u32,
+    retry_interval: Duration,
+) -> Result<ExchangeHandle, ExchangeError> {
+    let mut delay = retry_interval;
+
+    for retry in 1..=retries {
+        match ExchangeTask::spawn_with_rabbitmq(rabbitmq_pool.clone(), Some(metrics.clone())).await
+        {
+            Ok(exchange_handle) => return Ok(exchange_handle),
+            Err(e) => {
+                log::warn!(
+                    "Failed to connect the exchange to rabbitmq (retry {retry}/{retries} in {delay:?}), {}",
+                    Report::from_error(e)
+                );
+            }
+        }
+
+        sleep(delay).await;
+        delay = delay
+            .saturating_mul(2)
+            .min(MAX_RABBITMQ_STARTUP_RETRY_INTERVAL);
+    }
+
+    ExchangeTask::spawn_with_rabbitmq(rabbitmq_pool, Some(metrics)).await
+}
+
+/// Verify that the configured default tariffs exist, as users without a tariff would otherwise be
+/// rejected when logging in
+async fn verify_default_tariffs(settings: &Settings, db: &Db) -> Result<()> {
+    let mut conn = db
+        .get_conn()
+        .await
+        .whatever_context("Failed to get database connection")?;
+
+    for tariff_name in settings.tariffs.default_tariff_names() {
+        let tariff = Tariff::get_by_name(&mut conn, tariff_name)
+            .await
+            .optional()
+            .whatever_context("Failed to load default tariff")?;
+
+        if tariff.is_none() {
+            whatever!("The configured default tariff '{tariff_name}' does not exist");
+        }
+    }
+
+    Ok(())
+}
+
+/// Log a warning for changed settings which are only applied when the controller is restarted
+///
+/// The settings are compared against the ones active before the reload, so every change is only
+/// reported once.
+fn warn_about_restart_required_changes(previous_settings: &Settings, settings: &Settings) {
+    let sections = [
+        ("http", previous_settings.http != settings.http),
+        ("database", previous_settings.database != settings.database),
+        ("redis", previous_settings.redis != settings.redis),
+        (
+            "rabbit_mq",
+            previous_settings.rabbit_mq != settings.rabbit_mq,
+        ),
+    ];
+
+    for (section, _) in sections.iter().filter(|(_, changed)| *changed) {
+        log::warn!(
+            "Changes to the `{section}` settings require a restart of the controller to take effect"
+        );
+    }
 }
 
 /// Set up TLS for the HTTP server that is provided by the controller
 ///
 /// Receives the TLS-related settings from the controller configuration
 /// which contains the path to the private key and the certificate files
-/// from where the TLS configuration is loaded and set up.
+/// from where the TLS configuration is loaded and set up. The ALPN protocols
+/// are set by actix-web, which negotiates HTTP/2 with HTTP/1.1 as fallback.
 fn setup_rustls(tls: &HttpTls) -> Result<rustls::ServerConfig> {
     let cert_file = File::open(&tls.certificate).with_whatever_context(|_| {
         format!("Failed to open certificate file {:?}", &tls.certificate)
@@ -1133,14 +1346,51 @@ fn setup_rustls(tls: &HttpTls) -> Result<rustls::ServerConfig> {
         .collect::<Result<Vec<PrivatePkcs8KeyDer>, _>>()
         .whatever_context("Invalid pkcs8 private key")?;
 
-    let config = rustls::ServerConfig::builder()
-        .with_no_client_auth()
+    let builder = rustls::ServerConfig::builder();
+
+    let builder = if let Some(client_auth) = &tls.client_auth {
+        builder.with_client_cert_verifier(setup_client_cert_verifier(client_auth)?)
+    } else {
+        builder.with_no_client_auth()
+    };
+
+    let config = builder
         .with_single_cert(certs, rustls_pki_types::PrivateKeyDer::Pkcs8(key.remove(0)))
         .whatever_context("Invalid DER-encoded key ")?;
 
     Ok(config)
 }
 
+/// Set up the verifier for client certificates
+///
+/// Clients without a certificate are still accepted, so the public API remains reachable. The
+/// service endpoints check for a verified certificate in the [`ClientCertAuth`] middleware.
+///
+/// [`ClientCertAuth`]: api::v1::middleware::client_cert_auth::ClientCertAuth
+fn setup_client_cert_verifier(
+    client_auth: &HttpTlsClientAuth,
+) -> Result<Arc<dyn rustls::server::danger::ClientCertVerifier>> {
+    let ca_file = File::open(&client_auth.ca_certificate).with_whatever_context(|_| {
+        format!(
+            "Failed to open CA certificate file {:?}",
+            &client_auth.ca_certificate
+        )
+    })?;
+
+    let mut roots = rustls::RootCertStore::empty();
+    for certificate in rustls_pemfile::certs(&mut BufReader::new(ca_file)) {
+        let certificate = certificate.whatever_context("Invalid CA certificate")?;
+        roots
+            .add(certificate)
+            .whatever_context("Failed to add CA certificate")?;
+    }
+
+    rustls::server::WebPkiClientVerifier::builder(Arc::new(roots))
+        .allow_unauthenticated()
+        .build()
+        .whatever_context("Failed to build client certificate verifier")
+}
+
 struct ModuleInitializer {
     init_data: SignalingModuleInitData,
     signaling_modules: SignalingModules,
@@ -1185,3 +1435,237 @@ fn determine_socket_address(
     };
     Ok(to_socket_addrs)
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        fs::File,
+        io::{BufRead, BufReader, Write},
+        net::SocketAddr,
+        path::PathBuf,
+        sync::Arc,
+    };
+
+    use actix_web::{App, HttpResponse, HttpServer, dev::ServerHandle, web};
+    use opentalk_controller_settings::{
+        HttpTls, HttpTlsClientAuth, settings_file::ClientAuthEnforcement,
+    };
+    use pretty_assertions::assert_eq;
+    use rustls_pki_types::{CertificateDer, ServerName};
+    use tokio::net::TcpStream;
+    use tokio_rustls::TlsConnector;
+
+    use super::setup_rustls;
+    use crate::api::v1::middleware::client_cert_auth::{
+        ClientCertAuth, extract_client_certificate,
+    };
+
+    fn fixture(name: &str) -> PathBuf {
+        [env!("CARGO_MANIFEST_DIR"), "tests", "fixtures", name]
+            .iter()
+            .collect()
+    }
+
+    async fn negotiate_alpn(addr: SocketAddr, offered: &[&[u8]]) -> Option<Vec<u8>> {
+        let cert_file = File::open(fixture("localhost.crt")).unwrap();
+        let mut roots = rustls::RootCertStore::empty();
+        for cert in rustls_pemfile::certs(&mut BufReader::new(cert_file)) {
+            let cert: CertificateDer = cert.unwrap();
+            roots.add(cert).unwrap();
+        }
+
+        let mut config = rustls::ClientConfig::builder()
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        config.alpn_protocols = offered.iter().map(|protocol| protocol.to_vec()).collect();
+
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let stream = TlsConnector::from(Arc::new(config))
+            .connect(ServerName::try_from("localhost").unwrap(), stream)
+            .await
+            .unwrap();
+
+        stream.get_ref().1.alpn_protocol().map(<[u8]>::to_vec)
+    }
+
+    #[actix_rt::test]
+    async fn tls_alpn_negotiation() {
+        let tls = HttpTls {
+            certificate: fixture("localhost.crt"),
+            private_key: fixture("localhost.key"),
+            client_auth: None,
+        };
+        let config = setup_rustls(&tls).unwrap();
+
+        let server = HttpServer::new(App::new)
+            .workers(1)
+            .bind_rustls_0_23(("127.0.0.1", 0), config)
+            .unwrap();
+        let addr = server.addrs()[0];
+        let server = server.disable_signals().run();
+        let handle = server.handle();
+        actix_rt::spawn(server);
+
+        assert_eq!(
+            negotiate_alpn(addr, &[b"h2", b"http/1.1"]).await,
+            Some(b"h2".to_vec())
+        );
+        // WebSocket clients only offer HTTP/1.1
+        assert_eq!(
+            negotiate_alpn(addr, &[b"http/1.1"]).await,
+            Some(b"http/1.1".to_vec())
+        );
+
+        handle.stop(false).await;
+    }
+
+    fn load_certificates(name: &str) -> Vec<CertificateDer<'static>> {
+        let file = File::open(fixture(name)).unwrap();
+        rustls_pemfile::certs(&mut BufReader::new(file))
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    /// Start a server with a public endpoint and a service endpoint which is protected by the
+    /// client certificate middleware
+    fn start_client_auth_server(enforcement: ClientAuthEnforcement) -> (SocketAddr, ServerHandle) {
+        let tls = HttpTls {
+            certificate: fixture("localhost.crt"),
+            private_key: fixture("localhost.key"),
+            client_auth: Some(HttpTlsClientAuth {
+                ca_certificate: fixture("client_ca.crt"),
+                enforcement,
+            }),
+        };
+        let config = setup_rustls(&tls).unwrap();
+
+        let server = HttpServer::new(move || {
+            App::new()
+                .route(
+                    "/public",
+                    web::get().to(|| async { HttpResponse::Ok().finish() }),
+                )
+                .service(
+                    web::scope("/services")
+                        .wrap(ClientCertAuth::new(Some(enforcement)))
+                        .route(
+                            "/ping",
+                            web::get().to(|| async { HttpResponse::Ok().finish() }),
+                        ),
+                )
+        })
+        .on_connect(extract_client_certificate)
+        .workers(1)
+        .bind_rustls_0_23(("127.0.0.1", 0), config)
+        .unwrap();
+        let addr = server.addrs()[0];
+        let server = server.disable_signals().run();
+        let handle = server.handle();
+        actix_rt::spawn(server);
+
+        (addr, handle)
+    }
+
+    /// Send a request over a new TLS connection, authenticating with the client certificate
+    /// fixture of the given name
+    ///
+    /// Returns the status code of the response, or `None` if the server rejected the connection.
+    async fn request_status(
+        addr: SocketAddr,
+        path: &'static str,
+        client_certificate: Option<&'static str>,
+    ) -> Option<u16> {
+        let roots = {
+            let mut roots = rustls::RootCertStore::empty();
+            roots.add_parsable_certificates(load_certificates("localhost.crt"));
+            roots
+        };
+
+        let builder = rustls::ClientConfig::builder().with_root_certificates(roots);
+        let mut config = match client_certificate {
+            Some(name) => {
+                let key_file = File::open(fixture(&format!("{name}.key"))).unwrap();
+                let key = rustls_pemfile::private_key(&mut BufReader::new(key_file))
+                    .unwrap()
+                    .unwrap();
+
+                builder
+                    .with_client_auth_cert(load_certificates(&format!("{name}.crt")), key)
+                    .unwrap()
+            }
+            None => builder.with_no_client_auth(),
+        };
+        config.alpn_protocols = vec![b"http/1.1".to_vec()];
+
+        tokio::task::spawn_blocking(move || {
+            let connection = rustls::ClientConnection::new(
+                Arc::new(config),
+                ServerName::try_from("localhost").unwrap(),
+            )
+            .unwrap();
+            let socket = std::net::TcpStream::connect(addr).unwrap();
+            let mut stream = rustls::StreamOwned::new(connection, socket);
+
+            let request =
+                format!("GET {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n");
+            stream.write_all(request.as_bytes()).ok()?;
+
+            let mut status_line = String::new();
+            BufReader::new(stream).read_line(&mut status_line).ok()?;
+
+            status_line.split_whitespace().nth(1)?.parse().ok()
+        })
+        .await
+        .unwrap()
+    }
+
+    #[actix_rt::test]
+    async fn service_request_without_client_certificate_is_rejected() {
+        let (addr, handle) = start_client_auth_server(ClientAuthEnforcement::Required);
+
+        assert_eq!(
+            request_status(addr, "/services/ping", None).await,
+            Some(401)
+        );
+        // The public API stays reachable without a client certificate
+        assert_eq!(request_status(addr, "/public", None).await, Some(200));
+
+        handle.stop(false).await;
+    }
+
+    #[actix_rt::test]
+    async fn client_certificate_of_unknown_ca_is_rejected() {
+        let (addr, handle) = start_client_auth_server(ClientAuthEnforcement::Required);
+
+        assert_eq!(
+            request_status(addr, "/services/ping", Some("untrusted_client")).await,
+            None
+        );
+
+        handle.stop(false).await;
+    }
+
+    #[actix_rt::test]
+    async fn client_certificate_of_configured_ca_is_accepted() {
+        let (addr, handle) = start_client_auth_server(ClientAuthEnforcement::Required);
+
+        assert_eq!(
+            request_status(addr, "/services/ping", Some("client")).await,
+            Some(200)
+        );
+
+        handle.stop(false).await;
+    }
+
+    #[actix_rt::test]
+    async fn optional_client_certificate_is_not_enforced() {
+        let (addr, handle) = start_client_auth_server(ClientAuthEnforcement::Optional);
+
+        assert_eq!(
+            request_status(addr, "/services/ping", None).await,
+            Some(200)
+        );
+
+        handle.stop(false).await;
+    }
+}
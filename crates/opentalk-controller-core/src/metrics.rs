@@ -110,3 +110,151 @@ pub async fn metrics(
         .content_type("text/plain")
         .body(BoxBody::new(response))
 }
+
+#[cfg(test)]
+mod tests {
+    use opentalk_types_common::modules::ModuleId;
+    use opentelemetry::metrics::MeterProvider as _;
+    use pretty_assertions::assert_eq;
+    use prometheus::proto::MetricFamily;
+
+    use super::*;
+
+    fn signaling_metrics() -> (Registry, SdkMeterProvider, SignalingMetrics) {
+        let registry = Registry::new();
+        let exporter = opentelemetry_prometheus::exporter()
+            .with_registry(registry.clone())
+            .build()
+            .unwrap();
+        let provider =
+            SignalingMetrics::append_views(SdkMeterProvider::builder().with_reader(exporter))
+                .unwrap()
+                .build();
+        let metrics = SignalingMetrics::new(&provider.meter("test"));
+
+        (registry, provider, metrics)
+    }
+
+    fn family<'a>(families: &'a [MetricFamily], prefix: &str) -> Option<&'a MetricFamily> {
+        families
+            .iter()
+            .find(|family| family.name().starts_with(prefix))
+    }
+
+    fn metrics_with_labels<'a>(
+        family: &'a MetricFamily,
+        labels: &[(&str, &str)],
+    ) -> impl Iterator<Item = &'a prometheus::proto::Metric> {
+        family.metric().iter().filter(|metric| {
+            labels.iter().all(|(name, value)| {
+                metric
+                    .label()
+                    .iter()
+                    .any(|label| label.name() == *name && label.value() == *value)
+            })
+        })
+    }
+
+    fn report_generations(families: &[MetricFamily], module: &str, successful: &str) -> f64 {
+        let family = family(families, "signaling_report_generations_count")
+            .expect("report generations are recorded");
+
+        metrics_with_labels(family, &[("module", module), ("successful", successful)])
+            .map(|metric| metric.counter().value())
+            .sum()
+    }
+
+    #[test]
+    fn report_generation_outcomes_are_counted_per_module() {
+        let (registry, _provider, metrics) = signaling_metrics();
+        let legal_vote: ModuleId = "legal_vote".parse().expect("valid module id");
+        let training_participation_report: ModuleId = "training_participation_report"
+            .parse()
+            .expect("valid module id");
+
+        metrics.record_report_generation(&legal_vote, true);
+        metrics.record_report_generation(&legal_vote, true);
+        metrics.record_report_generation(&legal_vote, false);
+        metrics.record_report_generation(&training_participation_report, false);
+
+        let families = registry.gather();
+        assert_eq!(report_generations(&families, "legal_vote", "true"), 2.0);
+        assert_eq!(report_generations(&families, "legal_vote", "false"), 1.0);
+        assert_eq!(
+            report_generations(&families, "training_participation_report", "true"),
+            0.0
+        );
+        assert_eq!(
+            report_generations(&families, "training_participation_report", "false"),
+            1.0
+        );
+    }
+
+    #[test]
+    fn last_successful_report_generation_is_recorded_per_module() {
+        let (registry, _provider, metrics) = signaling_metrics();
+        let legal_vote: ModuleId = "legal_vote".parse().expect("valid module id");
+        let training_participation_report: ModuleId = "training_participation_report"
+            .parse()
+            .expect("valid module id");
+
+        let before = std::time::SystemTime::now()
+            .duration_since(std::time::SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as f64;
+        metrics.record_report_generation(&legal_vote, true);
+        metrics.record_report_generation(&training_participation_report, false);
+
+        let families = registry.gather();
+        let family = family(
+            &families,
+            "signaling_report_generation_last_success_timestamp",
+        )
+        .expect("last successful report generation is recorded");
+
+        let legal_vote_timestamps = metrics_with_labels(family, &[("module", "legal_vote")])
+            .map(|metric| metric.gauge().value())
+            .collect::<Vec<_>>();
+        assert_eq!(legal_vote_timestamps.len(), 1);
+        assert!(legal_vote_timestamps[0] >= before);
+
+        // Failed generations do not update the timestamp
+        assert_eq!(
+            metrics_with_labels(family, &[("module", "training_participation_report")]).count(),
+            0
+        );
+    }
+
+    #[test]
+    fn chat_messages_are_recorded_per_scope() {
+        let (registry, _provider, metrics) = signaling_metrics();
+
+        metrics.record_chat_message("global", 10);
+        metrics.record_chat_message("global", 20);
+        metrics.record_chat_message("private", 300);
+
+        let families = registry.gather();
+
+        let family_count =
+            family(&families, "signaling_chat_messages_count").expect("chat messages are counted");
+        for (scope, expected) in [("global", 2.0), ("private", 1.0), ("group", 0.0)] {
+            assert_eq!(
+                metrics_with_labels(family_count, &[("scope", scope)])
+                    .map(|metric| metric.counter().value())
+                    .sum::<f64>(),
+                expected
+            );
+        }
+
+        let family_size = family(&families, "signaling_chat_message_size")
+            .expect("chat message sizes are recorded");
+        for (scope, expected_count, expected_sum) in [("global", 2, 30.0), ("private", 1, 300.0)] {
+            let histograms = metrics_with_labels(family_size, &[("scope", scope)])
+                .map(|metric| metric.histogram())
+                .collect::<Vec<_>>();
+            assert_eq!(histograms.len(), 1);
+            assert_eq!(histograms[0].sample_count(), expected_count);
+            assert_eq!(histograms[0].sample_sum(), expected_sum);
+        }
+    }
+}
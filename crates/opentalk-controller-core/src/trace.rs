@@ -2,12 +2,18 @@
 //
 // SPDX-License-Identifier: EUPL-1.2
 
+use std::{
+    io::{self, Stdout, Write},
+    sync::Arc,
+};
+
 use actix_web::{
     Error, HttpMessage,
     dev::{ServiceRequest, ServiceResponse},
     http::header::USER_AGENT,
 };
-use opentalk_controller_settings::{Logging, LoggingOltpTracing};
+use opentalk_controller_settings::{Logging, LoggingOltpTracing, settings_file::PiiRedaction};
+use opentalk_log::{RedactionMode, Redactor};
 use opentelemetry::{KeyValue, trace::TracerProvider as _};
 use opentelemetry_otlp::{SpanExporter, WithExportConfig as _};
 use opentelemetry_sdk::{
@@ -20,7 +26,11 @@ use tracing_actix_web::{RequestId, RootSpanBuilder};
 use tracing_opentelemetry::OpenTelemetryLayer;
 use tracing_subscriber::{
     EnvFilter, Registry,
-    fmt::Layer,
+    fmt::{
+        Layer, MakeWriter,
+        format::{DefaultFields, Format},
+        writer::BoxMakeWriter,
+    },
     layer::{Layered, SubscriberExt},
     util::SubscriberInitExt,
 };
@@ -43,8 +53,13 @@ pub fn init(settings: &Logging) -> Result<()> {
     // The filter is created from environment (RUST_LOG) and config file
     let filter = create_filter(settings)?;
 
-    // FMT layer prints the trace events into stdout
-    let fmt = tracing_subscriber::fmt::Layer::default();
+    // FMT layer prints the trace events into stdout, identifiers are redacted if configured
+    let writer = match settings.pii_redaction {
+        PiiRedaction::Off => BoxMakeWriter::new(io::stdout),
+        PiiRedaction::Redact => BoxMakeWriter::new(RedactingStdout::new(RedactionMode::Redact)),
+        PiiRedaction::Hash => BoxMakeWriter::new(RedactingStdout::new(RedactionMode::Hash)),
+    };
+    let fmt = tracing_subscriber::fmt::Layer::default().with_writer(writer);
 
     // If opentelemetry is enabled install that layer
     let mut tracing_layer = None;
@@ -62,7 +77,7 @@ pub fn init(settings: &Logging) -> Result<()> {
     Ok(())
 }
 
-type SubscriberLayer = Layer<Layered<EnvFilter, Registry>>;
+type SubscriberLayer = Layer<Layered<EnvFilter, Registry>, DefaultFields, Format, BoxMakeWriter>;
 type Subscriber = Layered<EnvFilter, Registry>;
 
 fn init_tracing_layer(
@@ -105,6 +120,53 @@ fn init_tracing_layer(
     let tracer = tracer_provider.tracer("tracing-otel-subscriber");
     Ok(OpenTelemetryLayer::new(tracer))
 }
+/// Writes log lines to stdout after redacting the identifiers contained in them
+///
+/// The redaction is applied to the fully formatted line, so it also covers the messages of
+/// error reports and the fields of spans.
+struct RedactingStdout {
+    redactor: Arc<Redactor>,
+}
+
+impl RedactingStdout {
+    fn new(mode: RedactionMode) -> Self {
+        Self {
+            redactor: Arc::new(Redactor::new(mode)),
+        }
+    }
+}
+
+impl<'a> MakeWriter<'a> for RedactingStdout {
+    type Writer = RedactingWriter;
+
+    fn make_writer(&'a self) -> Self::Writer {
+        RedactingWriter {
+            redactor: self.redactor.clone(),
+            stdout: io::stdout(),
+        }
+    }
+}
+
+struct RedactingWriter {
+    redactor: Arc<Redactor>,
+    stdout: Stdout,
+}
+
+impl Write for RedactingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        // The fmt layer writes every event with a single call, so identifiers are never split
+        let line = String::from_utf8_lossy(buf);
+        self.stdout
+            .write_all(self.redactor.redact(&line).as_bytes())?;
+
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.stdout.flush()
+    }
+}
+
 /// Create the logging filter
 ///
 /// The filter is a combination of the values from the RUST_LOG environment variable, the config file and
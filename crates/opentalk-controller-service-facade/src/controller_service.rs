@@ -8,7 +8,7 @@ use bytes::Bytes;
 use futures_core::Stream;
 use opentalk_signaling_core::{
     ObjectStorageError,
-    assets::{ByStreamExt, NewAssetFileName},
+    assets::{AssetDownload, IfNoneMatchCondition, NewAssetFileName, RangeSpec},
 };
 use opentalk_types_api_v1::{
     assets::{AssetResource, AssetSortingQuery},
@@ -29,8 +29,10 @@ use opentalk_types_api_v1::{
         by_room_id::{
             GetRoomEventResponseBody, PostRoomsRoomserverStartInvitedRequestBody,
             PostRoomsRoomserverStartRequestBody, PostRoomsStartInvitedRequestBody,
-            PostRoomsStartRequestBody, RoomsStartResponseBody, RoomserverStartResponseBody,
+            PostRoomsStartRequestBody, PostRoomsTransferOwnershipRequestBody,
+            RoomsStartResponseBody, RoomserverStartResponseBody,
             assets::RoomsByRoomIdAssetsGetResponseBody,
+            capabilities::GetRoomCapabilitiesResponseBody,
             invites::{
                 GetRoomsInvitesResponseBody, InviteResource, PostInviteRequestBody,
                 PostInviteVerifyRequestBody, PostInviteVerifyResponseBody, PutInviteRequestBody,
@@ -39,7 +41,8 @@ use opentalk_types_api_v1::{
             streaming_targets::{
                 GetRoomStreamingTargetResponseBody, GetRoomStreamingTargetsResponseBody,
                 PatchRoomStreamingTargetRequestBody, PatchRoomStreamingTargetResponseBody,
-                PostRoomStreamingTargetResponseBody, RoomAndStreamingTargetId,
+                PostRoomStreamingTargetResponseBody, PutRoomStreamingTargetsOrderRequestBody,
+                RoomAndStreamingTargetId,
             },
         },
     },
@@ -56,6 +59,7 @@ use opentalk_types_common::{
     assets::AssetId,
     email::EmailAddress,
     events::EventId,
+    module_resources::ModuleResourceId,
     modules::ModuleId,
     rooms::{RoomId, RoomPassword, invite_codes::InviteCode},
     shared_folders::SharedFolder,
@@ -167,6 +171,25 @@ impl OpenTalkControllerService {
             .await
     }
 
+    /// Restore a room that was marked as deleted.
+    pub async fn restore_room(&self, room_id: RoomId) -> Result<RoomResource, ApiError> {
+        self.backend.read().await.restore_room(room_id).await
+    }
+
+    /// Transfer the ownership of a room to another user
+    pub async fn transfer_room_ownership(
+        &self,
+        current_user: RequestUser,
+        room_id: RoomId,
+        body: PostRoomsTransferOwnershipRequestBody,
+    ) -> Result<RoomResource, ApiError> {
+        self.backend
+            .read()
+            .await
+            .transfer_room_ownership(current_user, room_id, body)
+            .await
+    }
+
     /// Get a room
     pub async fn get_room(&self, room_id: &RoomId) -> Result<RoomResource, ApiError> {
         self.backend.read().await.get_room(room_id).await
@@ -177,6 +200,46 @@ impl OpenTalkControllerService {
         self.backend.read().await.get_room_tariff(room_id).await
     }
 
+    /// Get the capabilities of a room, derived from its tariff and the current settings
+    pub async fn get_room_capabilities(
+        &self,
+        room_id: &RoomId,
+    ) -> Result<GetRoomCapabilitiesResponseBody, ApiError> {
+        self.backend
+            .read()
+            .await
+            .get_room_capabilities(room_id)
+            .await
+    }
+
+    /// Get the state a single signaling module keeps for a room
+    pub async fn get_room_module_state(
+        &self,
+        current_user: RequestUser,
+        room_id: RoomId,
+        module_id: ModuleId,
+    ) -> Result<serde_json::Value, ApiError> {
+        self.backend
+            .read()
+            .await
+            .get_room_module_state(current_user, room_id, module_id)
+            .await
+    }
+
+    /// Get the stored protocol of a legal vote in a room
+    pub async fn get_room_legal_vote_protocol(
+        &self,
+        current_user: RequestUser,
+        room_id: RoomId,
+        legal_vote_id: ModuleResourceId,
+    ) -> Result<serde_json::Value, ApiError> {
+        self.backend
+            .read()
+            .await
+            .get_room_legal_vote_protocol(current_user, room_id, legal_vote_id)
+            .await
+    }
+
     /// Get a room's event
     pub async fn get_room_event(
         &self,
@@ -247,6 +310,11 @@ impl OpenTalkControllerService {
         self.backend.read().await.start_recording(body).await
     }
 
+    /// Requests the recorders of a room to stop the running recording
+    pub async fn stop_recording(&self, room_id: RoomId) -> Result<(), ApiError> {
+        self.backend.read().await.stop_recording(room_id).await
+    }
+
     /// Starts a signaling session for call-in
     pub async fn start_call_in(
         &self,
@@ -269,15 +337,20 @@ impl OpenTalkControllerService {
     }
 
     /// Get a specific asset inside a room.
+    ///
+    /// The asset contents are only returned if they don't match the `if_none_match` condition.
+    /// If a `range` is requested, only that part of the contents is returned.
     pub async fn get_room_asset(
         &self,
         room_id: RoomId,
         asset_id: AssetId,
-    ) -> Result<ByStreamExt, ApiError> {
+        if_none_match: Option<IfNoneMatchCondition>,
+        range: Option<Vec<RangeSpec>>,
+    ) -> Result<AssetDownload, ApiError> {
         self.backend
             .read()
             .await
-            .get_room_asset(room_id, asset_id)
+            .get_room_asset(room_id, asset_id, if_none_match, range)
             .await
     }
 
@@ -711,6 +784,33 @@ impl OpenTalkControllerService {
             .await
     }
 
+    /// Changes the order of the streaming targets of a room
+    pub async fn put_streaming_targets_order(
+        &self,
+        user_id: UserId,
+        room_id: RoomId,
+        order: PutRoomStreamingTargetsOrderRequestBody,
+    ) -> Result<GetRoomStreamingTargetsResponseBody, ApiError> {
+        self.backend
+            .read()
+            .await
+            .put_streaming_targets_order(user_id, room_id, order)
+            .await
+    }
+
+    /// Makes a streaming target the primary of its room
+    pub async fn put_primary_streaming_target(
+        &self,
+        user_id: UserId,
+        path_params: RoomAndStreamingTargetId,
+    ) -> Result<GetRoomStreamingTargetResponseBody, ApiError> {
+        self.backend
+            .read()
+            .await
+            .put_primary_streaming_target(user_id, path_params)
+            .await
+    }
+
     /// Deletes a streaming target
     pub async fn delete_streaming_target(
         &self,
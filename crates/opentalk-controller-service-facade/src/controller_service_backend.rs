@@ -7,7 +7,7 @@ use bytes::Bytes;
 use futures_core::Stream;
 use opentalk_signaling_core::{
     ObjectStorageError,
-    assets::{ByStreamExt, NewAssetFileName},
+    assets::{AssetDownload, IfNoneMatchCondition, NewAssetFileName, RangeSpec},
 };
 use opentalk_types_api_v1::{
     assets::{AssetResource, AssetSortingQuery},
@@ -28,8 +28,10 @@ use opentalk_types_api_v1::{
         by_room_id::{
             GetRoomEventResponseBody, PostRoomsRoomserverStartInvitedRequestBody,
             PostRoomsRoomserverStartRequestBody, PostRoomsStartInvitedRequestBody,
-            PostRoomsStartRequestBody, RoomsStartResponseBody, RoomserverStartResponseBody,
+            PostRoomsStartRequestBody, PostRoomsTransferOwnershipRequestBody,
+            RoomsStartResponseBody, RoomserverStartResponseBody,
             assets::RoomsByRoomIdAssetsGetResponseBody,
+            capabilities::GetRoomCapabilitiesResponseBody,
             invites::{
                 GetRoomsInvitesResponseBody, InviteResource, PostInviteRequestBody,
                 PostInviteVerifyRequestBody, PostInviteVerifyResponseBody, PutInviteRequestBody,
@@ -38,7 +40,8 @@ use opentalk_types_api_v1::{
             streaming_targets::{
                 GetRoomStreamingTargetResponseBody, GetRoomStreamingTargetsResponseBody,
                 PatchRoomStreamingTargetRequestBody, PatchRoomStreamingTargetResponseBody,
-                PostRoomStreamingTargetResponseBody, RoomAndStreamingTargetId,
+                PostRoomStreamingTargetResponseBody, PutRoomStreamingTargetsOrderRequestBody,
+                RoomAndStreamingTargetId,
             },
         },
     },
@@ -55,6 +58,7 @@ use opentalk_types_common::{
     assets::AssetId,
     email::EmailAddress,
     events::EventId,
+    module_resources::ModuleResourceId,
     modules::ModuleId,
     rooms::{RoomId, RoomPassword, invite_codes::InviteCode},
     shared_folders::SharedFolder,
@@ -106,12 +110,45 @@ pub trait OpenTalkControllerServiceBackend: Send + Sync {
         force_delete_reference_if_external_services_fail: bool,
     ) -> Result<(), ApiError>;
 
+    /// Restore a room that was marked as deleted.
+    async fn restore_room(&self, room_id: RoomId) -> Result<RoomResource, ApiError>;
+
+    /// Transfer the ownership of a room to another user
+    async fn transfer_room_ownership(
+        &self,
+        current_user: RequestUser,
+        room_id: RoomId,
+        body: PostRoomsTransferOwnershipRequestBody,
+    ) -> Result<RoomResource, ApiError>;
+
     /// Get a room
     async fn get_room(&self, room_id: &RoomId) -> Result<RoomResource, ApiError>;
 
     /// Get a room's tariff
     async fn get_room_tariff(&self, room_id: &RoomId) -> Result<TariffResource, ApiError>;
 
+    /// Get the capabilities of a room, derived from its tariff and the current settings
+    async fn get_room_capabilities(
+        &self,
+        room_id: &RoomId,
+    ) -> Result<GetRoomCapabilitiesResponseBody, ApiError>;
+
+    /// Get the state a single signaling module keeps for a room
+    async fn get_room_module_state(
+        &self,
+        current_user: RequestUser,
+        room_id: RoomId,
+        module_id: ModuleId,
+    ) -> Result<serde_json::Value, ApiError>;
+
+    /// Get the stored protocol of a legal vote in a room
+    async fn get_room_legal_vote_protocol(
+        &self,
+        current_user: RequestUser,
+        room_id: RoomId,
+        legal_vote_id: ModuleResourceId,
+    ) -> Result<serde_json::Value, ApiError>;
+
     /// Get a room's event
     async fn get_room_event(&self, room_id: &RoomId) -> Result<GetRoomEventResponseBody, ApiError>;
 
@@ -151,6 +188,9 @@ pub trait OpenTalkControllerServiceBackend: Send + Sync {
         body: PostRecordingStartRequestBody,
     ) -> Result<PostServiceStartResponseBody, ApiError>;
 
+    /// Requests the recorders of a room to stop the running recording
+    async fn stop_recording(&self, room_id: RoomId) -> Result<(), ApiError>;
+
     /// Starts a signaling session for call-in
     async fn start_call_in(
         &self,
@@ -169,7 +209,9 @@ pub trait OpenTalkControllerServiceBackend: Send + Sync {
         &self,
         room_id: RoomId,
         asset_id: AssetId,
-    ) -> Result<ByStreamExt, ApiError>;
+        if_none_match: Option<IfNoneMatchCondition>,
+        range: Option<Vec<RangeSpec>>,
+    ) -> Result<AssetDownload, ApiError>;
 
     /// Create an asset for a room from an uploaded file.
     async fn create_room_asset(
@@ -415,6 +457,21 @@ pub trait OpenTalkControllerServiceBackend: Send + Sync {
         update_streaming_target: PatchRoomStreamingTargetRequestBody,
     ) -> Result<PatchRoomStreamingTargetResponseBody, ApiError>;
 
+    /// Changes the order of the streaming targets of a room
+    async fn put_streaming_targets_order(
+        &self,
+        user_id: UserId,
+        room_id: RoomId,
+        order: PutRoomStreamingTargetsOrderRequestBody,
+    ) -> Result<GetRoomStreamingTargetsResponseBody, ApiError>;
+
+    /// Makes a streaming target the primary of its room
+    async fn put_primary_streaming_target(
+        &self,
+        user_id: UserId,
+        path_params: RoomAndStreamingTargetId,
+    ) -> Result<GetRoomStreamingTargetResponseBody, ApiError>;
+
     /// Deletes a streaming target
     async fn delete_streaming_target(
         &self,
@@ -8,7 +8,10 @@ use opentalk_controller_utils::CaptureApiError;
 use opentalk_db_storage::assets::Asset;
 use opentalk_signaling_core::{
     ChunkFormat, ObjectStorageError,
-    assets::{ByStreamExt, NewAssetFileName, delete_asset, get_asset, save_asset},
+    assets::{
+        AssetDownload, IfNoneMatchCondition, NewAssetFileName, RangeSpec, delete_asset, get_asset,
+        get_asset_e_tag, get_asset_range, resolve_ranges, save_asset,
+    },
 };
 use opentalk_types_api_v1::{
     assets::AssetResource, pagination::PagePaginationQuery,
@@ -44,14 +47,40 @@ impl ControllerBackend {
         &self,
         room_id: RoomId,
         asset_id: AssetId,
-    ) -> Result<ByStreamExt, CaptureApiError> {
+        if_none_match: Option<IfNoneMatchCondition>,
+        range: Option<Vec<RangeSpec>>,
+    ) -> Result<AssetDownload, CaptureApiError> {
         let mut conn = self.db.get_conn().await?;
 
         let asset = Asset::get(&mut conn, asset_id, room_id).await?;
 
-        let stream = get_asset(&self.storage, &asset.id).await?;
+        let e_tag = get_asset_e_tag(&self.storage, &asset.id).await?;
 
-        Ok(stream)
+        if if_none_match.is_some_and(|condition| condition.matches(&e_tag)) {
+            return Ok(AssetDownload::NotModified { e_tag });
+        }
+
+        if let Some(range) = range {
+            // The size is taken from the upload and is never negative
+            let size = u64::try_from(asset.size).unwrap_or_default();
+
+            let Some(range) = resolve_ranges(&range, size) else {
+                return Ok(AssetDownload::RangeNotSatisfiable { size });
+            };
+
+            let data = get_asset_range(&self.storage, &asset.id, range).await?;
+
+            return Ok(AssetDownload::PartialContent {
+                e_tag,
+                range,
+                size,
+                data,
+            });
+        }
+
+        let data = get_asset(&self.storage, &asset.id).await?;
+
+        Ok(AssetDownload::Content { e_tag, data })
     }
 
     pub(crate) async fn create_room_asset(
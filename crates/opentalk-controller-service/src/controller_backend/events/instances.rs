@@ -4,14 +4,21 @@
 
 //! Handles event instances
 
+use std::collections::BTreeMap;
+
 use chrono::{DateTime, Utc};
 use kustos::policies_builder::PoliciesBuilder;
 use opentalk_controller_service_facade::RequestUser;
+use opentalk_controller_settings::Settings;
 use opentalk_controller_utils::{CaptureApiError, event::EventExt};
+use opentalk_database::DbConnection;
 use opentalk_db_storage::{
     events::{Event, EventException, EventExceptionKind, NewEventException, UpdateEventException},
     invites::Invite,
+    rooms::Room,
+    sip_configs::SipConfig,
     streaming_targets::get_room_streaming_targets,
+    tariffs::Tariff,
     tenants::Tenant,
     users::User,
 };
@@ -27,6 +34,7 @@ use opentalk_types_api_v1::{
 };
 use opentalk_types_common::{
     events::{EventId, invites::EventInviteStatus},
+    rooms::RoomId,
     shared_folders::SharedFolder,
     time::DateTimeTz,
     training_participation_report::TrainingParticipationReportParameterSet,
@@ -126,6 +134,9 @@ impl ControllerBackend {
 
         let exceptions = EventException::get_all_for_event(&mut conn, event_id, &datetimes).await?;
 
+        let exception_rooms =
+            get_exception_rooms(&settings, &mut conn, &exceptions, &tariff).await?;
+
         let users = GetUserProfilesBatched::new()
             .add(&event)
             .add(&exceptions)
@@ -161,6 +172,7 @@ impl ControllerBackend {
                 is_favorite,
                 exception,
                 room.clone(),
+                &exception_rooms,
                 datetime.into(),
                 invitees.clone(),
                 invitees_truncated,
@@ -244,6 +256,9 @@ impl ControllerBackend {
         let exception =
             EventException::get_for_event(&mut conn, event_id, instance_id.into()).await?;
 
+        let exception_rooms =
+            get_exception_rooms(&settings, &mut conn, exception.as_slice(), &tariff).await?;
+
         let users = GetUserProfilesBatched::new()
             .add(&event)
             .add(&exception)
@@ -269,6 +284,7 @@ impl ControllerBackend {
             is_favorite,
             exception,
             room,
+            &exception_rooms,
             instance_id,
             invitees,
             invitees_truncated,
@@ -328,6 +344,10 @@ impl ControllerBackend {
 
         _ = verify_recurrence_date(&event, instance_id.into())?;
 
+        if let Some(Some(room_id)) = patch.room {
+            verify_exception_room(&mut conn, &event, room_id).await?;
+        }
+
         let exception = if let Some(exception) =
             EventException::get_for_event(&mut conn, event_id, instance_id.into()).await?
         {
@@ -362,6 +382,7 @@ impl ControllerBackend {
                 starts_at_tz: patch.starts_at.map(|dt| Some(dt.timezone)),
                 ends_at: patch.ends_at.map(|dt| Some(dt.to_datetime_tz())),
                 ends_at_tz: patch.ends_at.map(|dt| Some(dt.timezone)),
+                room_id: patch.room,
             };
 
             update_exception.apply(&mut conn, exception.id).await?
@@ -395,6 +416,7 @@ impl ControllerBackend {
                 starts_at_tz: patch.starts_at.map(|dt| dt.timezone),
                 ends_at: patch.ends_at.map(|dt| dt.to_datetime_tz()),
                 ends_at_tz: patch.ends_at.map(|dt| dt.timezone),
+                room_id: patch.room.flatten(),
             };
 
             new_exception.insert(&mut conn).await?
@@ -409,6 +431,15 @@ impl ControllerBackend {
             .fetch(&settings, &mut conn)
             .await?;
 
+        // Notify about and link to the room the instance takes place in
+        let (room, sip_config) = match exception.room_id {
+            Some(room_id) => (
+                Room::get(&mut conn, room_id).await?,
+                SipConfig::get_optional_by_room(&mut conn, room_id).await?,
+            ),
+            None => (room, sip_config),
+        };
+
         let event_room_info =
             EventRoomInfo::from_room(&settings, room.clone(), sip_config.clone(), &tariff);
 
@@ -478,6 +509,7 @@ impl ControllerBackend {
             is_favorite,
             Some(exception),
             event_room_info,
+            &BTreeMap::new(),
             instance_id,
             invitees,
             invitees_truncated,
@@ -514,7 +546,8 @@ fn create_event_instance(
     invite_status: EventInviteStatus,
     is_favorite: bool,
     exception: Option<EventException>,
-    room: EventRoomInfo,
+    mut room: EventRoomInfo,
+    exception_rooms: &BTreeMap<RoomId, EventRoomInfo>,
     instance_id: InstanceId,
     invitees: Vec<EventInvitee>,
     invitees_truncated: bool,
@@ -552,6 +585,13 @@ fn create_event_instance(
             exception.ends_at.map(InstanceId::from),
         );
         patch(&mut instance_ends_at_tz, exception.ends_at_tz);
+        patch(
+            &mut room,
+            exception
+                .room_id
+                .and_then(|room_id| exception_rooms.get(&room_id))
+                .cloned(),
+        );
     }
 
     let created_by = users.get(event.created_by);
@@ -595,6 +635,56 @@ fn patch<T>(dst: &mut T, value: Option<T>) {
     }
 }
 
+/// Load the rooms which the exceptions moved their instances to, keyed by the room id.
+async fn get_exception_rooms(
+    settings: &Settings,
+    conn: &mut DbConnection,
+    exceptions: &[EventException],
+    tariff: &Tariff,
+) -> opentalk_database::Result<BTreeMap<RoomId, EventRoomInfo>> {
+    let mut rooms = BTreeMap::new();
+
+    for room_id in exceptions.iter().filter_map(|exception| exception.room_id) {
+        if rooms.contains_key(&room_id) {
+            continue;
+        }
+
+        let room = Room::get(conn, room_id).await?;
+        let sip_config = SipConfig::get_optional_by_room(conn, room_id).await?;
+
+        rooms.insert(
+            room_id,
+            EventRoomInfo::from_room(settings, room, sip_config, tariff),
+        );
+    }
+
+    Ok(rooms)
+}
+
+/// Verify that an instance can be moved to the room.
+///
+/// Only rooms of the event creator which are not in the trash can be used, so the instance keeps
+/// the same owner and tariff as the rest of the event.
+async fn verify_exception_room(
+    conn: &mut DbConnection,
+    event: &Event,
+    room_id: RoomId,
+) -> Result<(), CaptureApiError> {
+    let room = Room::get(conn, room_id).await?;
+
+    if room.created_by != event.created_by
+        || room.tenant_id != event.tenant_id
+        || room.deleted_at.is_some()
+    {
+        return Err(ApiError::unprocessable_entity()
+            .with_code("invalid_room")
+            .with_message("room must be an active room of the event creator")
+            .into());
+    }
+
+    Ok(())
+}
+
 fn verify_recurrence_date(
     event: &Event,
     requested_dt: DateTime<Utc>,
@@ -625,7 +715,8 @@ mod tests {
     use std::time::SystemTime;
 
     use chrono_tz::Tz;
-    use opentalk_test_util::assert_eq_json;
+    use opentalk_db_storage::{events::NewEvent, rooms::Room};
+    use opentalk_test_util::{ROOM_ID, assert_eq_json, database::DatabaseContext};
     use opentalk_types_api_v1::{
         events::{EventInviteeProfile, PublicInviteUserProfile},
         users::PublicUserProfile,
@@ -636,9 +727,14 @@ mod tests {
         time::{TimeZone, Timestamp},
         users::{UserId, UserInfo},
     };
+    use serial_test::serial;
 
     use super::*;
 
+    fn is_invalid_room(error: &CaptureApiError) -> bool {
+        format!("{:?}", error.0).contains("invalid_room")
+    }
+
     #[test]
     fn event_instance_serialize() {
         let unix_epoch: Timestamp = SystemTime::UNIX_EPOCH.into();
@@ -769,4 +865,73 @@ mod tests {
             }
         );
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn exception_room_must_be_active_room_of_event_creator() {
+        let db_ctx = DatabaseContext::new(true).await;
+        let mut conn = db_ctx.db.get_conn().await.unwrap();
+
+        let owner = db_ctx.create_test_user(0, vec![]).await.unwrap();
+        let other_user = db_ctx.create_test_user(1, vec![]).await.unwrap();
+        let event_room = db_ctx
+            .create_test_room(ROOM_ID, owner.id, false)
+            .await
+            .unwrap();
+        let event = NewEvent {
+            title: "Test Event".parse().expect("valid event title"),
+            description: "Test Event".parse().expect("valid event description"),
+            room: event_room.id,
+            created_by: owner.id,
+            updated_by: owner.id,
+            is_time_independent: true,
+            is_all_day: None,
+            starts_at: None,
+            starts_at_tz: None,
+            ends_at: None,
+            ends_at_tz: None,
+            duration_secs: None,
+            is_recurring: None,
+            recurrence_pattern: None,
+            is_adhoc: false,
+            tenant_id: event_room.tenant_id,
+            show_meeting_details: false,
+        }
+        .insert(&mut conn)
+        .await
+        .unwrap();
+
+        // Another active room of the event creator can be used
+        let owner_room = db_ctx
+            .create_test_room(RoomId::generate(), owner.id, false)
+            .await
+            .unwrap();
+        verify_exception_room(&mut conn, &event, owner_room.id)
+            .await
+            .unwrap();
+
+        // Rooms of other users are rejected
+        let other_room = db_ctx
+            .create_test_room(RoomId::generate(), other_user.id, false)
+            .await
+            .unwrap();
+        let error = verify_exception_room(&mut conn, &event, other_room.id)
+            .await
+            .unwrap_err();
+        assert!(is_invalid_room(&error));
+
+        // Rooms in the trash are rejected
+        let deleted_room = db_ctx
+            .create_test_room(RoomId::generate(), owner.id, false)
+            .await
+            .unwrap();
+        let _ = Room::mark_deleted(&mut conn, deleted_room.id)
+            .await
+            .unwrap();
+        assert!(
+            verify_exception_room(&mut conn, &event, deleted_room.id)
+                .await
+                .is_err()
+        );
+    }
 }
@@ -44,7 +44,7 @@ use opentalk_types_common::{
     rooms::RoomId,
     shared_folders::SharedFolder,
     streaming::RoomStreamingTarget,
-    users::UserId,
+    users::{Language, UserId},
 };
 use snafu::Report;
 
@@ -691,6 +691,11 @@ async fn create_email_event_invite(
             shared_folder,
             streaming_targets,
         } => {
+            // The language of email-only invitees is unknown, use the one requested by the inviter
+            let language = email_invite
+                .language
+                .unwrap_or_else(|| settings.defaults.user_language.clone());
+
             create_invite_to_non_matching_email(
                 settings,
                 db,
@@ -704,6 +709,7 @@ async fn create_email_event_invite(
                 sip_config,
                 email,
                 email_invite.role,
+                language,
                 shared_folder,
                 streaming_targets,
             )
@@ -729,6 +735,7 @@ async fn create_invite_to_non_matching_email(
     sip_config: Option<SipConfig>,
     email: EmailAddress,
     role: EmailInviteRole,
+    language: Language,
     shared_folder: Option<SharedFolder>,
     streaming_targets: Vec<RoomStreamingTarget>,
 ) -> Result<bool, CaptureApiError> {
@@ -777,6 +784,7 @@ async fn create_invite_to_non_matching_email(
                             room,
                             sip_config,
                             invitee_user,
+                            language,
                             shared_folder,
                             streaming_targets,
                         )
@@ -816,6 +824,7 @@ async fn create_invite_to_non_matching_email(
                                 room,
                                 sip_config,
                                 invitee_email.as_ref(),
+                                language,
                                 invite.id.to_string(),
                                 shared_folder,
                                 streaming_targets,
@@ -226,6 +226,8 @@ impl IntoInviteResource for Invite {
             room_id: invite.room,
             active: invite.active,
             expiration: invite.expiration,
+            usage_count: u64::try_from(invite.usage_count).unwrap_or_default(),
+            last_used: invite.last_used_at,
         }
     }
 }
@@ -29,8 +29,8 @@ use opentalk_database::Db;
 use opentalk_keycloak_admin::KeycloakAdminClient;
 use opentalk_roomserver_client::Client as RoomServerClient;
 use opentalk_signaling_core::{
-    ExchangeHandle, ObjectStorage, ObjectStorageError, VolatileStorage,
-    assets::{ByStreamExt, NewAssetFileName},
+    ExchangeHandle, ModuleStateSummarizer, ObjectStorage, ObjectStorageError, VolatileStorage,
+    assets::{AssetDownload, IfNoneMatchCondition, NewAssetFileName, RangeSpec},
 };
 use opentalk_types_api_v1::{
     assets::{AssetResource, AssetSortingQuery},
@@ -51,8 +51,10 @@ use opentalk_types_api_v1::{
         by_room_id::{
             GetRoomEventResponseBody, PostRoomsRoomserverStartInvitedRequestBody,
             PostRoomsRoomserverStartRequestBody, PostRoomsStartInvitedRequestBody,
-            PostRoomsStartRequestBody, RoomsStartResponseBody, RoomserverStartResponseBody,
+            PostRoomsStartRequestBody, PostRoomsTransferOwnershipRequestBody,
+            RoomsStartResponseBody, RoomserverStartResponseBody,
             assets::RoomsByRoomIdAssetsGetResponseBody,
+            capabilities::GetRoomCapabilitiesResponseBody,
             invites::{
                 GetRoomsInvitesResponseBody, InviteResource, PostInviteRequestBody,
                 PostInviteVerifyRequestBody, PostInviteVerifyResponseBody, PutInviteRequestBody,
@@ -61,7 +63,8 @@ use opentalk_types_api_v1::{
             streaming_targets::{
                 GetRoomStreamingTargetResponseBody, GetRoomStreamingTargetsResponseBody,
                 PatchRoomStreamingTargetRequestBody, PatchRoomStreamingTargetResponseBody,
-                PostRoomStreamingTargetResponseBody, RoomAndStreamingTargetId,
+                PostRoomStreamingTargetResponseBody, PutRoomStreamingTargetsOrderRequestBody,
+                RoomAndStreamingTargetId,
             },
         },
     },
@@ -79,6 +82,7 @@ use opentalk_types_common::{
     email::EmailAddress,
     events::EventId,
     features::FeatureId,
+    module_resources::ModuleResourceId,
     modules::ModuleId,
     rooms::{RoomId, RoomPassword, invite_codes::InviteCode},
     shared_folders::SharedFolder,
@@ -91,7 +95,7 @@ pub use crate::controller_backend::{
     events::shared_folder::{delete_shared_folders, put_shared_folder},
     rooms::RoomsPoliciesBuilderExt,
 };
-use crate::services::MailService;
+use crate::services::{MailService, WebhookService};
 
 /// The default [`OpenTalkControllerServiceBackend`] implementation.
 pub struct ControllerBackend {
@@ -105,7 +109,9 @@ pub struct ControllerBackend {
     mail_service: Arc<Option<MailService>>,
     user_search_client: Arc<Option<KeycloakAdminClient>>,
     module_features: BTreeMap<ModuleId, BTreeSet<FeatureId>>,
+    module_state_summarizers: BTreeMap<ModuleId, ModuleStateSummarizer>,
     roomserver_client: Option<RoomServerClient>,
+    webhook: WebhookService,
 }
 
 impl ControllerBackend {
@@ -122,7 +128,9 @@ impl ControllerBackend {
         mail_service: Arc<Option<MailService>>,
         user_search_client: Arc<Option<KeycloakAdminClient>>,
         module_features: BTreeMap<ModuleId, BTreeSet<FeatureId>>,
+        module_state_summarizers: BTreeMap<ModuleId, ModuleStateSummarizer>,
         roomserver_client: Option<RoomServerClient>,
+        webhook: WebhookService,
     ) -> Self {
         Self {
             settings_provider,
@@ -135,7 +143,9 @@ impl ControllerBackend {
             mail_service,
             user_search_client,
             module_features,
+            module_state_summarizers,
             roomserver_client,
+            webhook,
         }
     }
 }
@@ -213,6 +223,21 @@ impl OpenTalkControllerServiceBackend for ControllerBackend {
             .await?)
     }
 
+    async fn restore_room(&self, room_id: RoomId) -> Result<RoomResource, ApiError> {
+        Ok(self.restore_room(room_id).await?)
+    }
+
+    async fn transfer_room_ownership(
+        &self,
+        current_user: RequestUser,
+        room_id: RoomId,
+        body: PostRoomsTransferOwnershipRequestBody,
+    ) -> Result<RoomResource, ApiError> {
+        Ok(self
+            .transfer_room_ownership(current_user, room_id, body)
+            .await?)
+    }
+
     async fn get_room(&self, room_id: &RoomId) -> Result<RoomResource, ApiError> {
         Ok(self.get_room(room_id).await?)
     }
@@ -221,6 +246,35 @@ impl OpenTalkControllerServiceBackend for ControllerBackend {
         Ok(self.get_room_tariff(room_id).await?)
     }
 
+    async fn get_room_capabilities(
+        &self,
+        room_id: &RoomId,
+    ) -> Result<GetRoomCapabilitiesResponseBody, ApiError> {
+        Ok(self.get_room_capabilities(room_id).await?)
+    }
+
+    async fn get_room_module_state(
+        &self,
+        current_user: RequestUser,
+        room_id: RoomId,
+        module_id: ModuleId,
+    ) -> Result<serde_json::Value, ApiError> {
+        Ok(self
+            .get_room_module_state(current_user, room_id, module_id)
+            .await?)
+    }
+
+    async fn get_room_legal_vote_protocol(
+        &self,
+        current_user: RequestUser,
+        room_id: RoomId,
+        legal_vote_id: ModuleResourceId,
+    ) -> Result<serde_json::Value, ApiError> {
+        Ok(self
+            .get_room_legal_vote_protocol(current_user, room_id, legal_vote_id)
+            .await?)
+    }
+
     async fn get_room_event(&self, room_id: &RoomId) -> Result<GetRoomEventResponseBody, ApiError> {
         Ok(self.get_room_event(room_id).await?)
     }
@@ -270,6 +324,10 @@ impl OpenTalkControllerServiceBackend for ControllerBackend {
         Ok(self.start_recording(body).await?)
     }
 
+    async fn stop_recording(&self, room_id: RoomId) -> Result<(), ApiError> {
+        Ok(self.stop_recording(room_id).await?)
+    }
+
     async fn start_call_in(
         &self,
         request: PostCallInStartRequestBody,
@@ -289,8 +347,12 @@ impl OpenTalkControllerServiceBackend for ControllerBackend {
         &self,
         room_id: RoomId,
         asset_id: AssetId,
-    ) -> Result<ByStreamExt, ApiError> {
-        Ok(self.get_room_asset(room_id, asset_id).await?)
+        if_none_match: Option<IfNoneMatchCondition>,
+        range: Option<Vec<RangeSpec>>,
+    ) -> Result<AssetDownload, ApiError> {
+        Ok(self
+            .get_room_asset(room_id, asset_id, if_none_match, range)
+            .await?)
     }
 
     async fn create_room_asset(
@@ -603,6 +665,27 @@ impl OpenTalkControllerServiceBackend for ControllerBackend {
             .await?)
     }
 
+    async fn put_streaming_targets_order(
+        &self,
+        user_id: UserId,
+        room_id: RoomId,
+        order: PutRoomStreamingTargetsOrderRequestBody,
+    ) -> Result<GetRoomStreamingTargetsResponseBody, ApiError> {
+        Ok(self
+            .put_streaming_targets_order(user_id, room_id, order)
+            .await?)
+    }
+
+    async fn put_primary_streaming_target(
+        &self,
+        user_id: UserId,
+        path_params: RoomAndStreamingTargetId,
+    ) -> Result<GetRoomStreamingTargetResponseBody, ApiError> {
+        Ok(self
+            .put_primary_streaming_target(user_id, path_params)
+            .await?)
+    }
+
     async fn delete_streaming_target(
         &self,
         current_user: RequestUser,
@@ -4,27 +4,40 @@
 
 //! Provides room-related implementation
 
-use std::str::FromStr;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    str::FromStr,
+};
 
+use chrono::Utc;
 use kustos::{
-    AccessMethod, Resource,
-    policies_builder::{GrantingAccess, PoliciesBuilder},
+    AccessMethod, Authz, Resource,
+    policies_builder::{GrantingAccess, PoliciesBuilder, Ready},
     prelude::IsSubject,
 };
 use opentalk_controller_service_facade::RequestUser;
+use opentalk_controller_settings::{FeatureOverrides, Tariffs};
 use opentalk_controller_utils::{
     CaptureApiError,
-    deletion::{Deleter, RoomDeleter},
+    deletion::{
+        Deleter, RoomDeleter,
+        room::{associated_resource_ids, notify_room_deleted},
+    },
+    tariff,
 };
+use opentalk_database::{DatabaseError, DbConnection};
 use opentalk_db_storage::{
-    events::Event,
+    events::{Event, EventInvite},
     invites::Invite,
+    module_resources::{Filter, ModuleResource},
     rooms::{NewRoom, Room, UpdateRoom},
     sip_configs::NewSipConfig,
     tariffs::Tariff,
+    users::User,
     utils::build_event_info,
 };
-use opentalk_signaling_core::Participant;
+use opentalk_signaling_core::{Participant, SignalingRoomId};
+use opentalk_signaling_module_legal_vote::storage::NewProtocol;
 use opentalk_types_api_v1::{
     error::{ApiError, ERROR_CODE_INVALID_VALUE, ValidationErrorEntry},
     pagination::PagePaginationQuery,
@@ -32,14 +45,18 @@ use opentalk_types_api_v1::{
         GetRoomsResponseBody, RoomResource,
         by_room_id::{
             GetRoomEventResponseBody, PostRoomsStartInvitedRequestBody, PostRoomsStartRequestBody,
-            RoomsStartResponseBody,
+            PostRoomsTransferOwnershipRequestBody, RoomsStartResponseBody,
+            capabilities::GetRoomCapabilitiesResponseBody,
         },
     },
 };
 use opentalk_types_common::{
-    features,
+    events::invites::InviteRole,
+    features::{self, FeatureId, ModuleFeatureId},
+    module_resources::ModuleResourceId,
+    modules::ModuleId,
     rooms::{RoomId, RoomPassword, invite_codes::InviteCode},
-    tariffs::TariffResource,
+    tariffs::{QuotaType, TariffResource},
     users::UserId,
 };
 
@@ -157,6 +174,8 @@ impl ControllerBackend {
 
         self.authz.add_policies(policies).await?;
 
+        self.webhook.room_created(room_resource.id);
+
         Ok(room_resource)
     }
 
@@ -199,6 +218,22 @@ impl ControllerBackend {
         let settings = self.settings_provider.get();
         let mut conn = self.db.get_conn().await?;
 
+        if settings.endpoints.soft_delete_rooms {
+            if Event::get_id_for_room(&mut conn, room_id).await?.is_some() {
+                return Err(ApiError::conflict()
+                    .with_message(format!(
+                        "Unable to delete room with id {room_id} due to conflicting event"
+                    ))
+                    .into());
+            }
+
+            _ = Room::mark_deleted(&mut conn, room_id).await?;
+
+            notify_room_deleted(log::logger(), &self.exchange_handle, room_id);
+
+            return Ok(());
+        }
+
         let deleter = RoomDeleter::new(room_id, force_delete_reference_if_external_services_fail);
 
         deleter
@@ -216,6 +251,92 @@ impl ControllerBackend {
         Ok(())
     }
 
+    pub(crate) async fn restore_room(
+        &self,
+        room_id: RoomId,
+    ) -> Result<RoomResource, CaptureApiError> {
+        let settings = self.settings_provider.get();
+        let mut conn = self.db.get_conn().await?;
+
+        let room = Room::restore(&mut conn, room_id).await?;
+        let created_by = User::get(&mut conn, room.created_by).await?;
+
+        let room_resource = RoomResource {
+            id: room.id,
+            created_by: created_by.to_public_user_profile(&settings),
+            created_at: room.created_at.into(),
+            password: room.password,
+            waiting_room: room.waiting_room,
+        };
+
+        Ok(room_resource)
+    }
+
+    pub(crate) async fn transfer_room_ownership(
+        &self,
+        current_user: RequestUser,
+        room_id: RoomId,
+        body: PostRoomsTransferOwnershipRequestBody,
+    ) -> Result<RoomResource, CaptureApiError> {
+        let settings = self.settings_provider.get();
+        let mut conn = self.db.get_conn().await?;
+
+        let room = Room::get(&mut conn, room_id).await?;
+        if room.tenant_id != current_user.tenant_id {
+            return Err(ApiError::not_found().into());
+        }
+
+        if room.created_by != current_user.id {
+            return Err(ApiError::forbidden()
+                .with_message("Only the owner of the room can transfer its ownership")
+                .into());
+        }
+
+        // Disabled users and users of other tenants are not able to access the room
+        let new_owner =
+            match User::get_filtered_by_tenant(&mut conn, room.tenant_id, body.new_owner).await {
+                Ok(user) => user,
+                Err(DatabaseError::NotFound) => {
+                    return Err(ApiError::unprocessable_entities([ValidationErrorEntry::new(
+                        "new_owner",
+                        ERROR_CODE_INVALID_VALUE,
+                        Some("The user does not exist in the tenant of the room"),
+                    )])
+                    .into());
+                }
+                Err(e) => return Err(e.into()),
+            };
+
+        if new_owner.id == room.created_by {
+            return Err(ApiError::unprocessable_entities([ValidationErrorEntry::new(
+                "new_owner",
+                ERROR_CODE_INVALID_VALUE,
+                Some("The user already owns the room"),
+            )])
+            .into());
+        }
+
+        let room = transfer_ownership_with_permissions(
+            &mut conn,
+            &self.authz,
+            room_id,
+            room.created_by,
+            new_owner.id,
+            body.revoke_previous_owner,
+        )
+        .await?;
+
+        let room_resource = RoomResource {
+            id: room.id,
+            created_by: new_owner.to_public_user_profile(&settings),
+            created_at: room.created_at.into(),
+            password: room.password,
+            waiting_room: room.waiting_room,
+        };
+
+        Ok(room_resource)
+    }
+
     pub(crate) async fn get_room(&self, room_id: &RoomId) -> Result<RoomResource, CaptureApiError> {
         let settings = self.settings_provider.get();
         let mut conn = self.db.get_conn().await?;
@@ -241,7 +362,14 @@ impl ControllerBackend {
         let mut conn = self.db.get_conn().await?;
 
         let room = Room::get(&mut conn, *room_id).await?;
-        let tariff = room.get_tariff(&mut conn).await?;
+        let tariff = tariff::get_room_tariff(&settings.tariffs, &mut conn, &room).await?;
+        let tariff = tariff::apply_feature_overrides(
+            &settings.feature_overrides,
+            &mut conn,
+            room.tenant_id,
+            tariff,
+        )
+        .await?;
 
         let response = tariff.to_tariff_resource(
             settings.defaults.disabled_features.clone(),
@@ -251,6 +379,106 @@ impl ControllerBackend {
         Ok(response)
     }
 
+    pub(crate) async fn get_room_capabilities(
+        &self,
+        room_id: &RoomId,
+    ) -> Result<GetRoomCapabilitiesResponseBody, CaptureApiError> {
+        let settings = self.settings_provider.get();
+        let mut conn = self.db.get_conn().await?;
+
+        let room = Room::get(&mut conn, *room_id).await?;
+
+        room_capabilities(
+            &mut conn,
+            &room,
+            &settings.tariffs,
+            &settings.feature_overrides,
+            &settings.defaults.disabled_features,
+            self.module_features.clone(),
+        )
+        .await
+    }
+
+    pub(crate) async fn get_room_module_state(
+        &self,
+        current_user: RequestUser,
+        room_id: RoomId,
+        module_id: ModuleId,
+    ) -> Result<serde_json::Value, CaptureApiError> {
+        let mut conn = self.db.get_conn().await?;
+
+        ensure_room_moderator(&mut conn, &current_user, room_id).await?;
+
+        drop(conn);
+
+        let Some(summarize) = self.module_state_summarizers.get(&module_id) else {
+            return Err(ApiError::not_found()
+                .with_code("unknown_module")
+                .with_message("The module does not exist")
+                .into());
+        };
+
+        let signaling_room_id = SignalingRoomId::new_for_room(room_id);
+
+        let Some(state) = summarize(self.volatile.clone(), signaling_room_id).await? else {
+            return Err(ApiError::not_found()
+                .with_code("no_active_state")
+                .with_message("The module has no active state in the room")
+                .into());
+        };
+
+        Ok(state)
+    }
+
+    pub(crate) async fn get_room_legal_vote_protocol(
+        &self,
+        current_user: RequestUser,
+        room_id: RoomId,
+        legal_vote_id: ModuleResourceId,
+    ) -> Result<serde_json::Value, CaptureApiError> {
+        let mut conn = self.db.get_conn().await?;
+
+        let resource = ModuleResource::get(
+            &mut conn,
+            Filter::new()
+                .with_id(legal_vote_id)
+                .with_namespace("legal_vote".into()),
+        )
+        .await?
+        .into_iter()
+        .find(|resource| {
+            resource.room_id == room_id && resource.tenant_id == current_user.tenant_id
+        })
+        .ok_or_else(ApiError::not_found)?;
+
+        drop(conn);
+
+        // Access is granted to the initiator of the vote and the room owner when the vote starts
+        if !self
+            .authz
+            .check_user(
+                current_user.id,
+                legal_vote_id.resource_id(),
+                AccessMethod::Get,
+            )
+            .await?
+        {
+            return Err(ApiError::forbidden().into());
+        }
+
+        let protocol: NewProtocol = serde_json::from_value(resource.data).map_err(|e| {
+            log::error!("Failed to deserialize legal vote protocol {legal_vote_id}, {e}");
+            ApiError::internal()
+        })?;
+
+        let protocol = serde_json::to_value(protocol.anonymized()).map_err(|e| {
+            log::error!("Failed to serialize legal vote protocol {legal_vote_id}, {e}");
+            ApiError::internal()
+        })?;
+
+        Ok(protocol)
+    }
+
     pub(crate) async fn get_room_event(
         &self,
         room_id: &RoomId,
@@ -407,8 +635,6 @@ impl ControllerBackend {
 
         let room = Room::get(&mut conn, invite.room).await?;
 
-        drop(conn);
-
         if let Some(room_password) = &room.password
             && let Some(password) = &password
             && password != room_password
@@ -416,10 +642,180 @@ impl ControllerBackend {
             return Err(StartRoomError::WrongRoomPassword.into());
         }
 
+        Invite::record_usage(&mut conn, invite.id, Utc::now()).await?;
+
         Ok(())
     }
 }
 
+/// Ensure that the user is the owner of the room or invited as a moderator to the room's event
+///
+/// Only the participants that are moderators in the meeting may inspect the state of its
+/// signaling modules.
+async fn ensure_room_moderator(
+    conn: &mut DbConnection,
+    current_user: &RequestUser,
+    room_id: RoomId,
+) -> Result<(), CaptureApiError> {
+    let room = Room::get(conn, room_id).await?;
+    if room.tenant_id != current_user.tenant_id {
+        return Err(ApiError::not_found().into());
+    }
+
+    if room.created_by == current_user.id {
+        return Ok(());
+    }
+
+    let is_moderator = EventInvite::get_for_user_and_room(conn, current_user.id, room_id)
+        .await?
+        .is_some_and(|invite| invite.role == InviteRole::Moderator);
+
+    if !is_moderator {
+        return Err(ApiError::forbidden().into());
+    }
+
+    Ok(())
+}
+
+/// Makes `new_owner` the owner of the room and moves the owner permissions along.
+///
+/// The permissions are stored by kustos and cannot be part of a database transaction. The
+/// ownership change is therefore committed first and reverted if the permissions cannot be applied.
+async fn transfer_ownership_with_permissions(
+    conn: &mut DbConnection,
+    authz: &Authz,
+    room_id: RoomId,
+    previous_owner: UserId,
+    new_owner: UserId,
+    revoke_previous_owner: bool,
+) -> Result<Room, CaptureApiError> {
+    let module_resources = ModuleResource::get_all_with_creator_for_room(conn, room_id).await?;
+    let module_resource_ids = Vec::from_iter(
+        module_resources
+            .iter()
+            .map(|(module_resource_id, _)| *module_resource_id),
+    );
+
+    let room = Room::transfer_ownership(conn, room_id, new_owner).await?;
+
+    if let Err(e) = authz
+        .add_policies(owner_policies(room_id, new_owner, &module_resource_ids))
+        .await
+    {
+        restore_room_owner(conn, room_id, previous_owner).await;
+        return Err(e.into());
+    }
+
+    if !revoke_previous_owner {
+        return Ok(room);
+    }
+
+    // Keep the access to module resources which were created by the previous owner
+    let revoked_module_resource_ids = module_resources
+        .into_iter()
+        .filter(|(_, created_by)| *created_by != previous_owner)
+        .map(|(module_resource_id, _)| module_resource_id.resource_id());
+
+    let resources = Vec::from_iter(
+        associated_resource_ids(room_id)
+            .into_iter()
+            .chain(revoked_module_resource_ids),
+    );
+
+    if let Err(e) = authz
+        .remove_all_user_permission_for_resources(previous_owner, resources)
+        .await
+    {
+        // The revocation may have been applied partially, hand the previous owner their full
+        // permissions back before taking the granted ones away from the new owner
+        if let Err(e) = authz
+            .add_policies(owner_policies(
+                room_id,
+                previous_owner,
+                &module_resource_ids,
+            ))
+            .await
+        {
+            log::error!(
+                "Failed to restore the permissions of the previous owner of room {room_id}, {e}"
+            );
+        }
+        if let Err(e) = authz
+            .remove_policies(owner_policies(room_id, new_owner, &module_resource_ids))
+            .await
+        {
+            log::error!(
+                "Failed to remove the permissions granted to the new owner of room {room_id}, {e}"
+            );
+        }
+        restore_room_owner(conn, room_id, previous_owner).await;
+
+        return Err(e.into());
+    }
+
+    Ok(room)
+}
+
+/// The permissions held by the owner of a room
+fn owner_policies(
+    room_id: RoomId,
+    owner: UserId,
+    module_resource_ids: &[ModuleResourceId],
+) -> PoliciesBuilder<Ready> {
+    let mut policies = PoliciesBuilder::new()
+        .grant_user_access(owner)
+        .room_read_access(room_id)
+        .room_write_access(room_id);
+    for module_resource_id in module_resource_ids {
+        policies = policies.add_resource(
+            module_resource_id.resource_id(),
+            [AccessMethod::Get, AccessMethod::Put, AccessMethod::Delete],
+        );
+    }
+    policies.finish()
+}
+
+/// Reverts an ownership transfer whose permission changes could not be applied
+async fn restore_room_owner(conn: &mut DbConnection, room_id: RoomId, previous_owner: UserId) {
+    if let Err(e) = Room::transfer_ownership(conn, room_id, previous_owner).await {
+        log::error!("Failed to restore the previous owner of room {room_id}, {e}");
+    }
+}
+
+/// The capabilities of the room, as resolved from the tariff of its owner and the given settings
+async fn room_capabilities(
+    conn: &mut DbConnection,
+    room: &Room,
+    tariffs: &Tariffs,
+    feature_overrides: &FeatureOverrides,
+    disabled_features: &BTreeSet<ModuleFeatureId>,
+    module_features: BTreeMap<ModuleId, BTreeSet<FeatureId>>,
+) -> Result<GetRoomCapabilitiesResponseBody, CaptureApiError> {
+    let tariff = tariff::get_room_tariff(tariffs, conn, room).await?;
+    let tariff =
+        tariff::apply_feature_overrides(feature_overrides, conn, room.tenant_id, tariff).await?;
+
+    let TariffResource {
+        modules, quotas, ..
+    } = tariff.to_tariff_resource(disabled_features.clone(), module_features);
+
+    // Assets created in the room, e.g. recordings, count towards the storage of the room owner
+    let remaining_storage = match quotas.get(&QuotaType::MaxStorage) {
+        Some(max_storage) => {
+            let used_storage = User::get_used_storage_u64(conn, &room.created_by).await?;
+            Some(max_storage.saturating_sub(used_storage))
+        }
+        None => None,
+    };
+
+    Ok(GetRoomCapabilitiesResponseBody {
+        modules,
+        participant_limit: quotas.get(&QuotaType::RoomParticipantLimit).copied(),
+        time_limit_secs: quotas.get(&QuotaType::RoomTimeLimitSecs).copied(),
+        remaining_storage,
+    })
+}
+
 /// Provides functionality to grant room privileges
 pub trait RoomsPoliciesBuilderExt {
     /// Set the room privileges needed to grant read access to guests
@@ -464,6 +860,10 @@ where
                 room_id.resource_id().with_suffix("/tariff"),
                 [AccessMethod::Get],
             )
+            .add_resource(
+                room_id.resource_id().with_suffix("/capabilities"),
+                [AccessMethod::Get],
+            )
             .add_resource(
                 room_id.resource_id().with_suffix("/event"),
                 [AccessMethod::Get],
@@ -480,6 +880,14 @@ where
                 room_id.resource_id().with_suffix("/roomserver/start"),
                 [AccessMethod::Post],
             )
+            .add_resource(
+                room_id.resource_id().with_suffix("/module_states/*"),
+                [AccessMethod::Get],
+            )
+            .add_resource(
+                room_id.resource_id().with_suffix("/legal_votes/*/protocol"),
+                [AccessMethod::Get],
+            )
     }
 
     fn room_write_access(self, room_id: RoomId) -> Self {
@@ -501,7 +909,12 @@ where
         )
         .add_resource(
             room_id.resource_id().with_suffix("/streaming_targets/*"),
-            [AccessMethod::Get, AccessMethod::Patch, AccessMethod::Delete],
+            [
+                AccessMethod::Get,
+                AccessMethod::Put,
+                AccessMethod::Patch,
+                AccessMethod::Delete,
+            ],
         )
         .add_resource(
             room_id.resource_id().with_suffix("/assets"),
@@ -515,5 +928,268 @@ where
             room_id.resource_id().with_suffix("/roomserver/*"),
             [AccessMethod::Post],
         )
+        .add_resource(
+            room_id.resource_id().with_suffix("/restore"),
+            [AccessMethod::Post],
+        )
+        .add_resource(
+            room_id.resource_id().with_suffix("/transfer_ownership"),
+            [AccessMethod::Post],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use chrono::Utc;
+    use diesel_async::SimpleAsyncConnection;
+    use kustos::{AccessMethod, Authz, Resource};
+    use opentalk_controller_settings::{FeatureOverrides, Tariffs, TenantFeatureOverrides};
+    use opentalk_db_storage::{
+        assets::NewAsset,
+        rooms::Room,
+        tariffs::{Tariff, UpdateTariff},
+        utils::Jsonb,
+    };
+    use opentalk_test_util::{ROOM_ID, database::DatabaseContext};
+    use opentalk_types_common::{
+        assets::AssetId, features::FeatureId, modules::ModuleId, tariffs::QuotaType,
+    };
+    use pretty_assertions::assert_eq;
+    use serial_test::serial;
+
+    use super::{owner_policies, room_capabilities, transfer_ownership_with_permissions};
+
+    fn module_id(module: &str) -> ModuleId {
+        module.parse().expect("valid module id")
+    }
+
+    fn module_features() -> BTreeMap<ModuleId, BTreeSet<FeatureId>> {
+        BTreeMap::from([
+            (module_id("chat"), BTreeSet::new()),
+            (
+                module_id("recording"),
+                BTreeSet::from([
+                    "record".parse().expect("valid feature id"),
+                    "stream".parse().expect("valid feature id"),
+                ]),
+            ),
+            (module_id("whiteboard"), BTreeSet::new()),
+        ])
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn transfer_ownership_moves_permissions() {
+        let db_ctx = DatabaseContext::new(true).await;
+        let authz = Authz::new(db_ctx.db.clone()).await.unwrap();
+        let alice = db_ctx.create_test_user(0, vec![]).await.unwrap();
+        let bob = db_ctx.create_test_user(1, vec![]).await.unwrap();
+        let room = db_ctx
+            .create_test_room(ROOM_ID, alice.id, false)
+            .await
+            .unwrap();
+        authz
+            .add_policies(owner_policies(room.id, alice.id, &[]))
+            .await
+            .unwrap();
+
+        let mut conn = db_ctx.db.get_conn().await.unwrap();
+        let transferred =
+            transfer_ownership_with_permissions(&mut conn, &authz, room.id, alice.id, bob.id, true)
+                .await
+                .unwrap();
+
+        assert_eq!(transferred.created_by, bob.id);
+        assert_eq!(
+            Room::get(&mut conn, room.id).await.unwrap().created_by,
+            bob.id
+        );
+        assert!(
+            authz
+                .check_user(bob.id, room.id.resource_id(), AccessMethod::Patch)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !authz
+                .check_user(alice.id, room.id.resource_id(), AccessMethod::Patch)
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn transfer_ownership_failed_revoke_is_reverted() {
+        let db_ctx = DatabaseContext::new(true).await;
+        let authz = Authz::new(db_ctx.db.clone()).await.unwrap();
+        let alice = db_ctx.create_test_user(0, vec![]).await.unwrap();
+        let bob = db_ctx.create_test_user(1, vec![]).await.unwrap();
+        let room = db_ctx
+            .create_test_room(ROOM_ID, alice.id, false)
+            .await
+            .unwrap();
+        authz
+            .add_policies(owner_policies(room.id, alice.id, &[]))
+            .await
+            .unwrap();
+
+        // Make revoking the permissions of the previous owner fail
+        let mut conn = db_ctx.db.get_conn().await.unwrap();
+        conn.batch_execute(&format!(
+            "CREATE FUNCTION reject_owner_revoke() RETURNS trigger AS $$
+            BEGIN
+                IF OLD.v0 LIKE '%{}%' THEN
+                    RAISE EXCEPTION 'revoke rejected';
+                END IF;
+                RETURN OLD;
+            END;
+            $$ LANGUAGE plpgsql;
+            CREATE TRIGGER reject_owner_revoke BEFORE DELETE ON casbin_rule
+                FOR EACH ROW EXECUTE FUNCTION reject_owner_revoke();",
+            alice.id
+        ))
+        .await
+        .unwrap();
+
+        let result =
+            transfer_ownership_with_permissions(&mut conn, &authz, room.id, alice.id, bob.id, true)
+                .await;
+        assert!(result.is_err());
+
+        assert_eq!(
+            Room::get(&mut conn, room.id).await.unwrap().created_by,
+            alice.id
+        );
+        assert!(
+            authz
+                .check_user(alice.id, room.id.resource_id(), AccessMethod::Patch)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !authz
+                .check_user(bob.id, room.id.resource_id(), AccessMethod::Patch)
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn room_capabilities_apply_the_tariff_of_the_room_owner() {
+        let db_ctx = DatabaseContext::new(true).await;
+        let mut conn = db_ctx.db.get_conn().await.unwrap();
+        let owner = db_ctx.create_test_user(0, vec![]).await.unwrap();
+        let room = db_ctx
+            .create_test_room(ROOM_ID, owner.id, false)
+            .await
+            .unwrap();
+
+        let _ = UpdateTariff {
+            name: None,
+            updated_at: Utc::now(),
+            quotas: Some(Jsonb(BTreeMap::from([
+                (QuotaType::MaxStorage, 100),
+                (QuotaType::RoomParticipantLimit, 10),
+                (QuotaType::RoomTimeLimitSecs, 3600),
+            ]))),
+            disabled_modules: Some(vec![module_id("whiteboard")]),
+            disabled_features: None,
+        }
+        .apply(&mut conn, owner.tariff_id)
+        .await
+        .unwrap();
+
+        // Assets of the room count towards the storage of the owner
+        let _ = NewAsset {
+            id: AssetId::generate(),
+            namespace: None,
+            kind: "recording".into(),
+            filename: "recording.webm".into(),
+            tenant_id: room.tenant_id,
+            size: 30,
+            retention_tag: "default".into(),
+        }
+        .insert_for_room(&mut conn, room.id)
+        .await
+        .unwrap();
+
+        let capabilities = room_capabilities(
+            &mut conn,
+            &room,
+            &Tariffs::default(),
+            &FeatureOverrides::default(),
+            &BTreeSet::from(["recording::stream"
+                .parse()
+                .expect("valid module feature id")]),
+            module_features(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            capabilities.modules.keys().cloned().collect::<Vec<_>>(),
+            vec![module_id("chat"), module_id("recording")]
+        );
+        assert_eq!(
+            capabilities.modules[&module_id("recording")].features,
+            BTreeSet::from(["record".parse().expect("valid feature id")])
+        );
+        assert_eq!(capabilities.participant_limit, Some(10));
+        assert_eq!(capabilities.time_limit_secs, Some(3600));
+        assert_eq!(capabilities.remaining_storage, Some(70));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn room_capabilities_apply_the_feature_overrides_of_the_tenant() {
+        let db_ctx = DatabaseContext::new(true).await;
+        let mut conn = db_ctx.db.get_conn().await.unwrap();
+        let owner = db_ctx.create_test_user(0, vec![]).await.unwrap();
+        let room = db_ctx
+            .create_test_room(ROOM_ID, owner.id, false)
+            .await
+            .unwrap();
+        let tariff = Tariff::get(&mut conn, owner.tariff_id).await.unwrap();
+        assert!(tariff.quotas.0.is_empty());
+
+        let feature_overrides = FeatureOverrides {
+            tenants: BTreeMap::from([(
+                "default".to_string(),
+                TenantFeatureOverrides {
+                    disabled_modules: BTreeSet::from([module_id("chat")]),
+                    ..Default::default()
+                },
+            )]),
+        };
+
+        let capabilities = room_capabilities(
+            &mut conn,
+            &room,
+            &Tariffs::default(),
+            &feature_overrides,
+            &BTreeSet::new(),
+            module_features(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            capabilities.modules.keys().cloned().collect::<Vec<_>>(),
+            vec![module_id("recording"), module_id("whiteboard")]
+        );
+        assert_eq!(
+            capabilities.modules[&module_id("recording")].features.len(),
+            2
+        );
+
+        // Limits are only returned if the tariff sets them
+        assert_eq!(capabilities.participant_limit, None);
+        assert_eq!(capabilities.time_limit_secs, None);
+        assert_eq!(capabilities.remaining_storage, None);
     }
 }
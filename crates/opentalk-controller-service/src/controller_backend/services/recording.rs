@@ -9,8 +9,15 @@ use opentalk_types_api_v1::{
     error::ApiError,
     services::{PostServiceStartResponseBody, recording::PostRecordingStartRequestBody},
 };
+use opentalk_types_common::rooms::RoomId;
+use snafu::Report;
 
-use crate::{ControllerBackend, signaling::ticket::start_or_continue_signaling_session};
+use crate::{
+    ControllerBackend,
+    signaling::{
+        ticket::start_or_continue_signaling_session, ws_modules::breakout::BreakoutStorageProvider,
+    },
+};
 
 impl ControllerBackend {
     pub(crate) async fn start_recording(
@@ -45,4 +52,50 @@ impl ControllerBackend {
 
         Ok(PostServiceStartResponseBody { ticket, resumption })
     }
+
+    pub(crate) async fn stop_recording(&self, room_id: RoomId) -> Result<(), CaptureApiError> {
+        let settings = self.settings_provider.get();
+        let mut conn = self.db.get_conn().await?;
+        let mut volatile = self.volatile.clone();
+
+        if settings
+            .rabbit_mq
+            .as_ref()
+            .and_then(|c| c.recording_task_queue.as_ref())
+            .is_none()
+        {
+            return Err(ApiError::not_found().into());
+        }
+
+        let room = Room::get(&mut conn, room_id).await?;
+
+        let breakout_rooms = volatile
+            .breakout_storage()
+            .get_breakout_config(room.id)
+            .await
+            .map_err(Into::<ApiError>::into)?
+            .map(|config| config.rooms.into_iter().map(|room| room.id).collect())
+            .unwrap_or_else(Vec::new);
+
+        let stopped = opentalk_signaling_module_recording::stop_recording(
+            &mut volatile,
+            &self.exchange_handle,
+            room.id,
+            breakout_rooms,
+        )
+        .await
+        .map_err(|e| {
+            log::error!("Unable to stop recording, {}", Report::from_error(e));
+            ApiError::internal()
+        })?;
+
+        if !stopped {
+            return Err(ApiError::conflict()
+                .with_code("recording_not_active")
+                .with_message("No recording is active in this room")
+                .into());
+        }
+
+        Ok(())
+    }
 }
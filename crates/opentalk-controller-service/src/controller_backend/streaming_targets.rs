@@ -2,13 +2,16 @@
 //
 // SPDX-License-Identifier: EUPL-1.2
 
+use std::collections::BTreeSet;
+
 use opentalk_controller_service_facade::RequestUser;
 use opentalk_controller_utils::CaptureApiError;
 use opentalk_db_storage::{
     rooms::Room,
     streaming_targets::{
         RoomStreamingTargetRecord, UpdateRoomStreamingTarget, get_room_streaming_targets,
-        insert_room_streaming_target,
+        insert_room_streaming_target, reorder_room_streaming_targets,
+        set_primary_room_streaming_target,
     },
     tenants::Tenant,
     users::User,
@@ -21,7 +24,8 @@ use opentalk_types_api_v1::{
         by_room_id::streaming_targets::{
             GetRoomStreamingTargetResponseBody, GetRoomStreamingTargetsResponseBody,
             PatchRoomStreamingTargetRequestBody, PatchRoomStreamingTargetResponseBody,
-            PostRoomStreamingTargetResponseBody, RoomAndStreamingTargetId,
+            PostRoomStreamingTargetResponseBody, PutRoomStreamingTargetsOrderRequestBody,
+            RoomAndStreamingTargetId,
         },
         streaming_targets::UpdateStreamingTargetKind,
     },
@@ -113,6 +117,8 @@ impl ControllerBackend {
 
         let room_streaming_target = RoomStreamingTarget {
             id: room_streaming_target.id,
+            order: room_streaming_target.order(),
+            is_primary: room_streaming_target.is_primary,
             streaming_target: StreamingTarget {
                 name: room_streaming_target.name,
                 kind: StreamingTargetKind::Custom {
@@ -221,6 +227,8 @@ impl ControllerBackend {
 
         let room_streaming_target = RoomStreamingTarget {
             id: room_streaming_target_table.id,
+            order: room_streaming_target_table.order(),
+            is_primary: room_streaming_target_table.is_primary,
             streaming_target: StreamingTarget {
                 name: room_streaming_target_table.name,
                 kind,
@@ -246,6 +254,69 @@ impl ControllerBackend {
         Ok(PatchRoomStreamingTargetResponseBody(room_streaming_target))
     }
 
+    pub(crate) async fn put_streaming_targets_order(
+        &self,
+        user_id: UserId,
+        room_id: RoomId,
+        PutRoomStreamingTargetsOrderRequestBody {
+            streaming_target_ids,
+        }: PutRoomStreamingTargetsOrderRequestBody,
+    ) -> Result<GetRoomStreamingTargetsResponseBody, CaptureApiError> {
+        let mut conn = self.db.get_conn().await?;
+
+        let existing_ids: BTreeSet<_> =
+            RoomStreamingTargetRecord::get_all_for_room(&mut conn, room_id)
+                .await?
+                .into_iter()
+                .map(|record| record.id)
+                .collect();
+        let requested_ids: BTreeSet<_> = streaming_target_ids.iter().copied().collect();
+
+        if requested_ids.len() != streaming_target_ids.len() || requested_ids != existing_ids {
+            return Err(ApiError::bad_request()
+                .with_code("invalid_streaming_target_order")
+                .with_message(
+                    "The order must contain every streaming target of the room exactly once",
+                )
+                .into());
+        }
+
+        let room_streaming_targets =
+            reorder_room_streaming_targets(&mut conn, room_id, streaming_target_ids).await?;
+
+        let room = Room::get(&mut conn, room_id).await?;
+        let with_streaming_key = room.created_by == user_id;
+
+        Ok(GetRoomStreamingTargetsResponseBody(
+            room_streaming_targets
+                .into_iter()
+                .map(|rst| build_resource(rst, with_streaming_key))
+                .collect(),
+        ))
+    }
+
+    pub(crate) async fn put_primary_streaming_target(
+        &self,
+        user_id: UserId,
+        RoomAndStreamingTargetId {
+            room_id,
+            streaming_target_id,
+        }: RoomAndStreamingTargetId,
+    ) -> Result<GetRoomStreamingTargetResponseBody, CaptureApiError> {
+        let mut conn = self.db.get_conn().await?;
+
+        let room_streaming_target =
+            set_primary_room_streaming_target(&mut conn, room_id, streaming_target_id).await?;
+
+        let room = Room::get(&mut conn, room_id).await?;
+        let with_streaming_key = room.created_by == user_id;
+
+        Ok(GetRoomStreamingTargetResponseBody(build_resource(
+            room_streaming_target,
+            with_streaming_key,
+        )))
+    }
+
     pub(crate) async fn delete_streaming_target(
         &self,
         current_user: RequestUser,
@@ -307,6 +378,8 @@ fn build_resource(
 
     RoomStreamingTargetResource {
         id: room_streaming_target.id,
+        order: room_streaming_target.order,
+        is_primary: room_streaming_target.is_primary,
         streaming_target: StreamingTargetResource {
             name: room_streaming_target.streaming_target.name,
             kind: kind_resource,
@@ -7,11 +7,10 @@ use opentalk_controller_settings::{
     TenantAssignment, UserSearchBackend, UserSearchBackendKeycloak,
     settings_file::UsersFindBehavior,
 };
-use opentalk_controller_utils::CaptureApiError;
+use opentalk_controller_utils::{CaptureApiError, tariff};
 use opentalk_database::{DatabaseError, DbConnection};
 use opentalk_db_storage::{
     assets,
-    tariffs::Tariff,
     tenants::Tenant,
     users::{UpdateUser, User},
 };
@@ -102,7 +101,20 @@ impl ControllerBackend {
         let settings = self.settings_provider.get();
         let mut conn = self.db.get_conn().await?;
 
-        let tariff = Tariff::get(&mut conn, current_user.tariff_id).await?;
+        let tariff = tariff::get_user_tariff(
+            &settings.tariffs,
+            &mut conn,
+            current_user.tariff_id,
+            current_user.tenant_id,
+        )
+        .await?;
+        let tariff = tariff::apply_feature_overrides(
+            &settings.feature_overrides,
+            &mut conn,
+            current_user.tenant_id,
+            tariff,
+        )
+        .await?;
 
         let response = tariff.to_tariff_resource(
             settings.defaults.disabled_features.clone(),
@@ -6,7 +6,7 @@
 
 use opentalk_controller_service_facade::RequestUser;
 use opentalk_controller_settings::Settings;
-use opentalk_controller_utils::CaptureApiError;
+use opentalk_controller_utils::{CaptureApiError, tariff};
 use opentalk_database::DbConnection;
 use opentalk_db_storage::{assets::Asset, tariffs::Tariff, users::User};
 use opentalk_types_api_v1::{
@@ -118,6 +118,8 @@ pub fn email_to_libravatar_url(libravatar_url: &str, email: &str) -> String {
 
 /// Checks if the given feature sting is disabled by the tariff of the given user or in the settings of the controller.
 ///
+/// Feature overrides configured for the tenant of the user take precedence over the tariff.
+///
 /// Return an [`ApiError`] if the given feature is disabled, differentiating between a config disable or tariff restriction.
 pub async fn require_feature(
     db_conn: &mut DbConnection,
@@ -132,7 +134,18 @@ pub async fn require_feature(
             .into());
     }
 
-    let tariff = Tariff::get_by_user_id(db_conn, &user_id).await?;
+    let mut tariff = Tariff::get_by_user_id(db_conn, &user_id).await?;
+
+    if !settings.feature_overrides.is_empty() {
+        let user = User::get(db_conn, user_id).await?;
+        tariff = tariff::apply_feature_overrides(
+            &settings.feature_overrides,
+            db_conn,
+            user.tenant_id,
+            tariff,
+        )
+        .await?;
+    }
 
     if tariff.is_feature_disabled(feature) {
         return Err(ApiError::forbidden()
@@ -157,6 +170,7 @@ pub fn asset_to_asset_resource(asset: Asset) -> AssetResource {
         filename,
         tenant_id: _,
         size,
+        retention_tag,
     } = asset;
     AssetResource {
         id,
@@ -165,5 +179,6 @@ pub fn asset_to_asset_resource(asset: Asset) -> AssetResource {
         created_at,
         kind,
         size,
+        retention_tag,
     }
 }
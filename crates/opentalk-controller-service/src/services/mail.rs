@@ -288,6 +288,8 @@ impl MailService {
     }
 
     /// Sends a Unregistered Invite mail task to the rabbit mq queue, if configured.
+    ///
+    /// The mail is rendered in the given `language`.
     #[allow(clippy::too_many_arguments)]
     pub async fn send_unregistered_invite(
         &self,
@@ -297,6 +299,7 @@ impl MailService {
         room: Room,
         sip_config: Option<SipConfig>,
         invitee: opentalk_keycloak_admin::users::User,
+        language: Language,
         shared_folder: Option<SharedFolder>,
         streaming_targets: Vec<RoomStreamingTarget>,
     ) -> Result<()> {
@@ -318,6 +321,7 @@ impl MailService {
                 streaming_targets,
             ),
             invitee,
+            language,
         );
 
         self.send_to_rabbitmq(settings, mail_task).await?;
@@ -325,6 +329,8 @@ impl MailService {
     }
 
     /// Sends a external Invite mail task to the rabbit mq queue, if configured.
+    ///
+    /// The mail is rendered in the given `language`.
     #[allow(clippy::too_many_arguments)]
     pub async fn send_external_invite(
         &self,
@@ -334,6 +340,7 @@ impl MailService {
         room: Room,
         sip_config: Option<SipConfig>,
         invitee: &str,
+        language: Language,
         invite_code: String,
         shared_folder: Option<SharedFolder>,
         streaming_targets: Vec<RoomStreamingTarget>,
@@ -351,6 +358,7 @@ impl MailService {
             ),
             invitee.to_string(),
             invite_code,
+            language,
         );
 
         self.send_to_rabbitmq(settings, mail_task).await?;
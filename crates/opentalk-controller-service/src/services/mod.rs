@@ -5,8 +5,10 @@
 //! Long Running Services that expose clean APIs and hide implementation details from endpoints
 //! If the amount of services grow, add another layer that bundles all services.
 mod mail;
+mod webhook;
 
 pub use mail::{
     ExternalMailRecipient, MailRecipient, MailService, RegisteredMailRecipient,
     UnregisteredMailRecipient,
 };
+pub use webhook::{WEBHOOK_DELIVERY_HEADER, WEBHOOK_SIGNATURE_HEADER, WebhookService};
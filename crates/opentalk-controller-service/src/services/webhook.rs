@@ -0,0 +1,255 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+//! WebhookService
+//!
+//! Delivers signed room lifecycle events to the configured webhook endpoint.
+//!
+//! Events are queued without waiting and delivered on a background task, so neither the
+//! HTTP endpoints nor the signaling runners are blocked by a slow or unreachable endpoint.
+use std::{sync::Arc, time::Duration};
+
+use opentalk_controller_settings::{SettingsProvider, Webhook, settings_file::WebhookEventType};
+use opentalk_database::Db;
+use opentalk_db_storage::events::Event;
+use opentalk_types_common::{events::EventId, rooms::RoomId, time::Timestamp};
+use reqwest11::header::CONTENT_TYPE;
+use ring::hmac;
+use serde::Serialize;
+use snafu::Report;
+use tokio::{sync::mpsc, time::sleep};
+use uuid::Uuid;
+
+/// Header containing the hex encoded HMAC-SHA256 signature of the request body
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "X-OpenTalk-Signature";
+
+/// Header containing the unique id of a delivery, which stays the same across retries
+pub const WEBHOOK_DELIVERY_HEADER: &str = "X-OpenTalk-Delivery";
+
+/// Upper bound for the delay between two delivery attempts
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+/// Handle to the background task delivering the webhook events
+#[derive(Debug, Clone)]
+pub struct WebhookService {
+    sender: mpsc::UnboundedSender<LifecycleEvent>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct LifecycleEvent {
+    event_type: WebhookEventType,
+    room_id: RoomId,
+    timestamp: Timestamp,
+}
+
+/// The JSON body of a webhook request
+#[derive(Debug, Serialize)]
+struct WebhookPayload {
+    delivery_id: Uuid,
+    event: WebhookEventType,
+    timestamp: Timestamp,
+    room_id: RoomId,
+    event_id: Option<EventId>,
+}
+
+impl WebhookService {
+    /// Spawn the delivery task and return a [`WebhookService`] to it
+    ///
+    /// The webhook settings are read for every event, so changes are applied when the settings
+    /// are reloaded.
+    pub fn spawn(
+        settings_provider: SettingsProvider,
+        db: Arc<Db>,
+    ) -> Result<Self, reqwest11::Error> {
+        let client = reqwest11::Client::builder()
+            .redirect(reqwest11::redirect::Policy::none())
+            .build()?;
+
+        let (sender, receiver) = mpsc::unbounded_channel();
+
+        tokio::spawn(
+            WebhookTask {
+                settings_provider,
+                db,
+                client,
+            }
+            .run(receiver),
+        );
+
+        Ok(Self { sender })
+    }
+
+    /// Notify the webhook endpoint that a room was created
+    pub fn room_created(&self, room_id: RoomId) {
+        self.send(WebhookEventType::RoomCreated, room_id);
+    }
+
+    /// Notify the webhook endpoint that the first participant joined a room
+    pub fn room_started(&self, room_id: RoomId) {
+        self.send(WebhookEventType::RoomStarted, room_id);
+    }
+
+    /// Notify the webhook endpoint that a room was torn down
+    pub fn room_ended(&self, room_id: RoomId) {
+        self.send(WebhookEventType::RoomEnded, room_id);
+    }
+
+    fn send(&self, event_type: WebhookEventType, room_id: RoomId) {
+        let event = LifecycleEvent {
+            event_type,
+            room_id,
+            timestamp: Timestamp::now(),
+        };
+
+        if self.sender.send(event).is_err() {
+            log::error!("Webhook task is gone, dropping {event_type:?} event for room {room_id}");
+        }
+    }
+}
+
+struct WebhookTask {
+    settings_provider: SettingsProvider,
+    db: Arc<Db>,
+    client: reqwest11::Client,
+}
+
+impl WebhookTask {
+    async fn run(self, mut receiver: mpsc::UnboundedReceiver<LifecycleEvent>) {
+        while let Some(event) = receiver.recv().await {
+            let Some(webhook) = self.settings_provider.get().webhook.clone() else {
+                continue;
+            };
+
+            if !webhook.events.contains(&event.event_type) {
+                continue;
+            }
+
+            let payload = WebhookPayload {
+                delivery_id: Uuid::new_v4(),
+                event: event.event_type,
+                timestamp: event.timestamp,
+                room_id: event.room_id,
+                event_id: self.event_id_for(event).await,
+            };
+
+            let body = match serde_json::to_vec(&payload) {
+                Ok(body) => body,
+                Err(e) => {
+                    log::error!(
+                        "Failed to serialize webhook payload, {}",
+                        Report::from_error(e)
+                    );
+                    continue;
+                }
+            };
+
+            tokio::spawn(deliver(
+                self.client.clone(),
+                webhook,
+                payload.delivery_id,
+                body,
+            ));
+        }
+    }
+
+    async fn event_id_for(&self, event: LifecycleEvent) -> Option<EventId> {
+        // A room created through the rooms endpoint cannot be linked to an event yet
+        if event.event_type == WebhookEventType::RoomCreated {
+            return None;
+        }
+
+        let result = match self.db.get_conn().await {
+            Ok(mut conn) => Event::get_id_for_room(&mut conn, event.room_id)
+                .await
+                .map_err(Report::from_error),
+            Err(e) => Err(Report::from_error(e)),
+        };
+
+        result.unwrap_or_else(|e| {
+            log::warn!(
+                "Failed to get the event of room {} for the webhook payload, {e}",
+                event.room_id
+            );
+            None
+        })
+    }
+}
+
+async fn deliver(client: reqwest11::Client, webhook: Webhook, delivery_id: Uuid, body: Vec<u8>) {
+    let signature = sign(&webhook.secret, &body);
+    let attempts = webhook.max_retries.saturating_add(1);
+
+    for attempt in 1..=attempts {
+        if attempt > 1 {
+            sleep(retry_delay(attempt - 1)).await;
+        }
+
+        let result = client
+            .post(webhook.url.clone())
+            .timeout(webhook.timeout)
+            .header(CONTENT_TYPE, "application/json")
+            .header(WEBHOOK_SIGNATURE_HEADER, &signature)
+            .header(WEBHOOK_DELIVERY_HEADER, delivery_id.to_string())
+            .body(body.clone())
+            .send()
+            .await
+            .and_then(|response| response.error_for_status());
+
+        match result {
+            Ok(_) => {
+                log::debug!("Delivered webhook {delivery_id}");
+                return;
+            }
+            Err(e) => log::warn!(
+                "Webhook delivery {delivery_id} failed (attempt {attempt}/{attempts}), {}",
+                Report::from_error(e)
+            ),
+        }
+    }
+
+    log::error!("Giving up on webhook delivery {delivery_id} after {attempts} attempts");
+}
+
+/// Exponential backoff starting at one second
+fn retry_delay(retry: u32) -> Duration {
+    Duration::from_secs(1u64 << retry.saturating_sub(1).min(6)).min(MAX_RETRY_DELAY)
+}
+
+/// Sign the body with HMAC-SHA256, the result is formatted as `sha256=<hex digest>`
+fn sign(secret: &str, body: &[u8]) -> String {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, secret.as_bytes());
+    let tag = hmac::sign(&key, body);
+
+    let digest: String = tag
+        .as_ref()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+
+    format!("sha256={digest}")
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn signature_matches_reference() {
+        // Test case 2 from RFC 4231
+        assert_eq!(
+            sign("Jefe", b"what do ya want for nothing?"),
+            "sha256=5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn retry_delay_is_capped() {
+        assert_eq!(retry_delay(1), Duration::from_secs(1));
+        assert_eq!(retry_delay(2), Duration::from_secs(2));
+        assert_eq!(retry_delay(4), Duration::from_secs(8));
+        assert_eq!(retry_delay(30), MAX_RETRY_DELAY);
+    }
+}
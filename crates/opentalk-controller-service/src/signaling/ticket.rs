@@ -14,7 +14,12 @@ use redis_args::{FromRedisValue, ToRedisArgs};
 use serde::{Deserialize, Serialize};
 use snafu::Report;
 
-use crate::signaling::storage::SignalingStorageProvider;
+use crate::{
+    controller_backend::rooms::start_room_error::StartRoomError,
+    signaling::{
+        storage::SignalingStorageProvider, ws_modules::moderation::ModerationStorageProvider,
+    },
+};
 
 /// Ticket data
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize, ToRedisArgs, FromRedisValue)]
@@ -107,6 +112,17 @@ async fn use_resumption_token(
         return Ok(None);
     }
 
+    // Guests have no stable identity other than the participant id kept by the resumption token
+    if data.participant == Participant::Guest
+        && volatile
+            .moderation_storage()
+            .is_guest_banned(room, data.participant_id)
+            .await
+            .map_err(Into::<ApiError>::into)?
+    {
+        return Err(StartRoomError::BannedFromRoom.into());
+    }
+
     if volatile
         .signaling_storage()
         .participant_id_in_use(data.participant_id)
@@ -0,0 +1,204 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+//! Automatic distribution of participants into breakout rooms
+
+use std::collections::{HashMap, HashSet};
+
+use opentalk_types_common::rooms::BreakoutRoomId;
+use opentalk_types_signaling::ParticipantId;
+use opentalk_types_signaling_breakout::command::AssignmentStrategy;
+use rand::{Rng, seq::SliceRandom as _};
+
+/// Distribute `participants` into `rooms` using the given `strategy`.
+///
+/// Participants listed in `pinned` keep their room and are counted when balancing the room
+/// sizes. With [`AssignmentStrategy::ByGroup`] the members of each of the `groups` are placed
+/// into the same room, which is the room of a pinned member if the group has one. Larger
+/// groups are placed first, so the rooms end up as balanced as possible.
+///
+/// The returned map contains the pinned and the newly assigned participants.
+pub(crate) fn assign_participants(
+    strategy: AssignmentStrategy,
+    rooms: &[BreakoutRoomId],
+    participants: &[ParticipantId],
+    pinned: &HashMap<ParticipantId, BreakoutRoomId>,
+    groups: &[Vec<ParticipantId>],
+    rng: &mut impl Rng,
+) -> HashMap<ParticipantId, BreakoutRoomId> {
+    let mut assignments = pinned.clone();
+
+    if rooms.is_empty() {
+        return assignments;
+    }
+
+    let mut room_sizes: Vec<usize> = rooms
+        .iter()
+        .map(|room| pinned.values().filter(|pinned| *pinned == room).count())
+        .collect();
+
+    let present: HashSet<ParticipantId> = participants.iter().copied().collect();
+
+    let mut units: Vec<Vec<ParticipantId>> = Vec::new();
+    let mut grouped = HashSet::new();
+
+    if strategy == AssignmentStrategy::ByGroup {
+        for group in groups {
+            let pinned_room = group.iter().find_map(|member| pinned.get(member)).copied();
+
+            let members: Vec<ParticipantId> = group
+                .iter()
+                .copied()
+                .filter(|member| present.contains(member) && !pinned.contains_key(member))
+                .filter(|member| grouped.insert(*member))
+                .collect();
+
+            if let Some(room) = pinned_room {
+                // Keep the group together with its pinned member
+                if let Some(index) = rooms.iter().position(|r| *r == room) {
+                    room_sizes[index] += members.len();
+                }
+
+                assignments.extend(members.into_iter().map(|member| (member, room)));
+            } else if !members.is_empty() {
+                units.push(members);
+            }
+        }
+    }
+
+    units.extend(
+        participants
+            .iter()
+            .copied()
+            .filter(|participant| !pinned.contains_key(participant))
+            .filter(|participant| !grouped.contains(participant))
+            .map(|participant| vec![participant]),
+    );
+
+    match strategy {
+        AssignmentStrategy::RoundRobin => {}
+        AssignmentStrategy::Random => units.shuffle(rng),
+        AssignmentStrategy::ByGroup => {
+            units.shuffle(rng);
+            // Stable sort keeps the random order among units of the same size
+            units.sort_by_key(|unit| std::cmp::Reverse(unit.len()));
+        }
+    }
+
+    for unit in units {
+        // The first of the least occupied rooms, which results in a round-robin distribution
+        // for single participants
+        let (index, _) = room_sizes
+            .iter()
+            .enumerate()
+            .min_by_key(|(index, size)| (**size, *index))
+            .expect("rooms are not empty");
+
+        room_sizes[index] += unit.len();
+
+        assignments.extend(
+            unit.into_iter()
+                .map(|participant| (participant, rooms[index])),
+        );
+    }
+
+    assignments
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use opentalk_types_common::rooms::BreakoutRoomId;
+    use opentalk_types_signaling::ParticipantId;
+    use opentalk_types_signaling_breakout::command::AssignmentStrategy;
+    use pretty_assertions::assert_eq;
+    use rand::{SeedableRng, rngs::StdRng};
+
+    use super::assign_participants;
+
+    fn room_sizes(
+        rooms: &[BreakoutRoomId],
+        assignments: &HashMap<ParticipantId, BreakoutRoomId>,
+    ) -> Vec<usize> {
+        rooms
+            .iter()
+            .map(|room| assignments.values().filter(|r| *r == room).count())
+            .collect()
+    }
+
+    #[test]
+    fn round_robin() {
+        let rooms = [BreakoutRoomId::from_u128(1), BreakoutRoomId::from_u128(2)];
+        let participants: Vec<_> = (1..=5).map(ParticipantId::from_u128).collect();
+
+        let assignments = assign_participants(
+            AssignmentStrategy::RoundRobin,
+            &rooms,
+            &participants,
+            &HashMap::new(),
+            &[],
+            &mut StdRng::seed_from_u64(0),
+        );
+
+        assert_eq!(assignments[&participants[0]], rooms[0]);
+        assert_eq!(assignments[&participants[1]], rooms[1]);
+        assert_eq!(assignments[&participants[2]], rooms[0]);
+        assert_eq!(assignments[&participants[3]], rooms[1]);
+        assert_eq!(assignments[&participants[4]], rooms[0]);
+    }
+
+    #[test]
+    fn random_respects_pinned_participants() {
+        let rooms = [BreakoutRoomId::from_u128(1), BreakoutRoomId::from_u128(2)];
+        let participants: Vec<_> = (1..=6).map(ParticipantId::from_u128).collect();
+        let pinned = HashMap::from([(participants[0], rooms[1]), (participants[1], rooms[1])]);
+
+        let assignments = assign_participants(
+            AssignmentStrategy::Random,
+            &rooms,
+            &participants,
+            &pinned,
+            &[],
+            &mut StdRng::seed_from_u64(0),
+        );
+
+        assert_eq!(assignments.len(), 6);
+        assert_eq!(assignments[&participants[0]], rooms[1]);
+        assert_eq!(assignments[&participants[1]], rooms[1]);
+        assert_eq!(room_sizes(&rooms, &assignments), vec![3, 3]);
+    }
+
+    #[test]
+    fn by_group_keeps_groups_together() {
+        let rooms = [BreakoutRoomId::from_u128(1), BreakoutRoomId::from_u128(2)];
+        let participants: Vec<_> = (1..=7).map(ParticipantId::from_u128).collect();
+        let pinned = HashMap::from([(participants[6], rooms[0])]);
+        let groups = [
+            vec![participants[0], participants[1], participants[2]],
+            vec![participants[3], participants[6]],
+        ];
+
+        let assignments = assign_participants(
+            AssignmentStrategy::ByGroup,
+            &rooms,
+            &participants,
+            &pinned,
+            &groups,
+            &mut StdRng::seed_from_u64(0),
+        );
+
+        assert_eq!(assignments.len(), 7);
+
+        // The group of a pinned participant follows it
+        assert_eq!(assignments[&participants[3]], rooms[0]);
+
+        let first_group_room = assignments[&participants[0]];
+        assert_eq!(assignments[&participants[1]], first_group_room);
+        assert_eq!(assignments[&participants[2]], first_group_room);
+        assert_eq!(first_group_room, rooms[1]);
+
+        assert_eq!(room_sizes(&rooms, &assignments), vec![4, 3]);
+    }
+}
@@ -25,12 +25,13 @@ use opentalk_signaling_core::{
 use opentalk_types_common::{
     modules::ModuleId,
     rooms::{BreakoutRoomId, RoomId},
+    time::Timestamp,
 };
-use opentalk_types_signaling::{ParticipantId, Role};
+use opentalk_types_signaling::{ParticipantId, ParticipationKind, Role};
 use opentalk_types_signaling_breakout::{
     AssociatedParticipantInOtherRoom, BreakoutRoom, MODULE_ID, ParticipantInOtherRoom,
-    command::BreakoutCommand,
-    event::{BreakoutEvent, Error, Started},
+    command::{BreakoutCommand, RoomParameter},
+    event::{AutoAssigned, BreakoutEvent, Error, Started},
     state::BreakoutState,
 };
 use snafu::whatever;
@@ -38,6 +39,7 @@ use tokio::time::sleep;
 
 use self::storage::{BreakoutConfig, BreakoutStorage};
 
+mod assignment;
 pub mod exchange;
 pub mod storage;
 
@@ -328,44 +330,38 @@ impl BreakoutRooms {
                     return Ok(());
                 }
 
-                let started = SystemTime::now();
+                let (rooms, assignments) = Self::create_rooms(start.rooms);
 
-                let mut rooms = vec![];
-                let mut assignments = HashMap::new();
+                self.start(&mut ctx, rooms, assignments, start.duration)
+                    .await?;
+            }
+            BreakoutCommand::AutoAssign(auto_assign) => {
+                if auto_assign.rooms.is_empty() {
+                    // Discard message, case should be handled by frontend
+                    return Ok(());
+                }
 
-                for room_param in start.rooms {
-                    let id = BreakoutRoomId::generate();
+                let (rooms, pinned) = Self::create_rooms(auto_assign.rooms);
+                let room_ids: Vec<BreakoutRoomId> = rooms.iter().map(|room| room.id).collect();
 
-                    for assigned_participant_id in room_param.assignments {
-                        _ = assignments.insert(assigned_participant_id, id);
-                    }
+                let participants = self.assignable_participants(&mut ctx).await?;
 
-                    rooms.push(BreakoutRoom {
-                        id,
-                        name: room_param.name,
-                    });
-                }
+                let assignments = assignment::assign_participants(
+                    auto_assign.strategy,
+                    &room_ids,
+                    &participants,
+                    &pinned,
+                    &auto_assign.groups,
+                    &mut rand::rng(),
+                );
 
-                let config = BreakoutConfig {
-                    rooms,
-                    started,
-                    duration: start.duration,
-                };
+                ctx.ws_send(AutoAssigned {
+                    strategy: auto_assign.strategy,
+                    assignments: assignments.clone().into_iter().collect(),
+                });
 
-                _ = ctx
-                    .volatile
-                    .breakout_storage()
-                    .set_breakout_config(self.parent, &config)
+                self.start(&mut ctx, rooms, assignments, auto_assign.duration)
                     .await?;
-
-                ctx.exchange_publish(
-                    control::exchange::global_room_all_participants(self.parent),
-                    exchange::Message::Start(exchange::Start {
-                        config,
-                        started,
-                        assignments,
-                    }),
-                );
             }
             BreakoutCommand::Stop => {
                 if ctx
@@ -387,6 +383,117 @@ impl BreakoutRooms {
         Ok(())
     }
 
+    /// Create the breakout rooms with new ids, returns the rooms and the participants assigned to them
+    fn create_rooms(
+        room_params: Vec<RoomParameter>,
+    ) -> (Vec<BreakoutRoom>, HashMap<ParticipantId, BreakoutRoomId>) {
+        let mut rooms = vec![];
+        let mut assignments = HashMap::new();
+
+        for room_param in room_params {
+            let id = BreakoutRoomId::generate();
+
+            for assigned_participant_id in room_param.assignments {
+                _ = assignments.insert(assigned_participant_id, id);
+            }
+
+            rooms.push(BreakoutRoom {
+                id,
+                name: room_param.name,
+            });
+        }
+
+        (rooms, assignments)
+    }
+
+    async fn start(
+        &mut self,
+        ctx: &mut ModuleContext<'_, Self>,
+        rooms: Vec<BreakoutRoom>,
+        assignments: HashMap<ParticipantId, BreakoutRoomId>,
+        duration: Option<Duration>,
+    ) -> Result<(), SignalingModuleError> {
+        let started = SystemTime::now();
+
+        let config = BreakoutConfig {
+            rooms,
+            started,
+            duration,
+        };
+
+        _ = ctx
+            .volatile
+            .breakout_storage()
+            .set_breakout_config(self.parent, &config)
+            .await?;
+
+        ctx.exchange_publish(
+            control::exchange::global_room_all_participants(self.parent),
+            exchange::Message::Start(exchange::Start {
+                config,
+                started,
+                assignments,
+            }),
+        );
+
+        Ok(())
+    }
+
+    /// Participants of the main room which can be distributed automatically, in the order they joined
+    ///
+    /// Moderators and recorders are left out, as are participants that already left the room.
+    async fn assignable_participants(
+        &mut self,
+        ctx: &mut ModuleContext<'_, Self>,
+    ) -> Result<Vec<ParticipantId>, SignalingModuleError> {
+        type ParticipantData = (
+            Option<Role>,
+            Option<ParticipationKind>,
+            Option<Timestamp>,
+            Option<Timestamp>,
+        );
+
+        let main_room = SignalingRoomId::new_for_room(self.parent);
+
+        let participants = ctx
+            .volatile
+            .breakout_storage()
+            .get_all_participants(main_room)
+            .await?;
+
+        let mut assignable = Vec::new();
+
+        for participant in participants {
+            let (role, kind, joined_at, left_at): ParticipantData = ctx
+                .volatile
+                .breakout_storage()
+                .bulk_attribute_actions(
+                    AttributeActions::new(main_room, participant)
+                        .get_global(ROLE)
+                        .get_local(KIND)
+                        .get_local(JOINED_AT)
+                        .get_local(LEFT_AT),
+                )
+                .await?;
+
+            if left_at.is_some()
+                || role == Some(Role::Moderator)
+                || kind == Some(ParticipationKind::Recorder)
+            {
+                continue;
+            }
+
+            assignable.push((joined_at, participant));
+        }
+
+        assignable.sort();
+
+        Ok(assignable
+            .into_iter()
+            .map(|(_, participant)| participant)
+            .collect())
+    }
+
     async fn on_exchange_msg(
         &mut self,
         mut ctx: ModuleContext<'_, Self>,
@@ -10,8 +10,16 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Message {
-    Kicked(ParticipantId),
-    Banned(ParticipantId),
+    Kicked {
+        target: ParticipantId,
+        issued_by: ParticipantId,
+        reason: Option<String>,
+    },
+    Banned {
+        target: ParticipantId,
+        issued_by: ParticipantId,
+        reason: Option<String>,
+    },
     SentToWaitingRoom(ParticipantId),
     Debriefed {
         kick_scope: KickScope,
@@ -29,7 +29,10 @@ use opentalk_types_signaling_moderation::{
         Accept, Ban, ChangeDisplayName, Kick, ModerationCommand, ResetRaisedHands,
         SendToWaitingRoom,
     },
-    event::{DebriefingStarted, DisplayNameChanged, Error, ModerationEvent, SessionEnded},
+    event::{
+        Banned, DebriefingStarted, DisplayNameChanged, Error, Kicked, ModerationEvent,
+        ParticipantRemoved, SessionEnded,
+    },
     state::{ModerationState, ModeratorFrontendData},
 };
 use snafu::{Report, ResultExt};
@@ -95,6 +98,20 @@ async fn set_waiting_room_enabled(
     Ok(())
 }
 
+/// The maximum number of characters of a kick or ban reason forwarded to the participant
+const MAX_REASON_LENGTH: usize = 256;
+
+/// Trims the reason given by a moderator and truncates it to [`MAX_REASON_LENGTH`] characters
+fn sanitize_reason(reason: Option<String>) -> Option<String> {
+    let reason = reason?;
+    let reason = reason.trim();
+    if reason.is_empty() {
+        return None;
+    }
+
+    Some(reason.chars().take(MAX_REASON_LENGTH).collect())
+}
+
 pub trait ModerationStorageProvider {
     fn moderation_storage(&mut self) -> &mut dyn ModerationStorage;
 }
@@ -204,39 +221,59 @@ impl SignalingModule for ModerationModule {
             Event::ParticipantLeft(_) => {}
             Event::ParticipantUpdated(_, _) => {}
             Event::RoleUpdated(_) => {}
-            Event::WsMessage(ModerationCommand::Ban(Ban { target })) => {
+            Event::WsMessage(ModerationCommand::Ban(Ban { target, reason })) => {
                 if ctx.role() != Role::Moderator {
                     ctx.ws_send(Error::InsufficientPermissions);
                     return Ok(());
                 }
 
-                ctx.volatile
-                    .moderation_storage()
-                    .waiting_room_accepted_remove_participant(self.room.room_id(), target)
-                    .await?;
-
                 let user_id: Option<UserId> = ctx
                     .volatile
                     .moderation_storage()
                     .get_local_attribute(target, self.room, USER_ID)
                     .await?;
+                let kind: Option<ParticipationKind> = ctx
+                    .volatile
+                    .moderation_storage()
+                    .get_local_attribute(target, self.room, KIND)
+                    .await?;
 
-                if let Some(user_id) = user_id {
-                    ctx.volatile
-                        .moderation_storage()
-                        .ban_user(self.room.room_id(), user_id)
-                        .await?;
-                } else {
-                    ctx.ws_send(Error::CannotBanGuest);
-                    return Ok(());
+                match (user_id, kind) {
+                    (Some(user_id), _) => {
+                        ctx.volatile
+                            .moderation_storage()
+                            .ban_user(self.room.room_id(), user_id)
+                            .await?;
+                    }
+                    (None, Some(ParticipationKind::Guest)) => {
+                        // Guests are banned by their participant id, which they keep when
+                        // rejoining with their resumption token
+                        ctx.volatile
+                            .moderation_storage()
+                            .ban_guest(self.room.room_id(), target)
+                            .await?;
+                    }
+                    _ => {
+                        ctx.ws_send(Error::CannotBanGuest);
+                        return Ok(());
+                    }
                 }
 
+                ctx.volatile
+                    .moderation_storage()
+                    .waiting_room_accepted_remove_participant(self.room.room_id(), target)
+                    .await?;
+
                 ctx.exchange_publish(
-                    control::exchange::current_room_by_participant_id(self.room, target),
-                    exchange::Message::Banned(target),
+                    control::exchange::current_room_all_participants(self.room),
+                    exchange::Message::Banned {
+                        target,
+                        issued_by: self.id,
+                        reason: sanitize_reason(reason),
+                    },
                 );
             }
-            Event::WsMessage(ModerationCommand::Kick(Kick { target })) => {
+            Event::WsMessage(ModerationCommand::Kick(Kick { target, reason })) => {
                 if ctx.role() != Role::Moderator {
                     ctx.ws_send(Error::InsufficientPermissions);
                     return Ok(());
@@ -254,8 +291,12 @@ impl SignalingModule for ModerationModule {
                     .await?;
 
                 ctx.exchange_publish(
-                    control::exchange::current_room_by_participant_id(self.room, target),
-                    exchange::Message::Kicked(target),
+                    control::exchange::current_room_all_participants(self.room),
+                    exchange::Message::Kicked {
+                        target,
+                        issued_by: self.id,
+                        reason: sanitize_reason(reason),
+                    },
                 );
             }
             Event::WsMessage(ModerationCommand::SendToWaitingRoom(SendToWaitingRoom {
@@ -520,16 +561,36 @@ impl SignalingModule for ModerationModule {
                 );
             }
 
-            Event::Exchange(exchange::Message::Banned(participant)) => {
-                if self.id == participant {
-                    ctx.ws_send(ModerationEvent::Banned);
+            Event::Exchange(exchange::Message::Banned {
+                target,
+                issued_by,
+                reason,
+            }) => {
+                if self.id == target {
+                    ctx.ws_send(ModerationEvent::Banned(Banned { reason }));
                     ctx.exit_normal(LeaveReason::Banned);
+                } else {
+                    ctx.ws_send(ModerationEvent::ParticipantRemoved(ParticipantRemoved {
+                        participant: target,
+                        issued_by,
+                        banned: true,
+                    }));
                 }
             }
-            Event::Exchange(exchange::Message::Kicked(participant)) => {
-                if self.id == participant {
-                    ctx.ws_send(ModerationEvent::Kicked);
+            Event::Exchange(exchange::Message::Kicked {
+                target,
+                issued_by,
+                reason,
+            }) => {
+                if self.id == target {
+                    ctx.ws_send(ModerationEvent::Kicked(Kicked { reason }));
                     ctx.exit_normal(LeaveReason::Kicked);
+                } else {
+                    ctx.ws_send(ModerationEvent::ParticipantRemoved(ParticipantRemoved {
+                        participant: target,
+                        issued_by,
+                        banned: false,
+                    }));
                 }
             }
             Event::Exchange(exchange::Message::SentToWaitingRoom(participant)) => {
@@ -623,6 +684,18 @@ impl SignalingModule for ModerationModule {
                 log::error!("Failed to clean up bans list {}", Report::from_error(e));
             }
 
+            if let Err(e) = ctx
+                .volatile
+                .moderation_storage()
+                .delete_guest_bans(self.room.room_id())
+                .await
+            {
+                log::error!(
+                    "Failed to clean up guest bans list {}",
+                    Report::from_error(e)
+                );
+            }
+
             if let Err(e) = ctx
                 .volatile
                 .moderation_storage()
@@ -712,6 +785,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sanitized_reason() {
+        assert_eq!(sanitize_reason(None), None);
+        assert_eq!(sanitize_reason(Some("   ".to_string())), None);
+        assert_eq!(
+            sanitize_reason(Some(" spamming the chat ".to_string())),
+            Some("spamming the chat".to_string())
+        );
+        assert_eq!(
+            sanitize_reason(Some("x".repeat(300))).map(|reason| reason.chars().count()),
+            Some(MAX_REASON_LENGTH)
+        );
+    }
+
     #[test]
     fn frontend_data_for_user() {
         assert_eq!(
@@ -44,6 +44,39 @@ mod test_common {
         assert!(!storage.is_user_banned(ROOM, ALICE_USER).await.unwrap());
     }
 
+    pub(super) async fn guest_bans(storage: &mut dyn ModerationStorage) {
+        assert!(
+            !storage
+                .is_guest_banned(ROOM, BOB_PARTICIPANT)
+                .await
+                .unwrap()
+        );
+
+        storage.ban_guest(ROOM, BOB_PARTICIPANT).await.unwrap();
+
+        assert!(
+            storage
+                .is_guest_banned(ROOM, BOB_PARTICIPANT)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !storage
+                .is_guest_banned(ROOM, ALICE_PARTICIPANT)
+                .await
+                .unwrap()
+        );
+
+        storage.delete_guest_bans(ROOM).await.unwrap();
+
+        assert!(
+            !storage
+                .is_guest_banned(ROOM, BOB_PARTICIPANT)
+                .await
+                .unwrap()
+        );
+    }
+
     pub(super) async fn waiting_room_enabled_flag(storage: &mut dyn ModerationStorage) {
         assert!(!storage.is_waiting_room_enabled(ROOM).await.unwrap());
 
@@ -31,6 +31,22 @@ pub trait ModerationStorage:
 
     async fn delete_user_bans(&mut self, room: RoomId) -> Result<(), SignalingModuleError>;
 
+    /// Ban a guest from the room, identified by the participant id kept across reconnects
+    /// through the resumption token
+    async fn ban_guest(
+        &mut self,
+        room: RoomId,
+        participant: ParticipantId,
+    ) -> Result<(), SignalingModuleError>;
+
+    async fn is_guest_banned(
+        &mut self,
+        room: RoomId,
+        participant: ParticipantId,
+    ) -> Result<bool, SignalingModuleError>;
+
+    async fn delete_guest_bans(&mut self, room: RoomId) -> Result<(), SignalingModuleError>;
+
     /// Return the `waiting_room` flag, and optionally set it to a defined value
     /// given by the `enabled` parameter beforehand if the flag is not present yet.
     async fn init_waiting_room_enabled(
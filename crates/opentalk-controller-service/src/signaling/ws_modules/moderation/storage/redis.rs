@@ -43,6 +43,39 @@ impl ModerationStorage for RedisConnection {
         })
     }
 
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn ban_guest(
+        &mut self,
+        room: RoomId,
+        participant: ParticipantId,
+    ) -> Result<(), SignalingModuleError> {
+        self.sadd(GuestBans { room }, participant)
+            .await
+            .context(RedisSnafu {
+                message: "Failed to SADD participant_id to guest bans",
+            })
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn is_guest_banned(
+        &mut self,
+        room: RoomId,
+        participant: ParticipantId,
+    ) -> Result<bool, SignalingModuleError> {
+        self.sismember(GuestBans { room }, participant)
+            .await
+            .context(RedisSnafu {
+                message: "Failed to SISMEMBER participant_id on guest bans",
+            })
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn delete_guest_bans(&mut self, room: RoomId) -> Result<(), SignalingModuleError> {
+        self.del(GuestBans { room }).await.context(RedisSnafu {
+            message: "Failed to DEL guest bans",
+        })
+    }
+
     #[tracing::instrument(level = "debug", skip(self))]
     async fn init_waiting_room_enabled(
         &mut self,
@@ -296,6 +329,13 @@ struct Bans {
     room: RoomId,
 }
 
+/// Set of participant-ids of guests banned in a room
+#[derive(ToRedisArgs)]
+#[to_redis_args(fmt = "opentalk-signaling:room={room}:guest_bans")]
+struct GuestBans {
+    room: RoomId,
+}
+
 /// If set to true the waiting room is enabled
 #[derive(ToRedisArgs)]
 #[to_redis_args(fmt = "opentalk-signaling:room={room}:waiting_room_enabled")]
@@ -349,6 +389,12 @@ mod tests {
         test_common::user_bans(&mut storage().await).await;
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn guest_bans() {
+        test_common::guest_bans(&mut storage().await).await;
+    }
+
     #[tokio::test]
     #[serial]
     async fn waiting_room_enabled_flag() {
@@ -10,6 +10,7 @@ use opentalk_types_signaling::ParticipantId;
 #[derive(Debug, Clone, Default)]
 pub(super) struct MemoryModerationState {
     banned_users: HashMap<RoomId, HashSet<UserId>>,
+    banned_guests: HashMap<RoomId, HashSet<ParticipantId>>,
     waiting_room_enabled: HashMap<RoomId, bool>,
     raise_hands_enabled: HashMap<RoomId, bool>,
     waiting_room_participants: HashMap<RoomId, HashSet<ParticipantId>>,
@@ -37,6 +38,25 @@ impl MemoryModerationState {
         _ = self.banned_users.remove(&room);
     }
 
+    pub(super) fn ban_guest(&mut self, room: RoomId, participant: ParticipantId) {
+        _ = self
+            .banned_guests
+            .entry(room)
+            .or_default()
+            .insert(participant);
+    }
+
+    pub(super) fn is_guest_banned(&self, room: RoomId, participant: ParticipantId) -> bool {
+        self.banned_guests
+            .get(&room)
+            .map(|b| b.contains(&participant))
+            .unwrap_or_default()
+    }
+
+    pub(super) fn delete_guest_bans(&mut self, room: RoomId) {
+        _ = self.banned_guests.remove(&room);
+    }
+
     pub(super) fn init_waiting_room_enabled(&mut self, room: RoomId, enabled: bool) -> bool {
         *self.waiting_room_enabled.entry(room).or_insert(enabled)
     }
@@ -45,6 +45,31 @@ impl ModerationStorage for VolatileStaticMemoryStorage {
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn ban_guest(
+        &mut self,
+        room: RoomId,
+        participant: ParticipantId,
+    ) -> Result<(), SignalingModuleError> {
+        state().write().ban_guest(room, participant);
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn is_guest_banned(
+        &mut self,
+        room: RoomId,
+        participant: ParticipantId,
+    ) -> Result<bool, SignalingModuleError> {
+        Ok(state().read().is_guest_banned(room, participant))
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn delete_guest_bans(&mut self, room: RoomId) -> Result<(), SignalingModuleError> {
+        state().write().delete_guest_bans(room);
+        Ok(())
+    }
+
     #[tracing::instrument(level = "debug", skip(self))]
     async fn init_waiting_room_enabled(
         &mut self,
@@ -242,6 +267,12 @@ mod tests {
         test_common::user_bans(&mut storage().await).await;
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn guest_bans() {
+        test_common::guest_bans(&mut storage().await).await;
+    }
+
     #[tokio::test]
     #[serial]
     async fn waiting_room_enabled_flag() {
@@ -41,12 +41,17 @@ pub use settings_error::SettingsError;
 pub use settings_file::SettingsRaw;
 pub use settings_provider::SettingsProvider;
 pub use settings_runtime::{
-    Avatar, CallIn, DEFAULT_EXTERNAL_TENANT_ID_USER_ATTRIBUTE_NAME, DEFAULT_LIBRAVATAR_URL,
-    DEFAULT_STATIC_TARIFF_NAME, DEFAULT_STATIC_TENANT_ID, Database, Defaults, Endpoints, Etcd,
-    Etherpad, Frontend, Http, HttpTls, LiveKit, Logging, LoggingOltpTracing, Metrics, MinIO,
-    Monitoring, Oidc, OidcController, OidcFrontend, OperatorInformation, Settings, SharedFolder,
-    Spacedeck, SubroomAudio, TariffAssignment, TariffStatusMapping, Tariffs, TenantAssignment,
-    Tenants, UserSearchBackend, UserSearchBackendKeycloak,
+    Automod, Avatar, CallIn, Chat, ConnectionLimit, DEFAULT_AUTOMOD_ANIMATION_DURATION,
+    DEFAULT_AUTOMOD_MAX_TIME_LIMIT, DEFAULT_CHAT_MAX_MESSAGE_SIZE,
+    DEFAULT_EXTERNAL_TENANT_ID_USER_ATTRIBUTE_NAME, DEFAULT_LEGAL_VOTE_MAX_ALLOWED_PARTICIPANTS,
+    DEFAULT_LIBRAVATAR_URL, DEFAULT_MAX_CONCURRENT_PDF_GENERATIONS, DEFAULT_STATIC_TARIFF_NAME,
+    DEFAULT_STATIC_TENANT_ID, Database, Defaults, DisplayNamePolicy, DisplayNameRules, Endpoints,
+    Etcd, Etherpad, FeatureOverrides, Frontend, Http, HttpCors, HttpTls, HttpTlsClientAuth,
+    LegalVote, LegalVoteTenant, LiveKit, Logging, LoggingOltpTracing, Metrics, MinIO, Monitoring,
+    Oidc, OidcController, OidcFrontend, OperatorInformation, Reports, ReportsVerificationQrCode,
+    Settings, SharedFolder, Spacedeck, StreamingProbe, SubroomAudio, TariffAssignment,
+    TariffStatusMapping, Tariffs, TenantAssignment, TenantFeatureOverrides, Tenants,
+    TrainingParticipationReport, UserSearchBackend, UserSearchBackendKeycloak, Webhook,
 };
 
 type Result<T, E = SettingsError> = std::result::Result<T, E>;
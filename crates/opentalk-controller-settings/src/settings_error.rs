@@ -46,4 +46,9 @@ pub enum SettingsError {
         "Found `user_search.users_find_behavior` value `from_user_search_backend`, but no user search backend is configured"
     ))]
     UsersFindBehaviorBackendMissing,
+
+    #[snafu(display(
+        "Found `http.http2` value `false` together with `[http.tls]`, HTTP/2 is always offered on TLS connections"
+    ))]
+    Http2DisabledWithTls,
 }
@@ -0,0 +1,17 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use serde::Deserialize;
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub(crate) struct Automod {
+    #[serde(default)]
+    pub persist_state: bool,
+
+    #[serde(default)]
+    pub max_time_limit_seconds: Option<u64>,
+
+    #[serde(default)]
+    pub animation_duration_seconds: Option<u64>,
+}
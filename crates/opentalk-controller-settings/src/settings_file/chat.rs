@@ -0,0 +1,14 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use serde::Deserialize;
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub(crate) struct Chat {
+    #[serde(default)]
+    pub max_message_size: Option<usize>,
+
+    #[serde(default)]
+    pub max_history_length: Option<usize>,
+}
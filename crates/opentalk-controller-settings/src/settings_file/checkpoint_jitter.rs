@@ -0,0 +1,16 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use serde::{Deserialize, Serialize};
+
+/// The distribution of the random delay before a training participation checkpoint
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckpointJitter {
+    /// Every delay within the configured range is equally likely
+    #[default]
+    Uniform,
+    /// Shorter delays are more likely, checkpoints cluster towards the start of the range
+    FrontLoaded,
+}
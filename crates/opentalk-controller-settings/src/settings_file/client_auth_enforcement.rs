@@ -0,0 +1,16 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use serde::Deserialize;
+
+/// How client certificates are enforced for the service endpoints
+#[derive(Default, Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ClientAuthEnforcement {
+    /// Requests without a valid client certificate are rejected
+    #[default]
+    Required,
+    /// Requests without a valid client certificate are logged, but not rejected
+    Optional,
+}
@@ -0,0 +1,60 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+pub(crate) struct ConnectionLimit {
+    #[serde(default)]
+    pub max_connections_per_user: Option<u32>,
+
+    /// Overrides of the connection limit, keyed by the OIDC tenant id
+    #[serde(default)]
+    pub tenants: Option<BTreeMap<String, ConnectionLimitTenant>>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+pub(crate) struct ConnectionLimitTenant {
+    #[serde(default)]
+    pub max_connections_per_user: Option<u32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde::Deserialize;
+
+    use super::ConnectionLimit;
+
+    #[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+    struct DummySettings {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        connection_limit: Option<ConnectionLimit>,
+    }
+
+    #[test]
+    fn tenant_overrides() {
+        let toml_settings: DummySettings = toml::from_str(
+            r#"
+            [connection_limit]
+            max_connections_per_user = 3
+
+            [connection_limit.tenants.strict]
+            max_connections_per_user = 1
+
+            [connection_limit.tenants.unlimited]
+        "#,
+        )
+        .unwrap();
+
+        let connection_limit =
+            crate::settings_runtime::ConnectionLimit::from(toml_settings.connection_limit.unwrap());
+
+        assert_eq!(connection_limit.limit_for("default"), Some(3));
+        assert_eq!(connection_limit.limit_for("strict"), Some(1));
+        assert_eq!(connection_limit.limit_for("unlimited"), None);
+    }
+}
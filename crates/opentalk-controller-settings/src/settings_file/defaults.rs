@@ -7,6 +7,8 @@ use std::collections::BTreeSet;
 use opentalk_types_common::{features::ModuleFeatureId, users::Language};
 use serde::Deserialize;
 
+use super::ScreenSharePolicy;
+
 #[derive(Clone, Default, Debug, PartialEq, Eq, Deserialize)]
 pub(crate) struct Defaults {
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -15,6 +17,56 @@ pub(crate) struct Defaults {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub screen_share_requires_permission: Option<bool>,
 
+    /// Takes precedence over `screen_share_requires_permission`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub screen_share_policy: Option<ScreenSharePolicy>,
+
     #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
     pub disabled_features: BTreeSet<ModuleFeatureId>,
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::{Defaults, ScreenSharePolicy};
+
+    fn screen_share_policy(toml: &str) -> ScreenSharePolicy {
+        let defaults: Defaults = toml::from_str(toml).unwrap();
+        crate::settings_runtime::Defaults::from(defaults).screen_share_policy
+    }
+
+    #[test]
+    fn screen_share_policy_from_legacy_flag() {
+        assert_eq!(screen_share_policy(""), ScreenSharePolicy::Everyone);
+        assert_eq!(
+            screen_share_policy("screen_share_requires_permission = false"),
+            ScreenSharePolicy::Everyone
+        );
+        assert_eq!(
+            screen_share_policy("screen_share_requires_permission = true"),
+            ScreenSharePolicy::RequiresPermission
+        );
+    }
+
+    #[test]
+    fn screen_share_policy_takes_precedence() {
+        assert_eq!(
+            screen_share_policy(
+                r#"
+                screen_share_requires_permission = true
+                screen_share_policy = "moderators_only"
+                "#
+            ),
+            ScreenSharePolicy::ModeratorsOnly
+        );
+        assert_eq!(
+            screen_share_policy(r#"screen_share_policy = "everyone""#),
+            ScreenSharePolicy::Everyone
+        );
+        assert_eq!(
+            screen_share_policy(r#"screen_share_policy = "requires_permission""#),
+            ScreenSharePolicy::RequiresPermission
+        );
+    }
+}
@@ -0,0 +1,86 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+pub(crate) struct DisplayNamePolicy {
+    #[serde(default)]
+    pub enforce: Option<bool>,
+
+    #[serde(default)]
+    pub min_length: Option<usize>,
+
+    #[serde(default)]
+    pub max_length: Option<usize>,
+
+    #[serde(default)]
+    pub disallowed_characters: Option<String>,
+
+    /// Overrides of the display name policy, keyed by the OIDC tenant id
+    #[serde(default)]
+    pub tenants: Option<BTreeMap<String, DisplayNamePolicyTenant>>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+pub(crate) struct DisplayNamePolicyTenant {
+    #[serde(default)]
+    pub enforce: Option<bool>,
+
+    #[serde(default)]
+    pub min_length: Option<usize>,
+
+    #[serde(default)]
+    pub max_length: Option<usize>,
+
+    #[serde(default)]
+    pub disallowed_characters: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde::Deserialize;
+
+    use super::DisplayNamePolicy;
+
+    #[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+    struct DummySettings {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        display_name_policy: Option<DisplayNamePolicy>,
+    }
+
+    #[test]
+    fn tenant_overrides() {
+        let toml_settings: DummySettings = toml::from_str(
+            r#"
+            [display_name_policy]
+            enforce = true
+            min_length = 3
+            disallowed_characters = "<>"
+
+            [display_name_policy.tenants.lenient]
+            enforce = false
+        "#,
+        )
+        .unwrap();
+
+        let policy = crate::settings_runtime::DisplayNamePolicy::from(
+            toml_settings.display_name_policy.unwrap(),
+        );
+
+        let rules = policy.rules_for(Some("default"));
+        assert!(rules.enforce);
+        assert!(rules.is_valid("Alice"));
+        assert!(!rules.is_valid("Al"));
+        assert!(!rules.is_valid("   Al   "));
+        assert!(!rules.is_valid("<b>Alice</b>"));
+        assert!(!rules.is_valid(&"a".repeat(101)));
+
+        assert_eq!(policy.rules_for(None), &policy.rules);
+        assert!(!policy.rules_for(Some("lenient")).enforce);
+    }
+}
@@ -20,4 +20,7 @@ pub(crate) struct Endpoints {
 
     #[serde(default)]
     pub(crate) disable_openapi: Option<bool>,
+
+    #[serde(default)]
+    pub(crate) soft_delete_rooms: Option<bool>,
 }
@@ -0,0 +1,74 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use opentalk_types_common::{features::ModuleFeatureId, modules::ModuleId};
+use serde::Deserialize;
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+pub(crate) struct FeatureOverrides {
+    /// Feature overrides keyed by the OIDC tenant id
+    #[serde(default)]
+    pub tenants: Option<BTreeMap<String, FeatureOverridesTenant>>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Eq)]
+pub(crate) struct FeatureOverridesTenant {
+    #[serde(default)]
+    pub enabled_modules: Option<BTreeSet<ModuleId>>,
+
+    #[serde(default)]
+    pub disabled_modules: Option<BTreeSet<ModuleId>>,
+
+    #[serde(default)]
+    pub enabled_features: Option<BTreeSet<ModuleFeatureId>>,
+
+    #[serde(default)]
+    pub disabled_features: Option<BTreeSet<ModuleFeatureId>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use pretty_assertions::assert_eq;
+    use serde::Deserialize;
+
+    use super::FeatureOverrides;
+
+    #[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+    struct DummySettings {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        feature_overrides: Option<FeatureOverrides>,
+    }
+
+    #[test]
+    fn tenant_overrides() {
+        let toml_settings: DummySettings = toml::from_str(
+            r#"
+            [feature_overrides.tenants.incident]
+            disabled_modules = ["recording"]
+
+            [feature_overrides.tenants.premium]
+            enabled_features = ["call_in::call_in"]
+        "#,
+        )
+        .unwrap();
+
+        let overrides = crate::settings_runtime::FeatureOverrides::from(
+            toml_settings.feature_overrides.unwrap(),
+        );
+
+        assert!(overrides.for_tenant("default").is_none());
+        assert_eq!(
+            overrides.for_tenant("incident").unwrap().disabled_modules,
+            BTreeSet::from(["recording".parse().expect("valid module id")])
+        );
+        assert_eq!(
+            overrides.for_tenant("premium").unwrap().enabled_features,
+            BTreeSet::from(["call_in::call_in".parse().expect("valid module feature id")])
+        );
+    }
+}
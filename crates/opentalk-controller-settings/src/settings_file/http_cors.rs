@@ -0,0 +1,11 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use serde::Deserialize;
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct HttpCors {
+    #[serde(default)]
+    pub allowed_origins: Option<Vec<String>>,
+}
@@ -6,8 +6,13 @@ use std::path::PathBuf;
 
 use serde::Deserialize;
 
+use super::HttpTlsClientAuth;
+
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
 pub struct HttpTls {
     pub certificate: PathBuf,
     pub private_key: PathBuf,
+
+    #[serde(default)]
+    pub client_auth: Option<HttpTlsClientAuth>,
 }
@@ -0,0 +1,17 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use super::ClientAuthEnforcement;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct HttpTlsClientAuth {
+    pub ca_certificate: PathBuf,
+
+    #[serde(default)]
+    pub enforcement: ClientAuthEnforcement,
+}
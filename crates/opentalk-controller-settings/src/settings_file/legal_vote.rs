@@ -0,0 +1,108 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Deserialize;
+
+use super::{LegalVoteKind, LegalVotePdfPolicy};
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+pub(crate) struct LegalVote {
+    #[serde(default)]
+    pub allowed_vote_kinds: Option<BTreeSet<LegalVoteKind>>,
+
+    #[serde(default)]
+    pub max_allowed_participants: Option<usize>,
+
+    #[serde(default)]
+    pub enable_abstain_by_default: Option<bool>,
+
+    #[serde(default)]
+    pub initiator_grace_period_seconds: Option<u64>,
+
+    #[serde(default)]
+    pub pdf_policy: Option<LegalVotePdfPolicy>,
+
+    /// Overrides of the allowed vote kinds and defaults, keyed by the OIDC tenant id
+    #[serde(default)]
+    pub tenants: Option<BTreeMap<String, LegalVoteTenant>>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+pub(crate) struct LegalVoteTenant {
+    #[serde(default)]
+    pub allowed_vote_kinds: Option<BTreeSet<LegalVoteKind>>,
+
+    #[serde(default)]
+    pub enable_abstain_by_default: Option<bool>,
+
+    #[serde(default)]
+    pub pdf_policy: Option<LegalVotePdfPolicy>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use pretty_assertions::assert_eq;
+    use serde::Deserialize;
+
+    use super::LegalVote;
+    use crate::settings_file::{LegalVoteKind, LegalVotePdfPolicy};
+
+    #[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+    struct DummySettings {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        legal_vote: Option<LegalVote>,
+    }
+
+    #[test]
+    fn tenant_overrides() {
+        let toml_settings: DummySettings = toml::from_str(
+            r#"
+            [legal_vote]
+            allowed_vote_kinds = ["roll_call", "live_roll_call"]
+
+            [legal_vote.tenants.hidden-only]
+            allowed_vote_kinds = ["pseudonymous"]
+
+            [legal_vote.tenants.abstain]
+            enable_abstain_by_default = true
+
+            [legal_vote.tenants.compliance]
+            pdf_policy = "always"
+        "#,
+        )
+        .unwrap();
+
+        let legal_vote =
+            crate::settings_runtime::LegalVote::from(toml_settings.legal_vote.unwrap());
+
+        assert_eq!(
+            legal_vote.allowed_vote_kinds_for("default"),
+            &BTreeSet::from([LegalVoteKind::RollCall, LegalVoteKind::LiveRollCall])
+        );
+        assert_eq!(
+            legal_vote.allowed_vote_kinds_for("hidden-only"),
+            &BTreeSet::from([LegalVoteKind::Pseudonymous])
+        );
+        assert_eq!(
+            legal_vote.allowed_vote_kinds_for("abstain"),
+            &BTreeSet::from([LegalVoteKind::RollCall, LegalVoteKind::LiveRollCall])
+        );
+
+        assert!(!legal_vote.enable_abstain_by_default_for("default"));
+        assert!(legal_vote.enable_abstain_by_default_for("abstain"));
+
+        assert_eq!(
+            legal_vote.pdf_policy_for("default"),
+            LegalVotePdfPolicy::PerVote
+        );
+        assert_eq!(
+            legal_vote.pdf_policy_for("compliance"),
+            LegalVotePdfPolicy::Always
+        );
+    }
+}
@@ -0,0 +1,22 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use serde::{Deserialize, Serialize};
+
+/// The kinds of legal votes which can be allowed or disallowed by the settings
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum LegalVoteKind {
+    /// A vote where the voters are recorded and published when the vote ends
+    RollCall,
+    /// A vote where the voters are published while the vote is ongoing
+    LiveRollCall,
+    /// A vote where the voters are not recorded
+    Pseudonymous,
+}
+
+impl LegalVoteKind {
+    /// All legal vote kinds
+    pub const ALL: [Self; 3] = [Self::RollCall, Self::LiveRollCall, Self::Pseudonymous];
+}
@@ -0,0 +1,29 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use serde::{Deserialize, Serialize};
+
+/// The policy deciding whether a PDF report is generated for a legal vote
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum LegalVotePdfPolicy {
+    /// A PDF report is generated for every vote, regardless of the vote parameters
+    Always,
+    /// No PDF report is generated, regardless of the vote parameters
+    Never,
+    /// The initiator of the vote decides whether a PDF report is generated
+    #[default]
+    PerVote,
+}
+
+impl LegalVotePdfPolicy {
+    /// The value of `create_pdf` enforced by the policy, `None` if the initiator decides
+    pub fn enforced_create_pdf(self) -> Option<bool> {
+        match self {
+            Self::Always => Some(true),
+            Self::Never => Some(false),
+            Self::PerVote => None,
+        }
+    }
+}
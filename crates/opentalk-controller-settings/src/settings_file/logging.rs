@@ -4,6 +4,8 @@
 
 use serde::Deserialize;
 
+use super::PiiRedaction;
+
 #[derive(Default, Debug, Clone, PartialEq, Eq, Deserialize)]
 pub(crate) struct Logging {
     pub default_directives: Option<Vec<String>>,
@@ -15,4 +17,6 @@ pub(crate) struct Logging {
     pub service_namespace: Option<String>,
 
     pub service_instance_id: Option<String>,
+
+    pub pii_redaction: Option<PiiRedaction>,
 }
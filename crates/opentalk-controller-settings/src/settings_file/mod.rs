@@ -3,18 +3,30 @@
 // SPDX-License-Identifier: EUPL-1.2
 
 mod authz;
+mod automod;
 mod avatar;
 mod call_in;
+mod chat;
+mod checkpoint_jitter;
+mod client_auth_enforcement;
+mod connection_limit;
 mod database;
 mod defaults;
+mod display_name_policy;
 mod endpoints;
 mod etcd;
 mod etherpad;
 mod extensions;
+mod feature_overrides;
 mod frontend;
 mod http;
+mod http_cors;
 mod http_tls;
+mod http_tls_client_auth;
 mod keycloak;
+mod legal_vote;
+mod legal_vote_kind;
+mod legal_vote_pdf_policy;
 mod live_kit_settings;
 mod logging;
 mod metrics;
@@ -24,37 +36,56 @@ mod oidc;
 mod oidc_controller;
 mod oidc_frontend;
 mod operator_information;
+mod pii_redaction;
 mod rabbit_mq_config;
 mod redis_config;
 mod reports;
 mod reports_template;
+mod reports_verification_qr_code;
 mod roomserver;
+mod screen_share_policy;
 mod settings_raw;
 mod shared_folder;
 mod spacedeck;
+mod streaming_probe;
 mod subroom_audio;
 mod tariff_assignment;
 mod tariff_status_mapping;
 mod tariffs;
 mod tenant_assignment;
 mod tenants;
+mod training_participation_report;
 mod user_search;
 mod user_search_backend;
 mod users_find_behavior;
+mod webhook;
+mod webhook_event_type;
 
 pub(crate) use authz::Authz;
+pub(crate) use automod::Automod;
 pub(crate) use avatar::Avatar;
 pub(crate) use call_in::CallIn;
+pub(crate) use chat::Chat;
+pub use checkpoint_jitter::CheckpointJitter;
+pub use client_auth_enforcement::ClientAuthEnforcement;
+pub(crate) use connection_limit::{ConnectionLimit, ConnectionLimitTenant};
 pub(crate) use database::Database;
 pub(crate) use defaults::Defaults;
+pub(crate) use display_name_policy::{DisplayNamePolicy, DisplayNamePolicyTenant};
 pub(crate) use endpoints::Endpoints;
 pub(crate) use etcd::Etcd;
 pub(crate) use etherpad::Etherpad;
 pub(crate) use extensions::Extensions;
+pub(crate) use feature_overrides::{FeatureOverrides, FeatureOverridesTenant};
 pub(crate) use frontend::Frontend;
 pub(crate) use http::Http;
+pub(crate) use http_cors::HttpCors;
 pub(crate) use http_tls::HttpTls;
+pub(crate) use http_tls_client_auth::HttpTlsClientAuth;
 pub(crate) use keycloak::Keycloak;
+pub(crate) use legal_vote::{LegalVote, LegalVoteTenant};
+pub use legal_vote_kind::LegalVoteKind;
+pub use legal_vote_pdf_policy::LegalVotePdfPolicy;
 pub(crate) use live_kit_settings::LiveKitSettings;
 pub(crate) use logging::Logging;
 pub(crate) use metrics::Metrics;
@@ -64,22 +95,29 @@ pub(crate) use oidc::Oidc;
 pub(crate) use oidc_controller::OidcController;
 pub(crate) use oidc_frontend::OidcFrontend;
 pub(crate) use operator_information::OperatorInformation;
+pub use pii_redaction::PiiRedaction;
 pub(crate) use rabbit_mq_config::RabbitMqConfig;
 pub(crate) use redis_config::RedisConfig;
 pub(crate) use reports::Reports;
 pub(crate) use reports_template::ReportsTemplate;
+pub(crate) use reports_verification_qr_code::ReportsVerificationQrCode;
 pub(crate) use roomserver::RoomServer;
+pub use screen_share_policy::ScreenSharePolicy;
 pub use settings_raw::SettingsRaw;
 #[cfg(test)]
 pub(crate) use settings_raw::{SETTINGS_RAW_MINIMAL_CONFIG_TOML, settings_raw_minimal_example};
 pub(crate) use shared_folder::SharedFolder;
 pub(crate) use spacedeck::Spacedeck;
+pub(crate) use streaming_probe::{StreamingProbe, StreamingProbeTenant};
 pub(crate) use subroom_audio::SubroomAudio;
 pub(crate) use tariff_assignment::TariffAssignment;
 pub(crate) use tariff_status_mapping::TariffStatusMapping;
-pub(crate) use tariffs::Tariffs;
+pub(crate) use tariffs::{Tariffs, TariffsTenant};
 pub(crate) use tenant_assignment::TenantAssignment;
 pub(crate) use tenants::Tenants;
+pub(crate) use training_participation_report::TrainingParticipationReport;
 pub(crate) use user_search::UserSearch;
 pub(crate) use user_search_backend::{UserSearchBackend, UserSearchBackendKeycloakWebapi};
 pub use users_find_behavior::UsersFindBehavior;
+pub(crate) use webhook::Webhook;
+pub use webhook_event_type::WebhookEventType;
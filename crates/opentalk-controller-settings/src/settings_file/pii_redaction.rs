@@ -0,0 +1,18 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use serde::{Deserialize, Serialize};
+
+/// How personally identifiable information is treated in the log output
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PiiRedaction {
+    /// Identifiers are logged as they are
+    #[default]
+    Off,
+    /// Identifiers are replaced with a placeholder
+    Redact,
+    /// Identifiers are replaced with a hash which is stable while the controller is running
+    Hash,
+}
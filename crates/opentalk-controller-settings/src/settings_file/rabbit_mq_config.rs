@@ -15,6 +15,12 @@ pub(crate) struct RabbitMqConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_channels_per_connection: Option<u32>,
 
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub startup_retries: Option<u32>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub startup_retry_interval_seconds: Option<u64>,
+
     /// Mail sending is disabled when this is None
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub mail_task_queue: Option<String>,
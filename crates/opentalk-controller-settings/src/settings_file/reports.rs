@@ -2,21 +2,45 @@
 //
 // SPDX-License-Identifier: EUPL-1.2
 
+use std::{collections::BTreeMap, path::PathBuf};
+
+use opentalk_types_common::time::TimeZone;
 use serde::Deserialize;
 
-use super::ReportsTemplate;
+use super::{ReportsTemplate, ReportsVerificationQrCode};
 
 #[derive(Default, Clone, Debug, Deserialize, PartialEq, Eq)]
 pub(crate) struct Reports {
     #[serde(default)]
     pub template: ReportsTemplate,
+
+    #[serde(default)]
+    pub max_concurrent_pdf_generations: Option<usize>,
+
+    #[serde(default)]
+    pub dump_path: Option<PathBuf>,
+
+    #[serde(default)]
+    pub font_path: Option<PathBuf>,
+
+    #[serde(default)]
+    pub verification_qr_code: Option<ReportsVerificationQrCode>,
+
+    #[serde(default)]
+    pub tenant_timezones: Option<BTreeMap<String, TimeZone>>,
 }
 
 #[cfg(test)]
 mod tests {
+    use std::{
+        collections::{BTreeMap, BTreeSet},
+        path::PathBuf,
+    };
+
+    use opentalk_types_common::time::TimeZone;
     use pretty_assertions::assert_eq;
 
-    use crate::settings_file::{Reports, ReportsTemplate};
+    use crate::settings_file::{Reports, ReportsTemplate, ReportsVerificationQrCode};
 
     #[test]
     fn meeting_report_settings() {
@@ -29,7 +53,12 @@ mod tests {
         assert_eq!(
             toml_settings,
             Reports {
-                template: ReportsTemplate::BuiltIn
+                template: ReportsTemplate::BuiltIn,
+                max_concurrent_pdf_generations: None,
+                dump_path: None,
+                font_path: None,
+                verification_qr_code: None,
+                tenant_timezones: None,
             }
         );
 
@@ -37,13 +66,30 @@ mod tests {
             r#"
         url = "http://localhost"
         template.inline = "lorem ipsum"
+        max_concurrent_pdf_generations = 2
+        dump_path = "/tmp/reports"
+        font_path = "/usr/share/fonts/opentalk"
+        verification_qr_code.url = "https://opentalk.example.org/verify/{asset_id}"
+        verification_qr_code.tenants = ["example-tenant"]
+        tenant_timezones.example-tenant = "Europe/Vienna"
         "#,
         )
         .unwrap();
         assert_eq!(
             toml_settings,
             Reports {
-                template: ReportsTemplate::Inline("lorem ipsum".to_string())
+                template: ReportsTemplate::Inline("lorem ipsum".to_string()),
+                max_concurrent_pdf_generations: Some(2),
+                dump_path: Some(PathBuf::from("/tmp/reports")),
+                font_path: Some(PathBuf::from("/usr/share/fonts/opentalk")),
+                verification_qr_code: Some(ReportsVerificationQrCode {
+                    url: "https://opentalk.example.org/verify/{asset_id}".to_string(),
+                    tenants: Some(BTreeSet::from(["example-tenant".to_string()])),
+                }),
+                tenant_timezones: Some(BTreeMap::from([(
+                    "example-tenant".to_string(),
+                    TimeZone::from(chrono_tz::Europe::Vienna),
+                )])),
             }
         );
     }
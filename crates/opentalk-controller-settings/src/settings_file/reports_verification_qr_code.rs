@@ -0,0 +1,17 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use std::collections::BTreeSet;
+
+use serde::Deserialize;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+pub(crate) struct ReportsVerificationQrCode {
+    /// The url encoded into the QR code, may contain `{room_id}` and `{asset_id}` placeholders
+    pub url: String,
+
+    /// The OIDC tenant ids for which the QR code is added to the reports
+    #[serde(default)]
+    pub tenants: Option<BTreeSet<String>>,
+}
@@ -0,0 +1,48 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use serde::{Deserialize, Serialize};
+
+/// Who may start a screen share in a meeting
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ScreenSharePolicy {
+    /// All participants may share their screen
+    #[default]
+    Everyone,
+    /// Moderators may share their screen, other participants only after a moderator granted
+    /// them the permission
+    ModeratorsOnly,
+    /// All participants, including moderators, may only share their screen after the
+    /// permission was granted
+    RequiresPermission,
+}
+
+impl ScreenSharePolicy {
+    /// Whether a participant may share their screen without an explicit permission
+    pub fn allows_without_permission(self, is_moderator: bool) -> bool {
+        match self {
+            Self::Everyone => true,
+            Self::ModeratorsOnly => is_moderator,
+            Self::RequiresPermission => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ScreenSharePolicy;
+
+    #[test]
+    fn allows_without_permission() {
+        assert!(ScreenSharePolicy::Everyone.allows_without_permission(false));
+        assert!(ScreenSharePolicy::Everyone.allows_without_permission(true));
+
+        assert!(!ScreenSharePolicy::ModeratorsOnly.allows_without_permission(false));
+        assert!(ScreenSharePolicy::ModeratorsOnly.allows_without_permission(true));
+
+        assert!(!ScreenSharePolicy::RequiresPermission.allows_without_permission(false));
+        assert!(!ScreenSharePolicy::RequiresPermission.allows_without_permission(true));
+    }
+}
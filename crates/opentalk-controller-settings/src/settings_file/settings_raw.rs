@@ -5,10 +5,11 @@
 use serde::Deserialize;
 
 use super::{
-    Authz, Avatar, CallIn, Database, Defaults, Endpoints, Etcd, Etherpad, Extensions, Frontend,
-    Http, Keycloak, LiveKitSettings, Logging, Metrics, MinIO, MonitoringSettings, Oidc,
-    OperatorInformation, RabbitMqConfig, RedisConfig, Reports, RoomServer, SharedFolder, Spacedeck,
-    SubroomAudio, Tariffs, Tenants, UserSearch,
+    Authz, Automod, Avatar, CallIn, Chat, ConnectionLimit, Database, Defaults, DisplayNamePolicy,
+    Endpoints, Etcd, Etherpad, Extensions, FeatureOverrides, Frontend, Http, Keycloak, LegalVote,
+    LiveKitSettings, Logging, Metrics, MinIO, MonitoringSettings, Oidc, OperatorInformation,
+    RabbitMqConfig, RedisConfig, Reports, RoomServer, SharedFolder, Spacedeck, StreamingProbe,
+    SubroomAudio, Tariffs, Tenants, TrainingParticipationReport, UserSearch, Webhook,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
@@ -59,6 +60,30 @@ pub struct SettingsRaw {
     #[serde(default)]
     pub(crate) subroom_audio: Option<SubroomAudio>,
 
+    #[serde(default)]
+    pub(crate) automod: Option<Automod>,
+
+    #[serde(default)]
+    pub(crate) training_participation_report: Option<TrainingParticipationReport>,
+
+    #[serde(default)]
+    pub(crate) chat: Option<Chat>,
+
+    #[serde(default)]
+    pub(crate) legal_vote: Option<LegalVote>,
+
+    #[serde(default)]
+    pub(crate) connection_limit: Option<ConnectionLimit>,
+
+    #[serde(default)]
+    pub(crate) display_name_policy: Option<DisplayNamePolicy>,
+
+    #[serde(default)]
+    pub(crate) streaming_probe: Option<StreamingProbe>,
+
+    #[serde(default)]
+    pub(crate) feature_overrides: Option<FeatureOverrides>,
+
     #[serde(default)]
     pub(crate) reports: Option<Reports>,
 
@@ -90,6 +115,9 @@ pub struct SettingsRaw {
     #[serde(default)]
     pub(crate) roomserver: Option<RoomServer>,
 
+    #[serde(default)]
+    pub(crate) webhook: Option<Webhook>,
+
     #[serde(flatten)]
     pub(crate) extensions: Extensions,
 
@@ -142,6 +170,14 @@ pub(crate) fn settings_raw_minimal_example() -> SettingsRaw {
         etherpad: None,
         spacedeck: None,
         subroom_audio: None,
+        automod: None,
+        training_participation_report: None,
+        chat: None,
+        legal_vote: None,
+        connection_limit: None,
+        display_name_policy: None,
+        streaming_probe: None,
+        feature_overrides: None,
         reports: None,
         shared_folder: None,
         call_in: None,
@@ -165,6 +201,7 @@ pub(crate) fn settings_raw_minimal_example() -> SettingsRaw {
             api_secret: "secret".to_string(),
         },
         roomserver: None,
+        webhook: None,
         extensions: Extensions::default(),
         operator_information: None,
     }
@@ -0,0 +1,63 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+pub(crate) struct StreamingProbe {
+    #[serde(default)]
+    pub enabled: Option<bool>,
+
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+
+    /// Overrides of whether the probe is enabled, keyed by the OIDC tenant id
+    #[serde(default)]
+    pub tenants: Option<BTreeMap<String, StreamingProbeTenant>>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+pub(crate) struct StreamingProbeTenant {
+    #[serde(default)]
+    pub enabled: Option<bool>,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use pretty_assertions::assert_eq;
+    use serde::Deserialize;
+
+    use super::StreamingProbe;
+
+    #[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+    struct DummySettings {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        streaming_probe: Option<StreamingProbe>,
+    }
+
+    #[test]
+    fn tenant_overrides() {
+        let toml_settings: DummySettings = toml::from_str(
+            r#"
+            [streaming_probe]
+            timeout_ms = 1500
+
+            [streaming_probe.tenants.probed]
+            enabled = true
+        "#,
+        )
+        .unwrap();
+
+        let probe =
+            crate::settings_runtime::StreamingProbe::from(toml_settings.streaming_probe.unwrap());
+
+        assert_eq!(probe.timeout, Duration::from_millis(1500));
+        assert!(!probe.is_enabled_for("default"));
+        assert!(probe.is_enabled_for("probed"));
+    }
+}
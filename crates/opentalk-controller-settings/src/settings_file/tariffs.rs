@@ -2,6 +2,8 @@
 //
 // SPDX-License-Identifier: EUPL-1.2
 
+use std::collections::BTreeMap;
+
 use serde::Deserialize;
 
 use super::TariffAssignment;
@@ -10,6 +12,19 @@ use super::TariffAssignment;
 pub(crate) struct Tariffs {
     #[serde(default, flatten, skip_serializing_if = "Option::is_none")]
     pub assignment: Option<TariffAssignment>,
+
+    #[serde(default)]
+    pub default_tariff_name: Option<String>,
+
+    /// Overrides of the default tariff, keyed by the OIDC tenant id
+    #[serde(default)]
+    pub tenants: Option<BTreeMap<String, TariffsTenant>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub(crate) struct TariffsTenant {
+    #[serde(default)]
+    pub default_tariff_name: Option<String>,
 }
 
 #[cfg(test)]
@@ -51,7 +66,11 @@ mod tests {
         assert_eq!(
             toml_settings,
             DummySettings {
-                tariffs: Some(Tariffs { assignment: None })
+                tariffs: Some(Tariffs {
+                    assignment: None,
+                    default_tariff_name: None,
+                    tenants: None,
+                })
             }
         );
     }
@@ -72,7 +91,9 @@ mod tests {
                 tariffs: Some(Tariffs {
                     assignment: Some(TariffAssignment::Static {
                         static_tariff_name: None
-                    })
+                    }),
+                    default_tariff_name: None,
+                    tenants: None,
                 })
             }
         );
@@ -95,7 +116,9 @@ mod tests {
                 tariffs: Some(Tariffs {
                     assignment: Some(TariffAssignment::Static {
                         static_tariff_name: Some("my_tariff".to_string())
-                    })
+                    }),
+                    default_tariff_name: None,
+                    tenants: None,
                 })
             }
         );
@@ -117,7 +140,9 @@ mod tests {
                 tariffs: Some(Tariffs {
                     assignment: Some(TariffAssignment::ByExternalTariffId {
                         status_mapping: None
-                    })
+                    }),
+                    default_tariff_name: None,
+                    tenants: None,
                 })
             }
         );
@@ -153,9 +178,41 @@ mod tests {
                             paid: BTreeSet::from(["paid".to_string(), "all_ok".to_string(),]),
                             downgraded: BTreeSet::from(["unpaid".to_string()])
                         })
-                    })
+                    }),
+                    default_tariff_name: None,
+                    tenants: None,
                 })
             }
         );
     }
+
+    #[test]
+    fn default_tariff_per_tenant() {
+        let toml_settings: DummySettings = toml::from_str(
+            r#"
+            [tariffs]
+            assignment = "by_external_tariff_id"
+            default_tariff_name = "basic"
+
+            [tariffs.tenants.premium]
+            default_tariff_name = "premium_basic"
+
+            [tariffs.tenants.other]
+        "#,
+        )
+        .unwrap();
+
+        let tariffs = crate::settings_runtime::Tariffs::from(toml_settings.tariffs.unwrap());
+
+        assert_eq!(tariffs.default_tariff_name_for("default"), Some("basic"));
+        assert_eq!(
+            tariffs.default_tariff_name_for("premium"),
+            Some("premium_basic")
+        );
+        assert_eq!(tariffs.default_tariff_name_for("other"), Some("basic"));
+        assert_eq!(
+            crate::settings_runtime::Tariffs::default().default_tariff_name_for("default"),
+            None
+        );
+    }
 }
@@ -0,0 +1,13 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use serde::Deserialize;
+
+use super::CheckpointJitter;
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub(crate) struct TrainingParticipationReport {
+    #[serde(default)]
+    pub checkpoint_jitter: CheckpointJitter,
+}
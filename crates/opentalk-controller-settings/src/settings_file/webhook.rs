@@ -0,0 +1,26 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use std::collections::BTreeSet;
+
+use serde::Deserialize;
+use url::Url;
+
+use super::WebhookEventType;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+pub(crate) struct Webhook {
+    pub url: Url,
+
+    pub secret: String,
+
+    #[serde(default)]
+    pub events: Option<BTreeSet<WebhookEventType>>,
+
+    #[serde(default)]
+    pub max_retries: Option<u32>,
+
+    #[serde(default)]
+    pub timeout_seconds: Option<u64>,
+}
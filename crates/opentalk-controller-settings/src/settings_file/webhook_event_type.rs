@@ -0,0 +1,22 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use serde::{Deserialize, Serialize};
+
+/// The room lifecycle events which can be delivered to the webhook endpoint
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum WebhookEventType {
+    /// A room was created through the REST API
+    RoomCreated,
+    /// The first participant joined a room
+    RoomStarted,
+    /// The last participant left a room and its signaling state was torn down
+    RoomEnded,
+}
+
+impl WebhookEventType {
+    /// All event types in their delivery order
+    pub const ALL: [Self; 3] = [Self::RoomCreated, Self::RoomStarted, Self::RoomEnded];
+}
@@ -231,6 +231,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn load_http2_disabled_with_tls() {
+        let tempdir = tempdir().unwrap();
+
+        let path = tempdir.path().join("controller.toml");
+
+        {
+            let mut file = File::create(&path).unwrap();
+            writeln!(
+                file,
+                r#"
+                {SETTINGS_RAW_MINIMAL_CONFIG_TOML}
+
+                [http]
+                http2 = false
+
+                [http.tls]
+                certificate = "/etc/opentalk/controller.crt"
+                private_key = "/etc/opentalk/controller.key"
+                "#
+            )
+            .expect("temp file should be writable");
+        }
+
+        assert_matches!(
+            SettingsProvider::load_from_path(&path),
+            Err(SettingsError::Http2DisabledWithTls)
+        );
+    }
+
     /// Test reloading the settings.
     ///
     /// This test sets and reads environment variables which is inherently unsafe.
@@ -0,0 +1,57 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use std::time::Duration;
+
+use crate::settings_file;
+
+/// The default for the longest time limit a moderator may set for a speaker.
+pub const DEFAULT_AUTOMOD_MAX_TIME_LIMIT: Duration = Duration::from_secs(60 * 60);
+
+/// The default duration of the animation shown for a random selection.
+pub const DEFAULT_AUTOMOD_ANIMATION_DURATION: Duration = Duration::from_secs(8);
+
+/// Automod settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Automod {
+    /// Checkpoint the automod session state to the database so it can be restored after a
+    /// controller restart.
+    pub persist_state: bool,
+
+    /// The longest time limit per speaker that is accepted when starting a session.
+    pub max_time_limit: Duration,
+
+    /// How long the animation of a random selection lasts before the selected speaker is set.
+    pub animation_duration: Duration,
+}
+
+impl Default for Automod {
+    fn default() -> Self {
+        Self {
+            persist_state: false,
+            max_time_limit: DEFAULT_AUTOMOD_MAX_TIME_LIMIT,
+            animation_duration: DEFAULT_AUTOMOD_ANIMATION_DURATION,
+        }
+    }
+}
+
+impl From<settings_file::Automod> for Automod {
+    fn from(
+        settings_file::Automod {
+            persist_state,
+            max_time_limit_seconds,
+            animation_duration_seconds,
+        }: settings_file::Automod,
+    ) -> Self {
+        Self {
+            persist_state,
+            max_time_limit: max_time_limit_seconds
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_AUTOMOD_MAX_TIME_LIMIT),
+            animation_duration: animation_duration_seconds
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_AUTOMOD_ANIMATION_DURATION),
+        }
+    }
+}
@@ -0,0 +1,41 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use crate::settings_file;
+
+/// The default maximum size of a chat message in bytes.
+pub const DEFAULT_CHAT_MAX_MESSAGE_SIZE: usize = 4096;
+
+/// Chat settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chat {
+    /// The maximum size of a chat message in bytes.
+    pub max_message_size: usize,
+
+    /// The maximum number of messages retained per chat history, unbounded if not set.
+    pub max_history_length: Option<usize>,
+}
+
+impl Default for Chat {
+    fn default() -> Self {
+        Self {
+            max_message_size: DEFAULT_CHAT_MAX_MESSAGE_SIZE,
+            max_history_length: None,
+        }
+    }
+}
+
+impl From<settings_file::Chat> for Chat {
+    fn from(
+        settings_file::Chat {
+            max_message_size,
+            max_history_length,
+        }: settings_file::Chat,
+    ) -> Self {
+        Self {
+            max_message_size: max_message_size.unwrap_or(DEFAULT_CHAT_MAX_MESSAGE_SIZE),
+            max_history_length: max_history_length.filter(|length| *length > 0),
+        }
+    }
+}
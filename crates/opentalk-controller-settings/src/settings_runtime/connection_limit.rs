@@ -0,0 +1,47 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use std::collections::BTreeMap;
+
+use crate::settings_file;
+
+/// Settings for limiting the number of concurrent signaling connections per user.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConnectionLimit {
+    /// The maximum number of concurrent connections, unless overridden for a tenant.
+    /// `None` if unlimited.
+    pub max_connections_per_user: Option<u32>,
+
+    /// The maximum number of concurrent connections per tenant, keyed by the OIDC tenant id.
+    pub tenants: BTreeMap<String, Option<u32>>,
+}
+
+impl ConnectionLimit {
+    /// The maximum number of concurrent connections of a user in the given tenant, `None` if
+    /// the number of connections is not limited.
+    pub fn limit_for(&self, oidc_tenant_id: &str) -> Option<u32> {
+        self.tenants
+            .get(oidc_tenant_id)
+            .copied()
+            .unwrap_or(self.max_connections_per_user)
+    }
+}
+
+impl From<settings_file::ConnectionLimit> for ConnectionLimit {
+    fn from(
+        settings_file::ConnectionLimit {
+            max_connections_per_user,
+            tenants,
+        }: settings_file::ConnectionLimit,
+    ) -> Self {
+        Self {
+            max_connections_per_user,
+            tenants: tenants
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(tenant, limit)| (tenant, limit.max_connections_per_user))
+                .collect(),
+        }
+    }
+}
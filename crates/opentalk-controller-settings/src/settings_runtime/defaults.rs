@@ -6,7 +6,7 @@ use std::collections::BTreeSet;
 
 use opentalk_types_common::{features::ModuleFeatureId, users::Language};
 
-use crate::settings_file;
+use crate::settings_file::{self, ScreenSharePolicy};
 
 /// Some settings that apply for the whole installation.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -14,8 +14,8 @@ pub struct Defaults {
     /// The user language.
     pub user_language: Language,
 
-    /// Flag indicating whether the screen share requires an explicit permission.
-    pub screen_share_requires_permission: bool,
+    /// Who may start a screen share without an explicit permission.
+    pub screen_share_policy: ScreenSharePolicy,
 
     /// A list of disabled features.
     pub disabled_features: BTreeSet<ModuleFeatureId>,
@@ -26,12 +26,20 @@ impl From<settings_file::Defaults> for Defaults {
         settings_file::Defaults {
             user_language,
             screen_share_requires_permission,
+            screen_share_policy,
             disabled_features,
         }: settings_file::Defaults,
     ) -> Self {
+        // The boolean predates the policy and is kept for existing configurations
+        let screen_share_policy =
+            screen_share_policy.unwrap_or(match screen_share_requires_permission {
+                Some(true) => ScreenSharePolicy::RequiresPermission,
+                Some(false) | None => ScreenSharePolicy::Everyone,
+            });
+
         Self {
             user_language: user_language.unwrap_or_else(default_user_language),
-            screen_share_requires_permission: screen_share_requires_permission.unwrap_or_default(),
+            screen_share_policy,
             disabled_features,
         }
     }
@@ -41,7 +49,7 @@ impl Default for Defaults {
     fn default() -> Self {
         Self {
             user_language: default_user_language(),
-            screen_share_requires_permission: false,
+            screen_share_policy: ScreenSharePolicy::default(),
             disabled_features: BTreeSet::default(),
         }
     }
@@ -0,0 +1,118 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use std::collections::BTreeMap;
+
+use crate::settings_file;
+
+const DEFAULT_MIN_LENGTH: usize = 1;
+const DEFAULT_MAX_LENGTH: usize = 100;
+
+/// Display name rules of a single tenant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisplayNameRules {
+    /// Whether participants are rejected when joining with a display name violating the rules.
+    pub enforce: bool,
+
+    /// The minimum number of characters of a display name, surrounding whitespace excluded.
+    pub min_length: usize,
+
+    /// The maximum number of characters of a display name, surrounding whitespace excluded.
+    pub max_length: usize,
+
+    /// Characters which must not be contained in a display name.
+    pub disallowed_characters: String,
+}
+
+impl Default for DisplayNameRules {
+    fn default() -> Self {
+        Self {
+            enforce: false,
+            min_length: DEFAULT_MIN_LENGTH,
+            max_length: DEFAULT_MAX_LENGTH,
+            disallowed_characters: String::new(),
+        }
+    }
+}
+
+impl DisplayNameRules {
+    /// Check whether the display name satisfies the rules.
+    ///
+    /// Control characters are never allowed, regardless of the configured characters.
+    pub fn is_valid(&self, display_name: &str) -> bool {
+        let display_name = display_name.trim();
+        let length = display_name.chars().count();
+
+        length >= self.min_length.max(1)
+            && length <= self.max_length
+            && !display_name
+                .chars()
+                .any(|c| c.is_control() || self.disallowed_characters.contains(c))
+    }
+}
+
+impl From<settings_file::DisplayNamePolicyTenant> for DisplayNameRules {
+    fn from(
+        settings_file::DisplayNamePolicyTenant {
+            enforce,
+            min_length,
+            max_length,
+            disallowed_characters,
+        }: settings_file::DisplayNamePolicyTenant,
+    ) -> Self {
+        Self {
+            enforce: enforce.unwrap_or_default(),
+            min_length: min_length.unwrap_or(DEFAULT_MIN_LENGTH),
+            max_length: max_length.unwrap_or(DEFAULT_MAX_LENGTH),
+            disallowed_characters: disallowed_characters.unwrap_or_default(),
+        }
+    }
+}
+
+/// Settings for validating the display names of participants joining a meeting.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DisplayNamePolicy {
+    /// The display name rules, unless overridden for a tenant.
+    pub rules: DisplayNameRules,
+
+    /// The display name rules per tenant, keyed by the OIDC tenant id.
+    pub tenants: BTreeMap<String, DisplayNameRules>,
+}
+
+impl DisplayNamePolicy {
+    /// The display name rules for the given tenant, the global rules if the tenant is unknown
+    /// or has no override.
+    pub fn rules_for(&self, oidc_tenant_id: Option<&str>) -> &DisplayNameRules {
+        oidc_tenant_id
+            .and_then(|oidc_tenant_id| self.tenants.get(oidc_tenant_id))
+            .unwrap_or(&self.rules)
+    }
+}
+
+impl From<settings_file::DisplayNamePolicy> for DisplayNamePolicy {
+    fn from(
+        settings_file::DisplayNamePolicy {
+            enforce,
+            min_length,
+            max_length,
+            disallowed_characters,
+            tenants,
+        }: settings_file::DisplayNamePolicy,
+    ) -> Self {
+        Self {
+            rules: settings_file::DisplayNamePolicyTenant {
+                enforce,
+                min_length,
+                max_length,
+                disallowed_characters,
+            }
+            .into(),
+            tenants: tenants
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(tenant, rules)| (tenant, rules.into()))
+                .collect(),
+        }
+    }
+}
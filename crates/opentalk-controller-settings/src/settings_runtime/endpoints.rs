@@ -15,6 +15,9 @@ pub struct Endpoints {
 
     /// Disable the OpenAPI endpoint.
     pub disable_openapi: bool,
+
+    /// Mark rooms as deleted instead of deleting them immediately.
+    pub soft_delete_rooms: bool,
 }
 
 impl From<settings_file::Endpoints> for Endpoints {
@@ -25,6 +28,7 @@ impl From<settings_file::Endpoints> for Endpoints {
             event_invite_external_email_address,
             disallow_custom_display_name,
             disable_openapi,
+            soft_delete_rooms,
         }: settings_file::Endpoints,
     ) -> Self {
         Self {
@@ -32,6 +36,7 @@ impl From<settings_file::Endpoints> for Endpoints {
                 .unwrap_or_default(),
             disallow_custom_display_name: disallow_custom_display_name.unwrap_or_default(),
             disable_openapi: disable_openapi.unwrap_or_default(),
+            soft_delete_rooms: soft_delete_rooms.unwrap_or_default(),
         }
     }
 }
@@ -0,0 +1,76 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use opentalk_types_common::{features::ModuleFeatureId, modules::ModuleId};
+
+use crate::settings_file;
+
+/// Overrides of the modules and features available to the rooms of a tenant, regardless of the
+/// tariff.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeatureOverrides {
+    /// The overrides per tenant, keyed by the OIDC tenant id.
+    pub tenants: BTreeMap<String, TenantFeatureOverrides>,
+}
+
+impl FeatureOverrides {
+    /// The overrides of the given tenant, if any are configured.
+    pub fn for_tenant(&self, oidc_tenant_id: &str) -> Option<&TenantFeatureOverrides> {
+        self.tenants.get(oidc_tenant_id)
+    }
+
+    /// Whether no overrides are configured for any tenant.
+    pub fn is_empty(&self) -> bool {
+        self.tenants.is_empty()
+    }
+}
+
+/// The modules and features enabled or disabled for a tenant. An override wins over the
+/// tariff, a module or feature listed as both enabled and disabled is disabled.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TenantFeatureOverrides {
+    /// Modules available regardless of the tariff.
+    pub enabled_modules: BTreeSet<ModuleId>,
+
+    /// Modules unavailable regardless of the tariff.
+    pub disabled_modules: BTreeSet<ModuleId>,
+
+    /// Features available regardless of the tariff.
+    pub enabled_features: BTreeSet<ModuleFeatureId>,
+
+    /// Features unavailable regardless of the tariff.
+    pub disabled_features: BTreeSet<ModuleFeatureId>,
+}
+
+impl From<settings_file::FeatureOverrides> for FeatureOverrides {
+    fn from(settings_file::FeatureOverrides { tenants }: settings_file::FeatureOverrides) -> Self {
+        Self {
+            tenants: tenants
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(tenant, overrides)| (tenant, overrides.into()))
+                .collect(),
+        }
+    }
+}
+
+impl From<settings_file::FeatureOverridesTenant> for TenantFeatureOverrides {
+    fn from(
+        settings_file::FeatureOverridesTenant {
+            enabled_modules,
+            disabled_modules,
+            enabled_features,
+            disabled_features,
+        }: settings_file::FeatureOverridesTenant,
+    ) -> Self {
+        Self {
+            enabled_modules: enabled_modules.unwrap_or_default(),
+            disabled_modules: disabled_modules.unwrap_or_default(),
+            enabled_features: enabled_features.unwrap_or_default(),
+            disabled_features: disabled_features.unwrap_or_default(),
+        }
+    }
+}
@@ -2,11 +2,15 @@
 //
 // SPDX-License-Identifier: EUPL-1.2
 
-use super::HttpTls;
+use std::time::Duration;
+
+use super::{HttpCors, HttpTls};
 use crate::settings_file;
 
 pub const DEFAULT_HTTP_PORT: u16 = 11311;
 
+pub const DEFAULT_IDEMPOTENCY_KEY_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
 /// The runtime configuration for the HTTP service provided by the OpenTalk controller.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Http {
@@ -18,6 +22,17 @@ pub struct Http {
 
     /// The TLS configuration.
     pub tls: Option<HttpTls>,
+
+    /// The CORS configuration.
+    pub cors: HttpCors,
+
+    /// Whether HTTP/2 with prior knowledge (h2c) is accepted on connections without TLS.
+    ///
+    /// Must not be disabled when TLS is configured, HTTP/2 is always offered via ALPN then.
+    pub http2: bool,
+
+    /// How long the results of requests sent with an `Idempotency-Key` header are kept.
+    pub idempotency_key_ttl: Duration,
 }
 
 impl From<Option<settings_file::Http>> for Http {
@@ -27,11 +42,25 @@ impl From<Option<settings_file::Http>> for Http {
 }
 
 impl From<settings_file::Http> for Http {
-    fn from(settings_file::Http { addr, port, tls }: settings_file::Http) -> Self {
+    fn from(
+        settings_file::Http {
+            addr,
+            port,
+            tls,
+            cors,
+            http2,
+            idempotency_key_ttl_seconds,
+        }: settings_file::Http,
+    ) -> Self {
         Self {
             addr,
             port: port.unwrap_or(DEFAULT_HTTP_PORT),
             tls: tls.map(Into::into),
+            cors: cors.map(Into::into).unwrap_or_default(),
+            http2: http2.unwrap_or(true),
+            idempotency_key_ttl: idempotency_key_ttl_seconds
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_IDEMPOTENCY_KEY_TTL),
         }
     }
 }
@@ -42,6 +71,9 @@ impl Default for Http {
             addr: None,
             port: DEFAULT_HTTP_PORT,
             tls: None,
+            cors: HttpCors::default(),
+            http2: true,
+            idempotency_key_ttl: DEFAULT_IDEMPOTENCY_KEY_TTL,
         }
     }
 }
@@ -0,0 +1,21 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use crate::settings_file;
+
+/// CORS configuration for the HTTP service.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct HttpCors {
+    /// The origins that are allowed to access the HTTP service.
+    ///
+    /// Entries are either exact origins such as `https://app.example.com` or wildcard subdomain
+    /// patterns such as `*.example.com`. Any origin is allowed when unset.
+    pub allowed_origins: Option<Vec<String>>,
+}
+
+impl From<settings_file::HttpCors> for HttpCors {
+    fn from(settings_file::HttpCors { allowed_origins }: settings_file::HttpCors) -> Self {
+        Self { allowed_origins }
+    }
+}
@@ -4,6 +4,7 @@
 
 use std::path::PathBuf;
 
+use super::HttpTlsClientAuth;
 use crate::settings_file;
 
 /// TLS configuration for the HTTP service.
@@ -14,6 +15,9 @@ pub struct HttpTls {
 
     /// The path to the private key file.
     pub private_key: PathBuf,
+
+    /// The client authentication configuration for the service endpoints.
+    pub client_auth: Option<HttpTlsClientAuth>,
 }
 
 impl From<settings_file::HttpTls> for HttpTls {
@@ -21,11 +25,13 @@ impl From<settings_file::HttpTls> for HttpTls {
         settings_file::HttpTls {
             certificate,
             private_key,
+            client_auth,
         }: settings_file::HttpTls,
     ) -> Self {
         HttpTls {
             certificate,
             private_key,
+            client_auth: client_auth.map(Into::into),
         }
     }
 }
@@ -0,0 +1,31 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use std::path::PathBuf;
+
+use crate::settings_file::{self, ClientAuthEnforcement};
+
+/// TLS client authentication configuration for the service endpoints.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpTlsClientAuth {
+    /// The path to the CA certificate against which client certificates are verified.
+    pub ca_certificate: PathBuf,
+
+    /// How client certificates are enforced for the service endpoints.
+    pub enforcement: ClientAuthEnforcement,
+}
+
+impl From<settings_file::HttpTlsClientAuth> for HttpTlsClientAuth {
+    fn from(
+        settings_file::HttpTlsClientAuth {
+            ca_certificate,
+            enforcement,
+        }: settings_file::HttpTlsClientAuth,
+    ) -> Self {
+        HttpTlsClientAuth {
+            ca_certificate,
+            enforcement,
+        }
+    }
+}
@@ -0,0 +1,136 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    time::Duration,
+};
+
+use crate::settings_file::{self, LegalVoteKind, LegalVotePdfPolicy};
+
+/// The default maximum number of participants which can be allowed to take part in a legal vote.
+pub const DEFAULT_LEGAL_VOTE_MAX_ALLOWED_PARTICIPANTS: usize = 1000;
+
+/// Legal vote settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LegalVote {
+    /// The vote kinds which can be started, unless overridden for a tenant.
+    pub allowed_vote_kinds: BTreeSet<LegalVoteKind>,
+
+    /// Overrides of the settings per tenant, keyed by the OIDC tenant id.
+    pub tenants: BTreeMap<String, LegalVoteTenant>,
+
+    /// The maximum number of participants which can be allowed to take part in a vote.
+    pub max_allowed_participants: usize,
+
+    /// Whether abstaining is enabled for votes which do not specify it, unless overridden for a
+    /// tenant.
+    pub enable_abstain_by_default: bool,
+
+    /// How long an active vote keeps running after its initiator left, the vote is canceled
+    /// immediately if zero.
+    pub initiator_grace_period: Duration,
+
+    /// Whether PDF reports are generated for votes, unless overridden for a tenant.
+    pub pdf_policy: LegalVotePdfPolicy,
+}
+
+/// Legal vote settings of a single tenant.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LegalVoteTenant {
+    /// The vote kinds which can be started in the tenant.
+    pub allowed_vote_kinds: Option<BTreeSet<LegalVoteKind>>,
+
+    /// Whether abstaining is enabled for votes in the tenant which do not specify it.
+    pub enable_abstain_by_default: Option<bool>,
+
+    /// Whether PDF reports are generated for votes in the tenant.
+    pub pdf_policy: Option<LegalVotePdfPolicy>,
+}
+
+impl LegalVote {
+    /// The vote kinds which can be started in the given tenant.
+    pub fn allowed_vote_kinds_for(&self, oidc_tenant_id: &str) -> &BTreeSet<LegalVoteKind> {
+        self.tenants
+            .get(oidc_tenant_id)
+            .and_then(|tenant| tenant.allowed_vote_kinds.as_ref())
+            .unwrap_or(&self.allowed_vote_kinds)
+    }
+
+    /// Whether abstaining is enabled for votes in the given tenant which do not specify it.
+    pub fn enable_abstain_by_default_for(&self, oidc_tenant_id: &str) -> bool {
+        self.tenants
+            .get(oidc_tenant_id)
+            .and_then(|tenant| tenant.enable_abstain_by_default)
+            .unwrap_or(self.enable_abstain_by_default)
+    }
+
+    /// Whether PDF reports are generated for votes in the given tenant.
+    pub fn pdf_policy_for(&self, oidc_tenant_id: &str) -> LegalVotePdfPolicy {
+        self.tenants
+            .get(oidc_tenant_id)
+            .and_then(|tenant| tenant.pdf_policy)
+            .unwrap_or(self.pdf_policy)
+    }
+}
+
+impl Default for LegalVote {
+    fn default() -> Self {
+        Self {
+            allowed_vote_kinds: LegalVoteKind::ALL.into(),
+            tenants: BTreeMap::new(),
+            max_allowed_participants: DEFAULT_LEGAL_VOTE_MAX_ALLOWED_PARTICIPANTS,
+            enable_abstain_by_default: false,
+            initiator_grace_period: Duration::ZERO,
+            pdf_policy: LegalVotePdfPolicy::default(),
+        }
+    }
+}
+
+impl From<settings_file::LegalVote> for LegalVote {
+    fn from(
+        settings_file::LegalVote {
+            allowed_vote_kinds,
+            max_allowed_participants,
+            enable_abstain_by_default,
+            initiator_grace_period_seconds,
+            pdf_policy,
+            tenants,
+        }: settings_file::LegalVote,
+    ) -> Self {
+        Self {
+            allowed_vote_kinds: allowed_vote_kinds.unwrap_or_else(|| LegalVoteKind::ALL.into()),
+            tenants: tenants
+                .unwrap_or_default()
+                .into_iter()
+                .map(
+                    |(
+                        tenant,
+                        settings_file::LegalVoteTenant {
+                            allowed_vote_kinds,
+                            enable_abstain_by_default,
+                            pdf_policy,
+                        },
+                    )| {
+                        (
+                            tenant,
+                            LegalVoteTenant {
+                                allowed_vote_kinds,
+                                enable_abstain_by_default,
+                                pdf_policy,
+                            },
+                        )
+                    },
+                )
+                .collect(),
+            max_allowed_participants: max_allowed_participants
+                .unwrap_or(DEFAULT_LEGAL_VOTE_MAX_ALLOWED_PARTICIPANTS),
+            enable_abstain_by_default: enable_abstain_by_default.unwrap_or_default(),
+            initiator_grace_period: Duration::from_secs(
+                initiator_grace_period_seconds.unwrap_or_default(),
+            ),
+            pdf_policy: pdf_policy.unwrap_or_default(),
+        }
+    }
+}
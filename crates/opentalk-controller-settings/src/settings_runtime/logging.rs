@@ -5,7 +5,7 @@
 use uuid::Uuid;
 
 use super::LoggingOltpTracing;
-use crate::settings_file;
+use crate::settings_file::{self, PiiRedaction};
 
 const DEFAULT_SERVICE_NAME: &str = "controller";
 const DEFAULT_SERVICE_NAMESPACE: &str = "opentalk";
@@ -18,6 +18,9 @@ pub struct Logging {
 
     /// OTLP tracing configuration, the endpoint will only be enabled if this is set.
     pub otlp_tracing: Option<LoggingOltpTracing>,
+
+    /// Redaction of user and participant identifiers in the log output.
+    pub pii_redaction: PiiRedaction,
 }
 
 impl From<settings_file::Logging> for Logging {
@@ -28,6 +31,7 @@ impl From<settings_file::Logging> for Logging {
             service_name,
             service_namespace,
             service_instance_id,
+            pii_redaction,
         }: settings_file::Logging,
     ) -> Self {
         let default_directives = default_directives.filter(|v| !v.is_empty());
@@ -41,6 +45,7 @@ impl From<settings_file::Logging> for Logging {
         Self {
             default_directives,
             otlp_tracing,
+            pii_redaction: pii_redaction.unwrap_or_default(),
         }
     }
 }
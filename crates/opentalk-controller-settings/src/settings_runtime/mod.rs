@@ -19,16 +19,24 @@
 )]
 
 mod authz;
+mod automod;
 mod avatar;
 mod call_in;
+mod chat;
+mod connection_limit;
 mod database;
 mod defaults;
+mod display_name_policy;
 mod endpoints;
 mod etcd;
 mod etherpad;
+mod feature_overrides;
 mod frontend;
 mod http;
+mod http_cors;
 mod http_tls;
+mod http_tls_client_auth;
+mod legal_vote;
 mod livekit;
 mod logging;
 mod logging_oltp_tracing;
@@ -42,30 +50,43 @@ mod oidc_frontend;
 mod operator_information;
 mod rabbitmq;
 mod redis;
+mod reports;
+mod reports_verification_qr_code;
 mod roomserver;
 pub(crate) mod settings;
 mod shared_folder;
 mod spacedeck;
+mod streaming_probe;
 mod subroom_audio;
 mod tariff_assignment;
 mod tariff_status_mapping;
 mod tariffs;
 mod tenant_assignment;
 mod tenants;
+mod training_participation_report;
 mod user_search_backend;
 mod user_search_backend_keycloak;
+mod webhook;
 
 pub use authz::Authz;
+pub use automod::{Automod, DEFAULT_AUTOMOD_ANIMATION_DURATION, DEFAULT_AUTOMOD_MAX_TIME_LIMIT};
 pub use avatar::{Avatar, DEFAULT_LIBRAVATAR_URL};
 pub use call_in::CallIn;
+pub use chat::{Chat, DEFAULT_CHAT_MAX_MESSAGE_SIZE};
+pub use connection_limit::ConnectionLimit;
 pub use database::Database;
 pub use defaults::Defaults;
+pub use display_name_policy::{DisplayNamePolicy, DisplayNameRules};
 pub use endpoints::Endpoints;
 pub use etcd::Etcd;
 pub use etherpad::Etherpad;
+pub use feature_overrides::{FeatureOverrides, TenantFeatureOverrides};
 pub use frontend::Frontend;
 pub use http::Http;
+pub use http_cors::HttpCors;
 pub use http_tls::HttpTls;
+pub use http_tls_client_auth::HttpTlsClientAuth;
+pub use legal_vote::{DEFAULT_LEGAL_VOTE_MAX_ALLOWED_PARTICIPANTS, LegalVote, LegalVoteTenant};
 pub use livekit::LiveKit;
 pub use logging::Logging;
 pub use logging_oltp_tracing::LoggingOltpTracing;
@@ -78,10 +99,13 @@ pub use oidc_frontend::OidcFrontend;
 pub use operator_information::OperatorInformation;
 pub use rabbitmq::RabbitMq;
 pub use redis::Redis;
+pub use reports::{DEFAULT_MAX_CONCURRENT_PDF_GENERATIONS, Reports};
+pub use reports_verification_qr_code::ReportsVerificationQrCode;
 pub use roomserver::RoomServer;
 pub use settings::Settings;
 pub use shared_folder::SharedFolder;
 pub use spacedeck::Spacedeck;
+pub use streaming_probe::{DEFAULT_STREAMING_PROBE_TIMEOUT, StreamingProbe};
 pub use subroom_audio::SubroomAudio;
 pub use tariff_assignment::{DEFAULT_STATIC_TARIFF_NAME, TariffAssignment};
 pub use tariff_status_mapping::TariffStatusMapping;
@@ -90,5 +114,7 @@ pub use tenant_assignment::{
     DEFAULT_EXTERNAL_TENANT_ID_USER_ATTRIBUTE_NAME, DEFAULT_STATIC_TENANT_ID, TenantAssignment,
 };
 pub use tenants::Tenants;
+pub use training_participation_report::TrainingParticipationReport;
 pub use user_search_backend::UserSearchBackend;
 pub use user_search_backend_keycloak::UserSearchBackendKeycloak;
+pub use webhook::{DEFAULT_WEBHOOK_MAX_RETRIES, DEFAULT_WEBHOOK_TIMEOUT, Webhook};
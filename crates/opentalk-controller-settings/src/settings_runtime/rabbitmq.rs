@@ -2,10 +2,14 @@
 //
 // SPDX-License-Identifier: EUPL-1.2
 
+use std::time::Duration;
+
 use crate::settings_file;
 
 pub const DEFAULT_MIN_CONNECTIONS: u32 = 10;
 pub const DEFAULT_MAX_CHANNELS_PER_CONNECTION: u32 = 100;
+pub const DEFAULT_STARTUP_RETRIES: u32 = 5;
+pub const DEFAULT_STARTUP_RETRY_INTERVAL: Duration = Duration::from_secs(1);
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RabbitMq {
@@ -18,6 +22,12 @@ pub struct RabbitMq {
     /// The maximum number of channels per connection.
     pub max_channels_per_connection: u32,
 
+    /// The number of retries when the connection cannot be established on startup.
+    pub startup_retries: u32,
+
+    /// The delay before the first startup retry, doubled for every further retry.
+    pub startup_retry_interval: Duration,
+
     /// Sending mails will only be enabled if this is set.
     pub mail_task_queue: Option<String>,
 
@@ -31,6 +41,8 @@ impl From<settings_file::RabbitMqConfig> for RabbitMq {
             url,
             min_connections,
             max_channels_per_connection,
+            startup_retries,
+            startup_retry_interval_seconds,
             mail_task_queue,
             recording_task_queue,
         }: settings_file::RabbitMqConfig,
@@ -40,6 +52,10 @@ impl From<settings_file::RabbitMqConfig> for RabbitMq {
             min_connections: min_connections.unwrap_or(DEFAULT_MIN_CONNECTIONS),
             max_channels_per_connection: max_channels_per_connection
                 .unwrap_or(DEFAULT_MAX_CHANNELS_PER_CONNECTION),
+            startup_retries: startup_retries.unwrap_or(DEFAULT_STARTUP_RETRIES),
+            startup_retry_interval: startup_retry_interval_seconds
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_STARTUP_RETRY_INTERVAL),
             mail_task_queue,
             recording_task_queue,
         }
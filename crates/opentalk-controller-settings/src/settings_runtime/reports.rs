@@ -0,0 +1,83 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use std::{collections::BTreeMap, path::PathBuf};
+
+use opentalk_types_common::time::TimeZone;
+
+use super::ReportsVerificationQrCode;
+use crate::settings_file;
+
+/// The default number of PDF reports that may be generated concurrently.
+pub const DEFAULT_MAX_CONCURRENT_PDF_GENERATIONS: usize = 4;
+
+/// Report generation settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reports {
+    /// The maximum number of PDF reports that are generated at the same time.
+    ///
+    /// Further report generations wait until a running one has finished.
+    pub max_concurrent_pdf_generations: usize,
+
+    /// The directory into which the template inputs and generated PDF files are dumped
+    /// for troubleshooting.
+    ///
+    /// The `OPENTALK_REPORT_DUMP_PATH` environment variable takes precedence over this setting.
+    pub dump_path: Option<PathBuf>,
+
+    /// A directory containing fonts which are available to the report templates in addition
+    /// to the embedded fonts.
+    pub font_path: Option<PathBuf>,
+
+    /// The QR code linking the archived reports to a verification page, added for the tenants
+    /// that opted in.
+    pub verification_qr_code: Option<ReportsVerificationQrCode>,
+
+    /// The default timezone of the reports for each tenant, keyed by the OIDC tenant id.
+    ///
+    /// Used when neither the report request, the event nor the user provide a timezone.
+    pub tenant_timezones: BTreeMap<String, TimeZone>,
+}
+
+impl Reports {
+    /// The default report timezone of the given tenant, if configured.
+    pub fn tenant_timezone(&self, oidc_tenant_id: &str) -> Option<TimeZone> {
+        self.tenant_timezones.get(oidc_tenant_id).copied()
+    }
+}
+
+impl Default for Reports {
+    fn default() -> Self {
+        Self {
+            max_concurrent_pdf_generations: DEFAULT_MAX_CONCURRENT_PDF_GENERATIONS,
+            dump_path: None,
+            font_path: None,
+            verification_qr_code: None,
+            tenant_timezones: BTreeMap::new(),
+        }
+    }
+}
+
+impl From<settings_file::Reports> for Reports {
+    fn from(
+        settings_file::Reports {
+            template: _,
+            max_concurrent_pdf_generations,
+            dump_path,
+            font_path,
+            verification_qr_code,
+            tenant_timezones,
+        }: settings_file::Reports,
+    ) -> Self {
+        Self {
+            max_concurrent_pdf_generations: max_concurrent_pdf_generations
+                .unwrap_or(DEFAULT_MAX_CONCURRENT_PDF_GENERATIONS)
+                .max(1),
+            dump_path,
+            font_path,
+            verification_qr_code: verification_qr_code.map(Into::into),
+            tenant_timezones: tenant_timezones.unwrap_or_default(),
+        }
+    }
+}
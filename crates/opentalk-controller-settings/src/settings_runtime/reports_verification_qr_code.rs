@@ -0,0 +1,73 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use std::collections::BTreeSet;
+
+use opentalk_types_common::{assets::AssetId, rooms::RoomId};
+
+use crate::settings_file;
+
+/// Settings for the QR code that links the archived vote protocols and training participation
+/// reports to a verification page.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportsVerificationQrCode {
+    /// The url encoded into the QR code.
+    ///
+    /// The `{room_id}` and `{asset_id}` placeholders are replaced with the ids of the room and
+    /// the stored report asset.
+    pub url: String,
+
+    /// The OIDC ids of the tenants that opted in to the QR code.
+    pub tenants: BTreeSet<String>,
+}
+
+impl ReportsVerificationQrCode {
+    /// Whether the QR code is added to the reports of the given tenant.
+    pub fn is_enabled_for(&self, oidc_tenant_id: &str) -> bool {
+        self.tenants.contains(oidc_tenant_id)
+    }
+
+    /// The url linking to the report asset that is stored with the given id.
+    pub fn url_for(&self, room_id: RoomId, asset_id: AssetId) -> String {
+        self.url
+            .replace("{room_id}", &room_id.to_string())
+            .replace("{asset_id}", &asset_id.to_string())
+    }
+}
+
+impl From<settings_file::ReportsVerificationQrCode> for ReportsVerificationQrCode {
+    fn from(
+        settings_file::ReportsVerificationQrCode { url, tenants }: settings_file::ReportsVerificationQrCode,
+    ) -> Self {
+        Self {
+            url,
+            tenants: tenants.unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use opentalk_types_common::{assets::AssetId, rooms::RoomId};
+    use pretty_assertions::assert_eq;
+
+    use super::ReportsVerificationQrCode;
+
+    #[test]
+    fn url_for_asset() {
+        let settings = ReportsVerificationQrCode {
+            url: "https://opentalk.example.org/verify/{room_id}/{asset_id}".to_string(),
+            tenants: BTreeSet::from(["example-tenant".to_string()]),
+        };
+
+        assert!(settings.is_enabled_for("example-tenant"));
+        assert!(!settings.is_enabled_for("other-tenant"));
+        assert_eq!(
+            settings.url_for(RoomId::from_u128(1), AssetId::from_u128(2)),
+            "https://opentalk.example.org/verify/00000000-0000-0000-0000-000000000001/00000000-0000-0000-0000-000000000002"
+        );
+    }
+}
@@ -0,0 +1,79 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use std::{collections::BTreeMap, time::Duration};
+
+use crate::settings_file;
+
+/// The default time to wait for a streaming endpoint to accept a connection.
+pub const DEFAULT_STREAMING_PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Settings for checking the reachability of streaming targets before a stream is started.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StreamingProbe {
+    /// Whether streaming targets are probed, unless overridden for a tenant.
+    pub enabled: bool,
+
+    /// The time to wait for a streaming endpoint to accept a connection.
+    pub timeout: Duration,
+
+    /// Whether streaming targets are probed per tenant, keyed by the OIDC tenant id.
+    pub tenants: BTreeMap<String, bool>,
+}
+
+impl Default for StreamingProbe {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            timeout: DEFAULT_STREAMING_PROBE_TIMEOUT,
+            tenants: BTreeMap::new(),
+        }
+    }
+}
+
+impl StreamingProbe {
+    /// Whether the streaming targets of the given tenant are probed before streaming to them.
+    pub fn is_enabled_for(&self, oidc_tenant_id: &str) -> bool {
+        self.tenants
+            .get(oidc_tenant_id)
+            .copied()
+            .unwrap_or(self.enabled)
+    }
+
+    /// Whether streaming targets of any tenant are probed.
+    pub fn is_enabled_for_any(&self) -> bool {
+        self.enabled || self.tenants.values().any(|enabled| *enabled)
+    }
+}
+
+impl From<settings_file::StreamingProbe> for StreamingProbe {
+    fn from(
+        settings_file::StreamingProbe {
+            enabled,
+            timeout_ms,
+            tenants,
+        }: settings_file::StreamingProbe,
+    ) -> Self {
+        let enabled = enabled.unwrap_or_default();
+
+        Self {
+            enabled,
+            timeout: timeout_ms
+                .map(Duration::from_millis)
+                .unwrap_or(DEFAULT_STREAMING_PROBE_TIMEOUT),
+            tenants: tenants
+                .unwrap_or_default()
+                .into_iter()
+                .map(
+                    |(
+                        tenant,
+                        settings_file::StreamingProbeTenant {
+                            enabled: tenant_enabled,
+                        },
+                    )| { (tenant, tenant_enabled.unwrap_or(enabled)) },
+                )
+                .collect(),
+        }
+    }
+}
@@ -2,6 +2,8 @@
 //
 // SPDX-License-Identifier: EUPL-1.2
 
+use std::collections::BTreeMap;
+
 use super::TariffAssignment;
 use crate::settings_file;
 
@@ -10,12 +12,56 @@ use crate::settings_file;
 pub struct Tariffs {
     /// The tariff assignment.
     pub assignment: TariffAssignment,
+
+    /// The name of the tariff assigned to users for which the assignment yields no tariff.
+    pub default_tariff_name: Option<String>,
+
+    /// The default tariff names per tenant, keyed by the OIDC tenant id.
+    pub tenants: BTreeMap<String, String>,
+}
+
+impl Tariffs {
+    /// The name of the default tariff for the given tenant, `None` if no default tariff is
+    /// configured.
+    pub fn default_tariff_name_for(&self, oidc_tenant_id: &str) -> Option<&str> {
+        self.tenants
+            .get(oidc_tenant_id)
+            .or(self.default_tariff_name.as_ref())
+            .map(String::as_str)
+    }
+
+    /// All configured default tariff names.
+    pub fn default_tariff_names(&self) -> impl Iterator<Item = &str> {
+        self.default_tariff_name
+            .iter()
+            .chain(self.tenants.values())
+            .map(String::as_str)
+    }
 }
 
 impl From<settings_file::Tariffs> for Tariffs {
-    fn from(settings_file::Tariffs { assignment }: settings_file::Tariffs) -> Self {
+    fn from(
+        settings_file::Tariffs {
+            assignment,
+            default_tariff_name,
+            tenants,
+        }: settings_file::Tariffs,
+    ) -> Self {
         Self {
             assignment: assignment.map(Into::into).unwrap_or_default(),
+            default_tariff_name,
+            tenants: tenants
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(
+                    |(
+                        tenant,
+                        settings_file::TariffsTenant {
+                            default_tariff_name,
+                        },
+                    )| { default_tariff_name.map(|name| (tenant, name)) },
+                )
+                .collect(),
         }
     }
 }
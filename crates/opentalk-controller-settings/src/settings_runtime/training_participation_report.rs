@@ -0,0 +1,20 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use crate::settings_file::{self, CheckpointJitter};
+
+/// Training participation report settings.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
+pub struct TrainingParticipationReport {
+    /// The distribution of the random delay before each presence checkpoint.
+    pub checkpoint_jitter: CheckpointJitter,
+}
+
+impl From<settings_file::TrainingParticipationReport> for TrainingParticipationReport {
+    fn from(
+        settings_file::TrainingParticipationReport { checkpoint_jitter }: settings_file::TrainingParticipationReport,
+    ) -> Self {
+        Self { checkpoint_jitter }
+    }
+}
@@ -0,0 +1,56 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use std::{collections::BTreeSet, time::Duration};
+
+use url::Url;
+
+use crate::settings_file::{self, WebhookEventType};
+
+/// The default number of delivery retries after the first failed attempt.
+pub const DEFAULT_WEBHOOK_MAX_RETRIES: u32 = 3;
+
+/// The default timeout of a single delivery attempt.
+pub const DEFAULT_WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Webhook settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Webhook {
+    /// The endpoint that receives the events.
+    pub url: Url,
+
+    /// The secret used to sign the event payloads.
+    pub secret: String,
+
+    /// The event types that are delivered to the endpoint.
+    pub events: BTreeSet<WebhookEventType>,
+
+    /// The number of retries after a failed delivery attempt.
+    pub max_retries: u32,
+
+    /// The timeout of a single delivery attempt.
+    pub timeout: Duration,
+}
+
+impl From<settings_file::Webhook> for Webhook {
+    fn from(
+        settings_file::Webhook {
+            url,
+            secret,
+            events,
+            max_retries,
+            timeout_seconds,
+        }: settings_file::Webhook,
+    ) -> Self {
+        Self {
+            url,
+            secret,
+            events: events.unwrap_or_else(|| BTreeSet::from(WebhookEventType::ALL)),
+            max_retries: max_retries.unwrap_or(DEFAULT_WEBHOOK_MAX_RETRIES),
+            timeout: timeout_seconds
+                .map(Duration::from_secs)
+                .unwrap_or(DEFAULT_WEBHOOK_TIMEOUT),
+        }
+    }
+}
@@ -5,7 +5,7 @@
 use std::fmt::Display;
 
 use opentalk_database::DatabaseError;
-use opentalk_signaling_core::{ObjectStorageError, assets::AssetError};
+use opentalk_signaling_core::{ObjectStorageError, SignalingModuleError, assets::AssetError};
 use opentalk_types_api_v1::error::ApiError;
 use rand::distr::uniform;
 use snafu::Whatever;
@@ -94,6 +94,16 @@ impl From<AssetError> for CaptureApiError {
     }
 }
 
+impl From<SignalingModuleError> for CaptureApiError {
+    fn from(value: SignalingModuleError) -> Self {
+        log::error!(
+            "REST API threw internal error from signaling module error: {}",
+            snafu::Report::from_error(value)
+        );
+        CaptureApiError(ApiError::internal())
+    }
+}
+
 impl From<uniform::Error> for CaptureApiError {
     fn from(value: uniform::Error) -> Self {
         log::error!("REST API threw internal error: {value:?}");
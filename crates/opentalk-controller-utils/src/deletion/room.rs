@@ -171,18 +171,7 @@ impl Deleter for RoomDeleter {
         exchange_handle: ExchangeHandle,
         settings: &Settings,
     ) -> Result<(), Error> {
-        let message = NamespacedEvent {
-            module: control::MODULE_ID,
-            timestamp: opentalk_types_common::time::Timestamp::now(),
-            payload: control::exchange::Message::RoomDeleted,
-        };
-
-        if let Err(e) = exchange_handle.publish(
-            control::exchange::global_room_all_participants(self.room_id),
-            serde_json::to_string(&message).expect("Failed to convert namespaced to json"),
-        ) {
-            warn!(log: logger, "Failed to publish message to exchange, {}", e);
-        }
+        notify_room_deleted(logger, &exchange_handle, self.room_id);
 
         delete_shared_folders(
             logger,
@@ -268,6 +257,22 @@ impl Deleter for RoomDeleter {
     }
 }
 
+/// Notify all participants in the room that the room has been deleted
+pub fn notify_room_deleted(logger: &dyn Log, exchange_handle: &ExchangeHandle, room_id: RoomId) {
+    let message = NamespacedEvent {
+        module: control::MODULE_ID,
+        timestamp: opentalk_types_common::time::Timestamp::now(),
+        payload: control::exchange::Message::RoomDeleted,
+    };
+
+    if let Err(e) = exchange_handle.publish(
+        control::exchange::global_room_all_participants(room_id),
+        serde_json::to_string(&message).expect("Failed to convert namespaced to json"),
+    ) {
+        warn!(log: logger, "Failed to publish message to exchange, {}", e);
+    }
+}
+
 pub(crate) async fn delete_rows_associated_with_room(
     logger: &dyn Log,
     conn: &mut DbConnection,
@@ -296,7 +301,8 @@ pub(crate) async fn delete_rows_associated_with_room(
     Ok(())
 }
 
-fn associated_resource_ids(room_id: RoomId) -> impl IntoIterator<Item = ResourceId> {
+/// Get the list of room resources which are granted to the room owner or other users
+pub fn associated_resource_ids(room_id: RoomId) -> impl IntoIterator<Item = ResourceId> + Send {
     [
         room_id.resource_id(),
         room_id.resource_id().with_suffix("/invites"),
@@ -304,10 +310,15 @@ fn associated_resource_ids(room_id: RoomId) -> impl IntoIterator<Item = Resource
         room_id.resource_id().with_suffix("/streaming_targets"),
         room_id.resource_id().with_suffix("/streaming_targets/*"),
         room_id.resource_id().with_suffix("/start"),
+        room_id.resource_id().with_suffix("/restore"),
         room_id.resource_id().with_suffix("/tariff"),
+        room_id.resource_id().with_suffix("/capabilities"),
         room_id.resource_id().with_suffix("/event"),
         room_id.resource_id().with_suffix("/assets"),
         room_id.resource_id().with_suffix("/assets/*"),
+        room_id.resource_id().with_suffix("/module_states/*"),
+        room_id.resource_id().with_suffix("/legal_votes/*/protocol"),
+        room_id.resource_id().with_suffix("/transfer_ownership"),
     ]
 }
 
@@ -24,5 +24,6 @@ mod capture_api_error;
 
 pub mod deletion;
 pub mod event;
+pub mod tariff;
 
 pub use capture_api_error::CaptureApiError;
@@ -0,0 +1,228 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+//! Utils for resolving the [`Tariff`] of users and rooms
+use opentalk_controller_settings::{FeatureOverrides, Tariffs, TenantFeatureOverrides};
+use opentalk_database::{DbConnection, OptionalExt as _};
+use opentalk_db_storage::{rooms::Room, tariffs::Tariff, tenants::Tenant};
+use opentalk_types_api_v1::error::ApiError;
+use opentalk_types_common::{tariffs::TariffId, tenants::TenantId};
+
+use crate::CaptureApiError;
+
+/// The error returned when neither an assigned nor a default tariff exists.
+pub fn missing_tariff_error() -> ApiError {
+    ApiError::forbidden()
+        .with_code("missing_tariff")
+        .with_message("No tariff is assigned and no default tariff is configured for the tenant")
+}
+
+/// Get the default tariff of the tenant with the given OIDC tenant id.
+///
+/// Returns the `missing_tariff` error if no default tariff is configured for the tenant or the
+/// configured tariff does not exist.
+pub async fn get_default_tariff(
+    tariffs: &Tariffs,
+    conn: &mut DbConnection,
+    oidc_tenant_id: &str,
+) -> Result<Tariff, CaptureApiError> {
+    let Some(tariff_name) = tariffs.default_tariff_name_for(oidc_tenant_id) else {
+        return Err(missing_tariff_error().into());
+    };
+
+    match Tariff::get_by_name(conn, tariff_name).await.optional()? {
+        Some(tariff) => Ok(tariff),
+        None => {
+            log::error!(
+                "The default tariff '{tariff_name}' of tenant '{oidc_tenant_id}' does not exist"
+            );
+            Err(missing_tariff_error().into())
+        }
+    }
+}
+
+/// Get the tariff assigned to a user, falling back to the default tariff of the user's tenant.
+pub async fn get_user_tariff(
+    tariffs: &Tariffs,
+    conn: &mut DbConnection,
+    tariff_id: TariffId,
+    tenant_id: TenantId,
+) -> Result<Tariff, CaptureApiError> {
+    match Tariff::get(conn, tariff_id).await.optional()? {
+        Some(tariff) => Ok(tariff),
+        None => get_tenant_default_tariff(tariffs, conn, tenant_id).await,
+    }
+}
+
+/// Get the tariff of the room, which is the tariff of its creator, falling back to the default
+/// tariff of the room's tenant.
+pub async fn get_room_tariff(
+    tariffs: &Tariffs,
+    conn: &mut DbConnection,
+    room: &Room,
+) -> Result<Tariff, CaptureApiError> {
+    match Tariff::get_by_user_id(conn, &room.created_by)
+        .await
+        .optional()?
+    {
+        Some(tariff) => Ok(tariff),
+        None => get_tenant_default_tariff(tariffs, conn, room.tenant_id).await,
+    }
+}
+
+async fn get_tenant_default_tariff(
+    tariffs: &Tariffs,
+    conn: &mut DbConnection,
+    tenant_id: TenantId,
+) -> Result<Tariff, CaptureApiError> {
+    let tenant = Tenant::get(conn, tenant_id).await?;
+
+    get_default_tariff(tariffs, conn, &tenant.oidc_tenant_id.to_string()).await
+}
+
+/// Apply the feature overrides configured for the tenant to the tariff.
+///
+/// Skips looking up the tenant if no overrides are configured at all.
+pub async fn apply_feature_overrides(
+    feature_overrides: &FeatureOverrides,
+    conn: &mut DbConnection,
+    tenant_id: TenantId,
+    tariff: Tariff,
+) -> Result<Tariff, CaptureApiError> {
+    if feature_overrides.is_empty() {
+        return Ok(tariff);
+    }
+
+    let tenant = Tenant::get(conn, tenant_id).await?;
+
+    Ok(
+        match feature_overrides.for_tenant(&tenant.oidc_tenant_id.to_string()) {
+            Some(overrides) => with_feature_overrides(tariff, overrides),
+            None => tariff,
+        },
+    )
+}
+
+/// Apply the feature overrides of a tenant to the tariff.
+///
+/// Modules and features enabled by the overrides are no longer disabled by the tariff, modules
+/// and features disabled by the overrides are disabled in addition to the ones of the tariff.
+pub fn with_feature_overrides(mut tariff: Tariff, overrides: &TenantFeatureOverrides) -> Tariff {
+    let mut disabled_modules = tariff.disabled_modules();
+    disabled_modules.retain(|module| !overrides.enabled_modules.contains(module));
+    disabled_modules.extend(overrides.disabled_modules.iter().cloned());
+
+    let mut disabled_features = tariff.disabled_features();
+    disabled_features.retain(|feature| !overrides.enabled_features.contains(feature));
+    disabled_features.extend(overrides.disabled_features.iter().cloned());
+
+    tariff.disabled_modules = disabled_modules.into_iter().map(Some).collect();
+    tariff.disabled_features = disabled_features.into_iter().map(Some).collect();
+
+    tariff
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use opentalk_controller_settings::{Tariffs, TenantFeatureOverrides};
+    use opentalk_db_storage::tariffs::Tariff;
+    use opentalk_test_util::database::DatabaseContext;
+    use opentalk_types_common::tariffs::TariffId;
+    use pretty_assertions::assert_eq;
+    use serial_test::serial;
+
+    use super::{get_default_tariff, missing_tariff_error, with_feature_overrides};
+    use crate::CaptureApiError;
+
+    #[test]
+    fn feature_overrides_win_over_tariff() {
+        let tariff = Tariff {
+            id: TariffId::nil(),
+            name: "test".into(),
+            created_at: Default::default(),
+            updated_at: Default::default(),
+            quotas: Default::default(),
+            disabled_modules: vec![Some("whiteboard".parse().expect("valid module id"))],
+            disabled_features: vec![Some(
+                "call_in::call_in".parse().expect("valid module feature id"),
+            )],
+        };
+
+        let overrides = TenantFeatureOverrides {
+            enabled_modules: BTreeSet::from(["whiteboard".parse().expect("valid module id")]),
+            disabled_modules: BTreeSet::from(["recording".parse().expect("valid module id")]),
+            enabled_features: BTreeSet::from(["call_in::call_in"
+                .parse()
+                .expect("valid module feature id")]),
+            disabled_features: BTreeSet::from(["recording::stream"
+                .parse()
+                .expect("valid module feature id")]),
+        };
+
+        let tariff = with_feature_overrides(tariff, &overrides);
+
+        assert_eq!(
+            tariff.disabled_modules(),
+            BTreeSet::from(["recording".parse().expect("valid module id")])
+        );
+        assert_eq!(
+            tariff.disabled_features(),
+            BTreeSet::from(["recording::stream"
+                .parse()
+                .expect("valid module feature id")])
+        );
+    }
+
+    #[actix_rt::test]
+    #[serial]
+    async fn no_default_tariff() {
+        let db_ctx = DatabaseContext::new(true).await;
+        let mut conn = db_ctx.db.get_conn().await.unwrap();
+
+        let error = get_default_tariff(&Tariffs::default(), &mut conn, "tenant")
+            .await
+            .unwrap_err();
+
+        assert_eq!(error, CaptureApiError::from(missing_tariff_error()));
+    }
+
+    #[actix_rt::test]
+    #[serial]
+    async fn unknown_default_tariff() {
+        let db_ctx = DatabaseContext::new(true).await;
+        let mut conn = db_ctx.db.get_conn().await.unwrap();
+
+        let tariffs = Tariffs {
+            default_tariff_name: Some("does_not_exist".to_string()),
+            ..Default::default()
+        };
+
+        let error = get_default_tariff(&tariffs, &mut conn, "tenant")
+            .await
+            .unwrap_err();
+
+        assert_eq!(error, CaptureApiError::from(missing_tariff_error()));
+    }
+
+    #[actix_rt::test]
+    #[serial]
+    async fn tenant_default_tariff() {
+        let db_ctx = DatabaseContext::new(true).await;
+        let mut conn = db_ctx.db.get_conn().await.unwrap();
+
+        let tariffs = Tariffs {
+            default_tariff_name: Some("does_not_exist".to_string()),
+            tenants: BTreeMap::from([("tenant".to_string(), "OpenTalkDefaultTariff".to_string())]),
+            ..Default::default()
+        };
+
+        let tariff = get_default_tariff(&tariffs, &mut conn, "tenant")
+            .await
+            .unwrap();
+
+        assert_eq!(tariff.name, "OpenTalkDefaultTariff");
+    }
+}
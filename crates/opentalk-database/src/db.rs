@@ -32,6 +32,19 @@ impl std::fmt::Debug for Db {
     }
 }
 
+/// Snapshot of the state of the database connection pool
+#[derive(Debug, Clone, Copy)]
+pub struct PoolStatus {
+    /// The maximum number of connections in the pool
+    pub max_size: usize,
+    /// The number of connections currently in the pool
+    pub size: usize,
+    /// The number of idle connections in the pool
+    pub available: usize,
+    /// The number of tasks waiting for a connection
+    pub waiting: usize,
+}
+
 impl Db {
     /// Creates a new Db instance from the specified database settings.
     #[tracing::instrument(skip(db_settings))]
@@ -60,6 +73,18 @@ impl Db {
         self.metrics = Some(metrics);
     }
 
+    /// Returns the current status of the connection pool
+    pub fn pool_status(&self) -> PoolStatus {
+        let status = self.pool.status();
+
+        PoolStatus {
+            max_size: status.max_size,
+            size: status.size,
+            available: status.available,
+            waiting: status.waiting,
+        }
+    }
+
     /// Returns an established connection from the connection pool
     #[tracing::instrument(skip_all)]
     pub async fn get_conn(&self) -> crate::Result<DbConnection> {
@@ -21,7 +21,7 @@ mod db;
 mod metrics;
 pub mod query_helper;
 
-pub use db::Db;
+pub use db::{Db, PoolStatus};
 pub use metrics::DatabaseMetrics;
 
 /// Pooled connection alias
@@ -32,6 +32,7 @@ pub struct Asset {
     pub filename: String,
     pub tenant_id: TenantId,
     pub size: i64,
+    pub retention_tag: String,
 }
 
 impl Asset {
@@ -189,6 +190,7 @@ pub struct NewAsset {
     pub filename: String,
     pub tenant_id: TenantId,
     pub size: i64,
+    pub retention_tag: String,
 }
 
 impl NewAsset {
@@ -789,6 +789,7 @@ pub struct EventException {
     pub starts_at_tz: Option<TimeZone>,
     pub ends_at: Option<DateTime<Utc>>,
     pub ends_at_tz: Option<TimeZone>,
+    pub room_id: Option<RoomId>,
 }
 
 impl HasUsers for &EventException {
@@ -858,6 +859,7 @@ pub struct NewEventException {
     pub starts_at_tz: Option<TimeZone>,
     pub ends_at: Option<DateTime<Tz>>,
     pub ends_at_tz: Option<TimeZone>,
+    pub room_id: Option<RoomId>,
 }
 
 impl NewEventException {
@@ -882,6 +884,7 @@ pub struct UpdateEventException {
     pub starts_at_tz: Option<Option<TimeZone>>,
     pub ends_at: Option<Option<DateTime<Tz>>>,
     pub ends_at_tz: Option<Option<TimeZone>>,
+    pub room_id: Option<Option<RoomId>>,
 }
 
 impl UpdateEventException {
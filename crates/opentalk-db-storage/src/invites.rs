@@ -61,6 +61,8 @@ pub struct Invite {
     pub room: RoomId,
     pub active: bool,
     pub expiration: Option<DateTime<Utc>>,
+    pub usage_count: i64,
+    pub last_used_at: Option<DateTime<Utc>>,
 }
 
 pub type InviteWithUsers = (Invite, User, User);
@@ -78,6 +80,25 @@ impl Invite {
         Ok(invite)
     }
 
+    /// Count a usage of the invite and remember when it happened
+    #[tracing::instrument(err, skip_all)]
+    pub async fn record_usage(
+        conn: &mut DbConnection,
+        invite_code_id: InviteCode,
+        now: DateTime<Utc>,
+    ) -> Result<()> {
+        let query = diesel::update(invites::table)
+            .filter(invites::id.eq(invite_code_id))
+            .set((
+                invites::usage_count.eq(invites::usage_count + 1),
+                invites::last_used_at.eq(now),
+            ));
+
+        query.execute(conn).await?;
+
+        Ok(())
+    }
+
     /// Retrieve all invites
     #[tracing::instrument(err, skip_all)]
     pub async fn get_all(conn: &mut DbConnection) -> Result<Vec<Invite>> {
@@ -168,7 +168,8 @@ sql_enum!(
         SelfCheck = b"self_check",
         SyncStorageFiles = b"sync_storage_files",
         RoomCleanup = b"room_cleanup",
-        KeycloakAccountSync = b"keycloak_account_sync"
+        KeycloakAccountSync = b"keycloak_account_sync",
+        RoomTrashCleanup = b"room_trash_cleanup"
     }
 );
 
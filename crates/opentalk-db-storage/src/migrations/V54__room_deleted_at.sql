@@ -0,0 +1 @@
+ALTER TABLE rooms ADD deleted_at TIMESTAMPTZ;
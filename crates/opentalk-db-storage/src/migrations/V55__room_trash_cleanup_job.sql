@@ -0,0 +1 @@
+ALTER TYPE job_type ADD VALUE 'room_trash_cleanup';
@@ -0,0 +1,3 @@
+ALTER TABLE assets ADD retention_tag VARCHAR(255) NOT NULL DEFAULT 'default';
+UPDATE assets SET retention_tag = '90_days' WHERE kind = 'recording';
+UPDATE assets SET retention_tag = 'indefinite' WHERE kind = 'vote_protocol';
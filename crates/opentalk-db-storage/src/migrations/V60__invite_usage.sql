@@ -0,0 +1,3 @@
+ALTER TABLE invites
+	ADD COLUMN usage_count BIGINT DEFAULT 0 NOT NULL,
+	ADD COLUMN last_used_at TIMESTAMPTZ;
@@ -0,0 +1,2 @@
+ALTER TABLE event_exceptions
+	ADD COLUMN room_id UUID REFERENCES rooms(id) ON DELETE SET NULL;
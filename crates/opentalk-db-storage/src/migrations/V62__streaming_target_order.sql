@@ -0,0 +1,24 @@
+ALTER TABLE room_streaming_targets
+    ADD COLUMN position INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN is_primary BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Number the existing streaming targets of each room and make the first one the primary
+UPDATE room_streaming_targets
+SET position = numbered.position,
+    is_primary = numbered.position = 0
+FROM (
+    SELECT id, ROW_NUMBER() OVER (PARTITION BY room_id ORDER BY name, id) - 1 AS position
+    FROM room_streaming_targets
+) AS numbered
+WHERE room_streaming_targets.id = numbered.id;
+
+CREATE UNIQUE INDEX room_streaming_targets_primary_idx
+    ON room_streaming_targets (room_id)
+    WHERE is_primary;
+
+-- Allow reordering streaming targets and setting the primary for rooms that already exist
+UPDATE casbin_rule
+SET v2 = v2 || '|PUT'
+WHERE ptype = 'p'
+    AND v1 LIKE '/rooms/%/streaming_targets/*'
+    AND v2 NOT LIKE '%PUT%';
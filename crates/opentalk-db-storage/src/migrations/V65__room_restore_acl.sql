@@ -0,0 +1,10 @@
+-- Allow the owners of rooms that already exist to restore them after they have been deleted
+INSERT INTO casbin_rule (ptype, v0, v1, v2, v3, v4, v5)
+SELECT 'p', 'user::' || rooms.created_by, '/rooms/' || rooms.id || '/restore', 'POST', '', '', ''
+FROM rooms
+WHERE NOT EXISTS (
+    SELECT 1 FROM casbin_rule
+    WHERE casbin_rule.ptype = 'p'
+        AND casbin_rule.v0 = 'user::' || rooms.created_by
+        AND casbin_rule.v1 = '/rooms/' || rooms.id || '/restore'
+);
@@ -0,0 +1,13 @@
+-- Allow everyone with read access to an existing room to retrieve the protocols of its legal votes
+INSERT INTO casbin_rule (ptype, v0, v1, v2, v3, v4, v5)
+SELECT DISTINCT 'p', room_rules.v0, room_rules.v1 || '/legal_votes/*/protocol', 'GET', '', '', ''
+FROM casbin_rule AS room_rules
+WHERE room_rules.ptype = 'p'
+    AND room_rules.v1 ~ '^/rooms/[^/]+$'
+    AND room_rules.v2 LIKE '%GET%'
+    AND NOT EXISTS (
+        SELECT 1 FROM casbin_rule
+        WHERE casbin_rule.ptype = 'p'
+            AND casbin_rule.v0 = room_rules.v0
+            AND casbin_rule.v1 = room_rules.v1 || '/legal_votes/*/protocol'
+    );
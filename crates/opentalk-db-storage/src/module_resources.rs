@@ -8,7 +8,7 @@ use chrono::{DateTime, Utc};
 use diesel::{
     ExpressionMethods, Identifiable, QueryDsl, Queryable, pg::Pg, prelude::*, sql_types::Jsonb,
 };
-use diesel_async::RunQueryDsl;
+use diesel_async::{AsyncConnection, RunQueryDsl, scoped_futures::ScopedFutureExt};
 use opentalk_database::{DatabaseError, DbConnection, Result};
 use opentalk_types_common::{
     module_resources::ModuleResourceId, rooms::RoomId, tenants::TenantId, users::UserId,
@@ -239,6 +239,20 @@ impl ModuleResource {
         Ok(module_resource_ids)
     }
 
+    #[tracing::instrument(err, skip_all)]
+    pub async fn get_all_with_creator_for_room(
+        conn: &mut DbConnection,
+        room_id: RoomId,
+    ) -> Result<Vec<(ModuleResourceId, UserId)>> {
+        let query = module_resources::table
+            .select((module_resources::id, module_resources::created_by))
+            .filter(module_resources::room_id.eq(room_id));
+
+        let module_resources_with_creator = query.load(conn).await?;
+
+        Ok(module_resources_with_creator)
+    }
+
     #[tracing::instrument(err, skip_all)]
     pub async fn get_all_with_creator_and_owner(
         conn: &mut DbConnection,
@@ -349,6 +363,23 @@ impl NewModuleResource {
 
         Ok(module)
     }
+
+    /// Insert the module resource in place of the module resources matching the filter
+    ///
+    /// Both happen in a single transaction, so concurrent readers see either the old or the new
+    /// module resource, but never none or both.
+    #[tracing::instrument(err, skip_all)]
+    pub async fn replace(self, conn: &mut DbConnection, filter: Filter) -> Result<ModuleResource> {
+        conn.transaction(|conn| {
+            async move {
+                ModuleResource::delete(conn, filter).await?;
+
+                self.insert(conn).await
+            }
+            .scope_boxed()
+        })
+        .await
+    }
 }
 
 #[cfg(test)]
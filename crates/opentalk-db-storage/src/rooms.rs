@@ -59,13 +59,18 @@ pub struct Room {
     pub waiting_room: bool,
     pub tenant_id: TenantId,
     pub e2e_encryption: bool,
+    pub deleted_at: Option<DateTime<Utc>>,
 }
 
 impl Room {
     /// Select a room using the given id
+    ///
+    /// Rooms that have been marked as deleted are not returned.
     #[tracing::instrument(err, skip_all)]
     pub async fn get(conn: &mut DbConnection, id: RoomId) -> Result<Self> {
-        let query = rooms::table.filter(rooms::id.eq(id));
+        let query = rooms::table
+            .filter(rooms::id.eq(id))
+            .filter(rooms::deleted_at.is_null());
 
         let room: Room = query.get_result(conn).await?;
 
@@ -73,10 +78,13 @@ impl Room {
     }
 
     /// Select a room and the creator using the given room id
+    ///
+    /// Rooms that have been marked as deleted are not returned.
     #[tracing::instrument(err, skip_all)]
     pub async fn get_with_user(conn: &mut DbConnection, id: RoomId) -> Result<(Self, User)> {
         let query = rooms::table
             .filter(rooms::id.eq(id))
+            .filter(rooms::deleted_at.is_null())
             .inner_join(users::table);
 
         let result: (Room, User) = query.get_result(conn).await?;
@@ -84,7 +92,7 @@ impl Room {
         Ok(result)
     }
 
-    /// Select all rooms joined with their creator
+    /// Select all rooms joined with their creator, including the ones marked as deleted
     #[tracing::instrument(err, skip_all)]
     pub async fn get_all_with_creator(conn: &mut DbConnection) -> Result<Vec<(Room, User)>> {
         let query = rooms::table
@@ -96,7 +104,7 @@ impl Room {
         Ok(room_with_creator)
     }
 
-    /// Select all rooms paginated
+    /// Select all rooms paginated, excluding the ones marked as deleted
     #[tracing::instrument(err, skip_all)]
     pub async fn get_all_with_creator_paginated(
         conn: &mut DbConnection,
@@ -106,6 +114,7 @@ impl Room {
         let query = rooms::table
             .inner_join(users::table)
             .select((rooms::all_columns, users::all_columns))
+            .filter(rooms::deleted_at.is_null())
             .order_by(rooms::id.desc())
             .paginate_by(limit, page);
 
@@ -114,7 +123,7 @@ impl Room {
         Ok(rooms_with_total)
     }
 
-    /// Select all rooms filtered by ids
+    /// Select all rooms filtered by ids, excluding the ones marked as deleted
     #[tracing::instrument(err, skip_all)]
     pub async fn get_by_ids_with_creator_paginated(
         conn: &mut DbConnection,
@@ -126,6 +135,7 @@ impl Room {
             .inner_join(users::table)
             .select((rooms::all_columns, users::all_columns))
             .filter(rooms::id.eq_any(ids))
+            .filter(rooms::deleted_at.is_null())
             .order_by(rooms::id.desc())
             .paginate_by(limit, page);
 
@@ -147,6 +157,80 @@ impl Room {
         Ok(room_ids)
     }
 
+    /// Select the ids of all rooms that were marked as deleted before the given point in time
+    #[tracing::instrument(err, skip_all)]
+    pub async fn get_all_deleted_before_ids(
+        conn: &mut DbConnection,
+        deleted_before: DateTime<Utc>,
+    ) -> Result<Vec<RoomId>> {
+        let query = rooms::table
+            .select(rooms::id)
+            .filter(rooms::deleted_at.le(deleted_before))
+            .order_by(rooms::deleted_at.asc());
+
+        let room_ids = query.load(conn).await?;
+
+        Ok(room_ids)
+    }
+
+    /// Mark a room as deleted without removing it from the database
+    ///
+    /// Returns [`DatabaseError::NotFound`](opentalk_database::DatabaseError::NotFound) if the
+    /// room does not exist or is already marked as deleted.
+    #[tracing::instrument(err, skip_all)]
+    pub async fn mark_deleted(conn: &mut DbConnection, room_id: RoomId) -> Result<Room> {
+        let target = rooms::table
+            .filter(rooms::id.eq(room_id))
+            .filter(rooms::deleted_at.is_null());
+
+        let room = diesel::update(target)
+            .set(rooms::deleted_at.eq(Utc::now()))
+            .get_result(conn)
+            .await?;
+
+        Ok(room)
+    }
+
+    /// Restore a room that was marked as deleted
+    ///
+    /// Returns [`DatabaseError::NotFound`](opentalk_database::DatabaseError::NotFound) if the
+    /// room does not exist or is not marked as deleted.
+    #[tracing::instrument(err, skip_all)]
+    pub async fn restore(conn: &mut DbConnection, room_id: RoomId) -> Result<Room> {
+        let target = rooms::table
+            .filter(rooms::id.eq(room_id))
+            .filter(rooms::deleted_at.is_not_null());
+
+        let room = diesel::update(target)
+            .set(rooms::deleted_at.eq(None::<DateTime<Utc>>))
+            .get_result(conn)
+            .await?;
+
+        Ok(room)
+    }
+
+    /// Transfer the ownership of a room to another user
+    ///
+    /// Returns [`DatabaseError::NotFound`](opentalk_database::DatabaseError::NotFound) if the
+    /// room does not exist or is marked as deleted.
+    #[tracing::instrument(err, skip_all)]
+    pub async fn transfer_ownership(
+        conn: &mut DbConnection,
+        room_id: RoomId,
+        new_owner: UserId,
+    ) -> Result<Room> {
+        let target = rooms::table
+            .filter(rooms::id.eq(room_id))
+            .filter(rooms::deleted_at.is_null());
+
+        let room = diesel::update(target)
+            .set(rooms::created_by.eq(new_owner))
+            .get_result(conn)
+            .await?;
+
+        Ok(room)
+    }
+
     /// Get the room's tariff
     #[tracing::instrument(err, skip_all)]
     pub async fn get_tariff(&self, conn: &mut DbConnection) -> Result<Tariff> {
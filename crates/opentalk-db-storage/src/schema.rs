@@ -15,6 +15,8 @@ diesel::table! {
         filename -> Varchar,
         tenant_id -> Uuid,
         size -> Int8,
+        #[max_length = 255]
+        retention_tag -> Varchar,
     }
 }
 
@@ -70,6 +72,7 @@ diesel::table! {
         ends_at -> Nullable<Timestamptz>,
         #[max_length = 255]
         ends_at_tz -> Nullable<Varchar>,
+        room_id -> Nullable<Uuid>,
     }
 }
 
@@ -192,6 +195,8 @@ diesel::table! {
         room -> Uuid,
         active -> Bool,
         expiration -> Nullable<Timestamptz>,
+        usage_count -> Int8,
+        last_used_at -> Nullable<Timestamptz>,
     }
 }
 
@@ -284,6 +289,8 @@ diesel::table! {
         streaming_endpoint -> Text,
         streaming_key -> Text,
         public_url -> Text,
+        position -> Int4,
+        is_primary -> Bool,
     }
 }
 
@@ -300,6 +307,7 @@ diesel::table! {
         waiting_room -> Bool,
         tenant_id -> Uuid,
         e2e_encryption -> Bool,
+        deleted_at -> Nullable<Timestamptz>,
     }
 }
 
@@ -392,6 +400,7 @@ diesel::joinable!(assets -> tenants (tenant_id));
 diesel::joinable!(event_email_invites -> events (event_id));
 diesel::joinable!(event_email_invites -> users (created_by));
 diesel::joinable!(event_exceptions -> events (event_id));
+diesel::joinable!(event_exceptions -> rooms (room_id));
 diesel::joinable!(event_exceptions -> users (created_by));
 diesel::joinable!(event_favorites -> events (event_id));
 diesel::joinable!(event_favorites -> users (user_id));
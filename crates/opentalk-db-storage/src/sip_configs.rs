@@ -56,6 +56,18 @@ impl SipConfig {
         Ok(sip_config)
     }
 
+    /// Get the sip config for the specified room, `None` if the room has none
+    #[tracing::instrument(err, skip_all)]
+    pub async fn get_optional_by_room(
+        conn: &mut DbConnection,
+        room_id: RoomId,
+    ) -> Result<Option<SipConfig>> {
+        let query = sip_configs::table.filter(sip_configs::room.eq(&room_id));
+        let sip_config = query.get_result(conn).await.optional()?;
+
+        Ok(sip_config)
+    }
+
     /// Delete the sip config for the specified room
     #[tracing::instrument(err, skip_all)]
     pub async fn delete_by_room(conn: &mut DbConnection, room_id: RoomId) -> Result<()> {
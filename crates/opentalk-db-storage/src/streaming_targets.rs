@@ -2,7 +2,7 @@
 //
 // SPDX-License-Identifier: EUPL-1.2
 
-use diesel::{ExpressionMethods, Identifiable, QueryDsl, Queryable};
+use diesel::{ExpressionMethods, Identifiable, OptionalExtension, QueryDsl, Queryable};
 use diesel_async::{AsyncConnection, RunQueryDsl, scoped_futures::ScopedFutureExt};
 use opentalk_database::{DatabaseError, DbConnection, Result};
 use opentalk_types_common::{
@@ -35,9 +35,16 @@ pub struct RoomStreamingTargetRecord {
     pub streaming_endpoint: String,
     pub streaming_key: StreamingKey,
     pub public_url: String,
+    pub position: i32,
+    pub is_primary: bool,
 }
 
 impl RoomStreamingTargetRecord {
+    /// The position of the streaming target in the order of the room's streaming targets
+    pub fn order(&self) -> u32 {
+        u32::try_from(self.position).unwrap_or_default()
+    }
+
     /// Retrieve a single streaming target
     #[tracing::instrument(err, skip_all)]
     pub async fn get(
@@ -54,7 +61,7 @@ impl RoomStreamingTargetRecord {
         Ok(streaming_target)
     }
 
-    /// Retrieve all streaming targets
+    /// Retrieve all streaming targets in their configured order
     #[tracing::instrument(err, skip_all)]
     pub async fn get_all_for_room(
         conn: &mut DbConnection,
@@ -62,28 +69,55 @@ impl RoomStreamingTargetRecord {
     ) -> Result<Vec<RoomStreamingTargetRecord>> {
         let streaming_targets = room_streaming_targets::table
             .filter(room_streaming_targets::room_id.eq(room_id))
+            .order_by(room_streaming_targets::position)
+            .then_order_by(room_streaming_targets::id)
             .load(conn)
             .await?;
 
         Ok(streaming_targets)
     }
 
+    /// Retrieve the primary streaming target of a room
+    #[tracing::instrument(err, skip_all)]
+    pub async fn get_primary_for_room(
+        conn: &mut DbConnection,
+        room_id: RoomId,
+    ) -> Result<Option<RoomStreamingTargetRecord>> {
+        let streaming_target = room_streaming_targets::table
+            .filter(room_streaming_targets::room_id.eq(room_id))
+            .filter(room_streaming_targets::is_primary.eq(true))
+            .first(conn)
+            .await
+            .optional()?;
+
+        Ok(streaming_target)
+    }
+
     /// Delete a streaming target using the given room & streaming target id
+    ///
+    /// If the primary streaming target is deleted, the first of the remaining streaming targets
+    /// becomes the primary.
     #[tracing::instrument(err, skip_all)]
     pub async fn delete_by_id(
         conn: &mut DbConnection,
         room_id: RoomId,
         streaming_target_id: StreamingTargetId,
     ) -> Result<()> {
-        let _ = diesel::delete(
-            room_streaming_targets::table
-                .filter(room_streaming_targets::id.eq(streaming_target_id))
-                .filter(room_streaming_targets::room_id.eq(room_id)),
-        )
-        .execute(conn)
-        .await?;
-
-        Ok(())
+        conn.transaction(|conn| {
+            async move {
+                let _ = diesel::delete(
+                    room_streaming_targets::table
+                        .filter(room_streaming_targets::id.eq(streaming_target_id))
+                        .filter(room_streaming_targets::room_id.eq(room_id)),
+                )
+                .execute(conn)
+                .await?;
+
+                ensure_primary(conn, room_id).await
+            }
+            .scope_boxed()
+        })
+        .await
     }
 
     /// Delete all streaming targets that are associated with a specific room
@@ -113,6 +147,8 @@ impl TryFrom<RoomStreamingTargetRecord> for RoomStreamingTarget {
 
         Ok(Self {
             id: record.id,
+            order: record.order(),
+            is_primary: record.is_primary,
             streaming_target: StreamingTarget {
                 name: record.name,
                 kind,
@@ -157,6 +193,8 @@ pub struct RoomStreamingTargetNew {
     pub streaming_endpoint: String,
     pub streaming_key: String,
     pub public_url: String,
+    pub position: i32,
+    pub is_primary: bool,
 }
 
 impl RoomStreamingTargetNew {
@@ -173,6 +211,7 @@ impl RoomStreamingTargetNew {
         streaming_target_kind: StreamingTargetKind,
         room_id: RoomId,
         name: String,
+        position: i32,
     ) -> Self {
         match streaming_target_kind {
             StreamingTargetKind::Custom {
@@ -186,6 +225,8 @@ impl RoomStreamingTargetNew {
                 streaming_endpoint: streaming_endpoint.into(),
                 streaming_key: streaming_key.into(),
                 public_url: public_url.into(),
+                position,
+                is_primary: position == 0,
             },
         }
     }
@@ -254,6 +295,8 @@ pub async fn get_room_streaming_targets(
 
             let room_streaming_target = RoomStreamingTarget {
                 id: st.id,
+                order: st.order(),
+                is_primary: st.is_primary,
                 streaming_target: StreamingTarget {
                     name: st.name,
                     kind: StreamingTargetKind::Custom {
@@ -271,26 +314,136 @@ pub async fn get_room_streaming_targets(
     Ok(room_streaming_targets)
 }
 
+/// Insert a streaming target after the existing streaming targets of the room
+///
+/// The first streaming target of a room becomes its primary.
 pub async fn insert_room_streaming_target(
     conn: &mut DbConnection,
     room_id: RoomId,
     streaming_target: StreamingTarget,
 ) -> Result<RoomStreamingTarget> {
-    let streaming_target_record = RoomStreamingTargetNew::from_streaming_target_kind(
-        streaming_target.kind.clone(),
-        room_id,
-        streaming_target.name.clone(),
-    )
-    .insert(conn)
-    .await?;
+    let kind = streaming_target.kind.clone();
+    let name = streaming_target.name.clone();
+
+    let streaming_target_record = conn
+        .transaction(|conn| {
+            async move {
+                let last_position: Option<i32> = room_streaming_targets::table
+                    .filter(room_streaming_targets::room_id.eq(room_id))
+                    .select(diesel::dsl::max(room_streaming_targets::position))
+                    .first(conn)
+                    .await?;
+
+                RoomStreamingTargetNew::from_streaming_target_kind(
+                    kind,
+                    room_id,
+                    name,
+                    last_position.map_or(0, |position| position + 1),
+                )
+                .insert(conn)
+                .await
+            }
+            .scope_boxed()
+        })
+        .await?;
 
     let room_streaming_target = RoomStreamingTarget {
         id: streaming_target_record.id,
+        order: streaming_target_record.order(),
+        is_primary: streaming_target_record.is_primary,
         streaming_target,
     };
     Ok(room_streaming_target)
 }
 
+/// Store the order of the streaming targets of a room
+///
+/// The position of each streaming target is its index in `streaming_target_ids`.
+pub async fn reorder_room_streaming_targets(
+    conn: &mut DbConnection,
+    room_id: RoomId,
+    streaming_target_ids: Vec<StreamingTargetId>,
+) -> Result<Vec<RoomStreamingTarget>> {
+    conn.transaction(|conn| {
+        async move {
+            for (position, streaming_target_id) in (0..).zip(streaming_target_ids) {
+                let _ = diesel::update(room_streaming_targets::table)
+                    .filter(room_streaming_targets::id.eq(streaming_target_id))
+                    .filter(room_streaming_targets::room_id.eq(room_id))
+                    .set(room_streaming_targets::position.eq(position))
+                    .execute(conn)
+                    .await?;
+            }
+
+            Ok::<_, DatabaseError>(())
+        }
+        .scope_boxed()
+    })
+    .await?;
+
+    get_room_streaming_targets(conn, room_id).await
+}
+
+/// Make the streaming target the primary of its room
+pub async fn set_primary_room_streaming_target(
+    conn: &mut DbConnection,
+    room_id: RoomId,
+    streaming_target_id: StreamingTargetId,
+) -> Result<RoomStreamingTarget> {
+    conn.transaction(|conn| {
+        async move {
+            let _ = diesel::update(room_streaming_targets::table)
+                .filter(room_streaming_targets::room_id.eq(room_id))
+                .filter(room_streaming_targets::is_primary.eq(true))
+                .set(room_streaming_targets::is_primary.eq(false))
+                .execute(conn)
+                .await?;
+
+            let record: RoomStreamingTargetRecord = diesel::update(room_streaming_targets::table)
+                .filter(room_streaming_targets::id.eq(streaming_target_id))
+                .filter(room_streaming_targets::room_id.eq(room_id))
+                .set(room_streaming_targets::is_primary.eq(true))
+                .returning(room_streaming_targets::all_columns)
+                .get_result(conn)
+                .await?;
+
+            Ok(record)
+        }
+        .scope_boxed()
+    })
+    .await
+    .and_then(RoomStreamingTarget::try_from)
+}
+
+/// Make the first streaming target of a room the primary if the room has none
+async fn ensure_primary(conn: &mut DbConnection, room_id: RoomId) -> Result<()> {
+    let has_primary = RoomStreamingTargetRecord::get_primary_for_room(conn, room_id)
+        .await?
+        .is_some();
+    if has_primary {
+        return Ok(());
+    }
+
+    let first: Option<StreamingTargetId> = room_streaming_targets::table
+        .filter(room_streaming_targets::room_id.eq(room_id))
+        .order_by(room_streaming_targets::position)
+        .then_order_by(room_streaming_targets::id)
+        .select(room_streaming_targets::id)
+        .first(conn)
+        .await
+        .optional()?;
+
+    if let Some(streaming_target_id) = first {
+        let _ = diesel::update(room_streaming_targets::table)
+            .filter(room_streaming_targets::id.eq(streaming_target_id))
+            .set(room_streaming_targets::is_primary.eq(true))
+            .execute(conn)
+            .await?;
+    }
+
+    Ok(())
+}
+
 pub async fn override_room_streaming_targets(
     conn: &mut DbConnection,
     room_id: RoomId,
@@ -301,13 +454,14 @@ pub async fn override_room_streaming_targets(
             // Delete existing records by room_id
             RoomStreamingTargetRecord::delete_by_room_id(conn, room_id).await?;
 
-            let new_records: Vec<RoomStreamingTargetNew> = streaming_targets
-                .into_iter()
-                .map(|streaming_target| {
+            let new_records: Vec<RoomStreamingTargetNew> = (0..)
+                .zip(streaming_targets)
+                .map(|(position, streaming_target)| {
                     RoomStreamingTargetNew::from_streaming_target_kind(
                         streaming_target.kind,
                         room_id,
                         streaming_target.name,
+                        position,
                     )
                 })
                 .collect();
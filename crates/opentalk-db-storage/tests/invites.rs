@@ -0,0 +1,81 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use chrono::{TimeZone as _, Utc};
+use opentalk_db_storage::{
+    invites::{Invite, NewInvite},
+    rooms::NewRoom,
+};
+use pretty_assertions::assert_eq;
+use serial_test::serial;
+
+use crate::common::make_user;
+
+mod common;
+
+#[tokio::test]
+#[serial]
+async fn record_usage() {
+    let db_ctx = opentalk_test_util::database::DatabaseContext::new(true).await;
+    let mut conn = db_ctx.db.get_conn().await.unwrap();
+
+    let user = make_user(&mut conn, "Aileen", "Strange", "Spectre").await;
+    let room = NewRoom {
+        created_by: user.id,
+        password: None,
+        waiting_room: false,
+        e2e_encryption: false,
+        tenant_id: user.tenant_id,
+    }
+    .insert(&mut conn)
+    .await
+    .unwrap();
+
+    let invite = NewInvite {
+        created_by: user.id,
+        updated_by: user.id,
+        room: room.id,
+        active: true,
+        expiration: None,
+    }
+    .insert(&mut conn)
+    .await
+    .unwrap();
+    let other_invite = NewInvite {
+        created_by: user.id,
+        updated_by: user.id,
+        room: room.id,
+        active: true,
+        expiration: None,
+    }
+    .insert(&mut conn)
+    .await
+    .unwrap();
+
+    // New invites have not been used yet
+    assert_eq!(invite.usage_count, 0);
+    assert_eq!(invite.last_used_at, None);
+
+    let first_usage = Utc.with_ymd_and_hms(2024, 7, 20, 14, 16, 19).unwrap();
+    let second_usage = Utc.with_ymd_and_hms(2024, 7, 20, 14, 30, 0).unwrap();
+
+    Invite::record_usage(&mut conn, invite.id, first_usage)
+        .await
+        .unwrap();
+    let invite_after_first_usage = Invite::get(&mut conn, invite.id).await.unwrap();
+    assert_eq!(invite_after_first_usage.usage_count, 1);
+    assert_eq!(invite_after_first_usage.last_used_at, Some(first_usage));
+
+    Invite::record_usage(&mut conn, invite.id, second_usage)
+        .await
+        .unwrap();
+    let invite_after_second_usage = Invite::get(&mut conn, invite.id).await.unwrap();
+    assert_eq!(invite_after_second_usage.usage_count, 2);
+    assert_eq!(invite_after_second_usage.last_used_at, Some(second_usage));
+
+    // Recording a usage does not touch other invites of the room
+    let other_invite = Invite::get(&mut conn, other_invite.id).await.unwrap();
+    assert_eq!(other_invite.usage_count, 0);
+    assert_eq!(other_invite.last_used_at, None);
+}
@@ -0,0 +1,119 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use chrono::{Duration, Utc};
+use opentalk_database::DbConnection;
+use opentalk_db_storage::{
+    rooms::{NewRoom, Room},
+    users::User,
+};
+use pretty_assertions::assert_eq;
+use serial_test::serial;
+
+use crate::common::make_user;
+
+mod common;
+
+async fn make_room(conn: &mut DbConnection, user: &User) -> Room {
+    NewRoom {
+        created_by: user.id,
+        password: None,
+        waiting_room: false,
+        e2e_encryption: false,
+        tenant_id: user.tenant_id,
+    }
+    .insert(conn)
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+#[serial]
+async fn soft_delete_and_restore() {
+    let db_ctx = opentalk_test_util::database::DatabaseContext::new(true).await;
+    let mut conn = db_ctx.db.get_conn().await.unwrap();
+
+    let user = make_user(&mut conn, "Aileen", "Strange", "Spectre").await;
+    let room = make_room(&mut conn, &user).await;
+
+    let deleted = Room::mark_deleted(&mut conn, room.id).await.unwrap();
+    assert!(deleted.deleted_at.is_some());
+
+    // Deleted rooms can neither be fetched nor deleted again
+    assert!(
+        Room::get(&mut conn, room.id)
+            .await
+            .unwrap_err()
+            .is_not_found()
+    );
+    assert!(
+        Room::get_with_user(&mut conn, room.id)
+            .await
+            .unwrap_err()
+            .is_not_found()
+    );
+    assert!(
+        Room::mark_deleted(&mut conn, room.id)
+            .await
+            .unwrap_err()
+            .is_not_found()
+    );
+
+    let restored = Room::restore(&mut conn, room.id).await.unwrap();
+    assert_eq!(restored.deleted_at, None);
+    assert_eq!(Room::get(&mut conn, room.id).await.unwrap().id, room.id);
+
+    // Rooms that are not marked as deleted cannot be restored
+    assert!(
+        Room::restore(&mut conn, room.id)
+            .await
+            .unwrap_err()
+            .is_not_found()
+    );
+}
+
+#[tokio::test]
+#[serial]
+async fn listing_filters_deleted_rooms() {
+    let db_ctx = opentalk_test_util::database::DatabaseContext::new(true).await;
+    let mut conn = db_ctx.db.get_conn().await.unwrap();
+
+    let user = make_user(&mut conn, "Aileen", "Strange", "Spectre").await;
+    let kept = make_room(&mut conn, &user).await;
+    let deleted = make_room(&mut conn, &user).await;
+    _ = Room::mark_deleted(&mut conn, deleted.id).await.unwrap();
+
+    let (rooms, total) = Room::get_all_with_creator_paginated(&mut conn, 10, 1)
+        .await
+        .unwrap();
+    assert_eq!(
+        rooms.iter().map(|(room, _)| room.id).collect::<Vec<_>>(),
+        vec![kept.id]
+    );
+    assert_eq!(total, 1);
+
+    let (rooms, total) =
+        Room::get_by_ids_with_creator_paginated(&mut conn, &[kept.id, deleted.id], 10, 1)
+            .await
+            .unwrap();
+    assert_eq!(
+        rooms.iter().map(|(room, _)| room.id).collect::<Vec<_>>(),
+        vec![kept.id]
+    );
+    assert_eq!(total, 1);
+
+    // The trash cleanup only sees the rooms that were deleted before the given point in time
+    assert_eq!(
+        Room::get_all_deleted_before_ids(&mut conn, Utc::now())
+            .await
+            .unwrap(),
+        vec![deleted.id]
+    );
+    assert_eq!(
+        Room::get_all_deleted_before_ids(&mut conn, Utc::now() - Duration::days(1))
+            .await
+            .unwrap(),
+        vec![]
+    );
+}
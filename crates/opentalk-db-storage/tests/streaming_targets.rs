@@ -0,0 +1,95 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use opentalk_db_storage::{
+    rooms::NewRoom,
+    streaming_targets::{
+        RoomStreamingTargetRecord, get_room_streaming_targets, insert_room_streaming_target,
+        reorder_room_streaming_targets, set_primary_room_streaming_target,
+    },
+};
+use opentalk_types_common::streaming::{StreamingTarget, StreamingTargetKind};
+use pretty_assertions::assert_eq;
+use serial_test::serial;
+
+use crate::common::make_user;
+
+mod common;
+
+fn streaming_target(name: &str) -> StreamingTarget {
+    StreamingTarget {
+        name: name.into(),
+        kind: StreamingTargetKind::Custom {
+            streaming_endpoint: "rtmp://stream.example.com/live/".parse().unwrap(),
+            streaming_key: "abcdefgh".parse().unwrap(),
+            public_url: "https://example.com/live".parse().unwrap(),
+        },
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn order_and_primary() {
+    let db_ctx = opentalk_test_util::database::DatabaseContext::new(true).await;
+    let mut conn = db_ctx.db.get_conn().await.unwrap();
+
+    let user = make_user(&mut conn, "Aileen", "Strange", "Spectre").await;
+    let room = NewRoom {
+        created_by: user.id,
+        password: None,
+        waiting_room: false,
+        e2e_encryption: false,
+        tenant_id: user.tenant_id,
+    }
+    .insert(&mut conn)
+    .await
+    .unwrap();
+
+    let mut ids = Vec::new();
+    for name in ["first", "second", "third"] {
+        let target = insert_room_streaming_target(&mut conn, room.id, streaming_target(name))
+            .await
+            .unwrap();
+        ids.push(target.id);
+    }
+
+    // Targets are appended and the first one becomes the primary
+    let targets = get_room_streaming_targets(&mut conn, room.id)
+        .await
+        .unwrap();
+    assert_eq!(
+        targets
+            .iter()
+            .map(|target| (target.id, target.order, target.is_primary))
+            .collect::<Vec<_>>(),
+        vec![(ids[0], 0, true), (ids[1], 1, false), (ids[2], 2, false)]
+    );
+
+    let targets = reorder_room_streaming_targets(&mut conn, room.id, vec![ids[2], ids[0], ids[1]])
+        .await
+        .unwrap();
+    assert_eq!(
+        targets.iter().map(|target| target.id).collect::<Vec<_>>(),
+        vec![ids[2], ids[0], ids[1]]
+    );
+
+    let primary = set_primary_room_streaming_target(&mut conn, room.id, ids[1])
+        .await
+        .unwrap();
+    assert!(primary.is_primary);
+    let targets = get_room_streaming_targets(&mut conn, room.id)
+        .await
+        .unwrap();
+    assert_eq!(targets.iter().filter(|target| target.is_primary).count(), 1);
+
+    // Deleting the primary promotes the first remaining target
+    RoomStreamingTargetRecord::delete_by_id(&mut conn, room.id, ids[1])
+        .await
+        .unwrap();
+    let primary = RoomStreamingTargetRecord::get_primary_for_room(&mut conn, room.id)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(primary.id, ids[2]);
+}
@@ -4,7 +4,8 @@
 
 use opentalk_db_storage::streaming_targets::{
     RoomStreamingTargetRecord, UpdateRoomStreamingTarget, get_room_streaming_targets,
-    insert_room_streaming_target, override_room_streaming_targets,
+    insert_room_streaming_target, override_room_streaming_targets, reorder_room_streaming_targets,
+    set_primary_room_streaming_target,
 };
 use opentalk_inventory::{RoomStreamingTargetInventory, error::StorageBackendSnafu};
 use opentalk_types_common::{
@@ -81,6 +82,26 @@ impl RoomStreamingTargetInventory for DatabaseConnection {
             .context(StorageBackendSnafu)
     }
 
+    async fn reorder_room_streaming_targets(
+        &mut self,
+        room_id: RoomId,
+        streaming_target_ids: Vec<StreamingTargetId>,
+    ) -> Result<Vec<RoomStreamingTarget>> {
+        reorder_room_streaming_targets(&mut self.inner, room_id, streaming_target_ids)
+            .await
+            .context(StorageBackendSnafu)
+    }
+
+    async fn set_primary_room_streaming_target(
+        &mut self,
+        room_id: RoomId,
+        streaming_target_id: StreamingTargetId,
+    ) -> Result<RoomStreamingTarget> {
+        set_primary_room_streaming_target(&mut self.inner, room_id, streaming_target_id)
+            .await
+            .context(StorageBackendSnafu)
+    }
+
     async fn replace_room_streaming_targets(
         &mut self,
         room_id: RoomId,
@@ -56,6 +56,20 @@ pub trait RoomStreamingTargetInventory {
         streaming_target_id: StreamingTargetId,
     ) -> Result<()>;
 
+    /// Store the order of the streaming targets of a room.
+    async fn reorder_room_streaming_targets(
+        &mut self,
+        room_id: RoomId,
+        streaming_target_ids: Vec<StreamingTargetId>,
+    ) -> Result<Vec<RoomStreamingTarget>>;
+
+    /// Make a room streaming target the primary of the room.
+    async fn set_primary_room_streaming_target(
+        &mut self,
+        room_id: RoomId,
+        streaming_target_id: StreamingTargetId,
+    ) -> Result<RoomStreamingTarget>;
+
     /// Replace the streaming targets for a room.
     async fn replace_room_streaming_targets(
         &mut self,
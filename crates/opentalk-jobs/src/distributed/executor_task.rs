@@ -29,7 +29,7 @@ use crate::{
     },
     jobs::{
         AdhocEventCleanup, EventCleanup, InviteCleanup, KeycloakAccountSync, RoomCleanup,
-        SelfCheck, SyncStorageFiles,
+        RoomTrashCleanup, SelfCheck, SyncStorageFiles,
     },
 };
 
@@ -376,6 +376,9 @@ impl JobExecutor {
             db::jobs::JobType::KeycloakAccountSync => {
                 execution_data.execute::<KeycloakAccountSync>().await
             }
+            db::jobs::JobType::RoomTrashCleanup => {
+                execution_data.execute::<RoomTrashCleanup>().await
+            }
         };
 
         let job_execution_update = match result {
@@ -9,6 +9,7 @@ mod event_cleanup;
 mod invite_cleanup;
 mod keycloak_account_sync;
 mod room_cleanup;
+mod room_trash_cleanup;
 mod self_check;
 mod sync_storage_files;
 mod user_cleanup;
@@ -18,6 +19,7 @@ pub use event_cleanup::EventCleanup;
 pub use invite_cleanup::InviteCleanup;
 pub use keycloak_account_sync::KeycloakAccountSync;
 pub use room_cleanup::RoomCleanup;
+pub use room_trash_cleanup::RoomTrashCleanup;
 pub use self_check::SelfCheck;
 pub use sync_storage_files::SyncStorageFiles;
 pub use user_cleanup::UserCleanup;
@@ -0,0 +1,150 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::{Days, Utc};
+use kustos::Authz;
+use log::Log;
+use opentalk_controller_settings::Settings;
+use opentalk_controller_utils::deletion::{Deleter, RoomDeleter};
+use opentalk_database::Db;
+use opentalk_db_storage::rooms::Room;
+use opentalk_log::{debug, error, info, warn};
+use opentalk_signaling_core::{ExchangeHandle, ObjectStorage};
+use opentalk_types_common::rooms::RoomId;
+use serde::{Deserialize, Serialize};
+use snafu::{Report, ResultExt};
+
+use crate::{
+    Error, Job, JobParameters,
+    error::{ParameterLoadingSnafu, ParameterSerializingSnafu},
+};
+
+/// A job for permanently deleting rooms that were marked as deleted at minimum a defined
+/// duration ago
+#[derive(Debug)]
+pub struct RoomTrashCleanup;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoomTrashCleanupParameters {
+    /// The number of days a deleted room is kept before it is purged.
+    ///
+    /// A value of `0` purges all rooms that are currently marked as deleted.
+    #[serde(default = "default_days_since_deletion")]
+    days_since_deletion: u64,
+
+    /// Only purge these rooms, regardless of how long ago they were marked as deleted.
+    ///
+    /// Allows administrators to permanently delete specific rooms right away.
+    #[serde(default)]
+    room_ids: Vec<RoomId>,
+
+    #[serde(default)]
+    fail_on_shared_folder_deletion_error: bool,
+}
+
+impl JobParameters for RoomTrashCleanupParameters {
+    fn try_from_json(json: serde_json::Value) -> Result<Self, Error> {
+        serde_json::from_value(json).context(ParameterLoadingSnafu)
+    }
+
+    fn to_json(&self) -> Result<serde_json::Value, Error> {
+        serde_json::to_value(self).context(ParameterSerializingSnafu)
+    }
+}
+
+#[async_trait]
+impl Job for RoomTrashCleanup {
+    type Parameters = RoomTrashCleanupParameters;
+
+    async fn execute(
+        logger: &dyn Log,
+        db: Arc<Db>,
+        exchange_handle: ExchangeHandle,
+        settings: &Settings,
+        parameters: Self::Parameters,
+    ) -> Result<(), Error> {
+        info!(log: logger, "Starting deleted rooms cleanup job");
+        debug!(log: logger, "Job parameters: {parameters:?}");
+
+        let mut conn = db.get_conn().await?;
+
+        let room_ids = if parameters.room_ids.is_empty() {
+            let delete_before = Utc::now()
+                .checked_sub_days(Days::new(parameters.days_since_deletion))
+                .ok_or_else(|| {
+                    error!(log: logger, "Couldn't subtract number of retention days");
+                    Error::JobExecutionFailed
+                })?;
+
+            Room::get_all_deleted_before_ids(&mut conn, delete_before).await?
+        } else {
+            let deleted_room_ids = Room::get_all_deleted_before_ids(&mut conn, Utc::now()).await?;
+
+            for room_id in &parameters.room_ids {
+                if !deleted_room_ids.contains(room_id) {
+                    warn!(log: logger, "Room {room_id} is not marked as deleted, skipping");
+                }
+            }
+
+            deleted_room_ids
+                .into_iter()
+                .filter(|room_id| parameters.room_ids.contains(room_id))
+                .collect()
+        };
+
+        if room_ids.is_empty() {
+            info!(log: logger, "No deleted rooms found. Job finished!");
+            return Ok(());
+        }
+
+        let authz = Authz::new(db.clone()).await?;
+
+        let object_storage = ObjectStorage::new(&settings.minio).await?;
+
+        let room_count = room_ids.len();
+        info!(log: logger, "Identified {room_count} deleted rooms for permanent deletion");
+
+        let mut deleter_failures = 0usize;
+        for room_id in room_ids {
+            info!(log: logger, "Deleting room {room_id}");
+            let deleter =
+                RoomDeleter::new(room_id, parameters.fail_on_shared_folder_deletion_error);
+
+            if let Err(e) = deleter
+                .perform(
+                    logger,
+                    &mut conn,
+                    &authz,
+                    None,
+                    exchange_handle.clone(),
+                    settings,
+                    &object_storage,
+                )
+                .await
+            {
+                warn!(log: logger, "Failed deletion: {}", Report::from_error(e));
+                deleter_failures += 1;
+            }
+        }
+
+        info!(
+            log: logger,
+            "Deleted {} rooms",
+            room_count - deleter_failures
+        );
+        if deleter_failures > 0 {
+            warn!(log: logger, "{deleter_failures} deleted rooms could not be purged due to errors");
+            return Err(Error::JobExecutionFailed);
+        }
+
+        Ok(())
+    }
+}
+
+fn default_days_since_deletion() -> u64 {
+    30
+}
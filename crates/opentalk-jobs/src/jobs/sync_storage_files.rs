@@ -352,6 +352,7 @@ mod tests {
             filename: "does_not_exist_in_storage.txt".into(),
             tenant_id: user.tenant_id,
             size: 42,
+            retention_tag: "default".into(),
         };
 
         asset.insert_for_room(&mut conn, room.id).await.unwrap();
@@ -20,9 +20,12 @@
     unused_results
 )]
 
+mod redact;
+
 use std::panic::Location;
 
 use log::Log;
+pub use redact::{RedactionMode, Redactor};
 
 // WARNING: this is not part of the crate's public API and is subject to change at any time
 // Copied over from the `log` crate, but extended by the `logger` argument
@@ -0,0 +1,159 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+//! Redaction of personally identifiable information in formatted log output
+
+use std::{
+    borrow::Cow,
+    hash::{BuildHasher, RandomState},
+};
+
+/// How identifiers found in log output are replaced
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedactionMode {
+    /// Replace identifiers with a fixed placeholder
+    Redact,
+    /// Replace identifiers with a keyed hash
+    ///
+    /// The key is generated randomly when the [`Redactor`] is created, so the same identifier
+    /// results in the same hash for the lifetime of the process, but hashes can't be correlated
+    /// across restarts or reversed by hashing known identifiers.
+    Hash,
+}
+
+/// Replaces UUIDs and e-mail addresses in formatted log lines
+///
+/// User ids, participant ids and most other identifiers in OpenTalk are UUIDs. Because the
+/// identifiers can't be told apart once a log line is formatted, every UUID is replaced, including
+/// room and event ids.
+#[derive(Debug)]
+pub struct Redactor {
+    mode: RedactionMode,
+    hasher: RandomState,
+}
+
+impl Redactor {
+    /// Create a new redactor
+    pub fn new(mode: RedactionMode) -> Self {
+        Self {
+            mode,
+            hasher: RandomState::new(),
+        }
+    }
+
+    /// Redact all identifiers contained in `input`
+    ///
+    /// Returns the input unchanged if it does not contain any identifiers.
+    pub fn redact<'a>(&self, input: &'a str) -> Cow<'a, str> {
+        let uuids_redacted = self.replace(input, find_uuid);
+
+        match self.replace(&uuids_redacted, find_email) {
+            Cow::Borrowed(_) => uuids_redacted,
+            Cow::Owned(redacted) => Cow::Owned(redacted),
+        }
+    }
+
+    fn replace<'a>(
+        &self,
+        input: &'a str,
+        find: fn(&str, usize) -> Option<(usize, usize)>,
+    ) -> Cow<'a, str> {
+        let mut output = String::new();
+        let mut copied_until = 0;
+        let mut position = 0;
+
+        while let Some((start, end)) = find(input, position) {
+            output.push_str(&input[copied_until..start]);
+            output.push_str(&self.placeholder(&input[start..end]));
+            copied_until = end;
+            position = end;
+        }
+
+        if copied_until == 0 {
+            return Cow::Borrowed(input);
+        }
+
+        output.push_str(&input[copied_until..]);
+        Cow::Owned(output)
+    }
+
+    fn placeholder(&self, identifier: &str) -> String {
+        match self.mode {
+            RedactionMode::Redact => "[redacted]".to_owned(),
+            RedactionMode::Hash => format!(
+                "[redacted:{:016x}]",
+                self.hasher.hash_one(identifier.to_ascii_lowercase())
+            ),
+        }
+    }
+}
+
+/// Find the next hyphenated UUID at or after `from`, returns its byte range
+fn find_uuid(input: &str, from: usize) -> Option<(usize, usize)> {
+    const GROUPS: [usize; 5] = [8, 4, 4, 4, 12];
+    const LENGTH: usize = 36;
+
+    let bytes = input.as_bytes();
+    let mut start = from;
+
+    while start + LENGTH <= bytes.len() {
+        let preceded_by_word = start > 0 && bytes[start - 1].is_ascii_alphanumeric();
+        let followed_by_word = bytes
+            .get(start + LENGTH)
+            .is_some_and(u8::is_ascii_alphanumeric);
+
+        if !preceded_by_word && !followed_by_word {
+            let mut position = start;
+            let matches = GROUPS.iter().enumerate().all(|(index, length)| {
+                let group = &bytes[position..position + length];
+                position += length;
+
+                let separated = index == GROUPS.len() - 1 || {
+                    position += 1;
+                    bytes[position - 1] == b'-'
+                };
+
+                separated && group.iter().all(u8::is_ascii_hexdigit)
+            });
+
+            if matches {
+                return Some((start, start + LENGTH));
+            }
+        }
+
+        start += 1;
+    }
+
+    None
+}
+
+/// Find the next e-mail address at or after `from`, returns its byte range
+fn find_email(input: &str, from: usize) -> Option<(usize, usize)> {
+    let is_local = |b: &u8| b.is_ascii_alphanumeric() || b"._%+-".contains(b);
+    let is_domain = |b: &u8| b.is_ascii_alphanumeric() || b".-".contains(b);
+
+    let bytes = input.as_bytes();
+
+    for at in (from..bytes.len()).filter(|&i| bytes[i] == b'@') {
+        let start = bytes[from..at]
+            .iter()
+            .rposition(|b| !is_local(b))
+            .map_or(from, |i| from + i + 1);
+
+        let end = bytes[at + 1..]
+            .iter()
+            .position(|b| !is_domain(b))
+            .map_or(bytes.len(), |i| at + 1 + i);
+
+        // Trailing dots belong to the surrounding sentence, not to the domain
+        let domain = input[at + 1..end].trim_end_matches('.');
+        let end = at + 1 + domain.len();
+
+        if start < at && domain.contains('.') && !domain.starts_with('.') {
+            return Some((start, end));
+        }
+    }
+
+    None
+}
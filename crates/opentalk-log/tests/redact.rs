@@ -0,0 +1,50 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use std::borrow::Cow;
+
+use opentalk_log::{RedactionMode, Redactor};
+
+#[test]
+fn redact_uuids_and_emails() {
+    let redactor = Redactor::new(RedactionMode::Redact);
+
+    assert_eq!(
+        redactor.redact(
+            "Participant 00000000-0000-0000-0000-00000000002a of user \
+             5A1E2D4C-9B3F-4E6A-8C7D-0E1F2A3B4C5D joined"
+        ),
+        "Participant [redacted] of user [redacted] joined"
+    );
+    assert_eq!(
+        redactor.redact("Failed to query user for email: alice.doe+ot@example.org."),
+        "Failed to query user for email: [redacted]."
+    );
+}
+
+#[test]
+fn keep_text_without_identifiers() {
+    let redactor = Redactor::new(RedactionMode::Redact);
+
+    for input in [
+        "Starting controller",
+        "Build abcdef12-3456-7890-abcd-ef1234567890f is not an id",
+        "Pattern opentalk@v1 is not an address",
+    ] {
+        assert!(matches!(redactor.redact(input), Cow::Borrowed(output) if output == input));
+    }
+}
+
+#[test]
+fn hash_identifiers_consistently() {
+    let redactor = Redactor::new(RedactionMode::Hash);
+
+    let first = redactor.redact("user 00000000-0000-0000-0000-00000000002a");
+    let second = redactor.redact("user 00000000-0000-0000-0000-00000000002A");
+    let other = redactor.redact("user 00000000-0000-0000-0000-00000000002b");
+
+    assert!(first.starts_with("user [redacted:"));
+    assert_eq!(first, second);
+    assert_ne!(first, other);
+}
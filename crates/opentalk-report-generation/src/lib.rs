@@ -27,23 +27,107 @@
 
 mod report_date_time;
 mod report_generation_error;
+mod report_timezone;
 mod world;
 
-use std::{borrow::Cow, collections::BTreeMap, path::Path};
+use std::{
+    borrow::Cow,
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
 
+use qrcode::{QrCode, render::svg};
 pub use report_date_time::{ReportDateTime, ToReportDateTime};
 pub use report_generation_error::ReportGenerationError;
-use report_generation_error::{CompilationSnafu, DumpDirectoryCreationSnafu, DumpFileExportSnafu};
-use snafu::ResultExt;
+use report_generation_error::{
+    CompilationSnafu, DumpDirectoryCreationSnafu, DumpFileExportSnafu, FontDirectoryReadSnafu,
+    NoFontsFoundSnafu, QrCodeEncodingSnafu,
+};
+pub use report_timezone::ReportTimezoneCandidates;
+use snafu::{ResultExt, ensure};
 use typst::{World as _, diag::SourceResult};
+use typst_kit::fonts::Fonts;
 use typst_pdf::PdfOptions;
 use world::World;
 
+/// The environment variable that overrides the configured report dump path.
+pub const DUMP_PATH_ENV_VAR: &str = "OPENTALK_REPORT_DUMP_PATH";
+
+/// The file name under which [`verification_qr_code`] images are passed to the report templates.
+pub const VERIFICATION_QR_CODE_FILE: &str = "verification_qr_code.svg";
+
+/// The file that is written to the dump directory by [`check_dump_path`].
+const DUMP_PATH_CHECK_FILE: &str = ".opentalk-report-dump-check";
+
+/// Determine the directory into which report generation data is dumped.
+///
+/// The [`DUMP_PATH_ENV_VAR`] environment variable takes precedence over the
+/// `configured` path for backwards compatibility.
+pub fn resolve_dump_path(configured: Option<&Path>) -> Option<PathBuf> {
+    std::env::var_os(DUMP_PATH_ENV_VAR)
+        .map(PathBuf::from)
+        .or_else(|| configured.map(Path::to_path_buf))
+}
+
+/// Check that the dump directory exists or can be created, and that it is writable.
+pub fn check_dump_path(dump_path: &Path) -> Result<(), ReportGenerationError> {
+    std::fs::create_dir_all(dump_path).context(DumpDirectoryCreationSnafu)?;
+
+    let check_file = dump_path.join(DUMP_PATH_CHECK_FILE);
+    std::fs::write(&check_file, []).context(DumpFileExportSnafu)?;
+    std::fs::remove_file(&check_file).context(DumpFileExportSnafu)?;
+
+    Ok(())
+}
+
+/// Check that the font directory can be read and contains at least one loadable font.
+///
+/// Returns the number of fonts that were loaded from the directory.
+pub fn check_font_path(font_path: &Path) -> Result<usize, ReportGenerationError> {
+    let _ = std::fs::read_dir(font_path).context(FontDirectoryReadSnafu)?;
+
+    let fonts = Fonts::searcher()
+        .include_system_fonts(false)
+        .include_embedded_fonts(false)
+        .search_with([font_path]);
+
+    let loaded = fonts
+        .fonts
+        .iter()
+        .filter(|slot| slot.get().is_some())
+        .count();
+    ensure!(
+        loaded > 0,
+        NoFontsFoundSnafu {
+            path: font_path.to_path_buf(),
+        }
+    );
+
+    Ok(loaded)
+}
+
+/// Render a QR code encoding `url` as an SVG image.
+///
+/// The image is meant to be passed to the report template as [`VERIFICATION_QR_CODE_FILE`].
+pub fn verification_qr_code(url: &str) -> Result<Vec<u8>, ReportGenerationError> {
+    let code = QrCode::new(url.as_bytes()).context(QrCodeEncodingSnafu)?;
+
+    let image = code
+        .render::<svg::Color<'_>>()
+        .min_dimensions(200, 200)
+        .build();
+
+    Ok(image.into_bytes())
+}
+
 /// Generate a pdf file from a typst source string and data.
+///
+/// The fonts in `font_path` are available to the template in addition to the embedded fonts.
 pub fn generate_pdf_report(
     source: String,
     data: BTreeMap<&Path, Cow<'static, [u8]>>,
     dump_to_path: Option<&Path>,
+    font_path: Option<&Path>,
 ) -> Result<Vec<u8>, ReportGenerationError> {
     if let Some(dump_path) = dump_to_path {
         log::info!("Dumping raw data and generated report file to {dump_path:?}");
@@ -56,7 +140,7 @@ pub fn generate_pdf_report(
         }
     }
 
-    let world = World::new(source, data);
+    let world = World::new(source, data, font_path);
 
     let report = match generate_pdf_report_inner(&world) {
         Ok(d) => Ok(d),
@@ -84,3 +168,32 @@ fn generate_pdf_report_inner(world: &World) -> SourceResult<Vec<u8>> {
     let document = typst::compile(&world).output?;
     typst_pdf::pdf(&document, &PdfOptions::default())
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{ReportGenerationError, check_font_path, verification_qr_code};
+
+    #[test]
+    fn font_path_without_fonts() {
+        let font_dir = tempfile::tempdir().unwrap();
+        std::fs::write(font_dir.path().join("README.txt"), "no fonts here").unwrap();
+
+        assert!(matches!(
+            check_font_path(font_dir.path()),
+            Err(ReportGenerationError::NoFontsFound { .. })
+        ));
+        assert!(matches!(
+            check_font_path(&font_dir.path().join("missing")),
+            Err(ReportGenerationError::FontDirectoryRead { .. })
+        ));
+    }
+
+    #[test]
+    fn verification_qr_code_is_svg() {
+        let image = verification_qr_code("https://opentalk.example.org/verify/42").unwrap();
+        let image = String::from_utf8(image).unwrap();
+
+        assert!(image.starts_with("<?xml"));
+        assert!(image.contains("<svg"));
+    }
+}
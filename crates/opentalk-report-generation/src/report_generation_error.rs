@@ -2,6 +2,8 @@
 //
 // SPDX-License-Identifier: EUPL-1.2
 
+use std::path::PathBuf;
+
 use ecow::EcoVec;
 use snafu::Snafu;
 use typst::diag::SourceDiagnostic;
@@ -30,4 +32,25 @@ pub enum ReportGenerationError {
         /// The source of the error
         source: std::io::Error,
     },
+
+    /// Error reading the font directory
+    #[snafu(display("Error reading the font directory"))]
+    FontDirectoryRead {
+        /// The source of the error
+        source: std::io::Error,
+    },
+
+    /// The font directory does not contain any loadable font
+    #[snafu(display("No loadable fonts found in {path:?}"))]
+    NoFontsFound {
+        /// The font directory
+        path: PathBuf,
+    },
+
+    /// Error encoding the verification QR code
+    #[snafu(display("Error encoding the verification QR code"))]
+    QrCodeEncoding {
+        /// The source of the error
+        source: qrcode::types::QrError,
+    },
 }
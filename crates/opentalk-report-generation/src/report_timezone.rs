@@ -0,0 +1,88 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use chrono_tz::Tz;
+
+/// The candidates from which the timezone of a report is resolved.
+///
+/// The candidates are tried in the order of the fields, the first one that is set is used.
+/// Reports fall back to UTC if none of them is set.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReportTimezoneCandidates {
+    /// The timezone that was explicitly requested for the report.
+    pub requested: Option<Tz>,
+
+    /// The timezone of the event that the room belongs to.
+    pub event: Option<Tz>,
+
+    /// The timezone of the user who the report is created for.
+    pub user: Option<Tz>,
+
+    /// The default timezone configured for the tenant of the room.
+    pub tenant_default: Option<Tz>,
+}
+
+impl ReportTimezoneCandidates {
+    /// Resolve the timezone in which the timestamps of the report are shown.
+    pub fn resolve(&self) -> Tz {
+        self.requested
+            .or(self.event)
+            .or(self.user)
+            .or(self.tenant_default)
+            .unwrap_or(Tz::UTC)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono_tz::Tz;
+    use pretty_assertions::assert_eq;
+
+    use super::ReportTimezoneCandidates;
+
+    const CANDIDATES: ReportTimezoneCandidates = ReportTimezoneCandidates {
+        requested: Some(chrono_tz::Europe::Berlin),
+        event: Some(chrono_tz::Europe::Vienna),
+        user: Some(chrono_tz::America::New_York),
+        tenant_default: Some(chrono_tz::Asia::Tokyo),
+    };
+
+    #[test]
+    fn requested_timezone() {
+        assert_eq!(CANDIDATES.resolve(), chrono_tz::Europe::Berlin);
+    }
+
+    #[test]
+    fn event_timezone() {
+        let candidates = ReportTimezoneCandidates {
+            requested: None,
+            ..CANDIDATES
+        };
+        assert_eq!(candidates.resolve(), chrono_tz::Europe::Vienna);
+    }
+
+    #[test]
+    fn user_timezone() {
+        let candidates = ReportTimezoneCandidates {
+            requested: None,
+            event: None,
+            ..CANDIDATES
+        };
+        assert_eq!(candidates.resolve(), chrono_tz::America::New_York);
+    }
+
+    #[test]
+    fn tenant_default_timezone() {
+        let candidates = ReportTimezoneCandidates {
+            tenant_default: Some(chrono_tz::Asia::Tokyo),
+            ..Default::default()
+        };
+        assert_eq!(candidates.resolve(), chrono_tz::Asia::Tokyo);
+    }
+
+    #[test]
+    fn utc_fallback() {
+        assert_eq!(ReportTimezoneCandidates::default().resolve(), Tz::UTC);
+    }
+}
@@ -28,11 +28,15 @@ pub struct World {
 }
 
 impl World {
-    pub fn new(text: String, files: BTreeMap<&Path, Cow<'static, [u8]>>) -> Self {
+    pub fn new(
+        text: String,
+        files: BTreeMap<&Path, Cow<'static, [u8]>>,
+        font_path: Option<&Path>,
+    ) -> Self {
         let fonts = Fonts::searcher()
             .include_system_fonts(false)
             .include_embedded_fonts(true)
-            .search();
+            .search_with(font_path);
 
         let library = Library::builder().build();
 
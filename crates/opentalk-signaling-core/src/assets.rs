@@ -5,14 +5,16 @@
 use std::{
     fmt::Display,
     pin::Pin,
+    str::FromStr,
     sync::Arc,
     task::{self, Poll},
+    time::Duration,
 };
 
 use aws_sdk_s3::primitives::{ByteStream, ByteStreamError};
 use bigdecimal::BigDecimal;
 use bytes::Bytes;
-use futures::Stream;
+use futures::{Stream, future::ready, stream};
 use opentalk_database::{Db, DbConnection};
 use opentalk_db_storage::{
     assets::{Asset, NewAsset},
@@ -67,15 +69,95 @@ pub enum AssetError {
     },
 }
 
+impl AssetError {
+    /// Whether the error was caused by the object storage, e.g. because it is unreachable
+    fn is_object_storage_error(&self) -> bool {
+        matches!(self, Self::ObjectStorage { .. })
+    }
+}
+
 type Result<T, E = AssetError> = std::result::Result<T, E>;
 
+/// The number of attempts to store an asset in [`save_asset_with_retry`]
+const SAVE_ASSET_ATTEMPTS: u32 = 3;
+
+/// The delay before retrying a failed asset upload, doubled for each further attempt
+const SAVE_ASSET_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
 const ASSET_FILE_NAME_MAX_LENGTH: usize = 100;
 
+/// The maximum length of an [`AssetRetentionTag`]
+const ASSET_RETENTION_TAG_MAX_LENGTH: usize = 255;
+
+/// Retention tag for assets without a specific retention policy
+pub const ASSET_RETENTION_TAG_DEFAULT: &str = "default";
+
+/// Retention tag for assets which are kept for 90 days
+pub const ASSET_RETENTION_TAG_90_DAYS: &str = "90_days";
+
+/// Retention tag for assets which are kept until they are deleted explicitly
+pub const ASSET_RETENTION_TAG_INDEFINITE: &str = "indefinite";
+
+/// A tag describing how long an asset is retained
+///
+/// The tag is stored with the asset, so a cleanup job can apply the policy declared when the
+/// asset was created. Tags consist of lowercase ascii letters, digits, `_` and `-`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetRetentionTag(String);
+
+impl AssetRetentionTag {
+    /// The default retention tag for assets of the given kind
+    pub fn default_for_kind(kind: &AssetFileKind) -> Self {
+        let tag = match kind.to_string().as_str() {
+            "recording" => ASSET_RETENTION_TAG_90_DAYS,
+            "vote_protocol" => ASSET_RETENTION_TAG_INDEFINITE,
+            _ => ASSET_RETENTION_TAG_DEFAULT,
+        };
+
+        Self(tag.to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Display for AssetRetentionTag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[derive(Debug, Snafu)]
+#[snafu(display(
+    "Invalid retention tag, expected 1 to {ASSET_RETENTION_TAG_MAX_LENGTH} characters of `a-z`, `0-9`, `_` or `-`"
+))]
+pub struct InvalidAssetRetentionTag;
+
+impl FromStr for AssetRetentionTag {
+    type Err = InvalidAssetRetentionTag;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let valid = !s.is_empty()
+            && s.len() <= ASSET_RETENTION_TAG_MAX_LENGTH
+            && s.chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-');
+
+        if !valid {
+            return Err(InvalidAssetRetentionTag);
+        }
+
+        Ok(Self(s.to_string()))
+    }
+}
+
+#[derive(Clone)]
 pub struct NewAssetFileName {
     event_title: Option<EventTitle>,
     kind: AssetFileKind,
     timestamp: Timestamp,
     extension: FileExtension,
+    retention_tag: Option<AssetRetentionTag>,
 }
 
 impl NewAssetFileName {
@@ -85,6 +167,7 @@ impl NewAssetFileName {
             kind,
             timestamp,
             extension,
+            retention_tag: None,
         }
     }
 
@@ -99,8 +182,22 @@ impl NewAssetFileName {
             kind,
             timestamp,
             extension,
+            retention_tag: None,
         }
     }
+
+    /// Use a custom retention tag instead of the default one for the asset kind
+    pub fn with_retention_tag(mut self, retention_tag: AssetRetentionTag) -> Self {
+        self.retention_tag = Some(retention_tag);
+        self
+    }
+
+    /// The retention tag the asset is stored with
+    pub fn retention_tag(&self) -> AssetRetentionTag {
+        self.retention_tag
+            .clone()
+            .unwrap_or_else(|| AssetRetentionTag::default_for_kind(&self.kind))
+    }
 }
 
 impl Display for NewAssetFileName {
@@ -144,6 +241,37 @@ pub async fn save_asset<E>(
     db: Arc<Db>,
     room_id: RoomId,
     namespace: Option<ModuleId>,
+    filename: NewAssetFileName,
+    data: impl Stream<Item = Result<Bytes, E>> + Unpin,
+    chunk_format: ChunkFormat,
+) -> Result<(AssetId, String)>
+where
+    ObjectStorageError: From<E>,
+{
+    save_asset_with_id(
+        storage,
+        db,
+        room_id,
+        namespace,
+        AssetId::generate(),
+        filename,
+        data,
+        chunk_format,
+    )
+    .await
+}
+
+/// Save an asset under an id that was reserved beforehand
+///
+/// This allows embedding a reference to the asset into its own content, e.g. a link in a
+/// generated report. See [`save_asset`] for details.
+#[allow(clippy::too_many_arguments)]
+pub async fn save_asset_with_id<E>(
+    storage: &ObjectStorage,
+    db: Arc<Db>,
+    room_id: RoomId,
+    namespace: Option<ModuleId>,
+    asset_id: AssetId,
     mut filename: NewAssetFileName,
     data: impl Stream<Item = Result<Bytes, E>> + Unpin,
     chunk_format: ChunkFormat,
@@ -155,8 +283,6 @@ where
 
     let room = prepare_storage(room_id, &mut conn).await?;
 
-    let asset_id = AssetId::generate();
-
     // Upload to s3 storage
     let size: Result<i64, _> = storage
         .put(&asset_key(&asset_id), data, chunk_format)
@@ -181,6 +307,7 @@ where
     }
 
     let kind = filename.kind.clone();
+    let retention_tag = filename.retention_tag();
     let filename = filename.to_string();
 
     // Create a database entry for the uploaded asset
@@ -189,6 +316,7 @@ where
         namespace,
         filename.clone(),
         kind,
+        retention_tag,
         asset_id,
         room,
         size,
@@ -211,6 +339,51 @@ where
     Ok((asset_id, filename))
 }
 
+/// Save an asset in the long term storage, retrying if the storage is unavailable
+///
+/// The upload is retried a limited number of times with an increasing delay. Other errors, e.g.
+/// [`AssetError::AssetStorageExceeded`], are returned right away.
+///
+/// The asset is stored under `asset_id`, see [`save_asset_with_id`].
+pub async fn save_asset_with_retry(
+    storage: &ObjectStorage,
+    db: Arc<Db>,
+    room_id: RoomId,
+    namespace: Option<ModuleId>,
+    asset_id: AssetId,
+    filename: NewAssetFileName,
+    data: Bytes,
+) -> Result<(AssetId, String)> {
+    let mut retry_interval = SAVE_ASSET_RETRY_INTERVAL;
+    let mut attempt = 1;
+
+    loop {
+        let result = save_asset_with_id(
+            storage,
+            db.clone(),
+            room_id,
+            namespace.clone(),
+            asset_id,
+            filename.clone(),
+            stream::once(ready(Ok::<_, ObjectStorageError>(data.clone()))),
+            ChunkFormat::Data,
+        )
+        .await;
+
+        match result {
+            Err(e) if e.is_object_storage_error() && attempt < SAVE_ASSET_ATTEMPTS => {
+                log::warn!(
+                    "Failed to store asset (attempt {attempt}/{SAVE_ASSET_ATTEMPTS}), retrying in {retry_interval:?}: {e}"
+                );
+                tokio::time::sleep(retry_interval).await;
+                retry_interval *= 2;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
 async fn rollback_object_storage(storage: &ObjectStorage, asset_id: &AssetId) -> Result<()> {
     log::info!("Rollback asset upload since room update failed");
     if let Err(rollback_err) = storage.delete(asset_key(asset_id)).await {
@@ -229,6 +402,7 @@ async fn insert_asset_into_database(
     namespace: Option<ModuleId>,
     filename: String,
     kind: AssetFileKind,
+    retention_tag: AssetRetentionTag,
     asset_id: AssetId,
     room: Room,
     size: i64,
@@ -240,6 +414,7 @@ async fn insert_asset_into_database(
         kind: kind.to_string(),
         tenant_id: room.tenant_id,
         size,
+        retention_tag: retention_tag.to_string(),
     }
     .insert_for_room(db_conn, room.id)
     .await
@@ -270,6 +445,125 @@ pub async fn get_asset(
     Ok(ByStreamExt(stream))
 }
 
+/// Get a byte range of an asset from the object storage
+pub async fn get_asset_range(
+    storage: &ObjectStorage,
+    asset_id: &AssetId,
+    range: ByteRange,
+) -> Result<ByStreamExt, crate::object_storage::ObjectStorageError> {
+    let stream = storage
+        .get_range(asset_key(asset_id), range.start, range.end)
+        .await?;
+    Ok(ByStreamExt(stream))
+}
+
+/// Get the entity tag of an asset from the object storage
+///
+/// The returned tag is the opaque part of the entity tag, without the surrounding quotes.
+pub async fn get_asset_e_tag(
+    storage: &ObjectStorage,
+    asset_id: &AssetId,
+) -> Result<String, crate::object_storage::ObjectStorageError> {
+    let e_tag = storage.get_e_tag(asset_key(asset_id)).await?;
+    Ok(e_tag.trim_matches('"').to_string())
+}
+
+/// The entity tags a client sent in an `If-None-Match` request header
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IfNoneMatchCondition {
+    /// Matches any existing asset
+    Any,
+
+    /// Matches any of the contained opaque entity tags
+    Tags(Vec<String>),
+}
+
+impl IfNoneMatchCondition {
+    /// Check whether the entity tag matches the condition
+    ///
+    /// Uses the weak comparison, i.e. only the opaque tags are compared.
+    pub fn matches(&self, e_tag: &str) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Tags(tags) => tags.iter().any(|tag| tag == e_tag),
+        }
+    }
+}
+
+/// A byte range a client sent in a `Range` request header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeSpec {
+    /// The bytes from the first to the second position, both inclusive
+    FromTo(u64, u64),
+
+    /// All bytes starting at the position
+    From(u64),
+
+    /// The given number of bytes at the end
+    Last(u64),
+}
+
+impl RangeSpec {
+    /// Resolve the range against the size of an asset
+    ///
+    /// Returns [`None`] if the range is not satisfiable. A range exceeding the end of the asset is
+    /// shortened to the asset size.
+    pub fn resolve(&self, size: u64) -> Option<ByteRange> {
+        let last = size.checked_sub(1)?;
+
+        match *self {
+            Self::FromTo(start, end) if start <= end && start <= last => Some(ByteRange {
+                start,
+                end: end.min(last),
+            }),
+            Self::From(start) if start <= last => Some(ByteRange { start, end: last }),
+            Self::Last(length) if length > 0 => Some(ByteRange {
+                start: size - length.min(size),
+                end: last,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Resolve the ranges of a `Range` request header against the size of an asset
+///
+/// Only a single range is supported, so [`None`] is returned for multiple ranges as well as for
+/// a range that is not satisfiable.
+pub fn resolve_ranges(ranges: &[RangeSpec], size: u64) -> Option<ByteRange> {
+    match ranges {
+        [range] => range.resolve(size),
+        _ => None,
+    }
+}
+
+/// A satisfiable byte range of an asset, both positions are inclusive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+    pub start: u64,
+    pub end: u64,
+}
+
+/// The result of downloading an asset
+pub enum AssetDownload {
+    /// The client already has the current version of the asset
+    NotModified { e_tag: String },
+
+    /// The asset contents
+    Content { e_tag: String, data: ByStreamExt },
+
+    /// The requested range of the asset contents
+    PartialContent {
+        e_tag: String,
+        range: ByteRange,
+        size: u64,
+        data: ByStreamExt,
+    },
+
+    /// The requested range cannot be served for an asset of this size
+    RangeNotSatisfiable { size: u64 },
+}
+
 /// Delete an asset from the object storage
 pub async fn delete_asset(
     storage: &ObjectStorage,
@@ -322,7 +616,10 @@ mod tests {
     };
     use pretty_assertions::assert_eq;
 
-    use super::NewAssetFileName;
+    use super::{
+        AssetRetentionTag, ByteRange, IfNoneMatchCondition, NewAssetFileName, RangeSpec,
+        resolve_ranges,
+    };
 
     #[test]
     fn new_asset_filename() {
@@ -364,4 +661,83 @@ mod tests {
             &filename.to_string()
         );
     }
+
+    #[test]
+    fn retention_tag() {
+        let filename = |kind| NewAssetFileName::new(kind, Timestamp::now(), FileExtension::pdf());
+
+        assert_eq!(
+            filename(asset_file_kind!("recording"))
+                .retention_tag()
+                .as_str(),
+            "90_days"
+        );
+        assert_eq!(
+            filename(asset_file_kind!("vote_protocol"))
+                .retention_tag()
+                .as_str(),
+            "indefinite"
+        );
+        assert_eq!(
+            filename(asset_file_kind!("meeting_report"))
+                .retention_tag()
+                .as_str(),
+            "default"
+        );
+
+        let custom: AssetRetentionTag = "legal-hold_10y".parse().unwrap();
+        assert_eq!(
+            filename(asset_file_kind!("recording"))
+                .with_retention_tag(custom.clone())
+                .retention_tag(),
+            custom
+        );
+
+        assert!(AssetRetentionTag::from_str("").is_err());
+        assert!(AssetRetentionTag::from_str("Keep Forever").is_err());
+        assert!(AssetRetentionTag::from_str(&"a".repeat(256)).is_err());
+    }
+
+    #[test]
+    fn if_none_match_condition() {
+        assert!(IfNoneMatchCondition::Any.matches("abc"));
+
+        let condition = IfNoneMatchCondition::Tags(vec!["abc".to_string(), "def".to_string()]);
+        assert!(condition.matches("def"));
+        assert!(!condition.matches("xyz"));
+
+        assert!(!IfNoneMatchCondition::Tags(vec![]).matches("abc"));
+    }
+
+    #[test]
+    fn resolve_range() {
+        let range = |start, end| Some(ByteRange { start, end });
+
+        assert_eq!(RangeSpec::FromTo(0, 99).resolve(1000), range(0, 99));
+        assert_eq!(RangeSpec::FromTo(900, 1999).resolve(1000), range(900, 999));
+        assert_eq!(RangeSpec::FromTo(1000, 1999).resolve(1000), None);
+        assert_eq!(RangeSpec::FromTo(10, 5).resolve(1000), None);
+
+        assert_eq!(RangeSpec::From(500).resolve(1000), range(500, 999));
+        assert_eq!(RangeSpec::From(1000).resolve(1000), None);
+
+        assert_eq!(RangeSpec::Last(100).resolve(1000), range(900, 999));
+        assert_eq!(RangeSpec::Last(2000).resolve(1000), range(0, 999));
+        assert_eq!(RangeSpec::Last(0).resolve(1000), None);
+
+        assert_eq!(RangeSpec::From(0).resolve(0), None);
+    }
+
+    #[test]
+    fn resolve_multiple_ranges() {
+        assert_eq!(
+            resolve_ranges(&[RangeSpec::From(0)], 10),
+            Some(ByteRange { start: 0, end: 9 })
+        );
+        assert_eq!(
+            resolve_ranges(&[RangeSpec::FromTo(0, 1), RangeSpec::From(5)], 10),
+            None
+        );
+        assert_eq!(resolve_ranges(&[], 10), None);
+    }
 }
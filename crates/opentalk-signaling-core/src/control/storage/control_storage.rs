@@ -2,11 +2,18 @@
 //
 // SPDX-License-Identifier: EUPL-1.2
 
-use std::collections::{BTreeMap, BTreeSet};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    net::IpAddr,
+};
 
 use async_trait::async_trait;
 use opentalk_db_storage::{events::Event, tariffs::Tariff};
-use opentalk_types_common::{rooms::RoomId, time::Timestamp, users::UserInfo};
+use opentalk_types_common::{
+    rooms::RoomId,
+    time::Timestamp,
+    users::{UserId, UserInfo},
+};
 use opentalk_types_signaling::{ParticipantId, Role};
 use redis::ToRedisArgs;
 use redis_args::ToRedisArgs;
@@ -14,7 +21,7 @@ use serde::{Serialize, de::DeserializeOwned};
 use snafu::ResultExt as _;
 
 use super::LEFT_AT;
-use crate::{SerdeJsonSnafu, SignalingModuleError, SignalingRoomId};
+use crate::{RunnerId, SerdeJsonSnafu, SignalingModuleError, SignalingRoomId};
 
 #[derive(
     Debug,
@@ -221,6 +228,7 @@ pub trait ControlStorage:
     + ControlStorageEvent
     + ControlStorageParticipantSet
     + ControlStorageSkipWaitingRoom
+    + ControlStorageConnections
 {
     async fn participants_all_left(
         &mut self,
@@ -342,6 +350,47 @@ pub trait ControlStorageSkipWaitingRoom {
     ) -> Result<bool, SignalingModuleError>;
 }
 
+/// The owner of signaling connections, used to limit the number of concurrent connections
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, derive_more::Display)]
+pub enum ConnectionOwner {
+    /// Connections of a registered user, across all rooms
+    #[display("user={_0}")]
+    User(UserId),
+
+    /// Connections without a user, identified by the address of the client
+    #[display("address={_0}")]
+    Address(IpAddr),
+}
+
+#[async_trait(?Send)]
+pub trait ControlStorageConnections {
+    /// Register the connection of `runner` for `owner`, unless the owner already holds `limit`
+    /// connections.
+    ///
+    /// Connections which have not been refreshed for longer than the connection expiry are not
+    /// counted. Returns `true` if the connection was registered.
+    async fn try_acquire_connection(
+        &mut self,
+        owner: ConnectionOwner,
+        runner: RunnerId,
+        limit: u32,
+    ) -> Result<bool, SignalingModuleError>;
+
+    /// Extend the expiry of a connection registered by [`Self::try_acquire_connection`].
+    async fn refresh_connection(
+        &mut self,
+        owner: ConnectionOwner,
+        runner: RunnerId,
+    ) -> Result<(), SignalingModuleError>;
+
+    /// Remove the connection of `runner` from the connections of `owner`.
+    async fn release_connection(
+        &mut self,
+        owner: ConnectionOwner,
+        runner: RunnerId,
+    ) -> Result<(), SignalingModuleError>;
+}
+
 #[async_trait(?Send)]
 pub trait ControlStorageParticipantSet {
     async fn participant_set_exists(
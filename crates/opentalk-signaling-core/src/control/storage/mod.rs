@@ -7,7 +7,8 @@ mod redis;
 mod volatile;
 
 pub use control_storage::{
-    AttributeActions, ControlStorage, ControlStorageEvent, ControlStorageParticipantAttributes,
+    AttributeActions, ConnectionOwner, ControlStorage, ControlStorageConnections,
+    ControlStorageEvent, ControlStorageParticipantAttributes,
     ControlStorageParticipantAttributesRaw, ControlStorageParticipantSet,
     ControlStorageSkipWaitingRoom, GlobalAttributeId, GlobalRoomAttributeId, LocalAttributeId,
     LocalRoomAttributeId, RoomAttributeId,
@@ -17,6 +18,10 @@ pub use control_storage::{
 const SKIP_WAITING_ROOM_KEY_EXPIRY: u32 = 120;
 pub const SKIP_WAITING_ROOM_KEY_REFRESH_INTERVAL: u64 = 60;
 
+// The time in seconds after which a connection that has not been refreshed is no longer counted
+const CONNECTION_EXPIRY: u32 = 180;
+pub const CONNECTION_REFRESH_INTERVAL: u64 = 60;
+
 pub const AVATAR_URL: LocalAttributeId = LocalAttributeId("avatar_url");
 pub const DISPLAY_NAME: GlobalAttributeId = GlobalAttributeId("display_name");
 pub const HAND_IS_UP: LocalAttributeId = LocalAttributeId("hand_is_up");
@@ -57,7 +62,7 @@ mod test_common {
     use serde::{Deserialize, Serialize};
 
     use super::*;
-    use crate::SignalingRoomId;
+    use crate::{RunnerId, SignalingRoomId};
 
     pub const ROOM: SignalingRoomId = SignalingRoomId::nil();
     pub const BOB: ParticipantId = ParticipantId::from_u128(0xdeadbeef);
@@ -600,4 +605,32 @@ mod test_common {
 
         assert!(s.get_skip_waiting_room(ALICE).await.unwrap());
     }
+
+    pub(super) async fn connections(s: &mut impl ControlStorage) {
+        let owner = ConnectionOwner::User(UserId::from_u128(0xc0ffee));
+        let first = RunnerId::from(uuid::Uuid::from_u128(1));
+        let second = RunnerId::from(uuid::Uuid::from_u128(2));
+        let third = RunnerId::from(uuid::Uuid::from_u128(3));
+
+        assert!(s.try_acquire_connection(owner, first, 2).await.unwrap());
+        assert!(s.try_acquire_connection(owner, second, 2).await.unwrap());
+        assert!(!s.try_acquire_connection(owner, third, 2).await.unwrap());
+
+        // Acquiring an already registered connection again must not count twice
+        assert!(s.try_acquire_connection(owner, second, 2).await.unwrap());
+
+        // Other owners are not affected
+        let address = ConnectionOwner::Address([127, 0, 0, 1].into());
+        assert!(s.try_acquire_connection(address, third, 2).await.unwrap());
+
+        s.refresh_connection(owner, first).await.unwrap();
+        s.release_connection(owner, first).await.unwrap();
+
+        assert!(s.try_acquire_connection(owner, third, 2).await.unwrap());
+        assert!(!s.try_acquire_connection(owner, first, 2).await.unwrap());
+
+        s.release_connection(owner, second).await.unwrap();
+        s.release_connection(owner, third).await.unwrap();
+        s.release_connection(address, third).await.unwrap();
+    }
 }
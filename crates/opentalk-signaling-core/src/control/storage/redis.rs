@@ -18,15 +18,15 @@ use serde::{Serialize, de::DeserializeOwned};
 use snafu::ResultExt;
 
 use super::{
-    AttributeActions, ControlStorage, ControlStorageParticipantAttributesRaw, LEFT_AT, ROLE,
-    SKIP_WAITING_ROOM_KEY_EXPIRY,
+    AttributeActions, CONNECTION_EXPIRY, ConnectionOwner, ControlStorage,
+    ControlStorageParticipantAttributesRaw, LEFT_AT, ROLE, SKIP_WAITING_ROOM_KEY_EXPIRY,
     control_storage::{
-        AttributeAction, ControlStorageEvent, ControlStorageParticipantSet,
-        ControlStorageSkipWaitingRoom, GlobalRoomAttributeId, LocalRoomAttributeId,
-        RoomAttributeId,
+        AttributeAction, ControlStorageConnections, ControlStorageEvent,
+        ControlStorageParticipantSet, ControlStorageSkipWaitingRoom, GlobalRoomAttributeId,
+        LocalRoomAttributeId, RoomAttributeId,
     },
 };
-use crate::{RedisConnection, RedisSnafu, SignalingModuleError, SignalingRoomId};
+use crate::{RedisConnection, RedisSnafu, RunnerId, SignalingModuleError, SignalingRoomId};
 
 #[async_trait(?Send)]
 impl ControlStorage for RedisConnection {
@@ -339,6 +339,81 @@ impl ControlStorageSkipWaitingRoom for RedisConnection {
     }
 }
 
+#[async_trait(?Send)]
+impl ControlStorageConnections for RedisConnection {
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn try_acquire_connection(
+        &mut self,
+        owner: ConnectionOwner,
+        runner: RunnerId,
+        limit: u32,
+    ) -> Result<bool, SignalingModuleError> {
+        // Connections are scored with the time of their last refresh, stale ones are dropped
+        // before counting
+        const ACQUIRE_SCRIPT: &str = r#"
+        redis.call("ZREMRANGEBYSCORE", KEYS[1], "-inf", ARGV[3])
+        if not redis.call("ZSCORE", KEYS[1], ARGV[1])
+            and redis.call("ZCARD", KEYS[1]) >= tonumber(ARGV[4]) then
+          return 0
+        end
+        redis.call("ZADD", KEYS[1], ARGV[2], ARGV[1])
+        redis.call("EXPIRE", KEYS[1], ARGV[5])
+        return 1
+        "#;
+
+        let now = chrono::Utc::now().timestamp();
+
+        redis::Script::new(ACQUIRE_SCRIPT)
+            .key(Connections { owner })
+            .arg(runner)
+            .arg(now)
+            .arg(now - i64::from(CONNECTION_EXPIRY))
+            .arg(limit)
+            .arg(CONNECTION_EXPIRY)
+            .invoke_async(self)
+            .await
+            .with_context(|_| RedisSnafu {
+                message: format!("Failed to acquire connection for {owner}"),
+            })
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn refresh_connection(
+        &mut self,
+        owner: ConnectionOwner,
+        runner: RunnerId,
+    ) -> Result<(), SignalingModuleError> {
+        redis::pipe()
+            .atomic()
+            .cmd("ZADD")
+            .arg(Connections { owner })
+            .arg("XX")
+            .arg(chrono::Utc::now().timestamp())
+            .arg(runner)
+            .ignore()
+            .expire(Connections { owner }, CONNECTION_EXPIRY.into())
+            .ignore()
+            .exec_async(self)
+            .await
+            .with_context(|_| RedisSnafu {
+                message: format!("Failed to refresh connection for {owner}"),
+            })
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn release_connection(
+        &mut self,
+        owner: ConnectionOwner,
+        runner: RunnerId,
+    ) -> Result<(), SignalingModuleError> {
+        self.zrem::<_, _, ()>(Connections { owner }, runner)
+            .await
+            .with_context(|_| RedisSnafu {
+                message: format!("Failed to release connection for {owner}"),
+            })
+    }
+}
+
 #[async_trait(?Send)]
 impl ControlStorageParticipantSet for RedisConnection {
     #[tracing::instrument(level = "debug", skip(self))]
@@ -470,6 +545,13 @@ pub struct SkipWaitingRoom {
     participant: ParticipantId,
 }
 
+/// The signaling connections of an owner, scored by the time of their last refresh
+#[derive(Debug, ToRedisArgs)]
+#[to_redis_args(fmt = "opentalk-signaling:connections:{owner}")]
+struct Connections {
+    owner: ConnectionOwner,
+}
+
 struct WrappedAttributeValueJson(Option<serde_json::Value>);
 
 impl ToRedisArgs for WrappedAttributeValueJson {
@@ -838,4 +920,10 @@ mod tests {
     async fn skip_waiting_room() {
         test_common::skip_waiting_room(&mut storage().await).await;
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn connections() {
+        test_common::connections(&mut storage().await).await;
+    }
 }
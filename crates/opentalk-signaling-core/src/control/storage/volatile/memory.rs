@@ -14,9 +14,10 @@ use opentalk_types_signaling::ParticipantId;
 use snafu::OptionExt as _;
 
 use crate::{
-    ExpiringDataHashMap, NotFoundSnafu, SignalingModuleError, SignalingRoomId,
+    ExpiringDataHashMap, NotFoundSnafu, RunnerId, SignalingModuleError, SignalingRoomId,
     control::storage::{
-        AttributeActions, LocalAttributeId, SKIP_WAITING_ROOM_KEY_EXPIRY,
+        AttributeActions, CONNECTION_EXPIRY, ConnectionOwner, LocalAttributeId,
+        SKIP_WAITING_ROOM_KEY_EXPIRY,
         control_storage::{
             AttributeAction, GlobalAttributeId, GlobalRoomAttributeId, LocalRoomAttributeId,
             RoomAttributeId,
@@ -39,6 +40,7 @@ pub(super) struct MemoryControlState {
     rooms_close_at: HashMap<SignalingRoomId, Timestamp>,
     room_alive: HashSet<RoomId>,
     participants_skip_waiting_room: ExpiringDataHashMap<ParticipantId, bool>,
+    connections: HashMap<ConnectionOwner, ExpiringDataHashMap<RunnerId, ()>>,
 }
 
 impl MemoryControlState {
@@ -338,6 +340,45 @@ impl MemoryControlState {
             .copied()
             .unwrap_or_default()
     }
+
+    pub(super) fn try_acquire_connection(
+        &mut self,
+        owner: ConnectionOwner,
+        runner: RunnerId,
+        limit: u32,
+    ) -> bool {
+        let expires_after = Duration::from_secs(CONNECTION_EXPIRY.into());
+        let connections = self.connections.entry(owner).or_default();
+        connections.cleanup_expired();
+
+        if connections.update_expiry(&runner, expires_after) {
+            return true;
+        }
+
+        if connections.len() >= limit as usize {
+            return false;
+        }
+
+        connections.insert_with_expiry(runner, (), expires_after);
+        true
+    }
+
+    pub(super) fn refresh_connection(&mut self, owner: ConnectionOwner, runner: RunnerId) {
+        if let Some(connections) = self.connections.get_mut(&owner) {
+            connections.update_expiry(&runner, Duration::from_secs(CONNECTION_EXPIRY.into()));
+        }
+    }
+
+    pub(super) fn release_connection(&mut self, owner: ConnectionOwner, runner: RunnerId) {
+        if let Some(connections) = self.connections.get_mut(&owner) {
+            connections.remove(&runner);
+            connections.cleanup_expired();
+
+            if connections.is_empty() {
+                self.connections.remove(&owner);
+            }
+        }
+    }
 }
 
 trait HashMapActions<R, A> {
@@ -15,9 +15,10 @@ use parking_lot::RwLock;
 
 use super::memory::MemoryControlState;
 use crate::{
-    SignalingModuleError, SignalingRoomId, VolatileStaticMemoryStorage,
+    RunnerId, SignalingModuleError, SignalingRoomId, VolatileStaticMemoryStorage,
     control::storage::{
-        AttributeActions, ControlStorage, ControlStorageEvent, ControlStorageParticipantAttributes,
+        AttributeActions, ConnectionOwner, ControlStorage, ControlStorageConnections,
+        ControlStorageEvent, ControlStorageParticipantAttributes,
         ControlStorageParticipantAttributesRaw, LEFT_AT, ROLE,
         control_storage::{
             ControlStorageParticipantSet, ControlStorageSkipWaitingRoom, RoomAttributeId,
@@ -225,6 +226,39 @@ impl ControlStorageSkipWaitingRoom for VolatileStaticMemoryStorage {
     }
 }
 
+#[async_trait(?Send)]
+impl ControlStorageConnections for VolatileStaticMemoryStorage {
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn try_acquire_connection(
+        &mut self,
+        owner: ConnectionOwner,
+        runner: RunnerId,
+        limit: u32,
+    ) -> Result<bool, SignalingModuleError> {
+        Ok(state().write().try_acquire_connection(owner, runner, limit))
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn refresh_connection(
+        &mut self,
+        owner: ConnectionOwner,
+        runner: RunnerId,
+    ) -> Result<(), SignalingModuleError> {
+        state().write().refresh_connection(owner, runner);
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn release_connection(
+        &mut self,
+        owner: ConnectionOwner,
+        runner: RunnerId,
+    ) -> Result<(), SignalingModuleError> {
+        state().write().release_connection(owner, runner);
+        Ok(())
+    }
+}
+
 #[async_trait(?Send)]
 impl ControlStorageParticipantSet for VolatileStaticMemoryStorage {
     #[tracing::instrument(level = "debug", skip(self))]
@@ -446,4 +480,10 @@ mod tests {
     async fn skip_waiting_room() {
         test_common::skip_waiting_room(&mut storage().await).await;
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn connections() {
+        test_common::connections(&mut storage().await).await;
+    }
 }
@@ -8,6 +8,7 @@ use core::{hash::BuildHasherDefault, time::Duration};
 use std::{borrow::Cow, collections::HashMap, sync::Arc};
 
 use bytestring::ByteString;
+use futures::{FutureExt as _, future::BoxFuture, stream::FuturesUnordered};
 use lapin::{
     BasicProperties, ChannelState, Consumer, ExchangeKind,
     message::Delivery,
@@ -30,6 +31,8 @@ use tokio::{
 use tokio_stream::StreamExt;
 use uuid::Uuid;
 
+use crate::SignalingMetrics;
+
 #[derive(Debug, Snafu)]
 pub enum Error {
     #[snafu(display("failed open channel"), context(false))]
@@ -44,6 +47,97 @@ pub enum Error {
 
 const EXCHANGE: &str = "opentalk_controller";
 
+/// Number of times publishing a [`ExchangeDelivery::Critical`] message to RabbitMQ is retried
+const CRITICAL_PUBLISH_RETRIES: u32 = 3;
+
+/// Initial wait duration between retries of a critical publish, doubled after each attempt
+const CRITICAL_PUBLISH_RETRY_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Delivery guarantee requested for a message published to the exchange
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ExchangeDelivery {
+    /// The message is dropped when it cannot be published on the first attempt
+    #[default]
+    BestEffort,
+
+    /// Publishing the message is retried a bounded number of times before it is dropped
+    ///
+    /// Use this for messages carrying state changes that other participants must not miss,
+    /// e.g. stopping a vote or controlling a recording.
+    Critical,
+}
+
+impl ExchangeDelivery {
+    fn is_critical(self) -> bool {
+        matches!(self, Self::Critical)
+    }
+}
+
+/// A message that is published to RabbitMQ by the [`ExchangeTask`]
+struct PendingPublish {
+    routing_key: String,
+    payload: String,
+    delivery: ExchangeDelivery,
+
+    /// Number of failed attempts to publish the message
+    failed_attempts: u32,
+
+    confirmation: Option<oneshot::Sender<Result<(), PublishError>>>,
+}
+
+impl PendingPublish {
+    /// Record a failed attempt to publish the message
+    ///
+    /// Returns the duration to wait before the next attempt, or `None` when the message must be
+    /// dropped.
+    fn record_failure(&mut self) -> Option<Duration> {
+        self.failed_attempts += 1;
+
+        if !self.delivery.is_critical() || self.failed_attempts > CRITICAL_PUBLISH_RETRIES {
+            return None;
+        }
+
+        Some(CRITICAL_PUBLISH_RETRY_INTERVAL * 2u32.pow(self.failed_attempts - 1))
+    }
+
+    /// Report the outcome to the publisher, if it waits for a [`PublishConfirmation`]
+    fn confirm(self, result: Result<(), PublishError>) {
+        if let Some(confirmation) = self.confirmation {
+            let _ = confirmation.send(result);
+        }
+    }
+}
+
+/// Critical messages waiting for their next attempt to be published to RabbitMQ
+///
+/// The wait happens outside of the [`ExchangeTask`] loop, so retries don't block other commands
+/// or incoming messages.
+#[derive(Default)]
+struct RetryQueue {
+    pending: FuturesUnordered<BoxFuture<'static, PendingPublish>>,
+}
+
+impl RetryQueue {
+    fn schedule(&mut self, pending: PendingPublish, wait_duration: Duration) {
+        self.pending.push(
+            async move {
+                sleep(wait_duration).await;
+                pending
+            }
+            .boxed(),
+        );
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Returns the next message whose wait duration has elapsed
+    async fn next(&mut self) -> Option<PendingPublish> {
+        self.pending.next().await
+    }
+}
+
 new_key_type! {
     struct SubscriberKey;
 }
@@ -70,6 +164,11 @@ pub struct ExchangeTask {
     ///
     /// This map is used to match the routing key of incoming messages to subscribers.
     routing_keys: HashMap<ByteString, Vec<SubscriberKey>, BuildHasherDefault<FxHasher>>,
+
+    /// Critical messages which failed to be published to RabbitMQ and will be retried
+    retry_queue: RetryQueue,
+
+    metrics: Option<Arc<SignalingMetrics>>,
 }
 
 struct RabbitMQParts {
@@ -87,6 +186,8 @@ enum Command {
     Publish {
         routing_key: String,
         data: String,
+        delivery: ExchangeDelivery,
+        confirmation: Option<oneshot::Sender<Result<(), PublishError>>>,
     },
 }
 
@@ -97,7 +198,7 @@ struct SubscriberEntry {
 
 impl ExchangeTask {
     /// Spawn the exchange task and return a [`ExchangeHandle`] to it
-    pub async fn spawn() -> Result<ExchangeHandle, Error> {
+    pub async fn spawn(metrics: Option<Arc<SignalingMetrics>>) -> Result<ExchangeHandle, Error> {
         let (command_sender, command_receiver) = mpsc::unbounded_channel();
 
         tokio::spawn(
@@ -107,15 +208,23 @@ impl ExchangeTask {
                 command_receiver,
                 subscriber: SlotMap::with_key(),
                 routing_keys: HashMap::default(),
+                retry_queue: RetryQueue::default(),
+                metrics: metrics.clone(),
             }
             .run(),
         );
 
-        Ok(ExchangeHandle { command_sender })
+        Ok(ExchangeHandle {
+            command_sender,
+            metrics,
+        })
     }
 
     /// Spawn the exchange task with rabbitmq support and return a [`ExchangeHandle`] to it
-    pub async fn spawn_with_rabbitmq(pool: Arc<RabbitMqPool>) -> Result<ExchangeHandle, Error> {
+    pub async fn spawn_with_rabbitmq(
+        pool: Arc<RabbitMqPool>,
+        metrics: Option<Arc<SignalingMetrics>>,
+    ) -> Result<ExchangeHandle, Error> {
         let channel = pool.create_channel().await?;
         let consumer = make_consumer(&channel).await?;
 
@@ -132,11 +241,16 @@ impl ExchangeTask {
                 command_receiver,
                 subscriber: SlotMap::with_key(),
                 routing_keys: HashMap::default(),
+                retry_queue: RetryQueue::default(),
+                metrics: metrics.clone(),
             }
             .run(),
         );
 
-        Ok(ExchangeHandle { command_sender })
+        Ok(ExchangeHandle {
+            command_sender,
+            metrics,
+        })
     }
 
     /// Loop forever and handle event
@@ -153,6 +267,9 @@ impl ExchangeTask {
                     delivery = rmq.consumer.next() => {
                         self.handle_delivery(delivery).await;
                     }
+                    Some(pending) = self.retry_queue.next(), if !self.retry_queue.is_empty() => {
+                        self.publish_to_rabbitmq(pending).await;
+                    }
                 }
             } else if let Some(command) = self.command_receiver.recv().await {
                 self.handle_command(command).await;
@@ -194,12 +311,24 @@ impl ExchangeTask {
                     }
                 }
             }
-            Command::Publish { routing_key, data } => {
+            Command::Publish {
+                routing_key,
+                data,
+                delivery,
+                confirmation,
+            } => {
+                if let Some(metrics) = &self.metrics {
+                    metrics.decrement_exchange_queue_depth();
+                }
+
                 self.handle_msg(&routing_key, &data).await;
 
-                let Some(mut rmq) = self.rmq.as_mut() else {
+                if self.rmq.is_none() {
+                    if let Some(confirmation) = confirmation {
+                        let _ = confirmation.send(Ok(()));
+                    }
                     return;
-                };
+                }
 
                 let payload = serde_json::to_string(&RabbitMqMessage {
                     sender: self.id,
@@ -208,24 +337,77 @@ impl ExchangeTask {
                 })
                 .unwrap();
 
-                // Reconnect while this fails with an error
-                while rmq
-                    .channel
-                    .basic_publish(
-                        EXCHANGE,
-                        "",
-                        BasicPublishOptions::default(),
-                        payload.as_bytes(),
-                        BasicProperties::default(),
-                    )
-                    .await
-                    .is_err()
-                    && rmq.channel.status().state() == ChannelState::Error
-                {
-                    self.reconnect_rabbitmq().await;
-                    rmq = self.rmq.as_mut().unwrap();
-                }
+                self.publish_to_rabbitmq(PendingPublish {
+                    routing_key,
+                    payload,
+                    delivery,
+                    failed_attempts: 0,
+                    confirmation,
+                })
+                .await;
+            }
+        }
+    }
+
+    /// Publish a message to the other controllers
+    ///
+    /// Critical messages that fail to be published are put into the [`RetryQueue`].
+    async fn publish_to_rabbitmq(&mut self, mut pending: PendingPublish) {
+        loop {
+            let Some(rmq) = self.rmq.as_mut() else {
+                return;
+            };
+
+            let result = rmq
+                .channel
+                .basic_publish(
+                    EXCHANGE,
+                    "",
+                    BasicPublishOptions::default(),
+                    pending.payload.as_bytes(),
+                    BasicProperties::default(),
+                )
+                .await;
+
+            let Err(e) = result else {
+                pending.confirm(Ok(()));
+                return;
+            };
+
+            // Reconnect while this fails because the channel is broken
+            if rmq.channel.status().state() == ChannelState::Error {
+                self.reconnect_rabbitmq().await;
+                continue;
+            }
+
+            if let Some(wait_duration) = pending.record_failure() {
+                log::warn!(
+                    "Failed to publish critical message to rabbitmq (retry {}/{CRITICAL_PUBLISH_RETRIES} in {wait_duration:?}), {}",
+                    pending.failed_attempts,
+                    Report::from_error(e)
+                );
+                self.retry_queue.schedule(pending, wait_duration);
+                return;
+            }
+
+            if let Some(metrics) = &self.metrics {
+                metrics.increment_exchange_publish_failures(pending.delivery.is_critical());
+            }
+
+            let error = PublishError::RabbitMq {
+                routing_key: pending.routing_key.clone(),
+                attempts: pending.failed_attempts,
+                source: e,
+            };
+
+            if pending.delivery.is_critical() {
+                log::error!("Dropping critical message, {}", Report::from_error(&error));
+            } else {
+                log::warn!("Dropping message, {}", Report::from_error(&error));
             }
+
+            pending.confirm(Err(error));
+            return;
         }
     }
 
@@ -363,6 +545,7 @@ async fn make_consumer(channel: &lapin::Channel) -> Result<Consumer, Error> {
 #[derive(Clone)]
 pub struct ExchangeHandle {
     command_sender: mpsc::UnboundedSender<Command>,
+    metrics: Option<Arc<SignalingMetrics>>,
 }
 
 #[derive(Debug, Snafu)]
@@ -370,8 +553,48 @@ pub struct ExchangeHandle {
 pub struct CreateSubscriberError;
 
 #[derive(Debug, Snafu)]
-#[snafu(display("Cannot publish message. Exchange task is not available, receiver dropped"))]
-pub struct PublishError;
+pub enum PublishError {
+    #[snafu(display(
+        "Cannot publish message with routing key {routing_key:?}. Exchange task is not available, receiver dropped"
+    ))]
+    ExchangeUnavailable {
+        routing_key: String,
+        delivery: ExchangeDelivery,
+    },
+
+    #[snafu(display(
+        "Failed to publish message with routing key {routing_key:?} to rabbitmq after {attempts} attempts"
+    ))]
+    RabbitMq {
+        routing_key: String,
+        attempts: u32,
+        source: lapin::Error,
+    },
+}
+
+/// Resolves to the outcome of a message published with [`ExchangeHandle::publish_with_delivery`]
+#[derive(Debug)]
+pub struct PublishConfirmation {
+    routing_key: String,
+    delivery: ExchangeDelivery,
+    receiver: oneshot::Receiver<Result<(), PublishError>>,
+}
+
+impl PublishConfirmation {
+    /// Wait until the message was published to the other controllers, or dropped
+    ///
+    /// Critical messages are only reported as dropped when all retries failed.
+    pub async fn confirmed(self) -> Result<(), PublishError> {
+        match self.receiver.await {
+            Ok(result) => result,
+            Err(_) => ExchangeUnavailableSnafu {
+                routing_key: self.routing_key,
+                delivery: self.delivery,
+            }
+            .fail(),
+        }
+    }
+}
 
 impl ExchangeHandle {
     /// Create a new [`SubscriberHandle`] which will receive all messages that match the given `routing_keys`
@@ -399,7 +622,7 @@ impl ExchangeHandle {
         })
     }
 
-    /// Publish a message to the exchange
+    /// Publish a message to the exchange on a best-effort basis
     ///
     /// Returns an error when the exchange is not reachable. The error is permanent.
     pub fn publish(
@@ -407,18 +630,93 @@ impl ExchangeHandle {
         routing_key: impl Into<String>,
         data: impl Into<String>,
     ) -> Result<(), PublishError> {
+        self.send_publish(
+            routing_key.into(),
+            data.into(),
+            ExchangeDelivery::BestEffort,
+            None,
+        )
+    }
+
+    /// Publish a message to the exchange with the given delivery guarantee
+    ///
+    /// Returns an error when the exchange is not reachable. The error is permanent. Otherwise the
+    /// returned [`PublishConfirmation`] can be used to wait for the message being published.
+    pub fn publish_with_delivery(
+        &self,
+        routing_key: impl Into<String>,
+        data: impl Into<String>,
+        delivery: ExchangeDelivery,
+    ) -> Result<PublishConfirmation, PublishError> {
+        let routing_key = routing_key.into();
+        let (confirmation, receiver) = oneshot::channel();
+
+        self.send_publish(
+            routing_key.clone(),
+            data.into(),
+            delivery,
+            Some(confirmation),
+        )?;
+
+        Ok(PublishConfirmation {
+            routing_key,
+            delivery,
+            receiver,
+        })
+    }
+
+    fn send_publish(
+        &self,
+        routing_key: String,
+        data: String,
+        delivery: ExchangeDelivery,
+        confirmation: Option<oneshot::Sender<Result<(), PublishError>>>,
+    ) -> Result<(), PublishError> {
+        // Count the message before sending it, the exchange task might process it right away
+        if let Some(metrics) = &self.metrics {
+            metrics.increment_exchange_queue_depth();
+        }
+
         self.command_sender
             .send(Command::Publish {
-                routing_key: routing_key.into(),
-                data: data.into(),
+                routing_key,
+                data,
+                delivery,
+                confirmation,
+            })
+            .map_err(|mpsc::error::SendError(command)| {
+                if let Some(metrics) = &self.metrics {
+                    metrics.decrement_exchange_queue_depth();
+                }
+
+                let Command::Publish {
+                    routing_key,
+                    delivery,
+                    ..
+                } = command
+                else {
+                    unreachable!("a publish command was sent");
+                };
+
+                PublishError::ExchangeUnavailable {
+                    routing_key,
+                    delivery,
+                }
             })
-            .map_err(|_| PublishError)
+    }
+
+    /// Returns `false` when the exchange task has stopped and no longer accepts commands
+    pub fn is_available(&self) -> bool {
+        !self.command_sender.is_closed()
     }
 
     #[cfg(feature = "mocking")]
     pub fn dummy() -> Self {
         let (command_sender, _) = mpsc::unbounded_channel();
-        Self { command_sender }
+        Self {
+            command_sender,
+            metrics: None,
+        }
     }
 }
 
@@ -454,3 +752,107 @@ struct RabbitMqMessage<'w> {
     routing_key: &'w str,
     data: Cow<'w, str>,
 }
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use pretty_assertions::assert_eq;
+    use tokio::sync::mpsc;
+
+    use super::{
+        CRITICAL_PUBLISH_RETRY_INTERVAL, ExchangeDelivery, ExchangeHandle, ExchangeTask,
+        PendingPublish, PublishError, RetryQueue,
+    };
+
+    fn pending_publish(routing_key: &str, delivery: ExchangeDelivery) -> PendingPublish {
+        PendingPublish {
+            routing_key: routing_key.into(),
+            payload: "payload".into(),
+            delivery,
+            failed_attempts: 0,
+            confirmation: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn publish_is_delivered_and_confirmed() {
+        let handle = ExchangeTask::spawn(None).await.unwrap();
+        let mut subscriber = handle.create_subscriber(vec!["room".into()]).await.unwrap();
+
+        let confirmation = handle
+            .publish_with_delivery("room", "message", ExchangeDelivery::Critical)
+            .unwrap();
+
+        confirmation.confirmed().await.unwrap();
+        assert_eq!(subscriber.receive().await.unwrap(), "message");
+    }
+
+    #[test]
+    fn publish_to_stopped_exchange_fails() {
+        let (command_sender, command_receiver) = mpsc::unbounded_channel();
+        drop(command_receiver);
+        let handle = ExchangeHandle {
+            command_sender,
+            metrics: None,
+        };
+
+        let error = handle
+            .publish_with_delivery("room", "message", ExchangeDelivery::Critical)
+            .unwrap_err();
+
+        assert!(matches!(
+            error,
+            PublishError::ExchangeUnavailable {
+                routing_key,
+                delivery: ExchangeDelivery::Critical,
+            } if routing_key == "room"
+        ));
+    }
+
+    #[test]
+    fn critical_publish_retries_are_bounded() {
+        let mut pending = pending_publish("room", ExchangeDelivery::Critical);
+
+        assert_eq!(
+            pending.record_failure(),
+            Some(CRITICAL_PUBLISH_RETRY_INTERVAL)
+        );
+        assert_eq!(
+            pending.record_failure(),
+            Some(CRITICAL_PUBLISH_RETRY_INTERVAL * 2)
+        );
+        assert_eq!(
+            pending.record_failure(),
+            Some(CRITICAL_PUBLISH_RETRY_INTERVAL * 4)
+        );
+        assert_eq!(pending.record_failure(), None);
+        assert_eq!(pending.failed_attempts, 4);
+    }
+
+    #[test]
+    fn best_effort_publish_is_not_retried() {
+        let mut pending = pending_publish("room", ExchangeDelivery::BestEffort);
+
+        assert_eq!(pending.record_failure(), None);
+    }
+
+    #[tokio::test]
+    async fn retry_queue_yields_messages_after_their_wait_duration() {
+        let mut queue = RetryQueue::default();
+        assert!(queue.is_empty());
+
+        queue.schedule(
+            pending_publish("late", ExchangeDelivery::Critical),
+            Duration::from_millis(50),
+        );
+        queue.schedule(
+            pending_publish("early", ExchangeDelivery::Critical),
+            Duration::from_millis(10),
+        );
+
+        assert_eq!(queue.next().await.unwrap().routing_key, "early");
+        assert_eq!(queue.next().await.unwrap().routing_key, "late");
+        assert!(queue.is_empty());
+    }
+}
@@ -84,6 +84,15 @@ where
     pub fn remove(&mut self, key: &K) -> Option<V> {
         self.data.remove(key).and_then(ExpiringData::take)
     }
+
+    /// The number of entries which are not expired
+    pub fn len(&self) -> usize {
+        self.data.values().filter(|v| !v.is_expired()).count()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
 
 #[cfg(test)]
@@ -150,6 +159,20 @@ mod tests {
         assert!(map.get(&2).is_some());
     }
 
+    #[test]
+    fn len() {
+        let mut map = ExpiringDataHashMap::new();
+        assert!(map.is_empty());
+
+        map.insert_with_expiry(1, "hello", Duration::from_millis(1000));
+        map.insert_with_expiry(2, "world", Duration::from_millis(2));
+
+        std::thread::sleep(Duration::from_millis(10));
+
+        assert_eq!(map.len(), 1);
+        assert!(!map.is_empty());
+    }
+
     #[test]
     fn cleanup_expired() {
         let mut map = ExpiringDataHashMap::new();
@@ -11,10 +11,13 @@ mod exchange_task;
 mod expiring_data;
 mod expiring_data_hash_map;
 mod init_context;
+mod malformed_message;
 mod metrics;
 mod module_context;
+mod module_state;
 mod object_storage;
 mod participant;
+mod pdf_generation_limiter;
 mod redis_wrapper;
 mod room_lock;
 mod runner_id;
@@ -31,14 +34,23 @@ pub mod control;
 pub use any_stream::{AnyStream, any_stream};
 pub use destroy_context::{CleanupScope, DestroyContext};
 pub use event::Event;
-pub use exchange_task::{Error as ExchangeError, ExchangeHandle, ExchangeTask, SubscriberHandle};
+pub use exchange_task::{
+    Error as ExchangeError, ExchangeDelivery, ExchangeHandle, ExchangeTask, PublishConfirmation,
+    PublishError as ExchangePublishError, SubscriberHandle,
+};
 pub use expiring_data::ExpiringData;
 pub use expiring_data_hash_map::ExpiringDataHashMap;
 pub use init_context::{ExchangeBinding, InitContext};
+pub use malformed_message::{
+    MalformedMessage, MalformedMessageError, MalformedMessageEvent, MalformedMessageReason,
+    parse_ws_message,
+};
 pub use metrics::SignalingMetrics;
 pub use module_context::{ExchangePublish, ModuleContext};
+pub use module_state::ModuleStateSummarizer;
 pub use object_storage::{ChunkFormat, ObjectStorage, ObjectStorageError};
 pub use participant::Participant;
+pub use pdf_generation_limiter::PdfGenerationLimiter;
 pub use redis_wrapper::{RedisConnection, RedisMetrics};
 pub use room_lock::{LockError, RoomGuard, RoomLocking, RoomLockingProvider};
 pub use runner_id::RunnerId;
@@ -0,0 +1,194 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+//! Parsing of incoming module messages with structured errors for malformed payloads.
+
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::Value;
+
+/// Event sent in the namespace of a module when a websocket message could not be parsed into
+/// the module's [`Incoming`](crate::SignalingModule::Incoming) type.
+///
+/// Serializes like the error events of the modules:
+///
+/// ```json
+/// {"message": "error", "error": "invalid_message", "field": "name", "reason": "missing_field"}
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "message", rename_all = "snake_case")]
+pub enum MalformedMessageEvent {
+    Error(MalformedMessage),
+}
+
+/// Describes why a websocket message could not be parsed.
+///
+/// Only the location and the kind of the problem are reported, the offending values are never
+/// included as they may contain sensitive data.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MalformedMessage {
+    pub error: MalformedMessageError,
+
+    /// Path of the offending field, e.g. `allowed_participants[2]`, absent if the problem is
+    /// located at the top level of the message.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+
+    pub reason: MalformedMessageReason,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MalformedMessageError {
+    InvalidMessage,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MalformedMessageReason {
+    /// A required field is missing
+    MissingField,
+    /// The message contains a field that is not known
+    UnknownField,
+    /// The value does not name a known command or variant
+    UnknownVariant,
+    /// The value has the wrong type, e.g. a string instead of a number
+    InvalidType,
+    /// The value has the right type but is not accepted, e.g. a string that is too long
+    InvalidValue,
+}
+
+impl From<MalformedMessage> for MalformedMessageEvent {
+    fn from(value: MalformedMessage) -> Self {
+        Self::Error(value)
+    }
+}
+
+/// Parse the payload of an incoming websocket message for a module.
+///
+/// On failure a [`MalformedMessage`] is returned which can be sent back to the client, while
+/// the connection stays open.
+pub fn parse_ws_message<T: DeserializeOwned>(payload: Value) -> Result<T, MalformedMessage> {
+    serde_path_to_error::deserialize(payload).map_err(|e| {
+        let path = e.path().to_string();
+        let message = e.inner().to_string();
+
+        let mut field = (path != ".").then_some(path);
+
+        let reason = if let Some(missing) = message
+            .strip_prefix("missing field `")
+            .and_then(|rest| rest.split('`').next())
+        {
+            // The name of a missing field is taken from the type definition and safe to report
+            field = Some(match field {
+                Some(parent) => format!("{parent}.{missing}"),
+                None => missing.to_owned(),
+            });
+            MalformedMessageReason::MissingField
+        } else if message.starts_with("unknown field") {
+            MalformedMessageReason::UnknownField
+        } else if message.starts_with("unknown variant") {
+            MalformedMessageReason::UnknownVariant
+        } else if message.starts_with("invalid type") {
+            MalformedMessageReason::InvalidType
+        } else {
+            MalformedMessageReason::InvalidValue
+        };
+
+        MalformedMessage {
+            error: MalformedMessageError::InvalidMessage,
+            field,
+            reason,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    use super::{
+        MalformedMessage, MalformedMessageError, MalformedMessageEvent, MalformedMessageReason,
+        parse_ws_message,
+    };
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(tag = "action", rename_all = "snake_case")]
+    enum Command {
+        Send(Send),
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct Send {
+        content: String,
+        #[serde(default)]
+        targets: Vec<u32>,
+    }
+
+    fn malformed(field: Option<&str>, reason: MalformedMessageReason) -> MalformedMessage {
+        MalformedMessage {
+            error: MalformedMessageError::InvalidMessage,
+            field: field.map(ToOwned::to_owned),
+            reason,
+        }
+    }
+
+    #[test]
+    fn valid_message() {
+        assert_eq!(
+            parse_ws_message::<Command>(json!({"action": "send", "content": "hello"})),
+            Ok(Command::Send(Send {
+                content: "hello".to_owned(),
+                targets: vec![],
+            }))
+        );
+    }
+
+    #[test]
+    fn malformed_messages() {
+        assert_eq!(
+            parse_ws_message::<Command>(json!({"action": "send"})),
+            Err(malformed(
+                Some("content"),
+                MalformedMessageReason::MissingField
+            ))
+        );
+        assert_eq!(
+            parse_ws_message::<Command>(json!({"action": "shout", "content": "hello"})),
+            Err(malformed(None, MalformedMessageReason::UnknownVariant))
+        );
+        assert_eq!(
+            parse_ws_message::<Send>(json!({"content": "hello", "secret": "password"})),
+            Err(malformed(None, MalformedMessageReason::UnknownField))
+        );
+        assert_eq!(
+            parse_ws_message::<Send>(json!({"content": "hello", "targets": [1, "password"]})),
+            Err(malformed(
+                Some("targets[1]"),
+                MalformedMessageReason::InvalidType
+            ))
+        );
+        assert_eq!(
+            parse_ws_message::<Command>(json!("password")),
+            Err(malformed(None, MalformedMessageReason::InvalidType))
+        );
+    }
+
+    #[test]
+    fn event_does_not_contain_values() {
+        let error = parse_ws_message::<Send>(json!({"content": 12345, "targets": []})).unwrap_err();
+
+        assert_eq!(
+            serde_json::to_value(MalformedMessageEvent::from(error)).unwrap(),
+            json!({
+                "message": "error",
+                "error": "invalid_message",
+                "field": "content",
+                "reason": "invalid_type",
+            })
+        );
+    }
+}
@@ -2,13 +2,16 @@
 //
 // SPDX-License-Identifier: EUPL-1.2
 
-use std::{collections::HashMap, time::Instant};
+use std::{
+    collections::HashMap,
+    time::{Instant, SystemTime},
+};
 
-use opentalk_types_common::rooms::RoomId;
+use opentalk_types_common::{modules::ModuleId, rooms::RoomId};
 use opentalk_types_signaling::ParticipantId;
 use opentelemetry::{
     Key, KeyValue,
-    metrics::{Counter, Histogram, Meter, UpDownCounter},
+    metrics::{Counter, Gauge, Histogram, Meter, UpDownCounter},
 };
 use opentelemetry_sdk::metrics::{
     Aggregation, Instrument, MeterProviderBuilder, MetricError, Stream, new_view,
@@ -21,6 +24,10 @@ const STARTUP_SUCCESSFUL: Key = Key::from_static_str("successful");
 const DESTROY_SUCCESSFUL: Key = Key::from_static_str("successful");
 const PARTICIPATION_KIND: Key = Key::from_static_str("participation_kind");
 const MEDIA_SESSION_TYPE: Key = Key::from_static_str("media_session_type");
+const CHAT_SCOPE: Key = Key::from_static_str("scope");
+const MODULE: Key = Key::from_static_str("module");
+const CRITICAL: Key = Key::from_static_str("critical");
+const REPORT_GENERATION_SUCCESSFUL: Key = Key::from_static_str("successful");
 const RUNNER_STARTUP_TIME: &str = "signaling.runner_startup_time_seconds";
 const RUNNER_DESTROY_TIME: &str = "signaling.runner_destroy_time_seconds";
 const ROOM_LIFE_TIME: &str = "signaling.room_life_time";
@@ -35,6 +42,13 @@ const PARTICIPANT_MEETING_TIME: &str = "signaling.participant_meeting_time";
 const PARTICIPANTS_PER_ROOM: &str = "signaling.participants_per_room";
 const PARTICIPANTS_PER_ROOM_BUCKETS: [i64; 7] = [2, 10, 25, 50, 100, 200, 300];
 const BUCKET_LABEL: &str = "bucket";
+const CHAT_MESSAGE_SIZE: &str = "signaling.chat_message_size_bytes";
+const CHAT_MESSAGES: &str = "signaling.chat_messages_count";
+const ACTIVE_MODULE_SESSIONS: &str = "signaling.active_module_sessions_count";
+const EXCHANGE_QUEUE_DEPTH: &str = "signaling.exchange_queue_depth";
+const EXCHANGE_PUBLISH_FAILURES: &str = "signaling.exchange_publish_failures_count";
+const REPORT_GENERATIONS: &str = "signaling.report_generations_count";
+const REPORT_GENERATION_LAST_SUCCESS: &str = "signaling.report_generation_last_success_timestamp";
 
 pub struct SignalingMetrics {
     pub runner_startup_time: Histogram<f64>,
@@ -52,6 +66,17 @@ pub struct SignalingMetrics {
     pub participant_meeting_time: Histogram<u64>,
     pub participants_per_room: UpDownCounter<i64>,
 
+    pub chat_message_size: Histogram<u64>,
+    pub chat_messages_count: Counter<u64>,
+
+    pub active_module_sessions_count: UpDownCounter<i64>,
+
+    pub exchange_queue_depth: UpDownCounter<i64>,
+    pub exchange_publish_failures_count: Counter<u64>,
+
+    pub report_generations_count: Counter<u64>,
+    pub report_generation_last_success: Gauge<u64>,
+
     rooms: Mutex<HashMap<RoomId, RoomMetrics>>,
     participants: Mutex<HashMap<ParticipantId, Instant>>,
 }
@@ -116,6 +141,13 @@ impl SignalingMetrics {
                     ],
                     record_min_max: false,
                 }),
+            )?)
+            .with_view(new_view(
+                Instrument::new().name(CHAT_MESSAGE_SIZE),
+                Stream::new().aggregation(Aggregation::ExplicitBucketHistogram {
+                    boundaries: vec![16.0, 64.0, 256.0, 1024.0, 2048.0, 4096.0],
+                    record_min_max: false,
+                }),
             )?))
     }
 
@@ -172,6 +204,38 @@ impl SignalingMetrics {
                 .i64_up_down_counter(PARTICIPANTS_PER_ROOM)
                 .with_description("Participants per room")
                 .build(),
+            chat_message_size: meter
+                .u64_histogram(CHAT_MESSAGE_SIZE)
+                .with_description("Size of accepted chat message contents")
+                .with_unit("bytes")
+                .build(),
+            chat_messages_count: meter
+                .u64_counter(CHAT_MESSAGES)
+                .with_description("Number of accepted chat messages")
+                .build(),
+            active_module_sessions_count: meter
+                .i64_up_down_counter(ACTIVE_MODULE_SESSIONS)
+                .with_description("Number of participants with an active signaling module")
+                .build(),
+            exchange_queue_depth: meter
+                .i64_up_down_counter(EXCHANGE_QUEUE_DEPTH)
+                .with_description("Number of messages waiting to be processed by the exchange task")
+                .build(),
+            exchange_publish_failures_count: meter
+                .u64_counter(EXCHANGE_PUBLISH_FAILURES)
+                .with_description(
+                    "Number of messages dropped because publishing them to the exchange failed",
+                )
+                .build(),
+            report_generations_count: meter
+                .u64_counter(REPORT_GENERATIONS)
+                .with_description("Number of generated PDF reports")
+                .build(),
+            report_generation_last_success: meter
+                .u64_gauge(REPORT_GENERATION_LAST_SUCCESS)
+                .with_description("Unix timestamp of the last successfully generated PDF report")
+                .with_unit("seconds")
+                .build(),
             rooms: Mutex::new(HashMap::new()),
             participants: Mutex::new(HashMap::new()),
         };
@@ -209,6 +273,29 @@ impl SignalingMetrics {
         self.destroyed_rooms_count.add(1, &[]);
     }
 
+    /// Number of rooms that are currently active on this controller
+    pub fn active_rooms_count(&self) -> usize {
+        self.rooms.lock().len()
+    }
+
+    /// Number of participants that are currently connected to this controller
+    pub fn active_participants_count(&self) -> usize {
+        self.participants.lock().len()
+    }
+
+    pub fn increment_exchange_queue_depth(&self) {
+        self.exchange_queue_depth.add(1, &[]);
+    }
+
+    pub fn decrement_exchange_queue_depth(&self) {
+        self.exchange_queue_depth.add(-1, &[]);
+    }
+
+    pub fn increment_exchange_publish_failures(&self, critical: bool) {
+        self.exchange_publish_failures_count
+            .add(1, &[KeyValue::new(CRITICAL, critical)]);
+    }
+
     pub fn increment_created_breakout_rooms_count(&self) {
         self.created_breakout_rooms_count.add(1, &[]);
     }
@@ -340,4 +427,46 @@ impl SignalingMetrics {
             &[KeyValue::new(MEDIA_SESSION_TYPE, session_type.to_owned())],
         );
     }
+
+    /// Record an accepted chat message.
+    ///
+    /// Only the scope kind and the size of the content are recorded, never the content itself
+    /// or the participants involved.
+    pub fn record_chat_message(&self, scope: &'static str, size: usize) {
+        let attributes = [KeyValue::new(CHAT_SCOPE, scope)];
+        self.chat_message_size.record(size as u64, &attributes);
+        self.chat_messages_count.add(1, &attributes);
+    }
+
+    /// Record that a signaling module was initialized for a participant
+    pub fn increment_active_module_sessions(&self, module: &ModuleId) {
+        self.active_module_sessions_count
+            .add(1, &[KeyValue::new(MODULE, module.to_string())]);
+    }
+
+    /// Record that a signaling module of a participant was destroyed
+    pub fn decrement_active_module_sessions(&self, module: &ModuleId) {
+        self.active_module_sessions_count
+            .add(-1, &[KeyValue::new(MODULE, module.to_string())]);
+    }
+
+    /// Record the outcome of a PDF report generation of a signaling module
+    pub fn record_report_generation(&self, module: &ModuleId, success: bool) {
+        let module = KeyValue::new(MODULE, module.to_string());
+        self.report_generations_count.add(
+            1,
+            &[
+                module.clone(),
+                KeyValue::new(REPORT_GENERATION_SUCCESSFUL, success),
+            ],
+        );
+
+        if success {
+            let now = SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default();
+            self.report_generation_last_success
+                .record(now.as_secs(), &[module]);
+        }
+    }
 }
@@ -10,12 +10,15 @@ use opentalk_types_common::{modules::ModuleId, time::Timestamp};
 use opentalk_types_signaling::{LeaveReason, NamespacedEvent, Role};
 use serde::Serialize;
 
-use crate::{AnyStream, SignalingMetrics, SignalingModule, VolatileStorage, any_stream};
+use crate::{
+    AnyStream, ExchangeDelivery, SignalingMetrics, SignalingModule, VolatileStorage, any_stream,
+};
 
 #[derive(Debug, Clone)]
 pub struct ExchangePublish {
     pub routing_key: String,
     pub message: String,
+    pub delivery: ExchangeDelivery,
 }
 
 /// Context passed to the module
@@ -73,6 +76,17 @@ where
         self.exchange_publish_to_namespace(routing_key, M::NAMESPACE, message.into())
     }
 
+    /// Queue a state-critical outgoing message to be sent via the message exchange
+    ///
+    /// Publishing the message is retried when it fails, see [`ExchangeDelivery::Critical`].
+    pub fn exchange_publish_critical(
+        &mut self,
+        routing_key: String,
+        message: impl Into<M::ExchangeMessage>,
+    ) {
+        self.exchange_publish_to_namespace_critical(routing_key, M::NAMESPACE, message.into())
+    }
+
     /// Queue a outgoing message to be sent via the message exchange
     pub fn exchange_publish_to_namespace(
         &mut self,
@@ -90,11 +104,41 @@ where
         );
     }
 
+    /// Queue a state-critical outgoing message to be sent via the message exchange
+    ///
+    /// Publishing the message is retried when it fails, see [`ExchangeDelivery::Critical`].
+    pub fn exchange_publish_to_namespace_critical(
+        &mut self,
+        routing_key: String,
+        module: ModuleId,
+        payload: impl Serialize,
+    ) {
+        self.push_exchange_publish(
+            routing_key,
+            NamespacedEvent {
+                module,
+                timestamp: self.timestamp,
+                payload,
+            },
+            ExchangeDelivery::Critical,
+        );
+    }
+
     /// Queue any serializable outgoing message to be sent via the message exchange
     pub fn exchange_publish_any(&mut self, routing_key: String, message: impl Serialize) {
+        self.push_exchange_publish(routing_key, message, ExchangeDelivery::BestEffort);
+    }
+
+    fn push_exchange_publish(
+        &mut self,
+        routing_key: String,
+        message: impl Serialize,
+        delivery: ExchangeDelivery,
+    ) {
         self.exchange_publish.push(ExchangePublish {
             routing_key,
             message: serde_json::to_string(&message).expect("value must be serializable to json"),
+            delivery,
         });
     }
 
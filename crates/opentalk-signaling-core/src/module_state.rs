@@ -0,0 +1,16 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use futures::future::LocalBoxFuture;
+
+use crate::{SignalingModuleError, SignalingRoomId, VolatileStorage};
+
+/// Summarizes the state a single module currently keeps for a room
+///
+/// See [`SignalingModule::summarize_state`](crate::SignalingModule::summarize_state).
+pub type ModuleStateSummarizer =
+    fn(
+        VolatileStorage,
+        SignalingRoomId,
+    ) -> LocalBoxFuture<'static, Result<Option<serde_json::Value>, SignalingModuleError>>;
@@ -53,9 +53,9 @@ use tokio::{
 };
 
 use crate::{
-    AnyStream, DestroyContext, Event, ExchangePublish, InitContext, ModuleContext, ObjectStorage,
-    Participant, SerdeJsonSnafu, SignalingModule, SignalingModuleError, SignalingRoomId,
-    VolatileStorage,
+    AnyStream, DestroyContext, Event, ExchangeDelivery, ExchangePublish, InitContext,
+    MalformedMessage, ModuleContext, ObjectStorage, Participant, SerdeJsonSnafu, SignalingModule,
+    SignalingModuleError, SignalingRoomId, VolatileStorage,
     control::{
         self, ControlStateExt as _, ControlStorageProvider,
         storage::{
@@ -65,6 +65,7 @@ use crate::{
         },
     },
     destroy_context::CleanupScope,
+    parse_ws_message,
     room_lock::RoomLockingProvider,
 };
 
@@ -109,13 +110,21 @@ where
             db,
             authz,
             room,
-            // todo: add breakout room support
             breakout_room: None,
             runner_interfaces: HashMap::new(),
             exchange_sender,
         }
     }
 
+    /// Let the participants join the given breakout room of the room instead of the main room
+    ///
+    /// Only the signaling room id is affected, the exchange is not shared with other
+    /// [`ModuleTester`] instances.
+    pub fn with_breakout_room(mut self, breakout_room: BreakoutRoomId) -> Self {
+        self.breakout_room = Some(breakout_room);
+        self
+    }
+
     async fn join_internal(
         &mut self,
         participant_id: ParticipantId,
@@ -219,6 +228,25 @@ where
         Ok(())
     }
 
+    /// Send a raw WebSocket payload to the underlying module that is mapped to `participant_id`.
+    ///
+    /// The payload is parsed like the runner does it, a payload that does not match the
+    /// module's incoming message type is answered with [`WsMessageOutgoing::Malformed`].
+    pub fn send_raw_ws_message(
+        &self,
+        participant_id: &ParticipantId,
+        payload: Value,
+    ) -> Result<(), SignalingModuleError> {
+        let (interface, ..) = self
+            .runner_interfaces
+            .get(participant_id)
+            .expect("User {} does not exist in module tester");
+
+        interface.ws.send(WsMessageIncoming::Raw(payload))?;
+
+        Ok(())
+    }
+
     /// Receive a WebSocket message from the underlying Module that is mapped to `participant_id`
     ///
     ///
@@ -506,6 +534,13 @@ where
                         WsMessageIncoming::Module(module_message) =>
                             self.module.on_event(ctx, Event::WsMessage(module_message)).await.expect("Error when handling incoming ws message"),
 
+                        WsMessageIncoming::Raw(payload) => match parse_ws_message(payload) {
+                            Ok(module_message) =>
+                                self.module.on_event(ctx, Event::WsMessage(module_message)).await.expect("Error when handling incoming ws message"),
+                            Err(malformed) =>
+                                self.interface.ws.send(WsMessageOutgoing::Malformed(malformed)).expect("Error sending malformed message error"),
+                        },
+
                         WsMessageIncoming::Control(control_message) =>
                             self.handle_ws_control_message(ctx, control_message).await.expect("Error when handling incoming ws control message"),
 
@@ -874,6 +909,7 @@ where
         let exchange_publish = ExchangePublish {
             routing_key: control::exchange::current_room_all_participants(self.room_id),
             message,
+            delivery: ExchangeDelivery::BestEffort,
         };
 
         self.exchange_sender
@@ -1129,6 +1165,8 @@ where
 {
     Module(M::Incoming),
     Control(ControlCommand),
+    /// An unparsed module message
+    Raw(Value),
     /// The 'WebSocket' was closed
     CloseWs,
 }
@@ -1141,6 +1179,8 @@ where
 {
     Module(M::Outgoing),
     Control(ControlEvent),
+    /// A module message could not be parsed
+    Malformed(MalformedMessage),
 }
 
 impl<M> Clone for WsMessageOutgoing<M>
@@ -1152,6 +1192,7 @@ where
         match self {
             Self::Module(outgoing) => Self::Module(outgoing.clone()),
             Self::Control(outgoing) => Self::Control(outgoing.clone()),
+            Self::Malformed(outgoing) => Self::Malformed(outgoing.clone()),
         }
     }
 }
@@ -1164,6 +1205,7 @@ where
         match self {
             Self::Module(arg0) => f.debug_tuple("Module").field(arg0).finish(),
             Self::Control(arg0) => f.debug_tuple("Control").field(arg0).finish(),
+            Self::Malformed(arg0) => f.debug_tuple("Malformed").field(arg0).finish(),
         }
     }
 }
@@ -1190,6 +1232,7 @@ where
                     serde_json::to_value(r0).expect("ControlEvent must be serializable");
                 value_left == value_right
             }
+            (Self::Malformed(l0), Self::Malformed(r0)) => l0 == r0,
             _ => false,
         }
     }
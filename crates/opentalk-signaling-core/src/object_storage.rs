@@ -461,6 +461,42 @@ impl ObjectStorage {
         Ok(data.body)
     }
 
+    /// Get the bytes from `start` to `end` (both inclusive) of an object
+    pub async fn get_range(&self, key: String, start: u64, end: u64) -> Result<ByteStream> {
+        let data = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .range(format!("bytes={start}-{end}"))
+            .send()
+            .await
+            .map_err(Into::into)
+            .context(GetSnafu)?;
+
+        Ok(data.body)
+    }
+
+    /// Get the entity tag of an object
+    ///
+    /// The entity tag is generated by the storage server from the object contents, so it stays
+    /// the same as long as the object is not overwritten.
+    pub async fn get_e_tag(&self, key: String) -> Result<String> {
+        let head_object = self
+            .client
+            .head_object()
+            .bucket(&self.bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(Into::into)
+            .context(GetSnafu)?;
+
+        head_object.e_tag.context(InvalidResponseSnafu {
+            message: "missing etag in head_object response",
+        })
+    }
+
     pub async fn get_object_size_if_exists(&self, key: String) -> Result<Option<i64>> {
         let response = self
             .client
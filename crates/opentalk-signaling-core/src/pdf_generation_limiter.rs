@@ -0,0 +1,58 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use std::sync::Arc;
+
+use opentalk_controller_settings::DEFAULT_MAX_CONCURRENT_PDF_GENERATIONS;
+use tokio::sync::Semaphore;
+
+/// Limits the number of PDF reports that are rendered at the same time
+///
+/// Rendering a report is memory intensive, so many rooms finishing at once could
+/// exhaust the available memory. The limiter is shared between all signaling modules,
+/// generations exceeding the permit count are queued until a permit becomes available.
+#[derive(Debug, Clone)]
+pub struct PdfGenerationLimiter {
+    semaphore: Arc<Semaphore>,
+}
+
+impl PdfGenerationLimiter {
+    /// Create a new limiter that allows `max_concurrent` generations at the same time
+    pub fn new(max_concurrent: usize) -> Self {
+        Self {
+            semaphore: Arc::new(Semaphore::new(max_concurrent.max(1))),
+        }
+    }
+
+    /// Wait for a free permit and drive the `generation` to completion while holding it
+    pub async fn run<F: Future>(&self, generation: F) -> F::Output {
+        let _permit = self
+            .semaphore
+            .acquire()
+            .await
+            .expect("the pdf generation semaphore is never closed");
+
+        generation.await
+    }
+}
+
+impl Default for PdfGenerationLimiter {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_CONCURRENT_PDF_GENERATIONS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PdfGenerationLimiter;
+
+    #[tokio::test]
+    async fn permits_are_released() {
+        let limiter = PdfGenerationLimiter::new(1);
+
+        assert_eq!(limiter.run(async { 1 }).await, 1);
+        assert_eq!(limiter.run(async { 2 }).await, 2);
+        assert_eq!(limiter.semaphore.available_permits(), 1);
+    }
+}
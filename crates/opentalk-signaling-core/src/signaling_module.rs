@@ -2,7 +2,7 @@
 //
 // SPDX-License-Identifier: EUPL-1.2
 
-use std::{collections::BTreeSet, fmt::Debug, sync::Arc};
+use std::{collections::BTreeSet, fmt::Debug, path::PathBuf, sync::Arc};
 
 use lapin_pool::RabbitMqPool;
 use opentalk_controller_settings::{Settings, SettingsProvider};
@@ -14,7 +14,8 @@ use snafu::Snafu;
 use tokio::sync::broadcast;
 
 use crate::{
-    DestroyContext, Event, InitContext, ModuleContext, VolatileStorage, room_lock::LockError,
+    DestroyContext, Event, InitContext, ModuleContext, PdfGenerationLimiter, SignalingMetrics,
+    SignalingRoomId, VolatileStorage, room_lock::LockError,
 };
 
 type Result<T> = std::result::Result<T, SignalingModuleError>;
@@ -102,6 +103,10 @@ pub struct SignalingModuleInitData {
     pub volatile: VolatileStorage,
     pub shutdown: broadcast::Sender<()>,
     pub reload: broadcast::Sender<()>,
+    pub metrics: Arc<SignalingMetrics>,
+    pub pdf_generation_limiter: PdfGenerationLimiter,
+    pub report_dump_path: Option<PathBuf>,
+    pub report_font_path: Option<PathBuf>,
 }
 
 /// Extension to a the signaling websocket
@@ -153,6 +158,18 @@ pub trait SignalingModule: Send + Sized + 'static {
         BTreeSet::default()
     }
 
+    /// Summarize the state this module currently keeps for `room`.
+    ///
+    /// Used to inspect a running room without joining it. Implementations must only read from
+    /// the volatile storage and never modify it. Returns `None` if the module has no active state
+    /// in the room, which is the default.
+    async fn summarize_state(
+        _volatile: VolatileStorage,
+        _room: SignalingRoomId,
+    ) -> Result<Option<serde_json::Value>> {
+        Ok(None)
+    }
+
     /// Events related to this module will be passed into this function together with [`ModuleContext`]
     /// which gives access to the websocket and other related information.
     async fn on_event(
@@ -168,4 +185,13 @@ pub trait SignalingModule: Send + Sized + 'static {
     ///
     /// If `None` is returned, the module is not initialized.
     async fn build_params(init: SignalingModuleInitData) -> Result<Option<Self::Params>>;
+
+    /// Apply hot-reloadable settings to the module parameters.
+    ///
+    /// Called once for each registered module after the controller reloaded its settings (e.g.
+    /// on `SIGHUP`). The parameters are shared with the modules of all running sessions, so
+    /// reloadable values must be kept in shared state that can be swapped atomically. Active
+    /// sessions are not interrupted. Changes that can only be applied with a restart of the
+    /// controller must be logged as a warning.
+    fn reload_params(_params: &Self::Params, _settings: &Settings) {}
 }
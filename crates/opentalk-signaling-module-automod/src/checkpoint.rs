@@ -0,0 +1,281 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+//! Persistence of the automod session state in the database
+//!
+//! Without redis the automod state only lives in the memory of the controller and is lost on a
+//! restart. When enabled via the `automod.persist_state` setting, the state is written to a
+//! module resource after each mutation and restored by the next participant joining the room.
+
+use std::sync::Arc;
+
+use opentalk_database::Db;
+use opentalk_db_storage::module_resources::{Filter, ModuleResource, NewModuleResource};
+use opentalk_signaling_core::{SerdeJsonSnafu, SignalingModuleError, SignalingRoomId};
+use opentalk_types_common::{rooms::RoomId, tenants::TenantId, users::UserId};
+use opentalk_types_signaling::ParticipantId;
+use opentalk_types_signaling_automod::MODULE_ID;
+use serde::{Deserialize, Serialize};
+use snafu::ResultExt;
+
+use crate::storage::{AutomodStorage, Entry, StorageConfig};
+
+/// Snapshot of the automod state of a single room
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct Checkpoint {
+    config: StorageConfig,
+    allow_list: Vec<ParticipantId>,
+    playlist: Vec<ParticipantId>,
+    history: Vec<Entry>,
+    #[serde(default)]
+    speaker: Option<ParticipantId>,
+}
+
+impl Checkpoint {
+    /// Collect the current state from the volatile storage. Returns `None` if automod is not active.
+    pub(crate) async fn collect(
+        storage: &mut dyn AutomodStorage,
+        room: SignalingRoomId,
+    ) -> Result<Option<Self>, SignalingModuleError> {
+        let Some(config) = storage.config_get(room).await? else {
+            return Ok(None);
+        };
+
+        let allow_list = storage
+            .allow_list_get_all(room)
+            .await?
+            .into_iter()
+            .collect();
+        let playlist = storage.playlist_get_all(room).await?;
+        let history = storage.history_get_entries(room, config.started).await?;
+        let speaker = storage.speaker_get(room).await?;
+
+        Ok(Some(Self {
+            config,
+            allow_list,
+            playlist,
+            history,
+            speaker,
+        }))
+    }
+
+    /// Write the checkpointed state back into the volatile storage and return the restored config.
+    pub(crate) async fn restore(
+        self,
+        storage: &mut dyn AutomodStorage,
+        room: SignalingRoomId,
+    ) -> Result<StorageConfig, SignalingModuleError> {
+        storage.allow_list_set(room, &self.allow_list).await?;
+        storage.playlist_set(room, &self.playlist).await?;
+
+        for entry in self.history {
+            storage.history_add(room, entry).await?;
+        }
+
+        if let Some(speaker) = self.speaker {
+            let _ = storage.speaker_set(room, speaker).await?;
+        }
+
+        storage.config_set(room, self.config.clone()).await?;
+
+        Ok(self.config)
+    }
+}
+
+/// Location of the checkpoint of a room inside the database
+#[derive(Clone)]
+pub(crate) struct CheckpointStore {
+    db: Arc<Db>,
+    tenant_id: TenantId,
+    room_id: RoomId,
+    room_owner: UserId,
+    tag: String,
+}
+
+impl CheckpointStore {
+    pub(crate) fn new(
+        db: Arc<Db>,
+        tenant_id: TenantId,
+        room_owner: UserId,
+        room: SignalingRoomId,
+    ) -> Self {
+        Self {
+            db,
+            tenant_id,
+            room_id: room.room_id(),
+            room_owner,
+            tag: format!("checkpoint:{room}"),
+        }
+    }
+
+    fn filter(&self) -> Filter {
+        Filter::new()
+            .with_namespace(MODULE_ID.to_string())
+            .with_tag(self.tag.clone())
+    }
+
+    /// Load the checkpoint of the room, if any exists
+    pub(crate) async fn load(&self) -> Result<Option<Checkpoint>, SignalingModuleError> {
+        let mut conn = self.db.get_conn().await?;
+
+        // Checkpoints are replaced atomically, but rooms might still have several of them from
+        // before, the most recent one wins.
+        let Some(resource) = ModuleResource::get(&mut conn, self.filter())
+            .await?
+            .into_iter()
+            .max_by_key(|resource| resource.updated_at)
+        else {
+            return Ok(None);
+        };
+
+        let checkpoint = serde_json::from_value(resource.data).context(SerdeJsonSnafu {
+            message: "Failed to deserialize automod checkpoint",
+        })?;
+
+        Ok(Some(checkpoint))
+    }
+
+    /// Replace the checkpoint of the room with the given one
+    pub(crate) async fn save(&self, checkpoint: &Checkpoint) -> Result<(), SignalingModuleError> {
+        let data = serde_json::to_value(checkpoint).context(SerdeJsonSnafu {
+            message: "Failed to serialize automod checkpoint",
+        })?;
+
+        let mut conn = self.db.get_conn().await?;
+
+        let _ = NewModuleResource {
+            tenant_id: self.tenant_id,
+            room_id: self.room_id,
+            created_by: self.room_owner,
+            namespace: MODULE_ID.to_string(),
+            tag: Some(self.tag.clone()),
+            data,
+        }
+        .replace(&mut conn, self.filter())
+        .await?;
+
+        Ok(())
+    }
+
+    /// Remove the checkpoint of the room
+    pub(crate) async fn delete(&self) -> Result<(), SignalingModuleError> {
+        let mut conn = self.db.get_conn().await?;
+
+        ModuleResource::delete(&mut conn, self.filter()).await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::Utc;
+    use opentalk_db_storage::module_resources::ModuleResource;
+    use opentalk_signaling_core::{SignalingRoomId, VolatileStaticMemoryStorage};
+    use opentalk_test_util::{ROOM_ID, database::DatabaseContext};
+    use opentalk_types_signaling::ParticipantId;
+    use opentalk_types_signaling_automod::config::{Parameter, SelectionStrategy};
+    use pretty_assertions::assert_eq;
+    use serial_test::serial;
+
+    use super::{Checkpoint, CheckpointStore};
+    use crate::storage::{AutomodStorage, Entry, StorageConfig, reset_memory_state};
+
+    const ALICE: ParticipantId = ParticipantId::from_u128(0xa11c3);
+    const BOB: ParticipantId = ParticipantId::from_u128(0x808);
+
+    #[tokio::test]
+    #[serial]
+    async fn save_load_and_restore() {
+        let db_ctx = DatabaseContext::new(true).await;
+        let owner = db_ctx.create_test_user(0, vec![]).await.unwrap();
+        let room = db_ctx
+            .create_test_room(ROOM_ID, owner.id, false)
+            .await
+            .unwrap();
+        let room_id = SignalingRoomId::new(room.id, None);
+        let store = CheckpointStore::new(db_ctx.db.clone(), room.tenant_id, owner.id, room_id);
+
+        assert!(store.load().await.unwrap().is_none());
+
+        reset_memory_state();
+        let mut memory = VolatileStaticMemoryStorage;
+        let storage: &mut dyn AutomodStorage = &mut memory;
+
+        let config = StorageConfig {
+            started: Utc::now(),
+            issued_by: ALICE,
+            parameter: Parameter {
+                selection_strategy: SelectionStrategy::Playlist,
+                show_list: true,
+                consider_hand_raise: Default::default(),
+                time_limit: Some(Duration::from_secs(60)),
+                allow_double_selection: Default::default(),
+                animation_on_random: Default::default(),
+                auto_append_on_join: Default::default(),
+                random_seed: Default::default(),
+            },
+        };
+        storage.config_set(room_id, config.clone()).await.unwrap();
+        storage.playlist_set(room_id, &[BOB, ALICE]).await.unwrap();
+        storage
+            .history_add(room_id, Entry::start(ALICE))
+            .await
+            .unwrap();
+        let _ = storage.speaker_set(room_id, ALICE).await.unwrap();
+
+        let checkpoint = Checkpoint::collect(storage, room_id)
+            .await
+            .unwrap()
+            .expect("automod is active");
+        store.save(&checkpoint).await.unwrap();
+
+        // Saving again replaces the previous checkpoint
+        storage.playlist_set(room_id, &[BOB]).await.unwrap();
+        let checkpoint = Checkpoint::collect(storage, room_id)
+            .await
+            .unwrap()
+            .expect("automod is active");
+        store.save(&checkpoint).await.unwrap();
+
+        let mut conn = db_ctx.db.get_conn().await.unwrap();
+        assert_eq!(
+            ModuleResource::get(&mut conn, store.filter())
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        // The volatile state is lost, e.g. due to a restart of the controller
+        reset_memory_state();
+        assert_eq!(storage.config_get(room_id).await.unwrap(), None);
+
+        let restored_config = store
+            .load()
+            .await
+            .unwrap()
+            .expect("checkpoint was saved")
+            .restore(storage, room_id)
+            .await
+            .unwrap();
+
+        assert_eq!(restored_config, config);
+        assert_eq!(
+            storage.config_get(room_id).await.unwrap(),
+            Some(config.clone())
+        );
+        assert_eq!(storage.playlist_get_all(room_id).await.unwrap(), vec![BOB]);
+        assert_eq!(
+            storage.history_get(room_id, config.started).await.unwrap(),
+            vec![ALICE]
+        );
+        assert_eq!(storage.speaker_get(room_id).await.unwrap(), Some(ALICE));
+
+        store.delete().await.unwrap();
+        assert!(store.load().await.unwrap().is_none());
+    }
+}
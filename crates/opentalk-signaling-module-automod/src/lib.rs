@@ -66,35 +66,96 @@
 //!
 //! Moderators will always be able to execute a re-selection of the current speaker regardless of
 //! the `selection_strategy`.
+//!
+//! When selecting the next speaker from the playlist or randomly, candidates who are no longer
+//! present in the room are removed from the remaining participants and skipped. If nobody present
+//! is left, the session ends.
+//!
+//! If a `random_seed` is set in the parameters, random selections are derived from it, so the
+//! same seed and the same events produce the same order of speakers. This makes the order
+//! predictable for everyone knowing the seed and should only be used where reproducibility is
+//! required, e.g. for recorded trainings. Sessions are not seeded by default.
+//!
+//! ### Persistence
+//!
+//! If the `automod.persist_state` setting is enabled, the config, allow_list, playlist, history
+//! and current speaker are checkpointed to the database after each mutation. When a participant
+//! joins a room whose volatile state is empty but which has a checkpoint (e.g. after a controller
+//! restart), the state is restored from it and the time limit of the speaker continues to run.
+//! The checkpoint is removed when the session stops or the room is destroyed.
+//!
+//! ### Export
+//!
+//! Moderators can export the speaker history of the running or the previous sessions of the
+//! room as a PDF report. The report lists the accumulated speaking time per participant and every
+//! turn in the order in which it was taken. It is stored as an asset of the room in the namespace
+//! of the automod module.
 
+mod checkpoint;
 mod exchange;
+mod report;
 mod state_machine;
 mod storage;
 
-use std::time::Duration;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
 
+use bytes::Bytes;
+use checkpoint::{Checkpoint, CheckpointStore};
+use chrono::{DateTime, Local, Utc};
+use chrono_tz::Tz;
 use either::Either;
 use futures::{FutureExt, stream::once};
+use opentalk_controller_settings::{
+    DEFAULT_AUTOMOD_ANIMATION_DURATION, DEFAULT_AUTOMOD_MAX_TIME_LIMIT,
+};
+use opentalk_database::Db;
+use opentalk_db_storage::users::User;
 use opentalk_signaling_core::{
-    DestroyContext, Event, InitContext, ModuleContext, RoomLockingProvider, SignalingModule,
-    SignalingModuleError, SignalingModuleInitData, SignalingRoomId, VolatileStorage, control,
+    ChunkFormat, DestroyContext, Event, InitContext, ModuleContext, ObjectStorage,
+    ObjectStorageError, PdfGenerationLimiter, RoomLockingProvider, SignalingModule,
+    SignalingModuleError, SignalingModuleInitData, SignalingRoomId, VolatileStorage,
+    assets::{AssetError, NewAssetFileName, save_asset},
+    control::{
+        self,
+        storage::{ControlStorageParticipantAttributes, DISPLAY_NAME, GlobalRoomAttributeId},
+    },
+};
+use opentalk_types_common::{
+    assets::{AssetFileKind, FileExtension, asset_file_kind},
+    modules::ModuleId,
+    time::{TimeZone, Timestamp},
+    users::UserId,
 };
-use opentalk_types_common::modules::ModuleId;
 use opentalk_types_signaling::{ParticipantId, Role};
 use opentalk_types_signaling_automod::{
     self, MODULE_ID,
     command::{AutomodCommand, Select, Start, Yield},
     config::{FrontendConfig, Parameter, SelectionStrategy},
-    event::{AutomodEvent, Error, RemainingUpdated, SpeakerUpdated, StartAnimation, StoppedReason},
+    event::{
+        AutomodEvent, Error, PdfAsset, RemainingUpdated, SpeakerUpdated, StartAnimation,
+        StoppedReason,
+    },
     state::AutomodState,
 };
-use snafu::{ResultExt, whatever};
+use rand::{SeedableRng, rngs::StdRng};
+use report::SessionReportParameter;
+use snafu::{Report, ResultExt, whatever};
 use state_machine::StateMachineOutput;
 use storage::AutomodStorage;
 use tokio::time::sleep;
 use uuid::Uuid;
 
-use crate::{exchange::Message, storage::StorageConfig};
+use crate::{
+    exchange::Message,
+    storage::{Entry, EntryKind, StorageConfig},
+};
+
+const SESSION_REPORT_TEMPLATE: &str = include_str!("session_report.typ");
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub struct ExpiryId(Uuid);
@@ -104,7 +165,8 @@ pub struct AnimationId(Uuid);
 
 pub enum TimerEvent {
     AnimationEnd(AnimationId, ParticipantId),
-    Expiry(ExpiryId),
+    /// The time limit of the given speaker has expired
+    Expiry(ExpiryId, ParticipantId),
 }
 
 pub struct Automod {
@@ -113,6 +175,48 @@ pub struct Automod {
 
     current_expiry_id: Option<ExpiryId>,
     current_animation_id: Option<AnimationId>,
+
+    checkpoint: Option<CheckpointStore>,
+
+    room_owner: UserId,
+    db: Arc<Db>,
+    storage: Arc<ObjectStorage>,
+    params: AutomodParams,
+}
+
+/// Parameters of the automod module
+#[derive(Debug, Clone)]
+pub struct AutomodParams {
+    /// Persist the automod state to the database so that it survives a controller restart
+    pub persist_state: bool,
+
+    /// The longest time limit per speaker a session may be started with
+    pub max_time_limit: Duration,
+
+    /// Duration of the animation shown for a random selection
+    pub animation_duration: Duration,
+
+    /// Limits the number of concurrently generated session reports
+    pub pdf_generation_limiter: PdfGenerationLimiter,
+
+    /// Directory into which the session report inputs are dumped for troubleshooting
+    pub report_dump_path: Option<PathBuf>,
+
+    /// Directory containing additional fonts for the session report
+    pub report_font_path: Option<PathBuf>,
+}
+
+impl Default for AutomodParams {
+    fn default() -> Self {
+        Self {
+            persist_state: false,
+            max_time_limit: DEFAULT_AUTOMOD_MAX_TIME_LIMIT,
+            animation_duration: DEFAULT_AUTOMOD_ANIMATION_DURATION,
+            pdf_generation_limiter: PdfGenerationLimiter::default(),
+            report_dump_path: None,
+            report_font_path: None,
+        }
+    }
 }
 
 trait AutomodStorageProvider {
@@ -132,7 +236,7 @@ impl AutomodStorageProvider for VolatileStorage {
 impl SignalingModule for Automod {
     const NAMESPACE: ModuleId = MODULE_ID;
 
-    type Params = ();
+    type Params = AutomodParams;
 
     type Incoming = AutomodCommand;
     type Outgoing = AutomodEvent;
@@ -145,17 +249,65 @@ impl SignalingModule for Automod {
 
     async fn init(
         ctx: InitContext<'_, Self>,
-        _params: &Self::Params,
+        params: &Self::Params,
         _protocol: &'static str,
     ) -> Result<Option<Self>, SignalingModuleError> {
+        let checkpoint = params.persist_state.then(|| {
+            CheckpointStore::new(
+                ctx.db().clone(),
+                ctx.room().tenant_id,
+                ctx.room().created_by,
+                ctx.room_id(),
+            )
+        });
+
         Ok(Some(Self {
             id: ctx.participant_id(),
             room: ctx.room_id(),
             current_expiry_id: None,
             current_animation_id: None,
+            checkpoint,
+            room_owner: ctx.room().created_by,
+            db: ctx.db().clone(),
+            storage: ctx.storage().clone(),
+            params: params.clone(),
         }))
     }
 
+    async fn summarize_state(
+        mut volatile: VolatileStorage,
+        room: SignalingRoomId,
+    ) -> Result<Option<serde_json::Value>, SignalingModuleError> {
+        let storage = volatile.storage();
+
+        let Some(config) = storage.config_get(room).await? else {
+            return Ok(None);
+        };
+
+        let speaker = storage.speaker_get(room).await?;
+        let history = storage.history_get(room, config.started).await?;
+        let remaining =
+            Self::get_remaining(storage, room, config.parameter.selection_strategy).await?;
+
+        // The public config hides the participant lists if the moderator chose not to show them
+        let state = AutomodState {
+            config: FrontendConfig {
+                parameter: config.parameter,
+                history,
+                remaining,
+                issued_by: config.issued_by,
+            }
+            .into_public(),
+            speaker,
+        };
+
+        Ok(Some(serde_json::json!({
+            "started": config.started,
+            "config": state.config,
+            "speaker": state.speaker,
+        })))
+    }
+
     async fn on_event(
         &mut self,
         ctx: ModuleContext<'_, Self>,
@@ -186,10 +338,10 @@ impl SignalingModule for Automod {
 
                 Ok(())
             }
-            Event::Ext(TimerEvent::Expiry(expiry_id)) => {
+            Event::Ext(TimerEvent::Expiry(expiry_id, speaker)) => {
                 if let Some(current_expiry_id) = self.current_expiry_id {
                     if current_expiry_id == expiry_id {
-                        self.on_expired_event(ctx).await?;
+                        self.on_expired_event(ctx, speaker).await?;
                     }
                 }
 
@@ -213,13 +365,24 @@ impl SignalingModule for Automod {
             let _ = storage.allow_list_delete(self.room).await;
             let _ = storage.playlist_delete(self.room).await;
             let _ = storage.history_delete(self.room).await;
+
+            if let Some(checkpoint) = &self.checkpoint {
+                let _ = checkpoint.delete().await;
+            }
         }
     }
 
     async fn build_params(
-        _init: SignalingModuleInitData,
+        init: SignalingModuleInitData,
     ) -> Result<Option<Self::Params>, SignalingModuleError> {
-        Ok(Some(()))
+        Ok(Some(AutomodParams {
+            persist_state: init.startup_settings.automod.persist_state,
+            max_time_limit: init.startup_settings.automod.max_time_limit,
+            animation_duration: init.startup_settings.automod.animation_duration,
+            pdf_generation_limiter: init.pdf_generation_limiter,
+            report_dump_path: init.report_dump_path,
+            report_font_path: init.report_font_path,
+        }))
     }
 }
 
@@ -262,7 +425,10 @@ impl Automod {
         ctx: &mut ModuleContext<'_, Self>,
     ) -> Result<Option<AutomodState>, SignalingModuleError> {
         let storage = ctx.volatile.storage();
-        let config = storage.config_get(self.room).await?;
+        let (config, restored) = match storage.config_get(self.room).await? {
+            Some(config) => (Some(config), false),
+            None => (self.restore_checkpoint(storage).await?, true),
+        };
 
         // If config is some, automod is active and running
         let Some(config) = config else {
@@ -271,17 +437,33 @@ impl Automod {
         };
 
         let speaker = storage.speaker_get(self.room).await?;
+
+        if restored {
+            // The expiry timer of the speaker got lost together with the state, the participant
+            // restoring the state takes over the remaining time of the speaker
+            if let Some(speaker) = speaker {
+                let history = storage
+                    .history_get_entries(self.room, config.started)
+                    .await?;
+
+                if let Some(time_left) = speaker_time_left(&config, &history, speaker, Utc::now()) {
+                    self.schedule_expiry(ctx, speaker, time_left);
+                }
+            }
+        }
+
+        let storage = ctx.volatile.storage();
         let history = storage.history_get(self.room, config.started).await?;
         let auto_append = config.parameter.auto_append_on_join && !history.contains(&self.id);
 
         if auto_append {
             self.add_to_remaining(storage, config.parameter.selection_strategy)
                 .await?;
+            self.write_checkpoint(storage).await?;
         }
 
-        let remaining = self
-            .get_remaining(storage, config.parameter.selection_strategy)
-            .await?;
+        let remaining =
+            Self::get_remaining(storage, self.room, config.parameter.selection_strategy).await?;
 
         if auto_append {
             ctx.exchange_publish(
@@ -340,9 +522,14 @@ impl Automod {
                 .await?;
 
             if removed > 0 {
-                let remaining = self
-                    .get_remaining(ctx.volatile.storage(), config.parameter.selection_strategy)
-                    .await?;
+                self.write_checkpoint(ctx.volatile.storage()).await?;
+
+                let remaining = Self::get_remaining(
+                    ctx.volatile.storage(),
+                    self.room,
+                    config.parameter.selection_strategy,
+                )
+                .await?;
                 ctx.exchange_publish(
                     control::exchange::current_room_all_participants(self.room),
                     exchange::Message::RemainingUpdate(exchange::RemainingUpdate { remaining }),
@@ -366,10 +553,11 @@ impl Automod {
     async fn on_expired_event(
         &mut self,
         mut ctx: ModuleContext<'_, Self>,
+        expired_speaker: ParticipantId,
     ) -> Result<(), SignalingModuleError> {
         let guard = ctx.volatile.room_locking().lock_room(self.room).await?;
 
-        let result = self.on_expired_event_inner(&mut ctx).await;
+        let result = self.on_expired_event_inner(&mut ctx, expired_speaker).await;
 
         ctx.volatile.room_locking().unlock_room(guard).await?;
         result
@@ -378,13 +566,14 @@ impl Automod {
     async fn on_expired_event_inner(
         &mut self,
         ctx: &mut ModuleContext<'_, Self>,
+        expired_speaker: ParticipantId,
     ) -> Result<(), SignalingModuleError> {
         let storage = ctx.volatile.storage();
         let config = storage.config_get(self.room).await?;
         if let Some(config) = config {
             let speaker = storage.speaker_get(self.room).await?;
 
-            if speaker == Some(self.id) {
+            if speaker == Some(expired_speaker) {
                 self.select_next(ctx, config, None).await?;
             }
         }
@@ -422,6 +611,61 @@ impl Automod {
         Ok(())
     }
 
+    /// Ends the speaker status of `speaker` once `time_limit` has passed, unless another speaker
+    /// was selected in the meantime.
+    fn schedule_expiry(
+        &mut self,
+        ctx: &mut ModuleContext<'_, Self>,
+        speaker: ParticipantId,
+        time_limit: Duration,
+    ) {
+        let expiry_id = ExpiryId(Uuid::new_v4());
+        self.current_expiry_id = Some(expiry_id);
+
+        ctx.add_event_stream(once(
+            sleep(time_limit).map(move |_| TimerEvent::Expiry(expiry_id, speaker)),
+        ));
+    }
+
+    /// Writes the current state to the checkpoint, if persistence is enabled.
+    /// The storage mutex must be locked when calling this method.
+    async fn write_checkpoint(
+        &self,
+        storage: &mut dyn AutomodStorage,
+    ) -> Result<(), SignalingModuleError> {
+        let Some(store) = &self.checkpoint else {
+            return Ok(());
+        };
+
+        if let Some(checkpoint) = Checkpoint::collect(storage, self.room).await? {
+            store.save(&checkpoint).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Restores the state from the checkpoint, if persistence is enabled and a checkpoint exists.
+    /// The storage mutex must be locked when calling this method.
+    async fn restore_checkpoint(
+        &self,
+        storage: &mut dyn AutomodStorage,
+    ) -> Result<Option<StorageConfig>, SignalingModuleError> {
+        let Some(store) = &self.checkpoint else {
+            return Ok(None);
+        };
+
+        let Some(checkpoint) = store.load().await? else {
+            return Ok(None);
+        };
+
+        tracing::info!(
+            "Restoring automod state of room {} from checkpoint",
+            self.room
+        );
+
+        Ok(Some(checkpoint.restore(storage, self.room).await?))
+    }
+
     async fn add_to_remaining(
         &mut self,
         storage: &mut dyn AutomodStorage,
@@ -439,18 +683,18 @@ impl Automod {
     /// Retrieves the remaining participants list.
     /// The storage mutex must be locked when calling this method.
     async fn get_remaining(
-        &mut self,
         storage: &mut dyn AutomodStorage,
+        room: SignalingRoomId,
         selection_strategy: SelectionStrategy,
     ) -> Result<Vec<ParticipantId>, SignalingModuleError> {
         Ok(if selection_strategy.uses_allow_list() {
             storage
-                .allow_list_get_all(self.room)
+                .allow_list_get_all(room)
                 .await?
                 .into_iter()
                 .collect()
         } else {
-            storage.playlist_get_all(self.room).await?
+            storage.playlist_get_all(room).await?
         })
     }
 
@@ -528,6 +772,14 @@ impl Automod {
                 allow_list,
                 playlist,
             }) => {
+                if parameter
+                    .time_limit
+                    .is_some_and(|time_limit| time_limit > self.params.max_time_limit)
+                {
+                    ctx.ws_send(AutomodEvent::Error(Error::InvalidConfiguration));
+                    return Ok(());
+                }
+
                 if !self
                     .validate_lists(
                         &mut ctx,
@@ -574,8 +826,7 @@ impl Automod {
                         //
                         // If it does check the `validate_lists` function.
                         ctx.volatile.room_locking().unlock_room(guard).await?;
-                        // TODO: This should be a explicit failure point
-                        // (Error::InvalidConfiguration).
+                        ctx.ws_send(AutomodEvent::Error(Error::InvalidConfiguration));
                         return Ok(());
                     }
                 };
@@ -585,6 +836,7 @@ impl Automod {
                     ctx,
                     guard
                 );
+                try_or_unlock!(self.write_checkpoint(storage).await; ctx, guard);
 
                 ctx.exchange_publish(
                     control::exchange::current_room_all_participants(self.room),
@@ -639,6 +891,8 @@ impl Automod {
                         );
                     }
 
+                    try_or_unlock!(self.write_checkpoint(storage).await; ctx, guard);
+
                     // depending on the strategy find out if the remaining-list has been changed
                     let remaining = if config.parameter.selection_strategy.uses_allow_list() {
                         edit.allow_list
@@ -713,6 +967,16 @@ impl Automod {
 
                 ctx.volatile.room_locking().unlock_room(guard).await?;
             }
+            AutomodCommand::Skip => {
+                let guard = ctx.volatile.room_locking().lock_room(self.room).await?;
+                let config = try_or_unlock!(storage.config_get( self.room).await; ctx, guard);
+
+                if let Some(config) = config {
+                    try_or_unlock!(self.skip_speaker(&mut ctx, config).await; ctx, guard);
+                }
+
+                ctx.volatile.room_locking().unlock_room(guard).await?;
+            }
             AutomodCommand::Yield(Yield { next }) => {
                 let guard = ctx.volatile.room_locking().lock_room(self.room).await?;
                 let config = try_or_unlock!(storage.config_get( self.room).await; ctx, guard);
@@ -734,11 +998,188 @@ impl Automod {
 
                 ctx.volatile.room_locking().unlock_room(guard).await?;
             }
+            AutomodCommand::ExportSession => {
+                let report_timezone = self.report_timezone().await?;
+
+                let guard = ctx.volatile.room_locking().lock_room(self.room).await?;
+                let parameter = try_or_unlock!(
+                    self.collect_session_report(storage, report_timezone).await;
+                    ctx,
+                    guard
+                );
+                ctx.volatile.room_locking().unlock_room(guard).await?;
+
+                let Some(parameter) = parameter else {
+                    ctx.ws_send(AutomodEvent::Error(Error::NoSession));
+                    return Ok(());
+                };
+
+                self.export_session_report(&mut ctx, parameter).await?;
+            }
         }
 
         Ok(())
     }
 
+    /// Returns the timezone of the room owner, which is used for the session report.
+    async fn report_timezone(&self) -> Result<TimeZone, SignalingModuleError> {
+        let mut conn = self.db.get_conn().await?;
+        let owner = User::get(&mut conn, self.room_owner).await?;
+
+        Ok(owner.timezone.unwrap_or(TimeZone::from(Tz::UTC)))
+    }
+
+    /// Collects the speaker history of the running session, or of all previous sessions if
+    /// none is running. Returns `None` if no session has taken place in the room yet.
+    /// The storage mutex must be locked when calling this method.
+    async fn collect_session_report(
+        &self,
+        storage: &mut dyn AutomodStorage,
+        report_timezone: TimeZone,
+    ) -> Result<Option<SessionReportParameter>, SignalingModuleError> {
+        let started_at = storage
+            .config_get(self.room)
+            .await?
+            .map(|config| config.started);
+
+        let entries = storage
+            .history_get_entries(self.room, started_at.unwrap_or(DateTime::<Utc>::MIN_UTC))
+            .await?;
+
+        if started_at.is_none() && entries.is_empty() {
+            return Ok(None);
+        }
+
+        let now = Utc::now();
+        let turns = report::collect_turns(entries, now);
+
+        let participants: Vec<ParticipantId> = turns
+            .iter()
+            .map(|turn| turn.participant)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        let display_names: Vec<Option<String>> = storage
+            .get_attribute_for_participants(
+                &participants,
+                GlobalRoomAttributeId {
+                    room: self.room.room_id(),
+                    attribute: DISPLAY_NAME,
+                }
+                .into(),
+            )
+            .await?;
+        let names: BTreeMap<ParticipantId, String> = participants
+            .into_iter()
+            .zip(display_names)
+            .filter_map(|(participant, name)| Some((participant, name?)))
+            .collect();
+
+        Ok(Some(SessionReportParameter::new(
+            started_at,
+            now,
+            report_timezone,
+            &turns,
+            &names,
+        )))
+    }
+
+    /// Generates the session report and stores it as an asset of the room.
+    ///
+    /// The resulting asset is only announced to the requesting participant.
+    async fn export_session_report(
+        &self,
+        ctx: &mut ModuleContext<'_, Self>,
+        parameter: SessionReportParameter,
+    ) -> Result<(), SignalingModuleError> {
+        let timestamp = Local::now().naive_local().format("%Y-%m-%dT%H:%M:%S.%f");
+        let dump_to_path = self
+            .params
+            .report_dump_path
+            .as_deref()
+            .map(|path| path.join(format!("{MODULE_ID}/{timestamp}")));
+
+        let report = self
+            .params
+            .pdf_generation_limiter
+            .run(async {
+                Self::generate_pdf_report(
+                    SESSION_REPORT_TEMPLATE.to_string(),
+                    &parameter,
+                    dump_to_path.as_deref(),
+                    self.params.report_font_path.as_deref(),
+                )
+            })
+            .await;
+
+        let report = match report {
+            Ok(report) => report,
+            Err(e) => {
+                ctx.ws_send(AutomodEvent::Error(Error::Generate));
+                return Err(e);
+            }
+        };
+
+        const ASSET_FILE_KIND: AssetFileKind = asset_file_kind!("automod_session_report");
+        let file_name =
+            NewAssetFileName::new(ASSET_FILE_KIND, Timestamp::now(), FileExtension::pdf());
+        let report =
+            async_stream::stream!(yield Result::<_, ObjectStorageError>::Ok(Bytes::from(report)));
+
+        let result = save_asset(
+            &self.storage,
+            self.db.clone(),
+            self.room.room_id(),
+            Some(Self::NAMESPACE),
+            file_name,
+            Box::pin(report),
+            ChunkFormat::Data,
+        )
+        .await;
+
+        match result {
+            Ok((asset_id, filename)) => {
+                ctx.ws_send(AutomodEvent::PdfAsset(PdfAsset { filename, asset_id }));
+            }
+            Err(AssetError::AssetStorageExceeded) => {
+                tracing::debug!("Storage exceeded while storing automod session report");
+                ctx.ws_send(AutomodEvent::Error(Error::StorageExceeded));
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Error while storing automod session report: {}",
+                    Report::from_error(e)
+                );
+                ctx.ws_send(AutomodEvent::Error(Error::Storage));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn generate_pdf_report(
+        template: String,
+        parameter: &SessionReportParameter,
+        dump_to_path: Option<&Path>,
+        font_path: Option<&Path>,
+    ) -> Result<Vec<u8>, SignalingModuleError> {
+        opentalk_report_generation::generate_pdf_report(
+            template,
+            BTreeMap::from_iter([(
+                Path::new("data.json"),
+                serde_json::to_string_pretty(parameter)
+                    .whatever_context::<_, SignalingModuleError>(
+                        "Failed to serialize session report",
+                    )?
+                    .into_bytes()
+                    .into(),
+            )]),
+            dump_to_path,
+            font_path,
+        )
+        .whatever_context::<_, SignalingModuleError>("Failed to generate session report")
+    }
+
     /// Stops the current session, clearing up stored data.
     /// The storage mutex must be locked when calling this method.
     async fn stop_session(
@@ -754,6 +1195,10 @@ impl Automod {
             storage.allow_list_delete(self.room).await?;
             storage.playlist_delete(self.room).await?;
 
+            if let Some(checkpoint) = &self.checkpoint {
+                checkpoint.delete().await?;
+            }
+
             ctx.exchange_publish(
                 control::exchange::current_room_all_participants(self.room),
                 exchange::Message::Stop(stopped_reason),
@@ -763,6 +1208,37 @@ impl Automod {
         Ok(())
     }
 
+    /// Ends the turn of the current speaker without selecting the next one.
+    ///
+    /// Unlike [`Select::None`] the session stays active and unlike [`Yield`] the selection
+    /// strategy is not executed. The speaker is recorded into the history as completed.
+    /// The storage mutex must be locked when calling this method.
+    async fn skip_speaker(
+        &mut self,
+        ctx: &mut ModuleContext<'_, Self>,
+        config: StorageConfig,
+    ) -> Result<(), SignalingModuleError> {
+        let update =
+            state_machine::select_unchecked(ctx.volatile.storage(), self.room, &config, None)
+                .await
+                .whatever_context::<_, SignalingModuleError>("Failed to skip speaker")?;
+
+        // The speaker's expiry timer is cancelled when its module receives the speaker update,
+        // reset it here as well in case the moderator skipped themselves.
+        self.current_expiry_id = None;
+
+        if let Some(update) = update {
+            self.write_checkpoint(ctx.volatile.storage()).await?;
+
+            ctx.exchange_publish(
+                control::exchange::current_room_all_participants(self.room),
+                exchange::Message::SpeakerUpdate(update),
+            );
+        }
+
+        Ok(())
+    }
+
     /// Unselects the current speaker!!11elf
     async fn select_none(
         &mut self,
@@ -819,13 +1295,11 @@ impl Automod {
         ctx: &mut ModuleContext<'_, Self>,
         config: StorageConfig,
     ) -> Result<(), SignalingModuleError> {
-        let result = state_machine::select_random(
-            ctx.volatile.storage(),
-            self.room,
-            &config,
-            &mut rand::rng(),
-        )
-        .await;
+        let mut rng = self.selection_rng(ctx.volatile.storage(), &config).await?;
+
+        let result =
+            state_machine::select_random(ctx.volatile.storage(), self.room, &config, &mut rng)
+                .await;
 
         self.handle_selection_result(ctx, config, result).await
     }
@@ -837,18 +1311,39 @@ impl Automod {
         config: StorageConfig,
         nominated: Option<ParticipantId>,
     ) -> Result<(), SignalingModuleError> {
+        let mut rng = self.selection_rng(ctx.volatile.storage(), &config).await?;
+
         let result = state_machine::select_next(
             ctx.volatile.storage(),
             self.room,
             &config,
             nominated,
-            &mut rand::rng(),
+            &mut rng,
         )
         .await;
 
         self.handle_selection_result(ctx, config, result).await
     }
 
+    /// Returns the RNG used for random selections.
+    ///
+    /// If the session has a `random_seed`, the RNG is derived from the seed and the number of
+    /// speakers selected so far. The same seed therefore yields the same sequence of random draws
+    /// for the whole session, participants joining or leaving in between do not shift it.
+    async fn selection_rng(
+        &self,
+        storage: &mut dyn AutomodStorage,
+        config: &StorageConfig,
+    ) -> Result<StdRng, SignalingModuleError> {
+        let Some(seed) = config.parameter.random_seed else {
+            return Ok(StdRng::from_rng(&mut rand::rng()));
+        };
+
+        let selections = storage.history_get(self.room, config.started).await?.len() as u64;
+
+        Ok(StdRng::seed_from_u64(seed.wrapping_add(selections)))
+    }
+
     async fn handle_selection_result(
         &mut self,
         ctx: &mut ModuleContext<'_, Self>,
@@ -858,6 +1353,8 @@ impl Automod {
         match result {
             Ok(Some(StateMachineOutput::SpeakerUpdate(update))) => {
                 if update.speaker.is_some() {
+                    self.write_checkpoint(ctx.volatile.storage()).await?;
+
                     ctx.exchange_publish(
                         control::exchange::current_room_all_participants(self.room),
                         exchange::Message::SpeakerUpdate(update),
@@ -875,7 +1372,7 @@ impl Automod {
 
                 let result = start_animation.result;
                 ctx.add_event_stream(once(
-                    sleep(Duration::from_secs(8))
+                    sleep(self.params.animation_duration)
                         .map(move |_| TimerEvent::AnimationEnd(animation_id, result)),
                 ));
 
@@ -889,6 +1386,8 @@ impl Automod {
                 .await
                 .whatever_context::<_, SignalingModuleError>("Failed to reset speaker")?;
 
+                self.write_checkpoint(ctx.volatile.storage()).await?;
+
                 if let Some(update) = update {
                     ctx.exchange_publish(
                         control::exchange::current_room_all_participants(self.room),
@@ -949,12 +1448,7 @@ impl Automod {
                     // Add sleep "stream-future" which indicates the expiration of the speaker status
                     if let Some(time_limit) = config.and_then(|config| config.parameter.time_limit)
                     {
-                        let expiry_id = ExpiryId(Uuid::new_v4());
-                        self.current_expiry_id = Some(expiry_id);
-
-                        ctx.add_event_stream(once(
-                            sleep(time_limit).map(move |_| TimerEvent::Expiry(expiry_id)),
-                        ));
+                        self.schedule_expiry(&mut ctx, self.id, time_limit);
                     }
 
                     ctx.volatile.room_locking().unlock_room(guard).await?;
@@ -985,3 +1479,107 @@ impl Automod {
         Ok(())
     }
 }
+
+/// The time the speaker has left at `now`, if the session has a time limit
+///
+/// The time limit starts with the latest turn of the speaker in the history. If the turn is not
+/// part of the history, the full time limit applies.
+fn speaker_time_left(
+    config: &StorageConfig,
+    history: &[Entry],
+    speaker: ParticipantId,
+    now: DateTime<Utc>,
+) -> Option<Duration> {
+    let time_limit = config.parameter.time_limit?;
+
+    let Some(turn) = history
+        .iter()
+        .rev()
+        .find(|entry| entry.participant == speaker && entry.kind == EntryKind::Start)
+    else {
+        return Some(time_limit);
+    };
+
+    let elapsed = (now - turn.timestamp).to_std().unwrap_or_default();
+
+    Some(time_limit.saturating_sub(elapsed))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::{TimeDelta, Utc};
+    use opentalk_types_signaling::ParticipantId;
+    use opentalk_types_signaling_automod::config::{Parameter, SelectionStrategy};
+    use pretty_assertions::assert_eq;
+
+    use super::speaker_time_left;
+    use crate::storage::{Entry, EntryKind, StorageConfig};
+
+    const ALICE: ParticipantId = ParticipantId::from_u128(0xa11c3);
+    const BOB: ParticipantId = ParticipantId::from_u128(0x808);
+
+    fn config(time_limit: Option<Duration>) -> StorageConfig {
+        StorageConfig {
+            started: Utc::now(),
+            issued_by: ALICE,
+            parameter: Parameter {
+                selection_strategy: SelectionStrategy::Playlist,
+                show_list: Default::default(),
+                consider_hand_raise: Default::default(),
+                time_limit,
+                allow_double_selection: Default::default(),
+                animation_on_random: Default::default(),
+                auto_append_on_join: Default::default(),
+                random_seed: Default::default(),
+            },
+        }
+    }
+
+    #[test]
+    fn speaker_time_left_after_restore() {
+        let now = Utc::now();
+        let entry = |seconds_ago, participant, kind| Entry {
+            timestamp: now - TimeDelta::seconds(seconds_ago),
+            participant,
+            kind,
+        };
+        let history = [
+            entry(100, ALICE, EntryKind::Start),
+            entry(90, ALICE, EntryKind::Stop),
+            entry(90, BOB, EntryKind::Start),
+            entry(80, BOB, EntryKind::Stop),
+            entry(20, ALICE, EntryKind::Start),
+        ];
+        let config = config(Some(Duration::from_secs(60)));
+
+        // The latest turn of the speaker counts
+        assert_eq!(
+            speaker_time_left(&config, &history, ALICE, now),
+            Some(Duration::from_secs(40))
+        );
+
+        // The time limit of an earlier turn has already passed
+        assert_eq!(
+            speaker_time_left(&config, &history[..4], BOB, now),
+            Some(Duration::ZERO)
+        );
+
+        // Without a turn in the history, the full time limit applies
+        assert_eq!(
+            speaker_time_left(&config, &[], ALICE, now),
+            Some(Duration::from_secs(60))
+        );
+    }
+
+    #[test]
+    fn speaker_time_left_without_time_limit() {
+        let history = [Entry::start(ALICE)];
+
+        assert_eq!(
+            speaker_time_left(&config(None), &history, ALICE, Utc::now()),
+            None
+        );
+    }
+}
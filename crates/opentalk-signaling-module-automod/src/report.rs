@@ -0,0 +1,215 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+//! Types used inside the session report template.
+//!
+// IMPORTANT: when changing the structs below, make sure to update the following documentation:
+// * docs/admin/core/automod.md
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
+use opentalk_report_generation::{ReportDateTime, ToReportDateTime};
+use opentalk_types_common::time::TimeZone;
+use opentalk_types_signaling::ParticipantId;
+
+use crate::storage::{Entry, EntryKind};
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SessionReportParameter {
+    /// The start of the exported history, `None` if the history spans multiple sessions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<ReportDateTime>,
+
+    /// The point in time at which the report was generated.
+    pub generated_at: ReportDateTime,
+
+    /// The timezone in which the timestamps in this report are represented.
+    pub report_timezone: TimeZone,
+
+    /// Every turn of a speaker, in the order in which they spoke.
+    pub turns: Vec<SpeakerTurn>,
+
+    /// The accumulated speaking time per participant, longest first.
+    pub speakers: Vec<SpeakerStatistic>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SpeakerTurn {
+    pub participant: ParticipantId,
+
+    pub name: String,
+
+    pub started_at: ReportDateTime,
+
+    /// The speaking time in seconds.
+    pub duration: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SpeakerStatistic {
+    pub participant: ParticipantId,
+
+    pub name: String,
+
+    /// The number of turns the participant had.
+    pub turns: usize,
+
+    /// The accumulated speaking time in seconds.
+    pub duration: i64,
+}
+
+/// A completed or ongoing turn of a speaker, reconstructed from the history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Turn {
+    pub participant: ParticipantId,
+    pub started_at: DateTime<Utc>,
+    pub ended_at: DateTime<Utc>,
+}
+
+/// Reconstructs the speaker turns from the history entries.
+///
+/// A turn that has not been stopped yet is considered to last until `now`.
+pub(crate) fn collect_turns(mut entries: Vec<Entry>, now: DateTime<Utc>) -> Vec<Turn> {
+    entries.sort_by_key(|entry| entry.timestamp);
+
+    let mut running: HashMap<ParticipantId, DateTime<Utc>> = HashMap::new();
+    let mut turns = Vec::new();
+
+    for entry in entries {
+        match entry.kind {
+            EntryKind::Start => {
+                running.entry(entry.participant).or_insert(entry.timestamp);
+            }
+            EntryKind::Stop => {
+                if let Some(started_at) = running.remove(&entry.participant) {
+                    turns.push(Turn {
+                        participant: entry.participant,
+                        started_at,
+                        ended_at: entry.timestamp,
+                    });
+                }
+            }
+        }
+    }
+
+    turns.extend(running.into_iter().map(|(participant, started_at)| Turn {
+        participant,
+        started_at,
+        ended_at: now,
+    }));
+    turns.sort_by_key(|turn| turn.started_at);
+
+    turns
+}
+
+impl SessionReportParameter {
+    pub(crate) fn new(
+        started_at: Option<DateTime<Utc>>,
+        generated_at: DateTime<Utc>,
+        report_timezone: TimeZone,
+        turns: &[Turn],
+        names: &BTreeMap<ParticipantId, String>,
+    ) -> Self {
+        let tz = Tz::from(report_timezone);
+        let name_of = |participant: &ParticipantId| {
+            names
+                .get(participant)
+                .cloned()
+                .unwrap_or_else(|| participant.to_string())
+        };
+
+        let mut statistics: BTreeMap<ParticipantId, (usize, i64)> = BTreeMap::new();
+        for turn in turns {
+            let (count, duration) = statistics.entry(turn.participant).or_default();
+            *count += 1;
+            *duration += (turn.ended_at - turn.started_at).num_seconds();
+        }
+
+        let mut speakers: Vec<SpeakerStatistic> = statistics
+            .into_iter()
+            .map(|(participant, (turns, duration))| SpeakerStatistic {
+                participant,
+                name: name_of(&participant),
+                turns,
+                duration,
+            })
+            .collect();
+        speakers.sort_by(|a, b| b.duration.cmp(&a.duration));
+
+        Self {
+            started_at: started_at.to_report_date_time(&tz),
+            generated_at: generated_at.to_report_date_time(&tz),
+            report_timezone,
+            turns: turns
+                .iter()
+                .map(|turn| SpeakerTurn {
+                    participant: turn.participant,
+                    name: name_of(&turn.participant),
+                    started_at: turn.started_at.to_report_date_time(&tz),
+                    duration: (turn.ended_at - turn.started_at).num_seconds(),
+                })
+                .collect(),
+            speakers,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, Utc};
+    use opentalk_types_signaling::ParticipantId;
+    use pretty_assertions::assert_eq;
+
+    use super::{Turn, collect_turns};
+    use crate::storage::{Entry, EntryKind};
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(secs, 0).unwrap()
+    }
+
+    fn entry(participant: ParticipantId, kind: EntryKind, secs: i64) -> Entry {
+        Entry {
+            timestamp: at(secs),
+            participant,
+            kind,
+        }
+    }
+
+    #[test]
+    fn turns_from_history() {
+        let alice = ParticipantId::from_u128(1);
+        let bob = ParticipantId::from_u128(2);
+
+        let entries = vec![
+            entry(alice, EntryKind::Stop, 30),
+            entry(alice, EntryKind::Start, 0),
+            entry(bob, EntryKind::Start, 30),
+            entry(bob, EntryKind::Stop, 50),
+            entry(alice, EntryKind::Start, 50),
+        ];
+
+        assert_eq!(
+            collect_turns(entries, at(80)),
+            vec![
+                Turn {
+                    participant: alice,
+                    started_at: at(0),
+                    ended_at: at(30),
+                },
+                Turn {
+                    participant: bob,
+                    started_at: at(30),
+                    ended_at: at(50),
+                },
+                Turn {
+                    participant: alice,
+                    started_at: at(50),
+                    ended_at: at(80),
+                },
+            ]
+        );
+    }
+}
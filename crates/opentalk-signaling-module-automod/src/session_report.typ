@@ -0,0 +1,92 @@
+#set page(
+  paper: "a4",
+)
+#set text(
+  size: 10pt,
+)
+
+#let data = json("data.json")
+#let parse_datetime(s) = toml.decode("date = " + s).date
+#let datetime_format = "[year]-[month]-[day] [hour]:[minute]:[second]"
+#let format_duration(seconds) = {
+  let minutes = calc.quo(seconds, 60)
+  let rest = calc.rem(seconds, 60)
+  str(minutes) + ":" + if rest < 10 { "0" } + str(rest)
+}
+
+= Speaker Session Report
+
+#let metadata_table_content = ()
+
+#if "started_at" in data {
+  metadata_table_content.push((
+    [Session start],
+    [ #parse_datetime(data.started_at).display(datetime_format) ]
+  ))
+}
+
+#metadata_table_content.push((
+  [Generated],
+  [ #parse_datetime(data.generated_at).display(datetime_format) ]
+))
+
+#metadata_table_content.push((
+  [Report timezone],
+  data.report_timezone
+))
+
+#table(
+  stroke: none,
+  columns: 2,
+  ..for (name, content) in metadata_table_content {
+    ([*#name*:], [#content])
+  }
+)
+
+#set table.hline(stroke: 0.5pt + rgb("bfbfbf"))
+
+== Speaking Time
+
+#table(
+  stroke: none,
+  columns: (auto, 1fr, auto, auto),
+  table.header(
+    [*Nr*],
+    [*Name*],
+    [*Turns*],
+    [*Duration*],
+  ),
+  table.hline(y: 0),
+  table.hline(y: 1),
+  ..for (i, speaker) in data.speakers.enumerate(start: 1) {
+    (
+      [#i],
+      [#speaker.name],
+      [#speaker.turns],
+      [#format_duration(speaker.duration)],
+    )
+  }
+)
+
+== Speakers
+
+#table(
+  stroke: none,
+  columns: (auto, 1fr, auto, auto),
+  table.header(
+    [*Nr*],
+    [*Name*],
+    [*Start*],
+    [*Duration*],
+  ),
+  table.hline(y: 0),
+  table.hline(y: 1),
+  ..for (i, turn) in data.turns.enumerate(start: 1) {
+    (
+      [#i],
+      [#turn.name],
+      [#parse_datetime(turn.started_at).display(datetime_format)],
+      [#format_duration(turn.duration)],
+    )
+  }
+)
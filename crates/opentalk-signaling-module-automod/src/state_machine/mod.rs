@@ -23,6 +23,12 @@ mod random;
 pub use next::select_next;
 pub use random::select_random;
 
+/// Upper bound of candidates checked for presence during a single selection.
+///
+/// Every absent candidate is removed from the remaining participants, so the selection ends
+/// naturally once the list is drained. The bound only guards against storage inconsistencies.
+const MAX_SELECTION_ATTEMPTS: usize = 64;
+
 /// Error returned by the state machine
 #[derive(Debug, Snafu)]
 pub enum Error {
@@ -62,6 +68,24 @@ pub fn map_select_unchecked(
     output.map(|opt| opt.map(StateMachineOutput::SpeakerUpdate))
 }
 
+/// Removes a participant who is no longer present in the room from the remaining participants.
+async fn remove_absent(
+    storage: &mut dyn AutomodStorage,
+    room: SignalingRoomId,
+    config: &StorageConfig,
+    participant: ParticipantId,
+) -> Result<(), Error> {
+    if config.parameter.selection_strategy.uses_allow_list() {
+        storage.allow_list_remove(room, participant).await?;
+    } else {
+        storage
+            .playlist_remove_all_occurrences(room, participant)
+            .await?;
+    }
+
+    Ok(())
+}
+
 /// Selects the given participant (or None) as the current speaker and generates the appropriate
 /// [`SpeakerUpdate`] if necessary.
 /// Does not check if the participant exists or is even eligible to be speaker.
@@ -120,10 +144,11 @@ mod test {
 
     use chrono::{DateTime, Utc};
     use opentalk_signaling_core::{RedisConnection, SignalingRoomId, VolatileStaticMemoryStorage};
+    use opentalk_types_signaling::ParticipantId;
     use rand::{SeedableRng, rngs::StdRng};
     use redis::aio::ConnectionManager;
 
-    use crate::storage::reset_memory_state;
+    use crate::storage::{AutomodStorage, reset_memory_state};
 
     pub const ROOM: SignalingRoomId = SignalingRoomId::nil();
 
@@ -144,6 +169,15 @@ mod test {
         VolatileStaticMemoryStorage
     }
 
+    pub async fn join(storage: &mut dyn AutomodStorage, participants: &[ParticipantId]) {
+        for participant in participants {
+            storage
+                .add_participant_to_set(ROOM, *participant)
+                .await
+                .unwrap();
+        }
+    }
+
     pub fn rng() -> StdRng {
         StdRng::seed_from_u64(0)
     }
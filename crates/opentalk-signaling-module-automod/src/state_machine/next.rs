@@ -27,7 +27,7 @@ pub async fn select_next<R: Rng>(
         Parameter {
             selection_strategy: SelectionStrategy::Playlist,
             ..
-        } => storage.playlist_pop(room).await?,
+        } => select_next_playlist(storage, room).await?,
         Parameter {
             selection_strategy: SelectionStrategy::Nomination,
             allow_double_selection,
@@ -42,6 +42,26 @@ pub async fn select_next<R: Rng>(
     super::map_select_unchecked(super::select_unchecked(storage, room, config, participant).await)
 }
 
+/// Returns the next (if any) participant of the playlist who is still present in the room.
+///
+/// Absent participants are popped from the playlist and skipped.
+async fn select_next_playlist(
+    storage: &mut dyn AutomodStorage,
+    room: SignalingRoomId,
+) -> Result<Option<ParticipantId>, Error> {
+    for _ in 0..super::MAX_SELECTION_ATTEMPTS {
+        let Some(participant) = storage.playlist_pop(room).await? else {
+            return Ok(None);
+        };
+
+        if storage.participants_contains(room, participant).await? {
+            return Ok(Some(participant));
+        }
+    }
+
+    Ok(None)
+}
+
 /// Returns the next (if any) participant to be selected inside a `Nomination` selection strategy.
 async fn select_next_nomination(
     storage: &mut dyn AutomodStorage,
@@ -90,7 +110,7 @@ mod test {
     use crate::{
         state_machine::{
             self, exchange,
-            test::{ROOM, rng, setup_memory, setup_redis, unix_epoch},
+            test::{ROOM, join, rng, setup_memory, setup_redis, unix_epoch},
         },
         storage::Entry,
     };
@@ -124,6 +144,7 @@ mod test {
         let p3 = ParticipantId::from_u128(3);
 
         storage.allow_list_set(ROOM, &[p1, p2, p3]).await.unwrap();
+        join(storage, &[p1, p2, p3]).await;
 
         let config = StorageConfig {
             started: unix_epoch(0),
@@ -136,6 +157,7 @@ mod test {
                 allow_double_selection: false,
                 animation_on_random: true,
                 auto_append_on_join: false,
+                random_seed: None,
             },
         };
 
@@ -168,6 +190,7 @@ mod test {
         // Add current speaker
         storage.history_add(ROOM, Entry::start(p1)).await.unwrap();
         storage.allow_list_set(ROOM, &[p1]).await.unwrap();
+        join(storage, &[p1]).await;
         storage.speaker_set(ROOM, p1).await.unwrap();
 
         let config = StorageConfig {
@@ -181,6 +204,7 @@ mod test {
                 allow_double_selection: true,
                 animation_on_random: false,
                 auto_append_on_join: false,
+                random_seed: None,
             },
         };
 
@@ -223,6 +247,7 @@ mod test {
                 allow_double_selection: false,
                 animation_on_random: false,
                 auto_append_on_join: false,
+                random_seed: None,
             },
         };
 
@@ -283,6 +308,7 @@ mod test {
         let p3 = ParticipantId::from_u128(3);
 
         storage.allow_list_set(ROOM, &[p1, p2]).await.unwrap();
+        join(storage, &[p1, p2]).await;
 
         let config = StorageConfig {
             started: unix_epoch(0),
@@ -295,6 +321,7 @@ mod test {
                 allow_double_selection: false,
                 animation_on_random: false,
                 auto_append_on_join: false,
+                random_seed: None,
             },
         };
         // Check allowed participant
@@ -383,6 +410,7 @@ mod test {
                 allow_double_selection: false,
                 animation_on_random: false,
                 auto_append_on_join: false,
+                random_seed: None,
             },
         };
 
@@ -427,6 +455,7 @@ mod test {
         let p3 = ParticipantId::from_u128(3);
 
         storage.allow_list_set(ROOM, &[p1, p2, p3]).await.unwrap();
+        join(storage, &[p1, p2, p3]).await;
 
         let config = StorageConfig {
             started: unix_epoch(0),
@@ -439,6 +468,7 @@ mod test {
                 allow_double_selection: false,
                 animation_on_random: false,
                 auto_append_on_join: false,
+                random_seed: None,
             },
         };
 
@@ -524,6 +554,7 @@ mod test {
                 allow_double_selection: false,
                 animation_on_random: false,
                 auto_append_on_join: false,
+                random_seed: None,
             },
         };
 
@@ -607,6 +638,7 @@ mod test {
                 allow_double_selection: false,
                 animation_on_random: false,
                 auto_append_on_join: false,
+                random_seed: None,
             },
         };
 
@@ -631,6 +663,7 @@ mod test {
 
         // Create playlist
         storage.playlist_set(ROOM, &[p2, p1, p3]).await.unwrap();
+        join(storage, &[p2, p1, p3]).await;
 
         // select_next with empty history and playlist
         let next = select_next(storage, ROOM, &config, None, &mut rng)
@@ -736,4 +769,71 @@ mod test {
             }))
         );
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn select_next_with_absent_playlist_redis() {
+        select_next_with_absent_playlist(&mut setup_redis().await).await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn select_next_with_absent_playlist_memory() {
+        select_next_with_absent_playlist(&mut setup_memory().await).await;
+    }
+
+    /// Test that next skips playlist entries who are no longer present in the room
+    async fn select_next_with_absent_playlist(storage: &mut dyn AutomodStorage) {
+        let mut rng = rng();
+
+        let p1 = ParticipantId::from_u128(11);
+        let p2 = ParticipantId::from_u128(12);
+        let p3 = ParticipantId::from_u128(13);
+
+        storage.remove_participant_set(ROOM).await.unwrap();
+        storage.playlist_set(ROOM, &[p1, p2, p3]).await.unwrap();
+        join(storage, &[p2]).await;
+
+        let config = StorageConfig {
+            started: unix_epoch(0),
+            issued_by: p2,
+            parameter: Parameter {
+                selection_strategy: SelectionStrategy::Playlist,
+                show_list: false,
+                consider_hand_raise: false,
+                time_limit: None,
+                allow_double_selection: false,
+                animation_on_random: false,
+                auto_append_on_join: false,
+                random_seed: None,
+            },
+        };
+
+        // p1 is absent and skipped
+        let next = select_next(storage, ROOM, &config, None, &mut rng)
+            .await
+            .unwrap();
+        assert_eq!(
+            next,
+            Some(StateMachineOutput::SpeakerUpdate(exchange::SpeakerUpdate {
+                speaker: Some(p2),
+                history: Some(vec![p2]),
+                remaining: Some(vec![p3])
+            }))
+        );
+
+        // Only the absent p3 is remaining, which ends the selection
+        let next = select_next(storage, ROOM, &config, None, &mut rng)
+            .await
+            .unwrap();
+        assert_eq!(
+            next,
+            Some(StateMachineOutput::SpeakerUpdate(exchange::SpeakerUpdate {
+                speaker: None,
+                history: Some(vec![p2]),
+                remaining: Some(vec![])
+            }))
+        );
+        assert_eq!(storage.speaker_get(ROOM).await.unwrap(), None);
+    }
 }
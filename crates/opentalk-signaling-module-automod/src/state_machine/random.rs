@@ -3,8 +3,12 @@
 // SPDX-License-Identifier: EUPL-1.2
 
 use opentalk_signaling_core::SignalingRoomId;
+use opentalk_types_signaling::ParticipantId;
 use opentalk_types_signaling_automod::config::{Parameter, SelectionStrategy};
-use rand::{Rng, seq::IndexedRandom};
+use rand::{
+    Rng,
+    seq::{IndexedRandom, IteratorRandom},
+};
 
 use super::{Error, StateMachineOutput};
 use crate::{
@@ -14,12 +18,49 @@ use crate::{
 
 /// Depending on the config will select a random participant to be speaker. This may be used when
 /// the selection_strategy ist `random` or a moderator issues a `Select::Random` command.
+///
+/// Candidates who are no longer present in the room are removed from the remaining participants
+/// and another one is drawn. If no present participant is left, the speaker is unset.
 pub async fn select_random<R: Rng>(
     storage: &mut dyn AutomodStorage,
     room: SignalingRoomId,
     config: &StorageConfig,
     rng: &mut R,
 ) -> Result<Option<StateMachineOutput>, Error> {
+    let mut participant = None;
+
+    for _ in 0..super::MAX_SELECTION_ATTEMPTS {
+        let candidate = match pick_random(storage, room, config, rng).await? {
+            Pick::Animation(start_animation) => {
+                return Ok(Some(StateMachineOutput::StartAnimation(start_animation)));
+            }
+            Pick::Participant(Some(candidate)) => candidate,
+            Pick::Participant(None) => break,
+        };
+
+        if storage.participants_contains(room, candidate).await? {
+            participant = Some(candidate);
+            break;
+        }
+
+        super::remove_absent(storage, room, config, candidate).await?;
+    }
+
+    super::map_select_unchecked(super::select_unchecked(storage, room, config, participant).await)
+}
+
+/// Result of a single random draw
+enum Pick {
+    Animation(exchange::StartAnimation),
+    Participant(Option<ParticipantId>),
+}
+
+async fn pick_random<R: Rng>(
+    storage: &mut dyn AutomodStorage,
+    room: SignalingRoomId,
+    config: &StorageConfig,
+    rng: &mut R,
+) -> Result<Pick, Error> {
     let participant = match &config.parameter {
         Parameter {
             selection_strategy:
@@ -28,11 +69,7 @@ pub async fn select_random<R: Rng>(
             ..
         } => {
             if config.parameter.animation_on_random {
-                let pool: Vec<opentalk_types_signaling::ParticipantId> = storage
-                    .allow_list_get_all(room)
-                    .await?
-                    .into_iter()
-                    .collect();
+                let pool = present_allow_list(storage, room, config).await?;
 
                 // Special case: only one participant in pool -> skip animation
                 if let [participant_id] = pool[..] {
@@ -47,13 +84,27 @@ pub async fn select_random<R: Rng>(
                     let selection = pool.choose(rng).copied();
 
                     if let Some(result) = selection {
-                        return Ok(Some(StateMachineOutput::StartAnimation(
-                            exchange::StartAnimation { pool, result },
-                        )));
+                        return Ok(Pick::Animation(exchange::StartAnimation { pool, result }));
                     } else {
                         None
                     }
                 }
+            } else if config.parameter.random_seed.is_some() {
+                // The storage picks random members with its own RNG, choose from the ordered
+                // allow_list instead to keep seeded sessions reproducible
+                let participant = storage
+                    .allow_list_get_all(room)
+                    .await?
+                    .into_iter()
+                    .choose(rng);
+
+                if let Some(participant) = participant {
+                    if !allow_double_selection {
+                        storage.allow_list_remove(room, participant).await?;
+                    }
+                }
+
+                participant
             } else if *allow_double_selection {
                 // GET RANDOM MEMBER FROM ALLOW_LIST
                 storage.allow_list_random(room).await?
@@ -79,12 +130,33 @@ pub async fn select_random<R: Rng>(
         }
     };
 
-    super::map_select_unchecked(super::select_unchecked(storage, room, config, participant).await)
+    Ok(Pick::Participant(participant))
+}
+
+/// Returns the allow_list without the participants who are no longer present in the room.
+/// Absent participants are removed from the allow_list.
+async fn present_allow_list(
+    storage: &mut dyn AutomodStorage,
+    room: SignalingRoomId,
+    config: &StorageConfig,
+) -> Result<Vec<ParticipantId>, Error> {
+    let mut pool = Vec::new();
+
+    for participant in storage.allow_list_get_all(room).await? {
+        if storage.participants_contains(room, participant).await? {
+            pool.push(participant);
+        } else {
+            super::remove_absent(storage, room, config, participant).await?;
+        }
+    }
+
+    Ok(pool)
 }
 
 #[cfg(test)]
 mod test {
-    use opentalk_types_signaling::ParticipantId;
+    use std::collections::BTreeSet;
+
     use pretty_assertions::{assert_eq, assert_ne};
     use serial_test::serial;
 
@@ -92,7 +164,7 @@ mod test {
     use crate::{
         state_machine::{
             StateMachineOutput,
-            test::{ROOM, rng, setup_memory, setup_redis, unix_epoch},
+            test::{ROOM, join, rng, setup_memory, setup_redis, unix_epoch},
         },
         storage::{Entry, EntryKind},
     };
@@ -203,6 +275,7 @@ mod test {
         let p3 = ParticipantId::from_u128(3);
 
         storage.allow_list_set(ROOM, &[p1, p2, p3]).await.unwrap();
+        join(storage, &[p1, p2, p3]).await;
 
         let config = StorageConfig {
             started: unix_epoch(0),
@@ -215,6 +288,7 @@ mod test {
                 allow_double_selection: false,
                 animation_on_random: false,
                 auto_append_on_join: false,
+                random_seed: None,
             },
         };
 
@@ -289,6 +363,7 @@ mod test {
         let p3 = ParticipantId::from_u128(3);
 
         storage.allow_list_set(ROOM, &[p1, p2, p3]).await.unwrap();
+        join(storage, &[p1, p2, p3]).await;
 
         let config = StorageConfig {
             started: unix_epoch(0),
@@ -301,6 +376,7 @@ mod test {
                 allow_double_selection: false,
                 animation_on_random: true,
                 auto_append_on_join: false,
+                random_seed: None,
             },
         };
 
@@ -312,6 +388,69 @@ mod test {
         ));
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn seeded_selection_is_reproducible_redis() {
+        seeded_selection_is_reproducible(&mut setup_redis().await).await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn seeded_selection_is_reproducible_memory() {
+        seeded_selection_is_reproducible(&mut setup_memory().await).await;
+    }
+
+    /// Tests that two sessions with the same seed select the speakers in the same order
+    async fn seeded_selection_is_reproducible(storage: &mut dyn AutomodStorage) {
+        let p1 = ParticipantId::from_u128(1);
+        let p2 = ParticipantId::from_u128(2);
+        let p3 = ParticipantId::from_u128(3);
+        let p4 = ParticipantId::from_u128(4);
+
+        let config = StorageConfig {
+            started: unix_epoch(0),
+            issued_by: p1,
+            parameter: Parameter {
+                selection_strategy: SelectionStrategy::Random,
+                show_list: false,
+                consider_hand_raise: false,
+                time_limit: None,
+                allow_double_selection: false,
+                animation_on_random: false,
+                auto_append_on_join: false,
+                random_seed: Some(42),
+            },
+        };
+
+        let mut orders = Vec::new();
+
+        for _ in 0..2 {
+            storage
+                .allow_list_set(ROOM, &[p1, p2, p3, p4])
+                .await
+                .unwrap();
+            join(storage, &[p1, p2, p3, p4]).await;
+
+            let mut rng = rng();
+            let mut order = Vec::new();
+
+            for _ in 0..4 {
+                select_random(storage, ROOM, &config, &mut rng)
+                    .await
+                    .unwrap();
+
+                order.push(storage.speaker_get(ROOM).await.unwrap().unwrap());
+            }
+
+            orders.push(order);
+
+            storage.speaker_delete(ROOM).await.unwrap();
+            storage.history_delete(ROOM).await.unwrap();
+        }
+
+        assert_eq!(orders[0], orders[1]);
+    }
+
     #[tokio::test]
     #[serial]
     async fn select_random_when_none_redis() {
@@ -335,6 +474,7 @@ mod test {
         let p3 = ParticipantId::from_u128(3);
 
         storage.allow_list_set(ROOM, &[p1, p2, p3]).await.unwrap();
+        join(storage, &[p1, p2, p3]).await;
 
         let config = StorageConfig {
             started: unix_epoch(0),
@@ -347,6 +487,7 @@ mod test {
                 allow_double_selection: false,
                 animation_on_random: false,
                 auto_append_on_join: false,
+                random_seed: None,
             },
         };
 
@@ -382,6 +523,7 @@ mod test {
         let p3 = ParticipantId::from_u128(3);
 
         storage.playlist_set(ROOM, &[p1, p2, p3]).await.unwrap();
+        join(storage, &[p1, p2, p3]).await;
 
         storage
             .history_add(
@@ -418,6 +560,7 @@ mod test {
                 allow_double_selection: false,
                 animation_on_random: false,
                 auto_append_on_join: false,
+                random_seed: None,
             },
         };
 
@@ -481,6 +624,7 @@ mod test {
         let p3 = ParticipantId::from_u128(3);
 
         storage.allow_list_set(ROOM, &[p1, p2, p3]).await.unwrap();
+        join(storage, &[p1, p2, p3]).await;
 
         storage
             .history_add(
@@ -517,6 +661,7 @@ mod test {
                 allow_double_selection: true,
                 animation_on_random: false,
                 auto_append_on_join: false,
+                random_seed: None,
             },
         };
 
@@ -539,4 +684,73 @@ mod test {
 
         panic!("selected did not contain any duplicates ???")
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn select_random_skips_absent_redis() {
+        select_random_skips_absent(&mut setup_redis().await).await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn select_random_skips_absent_memory() {
+        select_random_skips_absent(&mut setup_memory().await).await;
+    }
+
+    /// Test that absent participants are removed from the allow_list and never selected, ending
+    /// the selection once nobody present is left
+    async fn select_random_skips_absent(storage: &mut dyn AutomodStorage) {
+        let mut rng = rng();
+
+        let p1 = ParticipantId::from_u128(11);
+        let p2 = ParticipantId::from_u128(12);
+        let p3 = ParticipantId::from_u128(13);
+
+        storage.remove_participant_set(ROOM).await.unwrap();
+        storage.allow_list_set(ROOM, &[p1, p2, p3]).await.unwrap();
+        join(storage, &[p2]).await;
+
+        let config = StorageConfig {
+            started: unix_epoch(0),
+            issued_by: p2,
+            parameter: Parameter {
+                selection_strategy: SelectionStrategy::Random,
+                show_list: false,
+                consider_hand_raise: false,
+                time_limit: None,
+                allow_double_selection: true,
+                animation_on_random: false,
+                auto_append_on_join: false,
+                random_seed: None,
+            },
+        };
+
+        select_random(storage, ROOM, &config, &mut rng)
+            .await
+            .unwrap();
+
+        assert_eq!(storage.speaker_get(ROOM).await.unwrap(), Some(p2));
+        assert_eq!(
+            storage.allow_list_get_all(ROOM).await.unwrap(),
+            BTreeSet::from([p2])
+        );
+
+        // Everyone in the allow_list is gone
+        storage.remove_participant_set(ROOM).await.unwrap();
+
+        let output = select_random(storage, ROOM, &config, &mut rng)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            output,
+            Some(StateMachineOutput::SpeakerUpdate(exchange::SpeakerUpdate {
+                speaker: None,
+                history: Some(vec![p2]),
+                remaining: Some(vec![]),
+            }))
+        );
+        assert_eq!(storage.speaker_get(ROOM).await.unwrap(), None);
+        assert!(storage.allow_list_get_all(ROOM).await.unwrap().is_empty());
+    }
 }
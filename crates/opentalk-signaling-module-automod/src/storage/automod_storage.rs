@@ -8,7 +8,7 @@ use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use opentalk_signaling_core::{
     RoomLocking, SignalingModuleError, SignalingRoomId,
-    control::storage::ControlStorageParticipantSet,
+    control::storage::{ControlStorageParticipantAttributesRaw, ControlStorageParticipantSet},
 };
 use opentalk_types_signaling::ParticipantId;
 use redis_args::{FromRedisValue, ToRedisArgs};
@@ -25,6 +25,7 @@ pub(crate) trait AutomodStorage:
     + AutomodSpeakerStorage
     + AutomodHistoryStorage
     + ControlStorageParticipantSet
+    + ControlStorageParticipantAttributesRaw
 {
 }
 
@@ -198,7 +199,7 @@ pub(crate) trait AutomodHistoryStorage {
     /// Delete the history.
     async fn history_delete(&mut self, room: SignalingRoomId) -> Result<(), SignalingModuleError>;
 
-    #[cfg(test)]
+    /// Get all history entries, including stop entries, after the given `since` timestamp.
     async fn history_get_entries(
         &mut self,
         room: SignalingRoomId,
@@ -151,6 +151,7 @@ pub(crate) mod test_common {
                 allow_double_selection: Default::default(),
                 animation_on_random: Default::default(),
                 auto_append_on_join: Default::default(),
+                random_seed: Default::default(),
             },
         };
         storage.config_set(ROOM, config.clone()).await.unwrap();
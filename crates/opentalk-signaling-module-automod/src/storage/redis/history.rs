@@ -77,7 +77,6 @@ impl AutomodHistoryStorage for RedisConnection {
             })
     }
 
-    #[cfg(test)]
     async fn history_get_entries(
         &mut self,
         room: SignalingRoomId,
@@ -233,7 +233,6 @@ impl MemoryAutomodState {
         self.histories.remove(&room);
     }
 
-    #[cfg(test)]
     pub(crate) fn history_get_entries(
         &self,
         room: SignalingRoomId,
@@ -301,7 +301,6 @@ impl AutomodHistoryStorage for VolatileStaticMemoryStorage {
         Ok(())
     }
 
-    #[cfg(test)]
     async fn history_get_entries(
         &mut self,
         room: SignalingRoomId,
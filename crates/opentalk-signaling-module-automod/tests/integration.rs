@@ -2,9 +2,15 @@
 //
 // SPDX-License-Identifier: EUPL-1.2
 
-use opentalk_signaling_core::module_tester::WsMessageOutgoing;
+use std::time::Duration;
+
+use opentalk_db_storage::module_resources::{Filter, ModuleResource};
+use opentalk_signaling_core::{
+    SignalingModule, SignalingRoomId,
+    module_tester::{ModuleTester, WsMessageOutgoing},
+};
 use opentalk_signaling_module_automod as automod;
-use opentalk_test_util::{TestContext, TestUser, USER_1, USER_2, common};
+use opentalk_test_util::{ROOM_ID, TestContext, TestUser, USER_1, USER_2, common};
 use opentalk_types_signaling::{ParticipantId, Role};
 use opentalk_types_signaling_automod::{
     command::{AutomodCommand, Edit, Select, Start, Yield},
@@ -13,6 +19,7 @@ use opentalk_types_signaling_automod::{
 };
 use opentalk_types_signaling_control::event::ControlEvent;
 use pretty_assertions::assert_eq;
+use serde_json::json;
 use serial_test::serial;
 
 #[actix_rt::test]
@@ -20,7 +27,7 @@ use serial_test::serial;
 async fn reject_start_empty_allow_or_playlist() {
     let test_ctx = TestContext::default().await;
     let (mut module_tester, _user1, _user2) =
-        common::setup_users::<automod::Automod>(&test_ctx, ()).await;
+        common::setup_users::<automod::Automod>(&test_ctx, automod::AutomodParams::default()).await;
 
     module_tester
         .send_ws_message(
@@ -34,6 +41,7 @@ async fn reject_start_empty_allow_or_playlist() {
                     allow_double_selection: false,
                     animation_on_random: true,
                     auto_append_on_join: false,
+                    random_seed: None,
                 },
                 allow_list: None,
                 playlist: None,
@@ -60,7 +68,7 @@ async fn reject_start_empty_allow_or_playlist() {
 async fn reject_start_invalid_allow_list() {
     let test_ctx = TestContext::default().await;
     let (mut module_tester, _user1, _user2) =
-        common::setup_users::<automod::Automod>(&test_ctx, ()).await;
+        common::setup_users::<automod::Automod>(&test_ctx, automod::AutomodParams::default()).await;
 
     module_tester
         .send_ws_message(
@@ -74,6 +82,7 @@ async fn reject_start_invalid_allow_list() {
                     allow_double_selection: false,
                     animation_on_random: true,
                     auto_append_on_join: false,
+                    random_seed: None,
                 },
                 // Add the invalid user
                 allow_list: Some(vec![
@@ -104,7 +113,7 @@ async fn reject_start_invalid_allow_list() {
 async fn reject_start_invalid_allow_list_with_some_correct() {
     let test_ctx = TestContext::default().await;
     let (mut module_tester, _user1, _user2) =
-        common::setup_users::<automod::Automod>(&test_ctx, ()).await;
+        common::setup_users::<automod::Automod>(&test_ctx, automod::AutomodParams::default()).await;
 
     module_tester
         .send_ws_message(
@@ -118,6 +127,7 @@ async fn reject_start_invalid_allow_list_with_some_correct() {
                     allow_double_selection: false,
                     animation_on_random: true,
                     auto_append_on_join: false,
+                    random_seed: None,
                 },
                 // Add the invalid user
                 allow_list: Some(vec![
@@ -150,7 +160,7 @@ async fn reject_start_invalid_allow_list_with_some_correct() {
 async fn reject_start_if_session_already_running() {
     let test_ctx = TestContext::default().await;
     let (mut module_tester, _user1, _user2) =
-        common::setup_users::<automod::Automod>(&test_ctx, ()).await;
+        common::setup_users::<automod::Automod>(&test_ctx, automod::AutomodParams::default()).await;
 
     module_tester
         .send_ws_message(
@@ -164,6 +174,7 @@ async fn reject_start_if_session_already_running() {
                     allow_double_selection: false,
                     animation_on_random: true,
                     auto_append_on_join: false,
+                    random_seed: None,
                 },
                 // Add valid users
                 allow_list: Some(vec![USER_1.participant_id, USER_2.participant_id]),
@@ -195,6 +206,7 @@ async fn reject_start_if_session_already_running() {
                     allow_double_selection: false,
                     animation_on_random: true,
                     auto_append_on_join: false,
+                    random_seed: None,
                 },
                 // Add valid users
                 allow_list: Some(vec![USER_1.participant_id, USER_2.participant_id]),
@@ -217,12 +229,213 @@ async fn reject_start_if_session_already_running() {
     module_tester.shutdown().await.unwrap();
 }
 
+#[actix_rt::test]
+#[serial]
+async fn reject_start_with_overlong_time_limit() {
+    let test_ctx = TestContext::default().await;
+    let params = automod::AutomodParams {
+        max_time_limit: Duration::from_secs(300),
+        ..Default::default()
+    };
+    let (mut module_tester, _user1, _user2) =
+        common::setup_users::<automod::Automod>(&test_ctx, params).await;
+
+    module_tester
+        .send_ws_message(
+            &USER_1.participant_id,
+            AutomodCommand::Start(Start {
+                parameter: Parameter {
+                    selection_strategy: SelectionStrategy::Random,
+                    show_list: true,
+                    consider_hand_raise: false,
+                    time_limit: Some(Duration::from_secs(301)),
+                    allow_double_selection: false,
+                    animation_on_random: true,
+                    auto_append_on_join: false,
+                    random_seed: None,
+                },
+                allow_list: Some(vec![USER_1.participant_id, USER_2.participant_id]),
+                playlist: None,
+            }),
+        )
+        .unwrap();
+
+    let answer = module_tester
+        .receive_ws_message(&USER_1.participant_id)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        answer,
+        WsMessageOutgoing::Module(AutomodEvent::Error(Error::InvalidConfiguration))
+    );
+
+    module_tester.shutdown().await.unwrap();
+}
+
+#[actix_rt::test]
+#[serial]
+async fn accept_start_with_maximum_time_limit() {
+    let test_ctx = TestContext::default().await;
+    let params = automod::AutomodParams {
+        max_time_limit: Duration::from_secs(300),
+        ..Default::default()
+    };
+    let (mut module_tester, _user1, _user2) =
+        common::setup_users::<automod::Automod>(&test_ctx, params).await;
+
+    module_tester
+        .send_ws_message(
+            &USER_1.participant_id,
+            AutomodCommand::Start(Start {
+                parameter: Parameter {
+                    selection_strategy: SelectionStrategy::Random,
+                    show_list: true,
+                    consider_hand_raise: false,
+                    time_limit: Some(Duration::from_secs(300)),
+                    allow_double_selection: false,
+                    animation_on_random: true,
+                    auto_append_on_join: false,
+                    random_seed: None,
+                },
+                allow_list: Some(vec![USER_1.participant_id, USER_2.participant_id]),
+                playlist: None,
+            }),
+        )
+        .unwrap();
+
+    let answer = module_tester
+        .receive_ws_message(&USER_1.participant_id)
+        .await
+        .unwrap();
+
+    if let WsMessageOutgoing::Module(AutomodEvent::Started(_)) = answer {
+        // ok
+    } else {
+        panic!()
+    }
+
+    module_tester.shutdown().await.unwrap();
+}
+
+#[actix_rt::test]
+#[serial]
+async fn summarize_state() {
+    let test_ctx = TestContext::default().await;
+    let (mut module_tester, _user1, _user2) =
+        common::setup_users::<automod::Automod>(&test_ctx, automod::AutomodParams::default()).await;
+    let room = SignalingRoomId::new_for_room(ROOM_ID);
+
+    assert_eq!(
+        automod::Automod::summarize_state(test_ctx.volatile.clone(), room)
+            .await
+            .unwrap(),
+        None
+    );
+
+    module_tester
+        .send_ws_message(
+            &USER_1.participant_id,
+            AutomodCommand::Start(Start {
+                parameter: Parameter {
+                    selection_strategy: SelectionStrategy::Playlist,
+                    show_list: true,
+                    consider_hand_raise: false,
+                    time_limit: None,
+                    allow_double_selection: false,
+                    animation_on_random: false,
+                    auto_append_on_join: false,
+                    random_seed: None,
+                },
+                allow_list: None,
+                playlist: Some(vec![USER_2.participant_id, USER_1.participant_id]),
+            }),
+        )
+        .unwrap();
+
+    let answer = module_tester
+        .receive_ws_message(&USER_1.participant_id)
+        .await
+        .unwrap();
+    assert!(matches!(
+        answer,
+        WsMessageOutgoing::Module(AutomodEvent::Started(_))
+    ));
+
+    let state = automod::Automod::summarize_state(test_ctx.volatile.clone(), room)
+        .await
+        .unwrap()
+        .expect("Expected the running session in the state");
+    assert_eq!(
+        state["config"]["issued_by"],
+        serde_json::to_value(USER_1.participant_id).unwrap()
+    );
+    assert_eq!(
+        state["config"]["remaining"],
+        serde_json::to_value([USER_2.participant_id, USER_1.participant_id]).unwrap()
+    );
+    assert_eq!(state["speaker"], serde_json::Value::Null);
+
+    module_tester.shutdown().await.unwrap();
+}
+
+#[actix_rt::test]
+#[serial]
+async fn summarize_state_hides_list() {
+    let test_ctx = TestContext::default().await;
+    let (mut module_tester, _user1, _user2) =
+        common::setup_users::<automod::Automod>(&test_ctx, automod::AutomodParams::default()).await;
+    let room = SignalingRoomId::new_for_room(ROOM_ID);
+
+    module_tester
+        .send_ws_message(
+            &USER_1.participant_id,
+            AutomodCommand::Start(Start {
+                parameter: Parameter {
+                    selection_strategy: SelectionStrategy::Playlist,
+                    show_list: false,
+                    consider_hand_raise: false,
+                    time_limit: None,
+                    allow_double_selection: false,
+                    animation_on_random: false,
+                    auto_append_on_join: false,
+                    random_seed: None,
+                },
+                allow_list: None,
+                playlist: Some(vec![USER_2.participant_id, USER_1.participant_id]),
+            }),
+        )
+        .unwrap();
+
+    let answer = module_tester
+        .receive_ws_message(&USER_1.participant_id)
+        .await
+        .unwrap();
+    assert!(matches!(
+        answer,
+        WsMessageOutgoing::Module(AutomodEvent::Started(_))
+    ));
+
+    // The remaining participants are kept in the storage, but not reported in the state
+    let state = automod::Automod::summarize_state(test_ctx.volatile.clone(), room)
+        .await
+        .unwrap()
+        .expect("Expected the running session in the state");
+    assert_eq!(
+        state["config"]["issued_by"],
+        serde_json::to_value(USER_1.participant_id).unwrap()
+    );
+    assert_eq!(state["config"]["remaining"], json!([]));
+
+    module_tester.shutdown().await.unwrap();
+}
+
 #[actix_rt::test]
 #[serial]
 async fn accept_valid_edit() {
     let test_ctx = TestContext::default().await;
     let (mut module_tester, _user1, _user2) =
-        common::setup_users::<automod::Automod>(&test_ctx, ()).await;
+        common::setup_users::<automod::Automod>(&test_ctx, automod::AutomodParams::default()).await;
 
     module_tester
         .send_ws_message(
@@ -236,6 +449,7 @@ async fn accept_valid_edit() {
                     allow_double_selection: false,
                     animation_on_random: true,
                     auto_append_on_join: false,
+                    random_seed: None,
                 },
                 // Add valid users
                 allow_list: Some(vec![USER_1.participant_id, USER_2.participant_id]),
@@ -287,7 +501,7 @@ async fn accept_valid_edit() {
 async fn reject_invalid_edit() {
     let test_ctx = TestContext::default().await;
     let (mut module_tester, _user1, _user2) =
-        common::setup_users::<automod::Automod>(&test_ctx, ()).await;
+        common::setup_users::<automod::Automod>(&test_ctx, automod::AutomodParams::default()).await;
 
     module_tester
         .send_ws_message(
@@ -301,6 +515,7 @@ async fn reject_invalid_edit() {
                     allow_double_selection: false,
                     animation_on_random: true,
                     auto_append_on_join: false,
+                    random_seed: None,
                 },
                 // Add valid users
                 allow_list: Some(vec![USER_1.participant_id, USER_2.participant_id]),
@@ -374,7 +589,7 @@ async fn auto_append_none() {
 async fn auto_append(selection_strategy: SelectionStrategy) {
     let test_ctx = TestContext::default().await;
     let (mut module_tester, _user1, _user2) =
-        common::setup_users::<automod::Automod>(&test_ctx, ()).await;
+        common::setup_users::<automod::Automod>(&test_ctx, automod::AutomodParams::default()).await;
 
     module_tester
         .send_ws_message(
@@ -388,6 +603,7 @@ async fn auto_append(selection_strategy: SelectionStrategy) {
                     allow_double_selection: false,
                     animation_on_random: true,
                     auto_append_on_join: true,
+                    random_seed: None,
                 },
                 allow_list: Some(vec![USER_1.participant_id, USER_2.participant_id]),
                 playlist: Some(vec![USER_1.participant_id, USER_2.participant_id]),
@@ -424,7 +640,7 @@ async fn auto_append(selection_strategy: SelectionStrategy) {
             user3.clone(),
             Role::User,
             &USER_1.display_name(),
-            (),
+            automod::AutomodParams::default(),
         )
         .await
         .unwrap();
@@ -478,7 +694,7 @@ async fn auto_append(selection_strategy: SelectionStrategy) {
 async fn full_run_playlist() {
     let test_ctx = TestContext::default().await;
     let (mut module_tester, _user1, _user2) =
-        common::setup_users::<automod::Automod>(&test_ctx, ()).await;
+        common::setup_users::<automod::Automod>(&test_ctx, automod::AutomodParams::default()).await;
 
     const USER_3: TestUser = TestUser {
         n: 3,
@@ -495,7 +711,7 @@ async fn full_run_playlist() {
             user3.clone(),
             Role::User,
             &USER_1.display_name(),
-            (),
+            automod::AutomodParams::default(),
         )
         .await
         .unwrap();
@@ -533,6 +749,7 @@ async fn full_run_playlist() {
                     allow_double_selection: false,
                     animation_on_random: true,
                     auto_append_on_join: true,
+                    random_seed: None,
                 },
                 allow_list: Some(vec![
                     USER_1.participant_id,
@@ -671,7 +888,7 @@ async fn full_run_playlist() {
 async fn on_leaving_sends_remaning_update() {
     let test_ctx = TestContext::default().await;
     let (mut module_tester, _user1, _user2) =
-        common::setup_users::<automod::Automod>(&test_ctx, ()).await;
+        common::setup_users::<automod::Automod>(&test_ctx, automod::AutomodParams::default()).await;
 
     const USER_3: TestUser = TestUser {
         n: 3,
@@ -688,7 +905,7 @@ async fn on_leaving_sends_remaning_update() {
             user3.clone(),
             Role::User,
             &USER_1.display_name(),
-            (),
+            automod::AutomodParams::default(),
         )
         .await
         .unwrap();
@@ -726,6 +943,7 @@ async fn on_leaving_sends_remaning_update() {
                     allow_double_selection: false,
                     animation_on_random: true,
                     auto_append_on_join: true,
+                    random_seed: None,
                 },
                 allow_list: Some(vec![
                     USER_1.participant_id,
@@ -791,7 +1009,7 @@ async fn on_leaving_sends_remaning_update() {
 async fn skipping_last_speaker_finishes_the_session() {
     let test_ctx = TestContext::default().await;
     let (mut module_tester, _user1, _user2) =
-        common::setup_users::<automod::Automod>(&test_ctx, ()).await;
+        common::setup_users::<automod::Automod>(&test_ctx, automod::AutomodParams::default()).await;
 
     module_tester
         .send_ws_message(
@@ -805,6 +1023,7 @@ async fn skipping_last_speaker_finishes_the_session() {
                     allow_double_selection: false,
                     animation_on_random: true,
                     auto_append_on_join: true,
+                    random_seed: None,
                 },
                 allow_list: Some(vec![USER_1.participant_id, USER_2.participant_id]),
                 playlist: Some(vec![USER_1.participant_id, USER_2.participant_id]),
@@ -889,3 +1108,311 @@ async fn skipping_last_speaker_finishes_the_session() {
 
     module_tester.shutdown().await.unwrap();
 }
+
+#[actix_rt::test]
+#[serial]
+async fn skip_keeps_session_active() {
+    let test_ctx = TestContext::default().await;
+    let (mut module_tester, _user1, _user2) =
+        common::setup_users::<automod::Automod>(&test_ctx, automod::AutomodParams::default()).await;
+
+    module_tester
+        .send_ws_message(
+            &USER_1.participant_id,
+            AutomodCommand::Start(Start {
+                parameter: Parameter {
+                    selection_strategy: SelectionStrategy::Playlist,
+                    show_list: true,
+                    consider_hand_raise: false,
+                    time_limit: None,
+                    allow_double_selection: false,
+                    animation_on_random: true,
+                    auto_append_on_join: false,
+                    random_seed: None,
+                },
+                allow_list: None,
+                playlist: Some(vec![USER_1.participant_id, USER_2.participant_id]),
+            }),
+        )
+        .unwrap();
+
+    for user in [&USER_1, &USER_2] {
+        let started = module_tester
+            .receive_ws_message(&user.participant_id)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            started,
+            WsMessageOutgoing::Module(AutomodEvent::Started(_))
+        ));
+    }
+
+    module_tester
+        .send_ws_message(&USER_1.participant_id, AutomodCommand::Select(Select::Next))
+        .unwrap();
+
+    for user in [&USER_1, &USER_2] {
+        let update = module_tester
+            .receive_ws_message(&user.participant_id)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            update,
+            WsMessageOutgoing::Module(AutomodEvent::SpeakerUpdated(SpeakerUpdated {
+                speaker: Some(USER_1.participant_id),
+                history: Some(vec![USER_1.participant_id]),
+                remaining: Some(vec![USER_2.participant_id]),
+            }))
+        );
+    }
+
+    module_tester
+        .send_ws_message(&USER_1.participant_id, AutomodCommand::Skip)
+        .unwrap();
+
+    for user in [&USER_1, &USER_2] {
+        let update = module_tester
+            .receive_ws_message(&user.participant_id)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            update,
+            WsMessageOutgoing::Module(AutomodEvent::SpeakerUpdated(SpeakerUpdated {
+                speaker: None,
+                history: Some(vec![USER_1.participant_id]),
+                remaining: Some(vec![USER_2.participant_id]),
+            }))
+        );
+    }
+
+    // The session is still active, the next speaker can be selected
+    module_tester
+        .send_ws_message(&USER_1.participant_id, AutomodCommand::Select(Select::Next))
+        .unwrap();
+
+    for user in [&USER_1, &USER_2] {
+        let update = module_tester
+            .receive_ws_message(&user.participant_id)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            update,
+            WsMessageOutgoing::Module(AutomodEvent::SpeakerUpdated(SpeakerUpdated {
+                speaker: Some(USER_2.participant_id),
+                history: Some(vec![USER_1.participant_id, USER_2.participant_id]),
+                remaining: Some(vec![]),
+            }))
+        );
+    }
+
+    module_tester.shutdown().await.unwrap();
+}
+
+#[actix_rt::test]
+#[serial]
+async fn reject_skip_without_moderator_role() {
+    let test_ctx = TestContext::default().await;
+    let (mut module_tester, _user1, _user2) =
+        common::setup_users::<automod::Automod>(&test_ctx, automod::AutomodParams::default()).await;
+
+    module_tester
+        .send_ws_message(&USER_2.participant_id, AutomodCommand::Skip)
+        .unwrap();
+
+    let answer = module_tester
+        .receive_ws_message(&USER_2.participant_id)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        answer,
+        WsMessageOutgoing::Module(AutomodEvent::Error(Error::InsufficientPermissions))
+    );
+
+    module_tester.shutdown().await.unwrap();
+}
+
+#[actix_rt::test]
+#[serial]
+async fn persist_state_checkpoints_session() {
+    let test_ctx = TestContext::default().await;
+    let (mut module_tester, _user1, _user2) = common::setup_users::<automod::Automod>(
+        &test_ctx,
+        automod::AutomodParams {
+            persist_state: true,
+            ..Default::default()
+        },
+    )
+    .await;
+
+    module_tester
+        .send_ws_message(
+            &USER_1.participant_id,
+            AutomodCommand::Start(Start {
+                parameter: Parameter {
+                    selection_strategy: SelectionStrategy::Playlist,
+                    show_list: true,
+                    consider_hand_raise: false,
+                    time_limit: None,
+                    allow_double_selection: false,
+                    animation_on_random: false,
+                    auto_append_on_join: false,
+                    random_seed: None,
+                },
+                allow_list: None,
+                playlist: Some(vec![USER_1.participant_id, USER_2.participant_id]),
+            }),
+        )
+        .unwrap();
+
+    let answer = module_tester
+        .receive_ws_message(&USER_1.participant_id)
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        answer,
+        WsMessageOutgoing::Module(AutomodEvent::Started(_))
+    ));
+
+    let mut db_conn = test_ctx.db_ctx.db.get_conn().await.unwrap();
+    let checkpoints =
+        ModuleResource::get(&mut db_conn, Filter::new().with_namespace("automod".into()))
+            .await
+            .unwrap();
+    assert_eq!(checkpoints.len(), 1);
+    assert_eq!(
+        checkpoints[0].data["playlist"],
+        serde_json::json!([USER_1.participant_id, USER_2.participant_id])
+    );
+
+    module_tester
+        .send_ws_message(&USER_1.participant_id, AutomodCommand::Stop)
+        .unwrap();
+
+    let answer = module_tester
+        .receive_ws_message(&USER_1.participant_id)
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        answer,
+        WsMessageOutgoing::Module(AutomodEvent::Stopped(_))
+    ));
+
+    let checkpoints =
+        ModuleResource::get(&mut db_conn, Filter::new().with_namespace("automod".into()))
+            .await
+            .unwrap();
+    assert!(checkpoints.is_empty());
+
+    module_tester.shutdown().await.unwrap();
+}
+
+/// Run a seeded random session in a new room until every participant was selected and return
+/// the speakers in the order they were selected
+async fn seeded_selection_order(random_seed: u64) -> Vec<ParticipantId> {
+    let test_ctx = TestContext::default().await;
+    let (mut module_tester, _user1, _user2) =
+        common::setup_users::<automod::Automod>(&test_ctx, automod::AutomodParams::default()).await;
+
+    let mut allow_list = vec![USER_1.participant_id, USER_2.participant_id];
+
+    for n in 3..=4u32 {
+        let participant_id = ParticipantId::from_u128(n.into());
+        let user = test_ctx.db_ctx.create_test_user(n, vec![]).await.unwrap();
+
+        module_tester
+            .join_user(
+                participant_id,
+                user,
+                Role::User,
+                &USER_1.display_name(),
+                automod::AutomodParams::default(),
+            )
+            .await
+            .unwrap();
+
+        let joined = module_tester
+            .receive_ws_message(&USER_1.participant_id)
+            .await
+            .unwrap();
+        assert!(matches!(
+            joined,
+            WsMessageOutgoing::Control(ControlEvent::Joined(_))
+        ));
+
+        allow_list.push(participant_id);
+    }
+
+    module_tester
+        .send_ws_message(
+            &USER_1.participant_id,
+            AutomodCommand::Start(Start {
+                parameter: Parameter {
+                    selection_strategy: SelectionStrategy::Random,
+                    show_list: true,
+                    consider_hand_raise: false,
+                    time_limit: None,
+                    allow_double_selection: false,
+                    animation_on_random: false,
+                    auto_append_on_join: false,
+                    random_seed: Some(random_seed),
+                },
+                allow_list: Some(allow_list.clone()),
+                playlist: None,
+            }),
+        )
+        .unwrap();
+
+    let answer = module_tester
+        .receive_ws_message(&USER_1.participant_id)
+        .await
+        .unwrap();
+    assert!(matches!(
+        answer,
+        WsMessageOutgoing::Module(AutomodEvent::Started(_))
+    ));
+
+    let mut speakers = Vec::new();
+    for _ in 0..allow_list.len() {
+        module_tester
+            .send_ws_message(&USER_1.participant_id, AutomodCommand::Select(Select::Next))
+            .unwrap();
+
+        let answer = module_tester
+            .receive_ws_message(&USER_1.participant_id)
+            .await
+            .unwrap();
+        let WsMessageOutgoing::Module(AutomodEvent::SpeakerUpdated(SpeakerUpdated {
+            speaker: Some(speaker),
+            ..
+        })) = answer
+        else {
+            panic!("Expected a speaker to be selected, got {answer:?}");
+        };
+
+        speakers.push(speaker);
+    }
+
+    module_tester.shutdown().await.unwrap();
+
+    let mut selected = speakers.clone();
+    selected.sort();
+    assert_eq!(selected, allow_list);
+
+    speakers
+}
+
+#[actix_rt::test]
+#[serial]
+async fn seeded_random_selection_is_reproducible() {
+    let first = seeded_selection_order(42).await;
+    let second = seeded_selection_order(42).await;
+
+    assert_eq!(first, second);
+}
@@ -10,18 +10,22 @@
 
 use std::{
     collections::{BTreeMap, BTreeSet},
-    sync::Arc,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
 };
 
 use either::Either;
+use opentalk_controller_settings::{DEFAULT_CHAT_MAX_MESSAGE_SIZE, Settings};
 use opentalk_database::Db;
 use opentalk_db_storage::groups::Group;
 use opentalk_signaling_core::{
     CleanupScope, DestroyContext, Event, InitContext, LockError, ModuleContext, Participant,
-    RoomLockingProvider as _, SignalingModule, SignalingModuleError, SignalingModuleInitData,
-    SignalingRoomId, VolatileStorage,
+    RoomLockingProvider as _, SignalingMetrics, SignalingModule, SignalingModuleError,
+    SignalingModuleInitData, SignalingRoomId, VolatileStorage,
     control::{
-        exchange,
+        ControlStorageProvider as _, exchange,
         storage::{ControlStorageParticipantAttributes as _, USER_ID},
     },
 };
@@ -33,8 +37,8 @@ use opentalk_types_common::{
 use opentalk_types_signaling::{ParticipantId, Role};
 use opentalk_types_signaling_chat::{
     MODULE_ID, MessageId, Scope,
-    command::{ChatCommand, SendMessage, SetLastSeenTimestamp},
-    event::{ChatDisabled, ChatEnabled, ChatEvent, Error, HistoryCleared, MessageSent},
+    command::{ChatCommand, SendMessage, SetLastSeenTimestamp, SetScopeMuted},
+    event::{ChatDisabled, ChatEnabled, ChatEvent, Error, HistoryCleared, MessageAck, MessageSent},
     peer_state::ChatPeerState,
     state::{ChatState, GroupHistory, PrivateHistory, StoredMessage},
 };
@@ -50,14 +54,59 @@ fn current_room_by_group_id(room_id: SignalingRoomId, group_id: GroupId) -> Stri
     format!("room={room_id}:group={group_id}")
 }
 
+fn current_room_moderators(room_id: SignalingRoomId) -> String {
+    format!("room={room_id}:moderators")
+}
+
+/// Maximum length of the message id a client can attach to a sent message
+const MAX_CLIENT_MESSAGE_ID_LENGTH: usize = 64;
+
+/// Confirm to the sender that a message has been accepted, if the client attached an id to it.
+/// The client message id is only echoed and never used to store the message.
+fn acknowledge_message(
+    ctx: &mut ModuleContext<'_, Chat>,
+    id: MessageId,
+    client_msg_id: Option<String>,
+) {
+    if let Some(client_msg_id) = client_msg_id {
+        ctx.ws_send(MessageAck { id, client_msg_id });
+    }
+}
+
+/// Parameters shared by all instances of the chat module
+#[derive(Clone)]
+pub struct ChatParams {
+    metrics: Option<Arc<SignalingMetrics>>,
+    /// Maximum size of a chat message in bytes, updated when the settings are reloaded
+    max_message_size: Arc<AtomicUsize>,
+    /// Maximum number of messages retained per chat history, `0` if unbounded. Updated when the
+    /// settings are reloaded
+    max_history_length: Arc<AtomicUsize>,
+}
+
+impl Default for ChatParams {
+    fn default() -> Self {
+        Self {
+            metrics: None,
+            max_message_size: Arc::new(AtomicUsize::new(DEFAULT_CHAT_MAX_MESSAGE_SIZE)),
+            max_history_length: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+}
+
 pub struct Chat {
     id: ParticipantId,
     room: SignalingRoomId,
     last_seen_timestamp_global: Option<Timestamp>,
     last_seen_timestamps_private: BTreeMap<ParticipantId, Timestamp>,
     last_seen_timestamps_group: BTreeMap<GroupName, Timestamp>,
+    /// Scopes in which messages from others are delivered without notifying the participant
+    muted_scopes: Vec<Scope>,
     db: Arc<Db>,
     groups: Vec<Group>,
+    metrics: Option<Arc<SignalingMetrics>>,
+    max_message_size: Arc<AtomicUsize>,
+    max_history_length: Arc<AtomicUsize>,
 }
 
 impl Chat {
@@ -65,6 +114,81 @@ impl Chat {
         self.groups.iter().find(|group| group.name == *name)
     }
 
+    /// Whether the participant muted the scope a message was sent in. Private messages are
+    /// muted per correspondent, which is the source of the message for the receiver.
+    fn is_muted(&self, message: &MessageSent) -> bool {
+        match &message.scope {
+            Scope::Private(_) => self.muted_scopes.contains(&Scope::Private(message.source)),
+            scope => self.muted_scopes.contains(scope),
+        }
+    }
+
+    /// Build the chat state of another participant, containing the groups shared with them.
+    /// Returns `None` for participants without a user.
+    async fn peer_state(
+        &self,
+        ctx: &mut ModuleContext<'_, Self>,
+        participant_id: ParticipantId,
+    ) -> Result<Option<ChatPeerState>, SignalingModuleError> {
+        let user_id: Option<UserId> = ctx
+            .volatile
+            .storage()
+            .get_local_attribute(participant_id, self.room, USER_ID)
+            .await?;
+
+        let Some(user_id) = user_id else {
+            return Ok(None);
+        };
+
+        let mut conn = self.db.get_conn().await?;
+
+        let groups = Group::get_all_for_user(&mut conn, user_id).await?;
+
+        // Intersect our groups and the groups of the user and collect their names
+        let common_groups = self
+            .groups
+            .iter()
+            .filter(|self_group| groups.contains(self_group))
+            .map(|group| group.name.clone())
+            .collect();
+
+        Ok(Some(ChatPeerState {
+            groups: common_groups,
+        }))
+    }
+
+    /// The maximum number of messages retained per chat history, the oldest messages are evicted
+    /// when a new message exceeds it
+    fn max_history_length(&self) -> Option<usize> {
+        Some(self.max_history_length.load(Ordering::Relaxed)).filter(|length| *length > 0)
+    }
+
+    /// Record metrics of an accepted message. Must not receive anything but the scope kind and
+    /// the content length, private messages especially must not leak any content.
+    fn record_message_metrics(&self, scope: &'static str, content_len: usize) {
+        if let Some(metrics) = &self.metrics {
+            metrics.record_chat_message(scope, content_len);
+        }
+    }
+
+    /// Whether all participants left the given room
+    ///
+    /// Assumes participants are still present if that cannot be determined, so no state gets
+    /// removed while it is in use.
+    async fn all_left(ctx: &mut DestroyContext<'_>, signaling_room_id: SignalingRoomId) -> bool {
+        ctx.volatile
+            .control_storage()
+            .participants_all_left(signaling_room_id)
+            .await
+            .unwrap_or_else(|e| {
+                log::error!(
+                    "Failed to check whether all participants left {signaling_room_id}, {}",
+                    Report::from_error(e)
+                );
+                false
+            })
+    }
+
     async fn cleanup_room(ctx: &mut DestroyContext<'_>, signaling_room_id: SignalingRoomId) {
         if let Err(e) = ctx
             .volatile
@@ -81,11 +205,11 @@ impl Chat {
         if let Err(e) = ctx
             .volatile
             .storage()
-            .delete_private_chat_correspondents(signaling_room_id)
+            .delete_moderators_history(signaling_room_id)
             .await
         {
             log::error!(
-                "Failed to remove room chat correspondents on room destroy, {}",
+                "Failed to remove moderators chat history on room destroy, {}",
                 Report::from_error(e)
             );
         }
@@ -116,6 +240,18 @@ impl Chat {
             }
         }
 
+        if let Err(e) = ctx
+            .volatile
+            .storage()
+            .delete_private_chat_correspondents(signaling_room_id)
+            .await
+        {
+            log::error!(
+                "Failed to remove room chat correspondents on room destroy, {}",
+                Report::from_error(e)
+            );
+        }
+
         let participants = ctx
             .volatile
             .storage()
@@ -163,6 +299,14 @@ impl Chat {
                     e
                 );
             }
+            if let Err(e) = ctx
+                .volatile
+                .storage()
+                .delete_muted_scopes(signaling_room_id, participant)
+                .await
+            {
+                log::error!("Failed to clean up muted chat scopes, {}", e);
+            }
         }
     }
 
@@ -188,6 +332,7 @@ trait ChatStateExt: Sized {
         room: SignalingRoomId,
         participant: ParticipantId,
         groups: &[Group],
+        is_moderator: bool,
     ) -> Result<Self, SignalingModuleError>;
 }
 
@@ -198,10 +343,16 @@ impl ChatStateExt for ChatState {
         room: SignalingRoomId,
         participant: ParticipantId,
         groups: &[Group],
+        is_moderator: bool,
     ) -> Result<Self, SignalingModuleError> {
         let enabled = storage.is_chat_enabled(room.room_id()).await?;
 
         let room_history = storage.get_room_history(room).await?;
+        let moderators_history = if is_moderator {
+            Some(storage.get_moderators_history(room).await?)
+        } else {
+            None
+        };
         let mut groups_history = Vec::new();
         for group in groups {
             storage
@@ -239,15 +390,18 @@ impl ChatStateExt for ChatState {
         let last_seen_timestamps_group = storage
             .get_last_seen_timestamps_group(room, participant)
             .await?;
+        let muted_scopes = storage.get_muted_scopes(room, participant).await?;
 
         Ok(Self {
             room_history,
+            moderators_history,
             enabled,
             groups_history,
             private_history,
             last_seen_timestamp_global,
             last_seen_timestamps_private,
             last_seen_timestamps_group,
+            muted_scopes,
         })
     }
 }
@@ -269,7 +423,7 @@ impl ChatStorageProvider for VolatileStorage {
 impl SignalingModule for Chat {
     const NAMESPACE: ModuleId = MODULE_ID;
 
-    type Params = ();
+    type Params = ChatParams;
 
     type Incoming = ChatCommand;
     type Outgoing = ChatEvent;
@@ -282,7 +436,7 @@ impl SignalingModule for Chat {
 
     async fn init(
         mut ctx: InitContext<'_, Self>,
-        _params: &Self::Params,
+        params: &Self::Params,
         _protocol: &'static str,
     ) -> Result<Option<Self>, SignalingModuleError> {
         let id = ctx.participant_id();
@@ -303,6 +457,11 @@ impl SignalingModule for Chat {
             vec![]
         };
 
+        // Exchange bindings cannot change during a session, so every participant is bound to the
+        // moderators channel and messages are dropped on receipt unless the participant currently
+        // is a moderator. This way promotions and demotions take effect immediately.
+        ctx.add_exchange_binding(current_room_moderators(room));
+
         Ok(Some(Self {
             id,
             room,
@@ -311,6 +470,10 @@ impl SignalingModule for Chat {
             last_seen_timestamp_global: None,
             last_seen_timestamps_private: BTreeMap::new(),
             last_seen_timestamps_group: BTreeMap::new(),
+            muted_scopes: Vec::new(),
+            metrics: params.metrics.clone(),
+            max_message_size: params.max_message_size.clone(),
+            max_history_length: params.max_history_length.clone(),
         }))
     }
 
@@ -330,6 +493,7 @@ impl SignalingModule for Chat {
                     self.room,
                     self.id,
                     &self.groups,
+                    ctx.role() == Role::Moderator,
                 )
                 .await?;
                 self.last_seen_timestamp_global = module_frontend_data.last_seen_timestamp_global;
@@ -337,6 +501,8 @@ impl SignalingModule for Chat {
                     .clone_from(&module_frontend_data.last_seen_timestamps_private);
                 self.last_seen_timestamps_group
                     .clone_from(&module_frontend_data.last_seen_timestamps_group);
+                self.muted_scopes
+                    .clone_from(&module_frontend_data.muted_scopes);
 
                 *frontend_data = Some(module_frontend_data);
 
@@ -466,6 +632,19 @@ impl SignalingModule for Chat {
                     }
                 }
 
+                // Muting is a preference of the current session only
+                if let Err(e) = ctx
+                    .volatile
+                    .storage()
+                    .delete_muted_scopes(self.room, self.id)
+                    .await
+                {
+                    log::error!(
+                        "Failed to clear muted chat scopes, {}",
+                        Report::from_error(e)
+                    );
+                }
+
                 for group in &self.groups {
                     ctx.volatile
                         .storage()
@@ -479,38 +658,12 @@ impl SignalingModule for Chat {
             }
             Event::RaiseHand => {}
             Event::LowerHand => {}
-            Event::ParticipantJoined(participant_id, peer_frontend_data) => {
-                // Get user id of the joined participant
-                let user_id: Option<UserId> = ctx
-                    .volatile
-                    .storage()
-                    .get_local_attribute(participant_id, self.room, USER_ID)
-                    .await?;
-
-                if let Some(user_id) = user_id {
-                    let db = self.db.clone();
-                    let self_groups = self.groups.clone();
-
-                    // Get the user's groups
-                    let mut conn = db.get_conn().await?;
-
-                    let groups = Group::get_all_for_user(&mut conn, user_id).await?;
-
-                    // Intersect our groups and the groups of the user and collect their id/name
-                    // as strings into a set
-                    let common_groups = self_groups
-                        .iter()
-                        .filter(|self_group| groups.contains(self_group))
-                        .map(|group| group.name.clone())
-                        .collect();
-
-                    *peer_frontend_data = Some(ChatPeerState {
-                        groups: common_groups,
-                    });
-                }
+            Event::ParticipantJoined(participant_id, peer_frontend_data)
+            | Event::ParticipantUpdated(participant_id, peer_frontend_data) => {
+                // Updates replace the peer state in the frontend, so it is computed for both
+                *peer_frontend_data = self.peer_state(&mut ctx, participant_id).await?;
             }
             Event::ParticipantLeft(_) => {}
-            Event::ParticipantUpdated(_, _) => {}
             Event::RoleUpdated(_) => {}
             Event::WsMessage(ChatCommand::EnableChat) => {
                 if ctx.role() != Role::Moderator {
@@ -544,12 +697,24 @@ impl SignalingModule for Chat {
                     ChatEvent::ChatDisabled(ChatDisabled { issued_by: self.id }),
                 );
             }
-            Event::WsMessage(ChatCommand::SendMessage(SendMessage { scope, mut content })) => {
+            Event::WsMessage(ChatCommand::SendMessage(SendMessage {
+                scope,
+                mut content,
+                client_msg_id,
+            })) => {
                 // Discard empty messages
                 if content.is_empty() {
                     return Ok(());
                 }
 
+                if client_msg_id
+                    .as_ref()
+                    .is_some_and(|id| id.is_empty() || id.len() > MAX_CLIENT_MESSAGE_ID_LENGTH)
+                {
+                    ctx.ws_send(Error::InvalidClientMessageId);
+                    return Ok(());
+                }
+
                 let chat_enabled = ctx
                     .volatile
                     .storage()
@@ -562,7 +727,7 @@ impl SignalingModule for Chat {
                 }
 
                 // Limit message size
-                let max_message_size = 4096;
+                let max_message_size = self.max_message_size.load(Ordering::Relaxed);
                 if content.len() > max_message_size {
                     let mut last_idx = 0;
 
@@ -577,6 +742,7 @@ impl SignalingModule for Chat {
                 }
 
                 let source = self.id;
+                let content_len = content.len();
 
                 match scope {
                     Scope::Private(target) => {
@@ -585,6 +751,8 @@ impl SignalingModule for Chat {
                             source,
                             content,
                             scope: Scope::Private(target),
+                            client_msg_id: client_msg_id.clone(),
+                            silent: false,
                         };
 
                         let stored_msg = StoredMessage {
@@ -607,9 +775,13 @@ impl SignalingModule for Chat {
                                 self.id,
                                 target,
                                 &stored_msg,
+                                self.max_history_length(),
                             )
                             .await?;
 
+                        self.record_message_metrics("private", content_len);
+                        acknowledge_message(&mut ctx, stored_msg.id, client_msg_id);
+
                         ctx.exchange_publish(
                             exchange::current_room_by_participant_id(self.room, target),
                             ChatEvent::MessageSent(out_message.clone()),
@@ -624,6 +796,8 @@ impl SignalingModule for Chat {
                                 source,
                                 content,
                                 scope: Scope::Group(group_name),
+                                client_msg_id: client_msg_id.clone(),
+                                silent: false,
                             };
 
                             let stored_msg = StoredMessage {
@@ -636,9 +810,17 @@ impl SignalingModule for Chat {
 
                             ctx.volatile
                                 .storage()
-                                .add_message_to_group_chat_history(self.room, group.id, &stored_msg)
+                                .add_message_to_group_chat_history(
+                                    self.room,
+                                    group.id,
+                                    &stored_msg,
+                                    self.max_history_length(),
+                                )
                                 .await?;
 
+                            self.record_message_metrics("group", content_len);
+                            acknowledge_message(&mut ctx, stored_msg.id, client_msg_id);
+
                             let out_message = ChatEvent::MessageSent(out_message_contents);
 
                             ctx.exchange_publish(
@@ -653,6 +835,8 @@ impl SignalingModule for Chat {
                             source,
                             content,
                             scope: Scope::Global,
+                            client_msg_id: client_msg_id.clone(),
+                            silent: false,
                         };
 
                         let stored_msg = StoredMessage {
@@ -665,9 +849,16 @@ impl SignalingModule for Chat {
 
                         ctx.volatile
                             .storage()
-                            .add_message_to_room_history(self.room, &stored_msg)
+                            .add_message_to_room_history(
+                                self.room,
+                                &stored_msg,
+                                self.max_history_length(),
+                            )
                             .await?;
 
+                        self.record_message_metrics("global", content_len);
+                        acknowledge_message(&mut ctx, stored_msg.id, client_msg_id);
+
                         let out_message = ChatEvent::MessageSent(out_message_contents);
 
                         ctx.exchange_publish(
@@ -675,6 +866,45 @@ impl SignalingModule for Chat {
                             out_message,
                         );
                     }
+                    Scope::Moderators => {
+                        if ctx.role() != Role::Moderator {
+                            ctx.ws_send(Error::InsufficientPermissions);
+                            return Ok(());
+                        }
+
+                        let out_message_contents = MessageSent {
+                            id: MessageId::generate(),
+                            source,
+                            content,
+                            scope: Scope::Moderators,
+                            client_msg_id: client_msg_id.clone(),
+                            silent: false,
+                        };
+
+                        let stored_msg = StoredMessage {
+                            id: out_message_contents.id,
+                            source: out_message_contents.source,
+                            content: out_message_contents.content.clone(),
+                            scope: out_message_contents.scope.clone(),
+                            timestamp: ctx.timestamp(),
+                        };
+
+                        ctx.volatile
+                            .storage()
+                            .add_message_to_moderators_history(
+                                self.room,
+                                &stored_msg,
+                                self.max_history_length(),
+                            )
+                            .await?;
+
+                        self.record_message_metrics("moderators", content_len);
+                        acknowledge_message(&mut ctx, stored_msg.id, client_msg_id);
+
+                        let out_message = ChatEvent::MessageSent(out_message_contents);
+
+                        ctx.exchange_publish(current_room_moderators(self.room), out_message);
+                    }
                 }
             }
             Event::WsMessage(ChatCommand::ClearHistory) => {
@@ -712,9 +942,43 @@ impl SignalingModule for Chat {
                     Scope::Global => {
                         self.last_seen_timestamp_global = Some(timestamp);
                     }
+                    Scope::Moderators => {
+                        // Last seen timestamps are not tracked for the moderators channel
+                    }
                 };
             }
-            Event::Exchange(msg) => {
+            Event::WsMessage(ChatCommand::SetScopeMuted(SetScopeMuted { scope, muted })) => {
+                if matches!(&scope, Scope::Group(group) if self.get_group(group).is_none()) {
+                    return Ok(());
+                }
+
+                ctx.volatile
+                    .storage()
+                    .set_scope_muted(self.room, self.id, &scope, muted)
+                    .await?;
+
+                if muted {
+                    if !self.muted_scopes.contains(&scope) {
+                        self.muted_scopes.push(scope);
+                    }
+                } else {
+                    self.muted_scopes
+                        .retain(|muted_scope| *muted_scope != scope);
+                }
+            }
+            Event::Exchange(mut msg) => {
+                if let ChatEvent::MessageSent(message) = &mut msg {
+                    if matches!(message.scope, Scope::Moderators) && ctx.role() != Role::Moderator {
+                        return Ok(());
+                    }
+
+                    // The client message id is only meaningful to the sender of the message
+                    if message.source != self.id {
+                        message.client_msg_id = None;
+                        message.silent = self.is_muted(message);
+                    }
+                }
+
                 ctx.ws_send(msg);
             }
             Event::Ext(_) => {}
@@ -728,10 +992,12 @@ impl SignalingModule for Chat {
             CleanupScope::None => {}
             CleanupScope::Local => Chat::cleanup_room(&mut ctx, self.room).await,
             CleanupScope::Global => {
-                if self.room.breakout_room_id().is_some() {
-                    // Cleanup state for the main room
-                    Chat::cleanup_room(&mut ctx, SignalingRoomId::new(self.room.room_id(), None))
-                        .await;
+                let main_room = SignalingRoomId::new(self.room.room_id(), None);
+
+                // The main room is cleaned up by the last breakout room, unless participants
+                // moved back to the main room in the meantime
+                if self.room != main_room && Chat::all_left(&mut ctx, main_room).await {
+                    Chat::cleanup_room(&mut ctx, main_room).await;
                 }
 
                 Chat::cleanup_room(&mut ctx, self.room).await;
@@ -742,8 +1008,29 @@ impl SignalingModule for Chat {
     }
 
     async fn build_params(
-        _init: SignalingModuleInitData,
+        init: SignalingModuleInitData,
     ) -> Result<Option<Self::Params>, SignalingModuleError> {
-        Ok(Some(()))
+        Ok(Some(ChatParams {
+            metrics: Some(init.metrics),
+            max_message_size: Arc::new(AtomicUsize::new(
+                init.startup_settings.chat.max_message_size,
+            )),
+            max_history_length: Arc::new(AtomicUsize::new(
+                init.startup_settings
+                    .chat
+                    .max_history_length
+                    .unwrap_or_default(),
+            )),
+        }))
+    }
+
+    fn reload_params(params: &Self::Params, settings: &Settings) {
+        params
+            .max_message_size
+            .store(settings.chat.max_message_size, Ordering::Relaxed);
+        params.max_history_length.store(
+            settings.chat.max_history_length.unwrap_or_default(),
+            Ordering::Relaxed,
+        );
     }
 }
@@ -15,7 +15,7 @@ use opentalk_types_common::{
     users::{GroupId, GroupName},
 };
 use opentalk_types_signaling::ParticipantId;
-use opentalk_types_signaling_chat::state::StoredMessage;
+use opentalk_types_signaling_chat::{Scope, state::StoredMessage};
 
 use crate::ParticipantPair;
 
@@ -28,10 +28,13 @@ pub(crate) trait ChatStorage:
         room: SignalingRoomId,
     ) -> Result<Vec<StoredMessage>, SignalingModuleError>;
 
+    /// Add a message to the room history, evicting the oldest messages exceeding
+    /// `max_history_length`
     async fn add_message_to_room_history(
         &mut self,
         room: SignalingRoomId,
         message: &StoredMessage,
+        max_history_length: Option<usize>,
     ) -> Result<(), SignalingModuleError>;
 
     async fn delete_room_history(
@@ -39,6 +42,25 @@ pub(crate) trait ChatStorage:
         room: SignalingRoomId,
     ) -> Result<(), SignalingModuleError>;
 
+    async fn get_moderators_history(
+        &mut self,
+        room: SignalingRoomId,
+    ) -> Result<Vec<StoredMessage>, SignalingModuleError>;
+
+    /// Add a message to the moderators history, evicting the oldest messages exceeding
+    /// `max_history_length`
+    async fn add_message_to_moderators_history(
+        &mut self,
+        room: SignalingRoomId,
+        message: &StoredMessage,
+        max_history_length: Option<usize>,
+    ) -> Result<(), SignalingModuleError>;
+
+    async fn delete_moderators_history(
+        &mut self,
+        room: SignalingRoomId,
+    ) -> Result<(), SignalingModuleError>;
+
     async fn set_chat_enabled(
         &mut self,
         room: RoomId,
@@ -106,6 +128,26 @@ pub(crate) trait ChatStorage:
         participant: ParticipantId,
     ) -> Result<(), SignalingModuleError>;
 
+    async fn set_scope_muted(
+        &mut self,
+        room: SignalingRoomId,
+        participant: ParticipantId,
+        scope: &Scope,
+        muted: bool,
+    ) -> Result<(), SignalingModuleError>;
+
+    async fn get_muted_scopes(
+        &mut self,
+        room: SignalingRoomId,
+        participant: ParticipantId,
+    ) -> Result<Vec<Scope>, SignalingModuleError>;
+
+    async fn delete_muted_scopes(
+        &mut self,
+        room: SignalingRoomId,
+        participant: ParticipantId,
+    ) -> Result<(), SignalingModuleError>;
+
     async fn add_private_chat_correspondents(
         &mut self,
         room: SignalingRoomId,
@@ -151,11 +193,14 @@ pub(crate) trait ChatStorage:
         group: GroupId,
     ) -> Result<Vec<StoredMessage>, SignalingModuleError>;
 
+    /// Add a message to the history of a group, evicting the oldest messages exceeding
+    /// `max_history_length`
     async fn add_message_to_group_chat_history(
         &mut self,
         room: SignalingRoomId,
         group: GroupId,
         message: &StoredMessage,
+        max_history_length: Option<usize>,
     ) -> Result<(), SignalingModuleError>;
 
     async fn delete_group_chat_history(
@@ -171,12 +216,15 @@ pub(crate) trait ChatStorage:
         participant_two: ParticipantId,
     ) -> Result<Vec<StoredMessage>, SignalingModuleError>;
 
+    /// Add a message to the private history of two participants, evicting the oldest messages
+    /// exceeding `max_history_length`
     async fn add_message_to_private_chat_history(
         &mut self,
         room: SignalingRoomId,
         participant_one: ParticipantId,
         participant_two: ParticipantId,
         message: &StoredMessage,
+        max_history_length: Option<usize>,
     ) -> Result<(), SignalingModuleError>;
 
     async fn delete_private_chat_history(
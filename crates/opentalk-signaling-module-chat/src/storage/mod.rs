@@ -18,6 +18,7 @@ mod test_common {
     use chrono::{DateTime, Utc};
     use opentalk_signaling_core::SignalingRoomId;
     use opentalk_types_signaling::ParticipantId;
+    use opentalk_types_signaling_chat::{MessageId, Scope, state::StoredMessage};
     use pretty_assertions::assert_eq;
 
     use super::*;
@@ -67,6 +68,96 @@ mod test_common {
         );
     }
 
+    pub(super) async fn moderators_history(storage: &mut dyn ChatStorage) {
+        let message = StoredMessage {
+            id: MessageId::generate(),
+            source: SELF,
+            content: "Hello moderators".to_owned(),
+            scope: Scope::Moderators,
+            timestamp: unix_epoch(1000).into(),
+        };
+
+        storage
+            .add_message_to_moderators_history(ROOM, &message, None)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            storage.get_moderators_history(ROOM).await.unwrap(),
+            vec![message]
+        );
+        assert!(storage.get_room_history(ROOM).await.unwrap().is_empty());
+
+        storage.delete_moderators_history(ROOM).await.unwrap();
+
+        assert!(
+            storage
+                .get_moderators_history(ROOM)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    pub(super) async fn history_eviction(storage: &mut dyn ChatStorage) {
+        let messages: Vec<StoredMessage> = (1..=3)
+            .map(|i| StoredMessage {
+                id: MessageId::generate(),
+                source: SELF,
+                content: format!("Message {i}"),
+                scope: Scope::Global,
+                timestamp: unix_epoch(1000 * i).into(),
+            })
+            .collect();
+
+        // The participant has seen the first message, which is going to be evicted
+        storage
+            .set_last_seen_timestamp_global(ROOM, SELF, messages[0].timestamp)
+            .await
+            .unwrap();
+
+        for message in &messages[..2] {
+            storage
+                .add_message_to_room_history(ROOM, message, Some(2))
+                .await
+                .unwrap();
+        }
+
+        // Exactly at the boundary, nothing is evicted
+        assert_eq!(storage.get_room_history(ROOM).await.unwrap().len(), 2);
+
+        storage
+            .add_message_to_room_history(ROOM, &messages[2], Some(2))
+            .await
+            .unwrap();
+
+        let mut history = storage.get_room_history(ROOM).await.unwrap();
+        history.sort_by_key(|message| message.timestamp);
+        assert_eq!(history, messages[1..].to_vec());
+
+        // The last seen timestamp is unaffected and still marks the remaining messages as unseen
+        assert_eq!(
+            storage
+                .get_last_seen_timestamp_global(ROOM, SELF)
+                .await
+                .unwrap(),
+            Some(messages[0].timestamp)
+        );
+
+        // Without a limit the history keeps growing
+        storage
+            .add_message_to_room_history(ROOM, &messages[0], None)
+            .await
+            .unwrap();
+        assert_eq!(storage.get_room_history(ROOM).await.unwrap().len(), 3);
+
+        storage.delete_room_history(ROOM).await.unwrap();
+        storage
+            .delete_last_seen_timestamp_global(ROOM, SELF)
+            .await
+            .unwrap();
+    }
+
     pub(super) async fn last_seen_global_is_personal(storage: &mut dyn ChatStorage) {
         // Set the private last seen timestamps as if BOB and ALICE were the participants in the
         // same room, and ensure this doesn't affect the timestamps of SELF.
@@ -179,4 +270,60 @@ mod test_common {
                 .is_empty()
         );
     }
+
+    pub(super) async fn muted_scopes(storage: &mut dyn ChatStorage) {
+        assert!(
+            storage
+                .get_muted_scopes(ROOM, SELF)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        storage
+            .set_scope_muted(ROOM, SELF, &Scope::Global, true)
+            .await
+            .unwrap();
+        storage
+            .set_scope_muted(ROOM, SELF, &Scope::Private(BOB), true)
+            .await
+            .unwrap();
+        // Muting a scope twice has no effect
+        storage
+            .set_scope_muted(ROOM, SELF, &Scope::Global, true)
+            .await
+            .unwrap();
+
+        let muted = storage.get_muted_scopes(ROOM, SELF).await.unwrap();
+        assert_eq!(muted.len(), 2);
+        assert!(muted.contains(&Scope::Global));
+        assert!(muted.contains(&Scope::Private(BOB)));
+
+        // Muted scopes are personal
+        assert!(
+            storage
+                .get_muted_scopes(ROOM, ALICE)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        storage
+            .set_scope_muted(ROOM, SELF, &Scope::Global, false)
+            .await
+            .unwrap();
+        assert_eq!(
+            storage.get_muted_scopes(ROOM, SELF).await.unwrap(),
+            vec![Scope::Private(BOB)]
+        );
+
+        storage.delete_muted_scopes(ROOM, SELF).await.unwrap();
+        assert!(
+            storage
+                .get_muted_scopes(ROOM, SELF)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
 }
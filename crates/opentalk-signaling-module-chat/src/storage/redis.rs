@@ -16,9 +16,10 @@ use opentalk_types_common::{
     users::{GroupId, GroupName},
 };
 use opentalk_types_signaling::ParticipantId;
-use opentalk_types_signaling_chat::state::StoredMessage;
+use opentalk_types_signaling_chat::{Scope, state::StoredMessage};
 use redis::AsyncCommands as _;
 use redis_args::{FromRedisValue, ToRedisArgs};
+use serde::{Deserialize, Serialize};
 use snafu::{OptionExt as _, Report, ResultExt as _};
 use uuid::Uuid;
 
@@ -44,8 +45,9 @@ impl ChatStorage for RedisConnection {
         &mut self,
         room: SignalingRoomId,
         message: &StoredMessage,
+        max_history_length: Option<usize>,
     ) -> Result<(), SignalingModuleError> {
-        self.lpush(RoomChatHistory { room }, message)
+        push_bounded(self, RoomChatHistory { room }, message, max_history_length)
             .await
             .with_context(|_| RedisSnafu {
                 message: format!("Failed to add message to room chat history, room={room}"),
@@ -64,6 +66,49 @@ impl ChatStorage for RedisConnection {
             })
     }
 
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn get_moderators_history(
+        &mut self,
+        room: SignalingRoomId,
+    ) -> Result<Vec<StoredMessage>, SignalingModuleError> {
+        self.lrange(ModeratorsChatHistory { room }, 0, -1)
+            .await
+            .with_context(|_| RedisSnafu {
+                message: format!("Failed to get moderators chat history: room={room}"),
+            })
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn add_message_to_moderators_history(
+        &mut self,
+        room: SignalingRoomId,
+        message: &StoredMessage,
+        max_history_length: Option<usize>,
+    ) -> Result<(), SignalingModuleError> {
+        push_bounded(
+            self,
+            ModeratorsChatHistory { room },
+            message,
+            max_history_length,
+        )
+        .await
+        .with_context(|_| RedisSnafu {
+            message: format!("Failed to add message to moderators chat history, room={room}"),
+        })
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn delete_moderators_history(
+        &mut self,
+        room: SignalingRoomId,
+    ) -> Result<(), SignalingModuleError> {
+        self.del(ModeratorsChatHistory { room })
+            .await
+            .with_context(|_| RedisSnafu {
+                message: format!("Failed to delete moderators chat history, room={room}"),
+            })
+    }
+
     #[tracing::instrument(level = "debug", skip(self))]
     async fn set_chat_enabled(
         &mut self,
@@ -222,6 +267,57 @@ impl ChatStorage for RedisConnection {
             })
     }
 
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn set_scope_muted(
+        &mut self,
+        room: SignalingRoomId,
+        participant: ParticipantId,
+        scope: &Scope,
+        muted: bool,
+    ) -> Result<(), SignalingModuleError> {
+        let key = RoomParticipantMutedScopes { room, participant };
+        let scope = MutedScope(scope.clone());
+
+        if muted {
+            self.sadd::<_, _, ()>(key, scope).await.context(RedisSnafu {
+                message: "Failed to SADD muted chat scope",
+            })
+        } else {
+            self.srem::<_, _, ()>(key, scope).await.context(RedisSnafu {
+                message: "Failed to SREM muted chat scope",
+            })
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn get_muted_scopes(
+        &mut self,
+        room: SignalingRoomId,
+        participant: ParticipantId,
+    ) -> Result<Vec<Scope>, SignalingModuleError> {
+        let scopes: Vec<MutedScope> = self
+            .smembers(RoomParticipantMutedScopes { room, participant })
+            .await
+            .context(RedisSnafu {
+                message: "Failed to SMEMBERS muted chat scopes",
+            })?;
+
+        Ok(scopes.into_iter().map(|MutedScope(scope)| scope).collect())
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn delete_muted_scopes(
+        &mut self,
+        room: SignalingRoomId,
+        participant: ParticipantId,
+    ) -> Result<(), SignalingModuleError> {
+        self.del(RoomParticipantMutedScopes { room, participant })
+            .await
+            .context(RedisSnafu {
+                message: "Failed to DEL muted chat scopes",
+            })
+    }
+
     #[tracing::instrument(level = "debug", skip(self))]
     async fn add_private_chat_correspondents(
         &mut self,
@@ -286,14 +382,18 @@ impl ChatStorage for RedisConnection {
         room: SignalingRoomId,
         group: GroupId,
         message: &StoredMessage,
+        max_history_length: Option<usize>,
     ) -> Result<(), SignalingModuleError> {
-        self.lpush(RoomGroupChatHistory { room, group }, message)
-            .await
-            .with_context(|_| RedisSnafu {
-                message: format!(
-                    "Failed to add message to room chat history, {room}, group={group}",
-                ),
-            })
+        push_bounded(
+            self,
+            RoomGroupChatHistory { room, group },
+            message,
+            max_history_length,
+        )
+        .await
+        .with_context(|_| RedisSnafu {
+            message: format!("Failed to add message to room chat history, {room}, group={group}",),
+        })
     }
 
     #[tracing::instrument(level = "debug", skip(self))]
@@ -338,10 +438,13 @@ impl ChatStorage for RedisConnection {
         participant_one: ParticipantId,
         participant_two: ParticipantId,
         message: &StoredMessage,
+        max_history_length: Option<usize>,
     ) -> Result<(), SignalingModuleError> {
-        self.lpush(
+        push_bounded(
+            self,
             RoomPrivateChatHistory::new(room, participant_one, participant_two),
             message,
+            max_history_length,
         )
         .await
         .with_context(|_| RedisSnafu {
@@ -450,6 +553,30 @@ impl ChatStorage for RedisConnection {
     }
 }
 
+/// Pushes a message to a history list and trims the list to the newest `max_history_length`
+/// messages. The lists hold the newest message first.
+async fn push_bounded<K: ToRedisArgs + Send + Sync>(
+    redis: &mut RedisConnection,
+    key: K,
+    message: &StoredMessage,
+    max_history_length: Option<usize>,
+) -> redis::RedisResult<()> {
+    let Some(max_history_length) = max_history_length else {
+        return redis.lpush(key, message).await;
+    };
+
+    let last_index = isize::try_from(max_history_length).unwrap_or(isize::MAX) - 1;
+
+    redis::pipe()
+        .atomic()
+        .lpush(&key, message)
+        .ignore()
+        .ltrim(&key, 0, last_index)
+        .ignore()
+        .query_async(redis)
+        .await
+}
+
 /// Key to the chat history inside a room
 #[derive(ToRedisArgs)]
 #[to_redis_args(fmt = "opentalk-signaling:room={room}:chat:history")]
@@ -457,6 +584,13 @@ struct RoomChatHistory {
     room: SignalingRoomId,
 }
 
+/// Key to the moderators chat history inside a room
+#[derive(ToRedisArgs)]
+#[to_redis_args(fmt = "opentalk-signaling:room={room}:chat:moderators:history")]
+struct ModeratorsChatHistory {
+    room: SignalingRoomId,
+}
+
 /// If set to true the chat is enabled
 #[derive(ToRedisArgs)]
 #[to_redis_args(fmt = "opentalk-signaling:room={room}:chat_enabled")]
@@ -494,6 +628,20 @@ struct RoomParticipantLastSeenTimestampGlobal {
     participant: ParticipantId,
 }
 
+/// A set of chat scopes muted by a participant
+#[derive(ToRedisArgs)]
+#[to_redis_args(fmt = "opentalk-signaling:room={room}:participant={participant}:chat:muted_scopes")]
+struct RoomParticipantMutedScopes {
+    room: SignalingRoomId,
+    participant: ParticipantId,
+}
+
+/// A member of the [`RoomParticipantMutedScopes`] set
+#[derive(Serialize, Deserialize, ToRedisArgs, FromRedisValue)]
+#[to_redis_args(serde)]
+#[from_redis_value(serde)]
+struct MutedScope(Scope);
+
 /// A set of private chat correspondents for a participant in a room
 #[derive(ToRedisArgs)]
 #[to_redis_args(fmt = "opentalk-signaling:room={room}:private_chat_correspondents")]
@@ -617,6 +765,18 @@ mod tests {
         test_common::last_seen_global(&mut storage().await).await;
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn moderators_history() {
+        test_common::moderators_history(&mut storage().await).await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn history_eviction() {
+        test_common::history_eviction(&mut storage().await).await;
+    }
+
     #[tokio::test]
     #[serial]
     async fn last_seen_global_is_personal() {
@@ -635,6 +795,12 @@ mod tests {
         test_common::last_seen_private_is_personal(&mut storage().await).await;
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn muted_scopes() {
+        test_common::muted_scopes(&mut storage().await).await;
+    }
+
     #[test]
     fn redis_args() {
         let room_id = RoomId::from(uuid!("ecead1b3-eed0-4cb9-912e-4bb31a3914bd"));
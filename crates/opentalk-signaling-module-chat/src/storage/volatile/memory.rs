@@ -11,13 +11,14 @@ use opentalk_types_common::{
     users::{GroupId, GroupName},
 };
 use opentalk_types_signaling::ParticipantId;
-use opentalk_types_signaling_chat::state::StoredMessage;
+use opentalk_types_signaling_chat::{Scope, state::StoredMessage};
 
 use crate::ParticipantPair;
 
 #[derive(Debug, Clone, Default)]
 pub(super) struct MemoryChatState {
     room_history: HashMap<SignalingRoomId, Vec<StoredMessage>>,
+    moderators_history: HashMap<SignalingRoomId, Vec<StoredMessage>>,
     group_history: HashMap<(SignalingRoomId, GroupId), Vec<StoredMessage>>,
     private_history: HashMap<(SignalingRoomId, ParticipantPair), Vec<StoredMessage>>,
     chats_enabled: HashMap<RoomId, bool>,
@@ -26,6 +27,7 @@ pub(super) struct MemoryChatState {
     last_seen_timestamps_group:
         HashMap<SignalingRoomId, HashMap<ParticipantId, BTreeMap<GroupName, Timestamp>>>,
     last_seen_timestamps_global: HashMap<SignalingRoomId, HashMap<ParticipantId, Timestamp>>,
+    muted_scopes: HashMap<SignalingRoomId, HashMap<ParticipantId, Vec<Scope>>>,
     private_correspondents: HashMap<SignalingRoomId, HashSet<ParticipantPair>>,
     group_participants: HashMap<(SignalingRoomId, GroupId), HashSet<ParticipantId>>,
 }
@@ -44,17 +46,43 @@ impl MemoryChatState {
         &mut self,
         room: SignalingRoomId,
         message: &StoredMessage,
+        max_history_length: Option<usize>,
     ) {
-        self.room_history
-            .entry(room)
-            .or_default()
-            .push(message.clone());
+        push_bounded(
+            self.room_history.entry(room).or_default(),
+            message,
+            max_history_length,
+        );
     }
 
     pub(super) fn delete_room_history(&mut self, room: SignalingRoomId) {
         self.room_history.remove(&room);
     }
 
+    pub(super) fn get_moderators_history(&self, room: SignalingRoomId) -> Vec<StoredMessage> {
+        self.moderators_history
+            .get(&room)
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    pub(super) fn add_message_to_moderators_history(
+        &mut self,
+        room: SignalingRoomId,
+        message: &StoredMessage,
+        max_history_length: Option<usize>,
+    ) {
+        push_bounded(
+            self.moderators_history.entry(room).or_default(),
+            message,
+            max_history_length,
+        );
+    }
+
+    pub(super) fn delete_moderators_history(&mut self, room: SignalingRoomId) {
+        self.moderators_history.remove(&room);
+    }
+
     pub(super) fn set_chat_enabled(&mut self, room: RoomId, enabled: bool) {
         self.chats_enabled.insert(room, enabled);
     }
@@ -179,6 +207,52 @@ impl MemoryChatState {
         }
     }
 
+    pub(super) fn set_scope_muted(
+        &mut self,
+        room: SignalingRoomId,
+        participant: ParticipantId,
+        scope: &Scope,
+        muted: bool,
+    ) {
+        let scopes = self
+            .muted_scopes
+            .entry(room)
+            .or_default()
+            .entry(participant)
+            .or_default();
+        let is_muted = scopes.contains(scope);
+
+        if muted && !is_muted {
+            scopes.push(scope.clone());
+        } else if !muted && is_muted {
+            scopes.retain(|s| s != scope);
+        }
+    }
+
+    pub(super) fn get_muted_scopes(
+        &self,
+        room: SignalingRoomId,
+        participant: ParticipantId,
+    ) -> Vec<Scope> {
+        self.muted_scopes
+            .get(&room)
+            .and_then(|participants| participants.get(&participant).cloned())
+            .unwrap_or_default()
+    }
+
+    pub(super) fn delete_muted_scopes(
+        &mut self,
+        room: SignalingRoomId,
+        participant: ParticipantId,
+    ) {
+        self.muted_scopes
+            .get_mut(&room)
+            .and_then(|participants| participants.remove(&participant));
+        if matches!(self.muted_scopes.get(&room), Some(p) if p.is_empty()) {
+            self.muted_scopes.remove(&room);
+        }
+    }
+
     pub(super) fn add_private_chat_correspondents(
         &mut self,
         room: SignalingRoomId,
@@ -221,11 +295,13 @@ impl MemoryChatState {
         room: SignalingRoomId,
         group: GroupId,
         message: &StoredMessage,
+        max_history_length: Option<usize>,
     ) {
-        self.group_history
-            .entry((room, group))
-            .or_default()
-            .push(message.clone());
+        push_bounded(
+            self.group_history.entry((room, group)).or_default(),
+            message,
+            max_history_length,
+        );
     }
 
     pub(super) fn delete_group_chat_history(&mut self, room: SignalingRoomId, group: GroupId) {
@@ -250,11 +326,15 @@ impl MemoryChatState {
         participant_one: ParticipantId,
         participant_two: ParticipantId,
         message: &StoredMessage,
+        max_history_length: Option<usize>,
     ) {
-        self.private_history
-            .entry((room, ParticipantPair::new(participant_one, participant_two)))
-            .or_default()
-            .push(message.clone());
+        push_bounded(
+            self.private_history
+                .entry((room, ParticipantPair::new(participant_one, participant_two)))
+                .or_default(),
+            message,
+            max_history_length,
+        );
     }
 
     pub(super) fn delete_private_chat_history(
@@ -299,3 +379,17 @@ impl MemoryChatState {
         }
     }
 }
+
+/// Appends a message to a history, evicting the oldest messages exceeding `max_history_length`
+fn push_bounded(
+    history: &mut Vec<StoredMessage>,
+    message: &StoredMessage,
+    max_history_length: Option<usize>,
+) {
+    history.push(message.clone());
+
+    if let Some(max_history_length) = max_history_length {
+        let excess = history.len().saturating_sub(max_history_length);
+        history.drain(..excess);
+    }
+}
@@ -15,7 +15,7 @@ use opentalk_types_common::{
     users::{GroupId, GroupName},
 };
 use opentalk_types_signaling::ParticipantId;
-use opentalk_types_signaling_chat::state::StoredMessage;
+use opentalk_types_signaling_chat::{Scope, state::StoredMessage};
 use parking_lot::RwLock;
 
 use super::memory::MemoryChatState;
@@ -42,8 +42,11 @@ impl ChatStorage for VolatileStaticMemoryStorage {
         &mut self,
         room: SignalingRoomId,
         message: &StoredMessage,
+        max_history_length: Option<usize>,
     ) -> Result<(), SignalingModuleError> {
-        state().write().add_message_to_room_history(room, message);
+        state()
+            .write()
+            .add_message_to_room_history(room, message, max_history_length);
         Ok(())
     }
 
@@ -56,6 +59,36 @@ impl ChatStorage for VolatileStaticMemoryStorage {
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn get_moderators_history(
+        &mut self,
+        room: SignalingRoomId,
+    ) -> Result<Vec<StoredMessage>, SignalingModuleError> {
+        Ok(state().read().get_moderators_history(room))
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn add_message_to_moderators_history(
+        &mut self,
+        room: SignalingRoomId,
+        message: &StoredMessage,
+        max_history_length: Option<usize>,
+    ) -> Result<(), SignalingModuleError> {
+        state()
+            .write()
+            .add_message_to_moderators_history(room, message, max_history_length);
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn delete_moderators_history(
+        &mut self,
+        room: SignalingRoomId,
+    ) -> Result<(), SignalingModuleError> {
+        state().write().delete_moderators_history(room);
+        Ok(())
+    }
+
     #[tracing::instrument(level = "debug", skip(self))]
     async fn set_chat_enabled(
         &mut self,
@@ -185,6 +218,39 @@ impl ChatStorage for VolatileStaticMemoryStorage {
         Ok(())
     }
 
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn set_scope_muted(
+        &mut self,
+        room: SignalingRoomId,
+        participant: ParticipantId,
+        scope: &Scope,
+        muted: bool,
+    ) -> Result<(), SignalingModuleError> {
+        state()
+            .write()
+            .set_scope_muted(room, participant, scope, muted);
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn get_muted_scopes(
+        &mut self,
+        room: SignalingRoomId,
+        participant: ParticipantId,
+    ) -> Result<Vec<Scope>, SignalingModuleError> {
+        Ok(state().read().get_muted_scopes(room, participant))
+    }
+
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn delete_muted_scopes(
+        &mut self,
+        room: SignalingRoomId,
+        participant: ParticipantId,
+    ) -> Result<(), SignalingModuleError> {
+        state().write().delete_muted_scopes(room, participant);
+        Ok(())
+    }
+
     #[tracing::instrument(level = "debug", skip(self))]
     async fn add_private_chat_correspondents(
         &mut self,
@@ -230,10 +296,11 @@ impl ChatStorage for VolatileStaticMemoryStorage {
         room: SignalingRoomId,
         group: GroupId,
         message: &StoredMessage,
+        max_history_length: Option<usize>,
     ) -> Result<(), SignalingModuleError> {
         state()
             .write()
-            .add_message_to_group_chat_history(room, group, message);
+            .add_message_to_group_chat_history(room, group, message, max_history_length);
         Ok(())
     }
 
@@ -266,12 +333,14 @@ impl ChatStorage for VolatileStaticMemoryStorage {
         participant_one: ParticipantId,
         participant_two: ParticipantId,
         message: &StoredMessage,
+        max_history_length: Option<usize>,
     ) -> Result<(), SignalingModuleError> {
         state().write().add_message_to_private_chat_history(
             room,
             participant_one,
             participant_two,
             message,
+            max_history_length,
         );
         Ok(())
     }
@@ -333,6 +402,18 @@ mod tests {
         test_common::last_seen_global(&mut storage().await).await;
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn moderators_history() {
+        test_common::moderators_history(&mut storage().await).await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn history_eviction() {
+        test_common::history_eviction(&mut storage().await).await;
+    }
+
     #[tokio::test]
     #[serial]
     async fn last_seen_global_is_personal() {
@@ -350,4 +431,10 @@ mod tests {
     async fn last_seen_private_is_personal() {
         test_common::last_seen_private_is_personal(&mut storage().await).await;
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn muted_scopes() {
+        test_common::muted_scopes(&mut storage().await).await;
+    }
 }
@@ -3,19 +3,33 @@
 // SPDX-License-Identifier: EUPL-1.2
 
 use chrono::{DateTime, Utc};
-use opentalk_signaling_core::module_tester::{ModuleTester, WsMessageOutgoing};
-use opentalk_signaling_module_chat::Chat;
+use opentalk_signaling_core::{
+    MalformedMessage, MalformedMessageError, MalformedMessageReason,
+    control::{
+        ControlStorageProvider as _,
+        storage::{ControlStorageParticipantAttributes as _, DISPLAY_NAME},
+    },
+    module_tester::{ModuleTester, WsMessageOutgoing},
+};
+use opentalk_signaling_module_chat::{Chat, ChatParams};
 use opentalk_test_util::{ROOM_ID, TestContext, USER_1, USER_2};
-use opentalk_types_common::{time::Timestamp, users::GroupName};
+use opentalk_types_common::{
+    rooms::BreakoutRoomId,
+    time::Timestamp,
+    users::{DisplayName, GroupName},
+};
 use opentalk_types_signaling::{AssociatedParticipant, LeaveReason, Participant, Role};
 use opentalk_types_signaling_chat::{
     Scope,
-    command::{ChatCommand, SendMessage, SetLastSeenTimestamp},
-    event::{ChatEvent, MessageSent},
+    command::{ChatCommand, SendMessage, SetLastSeenTimestamp, SetScopeMuted},
+    event::{ChatEvent, MessageAck, MessageSent},
     peer_state::ChatPeerState,
     state::ChatState,
 };
-use opentalk_types_signaling_control::event::{ControlEvent, Left};
+use opentalk_types_signaling_control::{
+    event::{ControlEvent, Left},
+    state::ControlState,
+};
 use pretty_assertions::assert_eq;
 use serde_json::json;
 use serial_test::serial;
@@ -64,7 +78,7 @@ async fn last_seen_timestamps() {
                 user1.clone(),
                 Role::User,
                 &USER_1.display_name(),
-                (),
+                ChatParams::default(),
             )
             .await
             .unwrap();
@@ -94,6 +108,7 @@ async fn last_seen_timestamps() {
                         "last_seen_timestamp_global": null,
                         "last_seen_timestamps_private": {},
                         "last_seen_timestamps_group": {},
+                        "muted_scopes": [],
                         "room_history": [],
                         "private_history": [],
                     })
@@ -112,7 +127,7 @@ async fn last_seen_timestamps() {
                 user2,
                 Role::User,
                 &USER_2.display_name(),
-                (),
+                ChatParams::default(),
             )
             .await
             .unwrap();
@@ -177,7 +192,7 @@ async fn last_seen_timestamps() {
             user1,
             Role::User,
             &USER_1.display_name(),
-            (),
+            ChatParams::default(),
         )
         .await
         .unwrap();
@@ -216,6 +231,7 @@ async fn last_seen_timestamps() {
                     "last_seen_timestamps_group": {
                         "group1": timestamp_group_raw,
                     },
+                    "muted_scopes": [],
                 })
             );
         }
@@ -268,7 +284,7 @@ async fn common_groups_on_join() {
             user1,
             Role::User,
             &USER_1.display_name(),
-            (),
+            ChatParams::default(),
         )
         .await
         .unwrap();
@@ -303,6 +319,7 @@ async fn common_groups_on_join() {
                     "room_history": [],
                     "last_seen_timestamp_global": null,
                     "last_seen_timestamps_group": {},
+                    "muted_scopes": [],
                     "last_seen_timestamps_private": {},
                 })
             );
@@ -316,7 +333,7 @@ async fn common_groups_on_join() {
             user2,
             Role::User,
             &USER_2.display_name(),
-            (),
+            ChatParams::default(),
         )
         .await
         .unwrap();
@@ -359,6 +376,7 @@ async fn common_groups_on_join() {
                     "private_history": [],
                     "last_seen_timestamp_global": null,
                     "last_seen_timestamps_group": {},
+                    "muted_scopes": [],
                     "last_seen_timestamps_private": {},
                 })
             );
@@ -369,6 +387,113 @@ async fn common_groups_on_join() {
     module_tester.shutdown().await.unwrap();
 }
 
+#[actix_rt::test]
+#[serial]
+async fn common_groups_on_update() {
+    let test_ctx = TestContext::default().await;
+
+    let user1 = test_ctx
+        .db_ctx
+        .create_test_user(
+            USER_1.n,
+            vec![String::from("group1"), String::from("group2")],
+        )
+        .await
+        .unwrap();
+
+    let user2 = test_ctx
+        .db_ctx
+        .create_test_user(
+            USER_2.n,
+            vec![String::from("group1"), String::from("group3")],
+        )
+        .await
+        .unwrap();
+
+    let waiting_room = false;
+    let room = test_ctx
+        .db_ctx
+        .create_test_room(ROOM_ID, user1.id, waiting_room)
+        .await
+        .unwrap();
+
+    let mut module_tester = ModuleTester::<Chat>::new(
+        test_ctx.db_ctx.db.clone(),
+        test_ctx.authz,
+        test_ctx.volatile,
+        room,
+    );
+
+    for (participant, user) in [(USER_1, user1), (USER_2, user2)] {
+        module_tester
+            .join_user(
+                participant.participant_id,
+                user,
+                Role::User,
+                &participant.display_name(),
+                ChatParams::default(),
+            )
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            module_tester
+                .receive_ws_message(&participant.participant_id)
+                .await
+                .unwrap(),
+            WsMessageOutgoing::Control(ControlEvent::JoinSuccess(_))
+        ));
+    }
+
+    assert!(matches!(
+        module_tester
+            .receive_ws_message(&USER_1.participant_id)
+            .await
+            .unwrap(),
+        WsMessageOutgoing::Control(ControlEvent::Joined(_))
+    ));
+
+    // USER_2 changes their display name, the update must still contain the common groups
+    let display_name = DisplayName::from_str_lossy("Renamed");
+    module_tester
+        .volatile
+        .control_storage()
+        .set_global_attribute(
+            USER_2.participant_id,
+            ROOM_ID,
+            DISPLAY_NAME,
+            display_name.clone(),
+        )
+        .await
+        .unwrap();
+    module_tester.raise_hand(&USER_2.participant_id).unwrap();
+
+    let update = module_tester
+        .receive_ws_message(&USER_1.participant_id)
+        .await
+        .unwrap();
+    let WsMessageOutgoing::Control(ControlEvent::Update(participant)) = update else {
+        panic!("expected a participant update, got {update:?}");
+    };
+
+    assert_eq!(participant.id, USER_2.participant_id);
+    assert_eq!(
+        participant
+            .module_data
+            .get::<ControlState>()
+            .unwrap()
+            .unwrap()
+            .display_name,
+        display_name
+    );
+    assert_eq!(
+        serde_json::to_value(participant.module_data.get::<ChatPeerState>().unwrap()).unwrap(),
+        json!({"groups": ["group1"]})
+    );
+
+    module_tester.shutdown().await.unwrap();
+}
+
 #[actix_rt::test]
 #[serial]
 async fn private_chat_history_on_join() {
@@ -406,7 +531,7 @@ async fn private_chat_history_on_join() {
             user1.clone(),
             Role::User,
             &USER_1.display_name(),
-            (),
+            ChatParams::default(),
         )
         .await
         .unwrap();
@@ -432,6 +557,7 @@ async fn private_chat_history_on_join() {
                     "room_history": [],
                     "last_seen_timestamp_global": null,
                     "last_seen_timestamps_group": {},
+                    "muted_scopes": [],
                     "last_seen_timestamps_private": {},
                 })
             );
@@ -445,7 +571,7 @@ async fn private_chat_history_on_join() {
             user2,
             Role::User,
             &USER_2.display_name(),
-            (),
+            ChatParams::default(),
         )
         .await
         .unwrap();
@@ -479,6 +605,7 @@ async fn private_chat_history_on_join() {
                     "private_history": [],
                     "last_seen_timestamp_global": null,
                     "last_seen_timestamps_group": {},
+                    "muted_scopes": [],
                     "last_seen_timestamps_private": {},
                 })
             );
@@ -504,6 +631,7 @@ async fn private_chat_history_on_join() {
             ChatCommand::SendMessage(SendMessage {
                 content: "Low".into(),
                 scope: Scope::Private(USER_2.participant_id),
+                client_msg_id: None,
             }),
         )
         .unwrap();
@@ -520,7 +648,9 @@ async fn private_chat_history_on_join() {
                 id: _,
                 source,
                 content,
-                scope
+                scope,
+                client_msg_id: None,
+                silent: false,
             })) if source == USER_1.participant_id
                && scope == Scope::Private(USER_2.participant_id)
                && content == *"Low"
@@ -548,7 +678,7 @@ async fn private_chat_history_on_join() {
             user1,
             Role::User,
             &USER_1.display_name(),
-            (),
+            ChatParams::default(),
         )
         .await
         .unwrap();
@@ -565,11 +695,13 @@ async fn private_chat_history_on_join() {
             let ChatState {
                 enabled: _,
                 room_history: _,
+                moderators_history: _,
                 groups_history: _,
                 mut private_history,
                 last_seen_timestamp_global: _,
                 last_seen_timestamps_private: _,
                 last_seen_timestamps_group: _,
+                muted_scopes: _,
             } = chat_state.expect("some chat state");
             assert!(private_history.len() == 1);
             let mut correspondence = private_history.pop().unwrap();
@@ -583,3 +715,430 @@ async fn private_chat_history_on_join() {
 
     module_tester.shutdown().await.unwrap();
 }
+
+#[actix_rt::test]
+#[serial]
+async fn main_room_history_survives_breakout_room_cleanup() {
+    let test_ctx = TestContext::default().await;
+
+    let user1 = test_ctx
+        .db_ctx
+        .create_test_user(USER_1.n, vec![])
+        .await
+        .unwrap();
+
+    let user2 = test_ctx
+        .db_ctx
+        .create_test_user(USER_2.n, vec![])
+        .await
+        .unwrap();
+
+    let waiting_room = false;
+    let room = test_ctx
+        .db_ctx
+        .create_test_room(ROOM_ID, user1.id, waiting_room)
+        .await
+        .unwrap();
+
+    let mut main_room = ModuleTester::<Chat>::new(
+        test_ctx.db_ctx.db.clone(),
+        test_ctx.authz.clone(),
+        test_ctx.volatile.clone(),
+        room.clone(),
+    );
+    let mut breakout_room = ModuleTester::<Chat>::new(
+        test_ctx.db_ctx.db.clone(),
+        test_ctx.authz,
+        test_ctx.volatile,
+        room,
+    )
+    .with_breakout_room(BreakoutRoomId::from_u128(1));
+
+    main_room
+        .join_user(
+            USER_1.participant_id,
+            user1,
+            Role::User,
+            &USER_1.display_name(),
+            ChatParams::default(),
+        )
+        .await
+        .unwrap();
+    let _join_success = main_room
+        .receive_ws_message(&USER_1.participant_id)
+        .await
+        .unwrap();
+
+    main_room
+        .send_ws_message(
+            &USER_1.participant_id,
+            ChatCommand::SendMessage(SendMessage {
+                content: "Welcome back".into(),
+                scope: Scope::Global,
+                client_msg_id: None,
+            }),
+        )
+        .unwrap();
+    let _message_sent = main_room
+        .receive_ws_message(&USER_1.participant_id)
+        .await
+        .unwrap();
+
+    breakout_room
+        .join_user(
+            USER_2.participant_id,
+            user2.clone(),
+            Role::User,
+            &USER_2.display_name(),
+            ChatParams::default(),
+        )
+        .await
+        .unwrap();
+    let _join_success = breakout_room
+        .receive_ws_message(&USER_2.participant_id)
+        .await
+        .unwrap();
+
+    // The last participant leaves the breakout room to return to the main room
+    breakout_room.leave(&USER_2.participant_id).await.unwrap();
+
+    main_room
+        .join_user(
+            USER_2.participant_id,
+            user2,
+            Role::User,
+            &USER_2.display_name(),
+            ChatParams::default(),
+        )
+        .await
+        .unwrap();
+
+    let WsMessageOutgoing::Control(ControlEvent::JoinSuccess(join_success)) = main_room
+        .receive_ws_message(&USER_2.participant_id)
+        .await
+        .unwrap()
+    else {
+        panic!("Expected JoinSuccess message")
+    };
+
+    let chat_state = join_success
+        .module_data
+        .get::<ChatState>()
+        .unwrap()
+        .expect("some chat state");
+    assert_eq!(chat_state.room_history.len(), 1);
+    assert_eq!(chat_state.room_history[0].content, "Welcome back");
+
+    breakout_room.shutdown().await.unwrap();
+    main_room.shutdown().await.unwrap();
+}
+
+#[actix_rt::test]
+#[serial]
+async fn client_message_id_is_acknowledged() {
+    let test_ctx = TestContext::default().await;
+
+    let user1 = test_ctx
+        .db_ctx
+        .create_test_user(USER_1.n, vec![])
+        .await
+        .unwrap();
+
+    let user2 = test_ctx
+        .db_ctx
+        .create_test_user(USER_2.n, vec![])
+        .await
+        .unwrap();
+
+    let waiting_room = false;
+    let room = test_ctx
+        .db_ctx
+        .create_test_room(ROOM_ID, user1.id, waiting_room)
+        .await
+        .unwrap();
+
+    let mut module_tester = ModuleTester::<Chat>::new(
+        test_ctx.db_ctx.db.clone(),
+        test_ctx.authz,
+        test_ctx.volatile,
+        room,
+    );
+
+    for (participant, user) in [(USER_1, user1), (USER_2, user2)] {
+        module_tester
+            .join_user(
+                participant.participant_id,
+                user,
+                Role::User,
+                &participant.display_name(),
+                ChatParams::default(),
+            )
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            module_tester
+                .receive_ws_message(&participant.participant_id)
+                .await
+                .unwrap(),
+            WsMessageOutgoing::Control(ControlEvent::JoinSuccess(_))
+        ));
+    }
+
+    // USER_1 is notified about USER_2 joining
+    assert!(matches!(
+        module_tester
+            .receive_ws_message(&USER_1.participant_id)
+            .await
+            .unwrap(),
+        WsMessageOutgoing::Control(ControlEvent::Joined(_))
+    ));
+
+    module_tester
+        .send_ws_message(
+            &USER_1.participant_id,
+            ChatCommand::SendMessage(SendMessage {
+                content: "Hello".into(),
+                scope: Scope::Global,
+                client_msg_id: Some("local-1".into()),
+            }),
+        )
+        .unwrap();
+
+    let ack = module_tester
+        .receive_ws_message(&USER_1.participant_id)
+        .await
+        .unwrap();
+    let WsMessageOutgoing::Module(ChatEvent::MessageAck(MessageAck { id, client_msg_id })) = ack
+    else {
+        panic!("expected a message ack, got {ack:?}");
+    };
+    assert_eq!(client_msg_id, "local-1");
+
+    // The sender receives the client message id with the echoed message
+    let sent = module_tester
+        .receive_ws_message(&USER_1.participant_id)
+        .await
+        .unwrap();
+    assert!(matches!(
+        sent,
+        WsMessageOutgoing::Module(ChatEvent::MessageSent(MessageSent {
+            id: sent_id,
+            client_msg_id: Some(ref client_msg_id),
+            ..
+        })) if sent_id == id && client_msg_id == "local-1"
+    ));
+
+    // Other participants never see the client message id
+    let received = module_tester
+        .receive_ws_message(&USER_2.participant_id)
+        .await
+        .unwrap();
+    assert!(matches!(
+        received,
+        WsMessageOutgoing::Module(ChatEvent::MessageSent(MessageSent {
+            id: received_id,
+            client_msg_id: None,
+            ..
+        })) if received_id == id
+    ));
+
+    module_tester.shutdown().await.unwrap();
+}
+
+#[actix_rt::test]
+#[serial]
+async fn muted_scope_delivers_silent_messages() {
+    let test_ctx = TestContext::default().await;
+
+    let user1 = test_ctx
+        .db_ctx
+        .create_test_user(USER_1.n, vec![])
+        .await
+        .unwrap();
+
+    let user2 = test_ctx
+        .db_ctx
+        .create_test_user(USER_2.n, vec![])
+        .await
+        .unwrap();
+
+    let waiting_room = false;
+    let room = test_ctx
+        .db_ctx
+        .create_test_room(ROOM_ID, user1.id, waiting_room)
+        .await
+        .unwrap();
+
+    let mut module_tester = ModuleTester::<Chat>::new(
+        test_ctx.db_ctx.db.clone(),
+        test_ctx.authz,
+        test_ctx.volatile,
+        room,
+    );
+
+    for (participant, user) in [(USER_1, user1), (USER_2, user2)] {
+        module_tester
+            .join_user(
+                participant.participant_id,
+                user,
+                Role::User,
+                &participant.display_name(),
+                ChatParams::default(),
+            )
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            module_tester
+                .receive_ws_message(&participant.participant_id)
+                .await
+                .unwrap(),
+            WsMessageOutgoing::Control(ControlEvent::JoinSuccess(_))
+        ));
+    }
+
+    // USER_1 is notified about USER_2 joining
+    assert!(matches!(
+        module_tester
+            .receive_ws_message(&USER_1.participant_id)
+            .await
+            .unwrap(),
+        WsMessageOutgoing::Control(ControlEvent::Joined(_))
+    ));
+
+    for muted in [true, false] {
+        module_tester
+            .send_ws_message(
+                &USER_2.participant_id,
+                ChatCommand::SetScopeMuted(SetScopeMuted {
+                    scope: Scope::Global,
+                    muted,
+                }),
+            )
+            .unwrap();
+
+        module_tester
+            .send_ws_message(
+                &USER_1.participant_id,
+                ChatCommand::SendMessage(SendMessage {
+                    content: "Hello".into(),
+                    scope: Scope::Global,
+                    client_msg_id: None,
+                }),
+            )
+            .unwrap();
+
+        // The sender's own message is never silent
+        assert!(matches!(
+            module_tester
+                .receive_ws_message(&USER_1.participant_id)
+                .await
+                .unwrap(),
+            WsMessageOutgoing::Module(ChatEvent::MessageSent(MessageSent { silent: false, .. }))
+        ));
+
+        // The message is still delivered to the participant who muted the scope
+        let received = module_tester
+            .receive_ws_message(&USER_2.participant_id)
+            .await
+            .unwrap();
+        assert!(matches!(
+            received,
+            WsMessageOutgoing::Module(ChatEvent::MessageSent(MessageSent {
+                ref content,
+                silent,
+                ..
+            })) if content == "Hello" && silent == muted
+        ));
+    }
+
+    module_tester.shutdown().await.unwrap();
+}
+
+#[actix_rt::test]
+#[serial]
+async fn malformed_message_is_reported() {
+    let test_ctx = TestContext::default().await;
+
+    let user1 = test_ctx
+        .db_ctx
+        .create_test_user(USER_1.n, vec![])
+        .await
+        .unwrap();
+
+    let waiting_room = false;
+    let room = test_ctx
+        .db_ctx
+        .create_test_room(ROOM_ID, user1.id, waiting_room)
+        .await
+        .unwrap();
+
+    let mut module_tester = ModuleTester::<Chat>::new(
+        test_ctx.db_ctx.db.clone(),
+        test_ctx.authz,
+        test_ctx.volatile,
+        room,
+    );
+
+    module_tester
+        .join_user(
+            USER_1.participant_id,
+            user1,
+            Role::User,
+            &USER_1.display_name(),
+            ChatParams::default(),
+        )
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        module_tester
+            .receive_ws_message(&USER_1.participant_id)
+            .await
+            .unwrap(),
+        WsMessageOutgoing::Control(ControlEvent::JoinSuccess(_))
+    ));
+
+    module_tester
+        .send_raw_ws_message(
+            &USER_1.participant_id,
+            json!({"action": "shout_message", "content": "secret"}),
+        )
+        .unwrap();
+
+    let error = module_tester
+        .receive_ws_message(&USER_1.participant_id)
+        .await
+        .unwrap();
+    assert_eq!(
+        error,
+        WsMessageOutgoing::Malformed(MalformedMessage {
+            error: MalformedMessageError::InvalidMessage,
+            field: None,
+            reason: MalformedMessageReason::UnknownVariant,
+        })
+    );
+
+    // The connection stays usable after a malformed message
+    module_tester
+        .send_ws_message(
+            &USER_1.participant_id,
+            ChatCommand::SendMessage(SendMessage {
+                content: "Hello".into(),
+                scope: Scope::Global,
+                client_msg_id: None,
+            }),
+        )
+        .unwrap();
+
+    assert!(matches!(
+        module_tester
+            .receive_ws_message(&USER_1.participant_id)
+            .await
+            .unwrap(),
+        WsMessageOutgoing::Module(ChatEvent::MessageSent(_))
+    ));
+
+    module_tester.shutdown().await.unwrap();
+}
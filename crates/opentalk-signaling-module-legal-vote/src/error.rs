@@ -5,7 +5,9 @@
 use opentalk_database::DatabaseError;
 use opentalk_signaling_core::{ObjectStorageError, SignalingModuleError, assets::AssetError};
 use opentalk_types_signaling::ParticipantId;
-use opentalk_types_signaling_legal_vote::event::{ErrorKind as TypesErrorKind, GuestParticipants};
+use opentalk_types_signaling_legal_vote::event::{
+    ErrorKind as TypesErrorKind, GuestParticipants, MaxAllowedParticipants,
+};
 use snafu::Snafu;
 
 /// A legal vote error
@@ -43,6 +45,12 @@ pub(crate) enum ErrorKind {
     InsufficientPermissions,
     #[snafu(display("The requesting user has exceeded their storage"))]
     StorageExceeded,
+    #[snafu(display("The vote kind is not allowed by the policy of the tenant"))]
+    VoteKindNotAllowed,
+    #[snafu(display("The allowlist exceeds the maximum of {max} participants"))]
+    TooManyAllowedParticipants { max: usize },
+    #[snafu(display("The scheduled start time of the vote lies in the past"))]
+    StartTimeInPast,
 }
 
 impl From<ErrorKind> for TypesErrorKind {
@@ -57,6 +65,11 @@ impl From<ErrorKind> for TypesErrorKind {
             ErrorKind::PermissionError => TypesErrorKind::PermissionError,
             ErrorKind::InsufficientPermissions => TypesErrorKind::InsufficientPermissions,
             ErrorKind::StorageExceeded => TypesErrorKind::StorageExceeded,
+            ErrorKind::VoteKindNotAllowed => TypesErrorKind::VoteKindNotAllowed,
+            ErrorKind::TooManyAllowedParticipants { max } => {
+                TypesErrorKind::TooManyAllowedParticipants(MaxAllowedParticipants { max })
+            }
+            ErrorKind::StartTimeInPast => TypesErrorKind::StartTimeInPast,
         }
     }
 }
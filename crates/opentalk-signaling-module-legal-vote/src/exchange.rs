@@ -4,7 +4,10 @@
 
 use opentalk_types_signaling::ParticipantId;
 use opentalk_types_signaling_legal_vote::{
-    event::{Canceled, FinalResults, PdfAsset, ReportedIssue, StopKind, Stopped},
+    event::{
+        AwaitingConfirmation, Canceled, FinalResults, PdfAsset, ProvisionalResults, ReportedIssue,
+        Scheduled, StopKind, Stopped,
+    },
     parameters::Parameters,
     token::Token,
     vote::{LegalVoteId, VoteOption},
@@ -15,14 +18,23 @@ use serde::{Deserialize, Serialize};
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum Event {
+    /// A vote has been scheduled to start at a later point in time
+    Scheduled(Scheduled),
     /// A new vote has started
     Start(Parameters),
     /// A participant has successfully voted, the message gets dispatched to the underlying user id
     Voted(VoteSuccess),
     /// A vote has been stopped
     Stop(Stopped),
+    /// A vote has been stopped, its results are final once they have been confirmed
+    AwaitingConfirmation(AwaitingConfirmation),
+    /// The provisional results of a vote awaiting confirmation, only sent to moderators
+    ProvisionalResults(ProvisionalResults),
     /// A vote has been canceled
     Cancel(Canceled),
+    /// The initiator of the vote left, the vote is canceled unless they rejoin within the grace
+    /// period
+    InitiatorGraceStarted(LegalVoteId),
     /// The results for a vote have changed
     Update(VoteUpdate),
     /// A participant reported an issue
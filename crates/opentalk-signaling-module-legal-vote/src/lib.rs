@@ -11,60 +11,67 @@
 
 use std::{
     collections::{BTreeMap, BTreeSet, HashMap},
-    path::Path,
+    path::{Path, PathBuf},
     sync::Arc,
 };
 
+use arc_swap::ArcSwap;
 use bytes::Bytes;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, TimeDelta, Utc};
 use chrono_tz::Tz;
 use either::Either;
 use error::LegalVoteError;
 use futures::{FutureExt, stream::once};
 use kustos::{Authz, Resource, prelude::AccessMethod};
+use opentalk_controller_settings::{
+    ReportsVerificationQrCode, Settings, settings_file::LegalVoteKind,
+};
 use opentalk_database::Db;
 use opentalk_db_storage::{
+    events::Event as DbEvent,
     module_resources::{Filter, ModuleResource, NewModuleResource},
     rooms::Room,
+    tenants::{OidcTenantId, Tenant},
     users::User,
 };
+use opentalk_report_generation::ReportTimezoneCandidates;
 use opentalk_signaling_core::{
-    ChunkFormat, DestroyContext, Event, InitContext, ModuleContext, ObjectStorage, Participant,
-    SerdeJsonSnafu, SignalingModule, SignalingModuleError, SignalingModuleInitData,
-    SignalingRoomId, VolatileStorage,
-    assets::{NewAssetFileName, save_asset},
+    DestroyContext, Event, InitContext, ModuleContext, ObjectStorage, Participant,
+    PdfGenerationLimiter, SerdeJsonSnafu, SignalingMetrics, SignalingModule, SignalingModuleError,
+    SignalingModuleInitData, SignalingRoomId, VolatileStorage,
+    assets::{ASSET_RETENTION_TAG_DEFAULT, NewAssetFileName, save_asset_with_retry},
     control::{
         self,
         storage::{ControlStorageParticipantAttributes, LocalRoomAttributeId, USER_ID},
     },
 };
 use opentalk_types_common::{
-    assets::FileExtension,
+    assets::{AssetId, FileExtension},
     modules::ModuleId,
     tenants::TenantId,
-    time::Timestamp,
+    time::{TimeZone, Timestamp},
     users::{DisplayName, UserId},
 };
 use opentalk_types_signaling::{ParticipantId, Role};
 use opentalk_types_signaling_legal_vote::{
     MODULE_ID,
     cancel::{CancelReason, CustomCancelReason},
-    command::{Cancel, LegalVoteCommand, Stop, Vote},
+    command::{Cancel, ConfirmResults, GetNonVoters, LegalVoteCommand, RejectResults, Stop, Vote},
     event::{
-        Canceled, ErrorKind, FinalResults, LegalVoteEvent, PdfAsset, ReportedIssue, Response,
-        Results, StopKind, Stopped, VoteFailed, VoteResponse, VoteResults, VoteSuccess,
-        VotingRecord,
+        AwaitingConfirmation, Canceled, ErrorKind, FinalResults, LegalVoteEvent, NonVoters,
+        PdfAsset, ProvisionalResults, ReportedIssue, Response, Results, Scheduled, StopKind,
+        Stopped, VoteFailed, VoteResponse, VoteResults, VoteSuccess, VotingRecord,
     },
     invalid::Invalid,
     parameters::Parameters,
     state::LegalVoteState,
     tally::Tally,
     token::Token,
-    user_parameters::UserParameters,
+    user_parameters::{self, UserParameters},
     vote::{LegalVoteId, VoteKind, VoteOption},
 };
-use snafu::ResultExt;
-use storage::{LegalVoteStorage, VoteScriptResult, VoteStatus};
+use snafu::{Report, ResultExt};
+use storage::{LegalVoteStorage, PendingConfirmation, VoteScriptResult, VoteStatus};
 use tokio::time::sleep;
 
 use crate::{
@@ -79,9 +86,16 @@ mod report;
 pub mod exchange;
 pub mod storage;
 
-/// A TimerEvent used for the vote expiration feature
-pub struct TimerEvent {
-    legal_vote_id: LegalVoteId,
+/// Timer events of the legal vote module
+pub enum TimerEvent {
+    /// The duration of a vote has expired
+    Expired { legal_vote_id: LegalVoteId },
+
+    /// The start time of a scheduled vote has been reached
+    ScheduledStart { legal_vote_id: LegalVoteId },
+
+    /// The grace period of a vote whose initiator left has expired
+    InitiatorGraceExpired { legal_vote_id: LegalVoteId },
 }
 
 trait LegalVoteStorageProvider {
@@ -97,6 +111,62 @@ impl LegalVoteStorageProvider for VolatileStorage {
     }
 }
 
+/// The parameters of the legal vote module
+#[derive(Debug, Default)]
+pub struct LegalVoteParams {
+    /// The legal vote settings, swapped when the settings are reloaded
+    settings: ArcSwap<opentalk_controller_settings::LegalVote>,
+
+    /// The limiter for the vote protocol PDF generation, shared with other modules
+    pdf_generation_limiter: PdfGenerationLimiter,
+
+    /// The directory into which the vote protocol PDF generation data is dumped
+    report_dump_path: Option<PathBuf>,
+
+    /// The directory containing additional fonts for the vote protocol PDF generation
+    report_font_path: Option<PathBuf>,
+
+    /// The QR code linking the vote protocol to a verification page, if configured
+    verification_qr_code: Option<ReportsVerificationQrCode>,
+
+    /// The default timezone of the vote protocols per tenant, keyed by the OIDC tenant id
+    tenant_timezones: BTreeMap<String, TimeZone>,
+}
+
+impl LegalVoteParams {
+    /// Create the parameters using the given shared [`PdfGenerationLimiter`]
+    pub fn new(
+        settings: opentalk_controller_settings::LegalVote,
+        pdf_generation_limiter: PdfGenerationLimiter,
+        report_dump_path: Option<PathBuf>,
+        report_font_path: Option<PathBuf>,
+        verification_qr_code: Option<ReportsVerificationQrCode>,
+        tenant_timezones: BTreeMap<String, TimeZone>,
+    ) -> Self {
+        Self {
+            settings: ArcSwap::from_pointee(settings),
+            pdf_generation_limiter,
+            report_dump_path,
+            report_font_path,
+            verification_qr_code,
+            tenant_timezones,
+        }
+    }
+}
+
+impl From<opentalk_controller_settings::LegalVote> for LegalVoteParams {
+    fn from(settings: opentalk_controller_settings::LegalVote) -> Self {
+        Self::new(
+            settings,
+            PdfGenerationLimiter::default(),
+            None,
+            None,
+            None,
+            BTreeMap::new(),
+        )
+    }
+}
+
 /// The legal vote [`SignalingModule`]
 ///
 /// Holds a database interface and information about the underlying user & room. Vote information is
@@ -105,17 +175,23 @@ pub struct LegalVote {
     db: Arc<Db>,
     storage: Arc<ObjectStorage>,
     authz: Arc<Authz>,
+    params: Arc<LegalVoteParams>,
     participant_id: ParticipantId,
-    user_id: UserId,
+    /// The user id of this participant, guests have none
+    user_id: Option<UserId>,
     tenant_id: TenantId,
+    oidc_tenant_id: OidcTenantId,
     room_id: SignalingRoomId,
+
+    /// Votes scheduled by this participant which have not been started yet
+    scheduled_votes: BTreeMap<LegalVoteId, UserParameters>,
 }
 
 #[async_trait::async_trait(?Send)]
 impl SignalingModule for LegalVote {
     const NAMESPACE: ModuleId = MODULE_ID;
 
-    type Params = ();
+    type Params = Arc<LegalVoteParams>;
 
     type Incoming = LegalVoteCommand;
     type Outgoing = LegalVoteEvent;
@@ -127,22 +203,69 @@ impl SignalingModule for LegalVote {
 
     async fn init(
         ctx: InitContext<'_, Self>,
-        _params: &Self::Params,
+        params: &Self::Params,
         _protocol: &'static str,
     ) -> Result<Option<Self>, SignalingModuleError> {
-        if let Participant::User(user) = ctx.participant() {
-            Ok(Some(Self {
-                db: ctx.db().clone(),
-                storage: ctx.storage().clone(),
-                authz: ctx.authz().clone(),
-                participant_id: ctx.participant_id(),
-                user_id: user.id,
-                tenant_id: user.tenant_id,
-                room_id: ctx.room_id(),
-            }))
-        } else {
-            Ok(None)
+        // Guests can only take part in votes which allow guests
+        let (user_id, tenant_id) = match ctx.participant() {
+            Participant::User(user) => (Some(user.id), user.tenant_id),
+            Participant::Guest => (None, ctx.room().tenant_id),
+            Participant::Sip | Participant::Recorder => return Ok(None),
+        };
+
+        let tenant = Tenant::get(&mut ctx.db().get_conn().await?, tenant_id).await?;
+
+        Ok(Some(Self {
+            db: ctx.db().clone(),
+            storage: ctx.storage().clone(),
+            authz: ctx.authz().clone(),
+            params: params.clone(),
+            participant_id: ctx.participant_id(),
+            user_id,
+            tenant_id,
+            oidc_tenant_id: tenant.oidc_tenant_id,
+            room_id: ctx.room_id(),
+            scheduled_votes: BTreeMap::new(),
+        }))
+    }
+
+    async fn summarize_state(
+        mut volatile: VolatileStorage,
+        room: SignalingRoomId,
+    ) -> Result<Option<serde_json::Value>, SignalingModuleError> {
+        let storage = volatile.storage();
+
+        let current_vote = match storage.current_vote_get(room).await? {
+            Some(legal_vote_id) => {
+                storage
+                    .parameter_get(room, legal_vote_id)
+                    .await?
+                    .map(|parameters| {
+                        serde_json::json!({
+                            "legal_vote_id": legal_vote_id,
+                            "name": parameters.inner.name,
+                            "kind": parameters.inner.kind,
+                            "start_time": parameters.start_time,
+                            "end_time": parameters.end_time,
+                        })
+                    })
+            }
+            None => None,
+        };
+
+        let pending_confirmation = storage
+            .pending_confirmation_get(room)
+            .await?
+            .map(|pending| pending.legal_vote_id);
+
+        if current_vote.is_none() && pending_confirmation.is_none() {
+            return Ok(None);
         }
+
+        Ok(Some(serde_json::json!({
+            "current_vote": current_vote,
+            "pending_confirmation": pending_confirmation,
+        })))
     }
 
     async fn on_event(
@@ -159,9 +282,29 @@ impl SignalingModule for LegalVote {
                     .await?;
 
                 *frontend_data = Some(
-                    load_from_history(ctx.volatile.clone(), self.room_id, current_vote).await?,
+                    load_from_history(
+                        ctx.volatile.clone(),
+                        self.room_id,
+                        current_vote,
+                        self.allowed_vote_kinds(),
+                        self.max_allowed_participants(),
+                        self.enforced_create_pdf(),
+                    )
+                    .await?,
                 );
 
+                if let Some(pending) = ctx
+                    .volatile
+                    .storage()
+                    .pending_confirmation_get(self.room_id)
+                    .await?
+                {
+                    // Allows moderators to confirm the results after reconnecting
+                    if let Err(error) = self.send_pending_confirmation(&mut ctx, pending).await {
+                        self.handle_error(&mut ctx, error)?;
+                    }
+                }
+
                 let parameters = match current_vote {
                     Some(vote_id) => {
                         ctx.volatile
@@ -172,14 +315,15 @@ impl SignalingModule for LegalVote {
                     None => return Ok(()),
                 };
 
-                let Some(parameters) = parameters else {
+                let Some(mut parameters) = parameters else {
                     return Ok(());
                 };
 
-                if parameters
-                    .allowed_users
-                    .is_some_and(|users| users.contains(&self.user_id))
-                {
+                if let Err(error) = self.resume_initiator(&mut ctx, &mut parameters).await {
+                    self.handle_error(&mut ctx, error)?;
+                }
+
+                if let Some(user_id) = self.allowed_user_id(&parameters) {
                     let entry = if parameters.inner.kind.is_hidden() {
                         db_protocol::v1::ProtocolEntry::new(db_protocol::v1::VoteEvent::UserJoined(
                             None.into(),
@@ -187,7 +331,7 @@ impl SignalingModule for LegalVote {
                     } else {
                         db_protocol::v1::ProtocolEntry::new(db_protocol::v1::VoteEvent::UserJoined(
                             Some(db_protocol::v1::UserInfo {
-                                issuer: self.user_id,
+                                issuer: user_id,
                                 participant_id: self.participant_id,
                             })
                             .into(),
@@ -216,11 +360,21 @@ impl SignalingModule for LegalVote {
                     self.handle_error(&mut ctx, error)?;
                 }
             }
-            Event::Ext(timer_event) => {
+            Event::Ext(TimerEvent::ScheduledStart { legal_vote_id }) => {
+                if let Err(error) = self.start_scheduled_vote(&mut ctx, legal_vote_id).await {
+                    self.handle_error(&mut ctx, error)?;
+                }
+            }
+            Event::Ext(TimerEvent::InitiatorGraceExpired { legal_vote_id }) => {
+                if let Err(error) = self.expire_initiator_grace(&mut ctx, legal_vote_id).await {
+                    self.handle_error(&mut ctx, error)?;
+                }
+            }
+            Event::Ext(TimerEvent::Expired { legal_vote_id }) => {
                 let vote_status = ctx
                     .volatile
                     .storage()
-                    .get_vote_status(self.room_id, timer_event.legal_vote_id)
+                    .get_vote_status(self.room_id, legal_vote_id)
                     .await?;
 
                 match vote_status {
@@ -232,12 +386,7 @@ impl SignalingModule for LegalVote {
                         );
 
                         if let Err(error) = self
-                            .end_vote(
-                                &mut ctx,
-                                timer_event.legal_vote_id,
-                                expired_entry,
-                                stop_kind,
-                            )
+                            .end_vote(&mut ctx, legal_vote_id, expired_entry, stop_kind)
                             .await
                         {
                             match error {
@@ -310,6 +459,27 @@ impl SignalingModule for LegalVote {
                 }
             }
 
+            match storage.pending_confirmation_get(self.room_id).await {
+                Ok(Some(pending)) => {
+                    if let Err(e) = self
+                        .reject_results(storage, pending.legal_vote_id, CancelReason::RoomDestroyed)
+                        .await
+                    {
+                        log::error!(
+                            "Failed to reject results pending confirmation while destroying vote module {:?}",
+                            e
+                        );
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    log::error!(
+                        "Failed to get pending confirmation while destroying vote module, {:?}",
+                        e
+                    );
+                }
+            }
+
             if let Err(e) = self.cleanup_room(storage).await {
                 log::error!("Failed to cleanup room on destroy, {:?}", e)
             }
@@ -317,9 +487,20 @@ impl SignalingModule for LegalVote {
     }
 
     async fn build_params(
-        _init: SignalingModuleInitData,
+        init: SignalingModuleInitData,
     ) -> Result<Option<Self::Params>, SignalingModuleError> {
-        Ok(Some(()))
+        Ok(Some(Arc::new(LegalVoteParams::new(
+            init.startup_settings.legal_vote.clone(),
+            init.pdf_generation_limiter,
+            init.report_dump_path,
+            init.report_font_path,
+            init.startup_settings.reports.verification_qr_code.clone(),
+            init.startup_settings.reports.tenant_timezones.clone(),
+        ))))
+    }
+
+    fn reload_params(params: &Self::Params, settings: &Settings) {
+        params.settings.store(Arc::new(settings.legal_vote.clone()));
     }
 }
 
@@ -356,6 +537,16 @@ impl LegalVote {
                     return Err(error::ErrorKind::InsufficientPermissions.into());
                 }
 
+                if storage
+                    .scheduled_vote_remove(self.room_id, legal_vote_id)
+                    .await?
+                {
+                    self.cancel_scheduled_vote(ctx, legal_vote_id, CancelReason::Custom(reason))
+                        .await?;
+
+                    return Ok(());
+                }
+
                 let entry = self
                     .cancel_vote(storage, legal_vote_id, reason.clone())
                     .await?;
@@ -363,7 +554,7 @@ impl LegalVote {
                 self.save_protocol_in_database(storage, legal_vote_id)
                     .await?;
 
-                ctx.exchange_publish(
+                ctx.exchange_publish_critical(
                     control::exchange::current_room_all_participants(self.room_id),
                     exchange::Event::Cancel(Canceled {
                         legal_vote_id,
@@ -382,8 +573,68 @@ impl LegalVote {
                     .ok_or(error::ErrorKind::InvalidVoteId)?;
 
                 if parameters.inner.create_pdf {
-                    self.save_pdf(ctx, legal_vote_id, self.user_id, parameters.inner.timezone)
-                        .await?
+                    self.save_pdf(
+                        ctx,
+                        legal_vote_id,
+                        self.user_id()?,
+                        parameters.inner.timezone,
+                    )
+                    .await?
+                }
+            }
+            LegalVoteCommand::ConfirmResults(ConfirmResults { legal_vote_id }) => {
+                if !matches!(ctx.role(), Role::Moderator) {
+                    return Err(error::ErrorKind::InsufficientPermissions.into());
+                }
+
+                let pending = self
+                    .take_pending_confirmation(storage, legal_vote_id)
+                    .await?;
+
+                self.finalize_vote(ctx, legal_vote_id, pending.kind, pending.end_time)
+                    .await?;
+            }
+            LegalVoteCommand::RejectResults(RejectResults {
+                legal_vote_id,
+                reason,
+            }) => {
+                if !matches!(ctx.role(), Role::Moderator) {
+                    return Err(error::ErrorKind::InsufficientPermissions.into());
+                }
+
+                self.take_pending_confirmation(storage, legal_vote_id)
+                    .await?;
+
+                let reason = CancelReason::Custom(reason);
+
+                let entry = self
+                    .reject_results(storage, legal_vote_id, reason.clone())
+                    .await?;
+
+                ctx.exchange_publish_critical(
+                    control::exchange::current_room_all_participants(self.room_id),
+                    exchange::Event::Cancel(Canceled {
+                        legal_vote_id,
+                        reason,
+                        end_time: entry
+                            .timestamp
+                            .expect("Missing timestamp for reject results ProtocolEntry"),
+                    }),
+                );
+
+                let parameters = storage
+                    .parameter_get(self.room_id, legal_vote_id)
+                    .await?
+                    .ok_or(error::ErrorKind::InvalidVoteId)?;
+
+                if parameters.inner.create_pdf {
+                    self.save_pdf(
+                        ctx,
+                        legal_vote_id,
+                        self.user_id()?,
+                        parameters.inner.timezone,
+                    )
+                    .await?
                 }
             }
             LegalVoteCommand::Vote(vote_message) => {
@@ -395,9 +646,19 @@ impl LegalVote {
                     consumed_token,
                 }) = vote_response.response
                 {
-                    // Send a vote success message to all participants that have the same user id
+                    // Send a vote success message to all participants that have the same user id,
+                    // guests only have a single participant
+                    let routing_key = match self.user_id {
+                        Some(user_id) => {
+                            control::exchange::current_room_by_user_id(self.room_id, user_id)
+                        }
+                        None => control::exchange::current_room_by_participant_id(
+                            self.room_id,
+                            self.participant_id,
+                        ),
+                    };
                     ctx.exchange_publish(
-                        control::exchange::current_room_by_user_id(self.room_id, self.user_id),
+                        routing_key,
                         exchange::Event::Voted(exchange::VoteSuccess {
                             legal_vote_id: vote_message.legal_vote_id,
                             vote_option,
@@ -445,18 +706,15 @@ impl LegalVote {
                     .await?
                     .ok_or(error::ErrorKind::InvalidVoteId)?;
 
-                if !parameters
-                    .allowed_users
-                    .is_some_and(|users| users.contains(&self.user_id))
-                {
+                let Some(user_id) = self.allowed_user_id(&parameters) else {
                     return Err(error::ErrorKind::InsufficientPermissions.into());
-                }
+                };
 
                 let user_info = if parameters.inner.kind.is_hidden() {
                     None
                 } else {
                     Some(db_protocol::v1::UserInfo {
-                        issuer: self.user_id,
+                        issuer: user_id,
                         participant_id: self.participant_id,
                     })
                 };
@@ -503,8 +761,28 @@ impl LegalVote {
                     return Err(error::ErrorKind::InvalidVoteId.into());
                 }
 
-                self.save_pdf(ctx, generate.legal_vote_id, self.user_id, generate.timezone)
-                    .await?;
+                self.save_pdf(
+                    ctx,
+                    generate.legal_vote_id,
+                    self.user_id()?,
+                    generate.timezone,
+                )
+                .await?;
+            }
+            LegalVoteCommand::GetNonVoters(GetNonVoters { legal_vote_id }) => {
+                if !matches!(ctx.role(), Role::Moderator) {
+                    return Err(error::ErrorKind::InsufficientPermissions.into());
+                }
+
+                let participants = self.get_non_voters(storage, legal_vote_id).await?;
+
+                ctx.ws_send(LegalVoteEvent::NonVoters(NonVoters {
+                    legal_vote_id,
+                    participants,
+                }));
+            }
+            LegalVoteCommand::GetState => {
+                self.send_state(ctx).await?;
             }
         }
         Ok(())
@@ -517,12 +795,23 @@ impl LegalVote {
         event: exchange::Event,
     ) -> Result<(), LegalVoteError> {
         match event {
+            exchange::Event::Scheduled(scheduled) => {
+                ctx.ws_send(LegalVoteEvent::Scheduled(scheduled));
+            }
             exchange::Event::Start(parameters) => {
                 ctx.ws_send(LegalVoteEvent::Started(parameters));
             }
             exchange::Event::Stop(stopped) => {
                 ctx.ws_send(LegalVoteEvent::Stopped(stopped));
             }
+            exchange::Event::AwaitingConfirmation(awaiting_confirmation) => {
+                ctx.ws_send(LegalVoteEvent::AwaitingConfirmation(awaiting_confirmation));
+            }
+            exchange::Event::ProvisionalResults(provisional_results) => {
+                if matches!(ctx.role(), Role::Moderator) {
+                    ctx.ws_send(LegalVoteEvent::ProvisionalResults(provisional_results));
+                }
+            }
             exchange::Event::Voted(vote_success) => {
                 ctx.ws_send(LegalVoteEvent::Voted(VoteResponse {
                     legal_vote_id: vote_success.legal_vote_id,
@@ -536,6 +825,14 @@ impl LegalVote {
             exchange::Event::Cancel(cancel) => {
                 ctx.ws_send(LegalVoteEvent::Canceled(cancel));
             }
+            exchange::Event::InitiatorGraceStarted(legal_vote_id) => {
+                let grace_period = self.params.settings.load().initiator_grace_period;
+
+                ctx.add_event_stream(once(
+                    sleep(grace_period)
+                        .map(move |_| TimerEvent::InitiatorGraceExpired { legal_vote_id }),
+                ));
+            }
             exchange::Event::Update(update) => {
                 let results = self
                     .get_vote_results(ctx.volatile.storage(), update.legal_vote_id)
@@ -562,23 +859,175 @@ impl LegalVote {
     async fn handle_start_message(
         &mut self,
         ctx: &mut ModuleContext<'_, LegalVote>,
-        incoming_parameters: UserParameters,
+        mut incoming_parameters: UserParameters,
     ) -> Result<(), LegalVoteError> {
-        let legal_vote_id = self
-            .new_vote_in_database()
+        incoming_parameters.enable_abstain = Some(
+            incoming_parameters
+                .enable_abstain
+                .unwrap_or_else(|| self.enable_abstain_by_default()),
+        );
+
+        if let Some(create_pdf) = self.enforced_create_pdf() {
+            incoming_parameters.create_pdf = create_pdf;
+        }
+
+        // Checked before anything is stored, a forbidden vote must not leave any traces
+        if !self.is_vote_kind_allowed(&incoming_parameters.kind) {
+            return Err(error::ErrorKind::VoteKindNotAllowed.into());
+        }
+
+        let max_allowed_participants = self.max_allowed_participants();
+        if incoming_parameters.allowed_participants.len() > max_allowed_participants {
+            return Err(error::ErrorKind::TooManyAllowedParticipants {
+                max: max_allowed_participants,
+            }
+            .into());
+        }
+
+        if let Some(start_at) = incoming_parameters.start_at {
+            return self.schedule_vote(ctx, incoming_parameters, start_at).await;
+        }
+
+        let legal_vote_id = self.new_legal_vote_id(incoming_parameters.sandbox).await?;
+
+        self.start_vote(ctx, legal_vote_id, incoming_parameters)
+            .await
+    }
+
+    /// Create the id for a new vote
+    ///
+    /// Sandbox votes only live in the volatile storage and never touch the database.
+    async fn new_legal_vote_id(&self, sandbox: bool) -> Result<LegalVoteId, LegalVoteError> {
+        if sandbox {
+            return Ok(LegalVoteId::generate());
+        }
+
+        self.new_vote_in_database(self.user_id()?)
             .await
-            .whatever_context::<_, LegalVoteError>("Failed to create new vote in database")?;
+            .whatever_context::<_, LegalVoteError>("Failed to create new vote in database")
+    }
+
+    /// Schedule a vote to be started at `start_at`
+    ///
+    /// The vote is started by this participant's module instance, so the schedule is canceled when
+    /// this participant leaves before the vote started.
+    async fn schedule_vote(
+        &mut self,
+        ctx: &mut ModuleContext<'_, LegalVote>,
+        incoming_parameters: UserParameters,
+        start_at: Timestamp,
+    ) -> Result<(), LegalVoteError> {
+        let now = ctx.timestamp();
+        if start_at <= now {
+            return Err(error::ErrorKind::StartTimeInPast.into());
+        }
+        let delay = (*start_at - *now)
+            .to_std()
+            .expect("start time lies in the future");
+
+        let legal_vote_id = self.new_legal_vote_id(incoming_parameters.sandbox).await?;
+
+        ctx.volatile
+            .storage()
+            .scheduled_vote_add(self.room_id, legal_vote_id)
+            .await?;
+
+        ctx.add_event_stream(once(
+            sleep(delay).map(move |_| TimerEvent::ScheduledStart { legal_vote_id }),
+        ));
+
+        self.scheduled_votes
+            .insert(legal_vote_id, incoming_parameters.clone());
+
+        ctx.exchange_publish(
+            control::exchange::current_room_all_participants(self.room_id),
+            exchange::Event::Scheduled(Scheduled {
+                legal_vote_id,
+                initiator_id: self.participant_id,
+                start_at,
+                parameters: incoming_parameters,
+            }),
+        );
+
+        Ok(())
+    }
+
+    /// Start a scheduled vote once its start time has been reached
+    async fn start_scheduled_vote(
+        &mut self,
+        ctx: &mut ModuleContext<'_, LegalVote>,
+        legal_vote_id: LegalVoteId,
+    ) -> Result<(), LegalVoteError> {
+        let Some(parameters) = self.scheduled_votes.remove(&legal_vote_id) else {
+            log::warn!("Legal vote timer contains an unknown scheduled vote id");
+            return Ok(());
+        };
+
+        if !ctx
+            .volatile
+            .storage()
+            .scheduled_vote_remove(self.room_id, legal_vote_id)
+            .await?
+        {
+            // The scheduled vote got canceled in the meantime
+            return Ok(());
+        }
+
+        self.start_vote(ctx, legal_vote_id, parameters).await
+    }
+
+    /// Cancel a vote which has been removed from the scheduled votes before it was started
+    async fn cancel_scheduled_vote(
+        &mut self,
+        ctx: &mut ModuleContext<'_, LegalVote>,
+        legal_vote_id: LegalVoteId,
+        reason: CancelReason,
+    ) -> Result<(), LegalVoteError> {
+        let parameters = self.scheduled_votes.remove(&legal_vote_id);
+
+        // The vote never started, so the empty protocol of a non-sandbox vote is not kept
+        if !parameters.is_some_and(|parameters| parameters.sandbox) {
+            ModuleResource::delete(
+                &mut self.db.get_conn().await?,
+                Filter::new().with_id(legal_vote_id.into()),
+            )
+            .await?;
+        }
+
+        ctx.exchange_publish_critical(
+            control::exchange::current_room_all_participants(self.room_id),
+            exchange::Event::Cancel(Canceled {
+                legal_vote_id,
+                reason,
+                end_time: *ctx.timestamp(),
+            }),
+        );
+
+        Ok(())
+    }
+
+    /// Start the vote with the given id
+    async fn start_vote(
+        &mut self,
+        ctx: &mut ModuleContext<'_, LegalVote>,
+        legal_vote_id: LegalVoteId,
+        incoming_parameters: UserParameters,
+    ) -> Result<(), LegalVoteError> {
+        let sandbox = incoming_parameters.sandbox;
+
         match self
             .start_vote_routine(ctx.volatile.storage(), legal_vote_id, incoming_parameters)
             .await
         {
             Ok((exchange_parameters, tokens)) => {
-                self.grant_user_access(ctx, exchange_parameters.legal_vote_id)
-                    .await?;
+                if !sandbox {
+                    self.grant_user_access(ctx, exchange_parameters.legal_vote_id)
+                        .await?;
+                }
 
                 if let Some(duration) = exchange_parameters.inner.duration {
                     ctx.add_event_stream(once(
-                        sleep(duration.into()).map(move |_| TimerEvent { legal_vote_id }),
+                        sleep(duration.into()).map(move |_| TimerEvent::Expired { legal_vote_id }),
                     ));
                 }
 
@@ -602,6 +1051,10 @@ impl LegalVote {
             Err(start_error) => {
                 log::warn!("Failed to start vote, {:?}", start_error);
 
+                if !sandbox {
+                    self.delete_orphaned_vote(legal_vote_id).await;
+                }
+
                 // return the cleanup error in case of failure as its more severe
                 ctx.volatile
                     .storage()
@@ -614,27 +1067,133 @@ impl LegalVote {
         Ok(())
     }
 
-    /// Set all vote related redis keys
-    async fn start_vote_routine(
-        &self,
-        storage: &mut dyn LegalVoteStorage,
-        legal_vote_id: LegalVoteId,
-        incoming_parameters: UserParameters,
-    ) -> Result<(Parameters, HashMap<ParticipantId, Token>), LegalVoteError> {
-        let start_time = Utc::now();
+    /// Delete the database entry of a vote which failed to start
+    ///
+    /// The entry is created before the vote is set up in the volatile storage. When the setup
+    /// fails partway through, the entry would otherwise remain as an empty protocol without any
+    /// participant being able to access it. Failing to delete the entry is only logged, so the
+    /// original error is reported to the initiator.
+    async fn delete_orphaned_vote(&self, legal_vote_id: LegalVoteId) {
+        let result = match self.db.get_conn().await {
+            Ok(mut conn) => {
+                ModuleResource::delete(&mut conn, Filter::new().with_id(legal_vote_id.into())).await
+            }
+            Err(e) => Err(e),
+        };
 
-        let (max_votes, participant_tokens, allowed_users) = self
-            .init_allowed_tokens(
-                storage,
-                legal_vote_id,
-                &incoming_parameters.allowed_participants,
-            )
-            .await?;
+        if let Err(e) = result {
+            log::error!("Failed to delete orphaned vote {legal_vote_id} from the database, {e:?}");
+        }
+    }
 
-        let parameters = Parameters {
-            initiator_id: self.participant_id,
-            legal_vote_id,
-            start_time,
+    /// The vote kinds which can be started in the tenant of this participant
+    fn allowed_vote_kinds(&self) -> Vec<VoteKind> {
+        self.params
+            .settings
+            .load()
+            .allowed_vote_kinds_for(&self.oidc_tenant_id.to_string())
+            .iter()
+            .map(|kind| match kind {
+                LegalVoteKind::RollCall => VoteKind::RollCall,
+                LegalVoteKind::LiveRollCall => VoteKind::LiveRollCall,
+                LegalVoteKind::Pseudonymous => VoteKind::Pseudonymous,
+            })
+            .collect()
+    }
+
+    /// The maximum number of participants which can be allowed to take part in a vote
+    fn max_allowed_participants(&self) -> usize {
+        self.params.settings.load().max_allowed_participants
+    }
+
+    /// Whether abstaining is enabled for votes which do not specify it
+    fn enable_abstain_by_default(&self) -> bool {
+        self.params
+            .settings
+            .load()
+            .enable_abstain_by_default_for(&self.oidc_tenant_id.to_string())
+    }
+
+    /// The value of `create_pdf` enforced by the PDF policy of the tenant of this participant
+    ///
+    /// Returns `None` when the initiator of a vote decides whether a PDF report is generated.
+    fn enforced_create_pdf(&self) -> Option<bool> {
+        self.params
+            .settings
+            .load()
+            .pdf_policy_for(&self.oidc_tenant_id.to_string())
+            .enforced_create_pdf()
+    }
+
+    fn is_vote_kind_allowed(&self, kind: &VoteKind) -> bool {
+        self.allowed_vote_kinds().contains(kind)
+    }
+
+    /// The user id of this participant
+    ///
+    /// Fails with `ErrorKind::InsufficientPermissions` for guests, only users can manage votes.
+    fn user_id(&self) -> Result<UserId, LegalVoteError> {
+        self.user_id
+            .ok_or_else(|| error::ErrorKind::InsufficientPermissions.into())
+    }
+
+    /// The user id of this participant if they are one of the allowed users of the vote
+    fn allowed_user_id(&self, parameters: &Parameters) -> Option<UserId> {
+        self.user_id.filter(|user_id| {
+            parameters
+                .allowed_users
+                .as_ref()
+                .is_some_and(|users| users.contains(user_id))
+        })
+    }
+
+    /// The user to record as issuer of a vote cancellation
+    ///
+    /// A vote can be canceled from the module of a guest when the room gets destroyed, in which
+    /// case the room owner is recorded.
+    async fn issuer_user_id(&self) -> Result<UserId, LegalVoteError> {
+        if let Some(user_id) = self.user_id {
+            return Ok(user_id);
+        }
+
+        let room = Room::get(&mut self.db.get_conn().await?, self.room_id.room_id()).await?;
+
+        Ok(room.created_by)
+    }
+
+    /// Set all vote related redis keys
+    async fn start_vote_routine(
+        &self,
+        storage: &mut dyn LegalVoteStorage,
+        legal_vote_id: LegalVoteId,
+        incoming_parameters: UserParameters,
+    ) -> Result<(Parameters, HashMap<ParticipantId, Token>), LegalVoteError> {
+        // The results of the previous vote must be confirmed or rejected before a new vote starts
+        if storage
+            .pending_confirmation_get(self.room_id)
+            .await?
+            .is_some()
+        {
+            return Err(error::ErrorKind::VoteAlreadyActive.into());
+        }
+
+        let start_time = Utc::now();
+
+        let (max_votes, participant_tokens, allowed_users) = self
+            .init_allowed_tokens(
+                storage,
+                legal_vote_id,
+                &incoming_parameters.allowed_participants,
+                incoming_parameters.allow_guests
+                    && incoming_parameters.kind == VoteKind::Pseudonymous,
+            )
+            .await?;
+
+        let parameters = Parameters {
+            initiator_id: self.participant_id,
+            legal_vote_id,
+            start_time,
+            end_time: vote_end_time(start_time, incoming_parameters.duration),
             max_votes,
             allowed_users: Some(allowed_users),
             inner: incoming_parameters,
@@ -645,8 +1204,22 @@ impl LegalVote {
             .parameter_set(self.room_id, legal_vote_id, &parameters)
             .await?;
 
-        self.init_vote_protocol(storage, legal_vote_id, start_time, parameters.clone())
-            .await?;
+        // Allows reconnecting participants to request their token again. The token of a hidden
+        // vote must not be linked to the participant, so it is not stored.
+        if !parameters.inner.kind.is_hidden() {
+            storage
+                .participant_tokens_set(self.room_id, legal_vote_id, &participant_tokens)
+                .await?;
+        }
+
+        self.init_vote_protocol(
+            storage,
+            legal_vote_id,
+            self.user_id()?,
+            start_time,
+            parameters.clone(),
+        )
+        .await?;
 
         if !storage
             .current_vote_set(self.room_id, legal_vote_id)
@@ -670,10 +1243,12 @@ impl LegalVote {
             .await?
             .created_by;
 
-        self.grant_module_resource_access(ctx, self.user_id, legal_vote_id)
+        let user_id = self.user_id()?;
+
+        self.grant_module_resource_access(ctx, user_id, legal_vote_id)
             .await?;
 
-        if self.user_id != room_owner {
+        if user_id != room_owner {
             self.grant_module_resource_access(ctx, room_owner, legal_vote_id)
                 .await?;
         }
@@ -716,15 +1291,13 @@ impl LegalVote {
         &self,
         storage: &mut dyn LegalVoteStorage,
         legal_vote_id: LegalVoteId,
+        issuer: UserId,
         start_time: DateTime<Utc>,
         parameters: Parameters,
     ) -> Result<(), SignalingModuleError> {
         let start_entry = db_protocol::v1::ProtocolEntry::new_with_time(
             start_time,
-            db_protocol::v1::VoteEvent::Start(db_protocol::v1::Start {
-                issuer: self.user_id,
-                parameters,
-            }),
+            db_protocol::v1::VoteEvent::Start(db_protocol::v1::Start { issuer, parameters }),
         );
 
         storage
@@ -736,12 +1309,16 @@ impl LegalVote {
 
     /// Set the allowed users list for the provided `legal_vote_id` to its initial state
     ///
+    /// Guests have no user id, so they get a token per participant when `allow_guests` is set.
+    /// Otherwise they are rejected.
+    ///
     /// Returns the maximum number of possible votes
     async fn init_allowed_tokens(
         &self,
         storage: &mut dyn LegalVoteStorage,
         legal_vote_id: LegalVoteId,
         allowed_participants: &[ParticipantId],
+        allow_guests: bool,
     ) -> Result<(u32, HashMap<ParticipantId, Token>, Vec<UserId>), LegalVoteError> {
         let mapped_users = storage
             .get_attribute_for_participants::<UserId>(
@@ -756,6 +1333,7 @@ impl LegalVote {
 
         let mut invalid_participants = Vec::new();
         let mut user_tokens = HashMap::new();
+        let mut guest_tokens = HashMap::new();
         let mut participant_tokens = HashMap::new();
         let mut allowed_users = vec![];
 
@@ -766,6 +1344,12 @@ impl LegalVote {
                     participant_tokens.insert(*participant_id, *token);
                     allowed_users.push(user_id);
                 }
+                None if allow_guests => {
+                    let token = *guest_tokens
+                        .entry(*participant_id)
+                        .or_insert_with(Token::generate);
+                    participant_tokens.insert(*participant_id, token);
+                }
                 None => {
                     invalid_participants.push(*participant_id);
                 }
@@ -780,9 +1364,13 @@ impl LegalVote {
             });
         }
 
-        let max_votes = user_tokens.len();
+        let max_votes = user_tokens.len() + guest_tokens.len();
 
-        let tokens = user_tokens.values().copied().collect::<Vec<Token>>();
+        let tokens = user_tokens
+            .values()
+            .chain(guest_tokens.values())
+            .copied()
+            .collect::<Vec<Token>>();
         storage
             .allow_token_set(self.room_id, legal_vote_id, tokens)
             .await?;
@@ -811,7 +1399,7 @@ impl LegalVote {
         let stop_kind = StopKind::ByParticipant(self.participant_id);
 
         let stop_entry = db_protocol::v1::ProtocolEntry::new(db_protocol::v1::VoteEvent::Stop(
-            db_protocol::v1::StopKind::ByUser(self.user_id),
+            db_protocol::v1::StopKind::ByUser(self.user_id()?),
         ));
 
         self.end_vote(ctx, legal_vote_id, stop_entry, stop_kind)
@@ -820,6 +1408,137 @@ impl LegalVote {
         Ok(())
     }
 
+    /// Send the [`LegalVoteState`] to the requesting participant
+    ///
+    /// Contains the same votes as the state sent on join. The participant's token is added to the
+    /// current vote unless the vote is hidden, and the provisional results are sent along for
+    /// live votes.
+    async fn send_state(&self, ctx: &mut ModuleContext<'_, Self>) -> Result<(), LegalVoteError> {
+        let current_vote = ctx
+            .volatile
+            .storage()
+            .current_vote_get(self.room_id)
+            .await?;
+
+        let mut state = load_from_history(
+            ctx.volatile.clone(),
+            self.room_id,
+            current_vote,
+            self.allowed_vote_kinds(),
+            self.max_allowed_participants(),
+            self.enforced_create_pdf(),
+        )
+        .await?;
+
+        let Some(legal_vote_id) = current_vote else {
+            ctx.ws_send(LegalVoteEvent::State(state));
+            return Ok(());
+        };
+
+        let token = ctx
+            .volatile
+            .storage()
+            .participant_token_get(self.room_id, legal_vote_id, self.participant_id)
+            .await?;
+
+        let mut is_live = false;
+        if let Some(summary) = state
+            .votes
+            .iter_mut()
+            .find(|summary| summary.parameters.legal_vote_id == legal_vote_id)
+        {
+            summary.parameters.token = token;
+            is_live = summary.parameters.inner.kind.is_live();
+        }
+
+        ctx.ws_send(LegalVoteEvent::State(state));
+
+        if is_live {
+            let results = self
+                .get_vote_results(ctx.volatile.storage(), legal_vote_id)
+                .await?;
+
+            ctx.ws_send(LegalVoteEvent::Updated(VoteResults {
+                legal_vote_id,
+                results,
+            }));
+        }
+
+        Ok(())
+    }
+
+    /// Get the participants of the allowed users that have not voted yet in the active vote
+    ///
+    /// Only available for votes which are not hidden, the voters of a hidden vote must not be
+    /// revealed. The participant ids are resolved from the user ids, so participants that
+    /// reconnected are returned with their current participant id.
+    async fn get_non_voters(
+        &self,
+        storage: &mut dyn LegalVoteStorage,
+        legal_vote_id: LegalVoteId,
+    ) -> Result<Vec<ParticipantId>, LegalVoteError> {
+        let parameters = storage
+            .parameter_get(self.room_id, legal_vote_id)
+            .await?
+            .ok_or(error::ErrorKind::InvalidVoteId)?;
+
+        if parameters.inner.kind.is_hidden() {
+            return Err(error::ErrorKind::InsufficientPermissions.into());
+        }
+
+        if storage.get_vote_status(self.room_id, legal_vote_id).await? != VoteStatus::Active {
+            return Err(error::ErrorKind::NoVoteActive.into());
+        }
+
+        let voters: BTreeSet<UserId> = storage
+            .protocol_get(self.room_id, legal_vote_id)
+            .await?
+            .into_iter()
+            .filter_map(|entry| match entry.event {
+                db_protocol::v1::VoteEvent::Vote(db_protocol::v1::Vote {
+                    user_info: Some(user_info),
+                    ..
+                }) => Some(user_info.issuer),
+                _ => None,
+            })
+            .collect();
+
+        let non_voters: BTreeSet<UserId> = parameters
+            .allowed_users
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|user_id| !voters.contains(user_id))
+            .collect();
+
+        let participants: Vec<ParticipantId> = storage
+            .get_all_participants(self.room_id)
+            .await?
+            .into_iter()
+            .collect();
+
+        let user_ids = storage
+            .get_attribute_for_participants::<UserId>(
+                &participants,
+                LocalRoomAttributeId {
+                    room: self.room_id,
+                    attribute: USER_ID,
+                }
+                .into(),
+            )
+            .await?;
+
+        Ok(participants
+            .into_iter()
+            .zip(user_ids)
+            .filter(|(_, user_id)| {
+                user_id
+                    .as_ref()
+                    .is_some_and(|user_id| non_voters.contains(user_id))
+            })
+            .map(|(participant_id, _)| participant_id)
+            .collect())
+    }
+
     /// Cast a vote
     ///
     /// Checks if the provided `vote_message` contains valid values & calls [`storage::vote`].
@@ -883,7 +1602,9 @@ impl LegalVote {
             }
         };
 
-        if vote_message.option == VoteOption::Abstain && !parameters.inner.enable_abstain {
+        if vote_message.option == VoteOption::Abstain
+            && !parameters.inner.enable_abstain.unwrap_or_default()
+        {
             return Ok((
                 VoteResponse {
                     legal_vote_id: vote_message.legal_vote_id,
@@ -893,12 +1614,15 @@ impl LegalVote {
             ));
         }
 
-        let user_info = match parameters.inner.kind {
-            VoteKind::Pseudonymous => None,
-            VoteKind::RollCall | VoteKind::LiveRollCall => Some(db_protocol::v1::UserInfo {
-                issuer: self.user_id,
-                participant_id: self.participant_id,
-            }),
+        // Votes of guests are always anonymous, they have no user identity
+        let user_info = match (parameters.inner.kind, self.user_id) {
+            (VoteKind::Pseudonymous, _) | (_, None) => None,
+            (VoteKind::RollCall | VoteKind::LiveRollCall, Some(user_id)) => {
+                Some(db_protocol::v1::UserInfo {
+                    issuer: user_id,
+                    participant_id: self.participant_id,
+                })
+            }
         };
 
         let vote_event = db_protocol::v1::Vote {
@@ -957,10 +1681,27 @@ impl LegalVote {
     }
 
     /// Cancel the active vote if the leaving participant is the initiator
+    ///
+    /// With a configured initiator grace period, the vote is only canceled if the initiator does
+    /// not rejoin in time.
     async fn handle_leaving(
-        &self,
+        &mut self,
         ctx: &mut ModuleContext<'_, Self>,
     ) -> Result<(), LegalVoteError> {
+        // Votes scheduled by this participant cannot start without them, cancel the schedule
+        let scheduled_vote_ids = Vec::from_iter(self.scheduled_votes.keys().copied());
+        for legal_vote_id in scheduled_vote_ids {
+            if ctx
+                .volatile
+                .storage()
+                .scheduled_vote_remove(self.room_id, legal_vote_id)
+                .await?
+            {
+                self.cancel_scheduled_vote(ctx, legal_vote_id, CancelReason::InitiatorLeft)
+                    .await?;
+            }
+        }
+
         let storage = ctx.volatile.storage();
 
         let current_vote_id = match storage.current_vote_get(self.room_id).await? {
@@ -973,10 +1714,7 @@ impl LegalVote {
             .await?
             .ok_or(error::ErrorKind::InvalidVoteId)?;
 
-        if parameters
-            .allowed_users
-            .is_some_and(|users| users.contains(&self.user_id))
-        {
+        if let Some(user_id) = self.allowed_user_id(&parameters) {
             let entry = if parameters.inner.kind.is_hidden() {
                 db_protocol::v1::ProtocolEntry::new(db_protocol::v1::VoteEvent::UserLeft(
                     None.into(),
@@ -984,7 +1722,7 @@ impl LegalVote {
             } else {
                 db_protocol::v1::ProtocolEntry::new(db_protocol::v1::VoteEvent::UserLeft(
                     Some(db_protocol::v1::UserInfo {
-                        issuer: self.user_id,
+                        issuer: user_id,
                         participant_id: self.participant_id,
                     })
                     .into(),
@@ -996,36 +1734,139 @@ impl LegalVote {
                 .await?;
         }
 
-        if parameters.initiator_id == self.participant_id {
-            let reason = CancelReason::InitiatorLeft;
+        if parameters.initiator_id != self.participant_id {
+            return Ok(());
+        }
 
-            let entry = self
-                .cancel_vote_unchecked(storage, current_vote_id, reason.clone())
-                .await?;
+        let initiator = self.user_id()?;
 
-            self.save_protocol_in_database(storage, current_vote_id)
-                .await?;
+        if self.params.settings.load().initiator_grace_period.is_zero() {
+            return self
+                .cancel_vote_initiator_left(ctx, &parameters, initiator)
+                .await;
+        }
 
-            ctx.exchange_publish(
-                control::exchange::current_room_all_participants(self.room_id),
-                exchange::Event::Cancel(Canceled {
-                    legal_vote_id: current_vote_id,
-                    reason,
-                    end_time: entry
-                        .timestamp
-                        .expect("Missing timestamp on cancel vote ProtocolEntry"),
-                }),
-            );
+        // Keep the vote running in case the initiator only lost their connection, the remaining
+        // participants cancel it once the grace period expired
+        storage
+            .initiator_grace_start(self.room_id, current_vote_id, initiator)
+            .await?;
 
-            if parameters.inner.create_pdf {
-                self.save_pdf(
-                    ctx,
-                    current_vote_id,
-                    self.user_id,
-                    parameters.inner.timezone,
-                )
+        ctx.exchange_publish(
+            control::exchange::current_room_all_participants(self.room_id),
+            exchange::Event::InitiatorGraceStarted(current_vote_id),
+        );
+
+        Ok(())
+    }
+
+    /// Hand the vote back to the initiator if they rejoin during the grace period
+    async fn resume_initiator(
+        &self,
+        ctx: &mut ModuleContext<'_, Self>,
+        parameters: &mut Parameters,
+    ) -> Result<(), LegalVoteError> {
+        let Some(user_id) = self.user_id else {
+            return Ok(());
+        };
+
+        let storage = ctx.volatile.storage();
+
+        let initiator = storage
+            .initiator_grace_get(self.room_id, parameters.legal_vote_id)
+            .await?;
+
+        if initiator != Some(user_id)
+            || !storage
+                .initiator_grace_end(self.room_id, parameters.legal_vote_id)
+                .await?
+        {
+            return Ok(());
+        }
+
+        parameters.initiator_id = self.participant_id;
+
+        storage
+            .parameter_set(self.room_id, parameters.legal_vote_id, parameters)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Cancel the vote once the grace period after its initiator left has expired
+    ///
+    /// Every remaining participant runs the grace period timer, only the first one to end the
+    /// grace period cancels the vote.
+    async fn expire_initiator_grace(
+        &self,
+        ctx: &mut ModuleContext<'_, Self>,
+        legal_vote_id: LegalVoteId,
+    ) -> Result<(), LegalVoteError> {
+        let storage = ctx.volatile.storage();
+
+        let Some(initiator) = storage
+            .initiator_grace_get(self.room_id, legal_vote_id)
+            .await?
+        else {
+            return Ok(());
+        };
+
+        if !storage
+            .initiator_grace_end(self.room_id, legal_vote_id)
+            .await?
+        {
+            // The initiator rejoined or another participant canceled the vote in the meantime
+            return Ok(());
+        }
+
+        if storage.current_vote_get(self.room_id).await? != Some(legal_vote_id) {
+            // The vote got stopped or canceled during the grace period
+            return Ok(());
+        }
+
+        let parameters = storage
+            .parameter_get(self.room_id, legal_vote_id)
+            .await?
+            .ok_or(error::ErrorKind::InvalidVoteId)?;
+
+        self.cancel_vote_initiator_left(ctx, &parameters, initiator)
+            .await
+    }
+
+    /// Cancel the vote because its initiator left the room
+    ///
+    /// The vote protocol is sent to the `initiator` if a PDF was requested.
+    async fn cancel_vote_initiator_left(
+        &self,
+        ctx: &mut ModuleContext<'_, Self>,
+        parameters: &Parameters,
+        initiator: UserId,
+    ) -> Result<(), LegalVoteError> {
+        let storage = ctx.volatile.storage();
+        let legal_vote_id = parameters.legal_vote_id;
+        let reason = CancelReason::InitiatorLeft;
+
+        let entry = self
+            .cancel_vote_unchecked(storage, legal_vote_id, reason.clone())
+            .await?;
+
+        self.save_protocol_in_database(storage, legal_vote_id)
+            .await?;
+
+        ctx.exchange_publish_critical(
+            control::exchange::current_room_all_participants(self.room_id),
+            exchange::Event::Cancel(Canceled {
+                legal_vote_id,
+                reason,
+                end_time: entry
+                    .timestamp
+                    .expect("Missing timestamp on cancel vote ProtocolEntry"),
+            }),
+        );
+
+        if parameters.inner.create_pdf {
+            self.save_pdf(ctx, legal_vote_id, initiator, parameters.inner.timezone)
                 .await?;
-            }
         }
 
         Ok(())
@@ -1043,7 +1884,11 @@ impl LegalVote {
             .ok_or(error::ErrorKind::InvalidVoteId)?;
 
         let tally = storage
-            .count_get(self.room_id, legal_vote_id, parameters.inner.enable_abstain)
+            .count_get(
+                self.room_id,
+                legal_vote_id,
+                parameters.inner.enable_abstain.unwrap_or_default(),
+            )
             .await?;
 
         let protocol_entries = storage.protocol_get(self.room_id, legal_vote_id).await?;
@@ -1091,7 +1936,7 @@ impl LegalVote {
     ) -> Result<db_protocol::v1::ProtocolEntry, LegalVoteError> {
         let cancel_entry = db_protocol::v1::ProtocolEntry::new(db_protocol::v1::VoteEvent::Cancel(
             db_protocol::v1::Cancel {
-                issuer: self.user_id,
+                issuer: self.issuer_user_id().await?,
                 reason,
             },
         ));
@@ -1107,6 +1952,9 @@ impl LegalVote {
     }
 
     /// End the vote behind `legal_vote_id` using the provided parameters as stop parameters
+    ///
+    /// When the vote requires a confirmation, the results are only final once a moderator
+    /// confirmed them. Until then, no new vote can be started.
     async fn end_vote(
         &self,
         ctx: &mut ModuleContext<'_, Self>,
@@ -1116,13 +1964,170 @@ impl LegalVote {
     ) -> Result<(), LegalVoteError> {
         let volatile = &mut ctx.volatile.clone();
         let storage = volatile.storage();
+
+        let parameters = storage
+            .parameter_get(self.room_id, legal_vote_id)
+            .await?
+            .ok_or(error::ErrorKind::InvalidVoteId)?;
+
+        let end_time = end_entry
+            .timestamp
+            .expect("Missing timestamp for end vote ProtocolEntry");
+
+        if !parameters.inner.require_confirmation {
+            if !storage
+                .end_current_vote(self.room_id, legal_vote_id, &end_entry)
+                .await?
+            {
+                return Err(error::ErrorKind::InvalidVoteId.into());
+            }
+
+            return self
+                .finalize_vote(ctx, legal_vote_id, stop_kind, end_time)
+                .await;
+        }
+
+        let pending = PendingConfirmation {
+            legal_vote_id,
+            kind: stop_kind,
+            end_time,
+        };
+
+        // Set before ending the vote, so no new vote can start in between
+        if !storage
+            .pending_confirmation_set(self.room_id, &pending)
+            .await?
+        {
+            return Err(error::ErrorKind::InvalidVoteId.into());
+        }
+
         if !storage
             .end_current_vote(self.room_id, legal_vote_id, &end_entry)
             .await?
         {
+            storage
+                .pending_confirmation_remove(self.room_id, &pending)
+                .await?;
+
             return Err(error::ErrorKind::InvalidVoteId.into());
         }
 
+        self.save_protocol_in_database(storage, legal_vote_id)
+            .await?;
+
+        ctx.exchange_publish_critical(
+            control::exchange::current_room_all_participants(self.room_id),
+            exchange::Event::AwaitingConfirmation(AwaitingConfirmation {
+                legal_vote_id,
+                kind: stop_kind,
+                end_time,
+            }),
+        );
+
+        let results = self.validate_vote_results(ctx, legal_vote_id).await?;
+
+        ctx.exchange_publish(
+            control::exchange::current_room_all_participants(self.room_id),
+            exchange::Event::ProvisionalResults(ProvisionalResults {
+                legal_vote_id,
+                results,
+            }),
+        );
+
+        Ok(())
+    }
+
+    /// Send the state of a vote awaiting confirmation to this participant
+    ///
+    /// Moderators additionally receive the provisional results, so they can confirm them.
+    async fn send_pending_confirmation(
+        &self,
+        ctx: &mut ModuleContext<'_, Self>,
+        pending: PendingConfirmation,
+    ) -> Result<(), LegalVoteError> {
+        let legal_vote_id = pending.legal_vote_id;
+
+        ctx.ws_send(LegalVoteEvent::AwaitingConfirmation(AwaitingConfirmation {
+            legal_vote_id,
+            kind: pending.kind,
+            end_time: pending.end_time,
+        }));
+
+        if matches!(ctx.role(), Role::Moderator) {
+            let results = self.validate_vote_results(ctx, legal_vote_id).await?;
+
+            ctx.ws_send(LegalVoteEvent::ProvisionalResults(ProvisionalResults {
+                legal_vote_id,
+                results,
+            }));
+        }
+
+        Ok(())
+    }
+
+    /// Remove the pending confirmation of `legal_vote_id`
+    ///
+    /// Fails with `VoteError::InvalidVoteId` when the results of `legal_vote_id` are not pending
+    /// confirmation, e.g. because another moderator confirmed or rejected them already.
+    async fn take_pending_confirmation(
+        &self,
+        storage: &mut dyn LegalVoteStorage,
+        legal_vote_id: LegalVoteId,
+    ) -> Result<PendingConfirmation, LegalVoteError> {
+        let pending = storage
+            .pending_confirmation_get(self.room_id)
+            .await?
+            .filter(|pending| pending.legal_vote_id == legal_vote_id)
+            .ok_or(error::ErrorKind::InvalidVoteId)?;
+
+        if !storage
+            .pending_confirmation_remove(self.room_id, &pending)
+            .await?
+        {
+            return Err(error::ErrorKind::InvalidVoteId.into());
+        }
+
+        Ok(pending)
+    }
+
+    /// Discard the results of a vote that was awaiting confirmation
+    ///
+    /// Adds a `ProtocolEntry` with `VoteEvent::Cancel` to the vote protocol and saves it, so the
+    /// vote is recorded as canceled.
+    async fn reject_results(
+        &self,
+        storage: &mut dyn LegalVoteStorage,
+        legal_vote_id: LegalVoteId,
+        reason: CancelReason,
+    ) -> Result<db_protocol::v1::ProtocolEntry, LegalVoteError> {
+        let cancel_entry = db_protocol::v1::ProtocolEntry::new(db_protocol::v1::VoteEvent::Cancel(
+            db_protocol::v1::Cancel {
+                issuer: self.issuer_user_id().await?,
+                reason,
+            },
+        ));
+
+        storage
+            .protocol_add_entry(self.room_id, legal_vote_id, cancel_entry.clone())
+            .await?;
+
+        self.save_protocol_in_database(storage, legal_vote_id)
+            .await?;
+
+        Ok(cancel_entry)
+    }
+
+    /// Record the final results of an ended vote and announce them to all participants
+    async fn finalize_vote(
+        &self,
+        ctx: &mut ModuleContext<'_, Self>,
+        legal_vote_id: LegalVoteId,
+        stop_kind: StopKind,
+        end_time: DateTime<Utc>,
+    ) -> Result<(), LegalVoteError> {
+        let volatile = &mut ctx.volatile.clone();
+        let storage = volatile.storage();
+
         let final_results = self.validate_vote_results(ctx, legal_vote_id).await?;
 
         let final_results_entry = match &final_results {
@@ -1145,15 +2150,13 @@ impl LegalVote {
         self.save_protocol_in_database(storage, legal_vote_id)
             .await?;
 
-        ctx.exchange_publish(
+        ctx.exchange_publish_critical(
             control::exchange::current_room_all_participants(self.room_id),
             exchange::Event::Stop(Stopped {
                 legal_vote_id,
                 kind: stop_kind,
                 results: final_results,
-                end_time: end_entry
-                    .timestamp
-                    .expect("Missing timestamp for end vote ProtocolEntry"),
+                end_time,
             }),
         );
 
@@ -1169,9 +2172,16 @@ impl LegalVote {
                 // Send the pdf message to the participant id of the vote initiator in case of an auto stop
                 StopKind::Auto => {
                     let protocol = storage.protocol_get(self.room_id, legal_vote_id).await?;
+                    let timezone = self.report_timezone(timezone, None).await?;
 
                     let pdf_asset = self
-                        .create_pdf_asset(legal_vote_id, ctx.timestamp(), timezone, protocol)
+                        .create_pdf_asset(
+                            legal_vote_id,
+                            ctx.timestamp(),
+                            timezone,
+                            protocol,
+                            ctx.metrics().cloned(),
+                        )
                         .await?;
 
                     ctx.exchange_publish(
@@ -1183,7 +2193,7 @@ impl LegalVote {
                     );
                 }
                 _ => {
-                    self.save_pdf(ctx, legal_vote_id, self.user_id, timezone)
+                    self.save_pdf(ctx, legal_vote_id, self.user_id()?, timezone)
                         .await?;
                 }
             }
@@ -1229,7 +2239,7 @@ impl LegalVote {
             yes: 0,
             no: 0,
             abstain: {
-                if parameters.inner.enable_abstain {
+                if parameters.inner.enable_abstain.unwrap_or_default() {
                     Some(0)
                 } else {
                     None
@@ -1256,7 +2266,11 @@ impl LegalVote {
         }
 
         let tally = storage
-            .count_get(self.room_id, legal_vote_id, parameters.inner.enable_abstain)
+            .count_get(
+                self.room_id,
+                legal_vote_id,
+                parameters.inner.enable_abstain.unwrap_or_default(),
+            )
             .await?;
 
         if protocol_tally == tally && total_votes <= parameters.max_votes {
@@ -1282,6 +2296,12 @@ impl LegalVote {
 
         storage.history_delete(self.room_id).await?;
 
+        storage.scheduled_votes_delete(self.room_id).await?;
+
+        storage.initiator_graces_delete(self.room_id).await?;
+
+        storage.pending_confirmation_delete(self.room_id).await?;
+
         if let Some(current_vote_id) = storage.current_vote_get(self.room_id).await? {
             storage.cleanup_vote(self.room_id, current_vote_id).await?;
             storage.current_vote_delete(self.room_id).await?;
@@ -1293,7 +2313,10 @@ impl LegalVote {
     /// Creates a new vote in the database
     ///
     /// Adds a new vote with an empty protocol to the database. Returns the [`VoteId`] of the new vote.
-    async fn new_vote_in_database(&self) -> Result<LegalVoteId, SignalingModuleError> {
+    async fn new_vote_in_database(
+        &self,
+        created_by: UserId,
+    ) -> Result<LegalVoteId, SignalingModuleError> {
         let db = self.db.clone();
 
         let room_id = self.room_id.room_id();
@@ -1302,7 +2325,7 @@ impl LegalVote {
         let module_resource = NewModuleResource {
             tenant_id,
             room_id,
-            created_by: self.user_id,
+            created_by,
             namespace: Self::NAMESPACE.to_string(),
             tag: Some("protocol".into()),
             data: serde_json::to_value(db_protocol::NewProtocol::new(vec![])).unwrap(),
@@ -1314,6 +2337,11 @@ impl LegalVote {
     }
 
     /// Save the protocol for `legal_vote_id` in the database
+    ///
+    /// Only the entries which were added since the last save are appended to the stored protocol.
+    /// The whole protocol is rewritten when the stored protocol diverged from the saved entries.
+    ///
+    /// The protocol of a sandbox vote is only kept in the volatile storage.
     async fn save_protocol_in_database(
         &self,
         storage: &mut dyn LegalVoteStorage,
@@ -1321,30 +2349,58 @@ impl LegalVote {
     ) -> Result<(), LegalVoteError> {
         let entries = storage.protocol_get(self.room_id, legal_vote_id).await?;
 
-        let protocol = db_protocol::NewProtocol::new(entries);
+        if is_sandbox_protocol(&entries) {
+            return Ok(());
+        }
+
+        let flushed = storage
+            .protocol_flushed_get(self.room_id, legal_vote_id)
+            .await?;
+        let entry_count = entries.len();
 
-        let db = self.db.clone();
+        let filter = || {
+            Filter::new()
+                .with_id(*legal_vote_id.inner())
+                .with_namespace("legal_vote".into())
+        };
 
-        let mut conn = db.get_conn().await?;
+        let mut conn = self.db.get_conn().await?;
 
-        let protocol = serde_json::to_value(protocol).context(SerdeJsonSnafu {
-            message: "Failed to serialize",
-        })?;
+        let append_operations =
+            db_protocol::patch::append_operations(&entries, flushed).context(SerdeJsonSnafu {
+                message: "Failed to serialize",
+            })?;
 
-        let add_protocol = opentalk_db_storage::module_resources::Operation::Add {
-            path: "/".into(),
-            value: protocol,
+        let appended = match append_operations {
+            Some(operations) => {
+                match ModuleResource::patch(&mut conn, filter(), operations).await {
+                    Ok(_) => true,
+                    Err(e) => {
+                        log::warn!(
+                            "Failed to append to the protocol of legal vote {legal_vote_id}, rewriting it: {}",
+                            Report::from_error(e)
+                        );
+                        false
+                    }
+                }
+            }
+            None => false,
         };
 
-        ModuleResource::patch(
-            &mut conn,
-            Filter::new()
-                .with_id(*legal_vote_id.inner())
-                .with_namespace("legal_vote".into()),
-            vec![add_protocol],
-        )
-        .await
-        .whatever_context::<_, LegalVoteError>("Failed to save protocol to database")?;
+        if !appended {
+            let rewrite_operations =
+                db_protocol::patch::rewrite_operations(entries).context(SerdeJsonSnafu {
+                    message: "Failed to serialize",
+                })?;
+
+            ModuleResource::patch(&mut conn, filter(), rewrite_operations)
+                .await
+                .whatever_context::<_, LegalVoteError>("Failed to save protocol to database")?;
+        }
+
+        storage
+            .protocol_flushed_set(self.room_id, legal_vote_id, entry_count)
+            .await?;
 
         Ok(())
     }
@@ -1359,14 +2415,7 @@ impl LegalVote {
         msg_target: UserId,
         timezone: Option<chrono_tz::Tz>,
     ) -> Result<(), LegalVoteError> {
-        let timezone = match timezone {
-            Some(timezone) => Some(timezone),
-            None => {
-                let mut db_conn = self.db.get_conn().await?;
-                let moderator = User::get(&mut db_conn, self.user_id).await?;
-                moderator.timezone.map(Tz::from)
-            }
-        };
+        let timezone = self.report_timezone(timezone, Some(msg_target)).await?;
 
         let protocol = ctx
             .volatile
@@ -1375,7 +2424,13 @@ impl LegalVote {
             .await?;
 
         let pdf_asset = match self
-            .create_pdf_asset(legal_vote_id, ctx.timestamp(), timezone, protocol)
+            .create_pdf_asset(
+                legal_vote_id,
+                ctx.timestamp(),
+                timezone,
+                protocol,
+                ctx.metrics().cloned(),
+            )
             .await
         {
             Ok(pdf_asset) => pdf_asset,
@@ -1399,6 +2454,37 @@ impl LegalVote {
         Ok(())
     }
 
+    /// Resolve the timezone in which the vote protocol is generated
+    ///
+    /// The `requested` timezone from the vote parameters takes precedence over the timezone of the
+    /// event the room belongs to, the timezone of the `recipient` and the default of the tenant.
+    async fn report_timezone(
+        &self,
+        requested: Option<Tz>,
+        recipient: Option<UserId>,
+    ) -> Result<Tz, LegalVoteError> {
+        let mut db_conn = self.db.get_conn().await?;
+
+        let event = DbEvent::get_for_room(&mut db_conn, self.room_id.room_id()).await?;
+        let recipient_timezone = match recipient {
+            Some(recipient) => User::get(&mut db_conn, recipient).await?.timezone,
+            None => None,
+        };
+        let tenant_default = self
+            .params
+            .tenant_timezones
+            .get(&self.oidc_tenant_id.to_string())
+            .copied();
+
+        Ok(ReportTimezoneCandidates {
+            requested,
+            event: event.and_then(|event| event.starts_at_tz).map(Tz::from),
+            user: recipient_timezone.map(Tz::from),
+            tenant_default: tenant_default.map(Tz::from),
+        }
+        .resolve())
+    }
+
     async fn get_referenced_user_names(
         &self,
         protocol: &[db_protocol::v1::ProtocolEntry],
@@ -1420,41 +2506,69 @@ impl LegalVote {
         Ok(user_names)
     }
 
+    /// Generate the vote protocol PDF and store it as an asset
     async fn create_pdf_asset(
         &self,
         legal_vote_id: LegalVoteId,
         timestamp: Timestamp,
-        timezone: Option<chrono_tz::Tz>,
+        timezone: Tz,
         protocol: Vec<db_protocol::v1::ProtocolEntry>,
+        metrics: Option<Arc<SignalingMetrics>>,
     ) -> Result<PdfAsset, LegalVoteError> {
-        let timezone = timezone.unwrap_or(chrono_tz::UTC);
         let user_names = self.get_referenced_user_names(&protocol).await?;
-
-        let pdf_data = report::generate(
-            user_names,
-            protocol,
-            &timezone,
-            Path::new(&format!("{MODULE_ID}/{timestamp}")),
-        )
-        .whatever_context::<_, LegalVoteError>("Failed to generate legal vote PDF")?;
-
-        // convert the data to a applicable type for the `save_asset()` fn
-        let data: tokio_stream::Once<std::result::Result<bytes::Bytes, LegalVoteError>> =
-            tokio_stream::once(Ok(Bytes::from(pdf_data)));
+        let sandbox = is_sandbox_protocol(&protocol);
+
+        // The asset id is reserved up front, so that the protocol can link to its own asset
+        let asset_id = AssetId::generate();
+        let verification_url = self
+            .params
+            .verification_qr_code
+            .as_ref()
+            .filter(|qr_code| qr_code.is_enabled_for(&self.oidc_tenant_id.to_string()))
+            .map(|qr_code| qr_code.url_for(self.room_id.room_id(), asset_id));
+
+        let pdf_data = self
+            .params
+            .pdf_generation_limiter
+            .run(async {
+                report::generate(
+                    user_names,
+                    protocol,
+                    &timezone,
+                    self.params.report_dump_path.as_deref(),
+                    Path::new(&format!("{MODULE_ID}/{timestamp}")),
+                    self.params.report_font_path.as_deref(),
+                    verification_url,
+                )
+            })
+            .await;
+        if let Some(metrics) = metrics {
+            metrics.record_report_generation(&MODULE_ID, pdf_data.is_ok());
+        }
+        let pdf_data =
+            pdf_data.whatever_context::<_, LegalVoteError>("Failed to generate legal vote PDF")?;
 
         let kind = "vote_protocol"
             .parse()
             .expect("Must be parseable as AssetFileKind");
-        let filename = NewAssetFileName::new(kind, timestamp, FileExtension::pdf());
+        let mut filename = NewAssetFileName::new(kind, timestamp, FileExtension::pdf());
+        if sandbox {
+            // The protocol of a sandbox vote is not archived
+            filename = filename.with_retention_tag(
+                ASSET_RETENTION_TAG_DEFAULT
+                    .parse()
+                    .expect("Must be parseable as AssetRetentionTag"),
+            );
+        }
 
-        let (asset_id, filename) = save_asset(
+        let (asset_id, filename) = save_asset_with_retry(
             &self.storage,
             self.db.clone(),
             self.room_id.room_id(),
             Some(Self::NAMESPACE),
+            asset_id,
             filename,
-            data,
-            ChunkFormat::Data,
+            Bytes::from(pdf_data),
         )
         .await?;
 
@@ -1500,3 +2614,23 @@ impl LegalVote {
         Err(fatal)
     }
 }
+
+/// The time at which a vote started at `start_time` expires, if it has a duration
+///
+/// Sent to the participants, so they can display the remaining time of the vote.
+pub(crate) fn vote_end_time(
+    start_time: DateTime<Utc>,
+    duration: Option<user_parameters::Duration>,
+) -> Option<DateTime<Utc>> {
+    let duration = TimeDelta::from_std(duration?.into()).ok()?;
+
+    start_time.checked_add_signed(duration)
+}
+
+/// Whether the protocol belongs to a sandbox vote, which is not stored in the database
+fn is_sandbox_protocol(protocol: &[db_protocol::v1::ProtocolEntry]) -> bool {
+    protocol.iter().any(|entry| match &entry.event {
+        db_protocol::v1::VoteEvent::Start(start) => start.parameters.inner.sandbox,
+        _ => false,
+    })
+}
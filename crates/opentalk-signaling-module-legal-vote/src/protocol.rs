@@ -14,7 +14,7 @@ use opentalk_types_signaling_legal_vote::{
 };
 use snafu::{OptionExt, ResultExt, Snafu, ensure};
 
-use crate::{LegalVoteStorageProvider, storage::protocol as db_protocol};
+use crate::{LegalVoteStorageProvider, storage::protocol as db_protocol, vote_end_time};
 
 pub struct RawProtocol<'a>(&'a [db_protocol::v1::ProtocolEntry]);
 
@@ -173,12 +173,19 @@ pub async fn load_from_protocol(
     let storage_protocol = storage.protocol_get(room_id, vote_id).await?;
     let protocol = RawProtocol::from(&storage_protocol);
 
-    let vote_summary = protocol
-        .try_into()
-        .map_err(|err| SignalingModuleError::CustomError {
-            message: "Failed to summarize protocol".to_string(),
-            source: Some(Box::new(err)),
-        })?;
+    let mut vote_summary: VoteSummary =
+        protocol
+            .try_into()
+            .map_err(|err| SignalingModuleError::CustomError {
+                message: "Failed to summarize protocol".to_string(),
+                source: Some(Box::new(err)),
+            })?;
+
+    // Votes started before the end time was stored only contain the duration
+    let parameters = &mut vote_summary.parameters;
+    if parameters.end_time.is_none() {
+        parameters.end_time = vote_end_time(parameters.start_time, parameters.inner.duration);
+    }
 
     Ok(vote_summary)
 }
@@ -187,6 +194,9 @@ pub async fn load_from_history(
     mut volatile: VolatileStorage,
     room_id: SignalingRoomId,
     current_vote: Option<LegalVoteId>,
+    allowed_vote_kinds: Vec<VoteKind>,
+    max_allowed_participants: usize,
+    enforced_create_pdf: Option<bool>,
 ) -> Result<LegalVoteState, SignalingModuleError> {
     let storage = volatile.storage();
     let vote_futures = storage
@@ -200,5 +210,132 @@ pub async fn load_from_history(
         .await
         .into_iter()
         .collect::<Result<Vec<_>, SignalingModuleError>>()?;
-    Ok(LegalVoteState { votes })
+    Ok(LegalVoteState {
+        votes,
+        allowed_vote_kinds,
+        max_allowed_participants,
+        enforced_create_pdf,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{DateTime, TimeZone as _, Utc};
+    use opentalk_signaling_core::{SignalingRoomId, VolatileStaticMemoryStorage, VolatileStorage};
+    use opentalk_types_common::{rooms::RoomId, users::UserId};
+    use opentalk_types_signaling::ParticipantId;
+    use opentalk_types_signaling_legal_vote::{
+        parameters::Parameters,
+        user_parameters::{AllowedParticipants, Duration, Name, UserParameters},
+        vote::{LegalVoteId, VoteKind},
+    };
+    use pretty_assertions::assert_eq;
+    use serial_test::serial;
+
+    use super::load_from_protocol;
+    use crate::{
+        LegalVoteStorageProvider as _,
+        storage::protocol::v1::{ProtocolEntry, Start, VoteEvent},
+    };
+
+    fn start_time() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 1, 2, 3, 4, 5).unwrap()
+    }
+
+    fn parameters(
+        legal_vote_id: LegalVoteId,
+        duration: Option<u64>,
+        end_time: Option<DateTime<Utc>>,
+    ) -> Parameters {
+        Parameters {
+            initiator_id: ParticipantId::from_u128(1),
+            legal_vote_id,
+            start_time: start_time(),
+            end_time,
+            max_votes: 1,
+            allowed_users: None,
+            inner: UserParameters {
+                kind: VoteKind::RollCall,
+                name: Name::try_from("Budget").unwrap(),
+                subtitle: None,
+                topic: None,
+                allowed_participants: AllowedParticipants::try_from(vec![
+                    ParticipantId::from_u128(1),
+                ])
+                .unwrap(),
+                enable_abstain: Some(false),
+                auto_close: false,
+                duration: duration.map(|duration| Duration::try_from(duration).unwrap()),
+                create_pdf: false,
+                timezone: None,
+                sandbox: false,
+                start_at: None,
+                require_confirmation: false,
+                allow_guests: false,
+            },
+            token: None,
+        }
+    }
+
+    /// Store a protocol which only contains the start of the vote and load its summary
+    async fn load_started_vote(parameters: Parameters) -> Parameters {
+        let mut volatile = VolatileStorage::Left(VolatileStaticMemoryStorage);
+        let room = SignalingRoomId::new_for_room(RoomId::generate());
+        let legal_vote_id = parameters.legal_vote_id;
+
+        volatile
+            .storage()
+            .protocol_add_entry(
+                room,
+                legal_vote_id,
+                ProtocolEntry::new_with_time(
+                    start_time(),
+                    VoteEvent::Start(Start {
+                        issuer: UserId::from_u128(1),
+                        parameters,
+                    }),
+                ),
+            )
+            .await
+            .unwrap();
+
+        load_from_protocol(volatile, room, legal_vote_id)
+            .await
+            .unwrap()
+            .parameters
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn end_time_of_protocol_without_end_time_is_computed() {
+        let legal_vote_id = LegalVoteId::generate();
+
+        let loaded = load_started_vote(parameters(legal_vote_id, Some(60), None)).await;
+
+        assert_eq!(
+            loaded.end_time,
+            Some(Utc.with_ymd_and_hms(2025, 1, 2, 3, 5, 5).unwrap())
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn stored_end_time_is_kept() {
+        let legal_vote_id = LegalVoteId::generate();
+        let end_time = Utc.with_ymd_and_hms(2025, 1, 2, 3, 6, 0).unwrap();
+
+        let loaded = load_started_vote(parameters(legal_vote_id, Some(60), Some(end_time))).await;
+
+        assert_eq!(loaded.end_time, Some(end_time));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn vote_without_duration_has_no_end_time() {
+        let legal_vote_id = LegalVoteId::generate();
+
+        let loaded = load_started_vote(parameters(legal_vote_id, None, None)).await;
+
+        assert_eq!(loaded.end_time, None);
+    }
 }
@@ -12,6 +12,10 @@ pub struct ReportData {
     pub summary: Summary,
     pub votes: Vec<ResolvedVote>,
     pub events: Vec<TimedEvent>,
+
+    /// The url encoded into the verification QR code, if enabled for the tenant
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verification_url: Option<String>,
 }
 
 #[cfg(test)]
@@ -69,6 +73,7 @@ pub(crate) mod tests {
                 report_timezone: "Europe/Berlin"
                     .parse()
                     .expect("value must be parsable as Timezone"),
+                sandbox: false,
             },
             votes: vec![
                 ResolvedVote {
@@ -174,6 +179,7 @@ pub(crate) mod tests {
                     }),
                 }),
             }],
+            verification_url: None,
         }
     }
 
@@ -317,6 +323,7 @@ pub(crate) mod tests {
                 report_timezone: "Europe/Vienna"
                     .parse()
                     .expect("value must be parsable as Timezone"),
+                sandbox: false,
             },
             votes: vec![
                 ResolvedVote {
@@ -411,6 +418,7 @@ pub(crate) mod tests {
                     ),
                 },
             ],
+            verification_url: None,
         }
     }
 
@@ -533,6 +541,7 @@ pub(crate) mod tests {
                 report_timezone: "Europe/Vienna"
                     .parse()
                     .expect("value must be parsable as Timezone"),
+                sandbox: false,
             },
             votes: vec![
                 ResolvedVote {
@@ -561,6 +570,7 @@ pub(crate) mod tests {
                 },
             ],
             events: vec![],
+            verification_url: None,
         }
     }
 
@@ -51,4 +51,8 @@ pub struct Summary {
     pub final_results: Option<FinalResults>,
 
     pub report_timezone: TimeZone,
+
+    /// Whether the vote was a sandbox vote which is not archived
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub sandbox: bool,
 }
@@ -25,6 +25,10 @@
 
 = OpenTalk Vote Report
 
+#if data.summary.at("sandbox", default: false) [
+  *Test vote – this protocol is not archived.*
+]
+
 #let metadata_table_content = (
   (
     [Title],
@@ -275,3 +279,14 @@
     )
   }
 )
+
+#if "verification_url" in data [
+  == Verification
+
+  #grid(
+    columns: (auto, 1fr),
+    gutter: 1em,
+    image("verification_qr_code.svg", width: 3cm),
+    [The archived protocol can be retrieved and verified at #link(data.verification_url).],
+  )
+]
@@ -26,37 +26,54 @@ pub(crate) fn generate(
     user_names: BTreeMap<UserId, DisplayName>,
     protocol: Vec<ProtocolEntry>,
     timezone: &Tz,
+    dump_path: Option<&Path>,
     dump_to_relative_path: &Path,
+    font_path: Option<&Path>,
+    verification_url: Option<String>,
 ) -> Result<Vec<u8>, Error> {
     let builder = Builder::new(user_names);
-    let report_data = builder.build_report_data(protocol, timezone)?;
+    let mut report_data = builder.build_report_data(protocol, timezone)?;
+    report_data.verification_url = verification_url;
 
     generate_from_template(
         DEFAULT_TEMPLATE.to_string(),
         &report_data,
+        dump_path,
         dump_to_relative_path,
+        font_path,
     )
 }
 
 fn generate_from_template(
     template: String,
     parameter: &ReportData,
+    dump_path: Option<&Path>,
     dump_to_relative_path: &Path,
+    font_path: Option<&Path>,
 ) -> Result<Vec<u8>, Error> {
-    let dump_to_path = std::env::var("OPENTALK_REPORT_DUMP_PATH")
-        .map(|p| Path::new(&p).join(dump_to_relative_path))
-        .ok();
+    let dump_to_path = dump_path.map(|p| p.join(dump_to_relative_path));
+
+    let mut files = BTreeMap::from_iter([(
+        Path::new("data.json"),
+        serde_json::to_string_pretty(parameter)
+            .unwrap()
+            .into_bytes()
+            .into(),
+    )]);
+    if let Some(verification_url) = &parameter.verification_url {
+        let qr_code = opentalk_report_generation::verification_qr_code(verification_url)
+            .context(ReportGenerationSnafu)?;
+        files.insert(
+            Path::new(opentalk_report_generation::VERIFICATION_QR_CODE_FILE),
+            qr_code.into(),
+        );
+    }
 
     opentalk_report_generation::generate_pdf_report(
         template,
-        BTreeMap::from_iter([(
-            Path::new("data.json"),
-            serde_json::to_string_pretty(parameter)
-                .unwrap()
-                .into_bytes()
-                .into(),
-        )]),
+        files,
         dump_to_path.as_deref(),
+        font_path,
     )
     .context(ReportGenerationSnafu)
 }
@@ -81,7 +98,9 @@ mod tests {
         let pdf = generate_from_template(
             DEFAULT_TEMPLATE.to_string(),
             parameter,
+            opentalk_report_generation::resolve_dump_path(None).as_deref(),
             Path::new(&format!("{MODULE_ID}/{sample_name}")),
+            None,
         )
         .expect("generation should work");
         pdf_extract::extract_text_from_mem(&pdf)
@@ -332,4 +351,16 @@ mod tests {
         "#
         );
     }
+
+    #[test]
+    fn generate_with_verification_qr_code() {
+        let mut report_data = example_roll_call();
+        report_data.verification_url =
+            Some("https://opentalk.example.org/verify/21ca8797".to_string());
+
+        let text = generate("verification_qr_code", &report_data);
+
+        assert!(text.contains("Verification"));
+        assert!(text.contains("https://opentalk.example.org/verify/21ca8797"));
+    }
 }
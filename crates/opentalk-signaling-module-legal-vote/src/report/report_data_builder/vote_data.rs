@@ -69,19 +69,21 @@ impl VoteData {
             start_time: start.parameters.start_time.to_report_date_time(timezone),
             participant_count: start.parameters.max_votes,
             duration: start.parameters.inner.duration,
-            enable_abstain: start.parameters.inner.enable_abstain,
+            enable_abstain: start.parameters.inner.enable_abstain.unwrap_or_default(),
             auto_close: start.parameters.inner.auto_close,
             end_time: stop_info.time,
             stop_reason: stop_info.reason,
             vote_count: votes.len() as u32,
             final_results,
             report_timezone: (*timezone).into(),
+            sandbox: start.parameters.inner.sandbox,
         };
 
         Ok(ReportData {
             summary,
             votes,
             events,
+            verification_url: None,
         })
     }
 }
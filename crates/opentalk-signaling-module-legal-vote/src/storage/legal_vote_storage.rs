@@ -2,19 +2,21 @@
 //
 // SPDX-License-Identifier: EUPL-1.2
 
-use std::collections::BTreeSet;
+use std::collections::{BTreeSet, HashMap};
 
 use async_trait::async_trait;
 use opentalk_signaling_core::{
     SignalingModuleError, SignalingRoomId,
     control::storage::{ControlStorageParticipantAttributesRaw, ControlStorageParticipantSet},
 };
+use opentalk_types_common::users::UserId;
+use opentalk_types_signaling::ParticipantId;
 use opentalk_types_signaling_legal_vote::{
     parameters::Parameters, tally::Tally, token::Token, vote::LegalVoteId,
 };
 
 use super::{
-    VoteScriptResult, VoteStatus,
+    PendingConfirmation, VoteScriptResult, VoteStatus,
     protocol::v1::{ProtocolEntry, Vote},
 };
 use crate::error::LegalVoteError;
@@ -22,11 +24,15 @@ use crate::error::LegalVoteError;
 #[async_trait(?Send)]
 pub(crate) trait LegalVoteStorage:
     LegalVoteAllowTokenStorage
+    + LegalVoteParticipantTokenStorage
     + LegalVoteCurrentStorage
     + LegalVoteHistoryStorage
     + LegalVoteParameterStorage
     + LegalVoteProtocolStorage
     + LegalVoteCountStorage
+    + LegalVoteScheduledStorage
+    + LegalVoteInitiatorGraceStorage
+    + LegalVotePendingConfirmationStorage
     + ControlStorageParticipantSet
     + ControlStorageParticipantAttributesRaw
 {
@@ -84,6 +90,25 @@ pub(crate) trait LegalVoteAllowTokenStorage {
     ) -> Result<(), SignalingModuleError>;
 }
 
+#[async_trait(?Send)]
+pub(crate) trait LegalVoteParticipantTokenStorage {
+    /// Remember the tokens handed out to the participants of the provided `legal_vote`
+    async fn participant_tokens_set(
+        &mut self,
+        room: SignalingRoomId,
+        legal_vote: LegalVoteId,
+        tokens: &HashMap<ParticipantId, Token>,
+    ) -> Result<(), SignalingModuleError>;
+
+    /// Get the token handed out to `participant` for the provided `legal_vote`
+    async fn participant_token_get(
+        &mut self,
+        room: SignalingRoomId,
+        legal_vote: LegalVoteId,
+        participant: ParticipantId,
+    ) -> Result<Option<Token>, SignalingModuleError>;
+}
+
 #[async_trait(?Send)]
 pub(crate) trait LegalVoteCurrentStorage {
     /// Set the current vote id to `new_vote`
@@ -113,6 +138,109 @@ pub(crate) trait LegalVoteCurrentStorage {
     ) -> Result<(), SignalingModuleError>;
 }
 
+#[async_trait(?Send)]
+pub(crate) trait LegalVoteScheduledStorage {
+    /// Mark `legal_vote` as scheduled to start at a later point in time
+    async fn scheduled_vote_add(
+        &mut self,
+        room: SignalingRoomId,
+        legal_vote: LegalVoteId,
+    ) -> Result<(), SignalingModuleError>;
+
+    /// Remove `legal_vote` from the scheduled votes
+    ///
+    /// # Returns
+    /// - `Ok(true)` when the vote was scheduled and got removed.
+    /// - `Ok(false)` when the vote was not scheduled, e.g. because it was already started or
+    ///   canceled.
+    async fn scheduled_vote_remove(
+        &mut self,
+        room: SignalingRoomId,
+        legal_vote: LegalVoteId,
+    ) -> Result<bool, SignalingModuleError>;
+
+    /// Delete all scheduled votes of the room
+    async fn scheduled_votes_delete(
+        &mut self,
+        room: SignalingRoomId,
+    ) -> Result<(), SignalingModuleError>;
+}
+
+#[async_trait(?Send)]
+pub(crate) trait LegalVoteInitiatorGraceStorage {
+    /// Keep `legal_vote` running for a grace period after its initiator left
+    async fn initiator_grace_start(
+        &mut self,
+        room: SignalingRoomId,
+        legal_vote: LegalVoteId,
+        initiator: UserId,
+    ) -> Result<(), SignalingModuleError>;
+
+    /// Get the user id of the initiator if `legal_vote` is in its grace period
+    async fn initiator_grace_get(
+        &mut self,
+        room: SignalingRoomId,
+        legal_vote: LegalVoteId,
+    ) -> Result<Option<UserId>, SignalingModuleError>;
+
+    /// End the grace period of `legal_vote`
+    ///
+    /// # Returns
+    /// - `Ok(true)` when the vote was in its grace period and it got ended.
+    /// - `Ok(false)` when the vote was not in its grace period, e.g. because the initiator
+    ///   rejoined or the grace period already expired.
+    async fn initiator_grace_end(
+        &mut self,
+        room: SignalingRoomId,
+        legal_vote: LegalVoteId,
+    ) -> Result<bool, SignalingModuleError>;
+
+    /// Delete all initiator grace periods of the room
+    async fn initiator_graces_delete(
+        &mut self,
+        room: SignalingRoomId,
+    ) -> Result<(), SignalingModuleError>;
+}
+
+#[async_trait(?Send)]
+pub(crate) trait LegalVotePendingConfirmationStorage {
+    /// Mark the results of a stopped vote as pending confirmation
+    ///
+    /// Only sets the pending confirmation if none exists for the room yet.
+    ///
+    /// # Returns
+    /// - `Ok(true)` when the pending confirmation got set.
+    /// - `Ok(false)` when another vote is already pending confirmation.
+    async fn pending_confirmation_set(
+        &mut self,
+        room: SignalingRoomId,
+        pending: &PendingConfirmation,
+    ) -> Result<bool, SignalingModuleError>;
+
+    /// Get the vote of the room which is pending confirmation
+    async fn pending_confirmation_get(
+        &mut self,
+        room: SignalingRoomId,
+    ) -> Result<Option<PendingConfirmation>, SignalingModuleError>;
+
+    /// Remove the pending confirmation if it still equals `pending`
+    ///
+    /// # Returns
+    /// - `Ok(true)` when the pending confirmation got removed.
+    /// - `Ok(false)` when the results were already confirmed or rejected.
+    async fn pending_confirmation_remove(
+        &mut self,
+        room: SignalingRoomId,
+        pending: &PendingConfirmation,
+    ) -> Result<bool, SignalingModuleError>;
+
+    /// Delete the pending confirmation of the room
+    async fn pending_confirmation_delete(
+        &mut self,
+        room: SignalingRoomId,
+    ) -> Result<(), SignalingModuleError>;
+}
+
 #[async_trait(?Send)]
 pub(crate) trait LegalVoteHistoryStorage {
     /// Get the vote history as a hashset
@@ -176,4 +304,19 @@ pub(crate) trait LegalVoteProtocolStorage {
         room: SignalingRoomId,
         legal_vote: LegalVoteId,
     ) -> Result<Vec<ProtocolEntry>, SignalingModuleError>;
+
+    /// Set the number of protocol entries of `legal_vote` which are saved in the database
+    async fn protocol_flushed_set(
+        &mut self,
+        room: SignalingRoomId,
+        legal_vote: LegalVoteId,
+        count: usize,
+    ) -> Result<(), SignalingModuleError>;
+
+    /// Get the number of protocol entries of `legal_vote` which are saved in the database
+    async fn protocol_flushed_get(
+        &mut self,
+        room: SignalingRoomId,
+        legal_vote: LegalVoteId,
+    ) -> Result<usize, SignalingModuleError>;
 }
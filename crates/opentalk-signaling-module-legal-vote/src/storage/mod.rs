@@ -7,12 +7,15 @@ mod redis;
 mod volatile;
 
 mod legal_vote_storage;
+mod pending_confirmation;
 
 use ::redis::{ErrorKind, FromRedisValue, RedisError, RedisResult, Value};
 pub(crate) use legal_vote_storage::{
     LegalVoteAllowTokenStorage, LegalVoteCurrentStorage, LegalVoteHistoryStorage,
-    LegalVoteParameterStorage, LegalVoteStorage,
+    LegalVoteInitiatorGraceStorage, LegalVoteParameterStorage, LegalVoteParticipantTokenStorage,
+    LegalVotePendingConfirmationStorage, LegalVoteScheduledStorage, LegalVoteStorage,
 };
+pub(crate) use pending_confirmation::PendingConfirmation;
 pub use protocol::{NewProtocol, Protocol, v1};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -80,13 +83,14 @@ impl FromRedisValue for VoteScriptResult {
 
 #[cfg(test)]
 pub(crate) mod test_common {
-    use std::vec;
+    use std::{collections::HashMap, vec};
 
     use chrono::DateTime;
     use opentalk_signaling_core::SignalingRoomId;
     use opentalk_types_common::users::UserId;
     use opentalk_types_signaling::ParticipantId;
     use opentalk_types_signaling_legal_vote::{
+        event::StopKind,
         parameters::Parameters,
         token::Token,
         user_parameters::{AllowedParticipants, Duration, UserParameters},
@@ -95,7 +99,7 @@ pub(crate) mod test_common {
     use pretty_assertions::assert_eq;
 
     use super::LegalVoteStorage;
-    use crate::storage::{VoteStatus, protocol::v1::Vote};
+    use crate::storage::{PendingConfirmation, VoteStatus, protocol::v1::Vote};
 
     pub(crate) const ROOM: SignalingRoomId = SignalingRoomId::nil();
     pub(crate) const VOTE: LegalVoteId = LegalVoteId::nil();
@@ -113,11 +117,46 @@ pub(crate) mod test_common {
             .unwrap()
     }
 
+    pub(crate) async fn participant_tokens(storage: &mut dyn LegalVoteStorage) {
+        let tokens = HashMap::from([(ALICE_PARTICIPANT, Token::new(1))]);
+
+        storage
+            .participant_tokens_set(ROOM, VOTE, &tokens)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            storage
+                .participant_token_get(ROOM, VOTE, ALICE_PARTICIPANT)
+                .await
+                .unwrap(),
+            Some(Token::new(1))
+        );
+        assert_eq!(
+            storage
+                .participant_token_get(ROOM, VOTE, ParticipantId::from_u128(1))
+                .await
+                .unwrap(),
+            None
+        );
+
+        storage.cleanup_vote(ROOM, VOTE).await.unwrap();
+
+        assert_eq!(
+            storage
+                .participant_token_get(ROOM, VOTE, ALICE_PARTICIPANT)
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
     fn generate_parameter() -> Parameters {
         Parameters {
             initiator_id: ALICE_PARTICIPANT,
             legal_vote_id: VOTE,
             start_time: DateTime::from_timestamp_millis(1).unwrap(),
+            end_time: None,
             max_votes: 2,
             allowed_users: Some(vec![ALICE_USER]),
             token: Some(Token::generate()),
@@ -131,11 +170,15 @@ pub(crate) mod test_common {
                     ParticipantId::from_u128(2),
                 ])
                 .unwrap(),
-                enable_abstain: false,
+                enable_abstain: Some(false),
                 auto_close: false,
                 duration: Some(Duration::try_from(5u64).unwrap()),
                 create_pdf: true,
                 timezone: Some(chrono_tz::CET),
+                sandbox: false,
+                start_at: None,
+                require_confirmation: false,
+                allow_guests: false,
             },
         }
     }
@@ -159,6 +202,113 @@ pub(crate) mod test_common {
         assert!(storage.current_vote_get(ROOM).await.unwrap().is_none());
     }
 
+    pub(crate) async fn scheduled_vote(storage: &mut dyn LegalVoteStorage) {
+        assert!(!storage.scheduled_vote_remove(ROOM, VOTE).await.unwrap());
+
+        storage.scheduled_vote_add(ROOM, VOTE).await.unwrap();
+        assert!(storage.scheduled_vote_remove(ROOM, VOTE).await.unwrap());
+        assert!(!storage.scheduled_vote_remove(ROOM, VOTE).await.unwrap());
+
+        storage.scheduled_vote_add(ROOM, VOTE).await.unwrap();
+        storage.scheduled_votes_delete(ROOM).await.unwrap();
+        assert!(!storage.scheduled_vote_remove(ROOM, VOTE).await.unwrap());
+    }
+
+    pub(crate) async fn initiator_grace(storage: &mut dyn LegalVoteStorage) {
+        let initiator = UserId::from_u128(1);
+
+        assert!(!storage.initiator_grace_end(ROOM, VOTE).await.unwrap());
+        assert_eq!(storage.initiator_grace_get(ROOM, VOTE).await.unwrap(), None);
+
+        storage
+            .initiator_grace_start(ROOM, VOTE, initiator)
+            .await
+            .unwrap();
+        assert_eq!(
+            storage.initiator_grace_get(ROOM, VOTE).await.unwrap(),
+            Some(initiator)
+        );
+        assert!(storage.initiator_grace_end(ROOM, VOTE).await.unwrap());
+        assert!(!storage.initiator_grace_end(ROOM, VOTE).await.unwrap());
+
+        storage
+            .initiator_grace_start(ROOM, VOTE, initiator)
+            .await
+            .unwrap();
+        storage.initiator_graces_delete(ROOM).await.unwrap();
+        assert_eq!(storage.initiator_grace_get(ROOM, VOTE).await.unwrap(), None);
+    }
+
+    pub(crate) async fn pending_confirmation(storage: &mut dyn LegalVoteStorage) {
+        let pending = PendingConfirmation {
+            legal_vote_id: VOTE,
+            kind: StopKind::Auto,
+            end_time: DateTime::from_timestamp_millis(1).unwrap(),
+        };
+        let other = PendingConfirmation {
+            legal_vote_id: LegalVoteId::generate(),
+            ..pending.clone()
+        };
+
+        assert!(
+            storage
+                .pending_confirmation_get(ROOM)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert!(
+            storage
+                .pending_confirmation_set(ROOM, &pending)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !storage
+                .pending_confirmation_set(ROOM, &other)
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            Some(pending.clone()),
+            storage.pending_confirmation_get(ROOM).await.unwrap()
+        );
+
+        assert!(
+            !storage
+                .pending_confirmation_remove(ROOM, &other)
+                .await
+                .unwrap()
+        );
+        assert!(
+            storage
+                .pending_confirmation_remove(ROOM, &pending)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !storage
+                .pending_confirmation_remove(ROOM, &pending)
+                .await
+                .unwrap()
+        );
+
+        assert!(
+            storage
+                .pending_confirmation_set(ROOM, &pending)
+                .await
+                .unwrap()
+        );
+        storage.pending_confirmation_delete(ROOM).await.unwrap();
+        assert!(
+            storage
+                .pending_confirmation_get(ROOM)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
     pub(crate) async fn parameter(storage: &mut dyn LegalVoteStorage) {
         let parameter: Parameters = generate_parameter();
 
@@ -173,6 +323,16 @@ pub(crate) mod test_common {
         assert!(storage.parameter_get(ROOM, VOTE).await.unwrap().is_none());
     }
 
+    pub(crate) async fn protocol_flushed(storage: &mut dyn LegalVoteStorage) {
+        assert_eq!(0, storage.protocol_flushed_get(ROOM, VOTE).await.unwrap());
+
+        storage.protocol_flushed_set(ROOM, VOTE, 3).await.unwrap();
+        assert_eq!(3, storage.protocol_flushed_get(ROOM, VOTE).await.unwrap());
+
+        storage.cleanup_vote(ROOM, VOTE).await.unwrap();
+        assert_eq!(0, storage.protocol_flushed_get(ROOM, VOTE).await.unwrap());
+    }
+
     pub(crate) async fn voting(storage: &mut dyn LegalVoteStorage) {
         assert!(storage.current_vote_get(ROOM).await.unwrap().is_none());
         let parameter: Parameters = generate_parameter();
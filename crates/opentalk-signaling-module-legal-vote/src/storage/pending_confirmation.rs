@@ -0,0 +1,21 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use chrono::{DateTime, Utc};
+use opentalk_types_signaling_legal_vote::{event::StopKind, vote::LegalVoteId};
+use redis_args::{FromRedisValue, ToRedisArgs};
+use serde::{Deserialize, Serialize};
+
+/// A stopped vote whose results must be confirmed by a moderator before they are final
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToRedisArgs, FromRedisValue)]
+#[to_redis_args(serde)]
+#[from_redis_value(serde)]
+pub(crate) struct PendingConfirmation {
+    /// The id of the stopped vote
+    pub(crate) legal_vote_id: LegalVoteId,
+    /// How the vote was stopped
+    pub(crate) kind: StopKind,
+    /// The time at which the vote was stopped
+    pub(crate) end_time: DateTime<Utc>,
+}
@@ -4,6 +4,7 @@
 
 //! Signaling protocol for the `legal-vote` namespace.
 
+pub mod patch;
 pub mod v1;
 
 mod new_protocol;
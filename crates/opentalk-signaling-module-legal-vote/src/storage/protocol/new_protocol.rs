@@ -2,6 +2,8 @@
 //
 // SPDX-License-Identifier: EUPL-1.2
 
+use opentalk_types_signaling_legal_vote::vote::VoteKind;
+
 use crate::storage::protocol::v1;
 
 /// Represents a new protocol with version and protocol entries.
@@ -21,6 +23,40 @@ impl NewProtocol {
             entries,
         }
     }
+
+    /// Whether the protocol belongs to a pseudonymous vote
+    pub fn is_pseudonymous(&self) -> bool {
+        self.entries.iter().any(|entry| match &entry.event {
+            v1::VoteEvent::Start(start) => start.parameters.inner.kind == VoteKind::Pseudonymous,
+            _ => false,
+        })
+    }
+
+    /// Remove the information identifying the participants from the entries of a pseudonymous
+    /// vote
+    ///
+    /// The protocol of other vote kinds is returned unchanged.
+    pub fn anonymized(mut self) -> Self {
+        if !self.is_pseudonymous() {
+            return self;
+        }
+
+        for entry in &mut self.entries {
+            match &mut entry.event {
+                v1::VoteEvent::Vote(vote) => vote.user_info = None,
+                v1::VoteEvent::Issue(issue) => issue.user_info = None,
+                v1::VoteEvent::UserLeft(user_info) | v1::VoteEvent::UserJoined(user_info) => {
+                    user_info.inner = None
+                }
+                v1::VoteEvent::Start(_)
+                | v1::VoteEvent::Stop(_)
+                | v1::VoteEvent::FinalResults(_)
+                | v1::VoteEvent::Cancel(_) => {}
+            }
+        }
+
+        self
+    }
 }
 
 #[cfg(test)]
@@ -92,3 +128,103 @@ mod serde_tests {
         assert_eq!(produced, expected);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use chrono::{TimeZone, Utc};
+    use opentalk_types_common::users::UserId;
+    use opentalk_types_signaling::ParticipantId;
+    use opentalk_types_signaling_legal_vote::{
+        parameters::Parameters,
+        token::Token,
+        user_parameters::{AllowedParticipants, Name, UserParameters},
+        vote::{LegalVoteId, VoteKind, VoteOption},
+    };
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::storage::v1::{MaybeUserInfo, ProtocolEntry, Start, UserInfo, Vote, VoteEvent};
+
+    fn protocol(kind: VoteKind) -> NewProtocol {
+        let user_info = UserInfo {
+            issuer: UserId::from_u128(1),
+            participant_id: ParticipantId::from_u128(2),
+        };
+
+        NewProtocol::new(vec![
+            ProtocolEntry::new_with_optional_time(
+                None,
+                VoteEvent::Start(Start {
+                    issuer: UserId::from_u128(1),
+                    parameters: Parameters {
+                        initiator_id: ParticipantId::from_u128(2),
+                        legal_vote_id: LegalVoteId::from_u128(3),
+                        start_time: Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+                        end_time: None,
+                        max_votes: 1,
+                        allowed_users: None,
+                        inner: UserParameters {
+                            kind,
+                            name: Name::try_from("Test Name").unwrap(),
+                            subtitle: None,
+                            topic: None,
+                            allowed_participants: AllowedParticipants::try_from(vec![
+                                ParticipantId::from_u128(2),
+                            ])
+                            .unwrap(),
+                            enable_abstain: Some(false),
+                            auto_close: false,
+                            duration: None,
+                            create_pdf: false,
+                            timezone: None,
+                            sandbox: false,
+                            start_at: None,
+                            require_confirmation: false,
+                            allow_guests: false,
+                        },
+                        token: None,
+                    },
+                }),
+            ),
+            ProtocolEntry::new_with_optional_time(
+                None,
+                VoteEvent::UserJoined(MaybeUserInfo::from(Some(user_info))),
+            ),
+            ProtocolEntry::new_with_optional_time(
+                None,
+                VoteEvent::Vote(Vote {
+                    user_info: Some(user_info),
+                    token: Token::from_str("1111Cn8eVZg").unwrap(),
+                    option: VoteOption::Yes,
+                }),
+            ),
+        ])
+    }
+
+    #[test]
+    fn anonymized_pseudonymous_vote() {
+        let anonymized = protocol(VoteKind::Pseudonymous).anonymized();
+
+        let user_infos = anonymized
+            .entries
+            .iter()
+            .filter_map(|entry| match &entry.event {
+                VoteEvent::UserJoined(user_info) => user_info.inner,
+                VoteEvent::Vote(vote) => vote.user_info,
+                _ => None,
+            })
+            .count();
+
+        assert_eq!(user_infos, 0);
+        assert_eq!(anonymized.entries.len(), 3);
+    }
+
+    #[test]
+    fn anonymized_roll_call_vote() {
+        let protocol = protocol(VoteKind::RollCall);
+
+        assert_eq!(protocol.clone().anonymized(), protocol);
+    }
+}
@@ -0,0 +1,61 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+//! JSON patch operations for saving a vote protocol in the database.
+
+use opentalk_db_storage::module_resources::Operation;
+use serde_json::json;
+
+use crate::storage::protocol::{NewProtocol, v1};
+
+/// Build the operations which replace the whole stored protocol with `entries`
+pub fn rewrite_operations(
+    entries: Vec<v1::ProtocolEntry>,
+) -> Result<Vec<Operation>, serde_json::Error> {
+    let protocol = serde_json::to_value(NewProtocol::new(entries))?;
+
+    Ok(vec![Operation::Add {
+        path: "/".into(),
+        value: protocol,
+    }])
+}
+
+/// Build the operations which append the `entries` following the first `flushed` ones to the
+/// stored protocol
+///
+/// The operations start with a test of the last flushed entry, or of the empty entry list when
+/// nothing was flushed yet. Applying them fails when the stored protocol diverged from the flushed
+/// entries, in which case the protocol must be rewritten with [`rewrite_operations`].
+///
+/// Returns [`None`] when more entries were flushed than are available.
+pub fn append_operations(
+    entries: &[v1::ProtocolEntry],
+    flushed: usize,
+) -> Result<Option<Vec<Operation>>, serde_json::Error> {
+    if flushed > entries.len() {
+        return Ok(None);
+    }
+
+    let test = match flushed.checked_sub(1) {
+        Some(last_flushed) => Operation::Test {
+            path: format!("/entries/{last_flushed}"),
+            value: serde_json::to_value(&entries[last_flushed])?,
+        },
+        None => Operation::Test {
+            path: "/entries".into(),
+            value: json!([]),
+        },
+    };
+
+    let mut operations = vec![test];
+
+    for (index, entry) in entries.iter().enumerate().skip(flushed) {
+        operations.push(Operation::Add {
+            path: format!("/entries/{index}"),
+            value: serde_json::to_value(entry)?,
+        });
+    }
+
+    Ok(Some(operations))
+}
@@ -49,6 +49,7 @@ mod serde_tests {
                 initiator_id: ParticipantId::from_u128(1),
                 legal_vote_id: LegalVoteId::from_u128(2),
                 start_time: Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+                end_time: None,
                 max_votes: 5,
                 allowed_users: None,
                 inner: UserParameters {
@@ -60,11 +61,15 @@ mod serde_tests {
                         ParticipantId::from_u128(3),
                     ])
                     .unwrap(),
-                    enable_abstain: false,
+                    enable_abstain: Some(false),
                     auto_close: false,
                     duration: None,
                     create_pdf: false,
                     timezone: None,
+                    sandbox: false,
+                    start_at: None,
+                    require_confirmation: false,
+                    allow_guests: false,
                 },
                 token: None,
             },
@@ -119,6 +124,7 @@ mod serde_tests {
                 initiator_id: ParticipantId::from_u128(1),
                 legal_vote_id: LegalVoteId::from_u128(2),
                 start_time: Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap(),
+                end_time: None,
                 max_votes: 5,
                 allowed_users: None,
                 inner: UserParameters {
@@ -130,11 +136,15 @@ mod serde_tests {
                         ParticipantId::from_u128(3),
                     ])
                     .unwrap(),
-                    enable_abstain: false,
+                    enable_abstain: Some(false),
                     auto_close: false,
                     duration: None,
                     create_pdf: false,
                     timezone: None,
+                    sandbox: false,
+                    start_at: None,
+                    require_confirmation: false,
+                    allow_guests: false,
                 },
                 token: None,
             },
@@ -0,0 +1,82 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use async_trait::async_trait;
+use opentalk_signaling_core::{RedisConnection, RedisSnafu, SignalingModuleError, SignalingRoomId};
+use opentalk_types_common::users::UserId;
+use opentalk_types_signaling_legal_vote::vote::LegalVoteId;
+use redis::AsyncCommands;
+use redis_args::ToRedisArgs;
+use snafu::ResultExt;
+
+use crate::storage::LegalVoteInitiatorGraceStorage;
+
+#[async_trait(?Send)]
+impl LegalVoteInitiatorGraceStorage for RedisConnection {
+    #[tracing::instrument(name = "legal_vote_start_initiator_grace", skip(self))]
+    async fn initiator_grace_start(
+        &mut self,
+        room_id: SignalingRoomId,
+        legal_vote_id: LegalVoteId,
+        initiator: UserId,
+    ) -> Result<(), SignalingModuleError> {
+        self.hset(InitiatorGraceKey { room_id }, legal_vote_id, initiator)
+            .await
+            .context(RedisSnafu {
+                message: "Failed to start the initiator grace period",
+            })
+    }
+
+    #[tracing::instrument(name = "legal_vote_get_initiator_grace", skip(self))]
+    async fn initiator_grace_get(
+        &mut self,
+        room_id: SignalingRoomId,
+        legal_vote_id: LegalVoteId,
+    ) -> Result<Option<UserId>, SignalingModuleError> {
+        self.hget(InitiatorGraceKey { room_id }, legal_vote_id)
+            .await
+            .context(RedisSnafu {
+                message: "Failed to get the initiator grace period",
+            })
+    }
+
+    #[tracing::instrument(name = "legal_vote_end_initiator_grace", skip(self))]
+    async fn initiator_grace_end(
+        &mut self,
+        room_id: SignalingRoomId,
+        legal_vote_id: LegalVoteId,
+    ) -> Result<bool, SignalingModuleError> {
+        let removed: i64 = self
+            .hdel(InitiatorGraceKey { room_id }, legal_vote_id)
+            .await
+            .context(RedisSnafu {
+                message: "Failed to end the initiator grace period",
+            })?;
+
+        Ok(removed == 1)
+    }
+
+    #[tracing::instrument(name = "legal_vote_delete_initiator_graces", skip(self))]
+    async fn initiator_graces_delete(
+        &mut self,
+        room_id: SignalingRoomId,
+    ) -> Result<(), SignalingModuleError> {
+        self.del(InitiatorGraceKey { room_id })
+            .await
+            .context(RedisSnafu {
+                message: "Failed to delete the initiator grace periods",
+            })
+    }
+}
+
+/// Contains the user id of the initiator for every active vote whose initiator left, keyed by the
+/// [`LegalVoteId`].
+///
+/// Removing an entry is atomic, so either the rejoining initiator or the expired grace period
+/// takes effect, never both.
+#[derive(ToRedisArgs)]
+#[to_redis_args(fmt = "opentalk-signaling:room={room_id}:vote:initiator_grace")]
+pub(super) struct InitiatorGraceKey {
+    pub(super) room_id: SignalingRoomId,
+}
@@ -16,7 +16,8 @@ use history::VoteHistoryKey;
 use opentalk_signaling_core::{RedisConnection, RedisSnafu, SignalingModuleError, SignalingRoomId};
 use opentalk_types_signaling_legal_vote::vote::LegalVoteId;
 use parameters::VoteParametersKey;
-use protocol::ProtocolKey;
+use participant_tokens::ParticipantTokensKey;
+use protocol::{ProtocolFlushedKey, ProtocolKey};
 use snafu::ResultExt;
 use vote_count::VoteCountKey;
 
@@ -29,8 +30,12 @@ use crate::{
 pub(crate) mod allowed_tokens;
 pub(crate) mod current_legal_vote_id;
 pub(crate) mod history;
+pub(crate) mod initiator_grace;
 pub(crate) mod parameters;
+pub(crate) mod participant_tokens;
+pub(crate) mod pending_confirmation;
 pub mod protocol;
+pub(crate) mod scheduled;
 pub(crate) mod vote_count;
 
 #[async_trait(?Send)]
@@ -94,6 +99,14 @@ impl LegalVoteStorage for RedisConnection {
                 room_id,
                 legal_vote_id,
             })
+            .key(ProtocolFlushedKey {
+                room_id,
+                legal_vote_id,
+            })
+            .key(ParticipantTokensKey {
+                room_id,
+                legal_vote_id,
+            })
             .arg(legal_vote_id)
             .invoke_async(self)
             .await
@@ -201,6 +214,8 @@ return 1
 /// KEYS[3] = vote parameters key
 /// KEYS[4] = allowed users key
 /// KEYS[5] = vote protocol key
+/// KEYS[6] = flushed vote protocol entry count key
+/// KEYS[7] = participant tokens key
 ///
 /// ARGV[1] = legal_vote_id
 ///
@@ -214,6 +229,8 @@ redis.call("del", KEYS[2])
 redis.call("del", KEYS[3])
 redis.call("del", KEYS[4])
 redis.call("del", KEYS[5])
+redis.call("del", KEYS[6])
+redis.call("del", KEYS[7])
 "#;
 
 /// The user allowed token vote script
@@ -307,18 +324,48 @@ pub(crate) mod tests {
         test_common::allow_token(&mut storage().await).await
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn participant_tokens() {
+        test_common::participant_tokens(&mut storage().await).await
+    }
+
     #[tokio::test]
     #[serial]
     async fn current_vote() {
         test_common::current_vote(&mut storage().await).await
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn scheduled_vote() {
+        test_common::scheduled_vote(&mut storage().await).await
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn initiator_grace() {
+        test_common::initiator_grace(&mut storage().await).await
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn pending_confirmation() {
+        test_common::pending_confirmation(&mut storage().await).await
+    }
+
     #[tokio::test]
     #[serial]
     async fn parameter() {
         test_common::parameter(&mut storage().await).await
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn protocol_flushed() {
+        test_common::protocol_flushed(&mut storage().await).await
+    }
+
     #[tokio::test]
     #[serial]
     async fn voting() {
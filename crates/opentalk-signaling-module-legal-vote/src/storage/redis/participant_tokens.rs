@@ -0,0 +1,82 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use opentalk_signaling_core::{RedisConnection, RedisSnafu, SignalingModuleError, SignalingRoomId};
+use opentalk_types_signaling::ParticipantId;
+use opentalk_types_signaling_legal_vote::{token::Token, vote::LegalVoteId};
+use redis::AsyncCommands;
+use redis_args::ToRedisArgs;
+use snafu::ResultExt;
+
+use crate::storage::LegalVoteParticipantTokenStorage;
+
+#[async_trait(?Send)]
+impl LegalVoteParticipantTokenStorage for RedisConnection {
+    #[tracing::instrument(name = "legal_vote_set_participant_tokens", skip(self, tokens))]
+    async fn participant_tokens_set(
+        &mut self,
+        room_id: SignalingRoomId,
+        legal_vote_id: LegalVoteId,
+        tokens: &HashMap<ParticipantId, Token>,
+    ) -> Result<(), SignalingModuleError> {
+        if tokens.is_empty() {
+            return Ok(());
+        }
+
+        let tokens = Vec::from_iter(
+            tokens
+                .iter()
+                .map(|(participant, token)| (*participant, *token)),
+        );
+
+        self.hset_multiple(
+            ParticipantTokensKey {
+                room_id,
+                legal_vote_id,
+            },
+            &tokens,
+        )
+        .await
+        .with_context(|_| RedisSnafu {
+            message: format!(
+                "Failed to set the participant tokens for room_id:{room_id} legal_vote_id:{legal_vote_id}"
+            ),
+        })
+    }
+
+    #[tracing::instrument(name = "legal_vote_get_participant_token", skip(self))]
+    async fn participant_token_get(
+        &mut self,
+        room_id: SignalingRoomId,
+        legal_vote_id: LegalVoteId,
+        participant: ParticipantId,
+    ) -> Result<Option<Token>, SignalingModuleError> {
+        self.hget(
+            ParticipantTokensKey {
+                room_id,
+                legal_vote_id,
+            },
+            participant,
+        )
+        .await
+        .with_context(|_| RedisSnafu {
+            message: format!(
+                "Failed to get the participant token for room_id:{room_id} legal_vote_id:{legal_vote_id}"
+            ),
+        })
+    }
+}
+
+/// The tokens that were handed out to the participants of a vote, keyed by the participant id.
+///
+/// Only stored for votes which are not hidden, as it links the participants to their votes.
+#[derive(ToRedisArgs)]
+#[to_redis_args(fmt = "opentalk-signaling:room={room_id}:vote={legal_vote_id}:participant_tokens")]
+pub(super) struct ParticipantTokensKey {
+    pub(super) room_id: SignalingRoomId,
+    pub(super) legal_vote_id: LegalVoteId,
+}
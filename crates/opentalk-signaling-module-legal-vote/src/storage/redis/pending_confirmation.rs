@@ -0,0 +1,94 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use async_trait::async_trait;
+use opentalk_signaling_core::{RedisConnection, RedisSnafu, SignalingModuleError, SignalingRoomId};
+use redis::AsyncCommands;
+use redis_args::ToRedisArgs;
+use snafu::ResultExt;
+
+use crate::storage::{LegalVotePendingConfirmationStorage, PendingConfirmation};
+
+/// Deletes the pending confirmation only if it still matches the provided value.
+///
+/// KEYS[1] = pending confirmation key
+/// ARGV[1] = expected pending confirmation
+const REMOVE_PENDING_CONFIRMATION_SCRIPT: &str = r#"
+if redis.call("GET", KEYS[1]) == ARGV[1] then
+  return redis.call("DEL", KEYS[1])
+else
+  return 0
+end
+"#;
+
+#[async_trait(?Send)]
+impl LegalVotePendingConfirmationStorage for RedisConnection {
+    #[tracing::instrument(name = "legal_vote_set_pending_confirmation", skip(self))]
+    async fn pending_confirmation_set(
+        &mut self,
+        room_id: SignalingRoomId,
+        pending: &PendingConfirmation,
+    ) -> Result<bool, SignalingModuleError> {
+        let affected_entries: i64 = self
+            .set_nx(PendingConfirmationKey { room_id }, pending)
+            .await
+            .context(RedisSnafu {
+                message: "Failed to set pending confirmation",
+            })?;
+
+        Ok(affected_entries == 1)
+    }
+
+    #[tracing::instrument(name = "legal_vote_get_pending_confirmation", skip(self))]
+    async fn pending_confirmation_get(
+        &mut self,
+        room_id: SignalingRoomId,
+    ) -> Result<Option<PendingConfirmation>, SignalingModuleError> {
+        self.get(PendingConfirmationKey { room_id })
+            .await
+            .context(RedisSnafu {
+                message: "Failed to get pending confirmation",
+            })
+    }
+
+    #[tracing::instrument(name = "legal_vote_remove_pending_confirmation", skip(self))]
+    async fn pending_confirmation_remove(
+        &mut self,
+        room_id: SignalingRoomId,
+        pending: &PendingConfirmation,
+    ) -> Result<bool, SignalingModuleError> {
+        let removed: i64 = redis::Script::new(REMOVE_PENDING_CONFIRMATION_SCRIPT)
+            .key(PendingConfirmationKey { room_id })
+            .arg(pending)
+            .invoke_async(self)
+            .await
+            .context(RedisSnafu {
+                message: "Failed to remove pending confirmation",
+            })?;
+
+        Ok(removed == 1)
+    }
+
+    #[tracing::instrument(name = "legal_vote_delete_pending_confirmation", skip(self))]
+    async fn pending_confirmation_delete(
+        &mut self,
+        room_id: SignalingRoomId,
+    ) -> Result<(), SignalingModuleError> {
+        self.del(PendingConfirmationKey { room_id })
+            .await
+            .context(RedisSnafu {
+                message: "Failed to delete pending confirmation",
+            })
+    }
+}
+
+/// Contains the [`PendingConfirmation`] of a stopped vote whose results have not been confirmed
+/// or rejected yet.
+///
+/// Like the current vote id key, this key prevents new votes from being started while it exists.
+#[derive(ToRedisArgs)]
+#[to_redis_args(fmt = "opentalk-signaling:room={room_id}:vote:pending_confirmation")]
+pub(super) struct PendingConfirmationKey {
+    pub(super) room_id: SignalingRoomId,
+}
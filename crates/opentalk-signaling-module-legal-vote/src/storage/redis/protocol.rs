@@ -56,6 +56,47 @@ impl LegalVoteProtocolStorage for RedisConnection {
             message: "Failed to get vote protocol",
         })
     }
+
+    /// Set the number of protocol entries of `legal_vote_id` which are saved in the database
+    #[tracing::instrument(name = "legal_vote_set_protocol_flushed", skip(self))]
+    async fn protocol_flushed_set(
+        &mut self,
+        room_id: SignalingRoomId,
+        legal_vote_id: LegalVoteId,
+        count: usize,
+    ) -> Result<(), SignalingModuleError> {
+        self.set::<_, _, ()>(
+            ProtocolFlushedKey {
+                room_id,
+                legal_vote_id,
+            },
+            count,
+        )
+        .await
+        .context(RedisSnafu {
+            message: "Failed to set the flushed vote protocol entry count",
+        })
+    }
+
+    /// Get the number of protocol entries of `legal_vote_id` which are saved in the database
+    #[tracing::instrument(name = "legal_vote_get_protocol_flushed", skip(self))]
+    async fn protocol_flushed_get(
+        &mut self,
+        room_id: SignalingRoomId,
+        legal_vote_id: LegalVoteId,
+    ) -> Result<usize, SignalingModuleError> {
+        let count: Option<usize> = self
+            .get(ProtocolFlushedKey {
+                room_id,
+                legal_vote_id,
+            })
+            .await
+            .context(RedisSnafu {
+                message: "Failed to get the flushed vote protocol entry count",
+            })?;
+
+        Ok(count.unwrap_or_default())
+    }
 }
 
 /// Contains the vote protocol. The vote protocol is a list of [`ProtocolEntries`](ProtocolEntry)
@@ -66,3 +107,11 @@ pub(super) struct ProtocolKey {
     pub(super) room_id: SignalingRoomId,
     pub(super) legal_vote_id: LegalVoteId,
 }
+
+/// Contains the number of entries of the vote protocol which are already saved in the database
+#[derive(ToRedisArgs)]
+#[to_redis_args(fmt = "opentalk-signaling:room={room_id}:vote={legal_vote_id}:protocol_flushed")]
+pub(super) struct ProtocolFlushedKey {
+    pub(super) room_id: SignalingRoomId,
+    pub(super) legal_vote_id: LegalVoteId,
+}
@@ -0,0 +1,67 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+use async_trait::async_trait;
+use opentalk_signaling_core::{RedisConnection, RedisSnafu, SignalingModuleError, SignalingRoomId};
+use opentalk_types_signaling_legal_vote::vote::LegalVoteId;
+use redis::AsyncCommands;
+use redis_args::ToRedisArgs;
+use snafu::ResultExt;
+
+use crate::storage::LegalVoteScheduledStorage;
+
+#[async_trait(?Send)]
+impl LegalVoteScheduledStorage for RedisConnection {
+    #[tracing::instrument(name = "legal_vote_add_scheduled_vote", skip(self))]
+    async fn scheduled_vote_add(
+        &mut self,
+        room_id: SignalingRoomId,
+        legal_vote_id: LegalVoteId,
+    ) -> Result<(), SignalingModuleError> {
+        self.sadd(ScheduledVotesKey { room_id }, legal_vote_id)
+            .await
+            .context(RedisSnafu {
+                message: "Failed to add scheduled vote",
+            })
+    }
+
+    #[tracing::instrument(name = "legal_vote_remove_scheduled_vote", skip(self))]
+    async fn scheduled_vote_remove(
+        &mut self,
+        room_id: SignalingRoomId,
+        legal_vote_id: LegalVoteId,
+    ) -> Result<bool, SignalingModuleError> {
+        let removed: i64 = self
+            .srem(ScheduledVotesKey { room_id }, legal_vote_id)
+            .await
+            .context(RedisSnafu {
+                message: "Failed to remove scheduled vote",
+            })?;
+
+        Ok(removed == 1)
+    }
+
+    #[tracing::instrument(name = "legal_vote_delete_scheduled_votes", skip(self))]
+    async fn scheduled_votes_delete(
+        &mut self,
+        room_id: SignalingRoomId,
+    ) -> Result<(), SignalingModuleError> {
+        self.del(ScheduledVotesKey { room_id })
+            .await
+            .context(RedisSnafu {
+                message: "Failed to delete scheduled votes",
+            })
+    }
+}
+
+/// Contains a set of [`LegalVoteId`] from all votes that are scheduled to start at a later point in
+/// time.
+///
+/// Removing a vote from this set is atomic, so either the scheduled start or a cancellation takes
+/// effect, never both.
+#[derive(ToRedisArgs)]
+#[to_redis_args(fmt = "opentalk-signaling:room={room_id}:vote:scheduled")]
+pub(super) struct ScheduledVotesKey {
+    pub(super) room_id: SignalingRoomId,
+}
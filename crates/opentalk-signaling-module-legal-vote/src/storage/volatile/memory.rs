@@ -6,6 +6,8 @@ use std::collections::{BTreeSet, HashMap};
 
 use chrono::Utc;
 use opentalk_signaling_core::SignalingRoomId;
+use opentalk_types_common::users::UserId;
+use opentalk_types_signaling::ParticipantId;
 use opentalk_types_signaling_legal_vote::{
     parameters::Parameters,
     tally::Tally,
@@ -16,7 +18,7 @@ use opentalk_types_signaling_legal_vote::{
 use crate::{
     error::{ErrorKind, LegalVoteError},
     storage::{
-        VoteScriptResult, VoteStatus,
+        PendingConfirmation, VoteScriptResult, VoteStatus,
         protocol::v1::{ProtocolEntry, Vote, VoteEvent},
     },
 };
@@ -24,11 +26,16 @@ use crate::{
 #[derive(Debug, Clone, Default)]
 pub(crate) struct MemoryLegalVoteState {
     allowed_tokens: HashMap<(SignalingRoomId, LegalVoteId), BTreeSet<Token>>,
+    participant_tokens: HashMap<(SignalingRoomId, LegalVoteId), HashMap<ParticipantId, Token>>,
     count: HashMap<(SignalingRoomId, LegalVoteId), Tally>,
     parameters: HashMap<(SignalingRoomId, LegalVoteId), Parameters>,
     protocol: HashMap<(SignalingRoomId, LegalVoteId), Vec<ProtocolEntry>>,
+    protocol_flushed: HashMap<(SignalingRoomId, LegalVoteId), usize>,
     current_vote: HashMap<SignalingRoomId, LegalVoteId>,
     history: HashMap<SignalingRoomId, BTreeSet<LegalVoteId>>,
+    scheduled: HashMap<SignalingRoomId, BTreeSet<LegalVoteId>>,
+    initiator_grace: HashMap<SignalingRoomId, HashMap<LegalVoteId, UserId>>,
+    pending_confirmation: HashMap<SignalingRoomId, PendingConfirmation>,
 }
 
 impl MemoryLegalVoteState {
@@ -60,7 +67,9 @@ impl MemoryLegalVoteState {
 
         self.parameters.remove(&(room, legal_vote));
         self.allowed_tokens.remove(&(room, legal_vote));
+        self.participant_tokens.remove(&(room, legal_vote));
         self.protocol.remove(&(room, legal_vote));
+        self.protocol_flushed.remove(&(room, legal_vote));
         self.count.remove(&(room, legal_vote));
     }
 
@@ -127,6 +136,30 @@ impl MemoryLegalVoteState {
             .insert((room, vote), BTreeSet::from_iter(allowed_tokens));
     }
 
+    pub(crate) fn participant_tokens_set(
+        &mut self,
+        room: SignalingRoomId,
+        vote: LegalVoteId,
+        tokens: &HashMap<ParticipantId, Token>,
+    ) {
+        self.participant_tokens
+            .entry((room, vote))
+            .or_default()
+            .extend(tokens);
+    }
+
+    pub(crate) fn participant_token_get(
+        &self,
+        room: SignalingRoomId,
+        vote: LegalVoteId,
+        participant: ParticipantId,
+    ) -> Option<Token> {
+        self.participant_tokens
+            .get(&(room, vote))
+            .and_then(|tokens| tokens.get(&participant))
+            .copied()
+    }
+
     pub(crate) fn current_vote_set(
         &mut self,
         room: SignalingRoomId,
@@ -149,6 +182,94 @@ impl MemoryLegalVoteState {
         self.current_vote.remove(&room);
     }
 
+    pub(crate) fn scheduled_vote_add(&mut self, room: SignalingRoomId, vote: LegalVoteId) {
+        self.scheduled.entry(room).or_default().insert(vote);
+    }
+
+    pub(crate) fn scheduled_vote_remove(
+        &mut self,
+        room: SignalingRoomId,
+        vote: LegalVoteId,
+    ) -> bool {
+        self.scheduled
+            .get_mut(&room)
+            .is_some_and(|scheduled| scheduled.remove(&vote))
+    }
+
+    pub(crate) fn scheduled_votes_delete(&mut self, room: SignalingRoomId) {
+        self.scheduled.remove(&room);
+    }
+
+    pub(crate) fn initiator_grace_start(
+        &mut self,
+        room: SignalingRoomId,
+        vote: LegalVoteId,
+        initiator: UserId,
+    ) {
+        self.initiator_grace
+            .entry(room)
+            .or_default()
+            .insert(vote, initiator);
+    }
+
+    pub(crate) fn initiator_grace_get(
+        &self,
+        room: SignalingRoomId,
+        vote: LegalVoteId,
+    ) -> Option<UserId> {
+        self.initiator_grace
+            .get(&room)
+            .and_then(|grace| grace.get(&vote))
+            .copied()
+    }
+
+    pub(crate) fn initiator_grace_end(&mut self, room: SignalingRoomId, vote: LegalVoteId) -> bool {
+        self.initiator_grace
+            .get_mut(&room)
+            .is_some_and(|grace| grace.remove(&vote).is_some())
+    }
+
+    pub(crate) fn initiator_graces_delete(&mut self, room: SignalingRoomId) {
+        self.initiator_grace.remove(&room);
+    }
+
+    pub(crate) fn pending_confirmation_set(
+        &mut self,
+        room: SignalingRoomId,
+        pending: &PendingConfirmation,
+    ) -> bool {
+        if self.pending_confirmation.contains_key(&room) {
+            return false;
+        }
+
+        self.pending_confirmation.insert(room, pending.clone());
+        true
+    }
+
+    pub(crate) fn pending_confirmation_get(
+        &self,
+        room: SignalingRoomId,
+    ) -> Option<PendingConfirmation> {
+        self.pending_confirmation.get(&room).cloned()
+    }
+
+    pub(crate) fn pending_confirmation_remove(
+        &mut self,
+        room: SignalingRoomId,
+        pending: &PendingConfirmation,
+    ) -> bool {
+        if self.pending_confirmation.get(&room) != Some(pending) {
+            return false;
+        }
+
+        self.pending_confirmation.remove(&room);
+        true
+    }
+
+    pub(crate) fn pending_confirmation_delete(&mut self, room: SignalingRoomId) {
+        self.pending_confirmation.remove(&room);
+    }
+
     pub(crate) fn history_get(&self, room: SignalingRoomId) -> BTreeSet<LegalVoteId> {
         self.history.get(&room).cloned().unwrap_or_default()
     }
@@ -215,6 +336,22 @@ impl MemoryLegalVoteState {
             .unwrap_or_default()
     }
 
+    pub(crate) fn protocol_flushed_set(
+        &mut self,
+        room: SignalingRoomId,
+        vote: LegalVoteId,
+        count: usize,
+    ) {
+        self.protocol_flushed.insert((room, vote), count);
+    }
+
+    pub(crate) fn protocol_flushed_get(&self, room: SignalingRoomId, vote: LegalVoteId) -> usize {
+        self.protocol_flushed
+            .get(&(room, vote))
+            .copied()
+            .unwrap_or_default()
+    }
+
     fn consume_allow_token(
         &mut self,
         room: SignalingRoomId,
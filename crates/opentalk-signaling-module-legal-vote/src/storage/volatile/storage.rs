@@ -3,12 +3,14 @@
 // SPDX-License-Identifier: EUPL-1.2
 
 use std::{
-    collections::BTreeSet,
+    collections::{BTreeSet, HashMap},
     sync::{Arc, OnceLock},
 };
 
 use async_trait::async_trait;
 use opentalk_signaling_core::{SignalingModuleError, SignalingRoomId, VolatileStaticMemoryStorage};
+use opentalk_types_common::users::UserId;
+use opentalk_types_signaling::ParticipantId;
 use opentalk_types_signaling_legal_vote::{
     parameters::Parameters, tally::Tally, token::Token, vote::LegalVoteId,
 };
@@ -19,7 +21,10 @@ use crate::{
     error::LegalVoteError,
     storage::{
         LegalVoteAllowTokenStorage, LegalVoteCurrentStorage, LegalVoteHistoryStorage,
-        LegalVoteParameterStorage, LegalVoteStorage, VoteScriptResult, VoteStatus,
+        LegalVoteInitiatorGraceStorage, LegalVoteParameterStorage,
+        LegalVoteParticipantTokenStorage, LegalVotePendingConfirmationStorage,
+        LegalVoteScheduledStorage, LegalVoteStorage, PendingConfirmation, VoteScriptResult,
+        VoteStatus,
         legal_vote_storage::{LegalVoteCountStorage, LegalVoteProtocolStorage},
         protocol::v1::{ProtocolEntry, Vote},
     },
@@ -90,6 +95,34 @@ impl LegalVoteAllowTokenStorage for VolatileStaticMemoryStorage {
     }
 }
 
+#[async_trait(?Send)]
+impl LegalVoteParticipantTokenStorage for VolatileStaticMemoryStorage {
+    #[tracing::instrument(name = "legal_vote_set_participant_tokens", skip(self, tokens))]
+    async fn participant_tokens_set(
+        &mut self,
+        room: SignalingRoomId,
+        legal_vote: LegalVoteId,
+        tokens: &HashMap<ParticipantId, Token>,
+    ) -> Result<(), SignalingModuleError> {
+        state()
+            .write()
+            .participant_tokens_set(room, legal_vote, tokens);
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "legal_vote_get_participant_token", skip(self))]
+    async fn participant_token_get(
+        &mut self,
+        room: SignalingRoomId,
+        legal_vote: LegalVoteId,
+        participant: ParticipantId,
+    ) -> Result<Option<Token>, SignalingModuleError> {
+        Ok(state()
+            .read()
+            .participant_token_get(room, legal_vote, participant))
+    }
+}
+
 #[async_trait(?Send)]
 impl LegalVoteCurrentStorage for VolatileStaticMemoryStorage {
     #[tracing::instrument(name = "legal_vote_set_current_vote_id", skip(self))]
@@ -119,6 +152,118 @@ impl LegalVoteCurrentStorage for VolatileStaticMemoryStorage {
     }
 }
 
+#[async_trait(?Send)]
+impl LegalVoteScheduledStorage for VolatileStaticMemoryStorage {
+    #[tracing::instrument(name = "legal_vote_add_scheduled_vote", skip(self))]
+    async fn scheduled_vote_add(
+        &mut self,
+        room: SignalingRoomId,
+        legal_vote: LegalVoteId,
+    ) -> Result<(), SignalingModuleError> {
+        state().write().scheduled_vote_add(room, legal_vote);
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "legal_vote_remove_scheduled_vote", skip(self))]
+    async fn scheduled_vote_remove(
+        &mut self,
+        room: SignalingRoomId,
+        legal_vote: LegalVoteId,
+    ) -> Result<bool, SignalingModuleError> {
+        Ok(state().write().scheduled_vote_remove(room, legal_vote))
+    }
+
+    #[tracing::instrument(name = "legal_vote_delete_scheduled_votes", skip(self))]
+    async fn scheduled_votes_delete(
+        &mut self,
+        room: SignalingRoomId,
+    ) -> Result<(), SignalingModuleError> {
+        state().write().scheduled_votes_delete(room);
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl LegalVoteInitiatorGraceStorage for VolatileStaticMemoryStorage {
+    #[tracing::instrument(name = "legal_vote_start_initiator_grace", skip(self))]
+    async fn initiator_grace_start(
+        &mut self,
+        room: SignalingRoomId,
+        legal_vote: LegalVoteId,
+        initiator: UserId,
+    ) -> Result<(), SignalingModuleError> {
+        state()
+            .write()
+            .initiator_grace_start(room, legal_vote, initiator);
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "legal_vote_get_initiator_grace", skip(self))]
+    async fn initiator_grace_get(
+        &mut self,
+        room: SignalingRoomId,
+        legal_vote: LegalVoteId,
+    ) -> Result<Option<UserId>, SignalingModuleError> {
+        Ok(state().read().initiator_grace_get(room, legal_vote))
+    }
+
+    #[tracing::instrument(name = "legal_vote_end_initiator_grace", skip(self))]
+    async fn initiator_grace_end(
+        &mut self,
+        room: SignalingRoomId,
+        legal_vote: LegalVoteId,
+    ) -> Result<bool, SignalingModuleError> {
+        Ok(state().write().initiator_grace_end(room, legal_vote))
+    }
+
+    #[tracing::instrument(name = "legal_vote_delete_initiator_graces", skip(self))]
+    async fn initiator_graces_delete(
+        &mut self,
+        room: SignalingRoomId,
+    ) -> Result<(), SignalingModuleError> {
+        state().write().initiator_graces_delete(room);
+        Ok(())
+    }
+}
+
+#[async_trait(?Send)]
+impl LegalVotePendingConfirmationStorage for VolatileStaticMemoryStorage {
+    #[tracing::instrument(name = "legal_vote_set_pending_confirmation", skip(self))]
+    async fn pending_confirmation_set(
+        &mut self,
+        room: SignalingRoomId,
+        pending: &PendingConfirmation,
+    ) -> Result<bool, SignalingModuleError> {
+        Ok(state().write().pending_confirmation_set(room, pending))
+    }
+
+    #[tracing::instrument(name = "legal_vote_get_pending_confirmation", skip(self))]
+    async fn pending_confirmation_get(
+        &mut self,
+        room: SignalingRoomId,
+    ) -> Result<Option<PendingConfirmation>, SignalingModuleError> {
+        Ok(state().read().pending_confirmation_get(room))
+    }
+
+    #[tracing::instrument(name = "legal_vote_remove_pending_confirmation", skip(self))]
+    async fn pending_confirmation_remove(
+        &mut self,
+        room: SignalingRoomId,
+        pending: &PendingConfirmation,
+    ) -> Result<bool, SignalingModuleError> {
+        Ok(state().write().pending_confirmation_remove(room, pending))
+    }
+
+    #[tracing::instrument(name = "legal_vote_delete_pending_confirmation", skip(self))]
+    async fn pending_confirmation_delete(
+        &mut self,
+        room: SignalingRoomId,
+    ) -> Result<(), SignalingModuleError> {
+        state().write().pending_confirmation_delete(room);
+        Ok(())
+    }
+}
+
 #[async_trait(?Send)]
 impl LegalVoteHistoryStorage for VolatileStaticMemoryStorage {
     #[tracing::instrument(name = "legal_vote_get_history", skip(self))]
@@ -206,6 +351,30 @@ impl LegalVoteProtocolStorage for VolatileStaticMemoryStorage {
     ) -> Result<Vec<ProtocolEntry>, SignalingModuleError> {
         Ok(state().read().protocol_get(room, legal_vote))
     }
+
+    /// Set the number of protocol entries of `legal_vote_id` which are saved in the database
+    #[tracing::instrument(name = "legal_vote_set_protocol_flushed", skip(self))]
+    async fn protocol_flushed_set(
+        &mut self,
+        room: SignalingRoomId,
+        legal_vote: LegalVoteId,
+        count: usize,
+    ) -> Result<(), SignalingModuleError> {
+        state()
+            .write()
+            .protocol_flushed_set(room, legal_vote, count);
+        Ok(())
+    }
+
+    /// Get the number of protocol entries of `legal_vote_id` which are saved in the database
+    #[tracing::instrument(name = "legal_vote_get_protocol_flushed", skip(self))]
+    async fn protocol_flushed_get(
+        &mut self,
+        room: SignalingRoomId,
+        legal_vote: LegalVoteId,
+    ) -> Result<usize, SignalingModuleError> {
+        Ok(state().read().protocol_flushed_get(room, legal_vote))
+    }
 }
 
 #[cfg(test)]
@@ -227,18 +396,48 @@ pub(crate) mod tests {
         test_common::allow_token(&mut storage()).await
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn participant_tokens() {
+        test_common::participant_tokens(&mut storage()).await
+    }
+
     #[tokio::test]
     #[serial]
     async fn current_vote() {
         test_common::current_vote(&mut storage()).await
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn scheduled_vote() {
+        test_common::scheduled_vote(&mut storage()).await
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn initiator_grace() {
+        test_common::initiator_grace(&mut storage()).await
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn pending_confirmation() {
+        test_common::pending_confirmation(&mut storage()).await
+    }
+
     #[tokio::test]
     #[serial]
     async fn parameter() {
         test_common::parameter(&mut storage()).await
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn protocol_flushed() {
+        test_common::protocol_flushed(&mut storage()).await
+    }
+
     #[tokio::test]
     #[serial]
     async fn voting() {
@@ -2,34 +2,49 @@
 //
 // SPDX-License-Identifier: EUPL-1.2
 
-use std::{collections::HashMap, time::Duration};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
 
 use chrono::{DateTime, TimeZone, Utc};
+use opentalk_controller_settings::{
+    DEFAULT_LEGAL_VOTE_MAX_ALLOWED_PARTICIPANTS, LegalVoteTenant,
+    settings_file::{LegalVoteKind, LegalVotePdfPolicy},
+};
 use opentalk_db_storage::{
-    module_resources::{Filter, ModuleResource},
+    module_resources::{Filter, ModuleResource, NewModuleResource},
     users::User,
 };
 use opentalk_signaling_core::{
-    SignalingModule, SignalingModuleError,
+    MalformedMessage, MalformedMessageError, MalformedMessageReason, SignalingModule,
+    SignalingModuleError, SignalingRoomId,
     module_tester::{ModuleTester, WsMessageOutgoing},
 };
 use opentalk_signaling_module_legal_vote::{
-    LegalVote,
-    storage::{Protocol, v1::ProtocolEntry},
+    LegalVote, LegalVoteParams,
+    storage::{
+        NewProtocol, Protocol,
+        protocol::patch,
+        v1::{self as protocol_v1, ProtocolEntry},
+    },
 };
 use opentalk_test_util::{
     ROOM_ID, TestContext, TestUser, USER_1, USER_2, USERS,
     common::{self, TestContextVolatileStorage},
 };
-use opentalk_types_common::users::DisplayName;
+use opentalk_types_common::{time::Timestamp, users::DisplayName};
 use opentalk_types_signaling::{ParticipantId, Role};
 use opentalk_types_signaling_control::event::ControlEvent;
 use opentalk_types_signaling_legal_vote::{
     cancel::{CancelReason, CustomCancelReason},
-    command::{Cancel, LegalVoteCommand, Stop, Vote},
+    command::{Cancel, ConfirmResults, GetNonVoters, LegalVoteCommand, Stop, Vote},
     event::{
-        Canceled, ErrorKind, FinalResults, GuestParticipants, LegalVoteEvent, Response, Results,
-        StopKind, Stopped, VoteFailed, VoteResponse, VoteResults, VoteSuccess, VotingRecord,
+        AwaitingConfirmation, Canceled, ErrorKind, FinalResults, GuestParticipants, LegalVoteEvent,
+        MaxAllowedParticipants, NonVoters, ProvisionalResults, Response, Results, StopKind,
+        Stopped, VoteFailed, VoteResponse, VoteResults, VoteSuccess, VotingRecord,
     },
     parameters::Parameters,
     state::LegalVoteState,
@@ -74,7 +89,8 @@ async fn basic_vote_roll_call_memory() {
 
 async fn basic_vote_roll_call(storage: TestContextVolatileStorage) {
     let test_ctx = TestContext::new(storage).await;
-    let (mut module_tester, user1, _user2) = common::setup_users::<LegalVote>(&test_ctx, ()).await;
+    let (mut module_tester, user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, Arc::default()).await;
     let mut db_conn = test_ctx.db_ctx.db.get_conn().await.unwrap();
 
     // Start legal vote as user 1
@@ -88,11 +104,15 @@ async fn basic_vote_roll_call(storage: TestContextVolatileStorage) {
             USER_2.participant_id,
         ])
         .unwrap(),
-        enable_abstain: false,
+        enable_abstain: Some(false),
         auto_close: false,
         duration: None,
         create_pdf: false,
         timezone: None,
+        sandbox: false,
+        start_at: None,
+        require_confirmation: false,
+        allow_guests: false,
     };
 
     module_tester
@@ -324,7 +344,8 @@ async fn basic_vote_live_roll_call_memory() {
 
 async fn basic_vote_live_roll_call(storage: TestContextVolatileStorage) {
     let test_ctx = TestContext::new(storage).await;
-    let (mut module_tester, user1, _user2) = common::setup_users::<LegalVote>(&test_ctx, ()).await;
+    let (mut module_tester, user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, Arc::default()).await;
     let mut db_conn = test_ctx.db_ctx.db.get_conn().await.unwrap();
 
     // Start legal vote as user 1
@@ -338,11 +359,15 @@ async fn basic_vote_live_roll_call(storage: TestContextVolatileStorage) {
             USER_2.participant_id,
         ])
         .unwrap(),
-        enable_abstain: false,
+        enable_abstain: Some(false),
         auto_close: false,
         duration: None,
         create_pdf: false,
         timezone: None,
+        sandbox: false,
+        start_at: None,
+        require_confirmation: false,
+        allow_guests: false,
     };
 
     module_tester
@@ -574,7 +599,8 @@ async fn basic_vote_pseudonymous_memory() {
 
 async fn basic_vote_pseudonymous(storage: TestContextVolatileStorage) {
     let test_ctx = TestContext::new(storage).await;
-    let (mut module_tester, user1, _user2) = common::setup_users::<LegalVote>(&test_ctx, ()).await;
+    let (mut module_tester, user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, Arc::default()).await;
     let mut db_conn = test_ctx.db_ctx.db.get_conn().await.unwrap();
 
     // Start legal vote as user 1
@@ -588,11 +614,15 @@ async fn basic_vote_pseudonymous(storage: TestContextVolatileStorage) {
             USER_2.participant_id,
         ])
         .unwrap(),
-        enable_abstain: false,
+        enable_abstain: Some(false),
         auto_close: false,
         duration: None,
         create_pdf: false,
         timezone: None,
+        sandbox: false,
+        start_at: None,
+        require_confirmation: false,
+        allow_guests: false,
     };
 
     module_tester
@@ -780,7 +810,8 @@ async fn hidden_legal_vote_memory() {
 
 async fn hidden_legal_vote(storage: TestContextVolatileStorage) {
     let test_ctx = TestContext::new(storage).await;
-    let (mut module_tester, user1, _user2) = common::setup_users::<LegalVote>(&test_ctx, ()).await;
+    let (mut module_tester, user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, Arc::default()).await;
     let mut db_conn = test_ctx.db_ctx.db.get_conn().await.unwrap();
 
     // Start legal vote as user 1
@@ -794,11 +825,15 @@ async fn hidden_legal_vote(storage: TestContextVolatileStorage) {
             USER_2.participant_id,
         ])
         .unwrap(),
-        enable_abstain: false,
+        enable_abstain: Some(false),
         auto_close: false,
         duration: None,
         create_pdf: false,
         timezone: None,
+        sandbox: false,
+        start_at: None,
+        require_confirmation: false,
+        allow_guests: false,
     };
 
     module_tester
@@ -987,7 +1022,8 @@ async fn basic_vote_abstain_memory() {
 
 async fn basic_vote_abstain(storage: TestContextVolatileStorage) {
     let test_ctx = TestContext::new(storage).await;
-    let (mut module_tester, user1, _user2) = common::setup_users::<LegalVote>(&test_ctx, ()).await;
+    let (mut module_tester, user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, Arc::default()).await;
     let mut db_conn = test_ctx.db_ctx.db.get_conn().await.unwrap();
 
     // Start legal vote as user 1
@@ -1001,11 +1037,15 @@ async fn basic_vote_abstain(storage: TestContextVolatileStorage) {
             USER_2.participant_id,
         ])
         .unwrap(),
-        enable_abstain: true,
+        enable_abstain: Some(true),
         auto_close: false,
         duration: None,
         create_pdf: false,
         timezone: None,
+        sandbox: false,
+        start_at: None,
+        require_confirmation: false,
+        allow_guests: false,
     };
 
     module_tester
@@ -1237,7 +1277,8 @@ async fn expired_vote_memory() {
 
 async fn expired_vote(storage: TestContextVolatileStorage) {
     let test_ctx = TestContext::new(storage).await;
-    let (mut module_tester, user1, _user2) = common::setup_users::<LegalVote>(&test_ctx, ()).await;
+    let (mut module_tester, user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, Arc::default()).await;
     let mut db_conn = test_ctx.db_ctx.db.get_conn().await.unwrap();
 
     // Start legal vote as user 1
@@ -1251,11 +1292,15 @@ async fn expired_vote(storage: TestContextVolatileStorage) {
             USER_2.participant_id,
         ])
         .unwrap(),
-        enable_abstain: false,
+        enable_abstain: Some(false),
         auto_close: false,
         duration: Some(user_parameters::Duration::try_from(5).unwrap()),
         create_pdf: false,
         timezone: None,
+        sandbox: false,
+        start_at: None,
+        require_confirmation: false,
+        allow_guests: false,
     };
 
     module_tester
@@ -1275,6 +1320,10 @@ async fn expired_vote(storage: TestContextVolatileStorage) {
         assert_eq!(parameters.initiator_id, USER_1.participant_id);
         assert_eq!(parameters.inner, start_parameters);
         assert_eq!(parameters.max_votes, 2);
+        assert_eq!(
+            parameters.end_time,
+            Some(parameters.start_time + chrono::Duration::seconds(5))
+        );
 
         parameters.legal_vote_id
     } else {
@@ -1374,7 +1423,8 @@ async fn auto_stop_vote_memory() {
 
 async fn auto_stop_vote(storage: TestContextVolatileStorage) {
     let test_ctx = TestContext::new(storage).await;
-    let (mut module_tester, user1, _user2) = common::setup_users::<LegalVote>(&test_ctx, ()).await;
+    let (mut module_tester, user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, Arc::default()).await;
     let mut db_conn = test_ctx.db_ctx.db.get_conn().await.unwrap();
 
     // Start legal vote as user 1
@@ -1388,11 +1438,15 @@ async fn auto_stop_vote(storage: TestContextVolatileStorage) {
             USER_2.participant_id,
         ])
         .unwrap(),
-        enable_abstain: false,
+        enable_abstain: Some(false),
         auto_close: true,
         duration: None,
         create_pdf: false,
         timezone: None,
+        sandbox: false,
+        start_at: None,
+        require_confirmation: false,
+        allow_guests: false,
     };
 
     module_tester
@@ -1627,7 +1681,8 @@ async fn start_with_one_participant_memory() {
 
 async fn start_with_one_participant(storage: TestContextVolatileStorage) {
     let test_ctx = TestContext::new(storage).await;
-    let (module_tester, _user1, _user2) = common::setup_users::<LegalVote>(&test_ctx, ()).await;
+    let (module_tester, _user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, Arc::default()).await;
 
     // Start legal vote as user 1
     let start_parameters = UserParameters {
@@ -1636,11 +1691,15 @@ async fn start_with_one_participant(storage: TestContextVolatileStorage) {
         subtitle: Some(Subtitle::try_from("A subtitle").unwrap()),
         topic: Some(Topic::try_from("Does the test work?").unwrap()),
         allowed_participants: AllowedParticipants::try_from(vec![USER_1.participant_id]).unwrap(),
-        enable_abstain: false,
+        enable_abstain: Some(false),
         auto_close: false,
         duration: None,
         create_pdf: false,
         timezone: None,
+        sandbox: false,
+        start_at: None,
+        require_confirmation: false,
+        allow_guests: false,
     };
 
     module_tester
@@ -1667,7 +1726,8 @@ async fn initiator_left_memory() {
 
 async fn initiator_left(storage: TestContextVolatileStorage) {
     let test_ctx = TestContext::new(storage).await;
-    let (mut module_tester, _user1, _user2) = common::setup_users::<LegalVote>(&test_ctx, ()).await;
+    let (mut module_tester, _user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, Arc::default()).await;
 
     default_start_setup(&mut module_tester).await;
 
@@ -1694,6 +1754,125 @@ async fn initiator_left(storage: TestContextVolatileStorage) {
     module_tester.shutdown().await.unwrap()
 }
 
+#[actix_rt::test]
+#[serial]
+async fn initiator_grace_expired_redis() {
+    initiator_grace_expired(TestContextVolatileStorage::Redis).await
+}
+
+#[actix_rt::test]
+#[serial]
+async fn initiator_grace_expired_memory() {
+    initiator_grace_expired(TestContextVolatileStorage::Memory).await
+}
+
+async fn initiator_grace_expired(storage: TestContextVolatileStorage) {
+    let test_ctx = TestContext::new(storage).await;
+
+    let settings = opentalk_controller_settings::LegalVote {
+        initiator_grace_period: Duration::from_secs(1),
+        ..Default::default()
+    };
+    let (mut module_tester, _user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, Arc::new(LegalVoteParams::from(settings)))
+            .await;
+
+    let (legal_vote_id, _) = default_start_setup(&mut module_tester).await;
+
+    module_tester.leave(&USER_1.participant_id).await.unwrap();
+
+    // The vote gets canceled once the grace period expired
+    loop {
+        let message = module_tester
+            .receive_ws_message_override_timeout(&USER_2.participant_id, Duration::from_secs(3))
+            .await
+            .expect("Expected cancel after the grace period expired");
+
+        if let WsMessageOutgoing::Module(LegalVoteEvent::Canceled(canceled)) = message {
+            assert_eq!(canceled.legal_vote_id, legal_vote_id);
+            assert_eq!(canceled.reason, CancelReason::InitiatorLeft);
+            break;
+        }
+    }
+
+    module_tester.shutdown().await.unwrap()
+}
+
+#[actix_rt::test]
+#[serial]
+async fn initiator_rejoined_during_grace_redis() {
+    initiator_rejoined_during_grace(TestContextVolatileStorage::Redis).await
+}
+
+#[actix_rt::test]
+#[serial]
+async fn initiator_rejoined_during_grace_memory() {
+    initiator_rejoined_during_grace(TestContextVolatileStorage::Memory).await
+}
+
+async fn initiator_rejoined_during_grace(storage: TestContextVolatileStorage) {
+    let test_ctx = TestContext::new(storage).await;
+
+    let settings = opentalk_controller_settings::LegalVote {
+        initiator_grace_period: Duration::from_secs(1),
+        ..Default::default()
+    };
+    let params = Arc::new(LegalVoteParams::from(settings));
+    let (mut module_tester, user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, params.clone()).await;
+
+    let (legal_vote_id, _) = default_start_setup(&mut module_tester).await;
+
+    module_tester.leave(&USER_1.participant_id).await.unwrap();
+
+    module_tester
+        .join_user(
+            USER_1.participant_id,
+            user1,
+            Role::Moderator,
+            &USER_1.display_name(),
+            params,
+        )
+        .await
+        .unwrap();
+
+    // The vote keeps running after the grace period
+    while let Ok(message) = module_tester
+        .receive_ws_message_override_timeout(&USER_2.participant_id, Duration::from_secs(2))
+        .await
+    {
+        assert!(
+            !matches!(
+                message,
+                WsMessageOutgoing::Module(LegalVoteEvent::Canceled(_))
+            ),
+            "Expected the vote to keep running"
+        );
+    }
+
+    // The rejoined initiator is still able to stop the vote
+    module_tester
+        .send_ws_message(
+            &USER_1.participant_id,
+            LegalVoteCommand::Stop(Stop { legal_vote_id }),
+        )
+        .unwrap();
+
+    loop {
+        let message = module_tester
+            .receive_ws_message(&USER_2.participant_id)
+            .await
+            .expect("Expected stop message");
+
+        if let WsMessageOutgoing::Module(LegalVoteEvent::Stopped(stopped)) = message {
+            assert_eq!(stopped.legal_vote_id, legal_vote_id);
+            break;
+        }
+    }
+
+    module_tester.shutdown().await.unwrap()
+}
+
 #[actix_rt::test]
 #[serial]
 async fn ineligible_voter_redis() {
@@ -1708,7 +1887,8 @@ async fn ineligible_voter_memory() {
 
 async fn ineligible_voter(storage: TestContextVolatileStorage) {
     let test_ctx = TestContext::new(storage).await;
-    let (mut module_tester, _user1, _user2) = common::setup_users::<LegalVote>(&test_ctx, ()).await;
+    let (mut module_tester, _user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, Arc::default()).await;
 
     let start_parameters = UserParameters {
         kind: VoteKind::RollCall,
@@ -1716,11 +1896,15 @@ async fn ineligible_voter(storage: TestContextVolatileStorage) {
         subtitle: Some(Subtitle::try_from("TestVote").unwrap()),
         topic: Some(Topic::try_from("Does the test work?").unwrap()),
         allowed_participants: AllowedParticipants::try_from(vec![USER_1.participant_id]).unwrap(),
-        enable_abstain: false,
+        enable_abstain: Some(false),
         auto_close: false,
         duration: None,
         create_pdf: false,
         timezone: None,
+        sandbox: false,
+        start_at: None,
+        require_confirmation: false,
+        allow_guests: false,
     };
 
     module_tester
@@ -1775,7 +1959,8 @@ async fn start_with_allowed_guest_memory() {
 
 async fn start_with_allowed_guest(storage: TestContextVolatileStorage) {
     let test_ctx = TestContext::new(storage).await;
-    let (mut module_tester, _user1, _user2) = common::setup_users::<LegalVote>(&test_ctx, ()).await;
+    let (mut module_tester, _user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, Arc::default()).await;
 
     // start the vote with a guest as an allowed participant
     let guest = ParticipantId::from_u128(11311);
@@ -1791,11 +1976,15 @@ async fn start_with_allowed_guest(storage: TestContextVolatileStorage) {
             USER_2.participant_id,
         ])
         .unwrap(),
-        enable_abstain: false,
+        enable_abstain: Some(false),
         auto_close: false,
         duration: None,
         create_pdf: false,
         timezone: None,
+        sandbox: false,
+        start_at: None,
+        require_confirmation: false,
+        allow_guests: false,
     };
 
     // start vote with user 1
@@ -1824,146 +2013,181 @@ async fn start_with_allowed_guest(storage: TestContextVolatileStorage) {
 
 #[actix_rt::test]
 #[serial]
-async fn vote_on_nonexistent_vote_redis() {
-    vote_on_nonexistent_vote(TestContextVolatileStorage::Redis).await
+async fn start_with_allowed_guest_pseudonymous_redis() {
+    start_with_allowed_guest_pseudonymous(TestContextVolatileStorage::Redis).await
 }
 
 #[actix_rt::test]
 #[serial]
-async fn vote_on_nonexistent_vote_memory() {
-    vote_on_nonexistent_vote(TestContextVolatileStorage::Memory).await
+async fn start_with_allowed_guest_pseudonymous_memory() {
+    start_with_allowed_guest_pseudonymous(TestContextVolatileStorage::Memory).await
 }
 
-async fn vote_on_nonexistent_vote(storage: TestContextVolatileStorage) {
+async fn start_with_allowed_guest_pseudonymous(storage: TestContextVolatileStorage) {
     let test_ctx = TestContext::new(storage).await;
-    let (mut module_tester, _user1, _user2) = common::setup_users::<LegalVote>(&test_ctx, ()).await;
+    let (mut module_tester, _user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, Arc::default()).await;
 
-    let legal_vote_id = LegalVoteId::from_u128(11311);
+    // start the vote with a guest as an allowed participant
+    let guest = ParticipantId::from_u128(11311);
+
+    let start_parameters = UserParameters {
+        kind: VoteKind::Pseudonymous,
+        name: Name::try_from("TestVote").unwrap(),
+        subtitle: None,
+        topic: None,
+        allowed_participants: AllowedParticipants::try_from(vec![
+            USER_1.participant_id,
+            guest,
+            USER_2.participant_id,
+        ])
+        .unwrap(),
+        enable_abstain: Some(false),
+        auto_close: false,
+        duration: None,
+        create_pdf: false,
+        timezone: None,
+        sandbox: false,
+        start_at: None,
+        require_confirmation: false,
+        allow_guests: true,
+    };
 
     module_tester
         .send_ws_message(
             &USER_1.participant_id,
-            LegalVoteCommand::Vote(Vote {
-                legal_vote_id,
-                option: VoteOption::Yes,
-                token: Token::new(0),
-            }),
+            LegalVoteCommand::Start(start_parameters.clone()),
         )
         .unwrap();
 
-    let expected_vote_response = WsMessageOutgoing::Module(LegalVoteEvent::Voted(VoteResponse {
-        legal_vote_id,
-        response: Response::Failed(VoteFailed::InvalidVoteId),
-    }));
-
     let message = module_tester
         .receive_ws_message(&USER_1.participant_id)
         .await
         .unwrap();
 
-    assert_eq!(expected_vote_response, message);
+    let WsMessageOutgoing::Module(LegalVoteEvent::Started(parameters)) = message else {
+        panic!("Expected Start message, got {message:?}")
+    };
+
+    // The guest token counts towards the maximum number of votes
+    assert_eq!(parameters.inner, start_parameters);
+    assert_eq!(parameters.max_votes, 3);
 
     module_tester.shutdown().await.unwrap()
 }
 
 #[actix_rt::test]
 #[serial]
-async fn vote_on_completed_vote_redis() {
-    vote_on_completed_vote(TestContextVolatileStorage::Redis).await
+async fn start_with_forbidden_vote_kind_redis() {
+    start_with_forbidden_vote_kind(TestContextVolatileStorage::Redis).await
 }
 
 #[actix_rt::test]
 #[serial]
-async fn vote_on_completed_vote_memory() {
-    vote_on_completed_vote(TestContextVolatileStorage::Memory).await
+async fn start_with_forbidden_vote_kind_memory() {
+    start_with_forbidden_vote_kind(TestContextVolatileStorage::Memory).await
 }
 
-async fn vote_on_completed_vote(storage: TestContextVolatileStorage) {
+async fn start_with_forbidden_vote_kind(storage: TestContextVolatileStorage) {
     let test_ctx = TestContext::new(storage).await;
-    let (mut module_tester, _user1, _user2) = common::setup_users::<LegalVote>(&test_ctx, ()).await;
-
-    let (legal_vote_id, tokens) = default_start_setup(&mut module_tester).await;
 
-    // stop vote with user 1
-    module_tester
-        .send_ws_message(
-            &USER_1.participant_id,
-            LegalVoteCommand::Stop(Stop { legal_vote_id }),
-        )
-        .unwrap();
+    let settings = opentalk_controller_settings::LegalVote {
+        allowed_vote_kinds: BTreeSet::from([LegalVoteKind::RollCall, LegalVoteKind::LiveRollCall]),
+        tenants: BTreeMap::new(),
+        max_allowed_participants: DEFAULT_LEGAL_VOTE_MAX_ALLOWED_PARTICIPANTS,
+        enable_abstain_by_default: false,
+        ..Default::default()
+    };
+    let (mut module_tester, _user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, Arc::new(LegalVoteParams::from(settings)))
+            .await;
 
-    // expect vote stop
-    if let WsMessageOutgoing::Module(LegalVoteEvent::Stopped(Stopped { .. })) = module_tester
-        .receive_ws_message(&USER_2.participant_id)
-        .await
-        .unwrap()
-    {
-        // seems good
-    } else {
-        panic!("Expected stop message")
+    let start_parameters = UserParameters {
+        kind: VoteKind::Pseudonymous,
+        name: Name::try_from("TestVote").unwrap(),
+        subtitle: None,
+        topic: None,
+        allowed_participants: AllowedParticipants::try_from(vec![
+            USER_1.participant_id,
+            USER_2.participant_id,
+        ])
+        .unwrap(),
+        enable_abstain: Some(false),
+        auto_close: false,
+        duration: None,
+        create_pdf: false,
+        timezone: None,
+        sandbox: false,
+        start_at: None,
+        require_confirmation: false,
+        allow_guests: false,
     };
 
-    // try to vote with user 2
     module_tester
         .send_ws_message(
-            &USER_2.participant_id,
-            LegalVoteCommand::Vote(Vote {
-                legal_vote_id,
-                option: VoteOption::Yes,
-                token: tokens[1].unwrap(),
-            }),
+            &USER_1.participant_id,
+            LegalVoteCommand::Start(start_parameters),
         )
         .unwrap();
 
-    let expected_vote_response = WsMessageOutgoing::Module(LegalVoteEvent::Voted(VoteResponse {
-        legal_vote_id,
-        response: Response::Failed(VoteFailed::InvalidVoteId),
-    }));
-
     let message = module_tester
-        .receive_ws_message(&USER_2.participant_id)
+        .receive_ws_message(&USER_1.participant_id)
         .await
         .unwrap();
 
-    assert_eq!(expected_vote_response, message);
+    assert_eq!(
+        WsMessageOutgoing::Module(LegalVoteEvent::Error(ErrorKind::VoteKindNotAllowed)),
+        message
+    );
 
     module_tester.shutdown().await.unwrap()
 }
 
 #[actix_rt::test]
 #[serial]
-async fn vote_twice_redis() {
-    vote_twice(TestContextVolatileStorage::Redis).await
+async fn start_with_default_abstain_redis() {
+    start_with_default_abstain(TestContextVolatileStorage::Redis).await
 }
 
 #[actix_rt::test]
 #[serial]
-async fn vote_twice_memory() {
-    vote_twice(TestContextVolatileStorage::Memory).await
+async fn start_with_default_abstain_memory() {
+    start_with_default_abstain(TestContextVolatileStorage::Memory).await
 }
 
-async fn vote_twice(storage: TestContextVolatileStorage) {
+async fn start_with_default_abstain(storage: TestContextVolatileStorage) {
     let test_ctx = TestContext::new(storage).await;
-    let (mut module_tester, _user1, _user2) = common::setup_users::<LegalVote>(&test_ctx, ()).await;
 
-    let start_parameters = UserParameters {
+    let settings = opentalk_controller_settings::LegalVote {
+        enable_abstain_by_default: true,
+        ..Default::default()
+    };
+    let (mut module_tester, _user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, Arc::new(LegalVoteParams::from(settings)))
+            .await;
+
+    let mut start_parameters = UserParameters {
         kind: VoteKind::RollCall,
         name: Name::try_from("TestVote").unwrap(),
-        subtitle: Some(Subtitle::try_from("TestVote").unwrap()),
-        topic: Some(Topic::try_from("Does the test work?").unwrap()),
+        subtitle: None,
+        topic: None,
         allowed_participants: AllowedParticipants::try_from(vec![
             USER_1.participant_id,
             USER_2.participant_id,
         ])
         .unwrap(),
-        enable_abstain: false,
+        enable_abstain: None,
         auto_close: false,
         duration: None,
         create_pdf: false,
         timezone: None,
+        sandbox: false,
+        start_at: None,
+        require_confirmation: false,
+        allow_guests: false,
     };
 
-    // start vote with user 1
+    // Without an explicit value the default from the settings applies
     module_tester
         .send_ws_message(
             &USER_1.participant_id,
@@ -1971,105 +2195,1052 @@ async fn vote_twice(storage: TestContextVolatileStorage) {
         )
         .unwrap();
 
-    // receive start vote on user 1
-    let (legal_vote_id, token) =
-        if let WsMessageOutgoing::Module(LegalVoteEvent::Started(Parameters {
-            token,
-            legal_vote_id,
-            ..
-        })) = module_tester
-            .receive_ws_message(&USER_1.participant_id)
-            .await
-            .unwrap()
-        {
-            (legal_vote_id, token.unwrap())
-        } else {
-            panic!("Expected started message")
-        };
+    let message = module_tester
+        .receive_ws_message(&USER_1.participant_id)
+        .await
+        .unwrap();
+
+    let WsMessageOutgoing::Module(LegalVoteEvent::Started(parameters)) = message else {
+        panic!("Expected Start message, got {message:?}")
+    };
+    assert_eq!(parameters.inner.enable_abstain, Some(true));
 
-    // vote with user 1
     module_tester
         .send_ws_message(
             &USER_1.participant_id,
-            LegalVoteCommand::Vote(Vote {
-                legal_vote_id,
-                option: VoteOption::Yes,
-                token,
+            LegalVoteCommand::Cancel(Cancel {
+                legal_vote_id: parameters.legal_vote_id,
+                reason: CustomCancelReason::try_from("Restart").unwrap(),
             }),
         )
         .unwrap();
 
-    let expected_success_vote_response =
-        WsMessageOutgoing::Module(LegalVoteEvent::Voted(VoteResponse {
-            legal_vote_id,
-            response: Response::Success(VoteSuccess {
-                vote_option: VoteOption::Yes,
-                issuer: USER_1.participant_id,
-                consumed_token: token,
-            }),
-        }));
-
     let message = module_tester
         .receive_ws_message(&USER_1.participant_id)
         .await
         .unwrap();
+    assert!(matches!(
+        message,
+        WsMessageOutgoing::Module(LegalVoteEvent::Canceled(_))
+    ));
 
-    assert_eq!(expected_success_vote_response, message);
+    // An explicit value overrides the default
+    start_parameters.enable_abstain = Some(false);
 
-    // vote again with user 1
     module_tester
         .send_ws_message(
             &USER_1.participant_id,
-            LegalVoteCommand::Vote(Vote {
-                legal_vote_id,
-                option: VoteOption::No,
-                token,
-            }),
+            LegalVoteCommand::Start(start_parameters.clone()),
         )
         .unwrap();
 
-    let expected_failed_vote_response =
-        WsMessageOutgoing::Module(LegalVoteEvent::Voted(VoteResponse {
-            legal_vote_id,
-            response: Response::Failed(VoteFailed::Ineligible),
-        }));
-
-    // Ignore the vote Update message for the first vote above
-    module_tester
-        .receive_ws_message(&USER_1.participant_id)
-        .await
-        .unwrap();
-
     let message = module_tester
         .receive_ws_message(&USER_1.participant_id)
         .await
         .unwrap();
 
-    assert_eq!(expected_failed_vote_response, message);
+    let WsMessageOutgoing::Module(LegalVoteEvent::Started(parameters)) = message else {
+        panic!("Expected Start message, got {message:?}")
+    };
+    assert_eq!(parameters.inner.enable_abstain, Some(false));
 
     module_tester.shutdown().await.unwrap()
 }
 
 #[actix_rt::test]
 #[serial]
-async fn non_moderator_stop_redis() {
-    non_moderator_stop(TestContextVolatileStorage::Redis).await
+async fn pdf_policy_always_redis() {
+    start_with_pdf_policy(
+        TestContextVolatileStorage::Redis,
+        LegalVotePdfPolicy::Always,
+    )
+    .await
 }
 
 #[actix_rt::test]
 #[serial]
-async fn non_moderator_stop_memory() {
-    non_moderator_stop(TestContextVolatileStorage::Memory).await
+async fn pdf_policy_always_memory() {
+    start_with_pdf_policy(
+        TestContextVolatileStorage::Memory,
+        LegalVotePdfPolicy::Always,
+    )
+    .await
 }
 
-async fn non_moderator_stop(storage: TestContextVolatileStorage) {
-    let test_ctx = TestContext::new(storage).await;
-    let (mut module_tester, _user1, _user2) = common::setup_users::<LegalVote>(&test_ctx, ()).await;
+#[actix_rt::test]
+#[serial]
+async fn pdf_policy_never_redis() {
+    start_with_pdf_policy(TestContextVolatileStorage::Redis, LegalVotePdfPolicy::Never).await
+}
 
-    let (legal_vote_id, _) = default_start_setup(&mut module_tester).await;
+#[actix_rt::test]
+#[serial]
+async fn pdf_policy_never_memory() {
+    start_with_pdf_policy(
+        TestContextVolatileStorage::Memory,
+        LegalVotePdfPolicy::Never,
+    )
+    .await
+}
 
-    // stop vote with user 2
-    let stop_vote = LegalVoteCommand::Stop(Stop { legal_vote_id });
+#[actix_rt::test]
+#[serial]
+async fn pdf_policy_per_vote_redis() {
+    start_with_pdf_policy(
+        TestContextVolatileStorage::Redis,
+        LegalVotePdfPolicy::PerVote,
+    )
+    .await
+}
+
+#[actix_rt::test]
+#[serial]
+async fn pdf_policy_per_vote_memory() {
+    start_with_pdf_policy(
+        TestContextVolatileStorage::Memory,
+        LegalVotePdfPolicy::PerVote,
+    )
+    .await
+}
+
+/// Start a vote which does not request a PDF report under the given tenant PDF policy
+async fn start_with_pdf_policy(storage: TestContextVolatileStorage, policy: LegalVotePdfPolicy) {
+    let test_ctx = TestContext::new(storage).await;
+
+    // The global policy is the opposite of the tenant policy to ensure the override applies
+    let global_policy = match policy {
+        LegalVotePdfPolicy::Always => LegalVotePdfPolicy::Never,
+        LegalVotePdfPolicy::Never | LegalVotePdfPolicy::PerVote => LegalVotePdfPolicy::Always,
+    };
+    let settings = opentalk_controller_settings::LegalVote {
+        pdf_policy: global_policy,
+        tenants: BTreeMap::from([(
+            "default".to_string(),
+            LegalVoteTenant {
+                pdf_policy: Some(policy),
+                ..Default::default()
+            },
+        )]),
+        ..Default::default()
+    };
+    let (mut module_tester, _user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, Arc::new(LegalVoteParams::from(settings)))
+            .await;
+
+    let expected_enforced_create_pdf = match policy {
+        LegalVotePdfPolicy::Always => Some(true),
+        LegalVotePdfPolicy::Never => Some(false),
+        LegalVotePdfPolicy::PerVote => None,
+    };
+
+    // The effective policy is reflected in the state
+    module_tester
+        .send_ws_message(&USER_1.participant_id, LegalVoteCommand::GetState)
+        .unwrap();
+
+    let message = module_tester
+        .receive_ws_message(&USER_1.participant_id)
+        .await
+        .unwrap();
+
+    let WsMessageOutgoing::Module(LegalVoteEvent::State(state)) = message else {
+        panic!("Expected State message, got {message:?}")
+    };
+    assert_eq!(state.enforced_create_pdf, expected_enforced_create_pdf);
+
+    let requested_create_pdf = policy == LegalVotePdfPolicy::Never;
+
+    let start_parameters = UserParameters {
+        kind: VoteKind::RollCall,
+        name: Name::try_from("TestVote").unwrap(),
+        subtitle: None,
+        topic: None,
+        allowed_participants: AllowedParticipants::try_from(vec![
+            USER_1.participant_id,
+            USER_2.participant_id,
+        ])
+        .unwrap(),
+        enable_abstain: Some(false),
+        auto_close: false,
+        duration: None,
+        create_pdf: requested_create_pdf,
+        timezone: None,
+        sandbox: false,
+        start_at: None,
+        require_confirmation: false,
+        allow_guests: false,
+    };
+
+    module_tester
+        .send_ws_message(
+            &USER_1.participant_id,
+            LegalVoteCommand::Start(start_parameters),
+        )
+        .unwrap();
+
+    let message = module_tester
+        .receive_ws_message(&USER_1.participant_id)
+        .await
+        .unwrap();
+
+    let WsMessageOutgoing::Module(LegalVoteEvent::Started(parameters)) = message else {
+        panic!("Expected Start message, got {message:?}")
+    };
+    assert_eq!(
+        parameters.inner.create_pdf,
+        expected_enforced_create_pdf.unwrap_or(requested_create_pdf)
+    );
+
+    module_tester.shutdown().await.unwrap()
+}
+
+#[actix_rt::test]
+#[serial]
+async fn start_with_too_many_allowed_participants_redis() {
+    start_with_too_many_allowed_participants(TestContextVolatileStorage::Redis).await
+}
+
+#[actix_rt::test]
+#[serial]
+async fn start_with_too_many_allowed_participants_memory() {
+    start_with_too_many_allowed_participants(TestContextVolatileStorage::Memory).await
+}
+
+async fn start_with_too_many_allowed_participants(storage: TestContextVolatileStorage) {
+    let test_ctx = TestContext::new(storage).await;
+
+    let settings = opentalk_controller_settings::LegalVote {
+        max_allowed_participants: 1,
+        ..Default::default()
+    };
+    let (mut module_tester, _user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, Arc::new(LegalVoteParams::from(settings)))
+            .await;
+
+    let start_parameters = UserParameters {
+        kind: VoteKind::RollCall,
+        name: Name::try_from("TestVote").unwrap(),
+        subtitle: None,
+        topic: None,
+        allowed_participants: AllowedParticipants::try_from(vec![
+            USER_1.participant_id,
+            USER_2.participant_id,
+        ])
+        .unwrap(),
+        enable_abstain: Some(false),
+        auto_close: false,
+        duration: None,
+        create_pdf: false,
+        timezone: None,
+        sandbox: false,
+        start_at: None,
+        require_confirmation: false,
+        allow_guests: false,
+    };
+
+    module_tester
+        .send_ws_message(
+            &USER_1.participant_id,
+            LegalVoteCommand::Start(start_parameters),
+        )
+        .unwrap();
+
+    let message = module_tester
+        .receive_ws_message(&USER_1.participant_id)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        WsMessageOutgoing::Module(LegalVoteEvent::Error(
+            ErrorKind::TooManyAllowedParticipants(MaxAllowedParticipants { max: 1 })
+        )),
+        message
+    );
+
+    module_tester.shutdown().await.unwrap()
+}
+
+#[actix_rt::test]
+#[serial]
+async fn schedule_in_the_past_redis() {
+    schedule_in_the_past(TestContextVolatileStorage::Redis).await
+}
+
+#[actix_rt::test]
+#[serial]
+async fn schedule_in_the_past_memory() {
+    schedule_in_the_past(TestContextVolatileStorage::Memory).await
+}
+
+async fn schedule_in_the_past(storage: TestContextVolatileStorage) {
+    let test_ctx = TestContext::new(storage).await;
+    let (mut module_tester, _user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, Arc::default()).await;
+
+    let start_parameters = UserParameters {
+        kind: VoteKind::RollCall,
+        name: Name::try_from("TestVote").unwrap(),
+        subtitle: None,
+        topic: None,
+        allowed_participants: AllowedParticipants::try_from(vec![
+            USER_1.participant_id,
+            USER_2.participant_id,
+        ])
+        .unwrap(),
+        enable_abstain: Some(false),
+        auto_close: false,
+        duration: None,
+        create_pdf: false,
+        timezone: None,
+        sandbox: false,
+        start_at: Some(Timestamp::from(Utc.timestamp_opt(0, 0).unwrap())),
+        require_confirmation: false,
+        allow_guests: false,
+    };
+
+    module_tester
+        .send_ws_message(
+            &USER_1.participant_id,
+            LegalVoteCommand::Start(start_parameters),
+        )
+        .unwrap();
+
+    let message = module_tester
+        .receive_ws_message(&USER_1.participant_id)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        WsMessageOutgoing::Module(LegalVoteEvent::Error(ErrorKind::StartTimeInPast)),
+        message
+    );
+
+    module_tester.shutdown().await.unwrap()
+}
+
+#[actix_rt::test]
+#[serial]
+async fn confirm_results_redis() {
+    confirm_results(TestContextVolatileStorage::Redis).await
+}
+
+#[actix_rt::test]
+#[serial]
+async fn confirm_results_memory() {
+    confirm_results(TestContextVolatileStorage::Memory).await
+}
+
+async fn confirm_results(storage: TestContextVolatileStorage) {
+    let test_ctx = TestContext::new(storage).await;
+    let (mut module_tester, _user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, Arc::default()).await;
+
+    let start_parameters = UserParameters {
+        kind: VoteKind::RollCall,
+        name: Name::try_from("TestVote").unwrap(),
+        subtitle: None,
+        topic: None,
+        allowed_participants: AllowedParticipants::try_from(vec![
+            USER_1.participant_id,
+            USER_2.participant_id,
+        ])
+        .unwrap(),
+        enable_abstain: Some(false),
+        auto_close: false,
+        duration: None,
+        create_pdf: false,
+        timezone: None,
+        sandbox: false,
+        start_at: None,
+        require_confirmation: true,
+        allow_guests: false,
+    };
+
+    module_tester
+        .send_ws_message(
+            &USER_1.participant_id,
+            LegalVoteCommand::Start(start_parameters.clone()),
+        )
+        .unwrap();
+
+    let legal_vote_id = receive_start_on_user2(&mut module_tester).await.0;
+    module_tester
+        .receive_ws_message(&USER_1.participant_id)
+        .await
+        .unwrap();
+
+    module_tester
+        .send_ws_message(
+            &USER_1.participant_id,
+            LegalVoteCommand::Stop(Stop { legal_vote_id }),
+        )
+        .unwrap();
+
+    // All participants are informed that the vote awaits confirmation
+    let mut end_time = None;
+    for user in USERS {
+        let message = module_tester
+            .receive_ws_message(&user.participant_id)
+            .await
+            .unwrap();
+
+        let WsMessageOutgoing::Module(LegalVoteEvent::AwaitingConfirmation(AwaitingConfirmation {
+            legal_vote_id: awaiting_vote_id,
+            kind,
+            end_time: awaiting_end_time,
+        })) = message
+        else {
+            panic!("Expected AwaitingConfirmation message, got {message:?}")
+        };
+
+        assert_eq!(awaiting_vote_id, legal_vote_id);
+        assert_eq!(kind, StopKind::ByParticipant(USER_1.participant_id));
+        end_time = Some(awaiting_end_time);
+    }
+
+    // Only the moderator receives the provisional results
+    let message = module_tester
+        .receive_ws_message(&USER_1.participant_id)
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        message,
+        WsMessageOutgoing::Module(LegalVoteEvent::ProvisionalResults(ProvisionalResults {
+            legal_vote_id: provisional_vote_id,
+            results: FinalResults::Valid(_),
+        })) if provisional_vote_id == legal_vote_id
+    ));
+
+    // No new vote can be started before the results are confirmed
+    module_tester
+        .send_ws_message(
+            &USER_1.participant_id,
+            LegalVoteCommand::Start(start_parameters),
+        )
+        .unwrap();
+
+    let message = module_tester
+        .receive_ws_message(&USER_1.participant_id)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        WsMessageOutgoing::Module(LegalVoteEvent::Error(ErrorKind::VoteAlreadyActive)),
+        message
+    );
+
+    module_tester
+        .send_ws_message(
+            &USER_1.participant_id,
+            LegalVoteCommand::ConfirmResults(ConfirmResults { legal_vote_id }),
+        )
+        .unwrap();
+
+    for user in USERS {
+        let message = module_tester
+            .receive_ws_message(&user.participant_id)
+            .await
+            .unwrap();
+
+        let WsMessageOutgoing::Module(LegalVoteEvent::Stopped(stopped)) = message else {
+            panic!("Expected Stopped message, got {message:?}")
+        };
+
+        assert_eq!(stopped.legal_vote_id, legal_vote_id);
+        assert_eq!(stopped.kind, StopKind::ByParticipant(USER_1.participant_id));
+        assert_eq!(Some(stopped.end_time), end_time);
+    }
+
+    module_tester.shutdown().await.unwrap()
+}
+
+#[actix_rt::test]
+#[serial]
+async fn sandbox_vote_redis() {
+    sandbox_vote(TestContextVolatileStorage::Redis).await
+}
+
+#[actix_rt::test]
+#[serial]
+async fn sandbox_vote_memory() {
+    sandbox_vote(TestContextVolatileStorage::Memory).await
+}
+
+async fn sandbox_vote(storage: TestContextVolatileStorage) {
+    let test_ctx = TestContext::new(storage).await;
+    let (mut module_tester, _user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, Arc::default()).await;
+    let mut db_conn = test_ctx.db_ctx.db.get_conn().await.unwrap();
+
+    let start_parameters = UserParameters {
+        kind: VoteKind::RollCall,
+        name: Name::try_from("TestVote").unwrap(),
+        subtitle: None,
+        topic: None,
+        allowed_participants: AllowedParticipants::try_from(vec![
+            USER_1.participant_id,
+            USER_2.participant_id,
+        ])
+        .unwrap(),
+        enable_abstain: Some(false),
+        auto_close: false,
+        duration: None,
+        create_pdf: false,
+        timezone: None,
+        sandbox: true,
+        start_at: None,
+        require_confirmation: false,
+        allow_guests: false,
+    };
+
+    module_tester
+        .send_ws_message(
+            &USER_1.participant_id,
+            LegalVoteCommand::Start(start_parameters),
+        )
+        .unwrap();
+
+    let (legal_vote_id, user_1_token) =
+        if let WsMessageOutgoing::Module(LegalVoteEvent::Started(parameters)) = module_tester
+            .receive_ws_message(&USER_1.participant_id)
+            .await
+            .unwrap()
+        {
+            (parameters.legal_vote_id, parameters.token.unwrap())
+        } else {
+            panic!("Expected Start message")
+        };
+
+    module_tester
+        .receive_ws_message(&USER_2.participant_id)
+        .await
+        .unwrap();
+
+    // A sandbox vote must not be stored in the database
+    let module_resources =
+        ModuleResource::get(&mut db_conn, Filter::new().with_id(*legal_vote_id.inner()))
+            .await
+            .unwrap();
+    assert!(module_resources.is_empty());
+
+    module_tester
+        .send_ws_message(
+            &USER_1.participant_id,
+            LegalVoteCommand::Vote(Vote {
+                legal_vote_id,
+                option: VoteOption::Yes,
+                token: user_1_token,
+            }),
+        )
+        .unwrap();
+
+    // Consume the vote response and the update messages
+    module_tester
+        .receive_ws_message(&USER_1.participant_id)
+        .await
+        .unwrap();
+
+    for user in USERS {
+        module_tester
+            .receive_ws_message(&user.participant_id)
+            .await
+            .unwrap();
+    }
+
+    module_tester
+        .send_ws_message(
+            &USER_1.participant_id,
+            LegalVoteCommand::Stop(Stop { legal_vote_id }),
+        )
+        .unwrap();
+
+    for user in USERS {
+        let message = module_tester
+            .receive_ws_message(&user.participant_id)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            message,
+            WsMessageOutgoing::Module(LegalVoteEvent::Stopped(Stopped { .. }))
+        ));
+    }
+
+    // The protocol is not saved either
+    let module_resources =
+        ModuleResource::get(&mut db_conn, Filter::new().with_id(*legal_vote_id.inner()))
+            .await
+            .unwrap();
+    assert!(module_resources.is_empty());
+
+    module_tester.shutdown().await.unwrap()
+}
+
+#[actix_rt::test]
+#[serial]
+async fn get_non_voters_redis() {
+    get_non_voters(TestContextVolatileStorage::Redis).await
+}
+
+#[actix_rt::test]
+#[serial]
+async fn get_non_voters_memory() {
+    get_non_voters(TestContextVolatileStorage::Memory).await
+}
+
+async fn get_non_voters(storage: TestContextVolatileStorage) {
+    let test_ctx = TestContext::new(storage).await;
+    let (mut module_tester, _user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, Arc::default()).await;
+
+    let start_parameters = UserParameters {
+        kind: VoteKind::RollCall,
+        name: Name::try_from("TestVote").unwrap(),
+        subtitle: None,
+        topic: None,
+        allowed_participants: AllowedParticipants::try_from(vec![
+            USER_1.participant_id,
+            USER_2.participant_id,
+        ])
+        .unwrap(),
+        enable_abstain: Some(false),
+        auto_close: false,
+        duration: None,
+        create_pdf: false,
+        timezone: None,
+        sandbox: false,
+        start_at: None,
+        require_confirmation: false,
+        allow_guests: false,
+    };
+
+    module_tester
+        .send_ws_message(
+            &USER_1.participant_id,
+            LegalVoteCommand::Start(start_parameters),
+        )
+        .unwrap();
+
+    let (legal_vote_id, user_1_token) =
+        if let WsMessageOutgoing::Module(LegalVoteEvent::Started(parameters)) = module_tester
+            .receive_ws_message(&USER_1.participant_id)
+            .await
+            .unwrap()
+        {
+            (parameters.legal_vote_id, parameters.token.unwrap())
+        } else {
+            panic!("Expected Start message")
+        };
+
+    module_tester
+        .receive_ws_message(&USER_2.participant_id)
+        .await
+        .unwrap();
+
+    module_tester
+        .send_ws_message(
+            &USER_1.participant_id,
+            LegalVoteCommand::Vote(Vote {
+                legal_vote_id,
+                option: VoteOption::Yes,
+                token: user_1_token,
+            }),
+        )
+        .unwrap();
+
+    // Consume the vote response and the update messages
+    module_tester
+        .receive_ws_message(&USER_1.participant_id)
+        .await
+        .unwrap();
+
+    for user in USERS {
+        module_tester
+            .receive_ws_message(&user.participant_id)
+            .await
+            .unwrap();
+    }
+
+    // Only the moderator may ask for the non-voters
+    module_tester
+        .send_ws_message(
+            &USER_2.participant_id,
+            LegalVoteCommand::GetNonVoters(GetNonVoters { legal_vote_id }),
+        )
+        .unwrap();
+
+    let message = module_tester
+        .receive_ws_message(&USER_2.participant_id)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        WsMessageOutgoing::Module(LegalVoteEvent::Error(ErrorKind::InsufficientPermissions)),
+        message
+    );
+
+    module_tester
+        .send_ws_message(
+            &USER_1.participant_id,
+            LegalVoteCommand::GetNonVoters(GetNonVoters { legal_vote_id }),
+        )
+        .unwrap();
+
+    let message = module_tester
+        .receive_ws_message(&USER_1.participant_id)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        WsMessageOutgoing::Module(LegalVoteEvent::NonVoters(NonVoters {
+            legal_vote_id,
+            participants: vec![USER_2.participant_id],
+        })),
+        message
+    );
+
+    module_tester.shutdown().await.unwrap()
+}
+
+#[actix_rt::test]
+#[serial]
+async fn get_state_redis() {
+    get_state(TestContextVolatileStorage::Redis).await
+}
+
+#[actix_rt::test]
+#[serial]
+async fn get_state_memory() {
+    get_state(TestContextVolatileStorage::Memory).await
+}
+
+async fn get_state(storage: TestContextVolatileStorage) {
+    let test_ctx = TestContext::new(storage).await;
+    let (mut module_tester, _user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, Arc::default()).await;
+
+    let start_parameters = UserParameters {
+        kind: VoteKind::RollCall,
+        name: Name::try_from("TestVote").unwrap(),
+        subtitle: None,
+        topic: None,
+        allowed_participants: AllowedParticipants::try_from(vec![
+            USER_1.participant_id,
+            USER_2.participant_id,
+        ])
+        .unwrap(),
+        enable_abstain: Some(false),
+        auto_close: false,
+        duration: None,
+        create_pdf: false,
+        timezone: None,
+        sandbox: false,
+        start_at: None,
+        require_confirmation: false,
+        allow_guests: false,
+    };
+
+    module_tester
+        .send_ws_message(
+            &USER_1.participant_id,
+            LegalVoteCommand::Start(start_parameters),
+        )
+        .unwrap();
+
+    module_tester
+        .receive_ws_message(&USER_1.participant_id)
+        .await
+        .unwrap();
+
+    let (legal_vote_id, user_2_token) =
+        if let WsMessageOutgoing::Module(LegalVoteEvent::Started(parameters)) = module_tester
+            .receive_ws_message(&USER_2.participant_id)
+            .await
+            .unwrap()
+        {
+            (parameters.legal_vote_id, parameters.token.unwrap())
+        } else {
+            panic!("Expected Start message")
+        };
+
+    // A participant that missed the start can request the state including its token
+    module_tester
+        .send_ws_message(&USER_2.participant_id, LegalVoteCommand::GetState)
+        .unwrap();
+
+    let WsMessageOutgoing::Module(LegalVoteEvent::State(state)) = module_tester
+        .receive_ws_message(&USER_2.participant_id)
+        .await
+        .unwrap()
+    else {
+        panic!("Expected State message")
+    };
+
+    assert_eq!(state.votes.len(), 1);
+    assert_eq!(state.votes[0].parameters.legal_vote_id, legal_vote_id);
+    assert_eq!(state.votes[0].state, VoteState::Started);
+    assert_eq!(state.votes[0].parameters.token, Some(user_2_token));
+
+    module_tester.shutdown().await.unwrap()
+}
+
+#[actix_rt::test]
+#[serial]
+async fn vote_on_nonexistent_vote_redis() {
+    vote_on_nonexistent_vote(TestContextVolatileStorage::Redis).await
+}
+
+#[actix_rt::test]
+#[serial]
+async fn vote_on_nonexistent_vote_memory() {
+    vote_on_nonexistent_vote(TestContextVolatileStorage::Memory).await
+}
+
+async fn vote_on_nonexistent_vote(storage: TestContextVolatileStorage) {
+    let test_ctx = TestContext::new(storage).await;
+    let (mut module_tester, _user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, Arc::default()).await;
+
+    let legal_vote_id = LegalVoteId::from_u128(11311);
+
+    module_tester
+        .send_ws_message(
+            &USER_1.participant_id,
+            LegalVoteCommand::Vote(Vote {
+                legal_vote_id,
+                option: VoteOption::Yes,
+                token: Token::new(0),
+            }),
+        )
+        .unwrap();
+
+    let expected_vote_response = WsMessageOutgoing::Module(LegalVoteEvent::Voted(VoteResponse {
+        legal_vote_id,
+        response: Response::Failed(VoteFailed::InvalidVoteId),
+    }));
+
+    let message = module_tester
+        .receive_ws_message(&USER_1.participant_id)
+        .await
+        .unwrap();
+
+    assert_eq!(expected_vote_response, message);
+
+    module_tester.shutdown().await.unwrap()
+}
+
+#[actix_rt::test]
+#[serial]
+async fn vote_on_completed_vote_redis() {
+    vote_on_completed_vote(TestContextVolatileStorage::Redis).await
+}
+
+#[actix_rt::test]
+#[serial]
+async fn vote_on_completed_vote_memory() {
+    vote_on_completed_vote(TestContextVolatileStorage::Memory).await
+}
+
+async fn vote_on_completed_vote(storage: TestContextVolatileStorage) {
+    let test_ctx = TestContext::new(storage).await;
+    let (mut module_tester, _user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, Arc::default()).await;
+
+    let (legal_vote_id, tokens) = default_start_setup(&mut module_tester).await;
+
+    // stop vote with user 1
+    module_tester
+        .send_ws_message(
+            &USER_1.participant_id,
+            LegalVoteCommand::Stop(Stop { legal_vote_id }),
+        )
+        .unwrap();
+
+    // expect vote stop
+    if let WsMessageOutgoing::Module(LegalVoteEvent::Stopped(Stopped { .. })) = module_tester
+        .receive_ws_message(&USER_2.participant_id)
+        .await
+        .unwrap()
+    {
+        // seems good
+    } else {
+        panic!("Expected stop message")
+    };
+
+    // try to vote with user 2
+    module_tester
+        .send_ws_message(
+            &USER_2.participant_id,
+            LegalVoteCommand::Vote(Vote {
+                legal_vote_id,
+                option: VoteOption::Yes,
+                token: tokens[1].unwrap(),
+            }),
+        )
+        .unwrap();
+
+    let expected_vote_response = WsMessageOutgoing::Module(LegalVoteEvent::Voted(VoteResponse {
+        legal_vote_id,
+        response: Response::Failed(VoteFailed::InvalidVoteId),
+    }));
+
+    let message = module_tester
+        .receive_ws_message(&USER_2.participant_id)
+        .await
+        .unwrap();
+
+    assert_eq!(expected_vote_response, message);
+
+    module_tester.shutdown().await.unwrap()
+}
+
+#[actix_rt::test]
+#[serial]
+async fn vote_twice_redis() {
+    vote_twice(TestContextVolatileStorage::Redis).await
+}
+
+#[actix_rt::test]
+#[serial]
+async fn vote_twice_memory() {
+    vote_twice(TestContextVolatileStorage::Memory).await
+}
+
+async fn vote_twice(storage: TestContextVolatileStorage) {
+    let test_ctx = TestContext::new(storage).await;
+    let (mut module_tester, _user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, Arc::default()).await;
+
+    let start_parameters = UserParameters {
+        kind: VoteKind::RollCall,
+        name: Name::try_from("TestVote").unwrap(),
+        subtitle: Some(Subtitle::try_from("TestVote").unwrap()),
+        topic: Some(Topic::try_from("Does the test work?").unwrap()),
+        allowed_participants: AllowedParticipants::try_from(vec![
+            USER_1.participant_id,
+            USER_2.participant_id,
+        ])
+        .unwrap(),
+        enable_abstain: Some(false),
+        auto_close: false,
+        duration: None,
+        create_pdf: false,
+        timezone: None,
+        sandbox: false,
+        start_at: None,
+        require_confirmation: false,
+        allow_guests: false,
+    };
+
+    // start vote with user 1
+    module_tester
+        .send_ws_message(
+            &USER_1.participant_id,
+            LegalVoteCommand::Start(start_parameters.clone()),
+        )
+        .unwrap();
+
+    // receive start vote on user 1
+    let (legal_vote_id, token) =
+        if let WsMessageOutgoing::Module(LegalVoteEvent::Started(Parameters {
+            token,
+            legal_vote_id,
+            ..
+        })) = module_tester
+            .receive_ws_message(&USER_1.participant_id)
+            .await
+            .unwrap()
+        {
+            (legal_vote_id, token.unwrap())
+        } else {
+            panic!("Expected started message")
+        };
+
+    // vote with user 1
+    module_tester
+        .send_ws_message(
+            &USER_1.participant_id,
+            LegalVoteCommand::Vote(Vote {
+                legal_vote_id,
+                option: VoteOption::Yes,
+                token,
+            }),
+        )
+        .unwrap();
+
+    let expected_success_vote_response =
+        WsMessageOutgoing::Module(LegalVoteEvent::Voted(VoteResponse {
+            legal_vote_id,
+            response: Response::Success(VoteSuccess {
+                vote_option: VoteOption::Yes,
+                issuer: USER_1.participant_id,
+                consumed_token: token,
+            }),
+        }));
+
+    let message = module_tester
+        .receive_ws_message(&USER_1.participant_id)
+        .await
+        .unwrap();
+
+    assert_eq!(expected_success_vote_response, message);
+
+    // vote again with user 1
+    module_tester
+        .send_ws_message(
+            &USER_1.participant_id,
+            LegalVoteCommand::Vote(Vote {
+                legal_vote_id,
+                option: VoteOption::No,
+                token,
+            }),
+        )
+        .unwrap();
+
+    let expected_failed_vote_response =
+        WsMessageOutgoing::Module(LegalVoteEvent::Voted(VoteResponse {
+            legal_vote_id,
+            response: Response::Failed(VoteFailed::Ineligible),
+        }));
+
+    // Ignore the vote Update message for the first vote above
+    module_tester
+        .receive_ws_message(&USER_1.participant_id)
+        .await
+        .unwrap();
+
+    let message = module_tester
+        .receive_ws_message(&USER_1.participant_id)
+        .await
+        .unwrap();
+
+    assert_eq!(expected_failed_vote_response, message);
+
+    module_tester.shutdown().await.unwrap()
+}
+
+#[actix_rt::test]
+#[serial]
+async fn non_moderator_stop_redis() {
+    non_moderator_stop(TestContextVolatileStorage::Redis).await
+}
+
+#[actix_rt::test]
+#[serial]
+async fn non_moderator_stop_memory() {
+    non_moderator_stop(TestContextVolatileStorage::Memory).await
+}
+
+async fn non_moderator_stop(storage: TestContextVolatileStorage) {
+    let test_ctx = TestContext::new(storage).await;
+    let (mut module_tester, _user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, Arc::default()).await;
+
+    let (legal_vote_id, _) = default_start_setup(&mut module_tester).await;
+
+    // stop vote with user 2
+    let stop_vote = LegalVoteCommand::Stop(Stop { legal_vote_id });
 
     module_tester
         .send_ws_message(&USER_2.participant_id, stop_vote)
@@ -2102,7 +3273,8 @@ async fn non_moderator_cancel_memory() {
 
 async fn non_moderator_cancel(storage: TestContextVolatileStorage) {
     let test_ctx = TestContext::new(storage).await;
-    let (mut module_tester, _user1, _user2) = common::setup_users::<LegalVote>(&test_ctx, ()).await;
+    let (mut module_tester, _user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, Arc::default()).await;
 
     let (legal_vote_id, _) = default_start_setup(&mut module_tester).await;
 
@@ -2187,6 +3359,14 @@ async fn frontend_data_memory() {
     frontend_data(TestContextVolatileStorage::Memory).await
 }
 
+fn all_vote_kinds() -> Vec<VoteKind> {
+    vec![
+        VoteKind::RollCall,
+        VoteKind::LiveRollCall,
+        VoteKind::Pseudonymous,
+    ]
+}
+
 async fn frontend_data(storage: TestContextVolatileStorage) {
     async fn check_user_join_module_data(
         module_tester: &mut ModuleTester<LegalVote>,
@@ -2242,7 +3422,8 @@ async fn frontend_data(storage: TestContextVolatileStorage) {
     }
 
     let test_ctx = TestContext::new(storage).await;
-    let (mut module_tester, _user1, _user2) = common::setup_users::<LegalVote>(&test_ctx, ()).await;
+    let (mut module_tester, _user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, Arc::default()).await;
 
     const USER_3: TestUser = TestUser {
         n: 3,
@@ -2259,7 +3440,12 @@ async fn frontend_data(storage: TestContextVolatileStorage) {
     check_user_join_module_data(
         &mut module_tester,
         user3.clone(),
-        LegalVoteState { votes: vec![] },
+        LegalVoteState {
+            votes: vec![],
+            allowed_vote_kinds: all_vote_kinds(),
+            max_allowed_participants: DEFAULT_LEGAL_VOTE_MAX_ALLOWED_PARTICIPANTS,
+            enforced_create_pdf: None,
+        },
     )
     .await;
 
@@ -2274,11 +3460,15 @@ async fn frontend_data(storage: TestContextVolatileStorage) {
             USER_2.participant_id,
         ])
         .unwrap(),
-        enable_abstain: false,
+        enable_abstain: Some(false),
         auto_close: true,
         duration: None,
         create_pdf: false,
         timezone: None,
+        sandbox: false,
+        start_at: None,
+        require_confirmation: false,
+        allow_guests: false,
     };
 
     module_tester
@@ -2326,6 +3516,9 @@ async fn frontend_data(storage: TestContextVolatileStorage) {
                     state: VoteState::Started,
                     end_time: None,
                 }],
+                allowed_vote_kinds: all_vote_kinds(),
+                max_allowed_participants: DEFAULT_LEGAL_VOTE_MAX_ALLOWED_PARTICIPANTS,
+                enforced_create_pdf: None,
             },
         )
         .await;
@@ -2427,6 +3620,9 @@ async fn frontend_data(storage: TestContextVolatileStorage) {
         user3.clone(),
         LegalVoteState {
             votes: vec![vote_1_summary.clone()],
+            allowed_vote_kinds: all_vote_kinds(),
+            max_allowed_participants: DEFAULT_LEGAL_VOTE_MAX_ALLOWED_PARTICIPANTS,
+            enforced_create_pdf: None,
         },
     )
     .await;
@@ -2442,11 +3638,15 @@ async fn frontend_data(storage: TestContextVolatileStorage) {
             USER_2.participant_id,
         ])
         .unwrap(),
-        enable_abstain: true,
+        enable_abstain: Some(true),
         auto_close: true,
         duration: None,
         create_pdf: false,
         timezone: None,
+        sandbox: false,
+        start_at: None,
+        require_confirmation: false,
+        allow_guests: false,
     };
 
     module_tester
@@ -2494,6 +3694,9 @@ async fn frontend_data(storage: TestContextVolatileStorage) {
                         end_time: None,
                     },
                 ],
+                allowed_vote_kinds: all_vote_kinds(),
+                max_allowed_participants: DEFAULT_LEGAL_VOTE_MAX_ALLOWED_PARTICIPANTS,
+                enforced_create_pdf: None,
             },
         )
         .await;
@@ -2502,6 +3705,199 @@ async fn frontend_data(storage: TestContextVolatileStorage) {
     module_tester.shutdown().await.unwrap();
 }
 
+#[actix_rt::test]
+#[serial]
+async fn incremental_protocol_save_matches_full_save() {
+    let test_ctx = TestContext::new(TestContextVolatileStorage::Memory).await;
+    let user1 = test_ctx
+        .db_ctx
+        .create_test_user(USER_1.n, Vec::new())
+        .await
+        .unwrap();
+    let room = test_ctx
+        .db_ctx
+        .create_test_room(ROOM_ID, user1.id, false)
+        .await
+        .unwrap();
+    let mut db_conn = test_ctx.db_ctx.db.get_conn().await.unwrap();
+
+    let entries: Vec<ProtocolEntry> = [VoteOption::Yes, VoteOption::No, VoteOption::Abstain]
+        .into_iter()
+        .map(|option| {
+            ProtocolEntry::new(protocol_v1::VoteEvent::Vote(protocol_v1::Vote {
+                user_info: None,
+                token: Token::from_str("1111Cn8eVZg").unwrap(),
+                option,
+            }))
+        })
+        .collect();
+
+    let new_resource = || NewModuleResource {
+        tenant_id: user1.tenant_id,
+        room_id: room.id,
+        created_by: user1.id,
+        namespace: "legal_vote".into(),
+        tag: Some("protocol".into()),
+        data: to_value(NewProtocol::new(vec![])).unwrap(),
+    };
+
+    let full = new_resource().insert(&mut db_conn).await.unwrap();
+    let incremental = new_resource().insert(&mut db_conn).await.unwrap();
+
+    ModuleResource::patch(
+        &mut db_conn,
+        Filter::new().with_id(full.id),
+        patch::rewrite_operations(entries.clone()).unwrap(),
+    )
+    .await
+    .unwrap();
+
+    for (entries, flushed) in [(&entries[..2], 0), (&entries[..], 2)] {
+        ModuleResource::patch(
+            &mut db_conn,
+            Filter::new().with_id(incremental.id),
+            patch::append_operations(entries, flushed).unwrap().unwrap(),
+        )
+        .await
+        .unwrap();
+    }
+
+    // Appending fails when the stored protocol does not match the flushed entries
+    assert!(
+        ModuleResource::patch(
+            &mut db_conn,
+            Filter::new().with_id(incremental.id),
+            patch::append_operations(&entries, 0).unwrap().unwrap(),
+        )
+        .await
+        .is_err()
+    );
+
+    let full = ModuleResource::get(&mut db_conn, Filter::new().with_id(full.id))
+        .await
+        .unwrap()
+        .remove(0);
+    let incremental = ModuleResource::get(&mut db_conn, Filter::new().with_id(incremental.id))
+        .await
+        .unwrap()
+        .remove(0);
+
+    assert_eq!(full.data, incremental.data);
+    assert_eq!(to_value(NewProtocol::new(entries)).unwrap(), full.data);
+}
+
+#[actix_rt::test]
+#[serial]
+async fn summarize_state_redis() {
+    summarize_state(TestContextVolatileStorage::Redis).await
+}
+
+#[actix_rt::test]
+#[serial]
+async fn summarize_state_memory() {
+    summarize_state(TestContextVolatileStorage::Memory).await
+}
+
+async fn summarize_state(storage: TestContextVolatileStorage) {
+    let test_ctx = TestContext::new(storage).await;
+    let (mut module_tester, _user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, Arc::default()).await;
+    let room = SignalingRoomId::new_for_room(ROOM_ID);
+
+    assert_eq!(
+        LegalVote::summarize_state(test_ctx.volatile.clone(), room)
+            .await
+            .unwrap(),
+        None
+    );
+
+    let (legal_vote_id, _) = default_start_setup(&mut module_tester).await;
+
+    let state = LegalVote::summarize_state(test_ctx.volatile.clone(), room)
+        .await
+        .unwrap()
+        .expect("Expected the running vote in the state");
+    assert_eq!(
+        state["current_vote"]["legal_vote_id"],
+        to_value(legal_vote_id).unwrap()
+    );
+    assert_eq!(state["current_vote"]["kind"], "roll_call");
+    assert_eq!(state["pending_confirmation"], Value::Null);
+
+    module_tester.shutdown().await.unwrap()
+}
+
+#[actix_rt::test]
+#[serial]
+async fn failed_start_removes_vote_from_database_redis() {
+    failed_start_removes_vote_from_database(TestContextVolatileStorage::Redis).await
+}
+
+#[actix_rt::test]
+#[serial]
+async fn failed_start_removes_vote_from_database_memory() {
+    failed_start_removes_vote_from_database(TestContextVolatileStorage::Memory).await
+}
+
+async fn failed_start_removes_vote_from_database(storage: TestContextVolatileStorage) {
+    let test_ctx = TestContext::new(storage).await;
+    let (mut module_tester, _user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, Arc::default()).await;
+
+    let (legal_vote_id, _) = default_start_setup(&mut module_tester).await;
+
+    // The second vote is created in the database, but setting it up in the volatile storage fails
+    // after its parameters and protocol were stored, because the room already has an active vote
+    module_tester
+        .send_ws_message(
+            &USER_1.participant_id,
+            LegalVoteCommand::Start(UserParameters {
+                kind: VoteKind::RollCall,
+                name: Name::try_from("SecondVote").unwrap(),
+                subtitle: None,
+                topic: None,
+                allowed_participants: AllowedParticipants::try_from(vec![USER_1.participant_id])
+                    .unwrap(),
+                enable_abstain: Some(false),
+                auto_close: false,
+                duration: None,
+                create_pdf: false,
+                timezone: None,
+                sandbox: false,
+                start_at: None,
+                require_confirmation: false,
+                allow_guests: false,
+            }),
+        )
+        .unwrap();
+
+    assert_eq!(
+        module_tester
+            .receive_ws_message(&USER_1.participant_id)
+            .await
+            .unwrap(),
+        WsMessageOutgoing::Module(LegalVoteEvent::Error(ErrorKind::VoteAlreadyActive))
+    );
+
+    let mut db_conn = test_ctx.db_ctx.db.get_conn().await.unwrap();
+    let module_resources = ModuleResource::get(
+        &mut db_conn,
+        Filter::new().with_namespace(LegalVote::NAMESPACE.to_string()),
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        module_resources
+            .iter()
+            .map(|module_resource| module_resource.id)
+            .collect::<Vec<_>>(),
+        vec![*legal_vote_id.inner()]
+    );
+
+    module_tester.shutdown().await.unwrap()
+}
+
 /// Start a vote with user1 with default UserParameters
 async fn default_vote_start_by_user1(
     module_tester: &mut ModuleTester<LegalVote>,
@@ -2516,11 +3912,15 @@ async fn default_vote_start_by_user1(
             USER_2.participant_id,
         ])
         .unwrap(),
-        enable_abstain: false,
+        enable_abstain: Some(false),
         auto_close: false,
         duration: None,
         create_pdf: false,
         timezone: None,
+        sandbox: false,
+        start_at: None,
+        require_confirmation: false,
+        allow_guests: false,
     };
 
     module_tester
@@ -2575,3 +3975,51 @@ async fn default_start_setup(
     let (_, user_2_token) = receive_start_on_user2(module_tester).await;
     (legal_vote_id, vec![user_1_token, user_2_token])
 }
+
+#[actix_rt::test]
+#[serial]
+async fn malformed_message_is_reported() {
+    let test_ctx = TestContext::new(TestContextVolatileStorage::Memory).await;
+    let (mut module_tester, _user1, _user2) =
+        common::setup_users::<LegalVote>(&test_ctx, Arc::default()).await;
+
+    module_tester
+        .send_raw_ws_message(
+            &USER_2.participant_id,
+            serde_json::json!({"action": "vote", "option": "yes", "token": "secret"}),
+        )
+        .unwrap();
+
+    let error = module_tester
+        .receive_ws_message(&USER_2.participant_id)
+        .await
+        .unwrap();
+    assert_eq!(
+        error,
+        WsMessageOutgoing::Malformed(MalformedMessage {
+            error: MalformedMessageError::InvalidMessage,
+            field: Some("legal_vote_id".to_owned()),
+            reason: MalformedMessageReason::MissingField,
+        })
+    );
+
+    // The connection stays usable after a malformed message
+    module_tester
+        .send_ws_message(
+            &USER_2.participant_id,
+            LegalVoteCommand::GetNonVoters(GetNonVoters {
+                legal_vote_id: LegalVoteId::from_u128(11311),
+            }),
+        )
+        .unwrap();
+
+    assert_eq!(
+        module_tester
+            .receive_ws_message(&USER_2.participant_id)
+            .await
+            .unwrap(),
+        WsMessageOutgoing::Module(LegalVoteEvent::Error(ErrorKind::InsufficientPermissions))
+    );
+
+    module_tester.shutdown().await.unwrap();
+}
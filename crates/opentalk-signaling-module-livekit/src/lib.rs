@@ -4,6 +4,7 @@
 
 use std::{collections::BTreeSet, sync::Arc, time::Duration};
 
+use arc_swap::ArcSwap;
 use either::Either;
 use futures::{
     StreamExt,
@@ -17,7 +18,9 @@ use livekit_api::{
     },
 };
 use livekit_protocol::{ParticipantPermission, TrackSource};
-use opentalk_controller_settings::{LiveKit, SettingsProvider};
+use opentalk_controller_settings::{
+    LiveKit, Settings, SettingsProvider, settings_file::ScreenSharePolicy,
+};
 use opentalk_signaling_core::{
     CleanupScope, DestroyContext, Event, InitContext, ModuleContext, SignalingModule,
     SignalingModuleError, SignalingModuleInitData, SignalingRoomId, VolatileStorage, control,
@@ -25,7 +28,7 @@ use opentalk_signaling_core::{
 use opentalk_types_common::modules::ModuleId;
 use opentalk_types_signaling::{ParticipantId, ParticipationKind, ParticipationVisibility, Role};
 use opentalk_types_signaling_livekit::{
-    Credentials, MicrophoneRestrictionState,
+    Credentials, MicrophoneRestrictionState, ScreenSharePolicyState,
     command::{self, UnrestrictedParticipants},
     event,
     state::{self, LiveKitState},
@@ -56,7 +59,10 @@ pub struct Livekit {
 
 pub struct LivekitParams {
     settings_provider: SettingsProvider,
-    livekit_settings: LiveKit,
+    /// The livekit settings, swapped when the settings are reloaded
+    livekit_settings: ArcSwap<LiveKit>,
+    /// The livekit settings as found in the most recently loaded configuration
+    configured_livekit_settings: ArcSwap<LiveKit>,
     room_client: RoomClient,
 }
 
@@ -122,7 +128,7 @@ impl SignalingModule for Livekit {
                 let service_url = match control_data.participation_kind {
                     ParticipationKind::User | ParticipationKind::Guest => None,
                     ParticipationKind::Sip | ParticipationKind::Recorder => {
-                        let service_url = self.params.livekit_settings.service_url.clone();
+                        let service_url = self.params.livekit_settings.load().service_url.clone();
                         let service_url_ws = if service_url.starts_with("http") {
                             service_url.replacen("http", "ws", 1)
                         } else {
@@ -137,10 +143,11 @@ impl SignalingModule for Livekit {
                     credentials: Credentials {
                         room: room_name,
                         token: access_token,
-                        public_url: self.params.livekit_settings.public_url.clone(),
+                        public_url: self.params.livekit_settings.load().public_url.clone(),
                         service_url,
                     },
                     microphone_restriction_state,
+                    screen_share_policy: screen_share_policy_state(self.screen_share_policy()),
                 });
 
                 Ok(())
@@ -210,10 +217,48 @@ impl SignalingModule for Livekit {
 
         Ok(Some(Arc::new(LivekitParams {
             settings_provider: init.settings_provider.clone(),
-            livekit_settings: init.startup_settings.livekit.clone(),
+            livekit_settings: ArcSwap::from_pointee(init.startup_settings.livekit.clone()),
+            configured_livekit_settings: ArcSwap::from_pointee(
+                init.startup_settings.livekit.clone(),
+            ),
             room_client,
         })))
     }
+
+    fn reload_params(params: &Self::Params, settings: &Settings) {
+        let current = params.livekit_settings.load();
+        let reloaded = &settings.livekit;
+        let previous = params
+            .configured_livekit_settings
+            .swap(Arc::new(reloaded.clone()));
+
+        // The room client is created with the credentials and the service url on startup, only
+        // report changes since the previous reload
+        if reloaded.api_key != previous.api_key
+            || reloaded.api_secret != previous.api_secret
+            || reloaded.service_url != previous.service_url
+        {
+            log::warn!(
+                "Changes to the livekit credentials or service url require a restart of the controller to take effect"
+            );
+        }
+
+        params.livekit_settings.store(Arc::new(LiveKit {
+            api_key: current.api_key.clone(),
+            api_secret: current.api_secret.clone(),
+            service_url: current.service_url.clone(),
+            ..reloaded.clone()
+        }));
+    }
+}
+
+/// Map the configured screen share policy to its representation in the frontend state
+fn screen_share_policy_state(policy: ScreenSharePolicy) -> ScreenSharePolicyState {
+    match policy {
+        ScreenSharePolicy::Everyone => ScreenSharePolicyState::Everyone,
+        ScreenSharePolicy::ModeratorsOnly => ScreenSharePolicyState::ModeratorsOnly,
+        ScreenSharePolicy::RequiresPermission => ScreenSharePolicyState::RequiresPermission,
+    }
 }
 
 impl Livekit {
@@ -231,7 +276,7 @@ impl Livekit {
                 let service_url = match self.participation_kind {
                     ParticipationKind::User | ParticipationKind::Guest => None,
                     ParticipationKind::Sip | ParticipationKind::Recorder => {
-                        let service_url = self.params.livekit_settings.service_url.clone();
+                        let service_url = self.params.livekit_settings.load().service_url.clone();
                         let service_url_ws = if service_url.starts_with("http") {
                             service_url.replacen("http", "ws", 1)
                         } else {
@@ -245,7 +290,7 @@ impl Livekit {
                 ctx.ws_send(event::LiveKitEvent::Credentials(Credentials {
                     room: room_name,
                     token: access_token,
-                    public_url: self.params.livekit_settings.public_url.clone(),
+                    public_url: self.params.livekit_settings.load().public_url.clone(),
                     service_url,
                 }));
 
@@ -355,6 +400,15 @@ impl Livekit {
         }
     }
 
+    /// The configured policy for starting a screen share
+    fn screen_share_policy(&self) -> ScreenSharePolicy {
+        self.params
+            .settings_provider
+            .get()
+            .defaults
+            .screen_share_policy
+    }
+
     async fn set_screenshare_permissions(
         &mut self,
         mut ctx: ModuleContext<'_, Self>,
@@ -571,12 +625,9 @@ impl Livekit {
             .get_microphone_restriction_state(self.room_id.room_id())
             .await?;
 
-        let allow_screenshare = !self
-            .params
-            .settings_provider
-            .get()
-            .defaults
-            .screen_share_requires_permission;
+        let allow_screenshare = self
+            .screen_share_policy()
+            .allows_without_permission(self.role.is_moderator());
 
         let mut available_sources = LIVEKIT_MEDIA_SOURCES.to_vec();
         if !self.role.is_moderator() {
@@ -602,31 +653,33 @@ impl Livekit {
 
         let identity = self.participant_id.to_string();
 
-        let access_token = AccessToken::with_api_key(
-            &self.params.livekit_settings.api_key,
-            &self.params.livekit_settings.api_secret,
-        )
-        .with_name(&identity)
-        .with_identity(&identity)
-        .with_grants(VideoGrants {
-            room_create: false,
-            room_list: false,
-            room_record: false,
-            room_admin: false,
-            room_join: true,
-            room: room.name.clone(),
-            can_publish: true,
-            can_subscribe: true,
-            can_publish_data: false,
-            can_publish_sources,
-            can_update_own_metadata: false,
-            ingress_admin: false,
-            hidden: visibility.is_hidden(),
-            recorder: false,
-        })
-        .with_ttl(ACCESS_TOKEN_TTL)
-        .to_jwt()
-        .whatever_context::<&str, SignalingModuleError>("Failed to create livekit access-token")?;
+        let livekit_settings = self.params.livekit_settings.load_full();
+
+        let access_token =
+            AccessToken::with_api_key(&livekit_settings.api_key, &livekit_settings.api_secret)
+                .with_name(&identity)
+                .with_identity(&identity)
+                .with_grants(VideoGrants {
+                    room_create: false,
+                    room_list: false,
+                    room_record: false,
+                    room_admin: false,
+                    room_join: true,
+                    room: room.name.clone(),
+                    can_publish: true,
+                    can_subscribe: true,
+                    can_publish_data: false,
+                    can_publish_sources,
+                    can_update_own_metadata: false,
+                    ingress_admin: false,
+                    hidden: visibility.is_hidden(),
+                    recorder: false,
+                })
+                .with_ttl(ACCESS_TOKEN_TTL)
+                .to_jwt()
+                .whatever_context::<&str, SignalingModuleError>(
+                    "Failed to create livekit access-token",
+                )?;
 
         self.token_identities.insert(identity);
 
@@ -667,31 +720,33 @@ impl Livekit {
             self.token_identities.len()
         );
 
-        let token = AccessToken::with_api_key(
-            &self.params.livekit_settings.api_key,
-            &self.params.livekit_settings.api_secret,
-        )
-        .with_name(&identity)
-        .with_identity(&identity)
-        .with_grants(VideoGrants {
-            room_create: false,
-            room_list: false,
-            room_record: false,
-            room_admin: false,
-            room_join: true,
-            room: self.room_id.to_string(),
-            can_publish: false,
-            can_subscribe: true,
-            can_publish_data: false,
-            can_publish_sources: vec![],
-            can_update_own_metadata: false,
-            ingress_admin: false,
-            hidden: true,
-            recorder: false,
-        })
-        .with_ttl(ACCESS_TOKEN_TTL)
-        .to_jwt()
-        .whatever_context::<&str, SignalingModuleError>("Failed to create livekit access-token")?;
+        let livekit_settings = self.params.livekit_settings.load_full();
+
+        let token =
+            AccessToken::with_api_key(&livekit_settings.api_key, &livekit_settings.api_secret)
+                .with_name(&identity)
+                .with_identity(&identity)
+                .with_grants(VideoGrants {
+                    room_create: false,
+                    room_list: false,
+                    room_record: false,
+                    room_admin: false,
+                    room_join: true,
+                    room: self.room_id.to_string(),
+                    can_publish: false,
+                    can_subscribe: true,
+                    can_publish_data: false,
+                    can_publish_sources: vec![],
+                    can_update_own_metadata: false,
+                    ingress_admin: false,
+                    hidden: true,
+                    recorder: false,
+                })
+                .with_ttl(ACCESS_TOKEN_TTL)
+                .to_jwt()
+                .whatever_context::<&str, SignalingModuleError>(
+                    "Failed to create livekit access-token",
+                )?;
 
         self.token_identities.insert(identity);
 
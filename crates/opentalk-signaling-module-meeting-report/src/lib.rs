@@ -2,7 +2,11 @@
 //
 // SPDX-License-Identifier: EUPL-1.2
 
-use std::{collections::BTreeMap, path::Path, sync::Arc};
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 
 use bytes::Bytes;
 use chrono::{DateTime, Local, Utc};
@@ -14,8 +18,8 @@ use opentalk_db_storage::{events::Event as DbEvent, users::User};
 use opentalk_report_generation::ToReportDateTime;
 use opentalk_signaling_core::{
     ChunkFormat, DestroyContext, Event, InitContext, ModuleContext, ObjectStorage,
-    ObjectStorageError, SignalingModule, SignalingModuleError, SignalingModuleInitData,
-    SignalingRoomId, VolatileStorage,
+    ObjectStorageError, PdfGenerationLimiter, SignalingModule, SignalingModuleError,
+    SignalingModuleInitData, SignalingRoomId, VolatileStorage,
     assets::{AssetError, NewAssetFileName, save_asset},
     control::{
         self,
@@ -60,17 +64,25 @@ impl MeetingReportStorageProvider for VolatileStorage {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct MeetingReportParams {
+    pdf_generation_limiter: PdfGenerationLimiter,
+    report_dump_path: Option<PathBuf>,
+    report_font_path: Option<PathBuf>,
+}
+
 pub struct MeetingReport {
     room_id: SignalingRoomId,
     db: Arc<Db>,
     storage: Arc<ObjectStorage>,
+    params: MeetingReportParams,
 }
 
 #[async_trait::async_trait(?Send)]
 impl SignalingModule for MeetingReport {
     const NAMESPACE: ModuleId = MODULE_ID;
 
-    type Params = ();
+    type Params = MeetingReportParams;
 
     type Incoming = MeetingReportCommand;
 
@@ -86,13 +98,14 @@ impl SignalingModule for MeetingReport {
 
     async fn init(
         ctx: InitContext<'_, Self>,
-        _params: &Self::Params,
+        params: &Self::Params,
         _protocol: &'static str,
     ) -> Result<Option<Self>, SignalingModuleError> {
         Ok(Some(Self {
             room_id: ctx.room_id(),
             db: ctx.db.clone(),
             storage: ctx.storage.clone(),
+            params: params.clone(),
         }))
     }
 
@@ -123,9 +136,13 @@ impl SignalingModule for MeetingReport {
     async fn on_destroy(self, _ctx: DestroyContext<'_>) {}
 
     async fn build_params(
-        _init: SignalingModuleInitData,
+        init: SignalingModuleInitData,
     ) -> Result<Option<Self::Params>, SignalingModuleError> {
-        Ok(Some(()))
+        Ok(Some(MeetingReportParams {
+            pdf_generation_limiter: init.pdf_generation_limiter,
+            report_dump_path: init.report_dump_path,
+            report_font_path: init.report_font_path,
+        }))
     }
 }
 
@@ -159,13 +176,22 @@ impl MeetingReport {
             .collect_report_information(&mut ctx, include_email_addresses)
             .await?;
 
-        let report =
-            Self::generate_pdf_report(DEFAULT_TEMPLATE.to_string(), event, participants, timezone)
-                .await
-                .with_whatever_context::<_, _, SignalingModuleError>(|_| {
-                    ctx.ws_send(Error::Generate);
-                    "Failed to create pdf"
-                })?;
+        let report = self
+            .params
+            .pdf_generation_limiter
+            .run(Self::generate_pdf_report(
+                DEFAULT_TEMPLATE.to_string(),
+                event,
+                participants,
+                timezone,
+                self.params.report_dump_path.as_deref(),
+                self.params.report_font_path.as_deref(),
+            ))
+            .await
+            .with_whatever_context::<_, _, SignalingModuleError>(|_| {
+                ctx.ws_send(Error::Generate);
+                "Failed to create pdf"
+            })?;
         self.upload_pdf(report, ctx).await;
 
         Ok(())
@@ -217,6 +243,8 @@ impl MeetingReport {
         event: DbEvent,
         participants: Vec<ReportParticipant>,
         report_timezone: TimeZone,
+        dump_path: Option<&Path>,
+        font_path: Option<&Path>,
     ) -> Result<Vec<u8>, SignalingModuleError> {
         let tz = Tz::from(report_timezone);
         let starts_at = event.starts_at.to_report_date_time(&tz);
@@ -232,18 +260,20 @@ impl MeetingReport {
                 report_timezone,
                 participants,
             },
+            dump_path,
             Path::new(&format!("{MODULE_ID}/{timestamp}")),
+            font_path,
         )
     }
 
     fn generate_pdf_report_from_template(
         template: String,
         parameter: &ReportTemplateParameter,
+        dump_path: Option<&Path>,
         dump_to_relative_path: &Path,
+        font_path: Option<&Path>,
     ) -> Result<Vec<u8>, SignalingModuleError> {
-        let dump_to_path = std::env::var("OPENTALK_REPORT_DUMP_PATH")
-            .map(|p| Path::new(&p).join(dump_to_relative_path))
-            .ok();
+        let dump_to_path = dump_path.map(|p| p.join(dump_to_relative_path));
 
         let pdf = opentalk_report_generation::generate_pdf_report(
             template,
@@ -255,6 +285,7 @@ impl MeetingReport {
                     .into(),
             )]),
             dump_to_path.as_deref(),
+            font_path,
         )
         .whatever_context::<_, SignalingModuleError>("unable to build pdf")?;
         Ok(pdf)
@@ -375,7 +406,9 @@ mod tests {
         let pdf = MeetingReport::generate_pdf_report_from_template(
             DEFAULT_TEMPLATE.to_string(),
             parameter,
+            opentalk_report_generation::resolve_dump_path(None).as_deref(),
             Path::new(&format!("{MODULE_ID}/{sample_name}")),
+            None,
         )
         .expect("generation should work");
         pdf_extract::extract_text_from_mem(&pdf)
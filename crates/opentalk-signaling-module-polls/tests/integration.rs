@@ -4,7 +4,10 @@
 
 use std::{collections::BTreeSet, time::Duration};
 
-use opentalk_signaling_core::module_tester::{ModuleTester, WsMessageOutgoing};
+use opentalk_signaling_core::{
+    MalformedMessage, MalformedMessageError, MalformedMessageReason,
+    module_tester::{ModuleTester, WsMessageOutgoing},
+};
 use opentalk_signaling_module_polls::*;
 use opentalk_test_util::*;
 use opentalk_types_signaling_polls::{
@@ -578,3 +581,36 @@ async fn poll_with_invalid_multiple_choice() {
 
     module_tester.shutdown().await.unwrap()
 }
+
+#[actix_rt::test]
+#[serial]
+async fn malformed_message_is_reported() {
+    let test_ctx = TestContext::default().await;
+
+    let (mut module_tester, _user1, _user2) = common::setup_users::<Polls>(&test_ctx, ()).await;
+
+    module_tester
+        .send_raw_ws_message(
+            &USER_1.participant_id,
+            serde_json::json!({"action": "start", "live": true}),
+        )
+        .unwrap();
+
+    let error = module_tester
+        .receive_ws_message(&USER_1.participant_id)
+        .await
+        .unwrap();
+    assert_eq!(
+        error,
+        WsMessageOutgoing::Malformed(MalformedMessage {
+            error: MalformedMessageError::InvalidMessage,
+            field: Some("topic".to_owned()),
+            reason: MalformedMessageReason::MissingField,
+        })
+    );
+
+    // The connection stays usable after a malformed message
+    start_poll(&mut module_tester, true, false).await;
+
+    module_tester.shutdown().await.unwrap();
+}
@@ -4,26 +4,37 @@
 
 use std::{
     collections::{BTreeMap, BTreeSet, HashMap},
+    iter,
     sync::Arc,
 };
 
 use either::Either;
 use futures::{FutureExt, stream::once};
 use lapin_pool::{RabbitMqChannel, RabbitMqPool};
+use opentalk_controller_settings::SettingsProvider;
 use opentalk_database::Db;
-use opentalk_db_storage::streaming_targets::RoomStreamingTargetRecord;
+use opentalk_db_storage::{streaming_targets::RoomStreamingTargetRecord, tenants::Tenant};
 use opentalk_signaling_core::{
-    CleanupScope, DestroyContext, Event, InitContext, ModuleContext, SignalingModule,
-    SignalingModuleError, SignalingModuleInitData, SignalingRoomId, VolatileStorage,
+    CleanupScope, DestroyContext, Event, ExchangeDelivery, ExchangeHandle, InitContext,
+    ModuleContext, SerdeJsonSnafu, SignalingModule, SignalingModuleError, SignalingModuleInitData,
+    SignalingRoomId, VolatileStorage,
     control::{
         self,
         storage::{ControlStorageParticipantAttributes as _, RECORDING_CONSENT},
     },
 };
-use opentalk_types_common::{features::FeatureId, modules::ModuleId, streaming::StreamingTargetId};
-use opentalk_types_signaling::{ParticipantId, Role};
+use opentalk_types_common::{
+    features::FeatureId,
+    modules::ModuleId,
+    rooms::{BreakoutRoomId, RoomId},
+    streaming::{StreamingTargetId, StreamingTargetKind},
+    tenants::TenantId,
+    time::Timestamp,
+};
+use opentalk_types_signaling::{NamespacedEvent, ParticipantId, Role};
 use opentalk_types_signaling_recording::{
-    MODULE_ID, RECORD_FEATURE_ID, STREAM_FEATURE_ID, StreamStatus, StreamTargetSecret,
+    MODULE_ID, RECORD_FEATURE_ID, STREAM_FEATURE_ID, StreamKindSecret, StreamStatus,
+    StreamTargetSecret,
     command::{PauseStreaming, RecordingCommand, SetConsent, StartStreaming, StopStreaming},
     event::{Error, RecorderError, RecordingEvent},
     peer_state::RecordingPeerState,
@@ -35,6 +46,7 @@ use tokio::time::Duration;
 use self::storage::RecordingStorage;
 
 mod exchange;
+mod probe;
 mod rabbitmq;
 mod service;
 mod storage;
@@ -85,6 +97,8 @@ pub struct Recording {
     /// Whether or not the current participant is the recorder
     db: Arc<Db>,
 
+    tenant_id: TenantId,
+
     /// RabbitMQ channel used to send the recording start command over
     rabbitmq_channel: RabbitMqChannel,
 }
@@ -98,6 +112,7 @@ impl std::fmt::Debug for Recording {
 #[derive(Clone)]
 pub struct RecordingParams {
     pub queue: String,
+    pub settings_provider: SettingsProvider,
 }
 
 impl std::fmt::Debug for RecordingParams {
@@ -164,6 +179,7 @@ impl SignalingModule for Recording {
             params: params.clone(),
             enabled_features,
             db: ctx.db().clone(),
+            tenant_id: ctx.room().tenant_id,
             rabbitmq_channel,
             recorder_started: false,
         }))
@@ -173,6 +189,27 @@ impl SignalingModule for Recording {
         BTreeSet::from_iter([RECORD_FEATURE_ID, STREAM_FEATURE_ID])
     }
 
+    async fn summarize_state(
+        mut volatile: VolatileStorage,
+        room: SignalingRoomId,
+    ) -> Result<Option<serde_json::Value>, SignalingModuleError> {
+        let storage = volatile.storage();
+
+        if !storage.is_streaming_initialized(room).await? {
+            return Ok(None);
+        }
+
+        // Only the status is reported, the stream targets contain secrets
+        let streams: BTreeMap<_, _> = storage
+            .get_streams(room)
+            .await?
+            .into_iter()
+            .map(|(target_id, target)| (target_id, target.status))
+            .collect();
+
+        Ok(Some(serde_json::json!({ "streams": streams })))
+    }
+
     async fn on_event(
         &mut self,
         mut ctx: ModuleContext<'_, Self>,
@@ -314,7 +351,13 @@ impl SignalingModule for Recording {
         else {
             return Ok(None);
         };
-        Ok(Some((rabbitmq_pool.clone(), RecordingParams { queue })))
+        Ok(Some((
+            rabbitmq_pool.clone(),
+            RecordingParams {
+                queue,
+                settings_provider: init.settings_provider.clone(),
+            },
+        )))
     }
 }
 
@@ -329,6 +372,74 @@ async fn cleanup_streams(ctx: &mut DestroyContext<'_>, signaling_room_id: Signal
     }
 }
 
+/// Request the recorders of a room and its breakout rooms to stop all running streams and
+/// recordings
+///
+/// The recorders finalize and upload the recordings asynchronously. Returns `false` if no stream
+/// is running in any of the rooms.
+pub async fn stop_recording(
+    volatile: &mut VolatileStorage,
+    exchange_handle: &ExchangeHandle,
+    room_id: RoomId,
+    breakout_rooms: impl IntoIterator<Item = BreakoutRoomId>,
+) -> Result<bool, SignalingModuleError> {
+    let rooms = iter::once(None)
+        .chain(breakout_rooms.into_iter().map(Some))
+        .map(|breakout_room| SignalingRoomId::new(room_id, breakout_room));
+
+    let mut stopped = false;
+    for room in rooms {
+        stopped |= stop_streams(volatile, exchange_handle, room).await?;
+    }
+
+    Ok(stopped)
+}
+
+/// Request the recorders of a single signaling room to stop its running streams
+///
+/// Returns `false` if no stream is running in the room.
+async fn stop_streams(
+    volatile: &mut VolatileStorage,
+    exchange_handle: &ExchangeHandle,
+    room: SignalingRoomId,
+) -> Result<bool, SignalingModuleError> {
+    let target_ids: BTreeSet<StreamingTargetId> = volatile
+        .storage()
+        .get_streams(room)
+        .await?
+        .into_iter()
+        .filter(|(_, target)| {
+            matches!(
+                target.status,
+                StreamStatus::Active | StreamStatus::Starting | StreamStatus::Paused
+            )
+        })
+        .map(|(id, _)| id)
+        .collect();
+
+    if target_ids.is_empty() {
+        return Ok(false);
+    }
+
+    let message = NamespacedEvent {
+        module: RecordingService::NAMESPACE,
+        timestamp: Timestamp::now(),
+        payload: service::exchange::Message::StopStreams { target_ids },
+    };
+
+    exchange_handle
+        .publish_with_delivery(
+            control::exchange::current_room_all_recorders(room),
+            serde_json::to_string(&message).context(SerdeJsonSnafu {
+                message: "Failed to serialize StopStreams message",
+            })?,
+            ExchangeDelivery::Critical,
+        )
+        .with_whatever_context::<_, _, SignalingModuleError>(|err| format!("{err}"))?;
+
+    Ok(true)
+}
+
 impl Recording {
     async fn initialize_streaming(
         &self,
@@ -410,6 +521,19 @@ impl Recording {
             return Ok(());
         }
 
+        // Without explicitly selected targets, fall back to streaming to the primary target only
+        let target_ids = if target_ids.is_empty() {
+            match self.primary_streaming_target().await? {
+                Some(primary) => BTreeSet::from([primary]),
+                None => {
+                    ctx.ws_send(Error::InvalidStreamingId);
+                    return Ok(());
+                }
+            }
+        } else {
+            target_ids
+        };
+
         if !self
             .target_ids_exist(ctx.volatile.storage(), &target_ids)
             .await?
@@ -418,6 +542,16 @@ impl Recording {
             return Ok(());
         }
 
+        let unreachable_target_ids = self
+            .probe_streaming_targets(ctx.volatile.storage(), &target_ids)
+            .await?;
+        if !unreachable_target_ids.is_empty() {
+            ctx.ws_send(Error::StreamingTargetUnreachable {
+                target_ids: unreachable_target_ids,
+            });
+            return Ok(());
+        }
+
         let is_recorder_running = ctx
             .volatile
             .storage()
@@ -463,7 +597,7 @@ impl Recording {
             return Ok(());
         }
 
-        ctx.exchange_publish_to_namespace(
+        ctx.exchange_publish_to_namespace_critical(
             control::exchange::current_room_all_recorders(self.room),
             RecordingService::NAMESPACE,
             service::exchange::Message::StartStreams { target_ids },
@@ -490,7 +624,7 @@ impl Recording {
             return Ok(());
         }
 
-        ctx.exchange_publish_to_namespace(
+        ctx.exchange_publish_to_namespace_critical(
             control::exchange::current_room_all_recorders(self.room),
             RecordingService::NAMESPACE,
             service::exchange::Message::PauseStreams { target_ids },
@@ -535,7 +669,7 @@ impl Recording {
             return Ok(());
         }
 
-        ctx.exchange_publish_to_namespace(
+        ctx.exchange_publish_to_namespace_critical(
             control::exchange::current_room_all_recorders(self.room),
             RecordingService::NAMESPACE,
             service::exchange::Message::StopStreams { target_ids },
@@ -568,6 +702,65 @@ impl Recording {
         Ok(())
     }
 
+    /// Returns the id of the primary streaming target of the room, if any.
+    async fn primary_streaming_target(
+        &self,
+    ) -> Result<Option<StreamingTargetId>, SignalingModuleError> {
+        let mut conn = self.db.get_conn().await?;
+
+        let primary =
+            RoomStreamingTargetRecord::get_primary_for_room(&mut conn, self.room.room_id()).await?;
+
+        Ok(primary.map(|target| target.id))
+    }
+
+    /// Probes the livestream targets if enabled for the tenant of the room and returns the ids of
+    /// the targets that could not be reached
+    async fn probe_streaming_targets(
+        &self,
+        storage: &mut dyn RecordingStorage,
+        target_ids: &BTreeSet<StreamingTargetId>,
+    ) -> Result<BTreeSet<StreamingTargetId>, SignalingModuleError> {
+        let probe = self.params.settings_provider.get().streaming_probe.clone();
+        if !probe.is_enabled_for_any() {
+            return Ok(BTreeSet::new());
+        }
+
+        let mut conn = self.db.get_conn().await?;
+        let tenant = Tenant::get(&mut conn, self.tenant_id).await?;
+        if !probe.is_enabled_for(&tenant.oidc_tenant_id.to_string()) {
+            return Ok(BTreeSet::new());
+        }
+
+        let mut unreachable = BTreeSet::new();
+        for target_id in target_ids {
+            let target = storage.get_stream(self.room, *target_id).await?;
+            let StreamKindSecret::Livestream(StreamingTargetKind::Custom {
+                streaming_endpoint,
+                ..
+            }) = &target.kind
+            else {
+                continue;
+            };
+
+            if probe::is_reachable(streaming_endpoint, probe.timeout).await {
+                log::info!(
+                    "Streaming target {target_id} in room {} is reachable",
+                    self.room
+                );
+            } else {
+                log::warn!(
+                    "Streaming target {target_id} in room {} is unreachable at {}",
+                    self.room,
+                    streaming_endpoint.host_str().unwrap_or_default()
+                );
+                unreachable.insert(*target_id);
+            }
+        }
+
+        Ok(unreachable)
+    }
+
     async fn target_ids_exist(
         &self,
         storage: &mut dyn RecordingStorage,
@@ -581,3 +774,165 @@ impl Recording {
         Ok(true)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use opentalk_signaling_core::{
+        ExchangeTask, SignalingRoomId, SubscriberHandle, VolatileStaticMemoryStorage,
+        VolatileStorage, control,
+    };
+    use opentalk_types_common::{
+        rooms::{BreakoutRoomId, RoomId},
+        streaming::StreamingTargetId,
+    };
+    use opentalk_types_signaling_recording::{StreamKindSecret, StreamStatus, StreamTargetSecret};
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::{RecordingStorageProvider as _, stop_recording};
+
+    async fn set_recording(
+        volatile: &mut VolatileStorage,
+        room: SignalingRoomId,
+        status: StreamStatus,
+    ) -> StreamingTargetId {
+        let target_id = StreamingTargetId::generate();
+        let streams = BTreeMap::from([(
+            target_id,
+            StreamTargetSecret {
+                name: "Recording".to_string(),
+                kind: StreamKindSecret::Recording,
+                status,
+            },
+        )]);
+
+        volatile
+            .storage()
+            .set_streams(room, &streams)
+            .await
+            .unwrap();
+
+        target_id
+    }
+
+    async fn assert_stop_streams(recorders: &mut SubscriberHandle, target_id: StreamingTargetId) {
+        let message: serde_json::Value =
+            serde_json::from_str(&recorders.receive().await.unwrap()).unwrap();
+
+        assert_eq!(
+            message["payload"],
+            json!({"action": "stop_streams", "target_ids": [target_id]})
+        );
+    }
+
+    #[tokio::test]
+    async fn stop_recording_in_main_and_breakout_rooms() {
+        let mut volatile = VolatileStorage::Left(VolatileStaticMemoryStorage);
+        let exchange_handle = ExchangeTask::spawn(None).await.unwrap();
+
+        let room_id = RoomId::generate();
+        let main_room = SignalingRoomId::new_for_room(room_id);
+        let recording_breakout_room = BreakoutRoomId::generate();
+        let idle_breakout_room = BreakoutRoomId::generate();
+
+        let main_target = set_recording(&mut volatile, main_room, StreamStatus::Paused).await;
+        let breakout_target = set_recording(
+            &mut volatile,
+            SignalingRoomId::new(room_id, Some(recording_breakout_room)),
+            StreamStatus::Active,
+        )
+        .await;
+        _ = set_recording(
+            &mut volatile,
+            SignalingRoomId::new(room_id, Some(idle_breakout_room)),
+            StreamStatus::Inactive,
+        )
+        .await;
+
+        let mut main_recorders = exchange_handle
+            .create_subscriber(vec![control::exchange::current_room_all_recorders(
+                main_room,
+            )])
+            .await
+            .unwrap();
+        let mut breakout_recorders = exchange_handle
+            .create_subscriber(vec![control::exchange::current_room_all_recorders(
+                SignalingRoomId::new(room_id, Some(recording_breakout_room)),
+            )])
+            .await
+            .unwrap();
+
+        assert!(
+            stop_recording(
+                &mut volatile,
+                &exchange_handle,
+                room_id,
+                [recording_breakout_room, idle_breakout_room],
+            )
+            .await
+            .unwrap()
+        );
+
+        assert_stop_streams(&mut main_recorders, main_target).await;
+        assert_stop_streams(&mut breakout_recorders, breakout_target).await;
+    }
+
+    #[tokio::test]
+    async fn stop_recording_only_in_breakout_room() {
+        let mut volatile = VolatileStorage::Left(VolatileStaticMemoryStorage);
+        let exchange_handle = ExchangeTask::spawn(None).await.unwrap();
+
+        let room_id = RoomId::generate();
+        let breakout_room = SignalingRoomId::new(room_id, Some(BreakoutRoomId::generate()));
+        let target_id = set_recording(&mut volatile, breakout_room, StreamStatus::Starting).await;
+
+        let mut recorders = exchange_handle
+            .create_subscriber(vec![control::exchange::current_room_all_recorders(
+                breakout_room,
+            )])
+            .await
+            .unwrap();
+
+        // Without the breakout rooms, only the idle main room is checked
+        assert!(
+            !stop_recording(&mut volatile, &exchange_handle, room_id, [])
+                .await
+                .unwrap()
+        );
+
+        assert!(
+            stop_recording(
+                &mut volatile,
+                &exchange_handle,
+                room_id,
+                breakout_room.breakout_room_id(),
+            )
+            .await
+            .unwrap()
+        );
+        assert_stop_streams(&mut recorders, target_id).await;
+    }
+
+    #[tokio::test]
+    async fn stop_recording_without_active_streams() {
+        let mut volatile = VolatileStorage::Left(VolatileStaticMemoryStorage);
+        let exchange_handle = ExchangeTask::spawn(None).await.unwrap();
+
+        let room_id = RoomId::generate();
+        let breakout_room = BreakoutRoomId::generate();
+        _ = set_recording(
+            &mut volatile,
+            SignalingRoomId::new(room_id, Some(breakout_room)),
+            StreamStatus::Inactive,
+        )
+        .await;
+
+        assert!(
+            !stop_recording(&mut volatile, &exchange_handle, room_id, [breakout_room])
+                .await
+                .unwrap()
+        );
+    }
+}
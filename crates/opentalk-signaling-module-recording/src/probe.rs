@@ -0,0 +1,71 @@
+// SPDX-FileCopyrightText: OpenTalk GmbH <mail@opentalk.eu>
+//
+// SPDX-License-Identifier: EUPL-1.2
+
+//! Pre-flight reachability check for livestream targets.
+
+use std::time::Duration;
+
+use tokio::net::TcpStream;
+use url::Url;
+
+/// Default port of the `rtmp` scheme
+const RTMP_DEFAULT_PORT: u16 = 1935;
+
+/// Default port of the `rtmps` scheme
+const RTMPS_DEFAULT_PORT: u16 = 443;
+
+/// Returns the host and port a streaming endpoint is reached at, falling back to the
+/// default port of the url scheme if none is given explicitly.
+fn target_address(endpoint: &Url) -> Option<(&str, u16)> {
+    let host = endpoint.host_str()?;
+    let port = endpoint.port().or_else(|| match endpoint.scheme() {
+        "rtmp" => Some(RTMP_DEFAULT_PORT),
+        "rtmps" => Some(RTMPS_DEFAULT_PORT),
+        _ => endpoint.port_or_known_default(),
+    })?;
+
+    Some((host, port))
+}
+
+/// Checks whether a TCP connection to the streaming endpoint can be established within `timeout`.
+pub(crate) async fn is_reachable(endpoint: &Url, timeout: Duration) -> bool {
+    let Some(address) = target_address(endpoint) else {
+        log::debug!("Streaming endpoint {endpoint} has no host or port to probe");
+        return false;
+    };
+
+    match tokio::time::timeout(timeout, TcpStream::connect(address)).await {
+        Ok(Ok(_)) => true,
+        Ok(Err(e)) => {
+            log::debug!("Failed to connect to streaming endpoint {endpoint}: {e}");
+            false
+        }
+        Err(_) => {
+            log::debug!("Connecting to streaming endpoint {endpoint} timed out");
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+    use url::Url;
+
+    use super::target_address;
+
+    #[test]
+    fn scheme_default_ports() {
+        let rtmp = Url::parse("rtmp://stream.example.com/live").unwrap();
+        let rtmps = Url::parse("rtmps://stream.example.com/live").unwrap();
+        let explicit = Url::parse("rtmp://stream.example.com:8935/live").unwrap();
+
+        assert_eq!(target_address(&rtmp), Some(("stream.example.com", 1935)));
+        assert_eq!(target_address(&rtmps), Some(("stream.example.com", 443)));
+        assert_eq!(
+            target_address(&explicit),
+            Some(("stream.example.com", 8935))
+        );
+    }
+}
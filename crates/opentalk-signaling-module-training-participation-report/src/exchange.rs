@@ -5,6 +5,7 @@
 //! Types sent inside the signaling module between different runner instances
 
 use opentalk_types_common::time::Timestamp;
+use opentalk_types_signaling::ParticipantId;
 use opentalk_types_signaling_training_participation_report::event::{
     PdfAsset, PresenceLoggingEndedReason, PresenceLoggingStartedReason,
 };
@@ -41,6 +42,10 @@ pub enum Event {
     RoomOwnerHandOver {
         /// The next checkpoint timestamp, used to determine the timer that needs to be set in the runner
         next_checkpoint: Timestamp,
+
+        /// The room owner participants which may take over, ordered by participant id. Each
+        /// candidate only takes over if none of the preceding candidates did so in time.
+        candidates: Vec<ParticipantId>,
     },
 
     /// A PDF asset has been created, all participants of the room owner are informed.
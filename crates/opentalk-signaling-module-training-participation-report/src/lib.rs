@@ -22,7 +22,8 @@
 
 use std::{
     collections::{BTreeMap, BTreeSet, HashMap},
-    path::Path,
+    num::NonZeroU32,
+    path::{Path, PathBuf},
     sync::Arc,
 };
 
@@ -31,13 +32,19 @@ use chrono::{Duration, Local, Utc};
 use chrono_tz::Tz;
 use either::Either;
 use futures::{FutureExt as _, stream::once};
-use opentalk_database::Db;
-use opentalk_db_storage::{events::EventTrainingParticipationReportParameterSet, users::User};
+use opentalk_controller_settings::{ReportsVerificationQrCode, settings_file::CheckpointJitter};
+use opentalk_database::{Db, DbConnection};
+use opentalk_db_storage::{
+    events::{Event as DbEvent, EventTrainingParticipationReportParameterSet},
+    tenants::Tenant,
+    users::User,
+};
+use opentalk_report_generation::ReportTimezoneCandidates;
 use opentalk_signaling_core::{
-    ChunkFormat, CleanupScope, DestroyContext, Event, InitContext, ModuleContext, ObjectStorage,
-    ObjectStorageError, SignalingModule, SignalingModuleError, SignalingModuleInitData,
+    CleanupScope, DestroyContext, Event, InitContext, ModuleContext, ObjectStorage,
+    PdfGenerationLimiter, SignalingModule, SignalingModuleError, SignalingModuleInitData,
     VolatileStorage,
-    assets::{AssetError, NewAssetFileName, save_asset},
+    assets::{AssetError, NewAssetFileName, save_asset_with_retry},
     control::{
         self, ControlStorageProvider,
         storage::{
@@ -47,10 +54,10 @@ use opentalk_signaling_core::{
     },
 };
 use opentalk_types_common::{
-    assets::{AssetFileKind, FileExtension, asset_file_kind},
-    events::{EventDescription, EventTitle},
+    assets::{AssetFileKind, AssetId, FileExtension, asset_file_kind},
     modules::ModuleId,
     rooms::RoomId,
+    tenants::TenantId,
     time::{TimeZone, Timestamp},
     training_participation_report::{TimeRange, TrainingParticipationReportParameterSet},
     users::{DisplayName, UserId},
@@ -66,7 +73,7 @@ use opentalk_types_signaling_training_participation_report::{
     },
     state::{ParticipationLoggingState, TrainingParticipationReportState},
 };
-use rand::Rng as _;
+use rand::Rng;
 use snafu::{Report, ResultExt as _};
 use storage::{RoomState, TrainingParticipationReportStorage, TrainingReportState};
 use template::ReportTemplateParameter;
@@ -88,19 +95,44 @@ const DEFAULT_CHECKPOINT_INTERVAL: TimeRange = TimeRange {
     within: 30 * SECONDS_PER_MINUTE,
 };
 
+/// The time a room owner hand over candidate waits for each preceding candidate to take over
+const ROOM_OWNER_HAND_OVER_WINDOW: std::time::Duration = std::time::Duration::from_secs(2);
+
 /// An event queued by the runner for itself to handle a timeout
 #[derive(Debug, PartialEq, Eq)]
-pub struct TimeoutEvent(u32);
+pub enum TimeoutEvent {
+    /// The checkpoint timer with the contained id has expired
+    Checkpoint(u32),
+
+    /// The preceding candidates of a room owner hand over did not take over in time
+    RoomOwnerHandOver {
+        /// The next checkpoint timestamp of the hand over
+        next_checkpoint: Timestamp,
+    },
+}
+
+/// The parameters of the [`TrainingParticipationReport`] module
+#[derive(Debug, Clone)]
+pub struct TrainingParticipationReportParams {
+    pdf_generation_limiter: PdfGenerationLimiter,
+    report_dump_path: Option<PathBuf>,
+    report_font_path: Option<PathBuf>,
+    verification_qr_code: Option<ReportsVerificationQrCode>,
+    tenant_timezones: BTreeMap<String, TimeZone>,
+    checkpoint_jitter: CheckpointJitter,
+}
 
 /// Signaling module for tracking participant presence during a training session
 #[derive(Debug)]
 pub struct TrainingParticipationReport {
     room: RoomId,
+    tenant: TenantId,
     owner: UserId,
     participant: ParticipantId,
     db: Arc<Db>,
     storage: Arc<ObjectStorage>,
     room_owner_data: Option<RoomOwnerData>,
+    params: TrainingParticipationReportParams,
 }
 
 #[derive(Debug, Default, Clone)]
@@ -127,7 +159,7 @@ impl TrainingParticipationReportStorageProvider for VolatileStorage {
 impl SignalingModule for TrainingParticipationReport {
     const NAMESPACE: ModuleId = MODULE_ID;
 
-    type Params = ();
+    type Params = TrainingParticipationReportParams;
 
     type Incoming = TrainingParticipationReportCommand;
 
@@ -143,11 +175,12 @@ impl SignalingModule for TrainingParticipationReport {
 
     async fn init(
         ctx: InitContext<'_, Self>,
-        _params: &Self::Params,
+        params: &Self::Params,
         _protocol: &'static str,
     ) -> Result<Option<Self>, SignalingModuleError> {
         Ok(Some(Self {
             room: ctx.room_id().room_id(),
+            tenant: ctx.room().tenant_id,
             owner: ctx.room().created_by,
             participant: ctx.participant_id(),
             db: ctx.db().clone(),
@@ -156,6 +189,7 @@ impl SignalingModule for TrainingParticipationReport {
             // is only available on join, so we will store it when the join
             // is handled.
             room_owner_data: None,
+            params: params.clone(),
         }))
     }
 
@@ -183,9 +217,13 @@ impl SignalingModule for TrainingParticipationReport {
             Event::ParticipantLeft(participant) => {
                 self.handle_participant_left(&mut ctx, participant).await?
             }
-            Event::Ext(TimeoutEvent(timeout_id)) => {
+            Event::Ext(TimeoutEvent::Checkpoint(timeout_id)) => {
                 self.handle_timeout(&mut ctx, timeout_id).await?
             }
+            Event::Ext(TimeoutEvent::RoomOwnerHandOver { next_checkpoint }) => {
+                self.handle_room_owner_hand_over_timeout(&mut ctx, next_checkpoint)
+                    .await?
+            }
             Event::Exchange(event) => self.handle_exchange_event(&mut ctx, event).await?,
             Event::Leaving => self.handle_leaving(&mut ctx).await?,
             Event::RaiseHand
@@ -205,9 +243,19 @@ impl SignalingModule for TrainingParticipationReport {
     }
 
     async fn build_params(
-        _init: SignalingModuleInitData,
+        init: SignalingModuleInitData,
     ) -> Result<Option<Self::Params>, SignalingModuleError> {
-        Ok(Some(()))
+        Ok(Some(TrainingParticipationReportParams {
+            pdf_generation_limiter: init.pdf_generation_limiter,
+            report_dump_path: init.report_dump_path,
+            report_font_path: init.report_font_path,
+            verification_qr_code: init.startup_settings.reports.verification_qr_code.clone(),
+            tenant_timezones: init.startup_settings.reports.tenant_timezones.clone(),
+            checkpoint_jitter: init
+                .startup_settings
+                .training_participation_report
+                .checkpoint_jitter,
+        }))
     }
 }
 
@@ -264,7 +312,23 @@ impl TrainingParticipationReport {
                 if room_owner_data.other_room_owners.is_empty() {
                     // this is the first trainer in the room
 
-                    if room_owner_data.trainees.is_empty() {
+                    let report_state = ctx
+                        .volatile
+                        .storage()
+                        .get_training_report_state(self.room)
+                        .await?;
+
+                    if matches!(
+                        report_state,
+                        Some(
+                            TrainingReportState::WaitingForInitialTimeout
+                                | TrainingReportState::TrackingPresence
+                        )
+                    ) {
+                        // The presence logging is still in progress, but no room owner is
+                        // handling the checkpoints, e.g. because the controller restarted
+                        self.resume_presence_logging(ctx).await?;
+                    } else if room_owner_data.trainees.is_empty() {
                         ctx.volatile
                             .storage()
                             .initialize_room(
@@ -387,11 +451,17 @@ impl TrainingParticipationReport {
             TrainingParticipationReportCommand::EnablePresenceLogging {
                 initial_checkpoint_delay,
                 checkpoint_interval,
+                trainee_threshold,
+                confirmation_grace_window,
+                anonymize,
             } => {
                 self.handle_command_enable_presence_logging(
                     ctx,
                     initial_checkpoint_delay,
                     checkpoint_interval,
+                    trainee_threshold,
+                    confirmation_grace_window,
+                    anonymize,
                 )
                 .await
             }
@@ -409,6 +479,9 @@ impl TrainingParticipationReport {
         ctx: &mut ModuleContext<'_, Self>,
         initial_checkpoint_delay: Option<TimeRange>,
         checkpoint_interval: Option<TimeRange>,
+        trainee_threshold: Option<NonZeroU32>,
+        confirmation_grace_window: Option<u64>,
+        anonymize: bool,
     ) -> Result<(), SignalingModuleError> {
         let Some(room_owner_data) = self.room_owner_data.as_mut() else {
             ctx.ws_send(TrainingParticipationReportEvent::Error(
@@ -443,7 +516,14 @@ impl TrainingParticipationReport {
         let checkpoint_interval = checkpoint_interval
             .or(parameter_set_checkpoint_interval)
             .unwrap_or(DEFAULT_CHECKPOINT_INTERVAL);
-        if room_owner_data.trainees.is_empty() {
+        if let Some(grace_window) = confirmation_grace_window {
+            storage
+                .set_confirmation_grace_window(self.room, grace_window)
+                .await?;
+        }
+        let trainee_threshold = trainee_threshold.unwrap_or(NonZeroU32::MIN);
+        if room_owner_data.trainees.len() < trainee_threshold.get() as usize {
+            // Presence logging starts as soon as enough trainees are present
             storage
                 .initialize_room(
                     self.room,
@@ -454,6 +534,12 @@ impl TrainingParticipationReport {
                     room_owner_data.trainees.clone(),
                 )
                 .await?;
+            storage
+                .set_trainee_threshold(self.room, trainee_threshold)
+                .await?;
+            if anonymize {
+                storage.set_anonymize_report(self.room).await?;
+            }
 
             ctx.exchange_publish(
                 control::exchange::global_room_by_user_id(self.room, self.owner),
@@ -472,6 +558,9 @@ impl TrainingParticipationReport {
                 room_owner_data.trainees.clone(),
             )
             .await?;
+        if anonymize {
+            storage.set_anonymize_report(self.room).await?;
+        }
         ctx.exchange_publish(
             control::exchange::global_room_by_user_id(self.room, self.owner),
             exchange::Event::PresenceLoggingEnabled,
@@ -501,6 +590,7 @@ impl TrainingParticipationReport {
             self.room,
             ctx,
             &initial_checkpoint_delay,
+            self.params.checkpoint_jitter,
         )
         .await?;
 
@@ -515,13 +605,53 @@ impl TrainingParticipationReport {
         Ok(())
     }
 
+    /// Reschedule the checkpoint timer of a presence logging session that is in progress
+    async fn resume_presence_logging(
+        &mut self,
+        ctx: &mut ModuleContext<'_, Self>,
+    ) -> Result<(), SignalingModuleError> {
+        let Some(room_owner_data) = self.room_owner_data.as_mut() else {
+            unreachable!("presence logging can only be resumed by the runner of a room owner");
+        };
+
+        match ctx
+            .volatile
+            .storage()
+            .get_next_checkpoint(self.room)
+            .await?
+        {
+            Some(next_checkpoint) => {
+                // A checkpoint that passed in the meantime is handled right away
+                Self::start_checkpoint_timer(room_owner_data, ctx, next_checkpoint);
+            }
+            None => {
+                let initial_checkpoint_delay = ctx
+                    .volatile
+                    .storage()
+                    .get_initial_checkpoint_delay(self.room)
+                    .await?;
+                let _checkpoint = Self::switch_to_next_checkpoint(
+                    room_owner_data,
+                    self.room,
+                    ctx,
+                    &initial_checkpoint_delay,
+                    self.params.checkpoint_jitter,
+                )
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+
     async fn switch_to_next_checkpoint(
         room_owner_data: &mut RoomOwnerData,
         room: RoomId,
         ctx: &mut ModuleContext<'_, Self>,
         time_range: &TimeRange,
+        jitter: CheckpointJitter,
     ) -> Result<Timestamp, SignalingModuleError> {
-        let seconds_to_wait = Self::random_waiting_duration_seconds(time_range);
+        let seconds_to_wait = random_waiting_duration_seconds(time_range, jitter, &mut rand::rng());
 
         let wait_duration = Duration::new(
             seconds_to_wait
@@ -553,7 +683,7 @@ impl TrainingParticipationReport {
             .signed_duration_since(Utc::now())
             .to_std()
             .unwrap_or_default();
-        let event = TimeoutEvent(timeout_id);
+        let event = TimeoutEvent::Checkpoint(timeout_id);
 
         ctx.add_event_stream(once(sleep(duration).map(move |_| event)));
     }
@@ -640,9 +770,6 @@ impl TrainingParticipationReport {
                 .get_training_report_state(self.room)
                 .await?;
 
-            let is_first_trainee = state == Some(TrainingReportState::WaitingForParticipant)
-                && !is_room_owner
-                && room_owner_data.trainees.is_empty();
             if is_room_owner {
                 _ = room_owner_data.other_room_owners.insert(participant);
             } else {
@@ -661,26 +788,50 @@ impl TrainingParticipationReport {
                 Some(other_room_owner) if other_room_owner > &self.participant => true,
                 Some(_) => false,
             };
-            if is_first_trainee && this_runner_is_responsible {
-                let initial_checkpoint_delay = ctx
-                    .volatile
-                    .storage()
-                    .get_initial_checkpoint_delay(self.room)
-                    .await?;
-                self.start_presence_logging(
-                    ctx,
-                    initial_checkpoint_delay,
-                    PresenceLoggingStartedReason::FirstParticipantJoined,
+            if is_room_owner
+                || state != Some(TrainingReportState::WaitingForParticipant)
+                || !this_runner_is_responsible
+            {
+                return Ok(());
+            }
+
+            let trainee_count = room_owner_data.trainees.len();
+            let trainee_threshold = ctx
+                .volatile
+                .storage()
+                .get_trainee_threshold(self.room)
+                .await?
+                .unwrap_or(NonZeroU32::MIN);
+            if trainee_count < trainee_threshold.get() as usize {
+                return Ok(());
+            }
+
+            // Only start once, even if another runner considers itself responsible at the same time
+            if !ctx
+                .volatile
+                .storage()
+                .transition_training_report_state(
+                    self.room,
+                    TrainingReportState::WaitingForParticipant,
+                    TrainingReportState::WaitingForInitialTimeout,
                 )
-                .await?;
-                ctx.volatile
-                    .storage()
-                    .set_training_report_state(
-                        self.room,
-                        TrainingReportState::WaitingForInitialTimeout,
-                    )
-                    .await?;
+                .await?
+            {
+                return Ok(());
             }
+
+            let initial_checkpoint_delay = ctx
+                .volatile
+                .storage()
+                .get_initial_checkpoint_delay(self.room)
+                .await?;
+            let reason = if trainee_threshold == NonZeroU32::MIN {
+                PresenceLoggingStartedReason::FirstParticipantJoined
+            } else {
+                PresenceLoggingStartedReason::TraineeThresholdReached
+            };
+            self.start_presence_logging(ctx, initial_checkpoint_delay, reason)
+                .await?;
         }
         Ok(())
     }
@@ -730,12 +881,13 @@ impl TrainingParticipationReport {
             start,
             initial_checkpoint_delay,
             checkpoint_interval,
+            anonymize,
             ..
         } = room_state;
         let report_state = TrainingReportState::WaitingForParticipant;
 
-        ctx.volatile
-            .storage()
+        let storage = ctx.volatile.storage();
+        storage
             .initialize_room(
                 self.room,
                 start,
@@ -745,21 +897,13 @@ impl TrainingParticipationReport {
                 known_participants,
             )
             .await?;
+        if anonymize {
+            storage.set_anonymize_report(self.room).await?;
+        }
 
         Ok(())
     }
 
-    fn random_waiting_duration_seconds(range: &TimeRange) -> u64 {
-        let offset = if range.within == 0 {
-            0
-        } else {
-            let mut rng = rand::rng();
-            rng.random_range(0..range.within)
-        };
-        const MAXIMUM_ALLOWED: u64 = i64::MAX as u64;
-        range.after.saturating_add(offset).min(MAXIMUM_ALLOWED)
-    }
-
     async fn handle_timeout(
         &mut self,
         ctx: &mut ModuleContext<'_, Self>,
@@ -796,8 +940,14 @@ impl TrainingParticipationReport {
             .storage()
             .set_training_report_state(self.room, TrainingReportState::TrackingPresence)
             .await?;
-        let _checkpoint =
-            Self::switch_to_next_checkpoint(room_owner_data, self.room, ctx, &time_range).await?;
+        let _checkpoint = Self::switch_to_next_checkpoint(
+            room_owner_data,
+            self.room,
+            ctx,
+            &time_range,
+            self.params.checkpoint_jitter,
+        )
+        .await?;
 
         ctx.exchange_publish(
             control::exchange::global_room_all_participants(self.room),
@@ -806,6 +956,49 @@ impl TrainingParticipationReport {
         Ok(())
     }
 
+    async fn handle_room_owner_hand_over_timeout(
+        &mut self,
+        ctx: &mut ModuleContext<'_, Self>,
+        next_checkpoint: Timestamp,
+    ) -> Result<(), SignalingModuleError> {
+        if matches!(
+            ctx.volatile
+                .storage()
+                .get_training_report_state(self.room)
+                .await?,
+            None | Some(TrainingReportState::WaitingForParticipant),
+        ) {
+            // Presence logging has stopped in the meantime, nothing to take over.
+            return Ok(());
+        }
+
+        self.take_over_checkpoint_handling(ctx, next_checkpoint)
+            .await
+    }
+
+    async fn take_over_checkpoint_handling(
+        &mut self,
+        ctx: &mut ModuleContext<'_, Self>,
+        next_checkpoint: Timestamp,
+    ) -> Result<(), SignalingModuleError> {
+        let Some(room_owner_data) = self.room_owner_data.as_mut() else {
+            return Ok(());
+        };
+
+        if !ctx
+            .volatile
+            .storage()
+            .claim_room_owner_hand_over(self.room, self.participant)
+            .await?
+        {
+            // A preceding candidate has taken over already
+            return Ok(());
+        }
+
+        Self::start_checkpoint_timer(room_owner_data, ctx, next_checkpoint);
+        Ok(())
+    }
+
     async fn handle_exchange_event(
         &mut self,
         ctx: &mut ModuleContext<'_, Self>,
@@ -848,12 +1041,25 @@ impl TrainingParticipationReport {
                 }
                 Ok(())
             }
-            exchange::Event::RoomOwnerHandOver { next_checkpoint } => {
-                let Some(room_owner_data) = self.room_owner_data.as_mut() else {
+            exchange::Event::RoomOwnerHandOver {
+                next_checkpoint,
+                candidates,
+            } => {
+                if self.room_owner_data.is_none() {
                     return Ok(());
-                };
-                Self::start_checkpoint_timer(room_owner_data, ctx, next_checkpoint);
-                Ok(())
+                }
+                match room_owner_hand_over_delay(&candidates, self.participant) {
+                    None => Ok(()),
+                    Some(delay) if delay.is_zero() => {
+                        self.take_over_checkpoint_handling(ctx, next_checkpoint)
+                            .await
+                    }
+                    Some(delay) => {
+                        let event = TimeoutEvent::RoomOwnerHandOver { next_checkpoint };
+                        ctx.add_event_stream(once(sleep(delay).map(move |_| event)));
+                        Ok(())
+                    }
+                }
             }
             exchange::Event::PdfAsset(pdf_asset) => {
                 ctx.ws_send(pdf_asset);
@@ -911,7 +1117,7 @@ impl TrainingParticipationReport {
                         }
                         return Ok(());
                     }
-                    Some(room_owner_id) => {
+                    Some(_) => {
                         // At least one other room owner participant is present.
 
                         if room_owner_data.timeout_id.is_none() {
@@ -920,7 +1126,9 @@ impl TrainingParticipationReport {
                         };
 
                         // Let's hand over the responsibility for checkpoint
-                        // handling to the next runner.
+                        // handling to the next runner. All other room owner
+                        // participants are candidates, because the first one
+                        // might be leaving at the same time.
 
                         let Some(next_checkpoint) = ctx
                             .volatile
@@ -931,13 +1139,24 @@ impl TrainingParticipationReport {
                             return Ok(());
                         };
 
-                        ctx.exchange_publish(
-                            control::exchange::global_room_by_participant_id(
-                                self.room,
-                                *room_owner_id,
-                            ),
-                            exchange::Event::RoomOwnerHandOver { next_checkpoint },
-                        );
+                        ctx.volatile
+                            .storage()
+                            .reset_room_owner_hand_over(self.room)
+                            .await?;
+
+                        let candidates =
+                            Vec::from_iter(room_owner_data.other_room_owners.iter().copied());
+                        for candidate in &candidates {
+                            ctx.exchange_publish(
+                                control::exchange::global_room_by_participant_id(
+                                    self.room, *candidate,
+                                ),
+                                exchange::Event::RoomOwnerHandOver {
+                                    next_checkpoint,
+                                    candidates: candidates.clone(),
+                                },
+                            );
+                        }
                     }
                 }
             }
@@ -951,14 +1170,13 @@ impl TrainingParticipationReport {
         room_state: RoomState,
     ) -> Result<(), SignalingModuleError> {
         let mut conn = self.db.get_conn().await?;
-        let event = opentalk_db_storage::events::Event::get_for_room(&mut conn, self.room)
-            .await?
-            .ok_or(SignalingModuleError::NotFoundError {
+        let event = DbEvent::get_for_room(&mut conn, self.room).await?.ok_or(
+            SignalingModuleError::NotFoundError {
                 message: "Event for room not found".to_string(),
-            })?;
+            },
+        )?;
 
-        let event_creator = User::get(&mut conn, event.created_by).await?;
-        let timezone = event_creator.timezone.unwrap_or(TimeZone::from(Tz::UTC));
+        let timezone = TimeZone::from(self.report_timezone(&mut conn, &event).await?);
 
         let required_participants = Vec::from_iter(room_state.known_participants.clone());
 
@@ -972,90 +1190,168 @@ impl TrainingParticipationReport {
             .zip(display_names)
             .collect();
 
-        let report = Self::generate_pdf_report(
-            DEFAULT_TEMPLATE.to_string(),
-            room_state,
-            timezone,
-            participants,
-            event.title,
-            event.description,
-            ctx.timestamp,
-        )
-        .await
-        .with_whatever_context::<_, _, SignalingModuleError>(|_| {
+        // The asset id is reserved up front, so that the report can link to its own asset
+        let asset_id = AssetId::generate();
+        let verification_url = self.verification_url(&mut conn, asset_id).await?;
+
+        let report = self
+            .params
+            .pdf_generation_limiter
+            .run(Self::generate_pdf_report(
+                DEFAULT_TEMPLATE.to_string(),
+                room_state,
+                timezone,
+                participants,
+                event,
+                ctx.timestamp,
+                self.params.report_dump_path.as_deref(),
+                self.params.report_font_path.as_deref(),
+                verification_url,
+            ))
+            .await;
+        if let Some(metrics) = ctx.metrics() {
+            metrics.record_report_generation(&MODULE_ID, report.is_ok());
+        }
+        let report = report.with_whatever_context::<_, _, SignalingModuleError>(|_| {
             ctx.ws_send(Error::Generate);
             "Failed to create pdf"
         })?;
-        self.upload_pdf(report, ctx).await;
+        self.upload_pdf(report, asset_id, ctx).await;
 
         Ok(())
     }
 
+    /// Resolve the timezone of the report from the event, the event creator and the default of
+    /// the tenant
+    async fn report_timezone(
+        &self,
+        conn: &mut DbConnection,
+        event: &DbEvent,
+    ) -> Result<Tz, SignalingModuleError> {
+        let event_creator = User::get(conn, event.created_by).await?;
+        let tenant = Tenant::get(conn, self.tenant).await?;
+        let tenant_default = self
+            .params
+            .tenant_timezones
+            .get(&tenant.oidc_tenant_id.to_string())
+            .copied();
+
+        Ok(ReportTimezoneCandidates {
+            requested: None,
+            event: event.starts_at_tz.map(Tz::from),
+            user: event_creator.timezone.map(Tz::from),
+            tenant_default: tenant_default.map(Tz::from),
+        }
+        .resolve())
+    }
+
+    /// The url for the verification QR code of the report stored under `asset_id`, if the QR
+    /// code is enabled for the tenant of the room
+    async fn verification_url(
+        &self,
+        conn: &mut DbConnection,
+        asset_id: AssetId,
+    ) -> Result<Option<String>, SignalingModuleError> {
+        let Some(qr_code) = &self.params.verification_qr_code else {
+            return Ok(None);
+        };
+
+        let tenant = Tenant::get(conn, self.tenant).await?;
+        if !qr_code.is_enabled_for(&tenant.oidc_tenant_id.to_string()) {
+            return Ok(None);
+        }
+
+        Ok(Some(qr_code.url_for(self.room, asset_id)))
+    }
+
+    #[allow(clippy::too_many_arguments)]
     async fn generate_pdf_report(
         template: String,
         room_state: RoomState,
         report_timezone: TimeZone,
         participants: BTreeMap<ParticipantId, Option<DisplayName>>,
-        title: EventTitle,
-        description: EventDescription,
+        event: DbEvent,
         end: Timestamp,
+        dump_path: Option<&Path>,
+        font_path: Option<&Path>,
+        verification_url: Option<String>,
     ) -> Result<Vec<u8>, SignalingModuleError> {
         let timestamp = Local::now().naive_local().format("%Y-%m-%dT%H:%M:%S.%f");
         let report_tz = Tz::from(report_timezone);
 
+        let mut parameter = ReportTemplateParameter::build(
+            &room_state,
+            &report_tz,
+            participants,
+            event.title,
+            event.description,
+            end,
+        );
+        parameter.verification_url = verification_url;
+
         Self::generate_pdf_report_from_template(
             template,
-            &ReportTemplateParameter::build(
-                &room_state,
-                &report_tz,
-                participants,
-                title,
-                description,
-                end,
-            ),
+            &parameter,
+            dump_path,
             Path::new(&format!("{MODULE_ID}/{timestamp}")),
+            font_path,
         )
     }
 
     fn generate_pdf_report_from_template(
         template: String,
         parameter: &ReportTemplateParameter,
+        dump_path: Option<&Path>,
         dump_to_relative_path: &Path,
+        font_path: Option<&Path>,
     ) -> Result<Vec<u8>, SignalingModuleError> {
-        let dump_to_path = std::env::var("OPENTALK_REPORT_DUMP_PATH")
-            .map(|p| Path::new(&p).join(dump_to_relative_path))
-            .ok();
+        let dump_to_path = dump_path.map(|p| p.join(dump_to_relative_path));
+
+        let mut files = BTreeMap::from_iter([(
+            Path::new("data.json"),
+            serde_json::to_string_pretty(parameter)
+                .unwrap()
+                .into_bytes()
+                .into(),
+        )]);
+        if let Some(verification_url) = &parameter.verification_url {
+            let qr_code = opentalk_report_generation::verification_qr_code(verification_url)
+                .whatever_context::<_, SignalingModuleError>(
+                "unable to create verification QR code",
+            )?;
+            files.insert(
+                Path::new(opentalk_report_generation::VERIFICATION_QR_CODE_FILE),
+                qr_code.into(),
+            );
+        }
 
         let pdf = opentalk_report_generation::generate_pdf_report(
             template,
-            BTreeMap::from_iter([(
-                Path::new("data.json"),
-                serde_json::to_string_pretty(parameter)
-                    .unwrap()
-                    .into_bytes()
-                    .into(),
-            )]),
+            files,
             dump_to_path.as_deref(),
+            font_path,
         )
         .whatever_context::<_, SignalingModuleError>("unable to build pdf")?;
         Ok(pdf)
     }
 
-    async fn upload_pdf(&mut self, report: Vec<u8>, ctx: &mut ModuleContext<'_, Self>) {
+    async fn upload_pdf(
+        &mut self,
+        report: Vec<u8>,
+        asset_id: AssetId,
+        ctx: &mut ModuleContext<'_, Self>,
+    ) {
         const ASSET_FILE_KIND: AssetFileKind = asset_file_kind!("training_participation_report");
         let file_name =
             NewAssetFileName::new(ASSET_FILE_KIND, Timestamp::now(), FileExtension::pdf());
-        let report =
-            async_stream::stream!(yield Result::<_, ObjectStorageError>::Ok(Bytes::from(report)));
-        let report = Box::pin(report);
-        let result = save_asset(
+        let result = save_asset_with_retry(
             &self.storage,
             self.db.clone(),
             self.room,
             Some(Self::NAMESPACE),
+            asset_id,
             file_name,
-            report,
-            ChunkFormat::Data,
+            Bytes::from(report),
         )
         .await;
 
@@ -1136,21 +1432,79 @@ impl TrainingParticipationReport {
     }
 }
 
+/// The time `participant` waits before taking over a room owner hand over, or `None` if it is
+/// not a candidate of the hand over.
+fn room_owner_hand_over_delay(
+    candidates: &[ParticipantId],
+    participant: ParticipantId,
+) -> Option<std::time::Duration> {
+    let position = candidates
+        .iter()
+        .position(|candidate| *candidate == participant)?;
+    Some(ROOM_OWNER_HAND_OVER_WINDOW.saturating_mul(u32::try_from(position).unwrap_or(u32::MAX)))
+}
+
+/// Pick the number of seconds to wait for the next checkpoint
+///
+/// The result lies within `range.after..=range.after + range.within`, saturated at `i64::MAX`
+/// so that it can always be converted into a [`Duration`]. A `within` of zero yields exactly
+/// `range.after`.
+fn random_waiting_duration_seconds<R: Rng + ?Sized>(
+    range: &TimeRange,
+    jitter: CheckpointJitter,
+    rng: &mut R,
+) -> u64 {
+    const MAXIMUM_ALLOWED: u64 = i64::MAX as u64;
+
+    let offset = match jitter {
+        CheckpointJitter::Uniform => rng.random_range(0..=range.within),
+        // The smaller of two uniform samples, the probability decreases linearly towards the
+        // end of the range
+        CheckpointJitter::FrontLoaded => rng
+            .random_range(0..=range.within)
+            .min(rng.random_range(0..=range.within)),
+    };
+
+    range.after.saturating_add(offset).min(MAXIMUM_ALLOWED)
+}
+
 #[cfg(test)]
 mod tests {
-    use std::path::Path;
+    use std::{collections::BTreeSet, path::Path};
 
     use insta::assert_snapshot;
 
+    use opentalk_controller_settings::settings_file::CheckpointJitter;
+    use opentalk_signaling_core::{
+        PdfGenerationLimiter,
+        module_tester::{ModuleTester, WsMessageOutgoing},
+    };
+    use opentalk_test_util::{
+        ROOM_ID, TestContext, USER_1, USER_2, common::TestContextVolatileStorage,
+    };
+    use opentalk_types_common::{
+        time::Timestamp,
+        training_participation_report::{TimeRange, TrainingParticipationReportParameterSet},
+    };
+    use opentalk_types_signaling::{ParticipantId, Role};
+    use opentalk_types_signaling_training_participation_report::event::TrainingParticipationReportEvent;
+    use rand::{Rng as _, SeedableRng as _, rngs::StdRng};
+    use serial_test::serial;
+
     use crate::{
-        DEFAULT_TEMPLATE, MODULE_ID, TrainingParticipationReport, template::ReportTemplateParameter,
+        DEFAULT_TEMPLATE, MODULE_ID, ROOM_OWNER_HAND_OVER_WINDOW, TrainingParticipationReport,
+        TrainingParticipationReportParams, TrainingParticipationReportStorageProvider as _,
+        random_waiting_duration_seconds, room_owner_hand_over_delay, storage::TrainingReportState,
+        template::ReportTemplateParameter,
     };
 
     fn generate(sample_name: &str, parameter: &ReportTemplateParameter) -> String {
         let pdf = TrainingParticipationReport::generate_pdf_report_from_template(
             DEFAULT_TEMPLATE.to_string(),
             parameter,
+            opentalk_report_generation::resolve_dump_path(None).as_deref(),
             Path::new(&format!("{MODULE_ID}/{sample_name}")),
+            None,
         )
         .expect("generation should work");
         pdf_extract::extract_text_from_mem(&pdf)
@@ -1186,6 +1540,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn generate_report_with_verification_qr_code() {
+        let mut parameter = crate::template::tests::example_small();
+        parameter.verification_url = Some("https://opentalk.example.org/verify/42".to_string());
+
+        let text = generate("small_verification_qr_code", &parameter);
+
+        assert!(text.contains("Verification"));
+        assert!(text.contains("https://opentalk.example.org/verify/42"));
+    }
+
+    #[test]
+    fn generate_report_small_anonymized() {
+        assert_snapshot!(
+            generate(
+                "small_anonymized",
+                &crate::template::tests::example_small().anonymized()
+            ),
+            @r#"
+        Training participation report
+         Meeting: OpenTalk introduction training
+
+        Description: —
+
+        Report timezone: Europe/Berlin
+
+        Training start: 2025-02-18 09:01
+
+        Training end: 2025-02-18 13:32
+
+        Participation checkpoints
+         № Participant 09:22 11:22 13:19
+
+        1 Participant 1 09:22 11:25 —
+
+        2 Participant 2 09:22 11:25 13:19
+        "#
+        );
+    }
+
     #[test]
     fn generate_report_medium() {
         assert_snapshot!(
@@ -1353,4 +1747,227 @@ mod tests {
         "#
         );
     }
+
+    #[test]
+    fn room_owner_hand_over_candidates_wait_for_preceding_candidates() {
+        let bob = ParticipantId::from_u128(1);
+        let charlie = ParticipantId::from_u128(2);
+        let dave = ParticipantId::from_u128(3);
+        let candidates = [bob, charlie];
+
+        // Bob leaves at the same time as the room owner handing over, so Charlie must only wait
+        // for Bob's window before taking over
+        assert_eq!(
+            room_owner_hand_over_delay(&candidates, bob),
+            Some(std::time::Duration::ZERO)
+        );
+        assert_eq!(
+            room_owner_hand_over_delay(&candidates, charlie),
+            Some(ROOM_OWNER_HAND_OVER_WINDOW)
+        );
+        assert_eq!(room_owner_hand_over_delay(&candidates, dave), None);
+    }
+
+    const EXTREMES: [u64; 6] = [
+        0,
+        1,
+        i64::MAX as u64 - 1,
+        i64::MAX as u64,
+        u64::MAX - 1,
+        u64::MAX,
+    ];
+
+    fn assert_within_range(range: &TimeRange, jitter: CheckpointJitter, rng: &mut StdRng) {
+        const MAXIMUM_ALLOWED: u64 = i64::MAX as u64;
+
+        let lower = range.after.min(MAXIMUM_ALLOWED);
+        let upper = range
+            .after
+            .saturating_add(range.within)
+            .min(MAXIMUM_ALLOWED);
+
+        let seconds = random_waiting_duration_seconds(range, jitter, rng);
+        assert!(
+            (lower..=upper).contains(&seconds),
+            "{seconds} is not within {lower}..={upper} for {range:?} with {jitter:?}"
+        );
+    }
+
+    #[test]
+    fn waiting_duration_stays_within_range() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for jitter in [CheckpointJitter::Uniform, CheckpointJitter::FrontLoaded] {
+            for after in EXTREMES {
+                for within in EXTREMES {
+                    assert_within_range(&TimeRange { after, within }, jitter, &mut rng);
+                }
+            }
+
+            for _ in 0..10_000 {
+                let range = TimeRange {
+                    after: rng.random_range(0..=u64::MAX >> rng.random_range(0..64)),
+                    within: rng.random_range(0..=u64::MAX >> rng.random_range(0..64)),
+                };
+                assert_within_range(&range, jitter, &mut rng);
+            }
+        }
+    }
+
+    #[test]
+    fn waiting_duration_without_jitter_is_exact() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        for jitter in [CheckpointJitter::Uniform, CheckpointJitter::FrontLoaded] {
+            for after in EXTREMES {
+                assert_eq!(
+                    random_waiting_duration_seconds(
+                        &TimeRange { after, within: 0 },
+                        jitter,
+                        &mut rng
+                    ),
+                    after.min(i64::MAX as u64)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn front_loaded_waiting_duration_clusters_earlier() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let range = TimeRange {
+            after: 0,
+            within: 1000,
+        };
+
+        let mut mean = |jitter| {
+            (0..10_000)
+                .map(|_| random_waiting_duration_seconds(&range, jitter, &mut rng))
+                .sum::<u64>()
+                / 10_000
+        };
+
+        // The expected mean is 500 for the uniform and 333 for the front loaded distribution
+        assert!((450..=550).contains(&mean(CheckpointJitter::Uniform)));
+        assert!((283..=383).contains(&mean(CheckpointJitter::FrontLoaded)));
+    }
+
+    #[actix_rt::test]
+    #[serial]
+    async fn resume_pending_checkpoint_after_restart() {
+        let test_ctx = TestContext::new(TestContextVolatileStorage::Memory).await;
+        let user1 = test_ctx
+            .db_ctx
+            .create_test_user(USER_1.n, vec![])
+            .await
+            .unwrap();
+        let user2 = test_ctx
+            .db_ctx
+            .create_test_user(USER_2.n, vec![])
+            .await
+            .unwrap();
+        let room = test_ctx
+            .db_ctx
+            .create_test_room(ROOM_ID, user1.id, false)
+            .await
+            .unwrap();
+
+        // The presence logging state left behind by the previous controller instance, with a
+        // checkpoint that became due while the controller was down
+        let mut volatile = test_ctx.volatile.clone();
+        let storage = volatile.storage();
+        let time_range = TimeRange {
+            after: 600,
+            within: 0,
+        };
+        storage
+            .set_parameter_set(
+                ROOM_ID,
+                TrainingParticipationReportParameterSet {
+                    initial_checkpoint_delay: time_range.clone(),
+                    checkpoint_interval: time_range.clone(),
+                },
+            )
+            .await
+            .unwrap();
+        storage
+            .set_parameter_set_initialized(ROOM_ID)
+            .await
+            .unwrap();
+        storage
+            .initialize_room(
+                ROOM_ID,
+                Timestamp::now(),
+                TrainingReportState::TrackingPresence,
+                time_range.clone(),
+                time_range,
+                BTreeSet::from([USER_2.participant_id]),
+            )
+            .await
+            .unwrap();
+        let pending_checkpoint = Timestamp::now();
+        storage
+            .switch_to_next_checkpoint(ROOM_ID, pending_checkpoint)
+            .await
+            .unwrap();
+
+        let params = TrainingParticipationReportParams {
+            pdf_generation_limiter: PdfGenerationLimiter::default(),
+            report_dump_path: None,
+            report_font_path: None,
+            verification_qr_code: None,
+            tenant_timezones: BTreeMap::new(),
+            checkpoint_jitter: CheckpointJitter::Uniform,
+        };
+        let mut module_tester = ModuleTester::<TrainingParticipationReport>::new(
+            test_ctx.db_ctx.db.clone(),
+            test_ctx.authz.clone(),
+            test_ctx.volatile.clone(),
+            room,
+        );
+
+        module_tester
+            .join_user(
+                USER_2.participant_id,
+                user2,
+                Role::User,
+                &USER_2.name.parse().unwrap(),
+                params.clone(),
+            )
+            .await
+            .unwrap();
+        module_tester
+            .join_user(
+                USER_1.participant_id,
+                user1,
+                Role::Moderator,
+                &USER_1.name.parse().unwrap(),
+                params,
+            )
+            .await
+            .unwrap();
+
+        // The room owner reschedules the pending checkpoint, which is handled right away
+        loop {
+            if let WsMessageOutgoing::Module(
+                TrainingParticipationReportEvent::PresenceConfirmationRequested,
+            ) = module_tester
+                .receive_ws_message(&USER_2.participant_id)
+                .await
+                .unwrap()
+            {
+                break;
+            }
+        }
+
+        let next_checkpoint = volatile
+            .storage()
+            .get_next_checkpoint(ROOM_ID)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(*next_checkpoint > *pending_checkpoint);
+
+        module_tester.shutdown().await.unwrap();
+    }
 }
@@ -2,8 +2,9 @@
 //
 // SPDX-License-Identifier: EUPL-1.2
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
+use chrono::Duration;
 use opentalk_types_common::time::Timestamp;
 use opentalk_types_signaling::ParticipantId;
 
@@ -11,4 +12,26 @@ use opentalk_types_signaling::ParticipantId;
 pub(crate) struct Checkpoint {
     pub timestamp: Timestamp,
     pub presence: BTreeMap<ParticipantId, Timestamp>,
+    /// Participants from `presence` which confirmed after the grace window had passed
+    pub late: BTreeSet<ParticipantId>,
+}
+
+/// Whether a presence confirmation at `confirmation` is later than `grace_window` seconds after
+/// the `checkpoint` at which it was requested
+///
+/// Confirmations are never late if no grace window is set.
+pub(crate) fn is_late_confirmation(
+    checkpoint: Timestamp,
+    confirmation: Timestamp,
+    grace_window: Option<u64>,
+) -> bool {
+    let Some(grace_window) = grace_window else {
+        return false;
+    };
+    let grace_window = i64::try_from(grace_window)
+        .ok()
+        .and_then(Duration::try_seconds)
+        .unwrap_or(Duration::MAX);
+
+    confirmation.signed_duration_since(*checkpoint) > grace_window
 }
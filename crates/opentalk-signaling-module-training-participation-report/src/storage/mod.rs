@@ -9,14 +9,17 @@ mod training_participation_report_storage;
 mod training_report_state;
 mod volatile;
 
-pub(crate) use checkpoint::Checkpoint;
+pub(crate) use checkpoint::{Checkpoint, is_late_confirmation};
 pub(crate) use room_state::RoomState;
 pub(crate) use training_participation_report_storage::TrainingParticipationReportStorage;
 pub(crate) use training_report_state::TrainingReportState;
 
 #[cfg(test)]
 mod test_common {
-    use std::collections::{BTreeMap, BTreeSet};
+    use std::{
+        collections::{BTreeMap, BTreeSet},
+        num::NonZeroU32,
+    };
 
     use opentalk_signaling_core::SignalingModuleError;
     use opentalk_types_common::{
@@ -125,11 +128,34 @@ mod test_common {
                 history: vec![],
                 next_checkpoint: None,
                 known_participants,
+                anonymize: false,
             })
         );
         assert_eq!(storage.cleanup_room(room).await.unwrap(), None);
     }
 
+    pub(super) async fn anonymize_report(storage: &mut dyn TrainingParticipationReportStorage) {
+        let room = RoomId::generate();
+
+        initialize_room_example(storage, room).await.unwrap();
+        storage.set_anonymize_report(room).await.unwrap();
+
+        let room_state = storage
+            .cleanup_room(room)
+            .await
+            .unwrap()
+            .expect("room state must be present");
+        assert!(room_state.anonymize);
+
+        initialize_room_example(storage, room).await.unwrap();
+        let room_state = storage
+            .cleanup_room(room)
+            .await
+            .unwrap()
+            .expect("room state must be present");
+        assert!(!room_state.anonymize);
+    }
+
     async fn initialize_room_example(
         storage: &mut dyn TrainingParticipationReportStorage,
         room: RoomId,
@@ -199,6 +225,75 @@ mod test_common {
         assert_eq!(storage.get_training_report_state(room).await.unwrap(), None);
     }
 
+    pub(super) async fn transition_training_report_state(
+        storage: &mut dyn TrainingParticipationReportStorage,
+    ) {
+        let room = RoomId::generate();
+        assert!(
+            !storage
+                .transition_training_report_state(
+                    room,
+                    TrainingReportState::WaitingForInitialTimeout,
+                    TrainingReportState::TrackingPresence
+                )
+                .await
+                .unwrap()
+        );
+
+        initialize_room_example(storage, room).await.unwrap();
+
+        assert!(
+            !storage
+                .transition_training_report_state(
+                    room,
+                    TrainingReportState::WaitingForParticipant,
+                    TrainingReportState::TrackingPresence
+                )
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            storage.get_training_report_state(room).await.unwrap(),
+            Some(TrainingReportState::WaitingForInitialTimeout)
+        );
+
+        assert!(
+            storage
+                .transition_training_report_state(
+                    room,
+                    TrainingReportState::WaitingForInitialTimeout,
+                    TrainingReportState::TrackingPresence
+                )
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            storage.get_training_report_state(room).await.unwrap(),
+            Some(TrainingReportState::TrackingPresence)
+        );
+
+        _ = storage.cleanup_room(room).await.unwrap();
+    }
+
+    pub(super) async fn trainee_threshold(storage: &mut dyn TrainingParticipationReportStorage) {
+        let room = RoomId::generate();
+        assert_eq!(storage.get_trainee_threshold(room).await.unwrap(), None);
+
+        initialize_room_example(storage, room).await.unwrap();
+        let threshold = NonZeroU32::new(3).expect("value must be non-zero");
+        storage
+            .set_trainee_threshold(room, threshold)
+            .await
+            .unwrap();
+        assert_eq!(
+            storage.get_trainee_threshold(room).await.unwrap(),
+            Some(threshold)
+        );
+
+        _ = storage.cleanup_room(room).await.unwrap();
+        assert_eq!(storage.get_trainee_threshold(room).await.unwrap(), None);
+    }
+
     pub(super) async fn get_initial_checkpoint_delay(
         storage: &mut dyn TrainingParticipationReportStorage,
     ) {
@@ -451,11 +546,13 @@ mod test_common {
                 vec![
                     Checkpoint {
                         timestamp: checkpoint1,
-                        presence: BTreeMap::from_iter([(ALICE, recorded1a), (BOB, recorded1b),])
+                        presence: BTreeMap::from_iter([(ALICE, recorded1a), (BOB, recorded1b),]),
+                        late: BTreeSet::new(),
                     },
                     Checkpoint {
                         timestamp: checkpoint2,
-                        presence: BTreeMap::from_iter([(BOB, recorded2b),])
+                        presence: BTreeMap::from_iter([(BOB, recorded2b),]),
+                        late: BTreeSet::new(),
                     },
                 ]
             );
@@ -474,4 +571,98 @@ mod test_common {
             ParticipationLoggingState::Disabled
         );
     }
+
+    pub(super) async fn room_owner_hand_over(storage: &mut dyn TrainingParticipationReportStorage) {
+        let room = RoomId::generate();
+
+        initialize_room_example(storage, room).await.unwrap();
+
+        // The responsible room owner leaves and hands over to Bob and Charlie. Bob leaves at the
+        // same time and never claims the hand over, so Charlie takes over after waiting for Bob.
+        storage.reset_room_owner_hand_over(room).await.unwrap();
+        assert!(
+            storage
+                .claim_room_owner_hand_over(room, CHARLIE)
+                .await
+                .unwrap()
+        );
+        assert!(!storage.claim_room_owner_hand_over(room, BOB).await.unwrap());
+
+        // Charlie leaves later on and hands over to Alice
+        storage.reset_room_owner_hand_over(room).await.unwrap();
+        assert!(
+            storage
+                .claim_room_owner_hand_over(room, ALICE)
+                .await
+                .unwrap()
+        );
+
+        // Claims are removed together with the room
+        _ = storage.cleanup_room(room).await.unwrap();
+        assert!(storage.claim_room_owner_hand_over(room, BOB).await.unwrap());
+        storage.reset_room_owner_hand_over(room).await.unwrap();
+    }
+
+    pub(super) async fn record_presence_with_grace_window(
+        storage: &mut dyn TrainingParticipationReportStorage,
+    ) {
+        let room = RoomId::generate();
+
+        let checkpoint = "2025-02-03T01:00:00Z"
+            .parse()
+            .expect("value must be parsable as Timestamp");
+        let on_time = "2025-02-03T01:01:00Z"
+            .parse()
+            .expect("value must be parsable as Timestamp");
+        let late = "2025-02-03T01:05:00Z"
+            .parse()
+            .expect("value must be parsable as Timestamp");
+        let next_checkpoint = "2025-02-03T02:00:00Z"
+            .parse()
+            .expect("value must be parsable as Timestamp");
+
+        initialize_room_example(storage, room).await.unwrap();
+        storage
+            .set_confirmation_grace_window(room, 120)
+            .await
+            .unwrap();
+        storage
+            .set_training_report_state(room, TrainingReportState::TrackingPresence)
+            .await
+            .unwrap();
+        storage
+            .switch_to_next_checkpoint(room, checkpoint)
+            .await
+            .unwrap();
+        storage
+            .switch_to_next_checkpoint(room, next_checkpoint)
+            .await
+            .unwrap();
+
+        // Alice confirms within the grace window, Bob after it and Charlie not at all
+        storage
+            .record_presence_confirmation(room, ALICE, on_time)
+            .await
+            .unwrap();
+        storage
+            .record_presence_confirmation(room, BOB, late)
+            .await
+            .unwrap();
+
+        let room_state = storage
+            .cleanup_room(room)
+            .await
+            .unwrap()
+            .expect("room state must be present");
+
+        assert_eq!(
+            room_state.history,
+            vec![Checkpoint {
+                timestamp: checkpoint,
+                presence: BTreeMap::from_iter([(ALICE, on_time), (BOB, late)]),
+                late: BTreeSet::from_iter([BOB]),
+            }]
+        );
+        assert!(!room_state.history[0].presence.contains_key(&CHARLIE));
+    }
 }
@@ -2,7 +2,10 @@
 //
 // SPDX-License-Identifier: EUPL-1.2
 
-use std::collections::{BTreeMap, BTreeSet};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    num::NonZeroU32,
+};
 
 use async_trait::async_trait;
 use opentalk_signaling_core::{NotFoundSnafu, RedisConnection, RedisSnafu, SignalingModuleError};
@@ -18,7 +21,10 @@ use redis_args::{FromRedisValue, ToRedisArgs};
 use serde::{Deserialize, Serialize};
 use snafu::{OptionExt as _, ResultExt as _, ensure_whatever, whatever};
 
-use super::{Checkpoint, RoomState, TrainingParticipationReportStorage, TrainingReportState};
+use super::{
+    Checkpoint, RoomState, TrainingParticipationReportStorage, TrainingReportState,
+    is_late_confirmation,
+};
 
 #[async_trait(?Send)]
 impl TrainingParticipationReportStorage for RedisConnection {
@@ -137,6 +143,10 @@ impl TrainingParticipationReportStorage for RedisConnection {
             .del(KnownParticipantsKey { room })
             .lrange(CheckpointEntriesKey { room }, 0, -1)
             .del(CheckpointEntriesKey { room })
+            .del(TraineeThresholdKey { room })
+            .del(ConfirmationGraceWindowKey { room })
+            .get_del(AnonymizeReportKey { room })
+            .del(RoomOwnerHandOverKey { room })
             .query_async::<(
                 _,
                 _,
@@ -145,6 +155,10 @@ impl TrainingParticipationReportStorage for RedisConnection {
                 (),
                 Vec<CheckpointEntry>,
                 (),
+                (),
+                (),
+                Option<bool>,
+                (),
             )>(self)
             .await
             .context(RedisSnafu {
@@ -162,6 +176,10 @@ impl TrainingParticipationReportStorage for RedisConnection {
                 _,
                 checkpoint_entries,
                 _,
+                _,
+                _,
+                anonymize,
+                _,
             ) => Ok(Some(RoomState {
                 start,
                 report_state,
@@ -170,8 +188,9 @@ impl TrainingParticipationReportStorage for RedisConnection {
                 history: collect_checkpoints(checkpoint_entries.into_iter().rev()),
                 next_checkpoint,
                 known_participants,
+                anonymize: anonymize.unwrap_or_default(),
             })),
-            (None, None, None, _, _, _, _) => Ok(None),
+            (None, None, None, _, _, _, _, _, _, _, _) => Ok(None),
             _ => whatever!("inconsistent training participation report room state found on redis"),
         }
     }
@@ -207,6 +226,97 @@ impl TrainingParticipationReportStorage for RedisConnection {
         })
     }
 
+    async fn transition_training_report_state(
+        &mut self,
+        room: RoomId,
+        from: TrainingReportState,
+        to: TrainingReportState,
+    ) -> Result<bool, SignalingModuleError> {
+        redis::Script::new(TRANSITION_STATE_SCRIPT)
+            .key(TrainingReportStateKey { room })
+            .arg(from)
+            .arg(to)
+            .invoke_async(self)
+            .await
+            .context(RedisSnafu {
+                message: "failed to transition training participation report room state",
+            })
+    }
+
+    async fn set_trainee_threshold(
+        &mut self,
+        room: RoomId,
+        threshold: NonZeroU32,
+    ) -> Result<(), SignalingModuleError> {
+        self.set(TraineeThresholdKey { room }, threshold.get())
+            .await
+            .context(RedisSnafu {
+                message: "failed to set training participation report trainee threshold",
+            })
+    }
+
+    async fn get_trainee_threshold(
+        &mut self,
+        room: RoomId,
+    ) -> Result<Option<NonZeroU32>, SignalingModuleError> {
+        let threshold: Option<u32> =
+            self.get(TraineeThresholdKey { room })
+                .await
+                .context(RedisSnafu {
+                    message: "failed to get training participation report trainee threshold",
+                })?;
+        Ok(threshold.and_then(NonZeroU32::new))
+    }
+
+    async fn set_confirmation_grace_window(
+        &mut self,
+        room: RoomId,
+        grace_window: u64,
+    ) -> Result<(), SignalingModuleError> {
+        self.set(ConfirmationGraceWindowKey { room }, grace_window)
+            .await
+            .context(RedisSnafu {
+                message: "failed to set training participation report confirmation grace window",
+            })
+    }
+
+    async fn set_anonymize_report(&mut self, room: RoomId) -> Result<(), SignalingModuleError> {
+        self.set(AnonymizeReportKey { room }, true)
+            .await
+            .context(RedisSnafu {
+                message: "failed to set training participation report anonymization",
+            })
+    }
+
+    async fn reset_room_owner_hand_over(
+        &mut self,
+        room: RoomId,
+    ) -> Result<(), SignalingModuleError> {
+        self.del(RoomOwnerHandOverKey { room })
+            .await
+            .context(RedisSnafu {
+                message: "failed to reset training participation report room owner hand over",
+            })
+    }
+
+    async fn claim_room_owner_hand_over(
+        &mut self,
+        room: RoomId,
+        participant: ParticipantId,
+    ) -> Result<bool, SignalingModuleError> {
+        let response: Option<()> = self
+            .set_options(
+                RoomOwnerHandOverKey { room },
+                participant,
+                SetOptions::default().conditional_set(ExistenceCheck::NX),
+            )
+            .await
+            .context(RedisSnafu {
+                message: "failed to claim training participation report room owner hand over",
+            })?;
+        Ok(response.is_some())
+    }
+
     async fn get_initial_checkpoint_delay(
         &mut self,
         room: RoomId,
@@ -300,20 +410,29 @@ impl TrainingParticipationReportStorage for RedisConnection {
             training_report_state == Some(TrainingReportState::TrackingPresence),
             "failed to record presence confirmation because room is not in tracking presence state"
         );
-        let last_checkpoint: Vec<CheckpointEntry> = self
-            .lrange(CheckpointEntriesKey { room }, 0, 0)
+        let (entries, grace_window): (Vec<CheckpointEntry>, Option<u64>) = redis::pipe()
+            .lrange(CheckpointEntriesKey { room }, 0, -1)
+            .get(ConfirmationGraceWindowKey { room })
+            .query_async(self)
             .await
             .context(RedisSnafu {
                 message: "failed to load checkpoint entries for room",
             })?;
         ensure_whatever!(
             matches!(
-                last_checkpoint.first(),
+                entries.first(),
                 Some(CheckpointEntry::NextCheckpoint { .. })
                     | Some(CheckpointEntry::Participation { .. })
             ),
             "cannot record presence confirmation because no checkpoint has been found"
         );
+        // The entries are stored with the most recent one first
+        let checkpoint = entries.iter().find_map(|entry| match entry {
+            CheckpointEntry::NextCheckpoint { timestamp } => Some(*timestamp),
+            _ => None,
+        });
+        let late = checkpoint
+            .is_some_and(|checkpoint| is_late_confirmation(checkpoint, timestamp, grace_window));
 
         let () = self
             .lpush_exists(
@@ -321,6 +440,7 @@ impl TrainingParticipationReportStorage for RedisConnection {
                 CheckpointEntry::Participation {
                     participant,
                     timestamp,
+                    late,
                 },
             )
             .await
@@ -423,6 +543,47 @@ struct KnownParticipantsKey {
     room: RoomId,
 }
 
+#[derive(ToRedisArgs)]
+#[to_redis_args(fmt = "opentalk-signaling:room={room}:training_report:trainee_threshold")]
+struct TraineeThresholdKey {
+    room: RoomId,
+}
+
+#[derive(ToRedisArgs)]
+#[to_redis_args(fmt = "opentalk-signaling:room={room}:training_report:confirmation_grace_window")]
+struct ConfirmationGraceWindowKey {
+    room: RoomId,
+}
+
+#[derive(ToRedisArgs)]
+#[to_redis_args(fmt = "opentalk-signaling:room={room}:training_report:anonymize_report")]
+struct AnonymizeReportKey {
+    room: RoomId,
+}
+
+#[derive(ToRedisArgs)]
+#[to_redis_args(fmt = "opentalk-signaling:room={room}:training_report:room_owner_hand_over")]
+struct RoomOwnerHandOverKey {
+    room: RoomId,
+}
+
+/// Set the training report state to a new value if it has the expected value
+///
+/// ```text
+/// KEYS[1] = training report state key
+///
+/// ARGV[1] = expected state
+/// ARGV[2] = new state
+/// ```
+const TRANSITION_STATE_SCRIPT: &str = r#"
+if (redis.call("get", KEYS[1]) == ARGV[1]) then
+  redis.call("set", KEYS[1], ARGV[2])
+  return 1
+end
+
+return 0
+"#;
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToRedisArgs, FromRedisValue)]
 #[to_redis_args(serde)]
 #[from_redis_value(serde)]
@@ -435,6 +596,8 @@ enum CheckpointEntry {
     Participation {
         participant: ParticipantId,
         timestamp: Timestamp,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        late: bool,
     },
 }
 
@@ -448,14 +611,21 @@ fn collect_checkpoints(entries: impl Iterator<Item = CheckpointEntry>) -> Vec<Ch
                 checkpoints.push(Checkpoint {
                     timestamp,
                     presence: BTreeMap::new(),
+                    late: BTreeSet::new(),
                 });
             }
             CheckpointEntry::Participation {
                 participant,
                 timestamp,
+                late,
             } => {
                 if let Some(checkpoint) = checkpoints.last_mut() {
                     _ = checkpoint.presence.insert(participant, timestamp);
+                    if late {
+                        _ = checkpoint.late.insert(participant);
+                    } else {
+                        _ = checkpoint.late.remove(&participant);
+                    }
                 }
             }
         }
@@ -508,6 +678,18 @@ mod tests {
         test_common::get_set_training_report_state(&mut storage().await).await;
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn transition_training_report_state() {
+        test_common::transition_training_report_state(&mut storage().await).await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn trainee_threshold() {
+        test_common::trainee_threshold(&mut storage().await).await;
+    }
+
     #[tokio::test]
     #[serial]
     async fn get_initial_checkpoint_delay() {
@@ -531,4 +713,22 @@ mod tests {
     async fn record_presence() {
         test_common::record_presence(&mut storage().await).await;
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn record_presence_with_grace_window() {
+        test_common::record_presence_with_grace_window(&mut storage().await).await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn anonymize_report() {
+        test_common::anonymize_report(&mut storage().await).await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn room_owner_hand_over() {
+        test_common::room_owner_hand_over(&mut storage().await).await;
+    }
 }
@@ -18,4 +18,5 @@ pub(crate) struct RoomState {
     pub history: Vec<Checkpoint>,
     pub next_checkpoint: Option<Timestamp>,
     pub known_participants: BTreeSet<ParticipantId>,
+    pub anonymize: bool,
 }
@@ -2,7 +2,7 @@
 //
 // SPDX-License-Identifier: EUPL-1.2
 
-use std::collections::BTreeSet;
+use std::{collections::BTreeSet, num::NonZeroU32};
 
 use async_trait::async_trait;
 use opentalk_signaling_core::{SignalingModuleError, control::storage::ControlStorageEvent};
@@ -72,6 +72,53 @@ pub(crate) trait TrainingParticipationReportStorage: ControlStorageEvent {
         report_state: TrainingReportState,
     ) -> Result<(), SignalingModuleError>;
 
+    /// Atomically set the report state to `to` if it currently is `from`
+    ///
+    /// Returns `true` if the state has been changed.
+    async fn transition_training_report_state(
+        &mut self,
+        room: RoomId,
+        from: TrainingReportState,
+        to: TrainingReportState,
+    ) -> Result<bool, SignalingModuleError>;
+
+    async fn set_trainee_threshold(
+        &mut self,
+        room: RoomId,
+        threshold: NonZeroU32,
+    ) -> Result<(), SignalingModuleError>;
+
+    async fn get_trainee_threshold(
+        &mut self,
+        room: RoomId,
+    ) -> Result<Option<NonZeroU32>, SignalingModuleError>;
+
+    /// Set the number of seconds after a checkpoint in which a presence confirmation is on time
+    async fn set_confirmation_grace_window(
+        &mut self,
+        room: RoomId,
+        grace_window: u64,
+    ) -> Result<(), SignalingModuleError>;
+
+    /// Render the report of the current presence logging session with pseudonyms instead of
+    /// participant names
+    async fn set_anonymize_report(&mut self, room: RoomId) -> Result<(), SignalingModuleError>;
+
+    /// Forget who took over the checkpoint handling, done before a new room owner hand over
+    async fn reset_room_owner_hand_over(
+        &mut self,
+        room: RoomId,
+    ) -> Result<(), SignalingModuleError>;
+
+    /// Take over the checkpoint handling after a room owner hand over.
+    ///
+    /// Returns `false` if another participant has taken over already.
+    async fn claim_room_owner_hand_over(
+        &mut self,
+        room: RoomId,
+        participant: ParticipantId,
+    ) -> Result<bool, SignalingModuleError>;
+
     async fn get_initial_checkpoint_delay(
         &mut self,
         room: RoomId,
@@ -99,6 +146,9 @@ pub(crate) trait TrainingParticipationReportStorage: ControlStorageEvent {
         new_next_checkpoint: Timestamp,
     ) -> Result<(), SignalingModuleError>;
 
+    /// Record the presence confirmation of `participant` for the current checkpoint
+    ///
+    /// The confirmation is marked as late if it happened after the confirmation grace window.
     async fn record_presence_confirmation(
         &mut self,
         room: RoomId,
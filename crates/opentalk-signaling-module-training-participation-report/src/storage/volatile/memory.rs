@@ -2,7 +2,10 @@
 //
 // SPDX-License-Identifier: EUPL-1.2
 
-use std::collections::{BTreeMap, BTreeSet};
+use std::{
+    collections::{BTreeMap, BTreeSet, btree_map::Entry},
+    num::NonZeroU32,
+};
 
 use opentalk_signaling_core::{NotFoundSnafu, SignalingModuleError};
 use opentalk_types_common::{
@@ -14,13 +17,16 @@ use opentalk_types_signaling::ParticipantId;
 use opentalk_types_signaling_training_participation_report::state::ParticipationLoggingState;
 use snafu::{OptionExt as _, ensure_whatever};
 
-use crate::storage::{Checkpoint, RoomState, TrainingReportState};
+use crate::storage::{Checkpoint, RoomState, TrainingReportState, is_late_confirmation};
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub(crate) struct TrainingParticipationReportState {
     room_state: BTreeMap<RoomId, RoomState>,
     initialized: BTreeSet<RoomId>,
     parameter_sets: BTreeMap<RoomId, TrainingParticipationReportParameterSet>,
+    trainee_thresholds: BTreeMap<RoomId, NonZeroU32>,
+    confirmation_grace_windows: BTreeMap<RoomId, u64>,
+    room_owner_hand_overs: BTreeMap<RoomId, ParticipantId>,
 }
 
 impl TrainingParticipationReportState {
@@ -93,11 +99,15 @@ impl TrainingParticipationReportState {
                 history: vec![],
                 next_checkpoint: None,
                 known_participants,
+                anonymize: false,
             },
         );
     }
 
     pub(super) fn cleanup_room(&mut self, room: RoomId) -> Option<RoomState> {
+        _ = self.trainee_thresholds.remove(&room);
+        _ = self.confirmation_grace_windows.remove(&room);
+        _ = self.room_owner_hand_overs.remove(&room);
         self.room_state.remove(&room)
     }
 
@@ -117,6 +127,59 @@ impl TrainingParticipationReportState {
         Ok(())
     }
 
+    pub(super) fn transition_training_report_state(
+        &mut self,
+        room: RoomId,
+        from: TrainingReportState,
+        to: TrainingReportState,
+    ) -> bool {
+        match self.room_state.get_mut(&room) {
+            Some(room_state) if room_state.report_state == from => {
+                room_state.report_state = to;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    pub(super) fn set_trainee_threshold(&mut self, room: RoomId, threshold: NonZeroU32) {
+        _ = self.trainee_thresholds.insert(room, threshold);
+    }
+
+    pub(super) fn get_trainee_threshold(&self, room: RoomId) -> Option<NonZeroU32> {
+        self.trainee_thresholds.get(&room).copied()
+    }
+
+    pub(super) fn set_confirmation_grace_window(&mut self, room: RoomId, grace_window: u64) {
+        _ = self.confirmation_grace_windows.insert(room, grace_window);
+    }
+
+    pub(super) fn set_anonymize_report(
+        &mut self,
+        room: RoomId,
+    ) -> Result<(), SignalingModuleError> {
+        self.room_mut(room)?.anonymize = true;
+        Ok(())
+    }
+
+    pub(super) fn reset_room_owner_hand_over(&mut self, room: RoomId) {
+        _ = self.room_owner_hand_overs.remove(&room);
+    }
+
+    pub(super) fn claim_room_owner_hand_over(
+        &mut self,
+        room: RoomId,
+        participant: ParticipantId,
+    ) -> bool {
+        match self.room_owner_hand_overs.entry(room) {
+            Entry::Vacant(entry) => {
+                _ = entry.insert(participant);
+                true
+            }
+            Entry::Occupied(_) => false,
+        }
+    }
+
     pub(super) fn get_initial_checkpoint_delay(
         &self,
         room: RoomId,
@@ -163,6 +226,7 @@ impl TrainingParticipationReportState {
         let next_checkpoint = Checkpoint {
             timestamp: next_checkpoint_timestamp,
             presence: BTreeMap::new(),
+            late: BTreeSet::new(),
         };
         room_state.history.push(next_checkpoint);
         Ok(())
@@ -174,6 +238,7 @@ impl TrainingParticipationReportState {
         participant: ParticipantId,
         timestamp: Timestamp,
     ) -> Result<(), SignalingModuleError> {
+        let grace_window = self.confirmation_grace_windows.get(&room).copied();
         let room_state = self.room_mut(room)?;
         ensure_whatever!(
             room_state.report_state == TrainingReportState::TrackingPresence,
@@ -184,6 +249,11 @@ impl TrainingParticipationReportState {
                 format!("Cannot record presence confirmation for room {room} because it has no current checkpoint set")
             })?;
         _ = current_checkpoint.presence.insert(participant, timestamp);
+        if is_late_confirmation(current_checkpoint.timestamp, timestamp, grace_window) {
+            _ = current_checkpoint.late.insert(participant);
+        } else {
+            _ = current_checkpoint.late.remove(&participant);
+        }
 
         Ok(())
     }
@@ -4,6 +4,7 @@
 
 use std::{
     collections::BTreeSet,
+    num::NonZeroU32,
     sync::{Arc, OnceLock},
 };
 
@@ -117,6 +118,66 @@ impl TrainingParticipationReportStorage for VolatileStaticMemoryStorage {
             .set_training_report_state(room, report_state)
     }
 
+    async fn transition_training_report_state(
+        &mut self,
+        room: RoomId,
+        from: TrainingReportState,
+        to: TrainingReportState,
+    ) -> Result<bool, SignalingModuleError> {
+        Ok(state()
+            .write()
+            .transition_training_report_state(room, from, to))
+    }
+
+    async fn set_trainee_threshold(
+        &mut self,
+        room: RoomId,
+        threshold: NonZeroU32,
+    ) -> Result<(), SignalingModuleError> {
+        state().write().set_trainee_threshold(room, threshold);
+        Ok(())
+    }
+
+    async fn get_trainee_threshold(
+        &mut self,
+        room: RoomId,
+    ) -> Result<Option<NonZeroU32>, SignalingModuleError> {
+        Ok(state().read().get_trainee_threshold(room))
+    }
+
+    async fn set_confirmation_grace_window(
+        &mut self,
+        room: RoomId,
+        grace_window: u64,
+    ) -> Result<(), SignalingModuleError> {
+        state()
+            .write()
+            .set_confirmation_grace_window(room, grace_window);
+        Ok(())
+    }
+
+    async fn set_anonymize_report(&mut self, room: RoomId) -> Result<(), SignalingModuleError> {
+        state().write().set_anonymize_report(room)
+    }
+
+    async fn reset_room_owner_hand_over(
+        &mut self,
+        room: RoomId,
+    ) -> Result<(), SignalingModuleError> {
+        state().write().reset_room_owner_hand_over(room);
+        Ok(())
+    }
+
+    async fn claim_room_owner_hand_over(
+        &mut self,
+        room: RoomId,
+        participant: ParticipantId,
+    ) -> Result<bool, SignalingModuleError> {
+        Ok(state()
+            .write()
+            .claim_room_owner_hand_over(room, participant))
+    }
+
     async fn get_initial_checkpoint_delay(
         &mut self,
         room: RoomId,
@@ -214,6 +275,18 @@ mod tests {
         test_common::get_set_training_report_state(&mut storage()).await;
     }
 
+    #[tokio::test]
+    #[serial]
+    async fn transition_training_report_state() {
+        test_common::transition_training_report_state(&mut storage()).await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn trainee_threshold() {
+        test_common::trainee_threshold(&mut storage()).await;
+    }
+
     #[tokio::test]
     #[serial]
     async fn get_initial_checkpoint_delay() {
@@ -237,4 +310,22 @@ mod tests {
     async fn record_presence() {
         test_common::record_presence(&mut storage()).await;
     }
+
+    #[tokio::test]
+    #[serial]
+    async fn record_presence_with_grace_window() {
+        test_common::record_presence_with_grace_window(&mut storage()).await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn anonymize_report() {
+        test_common::anonymize_report(&mut storage()).await;
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn room_owner_hand_over() {
+        test_common::room_owner_hand_over(&mut storage()).await;
+    }
 }
@@ -2,7 +2,7 @@
 //
 // SPDX-License-Identifier: EUPL-1.2
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use chrono_tz::Tz;
 use opentalk_report_generation::{ReportDateTime, ToReportDateTime};
@@ -14,6 +14,8 @@ use crate::storage;
 pub(crate) struct Checkpoint {
     pub timestamp: ReportDateTime,
     pub presence: BTreeMap<ParticipantId, ReportDateTime>,
+    #[serde(default, skip_serializing_if = "BTreeSet::is_empty")]
+    pub late: BTreeSet<ParticipantId>,
 }
 
 impl Checkpoint {
@@ -21,6 +23,7 @@ impl Checkpoint {
         storage::Checkpoint {
             timestamp,
             presence,
+            late,
         }: &storage::Checkpoint,
         report_tz: &Tz,
     ) -> Self {
@@ -32,6 +35,7 @@ impl Checkpoint {
                     (*participant, timestamp.to_report_date_time(report_tz))
                 })
                 .collect(),
+            late: late.clone(),
         }
     }
 }
@@ -10,7 +10,7 @@ pub(crate) use report_template_parameter::ReportTemplateParameter;
 
 #[cfg(test)]
 pub(crate) mod tests {
-    use std::collections::BTreeMap;
+    use std::collections::{BTreeMap, BTreeSet};
 
     use chrono_tz::Europe::Berlin;
     use opentalk_types_common::users::DisplayName;
@@ -72,6 +72,7 @@ pub(crate) mod tests {
                                 .expect("value must be parsable as ReportDateTime"),
                         ),
                     ]),
+                    late: BTreeSet::new(),
                 },
                 Checkpoint {
                     timestamp: "2025-02-18T11:22:00"
@@ -91,6 +92,7 @@ pub(crate) mod tests {
                                 .expect("value must be parsable as ReportDateTime"),
                         ),
                     ]),
+                    late: BTreeSet::new(),
                 },
                 Checkpoint {
                     timestamp: "2025-02-18T13:19:00"
@@ -102,8 +104,11 @@ pub(crate) mod tests {
                             .parse()
                             .expect("value must be parsable as ReportDateTime"),
                     )]),
+                    late: BTreeSet::new(),
                 },
             ],
+            anonymized: false,
+            verification_url: None,
         }
     }
 
@@ -195,6 +200,7 @@ pub(crate) mod tests {
                                 .expect("value must be parsable as ReportDateTime"),
                         ),
                     ]),
+                    late: BTreeSet::new(),
                 },
                 Checkpoint {
                     timestamp: "2025-02-18T11:22:00"
@@ -206,6 +212,7 @@ pub(crate) mod tests {
                             .parse()
                             .expect("value must be parsable as ReportDateTime"),
                     )]),
+                    late: BTreeSet::new(),
                 },
                 Checkpoint {
                     timestamp: "2025-02-18T13:19:00"
@@ -217,12 +224,14 @@ pub(crate) mod tests {
                             .parse()
                             .expect("value must be parsable as ReportDateTime"),
                     )]),
+                    late: BTreeSet::new(),
                 },
                 Checkpoint {
                     timestamp: "2025-02-18T15:08:00"
                         .parse()
                         .expect("value must be parsable as ReportDateTime"),
                     presence: BTreeMap::from_iter([]),
+                    late: BTreeSet::new(),
                 },
                 Checkpoint {
                     timestamp: "2025-02-18T17:21:00"
@@ -234,6 +243,7 @@ pub(crate) mod tests {
                             .parse()
                             .expect("value must be parsable as ReportDateTime"),
                     )]),
+                    late: BTreeSet::new(),
                 },
                 Checkpoint {
                     timestamp: "2025-02-18T19:31:00"
@@ -245,6 +255,7 @@ pub(crate) mod tests {
                             .parse()
                             .expect("value must be parsable as ReportDateTime"),
                     )]),
+                    late: BTreeSet::new(),
                 },
                 Checkpoint {
                     timestamp: "2025-02-18T21:31:00"
@@ -256,6 +267,7 @@ pub(crate) mod tests {
                             .parse()
                             .expect("value must be parsable as ReportDateTime"),
                     )]),
+                    late: BTreeSet::new(),
                 },
                 Checkpoint {
                     timestamp: "2025-02-18T23:36:00"
@@ -267,6 +279,7 @@ pub(crate) mod tests {
                             .parse()
                             .expect("value must be parsable as ReportDateTime"),
                     )]),
+                    late: BTreeSet::new(),
                 },
                 Checkpoint {
                     timestamp: "2025-02-19T01:37:00"
@@ -286,6 +299,7 @@ pub(crate) mod tests {
                                 .expect("value must be parsable as ReportDateTime"),
                         ),
                     ]),
+                    late: BTreeSet::new(),
                 },
                 Checkpoint {
                     timestamp: "2025-02-19T03:27:00"
@@ -297,8 +311,11 @@ pub(crate) mod tests {
                             .parse()
                             .expect("value must be parsable as ReportDateTime"),
                     )]),
+                    late: BTreeSet::new(),
                 },
             ],
+            anonymized: false,
+            verification_url: None,
         }
     }
 
@@ -518,6 +535,7 @@ pub(crate) mod tests {
                         (yousef_id, timestamp01),
                         (zainab_id, timestamp01),
                     ]),
+                    late: BTreeSet::new(),
                 },
                 Checkpoint {
                     timestamp: timestamp02,
@@ -549,6 +567,7 @@ pub(crate) mod tests {
                         (yousef_id, timestamp02),
                         (zainab_id, timestamp02),
                     ]),
+                    late: BTreeSet::new(),
                 },
                 Checkpoint {
                     timestamp: timestamp03,
@@ -580,6 +599,7 @@ pub(crate) mod tests {
                         (yousef_id, timestamp03),
                         (zainab_id, timestamp03),
                     ]),
+                    late: BTreeSet::new(),
                 },
                 Checkpoint {
                     timestamp: timestamp04,
@@ -611,6 +631,7 @@ pub(crate) mod tests {
                         (yousef_id, timestamp04),
                         (zainab_id, timestamp04),
                     ]),
+                    late: BTreeSet::new(),
                 },
                 Checkpoint {
                     timestamp: timestamp05,
@@ -642,6 +663,7 @@ pub(crate) mod tests {
                         (yousef_id, timestamp05),
                         (zainab_id, timestamp05),
                     ]),
+                    late: BTreeSet::new(),
                 },
                 Checkpoint {
                     timestamp: timestamp06,
@@ -673,6 +695,7 @@ pub(crate) mod tests {
                         (yousef_id, timestamp06),
                         (zainab_id, timestamp06),
                     ]),
+                    late: BTreeSet::new(),
                 },
                 Checkpoint {
                     timestamp: timestamp07,
@@ -704,6 +727,7 @@ pub(crate) mod tests {
                         (yousef_id, timestamp07),
                         (zainab_id, timestamp07),
                     ]),
+                    late: BTreeSet::new(),
                 },
                 Checkpoint {
                     timestamp: timestamp08,
@@ -735,6 +759,7 @@ pub(crate) mod tests {
                         (yousef_id, timestamp08),
                         (zainab_id, timestamp08),
                     ]),
+                    late: BTreeSet::new(),
                 },
                 Checkpoint {
                     timestamp: timestamp09,
@@ -766,6 +791,7 @@ pub(crate) mod tests {
                         (yousef_id, timestamp09),
                         (zainab_id, timestamp09),
                     ]),
+                    late: BTreeSet::new(),
                 },
                 Checkpoint {
                     timestamp: timestamp10,
@@ -797,8 +823,11 @@ pub(crate) mod tests {
                         (yousef_id, timestamp10),
                         (zainab_id, timestamp10),
                     ]),
+                    late: BTreeSet::new(),
                 },
             ],
+            anonymized: false,
+            verification_url: None,
         }
     }
 
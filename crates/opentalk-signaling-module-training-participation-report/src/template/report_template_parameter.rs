@@ -25,6 +25,11 @@ pub(crate) struct ReportTemplateParameter {
     pub report_timezone: Tz,
     pub participants: BTreeMap<ParticipantId, Option<DisplayName>>,
     pub checkpoints: Vec<Checkpoint>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub anonymized: bool,
+    /// The url encoded into the verification QR code, if enabled for the tenant
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verification_url: Option<String>,
 }
 
 impl ReportTemplateParameter {
@@ -43,7 +48,7 @@ impl ReportTemplateParameter {
                 Checkpoint::from_storage_checkpoint(storage_checkpoint, report_tz)
             })
             .collect();
-        Self {
+        let parameter = Self {
             title,
             description,
             start: room_state.start.to_report_date_time(report_tz),
@@ -51,6 +56,34 @@ impl ReportTemplateParameter {
             report_timezone: *report_tz,
             participants,
             checkpoints,
+            anonymized: false,
+            verification_url: None,
+        };
+        if room_state.anonymize {
+            parameter.anonymized()
+        } else {
+            parameter
+        }
+    }
+
+    /// Replace the participant names with pseudonyms.
+    ///
+    /// The pseudonyms are numbered in the order of the randomly generated participant ids, so
+    /// they are stable within a report, but do not reveal anything about the participant names.
+    pub(crate) fn anonymized(self) -> Self {
+        let participants = self
+            .participants
+            .into_keys()
+            .enumerate()
+            .map(|(index, participant)| {
+                let pseudonym = format!("Participant {}", index + 1);
+                (participant, Some(DisplayName::from_str_lossy(&pseudonym)))
+            })
+            .collect();
+        Self {
+            participants,
+            anonymized: true,
+            ..self
         }
     }
 }
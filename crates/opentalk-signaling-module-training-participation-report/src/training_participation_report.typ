@@ -76,8 +76,15 @@
     columns.push(1fr)
   }
 
+  // Pseudonyms are already numbered, sorting them by name would mix up "Participant 10" and "Participant 2"
+  let participants = if data.at("anonymized", default: false) {
+    data.participants.pairs()
+  } else {
+    data.participants.pairs().sorted(key: k => k.at(1))
+  }
+
   let rows = ()
-  for (number, (id, name)) in data.participants.pairs().sorted(key: k => k.at(1)).enumerate(start: 1) {
+  for (number, (id, name)) in participants.enumerate(start: 1) {
     let row = (
       align(end)[#number],
       if name == none [
@@ -87,7 +94,12 @@
       ],
       checkpoints.map(checkpoint =>
         if id in checkpoint.presence [
-          #align(center)[#parse_datetime(checkpoint.presence.at(id)).display(time_format)]
+          #let time = parse_datetime(checkpoint.presence.at(id)).display(time_format)
+          #if id in checkpoint.at("late", default: ()) [
+            #align(center)[#time (late)]
+          ] else [
+            #align(center)[#time]
+          ]
         ] else [
           #align(center)[—]
         ]
@@ -111,3 +123,14 @@
     ..data_table.rows.flatten()
   )
 }
+
+#if "verification_url" in data [
+  == Verification
+
+  #grid(
+    columns: (auto, 1fr),
+    gutter: 1em,
+    image("verification_qr_code.svg", width: 3cm),
+    [The archived report can be retrieved and verified at #link(data.verification_url).],
+  )
+]